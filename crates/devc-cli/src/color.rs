@@ -0,0 +1,61 @@
+//! Color control for CLI output (`--no-color` and the `NO_COLOR` convention)
+
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+static DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turn off colored output for the rest of the process (`--no-color`)
+pub fn disable() {
+    DISABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether output may be colored: not disabled by `--no-color`, and
+/// `NO_COLOR` unset or empty as <https://no-color.org> specifies
+pub fn enabled() -> bool {
+    !DISABLED.load(Ordering::Relaxed)
+        && std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+}
+
+/// Theme for dialoguer prompts, plain when color is off
+pub fn theme() -> Box<dyn dialoguer::theme::Theme> {
+    if enabled() {
+        Box::new(dialoguer::theme::ColorfulTheme::default())
+    } else {
+        Box::new(dialoguer::theme::SimpleTheme)
+    }
+}
+
+/// Pass a streamed output line through, minus ANSI escape sequences when
+/// color is off (build tools and containers color their own output)
+pub fn plain(line: &str) -> Cow<'_, str> {
+    if enabled() {
+        Cow::Borrowed(line)
+    } else {
+        strip_ansi(line)
+    }
+}
+
+/// Remove ANSI CSI and OSC escape sequences from a line
+pub fn strip_ansi(line: &str) -> Cow<'_, str> {
+    static ANSI: OnceLock<regex::Regex> = OnceLock::new();
+    let ansi = ANSI.get_or_init(|| {
+        regex::Regex::new(r"\x1b\[[0-?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)")
+            .expect("valid ANSI regex")
+    });
+    ansi.replace_all(line, "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(strip_ansi("plain"), "plain");
+        assert_eq!(strip_ansi("\x1b[1;32mok\x1b[0m done"), "ok done");
+        assert_eq!(strip_ansi("\r\x1b[2Kstep"), "\rstep");
+        assert_eq!(strip_ansi("\x1b]8;;http://x\x07link\x1b]8;;\x07"), "link");
+    }
+}
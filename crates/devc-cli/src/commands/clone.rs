@@ -0,0 +1,377 @@
+//! `devc clone`: clone a repository and bring up its dev container
+
+use anyhow::{anyhow, bail, Context, Result};
+use devc_config::{ConfigError, DevContainerConfig, GlobalConfig};
+use devc_core::ContainerManager;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+/// Image used to clone into a volume; its entrypoint is `git`
+const GIT_IMAGE: &str = "alpine/git";
+
+/// Image for the devcontainer.json written when a repository has none
+const DEFAULT_IMAGE: &str = "mcr.microsoft.com/devcontainers/base:ubuntu";
+
+/// Clone `url` to a host directory (or into a named volume), then init and up
+pub async fn clone(
+    manager: &ContainerManager,
+    url: &str,
+    dir: Option<PathBuf>,
+    volume: Option<String>,
+) -> Result<()> {
+    let repo = repo_name_from_url(url)
+        .ok_or_else(|| anyhow!("Cannot derive a repository name from '{}'", url))?;
+
+    let workspace = match volume {
+        Some(volume) => clone_into_volume(manager, url, &volume, &repo).await?,
+        None => {
+            let target = match dir {
+                Some(dir) => dir,
+                None => std::env::current_dir()?.join(&repo),
+            };
+            clone_to_host(url, &target).await?;
+            ensure_devcontainer(&target, &repo)?;
+            target.canonicalize()?
+        }
+    };
+
+    let state = manager.init(&workspace).await?;
+    println!("Initialized container: {}", state.name);
+    super::up(manager, Some(state.id), None, None).await
+}
+
+async fn clone_to_host(url: &str, target: &Path) -> Result<()> {
+    if target.exists() {
+        bail!("{} already exists", target.display());
+    }
+
+    println!("Cloning {} into {}...", url, target.display());
+    let status = Command::new("git")
+        .arg("clone")
+        .arg(url)
+        .arg(target)
+        .status()
+        .await
+        .context("Failed to run git")?;
+    if !status.success() {
+        bail!("git clone failed ({})", status);
+    }
+    Ok(())
+}
+
+/// Clone into a named volume and stage its dev container config on the host
+///
+/// The sources stay in the volume; only `.devcontainer` (or `.devcontainer.json`)
+/// is copied to a host directory under the devc data dir, with a `workspaceMount`
+/// pointing the container at the volume.
+async fn clone_into_volume(
+    manager: &ContainerManager,
+    url: &str,
+    volume: &str,
+    repo: &str,
+) -> Result<PathBuf> {
+    if !is_valid_volume_name(volume) {
+        bail!(
+            "Invalid volume name '{}': use letters, digits, '_', '.' and '-', starting with a letter or digit",
+            volume
+        );
+    }
+    let provider = manager
+        .provider()
+        .ok_or_else(|| anyhow!("No container provider available"))?;
+    let (program, prefix) = provider.runtime_args();
+    let runtime = |args: &[&str]| {
+        let mut cmd = Command::new(&program);
+        cmd.args(&prefix).args(args);
+        cmd
+    };
+
+    let workspace = GlobalConfig::data_dir()?.join("volumes").join(volume);
+    if workspace.exists() {
+        bail!(
+            "A clone into volume '{}' already exists at {}",
+            volume,
+            workspace.display()
+        );
+    }
+
+    run(runtime(&["volume", "create", volume]), "volume create").await?;
+
+    println!("Cloning {} into volume '{}'...", url, volume);
+    let mount = format!("{}:/workspace", volume);
+    let status = runtime(&[
+        "run",
+        "--rm",
+        "-v",
+        &mount,
+        GIT_IMAGE,
+        "clone",
+        url,
+        "/workspace",
+    ])
+    .status()
+    .await
+    .context("Failed to run the clone container");
+    if !status.as_ref().is_ok_and(|s| s.success()) {
+        // Leave nothing behind that would make a retry fail on a non-empty volume
+        let _ = runtime(&["volume", "rm", volume]).output().await;
+        match status {
+            Ok(status) => bail!("git clone in container failed ({})", status),
+            Err(e) => return Err(e),
+        }
+    }
+
+    // Copy the config out through a stopped helper container; `cp` writes the
+    // files as the invoking user, unlike a bind mount from a rootful runtime
+    let helper = format!("devc-clone-{}", volume);
+    run(
+        runtime(&["create", "--name", &helper, "-v", &mount, GIT_IMAGE]),
+        "create",
+    )
+    .await?;
+    std::fs::create_dir_all(&workspace)?;
+    for config in [".devcontainer", ".devcontainer.json"] {
+        let src = format!("{}:/workspace/{}", helper, config);
+        let dest = workspace.join(config);
+        let copied = runtime(&["cp", &src, &dest.to_string_lossy()])
+            .output()
+            .await
+            .map(|out| out.status.success())
+            .unwrap_or(false);
+        if copied {
+            break;
+        }
+    }
+    let _ = runtime(&["rm", "-f", &helper]).output().await;
+
+    ensure_devcontainer(&workspace, repo)?;
+    let (config, config_path) = DevContainerConfig::load_from_dir(&workspace)?;
+    if let Some(context) = context_outside_staged_config(&config, &config_path) {
+        eprintln!(
+            "Warning: build.context \"{}\" is outside .devcontainer, but only the config was \
+             copied out of the volume; the build will not see the repository's other files",
+            context
+        );
+    }
+    if config.workspace_mount.is_some() {
+        println!(
+            "Note: {} already sets workspaceMount; leaving it unchanged",
+            config_path.display()
+        );
+    } else {
+        let raw = std::fs::read_to_string(&config_path)?;
+        let mount = format!(
+            "source={},target=${{containerWorkspaceFolder}},type=volume",
+            volume
+        );
+        std::fs::write(&config_path, with_workspace_mount(&raw, &mount)?)?;
+    }
+
+    Ok(workspace)
+}
+
+/// Whether `name` is a volume name the runtime accepts: `[a-zA-Z0-9][a-zA-Z0-9_.-]*`.
+/// It also names a directory under the data dir, so nothing else may get through.
+fn is_valid_volume_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphanumeric())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+/// The config's `build.context` when it points outside the staged
+/// `.devcontainer` folder, where a volume clone has none of the sources
+fn context_outside_staged_config<'a>(
+    config: &'a DevContainerConfig,
+    config_path: &Path,
+) -> Option<&'a str> {
+    let context = config.build.as_ref()?.context.as_deref()?;
+    let config_dir = config_path.parent()?;
+    let mut resolved = PathBuf::new();
+    for component in config_dir.join(context).components() {
+        match component {
+            std::path::Component::ParentDir => {
+                resolved.pop();
+            }
+            std::path::Component::CurDir => {}
+            other => resolved.push(other),
+        }
+    }
+    let staged = config_dir.file_name() == Some(std::ffi::OsStr::new(".devcontainer"));
+    (!staged || !resolved.starts_with(config_dir)).then_some(context)
+}
+
+async fn run(mut cmd: Command, what: &str) -> Result<()> {
+    let output = cmd
+        .output()
+        .await
+        .with_context(|| format!("Failed to run {}", what))?;
+    if !output.status.success() {
+        bail!(
+            "{} failed: {}",
+            what,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Make sure `workspace` has a devcontainer.json, offering to write a default
+fn ensure_devcontainer(workspace: &Path, name: &str) -> Result<()> {
+    match DevContainerConfig::load_from_dir(workspace) {
+        Ok(_) => return Ok(()),
+        Err(ConfigError::NotFound(_)) => {}
+        Err(e) => return Err(e.into()),
+    }
+
+    let create = std::io::IsTerminal::is_terminal(&std::io::stdin())
+        && dialoguer::Confirm::with_theme(&*crate::color::theme())
+            .with_prompt(format!(
+                "No devcontainer.json in {}. Create a default one?",
+                workspace.display()
+            ))
+            .default(true)
+            .interact()?;
+    if !create {
+        bail!(
+            "No devcontainer.json found in {}.\n\
+             Create .devcontainer/devcontainer.json, then run 'devc up' there.",
+            workspace.display()
+        );
+    }
+
+    let path = workspace.join(".devcontainer/devcontainer.json");
+    std::fs::create_dir_all(workspace.join(".devcontainer"))?;
+    std::fs::write(&path, default_devcontainer_json(name))?;
+    println!("Created {}", path.display());
+    Ok(())
+}
+
+fn default_devcontainer_json(name: &str) -> String {
+    let config = serde_json::json!({ "name": name, "image": DEFAULT_IMAGE });
+    format!("{:#}\n", config)
+}
+
+/// Insert a `workspaceMount` entry at the top of a devcontainer.json
+///
+/// Edits the text rather than re-serializing so comments and formatting survive.
+fn with_workspace_mount(raw: &str, mount: &str) -> Result<String> {
+    let brace = raw
+        .find('{')
+        .ok_or_else(|| anyhow!("devcontainer.json is not a JSON object"))?;
+    let rest = &raw[brace + 1..];
+    let separator = if rest.trim_start().starts_with('}') {
+        ""
+    } else {
+        ","
+    };
+    let entry = format!(
+        "\n  \"workspaceMount\": {}{}",
+        serde_json::json!(mount),
+        separator
+    );
+    Ok(format!("{}{}{}", &raw[..=brace], entry, rest))
+}
+
+/// Repository name from a git URL: "https://host/org/app.git" -> "app"
+fn repo_name_from_url(url: &str) -> Option<String> {
+    let trimmed = url.trim_end_matches('/');
+    let last = trimmed.rsplit(['/', ':']).next()?;
+    let name = last.strip_suffix(".git").unwrap_or(last);
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_name_from_url() {
+        assert_eq!(
+            repo_name_from_url("https://github.com/org/app.git").as_deref(),
+            Some("app")
+        );
+        assert_eq!(
+            repo_name_from_url("https://github.com/org/app/").as_deref(),
+            Some("app")
+        );
+        assert_eq!(
+            repo_name_from_url("git@github.com:org/app.git").as_deref(),
+            Some("app")
+        );
+        assert_eq!(repo_name_from_url("git@host:app").as_deref(), Some("app"));
+        assert_eq!(repo_name_from_url("git@host:"), None);
+    }
+
+    #[test]
+    fn test_is_valid_volume_name() {
+        assert!(is_valid_volume_name("app-src"));
+        assert!(is_valid_volume_name("App_1.data"));
+        assert!(!is_valid_volume_name(""));
+        assert!(!is_valid_volume_name("../escape"));
+        assert!(!is_valid_volume_name("/abs"));
+        assert!(!is_valid_volume_name(".hidden"));
+        assert!(!is_valid_volume_name("a/b"));
+    }
+
+    #[test]
+    fn test_context_outside_staged_config() {
+        let config = |context: &str| -> DevContainerConfig {
+            serde_json::from_value(serde_json::json!({
+                "build": { "dockerfile": "Dockerfile", "context": context }
+            }))
+            .unwrap()
+        };
+        let nested = Path::new("/data/volumes/app/.devcontainer/devcontainer.json");
+        assert_eq!(context_outside_staged_config(&config("."), nested), None);
+        assert_eq!(
+            context_outside_staged_config(&config("./docker"), nested),
+            None
+        );
+        assert_eq!(
+            context_outside_staged_config(&config(".."), nested),
+            Some("..")
+        );
+        let root = Path::new("/data/volumes/app/.devcontainer.json");
+        assert_eq!(context_outside_staged_config(&config("."), root), Some("."));
+        let image: DevContainerConfig =
+            serde_json::from_value(serde_json::json!({ "image": "ubuntu" })).unwrap();
+        assert_eq!(context_outside_staged_config(&image, nested), None);
+    }
+
+    #[test]
+    fn test_with_workspace_mount_keeps_comments() {
+        let raw = "// project config\n{\n  // base image\n  \"image\": \"ubuntu\"\n}\n";
+        let edited = with_workspace_mount(raw, "source=vol,target=/w,type=volume").unwrap();
+        assert!(edited.contains("// base image"));
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".devcontainer.json"), &edited).unwrap();
+        let (config, _) = DevContainerConfig::load_from_dir(dir.path()).unwrap();
+        assert_eq!(
+            config.workspace_mount.as_deref(),
+            Some("source=vol,target=/w,type=volume")
+        );
+        assert_eq!(config.image.as_deref(), Some("ubuntu"));
+    }
+
+    #[test]
+    fn test_with_workspace_mount_empty_object() {
+        let edited = with_workspace_mount("{}", "source=vol,target=/w,type=volume").unwrap();
+        let config: DevContainerConfig = serde_json::from_str(&edited).unwrap();
+        assert!(config.workspace_mount.is_some());
+    }
+
+    #[test]
+    fn test_default_devcontainer_parses() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".devcontainer")).unwrap();
+        std::fs::write(
+            dir.path().join(".devcontainer/devcontainer.json"),
+            default_devcontainer_json("app"),
+        )
+        .unwrap();
+        let (config, _) = DevContainerConfig::load_from_dir(dir.path()).unwrap();
+        assert_eq!(config.name.as_deref(), Some("app"));
+        assert_eq!(config.image.as_deref(), Some(DEFAULT_IMAGE));
+    }
+}
@@ -0,0 +1,362 @@
+//! `devc cp`: copy files between the host and a container
+
+use super::find_container;
+use anyhow::{anyhow, bail, Context, Result};
+use devc_core::ContainerManager;
+use devc_provider::{ContainerId, ContainerProvider, ExecConfig};
+use std::path::Path;
+
+/// One side of a `devc cp` invocation
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Endpoint {
+    /// A path on the host, kept verbatim so a trailing `/.` keeps its
+    /// "copy the contents" meaning
+    Host(String),
+    /// `-`: a tar archive on stdin (source) or stdout (destination)
+    Stdio,
+    /// `CONTAINER:PATH`
+    Container { container: String, path: String },
+}
+
+/// Parse a `devc cp` argument the way `docker cp` does: `-` is stdin/stdout,
+/// anything starting with `/` or `.` is a host path (so host paths containing
+/// a colon can be written as `./a:b`), and `NAME:PATH` names a container.
+fn parse_endpoint(arg: &str) -> Result<Endpoint> {
+    if arg == "-" {
+        return Ok(Endpoint::Stdio);
+    }
+    if arg.starts_with('/') || arg.starts_with('.') {
+        return Ok(Endpoint::Host(arg.to_string()));
+    }
+    match arg.split_once(':') {
+        Some(("", _)) => bail!("Missing container name in '{}'", arg),
+        Some((_, "")) => bail!("Missing container path in '{}'", arg),
+        Some((container, path)) => Ok(Endpoint::Container {
+            container: container.to_string(),
+            path: path.to_string(),
+        }),
+        None => Ok(Endpoint::Host(arg.to_string())),
+    }
+}
+
+/// Check the pair of endpoints and split it into (container, container path,
+/// host side, copying into the container)
+fn plan(src: Endpoint, dest: Endpoint, archive: bool) -> Result<(String, String, Endpoint, bool)> {
+    let (container, path, host, into) = match (src, dest) {
+        (Endpoint::Container { .. }, Endpoint::Container { .. }) => bail!(
+            "Copying between containers is not supported; \
+             pipe two `devc cp --archive` commands instead"
+        ),
+        (Endpoint::Container { container, path }, host) => (container, path, host, false),
+        (host, Endpoint::Container { container, path }) => (container, path, host, true),
+        _ => bail!("One of SRC and DEST must be CONTAINER:PATH"),
+    };
+
+    match (&host, archive) {
+        (Endpoint::Stdio, false) => bail!("'-' streams a tar archive; pass --archive"),
+        (Endpoint::Host(_), true) => {
+            bail!("--archive streams through stdin/stdout; use '-' as the host side")
+        }
+        _ => {}
+    }
+
+    Ok((container, path, host, into))
+}
+
+/// Arguments for `<runtime> cp` streaming a tar through stdin/stdout, after
+/// the runtime prefix
+fn archive_cp_args(container_id: &str, path: &str, into: bool) -> Vec<String> {
+    let container = format!("{}:{}", container_id, path);
+    let (src, dest) = if into {
+        ("-".to_string(), container)
+    } else {
+        (container, "-".to_string())
+    };
+    vec!["cp".to_string(), src, dest]
+}
+
+/// Container path to hand the provider when copying `src` to `dest`.
+///
+/// The provider always copies a directory's contents. `docker cp dir DEST`
+/// instead puts the directory itself inside DEST when DEST is an existing
+/// directory, so point the provider at `DEST/dir` in that case. `dir/.`, a
+/// missing DEST and plain files go to DEST as given. A stopped container
+/// can't run the probe, so DEST is then taken as not being a directory.
+async fn copy_into_target(
+    provider: &dyn ContainerProvider,
+    id: &ContainerId,
+    src: &Path,
+    dest: &str,
+) -> Result<String> {
+    let name = match src.file_name() {
+        Some(name) if src.is_dir() && !src.to_string_lossy().ends_with("/.") => name,
+        _ => return Ok(dest.to_string()),
+    };
+    let probe = ExecConfig {
+        cmd: vec!["test".to_string(), "-d".to_string(), dest.to_string()],
+        ..Default::default()
+    };
+    match provider.exec(id, &probe).await {
+        Ok(result) if result.exit_code == 0 => {}
+        Ok(_) => return Ok(dest.to_string()),
+        Err(e) => {
+            tracing::debug!("Cannot check whether {} is a directory: {}", dest, e);
+            return Ok(dest.to_string());
+        }
+    }
+    Ok(format!(
+        "{}/{}",
+        dest.trim_end_matches('/'),
+        name.to_string_lossy()
+    ))
+}
+
+/// Copy a file or directory between the host and a container.
+///
+/// Follows `docker cp`: directories are copied recursively with their modes
+/// kept, `dir` copies the directory itself and `dir/.` only its contents. With
+/// `archive`, the host side is `-` and a tar stream is read from stdin or
+/// written to stdout, so two containers can be bridged with a pipe.
+pub async fn copy(manager: &ContainerManager, src: &str, dest: &str, archive: bool) -> Result<()> {
+    let (container, path, host, into) = plan(parse_endpoint(src)?, parse_endpoint(dest)?, archive)?;
+
+    let state = find_container(manager, &container).await?;
+    let container_id = state
+        .container_id
+        .as_ref()
+        .ok_or_else(|| anyhow!("Container '{}' has not been created yet", state.name))?;
+
+    if let Endpoint::Host(host_path) = &host {
+        let provider = manager
+            .provider_for_type(state.provider)
+            .ok_or_else(|| anyhow!("Provider {} is not available", state.provider))?;
+        let id = ContainerId::new(container_id);
+        let host_path = Path::new(host_path);
+        if into {
+            let target = copy_into_target(provider, &id, host_path, &path).await?;
+            provider.copy_into(&id, host_path, &target).await?;
+        } else {
+            provider.copy_from(&id, &path, host_path).await?;
+        }
+        return Ok(());
+    }
+
+    // The tar stream goes straight between our stdio and the runtime
+    let (program, mut args) = manager
+        .runtime_args_for(&state)
+        .map_err(|e| anyhow!("{}", e))?;
+    args.extend(archive_cp_args(container_id, &path, into));
+
+    let status = std::process::Command::new(&program)
+        .args(&args)
+        .status()
+        .context("Failed to spawn command")?;
+
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use devc_config::GlobalConfig;
+    use devc_core::test_support::{MockCall, MockProvider};
+    use devc_core::{ContainerState, DevcContainerStatus, StateStore};
+    use devc_provider::ProviderType;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    fn container(name: &str, path: &str) -> Endpoint {
+        Endpoint::Container {
+            container: name.to_string(),
+            path: path.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_endpoint() {
+        assert_eq!(parse_endpoint("-").unwrap(), Endpoint::Stdio);
+        assert_eq!(
+            parse_endpoint("web:/app").unwrap(),
+            container("web", "/app")
+        );
+        assert_eq!(
+            parse_endpoint("./a:b").unwrap(),
+            Endpoint::Host("./a:b".to_string())
+        );
+        assert_eq!(
+            parse_endpoint("/tmp/x:y").unwrap(),
+            Endpoint::Host("/tmp/x:y".to_string())
+        );
+        assert_eq!(
+            parse_endpoint("notes.txt").unwrap(),
+            Endpoint::Host("notes.txt".to_string())
+        );
+        assert!(parse_endpoint(":/app").is_err());
+        assert!(parse_endpoint("web:").is_err());
+    }
+
+    #[test]
+    fn test_plan_requires_one_container_side() {
+        let host = || Endpoint::Host("./src".to_string());
+        assert!(plan(host(), host(), false).is_err());
+        assert!(plan(container("a", "/x"), container("b", "/y"), true).is_err());
+
+        let (name, path, _, into) = plan(host(), container("web", "/app"), false).unwrap();
+        assert_eq!((name.as_str(), path.as_str(), into), ("web", "/app", true));
+        let (_, _, _, into) = plan(container("web", "/app"), host(), false).unwrap();
+        assert!(!into);
+    }
+
+    #[test]
+    fn test_plan_archive_uses_stdio() {
+        assert!(plan(Endpoint::Stdio, container("web", "/app"), false).is_err());
+        assert!(plan(
+            Endpoint::Host("x.tar".into()),
+            container("web", "/app"),
+            true
+        )
+        .is_err());
+        assert!(plan(Endpoint::Stdio, container("web", "/app"), true).is_ok());
+        assert!(plan(container("web", "/app"), Endpoint::Stdio, true).is_ok());
+    }
+
+    #[test]
+    fn test_archive_cp_args() {
+        assert_eq!(
+            archive_cp_args("abc", "/data", false),
+            ["cp", "abc:/data", "-"]
+        );
+        assert_eq!(
+            archive_cp_args("abc", "/data", true),
+            ["cp", "-", "abc:/data"]
+        );
+    }
+
+    fn mock_manager(provider: MockProvider) -> ContainerManager {
+        let mut state = StateStore::new();
+        let mut web = ContainerState::new(
+            "web".to_string(),
+            ProviderType::Docker,
+            PathBuf::from("/tmp/web/.devcontainer/devcontainer.json"),
+            PathBuf::from("/tmp/web"),
+        );
+        web.status = DevcContainerStatus::Running;
+        web.container_id = Some("web_id".to_string());
+        state.add(web);
+        ContainerManager::new_for_testing(Box::new(provider), GlobalConfig::default(), state)
+    }
+
+    fn copies(calls: &Arc<Mutex<Vec<MockCall>>>) -> Vec<MockCall> {
+        calls
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|c| matches!(c, MockCall::CopyInto { .. } | MockCall::CopyFrom { .. }))
+            .cloned()
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_copy_goes_through_provider() {
+        let host = tempfile::tempdir().unwrap();
+        let file = host.path().join("notes.txt");
+        std::fs::write(&file, "hi").unwrap();
+
+        let provider = MockProvider::new(ProviderType::Docker);
+        let calls = provider.calls.clone();
+        let manager = mock_manager(provider);
+
+        copy(
+            &manager,
+            file.to_str().unwrap(),
+            "web:/app/notes.txt",
+            false,
+        )
+        .await
+        .unwrap();
+        copy(
+            &manager,
+            "web:/app/out",
+            host.path().to_str().unwrap(),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            copies(&calls),
+            vec![
+                MockCall::CopyInto {
+                    id: "web_id".to_string(),
+                    dest: "/app/notes.txt".to_string(),
+                },
+                MockCall::CopyFrom {
+                    id: "web_id".to_string(),
+                    src: "/app/out".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_copy_dir_follows_docker_cp_semantics() {
+        let host = tempfile::tempdir().unwrap();
+        let dir = host.path().join("src");
+        std::fs::create_dir(&dir).unwrap();
+        let dir = dir.to_str().unwrap().to_string();
+
+        let provider = MockProvider::new(ProviderType::Docker);
+        let calls = provider.calls.clone();
+        // `test -d`: /app exists, then /new does not
+        *provider.exec_responses.lock().unwrap() = vec![(0, String::new()), (1, String::new())];
+        let manager = mock_manager(provider);
+
+        // `dir` into an existing directory lands inside it
+        copy(&manager, &dir, "web:/app", false).await.unwrap();
+        // `dir` to a missing path becomes that path
+        copy(&manager, &dir, "web:/new", false).await.unwrap();
+        // `dir/.` copies only the contents, without probing
+        copy(&manager, &format!("{}/.", dir), "web:/app", false)
+            .await
+            .unwrap();
+
+        let dests: Vec<String> = copies(&calls)
+            .into_iter()
+            .map(|c| match c {
+                MockCall::CopyInto { dest, .. } => dest,
+                other => panic!("unexpected {:?}", other),
+            })
+            .collect();
+        assert_eq!(dests, ["/app/src", "/new", "/app"]);
+    }
+
+    /// Copying a directory into a stopped container works like `docker cp`,
+    /// even though the destination can't be probed
+    #[tokio::test]
+    async fn test_copy_dir_into_stopped_container() {
+        let host = tempfile::tempdir().unwrap();
+        let dir = host.path().join("src");
+        std::fs::create_dir(&dir).unwrap();
+
+        let provider = MockProvider::new(ProviderType::Docker);
+        let calls = provider.calls.clone();
+        *provider.exec_error.lock().unwrap() = Some(devc_provider::ProviderError::ExecError(
+            "container is not running".to_string(),
+        ));
+        let manager = mock_manager(provider);
+
+        copy(&manager, dir.to_str().unwrap(), "web:/app", false)
+            .await
+            .unwrap();
+        assert_eq!(
+            copies(&calls),
+            vec![MockCall::CopyInto {
+                id: "web_id".to_string(),
+                dest: "/app".to_string(),
+            }]
+        );
+    }
+}
@@ -1,12 +1,52 @@
-//! Lifecycle commands: exec, shell, up, down, start, stop, build, rebuild
+//! Lifecycle commands: exec, shell, attach, up, down, start, stop, build, rebuild
 
 use anyhow::{anyhow, bail, Context, Result};
-use devc_config::GlobalConfig;
-use devc_core::{Container, ContainerManager, ContainerState, DevcContainerStatus};
+use devc_config::ResizeMode;
+use devc_core::{
+    host_key_alias, Container, ContainerManager, ContainerState, DevcContainerStatus, SshManager,
+};
 use tokio::sync::mpsc;
 
 use super::{find_container, find_container_in_cwd};
 
+/// Check that a running container already has the given mounts. The runtime
+/// cannot add mounts to an existing container, so a missing one is an error
+/// pointing at recreating the container instead.
+async fn require_mounts(
+    manager: &ContainerManager,
+    state: &ContainerState,
+    mounts: &[devc_provider::MountConfig],
+) -> Result<()> {
+    let provider = manager
+        .provider_for_type(state.provider)
+        .ok_or_else(|| anyhow!("Provider {} is not available", state.provider))?;
+    let cid = state
+        .container_id
+        .as_deref()
+        .ok_or_else(|| anyhow!("Container '{}' has no container ID", state.name))?;
+    let details = provider
+        .inspect(&devc_provider::ContainerId::new(cid))
+        .await?;
+
+    for mount in mounts {
+        let present = details.mounts.iter().any(|m| {
+            m.destination == mount.target
+                && (mount.mount_type != devc_provider::MountType::Bind || m.source == mount.source)
+        });
+        if !present {
+            bail!(
+                "'{}' is not mounted at {} and mounts cannot be added to a running container; \
+                 run 'devc down {}' and 'devc up {} --mount ...' to recreate it with the mount",
+                state.name,
+                mount.target,
+                state.name,
+                state.name
+            );
+        }
+    }
+    Ok(())
+}
+
 /// Run `f` with a fresh output channel whose lines are printed to stdout.
 /// Drops the sender and awaits the reader task before returning, so the caller
 /// sees `f`'s return value only after all streamed lines have been printed.
@@ -18,7 +58,7 @@ where
     let (tx, mut rx) = mpsc::unbounded_channel::<String>();
     let reader = tokio::spawn(async move {
         while let Some(line) = rx.recv().await {
-            println!("{}", line);
+            println!("{}", crate::color::plain(&line));
         }
     });
     let result = f(tx).await;
@@ -32,13 +72,89 @@ pub async fn exec(
     container: &str,
     cmd: Vec<String>,
     root: bool,
+    workdir: Option<&str>,
+    mounts: &[devc_provider::MountConfig],
 ) -> Result<()> {
+    let ExecPrepared {
+        program,
+        prefix,
+        container_id,
+        exec_config,
+    } = exec_prepare(manager, container, cmd, root, workdir, mounts).await?;
+
+    // Build runtime args for direct spawn with inherited stdio
+    let mut args: Vec<String> = prefix;
+    args.push("exec".to_string());
+
+    // TTY and stdin flags
+    if exec_config.tty {
+        args.push("-it".to_string());
+    } else {
+        args.push("-i".to_string());
+    }
+
+    if let Some(ref workdir) = exec_config.working_dir {
+        args.push("--workdir".to_string());
+        args.push(workdir.clone());
+    }
+
+    if let Some(ref user) = exec_config.user {
+        args.push("--user".to_string());
+        args.push(user.clone());
+    }
+
+    for (key, val) in &exec_config.env {
+        args.push("-e".to_string());
+        args.push(format!("{}={}", key, val));
+    }
+
+    args.push(container_id);
+    args.extend(exec_config.cmd);
+
+    let status = std::process::Command::new(&program)
+        .args(&args)
+        .status()
+        .context("Failed to spawn command")?;
+
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
+
+    Ok(())
+}
+
+/// Resolved runtime and exec config for `devc exec`
+#[doc(hidden)]
+pub struct ExecPrepared {
+    pub program: String,
+    pub prefix: Vec<String>,
+    pub container_id: String,
+    pub exec_config: devc_provider::ExecConfig,
+}
+
+/// Everything `exec` does before spawning the runtime: check the container,
+/// prepare the exec context, and build the exec config with `--root` and
+/// `--workdir` applied. Public only so integration tests in `tests/` can call it.
+#[doc(hidden)]
+pub async fn exec_prepare(
+    manager: &ContainerManager,
+    container: &str,
+    cmd: Vec<String>,
+    root: bool,
+    workdir: Option<&str>,
+    mounts: &[devc_provider::MountConfig],
+) -> Result<ExecPrepared> {
     let state = find_container(manager, container).await?;
 
+    ensure_not_paused(&state)?;
     if state.status != DevcContainerStatus::Running {
         bail!("Container '{}' is not running", state.name);
     }
 
+    if !mounts.is_empty() {
+        require_mounts(manager, &state, mounts).await?;
+    }
+
     if cmd.is_empty() {
         bail!("No command specified");
     }
@@ -83,6 +199,11 @@ pub async fn exec(
         exec_config.user = Some("root".to_string());
     }
 
+    // --workdir is a path inside the container, so it is passed through as-is
+    if let Some(workdir) = workdir {
+        exec_config.working_dir = Some(workdir.to_string());
+    }
+
     // Inject GH_TOKEN if resolved from host
     if let Some(ref token) = exec_env.gh_token {
         exec_config
@@ -90,48 +211,27 @@ pub async fn exec(
             .insert("GH_TOKEN".to_string(), token.clone());
     }
 
-    // Build runtime args for direct spawn with inherited stdio
     let (program, prefix) = manager
         .runtime_args_for(&state)
         .map_err(|e| anyhow!("{}", e))?;
 
-    let mut args: Vec<String> = prefix;
-    args.push("exec".to_string());
-
-    // TTY and stdin flags
-    if exec_config.tty {
-        args.push("-it".to_string());
-    } else {
-        args.push("-i".to_string());
-    }
-
-    if let Some(ref workdir) = exec_config.working_dir {
-        args.push("--workdir".to_string());
-        args.push(workdir.clone());
-    }
-
-    if let Some(ref user) = exec_config.user {
-        args.push("--user".to_string());
-        args.push(user.clone());
-    }
-
-    for (key, val) in &exec_config.env {
-        args.push("-e".to_string());
-        args.push(format!("{}={}", key, val));
-    }
-
-    args.push(exec_env.container_id.clone());
-    args.extend(exec_config.cmd);
-
-    let status = std::process::Command::new(&program)
-        .args(&args)
-        .status()
-        .context("Failed to spawn command")?;
+    Ok(ExecPrepared {
+        program,
+        prefix,
+        container_id: exec_env.container_id,
+        exec_config,
+    })
+}
 
-    if !status.success() {
-        std::process::exit(status.code().unwrap_or(1));
+/// Refuse to exec into a paused container, whose processes can't run
+fn ensure_not_paused(state: &ContainerState) -> Result<()> {
+    if state.status == DevcContainerStatus::Paused {
+        bail!(
+            "Container '{}' is paused (run 'devc unpause {}' to resume)",
+            state.name,
+            state.name
+        );
     }
-
     Ok(())
 }
 
@@ -152,6 +252,7 @@ pub struct ShellPrepared {
 #[doc(hidden)]
 pub async fn shell_prepare(manager: &ContainerManager, container: &str) -> Result<ShellPrepared> {
     let state = find_container(manager, container).await?;
+    ensure_not_paused(&state)?;
 
     if state.status != DevcContainerStatus::Running {
         if !(state.status == DevcContainerStatus::Stopped
@@ -205,13 +306,110 @@ pub async fn shell_prepare(manager: &ContainerManager, container: &str) -> Resul
     })
 }
 
-/// Open a shell in a container, optionally running a command
-pub async fn shell(manager: &ContainerManager, container: &str, cmd: Vec<String>) -> Result<()> {
+/// Resolve the runtime container ID of a compose service other than the primary one.
+///
+/// Returns `None` when `service` is the project's primary (dev) service, which is
+/// reached through the normal shell path. Fails if the container is not a compose
+/// project, the service does not exist, or it is not running.
+#[doc(hidden)]
+pub async fn resolve_service_container(
+    manager: &ContainerManager,
+    container: &str,
+    service: &str,
+) -> Result<Option<String>> {
+    let state = find_container(manager, container).await?;
+    if state.compose_project.is_none() {
+        bail!(
+            "Container '{}' is not a compose project; --service does not apply",
+            state.name
+        );
+    }
+    if state.compose_service.as_deref() == Some(service) {
+        return Ok(None);
+    }
+
+    let services = manager
+        .compose_services(&state.id)
+        .await
+        .map_err(|e| anyhow!("{}", e))?;
+    let svc = services
+        .iter()
+        .find(|s| s.service_name == service)
+        .ok_or_else(|| {
+            let names: Vec<&str> = services.iter().map(|s| s.service_name.as_str()).collect();
+            anyhow!(
+                "Service '{}' not found in compose project (available: {})",
+                service,
+                names.join(", ")
+            )
+        })?;
+    if svc.status != devc_provider::ContainerStatus::Running {
+        bail!(
+            "Service '{}' is not running (status: {})",
+            service,
+            svc.status
+        );
+    }
+    Ok(Some(svc.container_id.0.clone()))
+}
+
+/// Open a shell in a container, optionally running a script in it
+///
+/// The script runs in a login shell with a TTY, so profile files are sourced
+/// first, unlike `exec`; a failing script exits devc with its status.
+pub async fn shell(
+    manager: &ContainerManager,
+    container: &str,
+    service: Option<&str>,
+    script: Option<&str>,
+) -> Result<()> {
+    if let Some(service) = service {
+        if let Some(service_id) = resolve_service_container(manager, container, service).await? {
+            let state = find_container(manager, container).await?;
+            let (program, prefix) = manager
+                .runtime_args_for(&state)
+                .map_err(|e| anyhow!("{}", e))?;
+            return exec_service_shell(&program, &prefix, &service_id, script);
+        }
+    }
+
     let prepared = shell_prepare(manager, container).await?;
     ssh_to_container(
+        manager.global_config().defaults.effective_resize_mode(),
+        &prepared.state,
+        &prepared.exec_env.container_id,
+        script,
+        &prepared.program,
+        &prepared.prefix,
+        &prepared.extra_env,
+    )
+    .await
+}
+
+/// Like `shell_prepare`, but refuses to start the container: attaching only
+/// makes sense for a container that is already running.
+#[doc(hidden)]
+pub async fn attach_prepare(manager: &ContainerManager, container: &str) -> Result<ShellPrepared> {
+    let state = find_container(manager, container).await?;
+    ensure_not_paused(&state)?;
+    if state.status != DevcContainerStatus::Running {
+        bail!(
+            "Container '{}' is not running (status: {}); start it with 'devc start' first",
+            state.name,
+            state.status
+        );
+    }
+    shell_prepare(manager, container).await
+}
+
+/// Attach to a running container: run postAttachCommand, then open an interactive shell
+pub async fn attach(manager: &ContainerManager, container: &str) -> Result<()> {
+    let prepared = attach_prepare(manager, container).await?;
+    ssh_to_container(
+        manager.global_config().defaults.effective_resize_mode(),
         &prepared.state,
         &prepared.exec_env.container_id,
-        &cmd,
+        None,
         &prepared.program,
         &prepared.prefix,
         &prepared.extra_env,
@@ -262,28 +460,27 @@ fn build_shell_extra_env(
     env
 }
 
-/// Connect to container, preferring SSH over stdio when available
+/// Connect to container over SSH when that is the resize mode and the
+/// container has dropbear, otherwise with plain exec
 async fn ssh_to_container(
+    resize_mode: ResizeMode,
     state: &ContainerState,
     container_id: &str,
-    cmd: &[String],
+    script: Option<&str>,
     program: &str,
     prefix: &[String],
     extra_env: &std::collections::HashMap<String, String>,
 ) -> Result<()> {
-    // Check if SSH is available for this container
-    let ssh_available = state
-        .metadata
-        .get("ssh_available")
-        .map(|v| v == "true")
-        .unwrap_or(false);
+    let use_ssh = resize_mode == ResizeMode::Ssh && ssh_available(state);
 
     // Resolve effective user and workspace_folder from metadata or devcontainer.json
     let parsed = Container::from_config(&state.config_path).ok();
     let effective_user = state.metadata.get("remote_user").cloned().or_else(|| {
-        parsed
-            .as_ref()
-            .and_then(|c| c.devcontainer.effective_user().map(|s| s.to_string()))
+        parsed.as_ref().and_then(|c| {
+            c.devcontainer
+                .effective_remote_user()
+                .map(|s| s.to_string())
+        })
     });
     let workspace_folder = state.metadata.get("workspace_folder").cloned().or_else(|| {
         parsed
@@ -291,11 +488,11 @@ async fn ssh_to_container(
             .and_then(|c| c.devcontainer.workspace_folder.clone())
     });
 
-    if ssh_available {
+    if use_ssh {
         match ssh_via_dropbear(
             state,
             container_id,
-            cmd,
+            script,
             program,
             prefix,
             workspace_folder.as_deref(),
@@ -313,7 +510,7 @@ async fn ssh_to_container(
                     "Warning: SSH connection failed ({}), falling back to exec",
                     e
                 );
-                if cmd.is_empty() {
+                if script.is_none() {
                     eprintln!("Note: Terminal resize will not work with exec fallback");
                 }
             }
@@ -325,7 +522,7 @@ async fn ssh_to_container(
         program,
         prefix,
         container_id,
-        cmd,
+        script,
         effective_user.as_deref(),
         workspace_folder.as_deref(),
         extra_env,
@@ -333,16 +530,120 @@ async fn ssh_to_container(
     .await
 }
 
+/// How a shell's stdin is attached. A terminal gets a PTY; anything else
+/// (`echo data | devc shell app cat`) is passed through as a plain pipe, so the
+/// input arrives unmodified and EOF ends the command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ShellStdin {
+    Tty,
+    Piped,
+}
+
+impl ShellStdin {
+    fn detect() -> Self {
+        if std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+            Self::Tty
+        } else {
+            Self::Piped
+        }
+    }
+
+    /// `docker/podman exec` flags
+    fn exec_flag(self) -> &'static str {
+        match self {
+            Self::Tty => "-it",
+            Self::Piped => "-i",
+        }
+    }
+
+    /// `ssh` PTY flag
+    fn ssh_flag(self) -> &'static str {
+        match self {
+            Self::Tty => "-t",
+            Self::Piped => "-T",
+        }
+    }
+}
+
+/// Whether dropbear was set up in this container
+fn ssh_available(state: &ContainerState) -> bool {
+    state
+        .metadata
+        .get("ssh_available")
+        .map(|v| v == "true")
+        .unwrap_or(false)
+}
+
 /// Connect via SSH over stdio using dropbear in inetd mode
 async fn ssh_via_dropbear(
     state: &ContainerState,
     container_id: &str,
-    cmd: &[String],
+    script: Option<&str>,
     program: &str,
     prefix: &[String],
     working_dir: Option<&str>,
     extra_env: &std::collections::HashMap<String, String>,
 ) -> Result<std::process::ExitStatus> {
+    // Get terminal environment from host to pass through
+    let term = std::env::var("TERM").unwrap_or_else(|_| "xterm-256color".to_string());
+    let colorterm = std::env::var("COLORTERM").unwrap_or_else(|_| "truecolor".to_string());
+
+    let mut args = dropbear_ssh_args(state, container_id, program, prefix)?;
+    let destination = args.pop().expect("destination is always last");
+    args.extend([
+        // Pass through terminal and locale settings + extra env (feature remoteEnv, GH_TOKEN)
+        "-o".to_string(),
+        {
+            let mut set_env = format!(
+                "SetEnv=TERM={} COLORTERM={} LANG=C.UTF-8 LC_ALL=C.UTF-8",
+                term, colorterm
+            );
+            for (key, val) in extra_env {
+                set_env.push_str(&format!(" {}={}", key, val));
+            }
+            set_env
+        },
+        // Force PTY allocation, unless input is piped
+        ShellStdin::detect().ssh_flag().to_string(),
+        destination,
+    ]);
+
+    if let Some(command) = ssh_remote_command(script, working_dir) {
+        args.push("--".to_string());
+        args.push(command);
+    }
+
+    let status = std::process::Command::new("ssh")
+        .args(&args)
+        .status()
+        .context("Failed to spawn SSH")?;
+
+    Ok(status)
+}
+
+/// The remote command for an SSH session: a login shell in the workspace
+/// folder, running `script` if given. sshd runs a bare command with
+/// `$SHELL -c`, which sources no profile, so the login shell is explicit.
+fn ssh_remote_command(script: Option<&str>, working_dir: Option<&str>) -> Option<String> {
+    let shell = match script {
+        Some(script) => format!("exec $SHELL -lc {}", shell_words::quote(script)),
+        None if working_dir.is_some() => "exec $SHELL -l".to_string(),
+        None => return None,
+    };
+    Some(match working_dir {
+        Some(wd) => format!("cd {} && {}", shell_words::quote(wd), shell),
+        None => shell,
+    })
+}
+
+/// Common `ssh` arguments for reaching a container's dropbear, ending with
+/// the `user@localhost` destination
+fn dropbear_ssh_args(
+    state: &ContainerState,
+    container_id: &str,
+    program: &str,
+    prefix: &[String],
+) -> Result<Vec<String>> {
     let user = state
         .metadata
         .get("remote_user")
@@ -350,9 +651,8 @@ async fn ssh_via_dropbear(
         .unwrap_or("root");
 
     // Get the SSH key path
-    let key_path = GlobalConfig::data_dir()
-        .map(|d| d.join("ssh/id_ed25519"))
-        .context("Failed to get data directory")?;
+    let ssh_manager = SshManager::new().context("Failed to get data directory")?;
+    let key_path = ssh_manager.key_path();
 
     if !key_path.exists() {
         bail!("SSH key not found at {:?}", key_path);
@@ -376,79 +676,84 @@ async fn ssh_via_dropbear(
         .to_str()
         .ok_or_else(|| anyhow!("SSH key path contains invalid UTF-8"))?;
 
-    // Get terminal environment from host to pass through
-    let term = std::env::var("TERM").unwrap_or_else(|_| "xterm-256color".to_string());
-    let colorterm = std::env::var("COLORTERM").unwrap_or_else(|_| "truecolor".to_string());
+    // Host keys are kept across rebuilds, so check them against devc's own
+    // known_hosts under a per-container alias
+    let known_hosts = ssh_manager.known_hosts_path();
+    let known_hosts_str = known_hosts
+        .to_str()
+        .ok_or_else(|| anyhow!("known_hosts path contains invalid UTF-8"))?;
 
-    let mut args = vec![
+    Ok(vec![
         "-o".to_string(),
         format!("ProxyCommand={}", proxy_cmd),
         "-o".to_string(),
-        "StrictHostKeyChecking=no".to_string(),
+        format!("HostKeyAlias={}", host_key_alias(&state.id)),
         "-o".to_string(),
-        "UserKnownHostsFile=/dev/null".to_string(),
+        "StrictHostKeyChecking=accept-new".to_string(),
         "-o".to_string(),
-        "LogLevel=ERROR".to_string(),
-        // Pass through terminal and locale settings + extra env (feature remoteEnv, GH_TOKEN)
+        format!("UserKnownHostsFile={}", known_hosts_str),
         "-o".to_string(),
-        {
-            let mut set_env = format!(
-                "SetEnv=TERM={} COLORTERM={} LANG=C.UTF-8 LC_ALL=C.UTF-8",
-                term, colorterm
-            );
-            for (key, val) in extra_env {
-                set_env.push_str(&format!(" {}={}", key, val));
-            }
-            set_env
-        },
+        "LogLevel=ERROR".to_string(),
         "-i".to_string(),
         key_path_str.to_string(),
-        "-t".to_string(), // Force PTY allocation
         format!("{}@localhost", user),
-    ];
+    ])
+}
 
-    // Append command if provided — SSH runs it in a login shell and exits
-    if !cmd.is_empty() {
-        args.push("--".to_string());
-        args.extend(cmd.iter().cloned());
-    } else if let Some(wd) = working_dir {
-        // For interactive shells, cd to the workspace folder
-        args.push("--".to_string());
-        args.push(format!("cd {} && exec $SHELL -l", shell_words::join([wd])));
-    }
+/// Shell over plain exec -it; window changes only reach it when the runtime
+/// CLI forwards SIGWINCH to the exec session
+async fn exec_shell_fallback(
+    program: &str,
+    prefix: &[String],
+    container_id: &str,
+    script: Option<&str>,
+    user: Option<&str>,
+    working_dir: Option<&str>,
+    extra_env: &std::collections::HashMap<String, String>,
+) -> Result<()> {
+    let mut args: Vec<String> = prefix.to_vec();
+    args.extend(exec_shell_args(
+        ShellStdin::detect(),
+        container_id,
+        script,
+        user,
+        working_dir,
+        extra_env,
+    ));
 
-    let status = std::process::Command::new("ssh")
+    let status = std::process::Command::new(program)
         .args(&args)
         .status()
-        .context("Failed to spawn SSH")?;
+        .context("Failed to spawn shell")?;
 
-    Ok(status)
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
+
+    Ok(())
 }
 
-/// Fallback to exec -it (doesn't support terminal resize)
-async fn exec_shell_fallback(
-    program: &str,
-    prefix: &[String],
+/// `exec ...` arguments for [`exec_shell_fallback`], after the runtime prefix
+fn exec_shell_args(
+    stdin: ShellStdin,
     container_id: &str,
-    cmd: &[String],
+    script: Option<&str>,
     user: Option<&str>,
     working_dir: Option<&str>,
     extra_env: &std::collections::HashMap<String, String>,
-) -> Result<()> {
-    // Build the shell command: interactive shell, or `bash -lc "cmd"` for commands
-    let shell_args: Vec<String> = if cmd.is_empty() {
-        vec!["/bin/bash".to_string()]
-    } else {
+) -> Vec<String> {
+    // Build the shell command: interactive shell, or `bash -lc "script"` for commands
+    let shell_args: Vec<String> = match script {
+        None => vec!["/bin/bash".to_string()],
         // Use bash -lc to get login shell environment (sources profile, sets PATH)
-        vec![
+        Some(script) => vec![
             "/bin/bash".to_string(),
             "-lc".to_string(),
-            shell_words::join(cmd),
-        ]
+            script.to_string(),
+        ],
     };
 
-    let mut args: Vec<String> = prefix.to_vec();
-    args.extend(["exec".to_string(), "-it".to_string()]);
+    let mut args = vec!["exec".to_string(), stdin.exec_flag().to_string()];
     if let Some(u) = user {
         args.push("--user".to_string());
         args.push(u.to_string());
@@ -463,6 +768,30 @@ async fn exec_shell_fallback(
     }
     args.push(container_id.to_string());
     args.extend(shell_args);
+    args
+}
+
+/// Open a shell in a companion compose service container.
+///
+/// Companion services are arbitrary images, so this uses the image's default
+/// user and prefers bash but falls back to sh.
+fn exec_service_shell(
+    program: &str,
+    prefix: &[String],
+    container_id: &str,
+    script: Option<&str>,
+) -> Result<()> {
+    let script = script
+        .unwrap_or("if [ -x /bin/bash ]; then exec /bin/bash; else exec /bin/sh; fi")
+        .to_string();
+
+    let mut args: Vec<String> = prefix.to_vec();
+    args.extend([
+        "exec".to_string(),
+        ShellStdin::detect().exec_flag().to_string(),
+    ]);
+    args.push(container_id.to_string());
+    args.extend(["/bin/sh".to_string(), "-c".to_string(), script]);
 
     let status = std::process::Command::new(program)
         .args(&args)
@@ -527,6 +856,33 @@ pub async fn resize(
         rows, cols
     );
 
+    // Go through the same mechanism as shells, falling back to exec
+    let resize_mode = manager.global_config().defaults.effective_resize_mode();
+    if resize_mode == ResizeMode::Ssh && ssh_available(state) {
+        match dropbear_ssh_args(state, container_id, &program, &prefix) {
+            Ok(mut args) => {
+                let destination = args.pop().expect("destination is always last");
+                args.extend(["-T".to_string(), destination, "--".to_string()]);
+                args.push(resize_cmd.clone());
+                match std::process::Command::new("ssh").args(&args).status() {
+                    Ok(status) if status.success() => {
+                        println!("Resized '{}' to {}x{}", state.name, cols, rows);
+                        return Ok(());
+                    }
+                    Ok(_) => eprintln!("Warning: resize over SSH failed, falling back to exec"),
+                    Err(e) => eprintln!(
+                        "Warning: SSH connection failed ({}), falling back to exec",
+                        e
+                    ),
+                }
+            }
+            Err(e) => eprintln!(
+                "Warning: SSH connection failed ({}), falling back to exec",
+                e
+            ),
+        }
+    }
+
     let mut args: Vec<String> = prefix;
     args.extend([
         "exec".to_string(),
@@ -576,7 +932,10 @@ pub async fn build(
         println!("Building '{}'...", state.name);
     }
 
-    let image_id = manager.build_with_options(&state.id, no_cache).await?;
+    let image_id = super::pull_progress::with_pull_progress(|tx| async move {
+        manager.build_with_progress(&state.id, no_cache, tx).await
+    })
+    .await?;
     println!("Built image: {}", image_id);
 
     Ok(())
@@ -615,7 +974,7 @@ pub async fn start(manager: &ContainerManager, container: &str) -> Result<()> {
 pub async fn stop(manager: &ContainerManager, container: &str) -> Result<()> {
     let state = find_container(manager, container).await?;
 
-    if state.status != DevcContainerStatus::Running {
+    if !state.can_stop() {
         println!("Container '{}' is not running", state.name);
         return Ok(());
     }
@@ -627,8 +986,189 @@ pub async fn stop(manager: &ContainerManager, container: &str) -> Result<()> {
     Ok(())
 }
 
+/// Pause a running container
+pub async fn pause(manager: &ContainerManager, container: &str) -> Result<()> {
+    let state = find_container(manager, container).await?;
+
+    if state.status != DevcContainerStatus::Running {
+        println!("Container '{}' is not running", state.name);
+        return Ok(());
+    }
+
+    manager.pause(&state.id).await?;
+    println!("Paused '{}'", state.name);
+    println!("\nRun 'devc unpause {}' to resume it.", state.name);
+
+    Ok(())
+}
+
+/// Resume a paused container
+pub async fn unpause(manager: &ContainerManager, container: &str) -> Result<()> {
+    let state = find_container(manager, container).await?;
+
+    if state.status != DevcContainerStatus::Paused {
+        println!("Container '{}' is not paused", state.name);
+        return Ok(());
+    }
+
+    manager.unpause(&state.id).await?;
+    println!("Resumed '{}'", state.name);
+
+    Ok(())
+}
+
+/// Commit a container's filesystem to an image
+pub async fn freeze(manager: &ContainerManager, container: &str) -> Result<()> {
+    let state = find_container(manager, container).await?;
+
+    println!("Freezing '{}'...", state.name);
+    let tag = manager.freeze(&state.id).await?;
+    println!("Frozen '{}' as {}", state.name, tag);
+    println!(
+        "Note: the image captures the container's writable layer only, not its volumes or bind mounts."
+    );
+    println!(
+        "\nRun 'devc thaw <name> {}' to create a container from it.",
+        tag
+    );
+
+    Ok(())
+}
+
+/// Create a new container from a frozen image
+pub async fn thaw(manager: &ContainerManager, name: &str, image: &str) -> Result<()> {
+    println!("Thawing {} as '{}'...", image, name);
+    let state = manager.thaw(name, image).await?;
+    println!("Created '{}'", state.name);
+    println!("\nRun 'devc up {}' to start it.", state.name);
+
+    Ok(())
+}
+
+/// Stop every running container
+pub async fn stop_all(manager: &ContainerManager, skip_confirm: bool) -> Result<()> {
+    let targets: Vec<_> = manager
+        .list()
+        .await?
+        .into_iter()
+        .filter(|c| c.status == DevcContainerStatus::Running)
+        .collect();
+    if targets.is_empty() {
+        println!("No running containers");
+        return Ok(());
+    }
+    if !confirm_all("Stop", &targets, skip_confirm)? {
+        return Ok(());
+    }
+
+    let results = futures::future::join_all(targets.iter().map(|c| manager.stop(&c.id))).await;
+    report_all("stop", "Stopped", &targets, results)
+}
+
+/// Bring down every running or created container. Adopted containers are
+/// only untracked, as with `devc down <name>`.
+pub async fn down_all(manager: &ContainerManager, skip_confirm: bool) -> Result<()> {
+    let targets: Vec<_> = manager
+        .list()
+        .await?
+        .into_iter()
+        .filter(|c| {
+            matches!(
+                c.status,
+                DevcContainerStatus::Running | DevcContainerStatus::Created
+            )
+        })
+        .collect();
+    if targets.is_empty() {
+        println!("No running or created containers");
+        return Ok(());
+    }
+    if !confirm_all("Bring down", &targets, skip_confirm)? {
+        return Ok(());
+    }
+
+    let results = futures::future::join_all(targets.iter().map(|c| manager.down(&c.id))).await;
+    report_all("bring down", "Brought down", &targets, results)
+}
+
+/// List the containers an `--all` command will touch and ask to continue.
+/// Without a terminal to ask on, `-y` is required.
+fn confirm_all(verb: &str, targets: &[ContainerState], skip_confirm: bool) -> Result<bool> {
+    if skip_confirm {
+        return Ok(true);
+    }
+    if !std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+        bail!(
+            "{} {} containers? Pass -y to confirm when not running interactively.",
+            verb,
+            targets.len()
+        );
+    }
+
+    println!("{} {} containers?", verb, targets.len());
+    for c in targets {
+        println!("  {}", c.name);
+    }
+    print!("Continue? [y/N] ");
+    std::io::Write::flush(&mut std::io::stdout())?;
+
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    let input = input.trim().to_lowercase();
+    if input != "y" && input != "yes" {
+        println!("Cancelled.");
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// Print one line per container and fail if any of them failed
+fn report_all(
+    verb: &str,
+    done: &str,
+    targets: &[ContainerState],
+    results: Vec<devc_core::Result<()>>,
+) -> Result<()> {
+    let mut failed = 0;
+    for (c, result) in targets.iter().zip(results) {
+        match result {
+            Ok(()) => println!("{} '{}'", done, c.name),
+            Err(e) => {
+                failed += 1;
+                eprintln!("Failed to {} '{}': {}", verb, c.name, e);
+            }
+        }
+    }
+    if failed > 0 {
+        bail!(
+            "{} of {} containers failed to {}",
+            failed,
+            targets.len(),
+            verb
+        );
+    }
+    Ok(())
+}
+
+/// `devc up --rebuild` options, handed on to [`rebuild`]
+#[derive(Debug, Clone, Copy)]
+pub struct UpRebuild {
+    /// Build without using the cache
+    pub no_cache: bool,
+    /// Skip the confirmation prompt
+    pub yes: bool,
+}
+
 /// Build, create, and start a container
-pub async fn up(manager: &ContainerManager, container: Option<String>) -> Result<()> {
+///
+/// With `rebuild`, an existing container is torn down and rebuilt through
+/// [`rebuild`] instead, confirming first unless `yes` is set.
+pub async fn up(
+    manager: &ContainerManager,
+    container: Option<String>,
+    detach: Option<&[String]>,
+    rebuild: Option<UpRebuild>,
+) -> Result<()> {
     let state = match container {
         Some(name) => find_container(manager, &name).await?,
         None => {
@@ -643,8 +1183,29 @@ pub async fn up(manager: &ContainerManager, container: Option<String>) -> Result
         }
     };
 
+    if let Some(UpRebuild { no_cache, yes }) = rebuild {
+        // Only ask when someone can answer: piped or CI stdin would read EOF
+        // and cancel, leaving `up --rebuild` a silent no-op
+        let yes = yes || !std::io::IsTerminal::is_terminal(&std::io::stdin());
+        return self::rebuild(manager, &state.id, no_cache, yes, false).await;
+    }
+
     println!("Starting '{}'...", state.name);
 
+    if let Some(global_args) = detach {
+        let id = state.id.clone();
+        with_stdout_stream(|tx| async move { manager.up_until_running(&id, Some(&tx)).await })
+            .await?;
+        let log_path = spawn_deferred_up(&state, global_args)?;
+        println!("Container '{}' is running", state.name);
+        println!(
+            "Lifecycle commands continue in the background, logging to {}",
+            log_path.display()
+        );
+        println!("\nConnect with: devc shell {}", state.name);
+        return Ok(());
+    }
+
     with_stdout_stream(|tx| async move {
         manager
             .up_with_progress(&state.id, None, Some(&tx), None)
@@ -658,6 +1219,42 @@ pub async fn up(manager: &ContainerManager, container: Option<String>) -> Result
     Ok(())
 }
 
+/// Log file for the background `up` started by `devc up --detach`
+fn deferred_up_log_path(state: &ContainerState) -> Result<std::path::PathBuf> {
+    Ok(devc_config::GlobalConfig::data_dir()?
+        .join("logs")
+        .join(format!("{}-up.log", state.name)))
+}
+
+/// Finish `up --detach` in a separate devc process. Re-running `up` on the now
+/// running container skips the build, create and start steps and only runs
+/// the deferred lifecycle phases. The process gets its own process group so
+/// closing the terminal doesn't take it down, and nothing waits on it.
+fn spawn_deferred_up(state: &ContainerState, global_args: &[String]) -> Result<std::path::PathBuf> {
+    let log_path = deferred_up_log_path(state)?;
+    if let Some(parent) = log_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let log = std::fs::File::create(&log_path)
+        .with_context(|| format!("Failed to create {}", log_path.display()))?;
+
+    let mut cmd = std::process::Command::new(std::env::current_exe()?);
+    cmd.args(global_args)
+        .arg("--no-color")
+        .arg("up")
+        .arg(&state.id)
+        .stdin(std::process::Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log);
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
+    cmd.spawn()
+        .context("Failed to start the background lifecycle process")?;
+
+    Ok(log_path)
+}
+
 /// Stop and remove a container (but keep state so it can be recreated with `up`)
 pub async fn down(manager: &ContainerManager, container: &str) -> Result<()> {
     let state = find_container(manager, container).await?;
@@ -676,15 +1273,30 @@ pub async fn rebuild(
     container: &str,
     no_cache: bool,
     skip_confirm: bool,
+    attach_shell: bool,
 ) -> Result<()> {
     let state = find_container(manager, container).await?;
 
     // Check for provider change
     let current_provider = manager
-        .provider_type()
+        .rebuild_provider_for(&state)
         .ok_or_else(|| anyhow::anyhow!("Not connected to a container provider"))?;
     let provider_changed = state.provider != current_provider;
 
+    // The old container is torn down before anything else, so migrating needs
+    // both providers reachable; fail before prompting rather than mid-way
+    if provider_changed
+        && state.container_id.is_some()
+        && manager.provider_for_type(state.provider).is_none()
+    {
+        bail!(
+            "Cannot migrate '{}' to {}: its current provider {} is not available to remove the old container",
+            state.name,
+            current_provider,
+            state.provider
+        );
+    }
+
     // Show confirmation unless --yes
     if !skip_confirm {
         println!("Rebuild '{}'?", state.name);
@@ -715,5 +1327,71 @@ pub async fn rebuild(
     manager.rebuild(&state.id, no_cache).await?;
     println!("Rebuilt '{}' successfully", state.name);
 
+    if attach_shell {
+        attach(manager, &state.id).await?;
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exec_shell_args_tty() {
+        let args = exec_shell_args(
+            ShellStdin::Tty,
+            "cid",
+            None,
+            Some("dev"),
+            None,
+            &Default::default(),
+        );
+        assert_eq!(args, ["exec", "-it", "--user", "dev", "cid", "/bin/bash"]);
+    }
+
+    #[test]
+    fn test_exec_shell_args_piped_stdin_skips_tty() {
+        let args = exec_shell_args(
+            ShellStdin::Piped,
+            "cid",
+            Some("cat"),
+            None,
+            Some("/workspaces/app"),
+            &Default::default(),
+        );
+        assert_eq!(
+            args,
+            [
+                "exec",
+                "-i",
+                "--workdir",
+                "/workspaces/app",
+                "cid",
+                "/bin/bash",
+                "-lc",
+                "cat"
+            ]
+        );
+        assert_eq!(ShellStdin::Piped.ssh_flag(), "-T");
+        assert_eq!(ShellStdin::Tty.ssh_flag(), "-t");
+    }
+
+    #[test]
+    fn test_ssh_remote_command_runs_script_in_login_shell() {
+        assert_eq!(
+            ssh_remote_command(Some("echo $HOME; ls"), Some("/workspaces/my app")).as_deref(),
+            Some("cd '/workspaces/my app' && exec $SHELL -lc 'echo $HOME; ls'")
+        );
+        assert_eq!(
+            ssh_remote_command(Some("make"), None).as_deref(),
+            Some("exec $SHELL -lc make")
+        );
+        assert_eq!(
+            ssh_remote_command(None, Some("/workspaces/app")).as_deref(),
+            Some("cd /workspaces/app && exec $SHELL -l")
+        );
+        assert_eq!(ssh_remote_command(None, None), None);
+    }
+}
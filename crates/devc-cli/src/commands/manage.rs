@@ -1,13 +1,23 @@
-//! Management commands: init, remove, adopt, list, config, creds, agents
+//! Management commands: init, remove, adopt, list, du, logs, config, creds, agents, version
 
 use anyhow::{anyhow, bail, Context, Result};
-use devc_config::GlobalConfig;
-use devc_core::{display_name_map, ContainerManager, DevcContainerStatus};
-
-use super::{exec_check, find_container, find_container_in_cwd};
-
-/// Remove a container
-pub async fn remove(manager: &ContainerManager, container: &str, force: bool) -> Result<()> {
+use devc_config::{DevContainerConfig, GlobalConfig};
+use devc_core::agents::{AgentContainerPresence, AgentKind, AgentSyncResult, AgentSyncSelection};
+use devc_core::{display_name_map, ContainerManager, ContainerState, DevcContainerStatus};
+use devc_provider::{
+    format_bytes, format_uptime, ContainerProvider, ContainerStatus, ProviderType,
+};
+use std::path::Path;
+
+use super::{exec_check, find_container, find_container_in_cwd, write_template};
+
+/// Remove a container, and with `remove_image` its images too
+pub async fn remove(
+    manager: &ContainerManager,
+    container: &str,
+    force: bool,
+    remove_image: bool,
+) -> Result<()> {
     let state = find_container(manager, container).await?;
 
     if !force && !state.can_remove() {
@@ -19,35 +29,130 @@ pub async fn remove(manager: &ContainerManager, container: &str, force: bool) ->
     }
 
     println!("Removing '{}'...", state.name);
-    manager.remove(&state.id, force).await?;
+    manager.remove(&state.id, force, remove_image).await?;
     println!("Removed '{}'", state.name);
 
     Ok(())
 }
 
+/// Stop tracking a container, leaving any runtime container in place
+pub async fn forget(manager: &ContainerManager, container: &str) -> Result<()> {
+    let state = find_container(manager, container).await?;
+
+    manager.forget(&state.id).await?;
+    println!("Forgot '{}'", state.name);
+    if state.container_id.is_some() {
+        println!(
+            "The runtime container was left in place; remove it with your container CLI if needed."
+        );
+    }
+
+    Ok(())
+}
+
+/// Pin a container to a provider
+pub async fn pin_provider(
+    manager: &ContainerManager,
+    container: &str,
+    provider: ProviderType,
+) -> Result<()> {
+    let state = find_container(manager, container).await?;
+
+    manager.pin_provider(&state.id, provider).await?;
+    println!("Pinned '{}' to {}", state.name, provider);
+    if state.provider != provider && state.image_id.is_some() {
+        println!(
+            "Its {} image was dropped; 'devc up' builds it with {}.",
+            state.provider, provider
+        );
+    }
+    if manager.provider_for_type(provider).is_none() {
+        eprintln!("Warning: {} is not available right now", provider);
+    }
+
+    Ok(())
+}
+
+/// A `--label` filter: `key=value` matches exactly, a bare `key` matches presence
+#[derive(Debug, PartialEq)]
+struct LabelFilter {
+    key: String,
+    value: Option<String>,
+}
+
+fn parse_label_filters(labels: &[String]) -> Result<Vec<LabelFilter>> {
+    labels
+        .iter()
+        .map(|raw| {
+            let (key, value) = match raw.split_once('=') {
+                Some((k, v)) => (k, Some(v.to_string())),
+                None => (raw.as_str(), None),
+            };
+            if key.is_empty() {
+                bail!("Invalid label filter '{}': expected KEY=VALUE or KEY", raw);
+            }
+            Ok(LabelFilter {
+                key: key.to_string(),
+                value,
+            })
+        })
+        .collect()
+}
+
+fn matches_label_filters(
+    labels: &std::collections::HashMap<String, String>,
+    filters: &[LabelFilter],
+) -> bool {
+    filters
+        .iter()
+        .all(|f| match (labels.get(&f.key), &f.value) {
+            (Some(actual), Some(expected)) => actual == expected,
+            (Some(_), None) => true,
+            (None, _) => false,
+        })
+}
+
 /// List containers
-pub async fn list(manager: &ContainerManager, discover: bool, sync: bool) -> Result<()> {
+///
+/// `labels` filters discovered containers by runtime label and managed
+/// containers by state metadata; all filters must match. With `sync`, up to
+/// `parallel` containers are inspected concurrently before anything is shown.
+pub async fn list(
+    manager: &ContainerManager,
+    discover: bool,
+    sync: bool,
+    parallel: usize,
+    labels: &[String],
+) -> Result<()> {
+    let filters = parse_label_filters(labels)?;
+
     if discover {
-        return list_discovered(manager).await;
+        return list_discovered(manager, &filters).await;
     }
 
     if sync {
         // Sync all managed containers first
-        let containers = manager.list().await?;
-        for container in &containers {
-            let _ = manager.sync_status(&container.id).await;
-        }
+        let _ = manager.sync_statuses(parallel).await;
     }
 
-    let containers = manager.list().await?;
-    let display_names = display_name_map(&containers);
+    let all_containers = manager.list().await?;
+    let display_names = display_name_map(&all_containers);
 
-    if containers.is_empty() {
+    if all_containers.is_empty() {
         println!("No containers found.");
         println!("\nUse 'devc init' in a directory with devcontainer.json to add a container.");
         return Ok(());
     }
 
+    let containers: Vec<_> = all_containers
+        .into_iter()
+        .filter(|c| matches_label_filters(&c.metadata, &filters))
+        .collect();
+    if containers.is_empty() {
+        println!("No containers match the given labels.");
+        return Ok(());
+    }
+
     // Column widths
     const NAME_WIDTH: usize = 26;
     const STATUS_WIDTH: usize = 12;
@@ -60,10 +165,12 @@ pub async fn list(manager: &ContainerManager, discover: bool, sync: bool) -> Res
     );
     println!("{}", "-".repeat(75));
 
+    let color = std::io::IsTerminal::is_terminal(&std::io::stdout()) && crate::color::enabled();
     for container in containers {
         let status_symbol = match container.status {
             DevcContainerStatus::Available => "◌",
             DevcContainerStatus::Running => "●",
+            DevcContainerStatus::Paused => "‖",
             DevcContainerStatus::Stopped => "○",
             DevcContainerStatus::Building => "◐",
             DevcContainerStatus::Built => "◑",
@@ -89,12 +196,13 @@ pub async fn list(manager: &ContainerManager, discover: bool, sync: bool) -> Res
         let provider_str = format!("{}", container.provider);
         let provider_padding = PROVIDER_WIDTH.saturating_sub(provider_str.len());
 
+        let code = status_color(container.status);
         println!(
             "{} {}{} {}{} {}{} {}",
-            status_symbol,
+            paint(status_symbol, code, color),
             display_name,
             " ".repeat(name_padding),
-            status_str,
+            paint(&status_str, code, color),
             " ".repeat(status_padding),
             provider_str,
             " ".repeat(provider_padding),
@@ -105,8 +213,518 @@ pub async fn list(manager: &ContainerManager, discover: bool, sync: bool) -> Res
     Ok(())
 }
 
+/// One row of `devc ps` output
+struct PsRow {
+    name: String,
+    uptime_secs: Option<i64>,
+    ports: Vec<String>,
+    image: String,
+}
+
+/// List running containers only (docker-style `ps`)
+pub async fn ps(manager: &ContainerManager, json: bool) -> Result<()> {
+    let all = manager.list().await?;
+    let display_names = display_name_map(&all);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+
+    let mut rows = Vec::new();
+    for container in all
+        .iter()
+        .filter(|c| c.status == DevcContainerStatus::Running)
+    {
+        let name = display_names
+            .get(&container.id)
+            .cloned()
+            .unwrap_or_else(|| container.name.clone());
+        // Inspect failures (e.g. container vanished) still list the row, minus details
+        let details = match (
+            manager.provider_for_type(container.provider),
+            container.container_id.as_deref(),
+        ) {
+            (Some(provider), Some(cid)) => provider
+                .inspect(&devc_provider::ContainerId::new(cid))
+                .await
+                .ok(),
+            _ => None,
+        };
+        let row = match details {
+            Some(d) => PsRow {
+                name,
+                uptime_secs: d.started_at.map(|t| now - t),
+                ports: d
+                    .ports
+                    .iter()
+                    .map(|p| match p.host_port {
+                        Some(host) => format!("{}->{}/{}", host, p.container_port, p.protocol),
+                        None => format!("{}/{}", p.container_port, p.protocol),
+                    })
+                    .collect(),
+                image: d.image,
+            },
+            None => PsRow {
+                name,
+                uptime_secs: None,
+                ports: Vec::new(),
+                image: container.image_id.clone().unwrap_or_default(),
+            },
+        };
+        rows.push(row);
+    }
+
+    if json {
+        let value: Vec<_> = rows
+            .iter()
+            .map(|r| {
+                serde_json::json!({
+                    "name": r.name,
+                    "uptime_secs": r.uptime_secs,
+                    "ports": r.ports,
+                    "image": r.image,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&value)?);
+        return Ok(());
+    }
+
+    if rows.is_empty() {
+        println!("No running containers.");
+        return Ok(());
+    }
+
+    const NAME_WIDTH: usize = 26;
+    const UPTIME_WIDTH: usize = 10;
+    const PORTS_WIDTH: usize = 24;
+
+    println!(
+        "{:<NAME_WIDTH$} {:<UPTIME_WIDTH$} {:<PORTS_WIDTH$} IMAGE",
+        "NAME", "UPTIME", "PORTS"
+    );
+    for row in rows {
+        let uptime = row
+            .uptime_secs
+            .map(format_uptime)
+            .unwrap_or_else(|| "-".to_string());
+        let ports = if row.ports.is_empty() {
+            "-".to_string()
+        } else {
+            row.ports.join(", ")
+        };
+        println!(
+            "{:<NAME_WIDTH$} {:<UPTIME_WIDTH$} {:<PORTS_WIDTH$} {}",
+            row.name, uptime, ports, row.image
+        );
+    }
+
+    Ok(())
+}
+
+/// Show disk used by each container's writable layer and anonymous volumes
+///
+/// The image is shared between containers, so its size is shown for reference
+/// and not counted in the total.
+pub async fn du(manager: &ContainerManager, json: bool) -> Result<()> {
+    let all = manager.list().await?;
+    let display_names = display_name_map(&all);
+    let usage = manager.disk_usage().await?;
+    let mut unavailable: Vec<_> = all
+        .iter()
+        .map(|c| c.provider)
+        .filter(|pt| manager.provider_for_type(*pt).is_none())
+        .collect();
+    unavailable.sort_by_key(|pt| pt.to_string());
+    unavailable.dedup();
+    for pt in unavailable {
+        eprintln!(
+            "Warning: {} provider not available; its containers show no usage",
+            pt
+        );
+    }
+
+    let rows: Vec<_> = all
+        .iter()
+        .map(|c| {
+            let name = display_names
+                .get(&c.id)
+                .cloned()
+                .unwrap_or_else(|| c.name.clone());
+            (name, usage.get(&c.id))
+        })
+        .collect();
+
+    if json {
+        let value: Vec<_> = rows
+            .iter()
+            .map(|(name, u)| match u {
+                Some(u) => serde_json::json!({
+                    "name": name,
+                    "writable_bytes": u.writable_bytes,
+                    "volumes": u
+                        .volumes
+                        .iter()
+                        .map(|v| serde_json::json!({ "name": v.name, "bytes": v.bytes }))
+                        .collect::<Vec<_>>(),
+                    "total_bytes": u.total_bytes(),
+                    "image": u.image,
+                    "image_bytes": u.image_bytes,
+                }),
+                None => serde_json::json!({
+                    "name": name,
+                    "writable_bytes": null,
+                    "volumes": [],
+                    "total_bytes": null,
+                    "image": null,
+                    "image_bytes": null,
+                }),
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&value)?);
+        return Ok(());
+    }
+
+    if rows.is_empty() {
+        println!("No containers.");
+        return Ok(());
+    }
+
+    const NAME_WIDTH: usize = 26;
+    const SIZE_WIDTH: usize = 10;
+
+    println!(
+        "{:<NAME_WIDTH$} {:>SIZE_WIDTH$} {:>SIZE_WIDTH$} {:>SIZE_WIDTH$}  IMAGE",
+        "NAME", "WRITABLE", "VOLUMES", "TOTAL"
+    );
+    for (name, u) in rows {
+        let Some(u) = u else {
+            println!(
+                "{:<NAME_WIDTH$} {:>SIZE_WIDTH$} {:>SIZE_WIDTH$} {:>SIZE_WIDTH$}  -",
+                name, "-", "-", "-"
+            );
+            continue;
+        };
+        let volumes: u64 = u.volumes.iter().map(|v| v.bytes).sum();
+        let image = match u.image_bytes {
+            Some(bytes) => format!("{} ({})", u.image, format_bytes(bytes)),
+            None => u.image.clone(),
+        };
+        println!(
+            "{:<NAME_WIDTH$} {:>SIZE_WIDTH$} {:>SIZE_WIDTH$} {:>SIZE_WIDTH$}  {}",
+            name,
+            format_bytes(u.writable_bytes),
+            format_bytes(volumes),
+            format_bytes(u.total_bytes()),
+            image
+        );
+    }
+
+    Ok(())
+}
+
+/// Print logs for one or more containers, or for one compose service
+///
+/// Several containers are interleaved line by line behind a name prefix.
+/// With `follow`, a container that stops drops out while the rest continue.
+///
+/// `grep` keeps only lines matching the regex (or, with `invert`, only lines
+/// that don't); it is compiled before any logs are read.
+///
+/// `json` prints one `{ts, stream, message}` object per line instead, with
+/// stderr tagged apart from stdout.
+#[allow(clippy::too_many_arguments)]
+pub async fn logs(
+    manager: &ContainerManager,
+    containers: &[String],
+    service: Option<&str>,
+    tail: Option<u64>,
+    follow: bool,
+    grep: Option<&str>,
+    invert: bool,
+    previous: bool,
+    json: bool,
+) -> Result<()> {
+    let filter = grep
+        .map(|pattern| LogFilter::new(pattern, invert))
+        .transpose()?;
+    let keep = |line: &str| filter.as_ref().is_none_or(|f| f.keep(line));
+
+    if previous {
+        let [container] = containers else {
+            bail!("--previous needs exactly one container");
+        };
+        let state = find_container(manager, container).await?;
+        let (details, lines) = manager
+            .previous_logs(&state.id, tail)
+            .await
+            .map_err(|e| anyhow!("{}", e))?;
+        match (details.status, details.exit_code) {
+            (ContainerStatus::Running, _) => {
+                eprintln!("{} is still running; showing its current logs", state.name)
+            }
+            (_, Some(code)) => eprintln!("{} exited with code {}", state.name, code),
+            (status, None) => eprintln!("{} is {}", state.name, status),
+        }
+        for line in lines.iter().filter(|l| keep(l)) {
+            println!("{}", crate::color::plain(line));
+        }
+        return Ok(());
+    }
+
+    if let Some(service) = service {
+        let [container] = containers else {
+            bail!("--service needs exactly one container");
+        };
+        if follow {
+            bail!("--service cannot be combined with --follow");
+        }
+        let state = find_container(manager, container).await?;
+        let lines = manager
+            .compose_service_logs(&state.id, service, tail)
+            .await
+            .map_err(|e| anyhow!("{}", e))?;
+        for line in lines.iter().filter(|l| keep(l)) {
+            println!("{}", crate::color::plain(line));
+        }
+        return Ok(());
+    }
+
+    let mut states = Vec::with_capacity(containers.len());
+    for container in containers {
+        states.push(find_container(manager, container).await?);
+    }
+    if json {
+        return json_logs(
+            manager,
+            &states,
+            tail,
+            follow,
+            filter,
+            &mut std::io::stdout(),
+        )
+        .await;
+    }
+    if let [state] = states.as_slice() {
+        if !follow {
+            let lines = manager.logs(&state.id, tail).await?;
+            for line in lines.iter().filter(|l| keep(l)) {
+                println!("{}", crate::color::plain(line));
+            }
+            return Ok(());
+        }
+    }
+
+    // One reader task per container feeds a shared channel; a stream that
+    // ends (its container stopped) just drops its sender
+    let color = std::io::IsTerminal::is_terminal(&std::io::stdout()) && crate::color::enabled();
+    let width = states.iter().map(|s| s.name.len()).max().unwrap_or(0);
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+    for (index, state) in states.iter().enumerate() {
+        let devc_provider::LogStream { stream, _child, .. } =
+            manager.log_stream(&state.id, tail, follow).await?;
+        let prefix = (states.len() > 1).then(|| log_prefix(&state.name, width, index, color));
+        let filter = filter.clone();
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            use tokio::io::AsyncBufReadExt;
+            let _child = _child;
+            let mut lines = tokio::io::BufReader::new(stream).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if filter.as_ref().is_some_and(|f| !f.keep(&line)) {
+                    continue;
+                }
+                let line = match &prefix {
+                    Some(prefix) => format!("{} {}", prefix, line),
+                    None => line,
+                };
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    drop(tx);
+
+    loop {
+        tokio::select! {
+            line = rx.recv() => match line {
+                Some(line) => println!("{}", crate::color::plain(&line)),
+                None => break,
+            },
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+    Ok(())
+}
+
+/// `logs --json`: one JSON object per line, read from each container's
+/// stdout and stderr separately so every line carries its stream
+///
+/// The runtime merges stderr into stdout for containers created with a TTY,
+/// which includes every container devc creates, so all their lines are
+/// tagged `stdout`. Lines are sorted by timestamp, except with `--follow`,
+/// where they are written as they arrive. Several containers add a
+/// `container` field naming where a line came from.
+async fn json_logs(
+    manager: &ContainerManager,
+    states: &[ContainerState],
+    tail: Option<u64>,
+    follow: bool,
+    filter: Option<LogFilter>,
+    out: &mut dyn std::io::Write,
+) -> Result<()> {
+    use devc_provider::{LogLine, LogSource};
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<(usize, LogLine)>();
+    let mut children = Vec::with_capacity(states.len());
+    for (index, state) in states.iter().enumerate() {
+        let devc_provider::LogStream {
+            stream,
+            stderr,
+            _child,
+        } = manager
+            .structured_log_stream(&state.id, tail, follow)
+            .await?;
+        children.push(_child);
+        let sources = std::iter::once((stream, LogSource::Stdout))
+            .chain(stderr.map(|s| (s, LogSource::Stderr)));
+        for (reader, source) in sources {
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                use tokio::io::AsyncBufReadExt;
+                let mut lines = tokio::io::BufReader::new(reader).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    if tx
+                        .send((index, LogLine::from_timestamped(&line, source)))
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+            });
+        }
+    }
+    drop(tx);
+
+    let mut write_line = |index: usize, line: &LogLine| -> Result<()> {
+        let mut value = serde_json::to_value(line)?;
+        if let (true, Some(obj)) = (states.len() > 1, value.as_object_mut()) {
+            obj.insert("container".to_string(), states[index].name.clone().into());
+        }
+        writeln!(out, "{}", value)?;
+        Ok(())
+    };
+    // The runtime's own errors ("No such container") share the stderr pipe,
+    // but lack the timestamp it puts on every container line
+    let mut runtime_errors = vec![Vec::new(); states.len()];
+    let mut lines = Vec::new();
+    loop {
+        tokio::select! {
+            line = rx.recv() => match line {
+                Some((index, line)) if line.stream == LogSource::Stderr && line.ts.is_none() => {
+                    runtime_errors[index].push(line.message);
+                }
+                Some((index, line)) => {
+                    if filter.as_ref().is_some_and(|f| !f.keep(&line.message)) {
+                        continue;
+                    }
+                    if follow {
+                        write_line(index, &line)?;
+                    } else {
+                        lines.push((index, line));
+                    }
+                }
+                None => break,
+            },
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
+
+    for ((state, child), errors) in states.iter().zip(children).zip(runtime_errors) {
+        let Some(mut child) = child else { continue };
+        let status = child.wait().await.context("Failed to read logs")?;
+        if !status.success() {
+            let reason = if errors.is_empty() {
+                status.to_string()
+            } else {
+                errors.join("\n")
+            };
+            bail!("Failed to read logs of {}: {}", state.name, reason);
+        }
+    }
+
+    // A stable sort keeps each stream's order among equal timestamps
+    lines.sort_by_key(|(_, line)| {
+        line.ts
+            .as_deref()
+            .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+    });
+    for (index, line) in &lines {
+        write_line(*index, line)?;
+    }
+    Ok(())
+}
+
+/// A `--grep` line filter, optionally inverted with `--invert`
+#[derive(Clone)]
+struct LogFilter {
+    regex: regex::Regex,
+    invert: bool,
+}
+
+impl LogFilter {
+    fn new(pattern: &str, invert: bool) -> Result<Self> {
+        let regex = regex::Regex::new(pattern)
+            .with_context(|| format!("Invalid --grep pattern '{}'", pattern))?;
+        Ok(Self { regex, invert })
+    }
+
+    /// Whether a log line should be printed
+    fn keep(&self, line: &str) -> bool {
+        self.regex.is_match(line) != self.invert
+    }
+}
+
+/// SGR code for a status in `devc list`, close to the TUI's status colors
+fn status_color(status: DevcContainerStatus) -> &'static str {
+    match status {
+        DevcContainerStatus::Running => "32",
+        DevcContainerStatus::Stopped | DevcContainerStatus::Building => "33",
+        DevcContainerStatus::Failed => "31",
+        DevcContainerStatus::Paused => "35",
+        DevcContainerStatus::Built => "34",
+        DevcContainerStatus::Created => "36",
+        DevcContainerStatus::Available | DevcContainerStatus::Configured => "2",
+    }
+}
+
+/// Wrap `text` in an SGR color when `color` is set
+fn paint(text: &str, code: &str, color: bool) -> String {
+    if color {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Colors cycled through for container name prefixes in combined logs
+const LOG_PREFIX_COLORS: &[&str] = &["36", "33", "32", "35", "34", "31"];
+
+/// `name |` prefix for combined log lines, padded to `width` and colored per
+/// container when writing to a terminal
+fn log_prefix(name: &str, width: usize, index: usize, color: bool) -> String {
+    let label = format!("{:<width$} |", name, width = width);
+    if color {
+        let code = LOG_PREFIX_COLORS[index % LOG_PREFIX_COLORS.len()];
+        format!("\x1b[{}m{}\x1b[0m", code, label)
+    } else {
+        label
+    }
+}
+
 /// List discovered devcontainers from all providers
-async fn list_discovered(manager: &ContainerManager) -> Result<()> {
+async fn list_discovered(manager: &ContainerManager, filters: &[LabelFilter]) -> Result<()> {
     use devc_provider::DevcontainerSource;
 
     let discovered = manager.discover().await?;
@@ -117,6 +735,15 @@ async fn list_discovered(manager: &ContainerManager) -> Result<()> {
         return Ok(());
     }
 
+    let discovered: Vec<_> = discovered
+        .into_iter()
+        .filter(|c| matches_label_filters(&c.labels, filters))
+        .collect();
+    if discovered.is_empty() {
+        println!("No devcontainers match the given labels.");
+        return Ok(());
+    }
+
     // Column widths
     const NAME_WIDTH: usize = 26;
     const STATUS_WIDTH: usize = 12;
@@ -172,9 +799,20 @@ async fn list_discovered(manager: &ContainerManager) -> Result<()> {
 }
 
 /// Initialize a new container from current directory
-pub async fn init(manager: &ContainerManager) -> Result<()> {
+pub async fn init(manager: &ContainerManager, template: Option<&str>, force: bool) -> Result<()> {
     let cwd = std::env::current_dir()?;
 
+    // Check if already initialized
+    let containers = manager.list().await?;
+    if containers.iter().any(|c| c.workspace_path == cwd) {
+        bail!("Container already initialized for this directory");
+    }
+
+    if let Some(name) = template {
+        let path = write_template(&cwd, name, force)?;
+        println!("Wrote {} from the '{}' template", path.display(), name);
+    }
+
     // Check if devcontainer.json exists
     let devcontainer_path = cwd.join(".devcontainer/devcontainer.json");
     let devcontainer_alt = cwd.join(".devcontainer.json");
@@ -182,16 +820,11 @@ pub async fn init(manager: &ContainerManager) -> Result<()> {
     if !devcontainer_path.exists() && !devcontainer_alt.exists() {
         bail!(
             "No devcontainer.json found in current directory.\n\
-             Create .devcontainer/devcontainer.json first."
+             Create .devcontainer/devcontainer.json first, or start from a\n\
+             template with 'devc init --template <name>' (see 'devc init --list')."
         );
     }
 
-    // Check if already initialized
-    let containers = manager.list().await?;
-    if containers.iter().any(|c| c.workspace_path == cwd) {
-        bail!("Container already initialized for this directory");
-    }
-
     let state = manager.init(&cwd).await?;
     println!("Initialized container: {}", state.name);
     println!("\nNext steps:");
@@ -210,9 +843,6 @@ pub async fn config(edit: bool) -> Result<()> {
     let config_path = GlobalConfig::config_path()?;
 
     if edit {
-        // Open in editor
-        let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
-
         // Create config file with defaults if it doesn't exist
         if !config_path.exists() {
             let config = GlobalConfig::default();
@@ -220,10 +850,15 @@ pub async fn config(edit: bool) -> Result<()> {
             println!("Created default config at {:?}", config_path);
         }
 
-        std::process::Command::new(&editor)
-            .arg(&config_path)
-            .status()
-            .context(format!("Failed to open editor: {}", editor))?;
+        let configured = GlobalConfig::load()
+            .ok()
+            .and_then(|config| config.defaults.editor);
+        let editor = devc_config::editor(configured.as_deref());
+        if let Some(warning) = &editor.warning {
+            eprintln!("Warning: {}; using '{}'", warning, editor.command);
+        }
+        devc_config::open_in_editor(&editor.command, &config_path)
+            .context(format!("Failed to open editor: {}", editor.command))?;
     } else {
         // Show config
         if config_path.exists() {
@@ -243,6 +878,141 @@ pub async fn config(edit: bool) -> Result<()> {
     Ok(())
 }
 
+/// Check devcontainer.json files without building
+///
+/// `path` is a config file, or a workspace whose configs are all checked.
+/// Parse errors, missing referenced files and unresolved variables are
+/// errors; schema issues are warnings unless `strict`, and unset `localEnv`
+/// variables are warnings.
+pub fn config_validate(path: Option<&Path>, strict: bool) -> Result<()> {
+    let target = match path {
+        Some(path) => path.to_path_buf(),
+        None => std::env::current_dir()?,
+    };
+    let config_paths = if target.is_dir() {
+        devc_config::config_paths_in_dir(&target)
+    } else {
+        vec![target.clone()]
+    };
+    if config_paths.is_empty() {
+        bail!("No devcontainer.json found in {}", target.display());
+    }
+
+    let mut errors = 0;
+    for config_path in &config_paths {
+        println!("{}", config_path.display());
+        let (config, issues) = match DevContainerConfig::load_validated(config_path, false) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                errors += 1;
+                println!("  error: {}", e);
+                continue;
+            }
+        };
+
+        let mut problems: Vec<(&str, String)> = issues
+            .iter()
+            .map(|issue| (if strict { "error" } else { "warning" }, issue.to_string()))
+            .collect();
+        problems.extend(
+            config
+                .missing_references(config_path)
+                .into_iter()
+                .map(|missing| ("error", missing)),
+        );
+        problems.extend(config.unresolved_variables().into_iter().map(|var| {
+            // An unset host variable expands to empty, per the spec
+            if var.starts_with("${localEnv:") {
+                ("warning", format!("{} is unset and expands to empty", var))
+            } else {
+                ("error", format!("unresolved variable {}", var))
+            }
+        }));
+
+        if problems.is_empty() {
+            println!("  ok");
+        }
+        for (level, message) in &problems {
+            println!("  {}: {}", level, message);
+        }
+        errors += problems
+            .iter()
+            .filter(|(level, _)| *level == "error")
+            .count();
+    }
+
+    if errors > 0 {
+        bail!("{} error(s) found", errors);
+    }
+    Ok(())
+}
+
+/// List the profiles in the global config, marking the active one
+pub fn config_profiles() -> Result<()> {
+    let config_path = GlobalConfig::config_path()?;
+    let config = if config_path.exists() {
+        GlobalConfig::load_from(&config_path)?
+    } else {
+        GlobalConfig::default()
+    };
+    let selected = GlobalConfig::selected_profile();
+
+    if config.profiles.is_empty() {
+        println!("No profiles defined in {:?}", config_path);
+        println!("Add a [profiles.<name>] table to override config keys per machine.");
+    }
+
+    let mut invalid = 0;
+    for (name, table) in &config.profiles {
+        let marker = if selected.as_deref() == Some(name.as_str()) {
+            "* "
+        } else {
+            "  "
+        };
+        let keys = profile_keys(table).join(", ");
+        match config.with_profile(name) {
+            Ok(_) => println!("{}{}  {}", marker, name, keys),
+            Err(e) => {
+                invalid += 1;
+                println!("{}{}  invalid: {}", marker, name, e);
+            }
+        }
+    }
+
+    if let Some(name) = selected {
+        if !config.profiles.contains_key(&name) {
+            return Err(devc_config::ConfigError::UnknownProfile {
+                name,
+                available: config.profiles.keys().cloned().collect(),
+            }
+            .into());
+        }
+    }
+    if invalid > 0 {
+        bail!("{} invalid profile(s)", invalid);
+    }
+
+    Ok(())
+}
+
+/// Dotted paths of the leaf keys a profile sets
+fn profile_keys(table: &toml::Table) -> Vec<String> {
+    let mut keys = Vec::new();
+    for (key, value) in table {
+        match value {
+            toml::Value::Table(nested) => {
+                keys.extend(
+                    profile_keys(nested)
+                        .into_iter()
+                        .map(|sub| format!("{}.{}", key, sub)),
+                );
+            }
+            _ => keys.push(key.clone()),
+        }
+    }
+    keys
+}
+
 /// Show credential forwarding diagnostics
 pub async fn creds(manager: &ContainerManager, container: Option<String>) -> Result<()> {
     use devc_core::credentials::host;
@@ -644,8 +1414,151 @@ pub async fn agents_doctor(manager: &ContainerManager, container: Option<String>
     Ok(())
 }
 
+/// Enabled column as in the TUI agent manager: explicit setting, else `auto`/`off`
+fn agent_enabled_label(presence: &AgentContainerPresence) -> &'static str {
+    match presence.enabled_explicit {
+        Some(true) => "enabled",
+        Some(false) => "disabled",
+        None if presence.enabled_effective => "auto",
+        None => "off",
+    }
+}
+
+/// Whether the agent's config and binary are both present in the container
+fn agent_presence_state(presence: &AgentContainerPresence) -> &'static str {
+    match (
+        presence.container_config_present,
+        presence.container_binary_present,
+    ) {
+        (true, true) => "on-container",
+        (false, false) => "missing",
+        _ => "partial",
+    }
+}
+
+fn agent_last_sync_label(result: Option<&AgentSyncResult>) -> String {
+    match result {
+        None => "-".to_string(),
+        Some(r) if !r.warnings.is_empty() => format!("warn({})", r.warnings.len()),
+        Some(r) if r.installed => "ok+install".to_string(),
+        Some(r) if r.copied => "ok".to_string(),
+        Some(_) => "skipped".to_string(),
+    }
+}
+
+/// Show per-agent presence inside a running container (CLI counterpart of the
+/// TUI agent manager)
+pub async fn agents_status(
+    manager: &ContainerManager,
+    container: Option<String>,
+    json: bool,
+) -> Result<()> {
+    let state = match container {
+        Some(name) => find_container(manager, &name).await?,
+        None => find_container_in_cwd(manager).await?,
+    };
+
+    if state.status != DevcContainerStatus::Running {
+        bail!(
+            "Container '{}' is not running (status: {}). Start it first.",
+            state.name,
+            state.status
+        );
+    }
+
+    let presences = manager.inspect_agents_for_container(&state.id).await?;
+    let mut last_sync: std::collections::HashMap<AgentKind, AgentSyncResult> = state
+        .metadata
+        .get("agent_sync_results")
+        .and_then(|raw| serde_json::from_str::<Vec<AgentSyncResult>>(raw).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|r| (r.agent, r))
+        .collect();
+
+    if json {
+        let value: Vec<_> = presences
+            .iter()
+            .map(|p| {
+                serde_json::json!({
+                    "agent": p.agent.as_str(),
+                    "enabled": p.enabled_effective,
+                    "enabled_explicit": p.enabled_explicit,
+                    "host_available": p.host_available,
+                    "host_reason": p.host_reason,
+                    "config_present": p.container_config_present,
+                    "binary_present": p.container_binary_present,
+                    "state": agent_presence_state(p),
+                    "warnings": p.warnings,
+                    "last_sync": last_sync.remove(&p.agent),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&value)?);
+        return Ok(());
+    }
+
+    println!("Agents in '{}':\n", state.name);
+    println!(
+        "{:<8} {:<9} {:<8} {:<4} {:<4} {:<13} LAST SYNC",
+        "AGENT", "ENABLED", "HOST", "CFG", "BIN", "STATE"
+    );
+    let yes_no = |present: bool| if present { "yes" } else { "no" };
+    for p in &presences {
+        let sync = last_sync.remove(&p.agent);
+        println!(
+            "{:<8} {:<9} {:<8} {:<4} {:<4} {:<13} {}",
+            p.agent,
+            agent_enabled_label(p),
+            if p.host_available { "ok" } else { "missing" },
+            yes_no(p.container_config_present),
+            yes_no(p.container_binary_present),
+            agent_presence_state(p),
+            agent_last_sync_label(sync.as_ref()),
+        );
+        if let Some(reason) = p.host_reason.as_deref().filter(|_| !p.host_available) {
+            println!("  host: {}", reason);
+        }
+        for warning in &p.warnings {
+            println!("  warning: {}", warning);
+        }
+        for warning in sync.iter().flat_map(|r| &r.warnings) {
+            println!("  last sync: {}", warning);
+        }
+    }
+
+    Ok(())
+}
+
 /// Force agent sync for a running container.
 pub async fn agents_sync(manager: &ContainerManager, container: Option<String>) -> Result<()> {
+    sync_agents(manager, container, AgentSyncSelection::EnabledOnly).await
+}
+
+/// Build the agent selection for `sync-agents` from `--only` and `--force`
+///
+/// Without `--only`, enabled agents are synced (or every agent with `--force`).
+/// `--force` also syncs listed agents that are disabled in the config.
+pub fn agent_sync_selection(only: &[String], force: bool) -> Result<AgentSyncSelection> {
+    let kinds = only
+        .iter()
+        .map(|name| name.trim().parse::<AgentKind>().map_err(|e| anyhow!(e)))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(match (kinds.is_empty(), force) {
+        (true, false) => AgentSyncSelection::EnabledOnly,
+        (true, true) => AgentSyncSelection::ForceOnly(AgentKind::ALL.to_vec()),
+        (false, false) => AgentSyncSelection::Only(kinds),
+        (false, true) => AgentSyncSelection::ForceOnly(kinds),
+    })
+}
+
+/// Re-push agent configs into a running container and report per-agent results
+pub async fn sync_agents(
+    manager: &ContainerManager,
+    container: Option<String>,
+    selection: AgentSyncSelection,
+) -> Result<()> {
     let state = match container {
         Some(name) => find_container(manager, &name).await?,
         None => find_container_in_cwd(manager).await?,
@@ -659,27 +1572,44 @@ pub async fn agents_sync(manager: &ContainerManager, container: Option<String>)
         );
     }
 
+    let empty_message = match selection {
+        AgentSyncSelection::EnabledOnly => "No enabled agents to sync.",
+        AgentSyncSelection::Only(_) => {
+            "No enabled agents to sync (use --force to include disabled agents)."
+        }
+        AgentSyncSelection::ForceOnly(_) => "No agents to sync.",
+    };
     println!("Syncing agents for '{}'...", state.name);
-    let results = manager.setup_agents_for_container(&state.id).await?;
+    let results = manager
+        .setup_agents_for_container_filtered(&state.id, selection)
+        .await?;
 
     if results.is_empty() {
-        println!("No enabled agents to sync.");
+        println!("{}", empty_message);
         return Ok(());
     }
 
     let mut warning_count = 0usize;
     for result in results {
+        let steps: Vec<&str> = [
+            (result.validated, "validated"),
+            (result.copied, "copied"),
+            (result.installed, "installed"),
+        ]
+        .into_iter()
+        .filter_map(|(done, step)| done.then_some(step))
+        .collect();
+        let steps = if steps.is_empty() {
+            "skipped".to_string()
+        } else {
+            steps.join(", ")
+        };
+
         if result.warnings.is_empty() {
-            if result.installed {
-                println!("- {}: ok (copied + installed)", result.agent);
-            } else if result.copied {
-                println!("- {}: ok (copied)", result.agent);
-            } else {
-                println!("- {}: skipped", result.agent);
-            }
+            println!("- {}: ok ({})", result.agent, steps);
         } else {
             warning_count += result.warnings.len();
-            println!("- {}: warning", result.agent);
+            println!("- {}: warning ({})", result.agent, steps);
             for warning in result.warnings {
                 println!("  {}", warning);
             }
@@ -792,3 +1722,260 @@ pub async fn adopt(manager: &ContainerManager, container: Option<String>) -> Res
 
     Ok(())
 }
+
+/// Details for `devc version --full`: spec level, platform, runtime and compose
+async fn full_version_lines(
+    provider: std::result::Result<&dyn ContainerProvider, String>,
+) -> Vec<String> {
+    let mut lines = vec![
+        format!("Spec:     {}", devc_config::DEVCONTAINER_SPEC_LEVEL),
+        format!(
+            "Platform: {}/{}",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        ),
+    ];
+    match provider {
+        Ok(provider) => {
+            let name = provider.info().provider_type;
+            lines.push(match provider.version().await {
+                Ok(version) => format!("Provider: {} {}", name, version),
+                Err(e) => format!(
+                    "Provider: {} (version unavailable: {})",
+                    name,
+                    e.to_string().trim()
+                ),
+            });
+            lines.push(match provider.compose_version().await {
+                Ok(compose) => format!("Compose:  {}", compose),
+                Err(_) => "Compose:  not detected".to_string(),
+            });
+        }
+        Err(e) => {
+            lines.push(format!("Provider: unavailable ({})", e));
+            lines.push("Compose:  unknown".to_string());
+        }
+    }
+    lines
+}
+
+/// Show devc's version; with `provider` set (from `--full`), also report the
+/// runtime, compose implementation, and supported spec level for bug reports
+pub async fn version(
+    provider: Option<devc_provider::Result<Box<dyn ContainerProvider>>>,
+) -> Result<()> {
+    println!("devc {}", env!("CARGO_PKG_VERSION"));
+    if let Some(provider) = provider {
+        let provider = provider
+            .as_deref()
+            .map_err(|e| e.to_string().trim().to_string());
+        for line in full_version_lines(provider).await {
+            println!("{}", line);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_log_prefix_pads_and_colors() {
+        assert_eq!(log_prefix("api", 5, 0, false), "api   |");
+        assert_eq!(log_prefix("web", 3, 1, true), "\x1b[33mweb |\x1b[0m");
+        // Colors wrap around once every one has been used
+        assert_eq!(
+            log_prefix("db", 2, LOG_PREFIX_COLORS.len(), true),
+            log_prefix("db", 2, 0, true)
+        );
+    }
+
+    #[test]
+    fn test_list_status_colors() {
+        assert_eq!(
+            paint("running", status_color(DevcContainerStatus::Running), true),
+            "\x1b[32mrunning\x1b[0m"
+        );
+        assert_eq!(status_color(DevcContainerStatus::Stopped), "33");
+        assert_eq!(status_color(DevcContainerStatus::Failed), "31");
+        assert_eq!(status_color(DevcContainerStatus::Configured), "2");
+        assert_eq!(paint("failed", "31", false), "failed");
+    }
+
+    #[test]
+    fn test_log_filter_matches_and_inverts() {
+        let filter = LogFilter::new("err(or)?", false).unwrap();
+        assert!(filter.keep("an error occurred"));
+        assert!(!filter.keep("all good"));
+
+        let inverted = LogFilter::new("err(or)?", true).unwrap();
+        assert!(!inverted.keep("an error occurred"));
+        assert!(inverted.keep("all good"));
+
+        assert!(LogFilter::new("(unclosed", false).is_err());
+    }
+
+    #[test]
+    fn test_parse_label_filters() {
+        let filters =
+            parse_label_filters(&["team=infra".to_string(), "devc.managed".to_string()]).unwrap();
+        assert_eq!(
+            filters,
+            vec![
+                LabelFilter {
+                    key: "team".to_string(),
+                    value: Some("infra".to_string()),
+                },
+                LabelFilter {
+                    key: "devc.managed".to_string(),
+                    value: None,
+                },
+            ]
+        );
+        assert!(parse_label_filters(&["=x".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_matches_label_filters() {
+        let labels = HashMap::from([
+            ("team".to_string(), "infra".to_string()),
+            ("env".to_string(), "dev".to_string()),
+        ]);
+        let check = |raw: &[&str]| {
+            let raw: Vec<String> = raw.iter().map(|s| s.to_string()).collect();
+            matches_label_filters(&labels, &parse_label_filters(&raw).unwrap())
+        };
+
+        assert!(check(&[]));
+        assert!(check(&["team=infra"]));
+        assert!(check(&["team=infra", "env"]));
+        assert!(!check(&["team=web"]));
+        assert!(!check(&["team=infra", "owner"]));
+    }
+
+    #[test]
+    fn test_agent_sync_selection() {
+        assert!(matches!(
+            agent_sync_selection(&[], false).unwrap(),
+            AgentSyncSelection::EnabledOnly
+        ));
+        assert!(matches!(
+            agent_sync_selection(&[], true).unwrap(),
+            AgentSyncSelection::ForceOnly(kinds) if kinds == AgentKind::ALL.to_vec()
+        ));
+
+        let only = vec!["codex".to_string(), " claude".to_string()];
+        assert!(matches!(
+            agent_sync_selection(&only, false).unwrap(),
+            AgentSyncSelection::Only(kinds) if kinds == vec![AgentKind::Codex, AgentKind::Claude]
+        ));
+        assert!(matches!(
+            agent_sync_selection(&only, true).unwrap(),
+            AgentSyncSelection::ForceOnly(kinds) if kinds.len() == 2
+        ));
+
+        let err = agent_sync_selection(&["copilot".to_string()], false).unwrap_err();
+        assert!(err.to_string().contains("unknown agent 'copilot'"));
+    }
+
+    #[test]
+    fn test_agent_status_labels() {
+        let mut presence = AgentContainerPresence {
+            agent: AgentKind::Codex,
+            enabled_effective: true,
+            enabled_explicit: None,
+            host_available: true,
+            host_reason: None,
+            container_config_present: true,
+            container_binary_present: false,
+            warnings: Vec::new(),
+        };
+        assert_eq!(agent_enabled_label(&presence), "auto");
+        assert_eq!(agent_presence_state(&presence), "partial");
+
+        presence.enabled_explicit = Some(false);
+        presence.container_binary_present = true;
+        assert_eq!(agent_enabled_label(&presence), "disabled");
+        assert_eq!(agent_presence_state(&presence), "on-container");
+
+        let mut result = AgentSyncResult::new(AgentKind::Codex);
+        assert_eq!(agent_last_sync_label(None), "-");
+        assert_eq!(agent_last_sync_label(Some(&result)), "skipped");
+        result.copied = true;
+        result.installed = true;
+        assert_eq!(agent_last_sync_label(Some(&result)), "ok+install");
+        result.warnings.push("npm missing".to_string());
+        assert_eq!(agent_last_sync_label(Some(&result)), "warn(1)");
+    }
+
+    #[tokio::test]
+    async fn test_full_version_lines() {
+        use devc_core::test_support::MockProvider;
+        use devc_provider::{ProviderError, ProviderType};
+
+        let provider = MockProvider::new(ProviderType::Podman);
+        let lines = full_version_lines(Ok(&provider)).await;
+        assert!(lines[0].contains(devc_config::DEVCONTAINER_SPEC_LEVEL));
+        assert!(lines.contains(&"Provider: podman 1.0.0-mock".to_string()));
+        assert!(lines.contains(&"Compose:  Docker Compose 2.0.0-mock".to_string()));
+
+        *provider.compose_version_result.lock().unwrap() =
+            Err(ProviderError::RuntimeError("unknown command".to_string()));
+        let lines = full_version_lines(Ok(&provider)).await;
+        assert!(lines.contains(&"Compose:  not detected".to_string()));
+
+        let lines = full_version_lines(Err("docker not found".to_string())).await;
+        assert!(lines.contains(&"Provider: unavailable (docker not found)".to_string()));
+    }
+
+    /// JSON logs carry each line's stream, come out in timestamp order, and
+    /// leave out the runtime's own untimestamped messages
+    #[tokio::test]
+    async fn test_json_logs_emits_lines_in_timestamp_order() {
+        use devc_core::test_support::MockProvider;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let config_path = tmp.path().join(".devcontainer/devcontainer.json");
+        std::fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        std::fs::write(&config_path, r#"{"image": "alpine:3"}"#).unwrap();
+        let mut cs = ContainerState::new(
+            "shipper".to_string(),
+            ProviderType::Docker,
+            config_path,
+            tmp.path().to_path_buf(),
+        );
+        cs.status = DevcContainerStatus::Running;
+        cs.container_id = Some("cid-json".to_string());
+
+        let mock = MockProvider::new(ProviderType::Docker);
+        *mock.logs_output.lock().unwrap() = "2026-03-01T12:00:00.5Z starting\n\
+                                             2026-03-01T12:00:02Z ready\n"
+            .to_string();
+        *mock.logs_stderr.lock().unwrap() =
+            "2026-03-01T12:00:01.25Z warning: slow disk\nruntime notice\n".to_string();
+        let mut store = devc_core::StateStore::new();
+        store.add(cs.clone());
+        let manager =
+            ContainerManager::new_for_testing(Box::new(mock), GlobalConfig::default(), store);
+
+        let mut out = Vec::new();
+        json_logs(&manager, &[cs], None, false, None, &mut out)
+            .await
+            .unwrap();
+        let lines: Vec<serde_json::Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                serde_json::json!({"ts": "2026-03-01T12:00:00.5Z", "stream": "stdout", "message": "starting"}),
+                serde_json::json!({"ts": "2026-03-01T12:00:01.25Z", "stream": "stderr", "message": "warning: slow disk"}),
+                serde_json::json!({"ts": "2026-03-01T12:00:02Z", "stream": "stdout", "message": "ready"}),
+            ]
+        );
+    }
+}
@@ -1,13 +1,22 @@
 //! CLI command implementations
 
+mod clone;
+mod copy;
 mod lifecycle;
 mod manage;
+mod pull_progress;
+mod serve;
+mod templates;
 
 use anyhow::{anyhow, Result};
 use devc_core::{display_name_map, ContainerManager, ContainerState};
 
+pub use clone::*;
+pub use copy::*;
 pub use lifecycle::*;
 pub use manage::*;
+pub use serve::*;
+pub use templates::*;
 
 /// Find a container by name or ID
 async fn find_container(manager: &ContainerManager, name_or_id: &str) -> Result<ContainerState> {
@@ -0,0 +1,385 @@
+//! Compact single-line progress for image pulls
+//!
+//! Parses the per-layer status lines printed by `docker pull` and `podman pull`
+//! and condenses them into one updating line with a spinner, overall percentage
+//! and a rough ETA. Non-pull lines (build output, status messages) are printed
+//! as-is above the progress line.
+
+use devc_provider::{format_bytes, parse_size};
+use std::collections::HashMap;
+use std::io::Write;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+const SPINNER: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Download state of a single image layer
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LayerState {
+    Pending,
+    Downloading { current: u64, total: Option<u64> },
+    Done { total: Option<u64> },
+}
+
+/// Aggregated progress across all layers of a pull
+#[derive(Debug, Default)]
+pub(crate) struct PullTracker {
+    layers: HashMap<String, LayerState>,
+}
+
+impl PullTracker {
+    /// Feed one output line. Returns true if it was a layer progress line.
+    pub(crate) fn observe(&mut self, line: &str) -> bool {
+        let Some((id, state)) = parse_layer_line(line) else {
+            return false;
+        };
+        // Keep the known total when a later status line drops the byte counts
+        let previous_total = match self.layers.get(&id) {
+            Some(LayerState::Downloading { total, .. }) | Some(LayerState::Done { total }) => {
+                *total
+            }
+            _ => None,
+        };
+        let state = match state {
+            LayerState::Done { total } => LayerState::Done {
+                total: total.or(previous_total),
+            },
+            LayerState::Downloading { current, total } => LayerState::Downloading {
+                current,
+                total: total.or(previous_total),
+            },
+            LayerState::Pending => LayerState::Pending,
+        };
+        self.layers.insert(id, state);
+        true
+    }
+
+    fn layer_counts(&self) -> (usize, usize) {
+        let done = self
+            .layers
+            .values()
+            .filter(|s| matches!(s, LayerState::Done { .. }))
+            .count();
+        (done, self.layers.len())
+    }
+
+    /// (downloaded, total) over the layers whose size is known
+    fn byte_counts(&self) -> Option<(u64, u64)> {
+        let mut current = 0;
+        let mut total = 0;
+        for state in self.layers.values() {
+            match *state {
+                LayerState::Downloading {
+                    current: c,
+                    total: Some(t),
+                } => {
+                    current += c.min(t);
+                    total += t;
+                }
+                LayerState::Done { total: Some(t) } => {
+                    current += t;
+                    total += t;
+                }
+                _ => {}
+            }
+        }
+        (total > 0).then_some((current, total))
+    }
+
+    /// Overall completion in percent, by bytes when sizes are known, else by layers
+    pub(crate) fn percent(&self) -> u8 {
+        let fraction = match self.byte_counts() {
+            Some((current, total)) => current as f64 / total as f64,
+            None => {
+                let (done, total) = self.layer_counts();
+                if total == 0 {
+                    return 0;
+                }
+                done as f64 / total as f64
+            }
+        };
+        (fraction * 100.0).round().min(100.0) as u8
+    }
+
+    /// Rough time remaining, extrapolated from the rate so far
+    pub(crate) fn eta(&self, elapsed: Duration) -> Option<Duration> {
+        let secs = elapsed.as_secs_f64();
+        let remaining = match self.byte_counts() {
+            Some((current, total)) if current > 0 => {
+                (total - current) as f64 * secs / current as f64
+            }
+            Some(_) => return None,
+            None => {
+                let (done, total) = self.layer_counts();
+                if done == 0 {
+                    return None;
+                }
+                (total - done) as f64 * secs / done as f64
+            }
+        };
+        Some(Duration::from_secs_f64(remaining))
+    }
+
+    /// One-line summary, e.g. `Pulling 3/7 layers 42% (120.3MB/286.0MB) ETA 35s`
+    pub(crate) fn summary(&self, elapsed: Duration) -> String {
+        let (done, total) = self.layer_counts();
+        let mut out = format!("Pulling {}/{} layers {}%", done, total, self.percent());
+        if let Some((current, total_bytes)) = self.byte_counts() {
+            out.push_str(&format!(
+                " ({}/{})",
+                format_bytes(current),
+                format_bytes(total_bytes)
+            ));
+        }
+        if let Some(eta) = self.eta(elapsed) {
+            out.push_str(&format!(" ETA {}", format_duration(eta)));
+        }
+        out
+    }
+}
+
+/// Parse a docker (`<id>: <status> [bar] <cur>/<total>`) or podman
+/// (`Copying blob <id> [done|skipped...]`) layer status line.
+fn parse_layer_line(line: &str) -> Option<(String, LayerState)> {
+    let line = line.trim();
+
+    if let Some(rest) = line.strip_prefix("Copying blob ") {
+        let mut parts = rest.split_whitespace();
+        let id = parts.next()?;
+        let id = id.strip_prefix("sha256:").unwrap_or(id);
+        let tail = parts.collect::<Vec<_>>().join(" ");
+        let state = if tail.contains("done") || tail.contains("skipped") {
+            LayerState::Done { total: None }
+        } else if let Some((current, total)) = parse_byte_progress(&tail) {
+            LayerState::Downloading {
+                current,
+                total: Some(total),
+            }
+        } else {
+            LayerState::Pending
+        };
+        return Some((id.to_string(), state));
+    }
+
+    let (id, status) = line.split_once(": ")?;
+    if id.len() < 12 || !id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let state = if status.starts_with("Downloading") {
+        match parse_byte_progress(status) {
+            Some((current, total)) => LayerState::Downloading {
+                current,
+                total: Some(total),
+            },
+            None => LayerState::Downloading {
+                current: 0,
+                total: None,
+            },
+        }
+    } else if status.starts_with("Pulling fs layer") || status.starts_with("Waiting") {
+        LayerState::Pending
+    } else if [
+        "Verifying Checksum",
+        "Download complete",
+        "Extracting",
+        "Pull complete",
+        "Already exists",
+    ]
+    .iter()
+    .any(|s| status.starts_with(s))
+    {
+        LayerState::Done { total: None }
+    } else {
+        return None;
+    };
+    Some((id.to_string(), state))
+}
+
+/// Find a trailing `12.3MB/45.6MB` (or `12.3MiB / 45.6MiB`) size pair
+fn parse_byte_progress(s: &str) -> Option<(u64, u64)> {
+    let tokens: Vec<&str> = s.split_whitespace().collect();
+    let (current, total) = match tokens.as_slice() {
+        [.., a, "/", b] => (*a, *b),
+        [.., last] => last.split_once('/')?,
+        [] => return None,
+    };
+    Some((parse_size(current)?, parse_size(total)?))
+}
+
+fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs();
+    if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Writes output lines, collapsing pull progress into a single updating line
+struct ProgressPrinter {
+    tracker: PullTracker,
+    started: Option<Instant>,
+    frame: usize,
+    line_active: bool,
+}
+
+impl ProgressPrinter {
+    fn new() -> Self {
+        Self {
+            tracker: PullTracker::default(),
+            started: None,
+            frame: 0,
+            line_active: false,
+        }
+    }
+
+    fn handle(&mut self, line: &str) {
+        let mut stdout = std::io::stdout().lock();
+        if self.tracker.observe(line) {
+            let started = *self.started.get_or_insert_with(Instant::now);
+            self.frame = self.frame.wrapping_add(1);
+            let _ = write!(
+                stdout,
+                "\r\x1b[2K{} {}",
+                SPINNER[self.frame % SPINNER.len()],
+                self.tracker.summary(started.elapsed())
+            );
+            self.line_active = true;
+        } else {
+            if self.line_active {
+                let _ = write!(stdout, "\r\x1b[2K");
+                self.line_active = false;
+            }
+            let _ = writeln!(stdout, "{}", crate::color::plain(line));
+        }
+        let _ = stdout.flush();
+    }
+
+    fn finish(&mut self) {
+        if self.line_active {
+            let mut stdout = std::io::stdout().lock();
+            let _ = write!(stdout, "\r\x1b[2K");
+            let _ = stdout.flush();
+            self.line_active = false;
+        }
+    }
+}
+
+/// Run `f` with an output channel, rendering pull progress as a compact
+/// updating line when stdout is a TTY and printing every line otherwise.
+/// Like `with_stdout_stream`, all output is flushed before returning.
+pub(crate) async fn with_pull_progress<F, Fut, T>(f: F) -> T
+where
+    F: FnOnce(mpsc::UnboundedSender<String>) -> Fut,
+    Fut: std::future::Future<Output = T>,
+{
+    let tty = std::io::IsTerminal::is_terminal(&std::io::stdout());
+    let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+    let reader = tokio::spawn(async move {
+        let mut printer = ProgressPrinter::new();
+        while let Some(line) = rx.recv().await {
+            if tty {
+                printer.handle(&line);
+            } else {
+                println!("{}", crate::color::plain(&line));
+            }
+        }
+        printer.finish();
+    });
+    let result = f(tx).await;
+    let _ = reader.await;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_docker_layer_lines() {
+        assert_eq!(
+            parse_layer_line("a2abf6c4d29d: Pulling fs layer"),
+            Some(("a2abf6c4d29d".to_string(), LayerState::Pending))
+        );
+        assert_eq!(
+            parse_layer_line("a2abf6c4d29d: Downloading [=====>      ]  12.5MB/50MB"),
+            Some((
+                "a2abf6c4d29d".to_string(),
+                LayerState::Downloading {
+                    current: 12_500_000,
+                    total: Some(50_000_000),
+                }
+            ))
+        );
+        assert_eq!(
+            parse_layer_line("a2abf6c4d29d: Pull complete"),
+            Some(("a2abf6c4d29d".to_string(), LayerState::Done { total: None }))
+        );
+        assert_eq!(parse_layer_line("Step 1/5 : FROM alpine"), None);
+        assert_eq!(parse_layer_line("Digest: sha256:abc"), None);
+    }
+
+    #[test]
+    fn test_parse_podman_layer_lines() {
+        assert_eq!(
+            parse_layer_line("Copying blob sha256:59bf1c3509f3"),
+            Some(("59bf1c3509f3".to_string(), LayerState::Pending))
+        );
+        assert_eq!(
+            parse_layer_line("Copying blob 59bf1c3509f3 done"),
+            Some(("59bf1c3509f3".to_string(), LayerState::Done { total: None }))
+        );
+        assert_eq!(
+            parse_layer_line("Copying blob 59bf1c3509f3 [===>---] 1.0MiB / 4.0MiB"),
+            Some((
+                "59bf1c3509f3".to_string(),
+                LayerState::Downloading {
+                    current: 1024 * 1024,
+                    total: Some(4 * 1024 * 1024),
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_tracker_percent_and_eta_by_bytes() {
+        let mut tracker = PullTracker::default();
+        tracker.observe("aaaaaaaaaaaa: Downloading [==>   ] 25MB/100MB");
+        tracker.observe("bbbbbbbbbbbb: Downloading [=>    ] 0B/100MB");
+        assert_eq!(tracker.percent(), 13);
+        // 25MB in 10s leaves 175MB at 2.5MB/s
+        assert_eq!(
+            tracker.eta(Duration::from_secs(10)),
+            Some(Duration::from_secs(70))
+        );
+
+        // Completion keeps the total seen while downloading
+        tracker.observe("aaaaaaaaaaaa: Pull complete");
+        assert_eq!(tracker.percent(), 50);
+        assert_eq!(
+            tracker.summary(Duration::from_secs(10)),
+            "Pulling 1/2 layers 50% (100.0MB/200.0MB) ETA 10s"
+        );
+    }
+
+    #[test]
+    fn test_tracker_falls_back_to_layer_counts() {
+        let mut tracker = PullTracker::default();
+        for id in [
+            "aaaaaaaaaaaa",
+            "bbbbbbbbbbbb",
+            "cccccccccccc",
+            "dddddddddddd",
+        ] {
+            tracker.observe(&format!("{}: Pulling fs layer", id));
+        }
+        assert_eq!(tracker.eta(Duration::from_secs(5)), None);
+
+        tracker.observe("aaaaaaaaaaaa: Already exists");
+        assert_eq!(tracker.percent(), 25);
+        assert_eq!(
+            tracker.summary(Duration::from_secs(30)),
+            "Pulling 1/4 layers 25% ETA 1m30s"
+        );
+    }
+}
@@ -0,0 +1,338 @@
+//! `devc serve`: a line-delimited JSON protocol over a Unix socket
+//!
+//! Each request is one JSON object per line and gets exactly one response line:
+//!
+//! ```text
+//! -> {"id": 1, "method": "up", "params": {"container": "myproject"}}
+//! <- {"id": 1, "result": {"id": "…", "name": "myproject"}}
+//! <- {"id": 2, "error": "Container 'nope' not found"}
+//! ```
+//!
+//! Read methods: `list`, `stats`, `logs` (`container`, optional `tail`).
+//! Write methods: `up`, `down`, `stop` (`container`).
+//! `container` accepts anything `devc` takes on the command line: a name, an ID,
+//! or an unambiguous prefix of either.
+
+use anyhow::{anyhow, bail, Result};
+use devc_core::{ContainerManager, ContainerState};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+
+use super::find_container;
+
+/// Serve manager operations on `socket` until Ctrl+C
+#[cfg(unix)]
+pub async fn serve(manager: ContainerManager, socket: &Path) -> Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+    use std::sync::Arc;
+
+    if let Ok(metadata) = std::fs::symlink_metadata(socket) {
+        if !metadata.file_type().is_socket() {
+            bail!("{} exists and is not a socket", socket.display());
+        }
+        if tokio::net::UnixStream::connect(socket).await.is_ok() {
+            bail!(
+                "Another server is already listening on {}",
+                socket.display()
+            );
+        }
+        // Left behind by a server that didn't shut down cleanly
+        std::fs::remove_file(socket)?;
+    }
+
+    let listener = bind_private(socket)?;
+
+    let manager = Arc::new(manager);
+    println!("Serving on {} (Ctrl+C to stop)", socket.display());
+
+    let result = loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    let manager = Arc::clone(&manager);
+                    tokio::spawn(async move {
+                        if let Err(e) = handle_connection(&manager, stream).await {
+                            tracing::debug!("serve connection error: {}", e);
+                        }
+                    });
+                }
+                Err(e) => break Err(e.into()),
+            },
+            _ = tokio::signal::ctrl_c() => break Ok(()),
+        }
+    };
+
+    let _ = std::fs::remove_file(socket);
+    result
+}
+
+/// Listen on `socket`, readable and writable only by the current user
+///
+/// up/down/stop act on the user's containers, so nobody else may connect.
+/// The socket is bound inside a fresh 0700 directory and only moved to
+/// `socket` once it is 0600, so there is no window where it is open to others.
+#[cfg(unix)]
+fn bind_private(socket: &Path) -> Result<tokio::net::UnixListener> {
+    use anyhow::Context;
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    let parent = socket
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let file_name = socket
+        .file_name()
+        .ok_or_else(|| anyhow!("Invalid socket path {}", socket.display()))?;
+    let staging = parent.join(format!(".devc-serve-{}", std::process::id()));
+    std::fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&staging)
+        .with_context(|| format!("Failed to create {}", staging.display()))?;
+    let staged = staging.join(file_name);
+
+    let result = tokio::net::UnixListener::bind(&staged)
+        .with_context(|| format!("Failed to bind {}", socket.display()))
+        .and_then(|listener| {
+            std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o600))?;
+            std::fs::rename(&staged, socket)?;
+            Ok(listener)
+        });
+    let _ = std::fs::remove_file(&staged);
+    let _ = std::fs::remove_dir(&staging);
+    result
+}
+
+/// Serve manager operations on `socket` until Ctrl+C
+#[cfg(not(unix))]
+pub async fn serve(_manager: ContainerManager, _socket: &Path) -> Result<()> {
+    bail!("devc serve requires Unix domain sockets, which this platform does not support")
+}
+
+/// Answer requests on one connection until the client hangs up
+async fn handle_connection<S>(manager: &ContainerManager, stream: S) -> std::io::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let (read, mut write) = tokio::io::split(stream);
+    let mut lines = BufReader::new(read).lines();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = dispatch(manager, &line).await;
+        write
+            .write_all(format!("{}\n", response).as_bytes())
+            .await?;
+    }
+    Ok(())
+}
+
+/// Turn one request line into its response
+async fn dispatch(manager: &ContainerManager, line: &str) -> Value {
+    let request: Value = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(e) => return json!({ "id": null, "error": format!("Invalid request: {}", e) }),
+    };
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let method = request
+        .get("method")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+    match call(manager, method, &params).await {
+        Ok(result) => json!({ "id": id, "result": result }),
+        Err(e) => json!({ "id": id, "error": e.to_string() }),
+    }
+}
+
+async fn call(manager: &ContainerManager, method: &str, params: &Value) -> Result<Value> {
+    match method {
+        "list" => Ok(serde_json::to_value(manager.list().await?)?),
+        "stats" => {
+            let containers = manager.list().await?;
+            let mut by_status: BTreeMap<String, usize> = BTreeMap::new();
+            for c in &containers {
+                *by_status.entry(c.status.to_string()).or_default() += 1;
+            }
+            Ok(json!({
+                "total": containers.len(),
+                "by_status": by_status,
+                "provider": manager.provider_type().map(|p| p.to_string()),
+            }))
+        }
+        "logs" => {
+            let state = target(manager, params).await?;
+            let tail = params.get("tail").and_then(Value::as_u64);
+            Ok(json!(manager.logs(&state.id, tail).await?))
+        }
+        "up" => {
+            let state = target(manager, params).await?;
+            manager.up(&state.id).await?;
+            Ok(json!({ "id": state.id, "name": state.name }))
+        }
+        "down" => {
+            let state = target(manager, params).await?;
+            manager.down(&state.id).await?;
+            Ok(json!({ "id": state.id, "name": state.name }))
+        }
+        "stop" => {
+            let state = target(manager, params).await?;
+            manager.stop(&state.id).await?;
+            Ok(json!({ "id": state.id, "name": state.name }))
+        }
+        "" => bail!("Missing method"),
+        other => bail!("Unknown method '{}'", other),
+    }
+}
+
+/// Resolve `params.container` to a tracked container
+async fn target(manager: &ContainerManager, params: &Value) -> Result<ContainerState> {
+    let name = params
+        .get("container")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("Missing params.container"))?;
+    find_container(manager, name).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use devc_config::GlobalConfig;
+    use devc_core::test_support::MockProvider;
+    use devc_core::{DevcContainerStatus, StateStore};
+    use devc_provider::ProviderType;
+    use std::path::PathBuf;
+
+    fn test_manager() -> ContainerManager {
+        let mut state = StateStore::new();
+        let mut running = ContainerState::new(
+            "web".to_string(),
+            ProviderType::Docker,
+            PathBuf::from("/tmp/web/.devcontainer/devcontainer.json"),
+            PathBuf::from("/tmp/web"),
+        );
+        running.status = DevcContainerStatus::Running;
+        running.container_id = Some("mock_container_id".to_string());
+        state.add(running);
+        state.add(ContainerState::new(
+            "api".to_string(),
+            ProviderType::Docker,
+            PathBuf::from("/tmp/api/.devcontainer/devcontainer.json"),
+            PathBuf::from("/tmp/api"),
+        ));
+        ContainerManager::new_for_testing(
+            Box::new(MockProvider::new(ProviderType::Docker)),
+            GlobalConfig::default(),
+            state,
+        )
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_bind_private_socket_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("devc.sock");
+        let _listener = bind_private(&socket).unwrap();
+
+        let mode = std::fs::metadata(&socket).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        tokio::net::UnixStream::connect(&socket).await.unwrap();
+        // The staging directory is gone
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_serve_refuses_to_replace_a_regular_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let notes = dir.path().join("notes.txt");
+        std::fs::write(&notes, "keep me").unwrap();
+
+        let err = serve(test_manager(), &notes).await.unwrap_err();
+        assert!(err.to_string().contains("is not a socket"), "{}", err);
+        assert_eq!(std::fs::read_to_string(&notes).unwrap(), "keep me");
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_read_methods() {
+        let manager = test_manager();
+
+        let response = dispatch(&manager, r#"{"id": 1, "method": "list"}"#).await;
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"].as_array().unwrap().len(), 2);
+
+        let response = dispatch(&manager, r#"{"id": "s", "method": "stats"}"#).await;
+        assert_eq!(response["id"], "s");
+        assert_eq!(response["result"]["total"], 2);
+        assert_eq!(response["result"]["by_status"]["running"], 1);
+        assert_eq!(response["result"]["provider"], "docker");
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_stop() {
+        let manager = test_manager();
+        let response = dispatch(
+            &manager,
+            r#"{"id": 2, "method": "stop", "params": {"container": "web"}}"#,
+        )
+        .await;
+        assert_eq!(response["result"]["name"], "web", "{}", response);
+
+        let web = manager.get_by_name("web").await.unwrap().unwrap();
+        assert_eq!(web.status, DevcContainerStatus::Stopped);
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_errors() {
+        let manager = test_manager();
+
+        let response = dispatch(&manager, "not json").await;
+        assert!(response["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("Invalid request"));
+
+        let response = dispatch(&manager, r#"{"id": 3, "method": "reboot"}"#).await;
+        assert_eq!(response["id"], 3);
+        assert_eq!(response["error"], "Unknown method 'reboot'");
+
+        let response = dispatch(&manager, r#"{"id": 4, "method": "up"}"#).await;
+        assert_eq!(response["error"], "Missing params.container");
+
+        let response = dispatch(
+            &manager,
+            r#"{"id": 5, "method": "logs", "params": {"container": "nope"}}"#,
+        )
+        .await;
+        assert_eq!(response["error"], "Container 'nope' not found");
+    }
+
+    #[tokio::test]
+    async fn test_connection_answers_each_line() {
+        let manager = test_manager();
+        let (client, server) = tokio::io::duplex(4096);
+        let server = tokio::spawn(async move { handle_connection(&manager, server).await });
+
+        let (read, mut write) = tokio::io::split(client);
+        write
+            .write_all(b"{\"id\": 1, \"method\": \"stats\"}\n\n{\"id\": 2, \"method\": \"list\"}\n")
+            .await
+            .unwrap();
+        write.shutdown().await.unwrap();
+        drop(write);
+
+        let mut lines = BufReader::new(read).lines();
+        let mut ids = Vec::new();
+        while let Some(line) = lines.next_line().await.unwrap() {
+            let response: Value = serde_json::from_str(&line).unwrap();
+            ids.push(response["id"].as_i64().unwrap());
+        }
+        assert_eq!(ids, vec![1, 2]);
+        server.await.unwrap().unwrap();
+    }
+}
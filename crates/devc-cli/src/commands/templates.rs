@@ -0,0 +1,138 @@
+//! Built-in devcontainer.json starters for `devc init --template`
+
+use anyhow::{anyhow, bail, Result};
+use std::path::{Path, PathBuf};
+
+/// A starter devcontainer.json
+pub struct Template {
+    pub name: &'static str,
+    pub description: &'static str,
+    config: &'static str,
+}
+
+/// Templates available to `devc init --template`
+pub const TEMPLATES: &[Template] = &[
+    Template {
+        name: "base-ubuntu",
+        description: "Ubuntu with common tools (git, curl, zsh)",
+        config: r#"{
+  "image": "mcr.microsoft.com/devcontainers/base:ubuntu",
+  "remoteUser": "vscode"
+}
+"#,
+    },
+    Template {
+        name: "node",
+        description: "Node.js LTS with npm and yarn",
+        config: r#"{
+  "image": "mcr.microsoft.com/devcontainers/javascript-node:22",
+  "forwardPorts": [3000],
+  "remoteUser": "node"
+}
+"#,
+    },
+    Template {
+        name: "python",
+        description: "Python 3 with pip",
+        config: r#"{
+  "image": "mcr.microsoft.com/devcontainers/python:3.12",
+  "remoteUser": "vscode"
+}
+"#,
+    },
+    Template {
+        name: "rust",
+        description: "Rust stable with cargo, clippy and rustfmt",
+        config: r#"{
+  "image": "mcr.microsoft.com/devcontainers/rust:1",
+  "remoteUser": "vscode"
+}
+"#,
+    },
+];
+
+/// Print the built-in templates
+pub fn list_templates() {
+    for template in TEMPLATES {
+        println!("{:<12}  {}", template.name, template.description);
+    }
+}
+
+/// Write the named template to `.devcontainer/devcontainer.json` under `dir`,
+/// or over the existing top-level config (either location) when `force` is
+/// set.
+///
+/// The templates leave `name` out, so the container is named after `dir`.
+pub fn write_template(dir: &Path, name: &str, force: bool) -> Result<PathBuf> {
+    let template = TEMPLATES
+        .iter()
+        .find(|t| t.name == name)
+        .ok_or_else(|| anyhow!("Unknown template '{}' (see 'devc init --list')", name))?;
+
+    let existing = [
+        dir.join(".devcontainer/devcontainer.json"),
+        dir.join(".devcontainer.json"),
+    ]
+    .into_iter()
+    .find(|p| p.exists());
+    let path = match existing {
+        Some(existing) if !force => bail!(
+            "{} already exists; pass --force to overwrite it",
+            existing.display()
+        ),
+        Some(existing) => existing,
+        None => {
+            std::fs::create_dir_all(dir.join(".devcontainer"))?;
+            dir.join(".devcontainer/devcontainer.json")
+        }
+    };
+    std::fs::write(&path, template.config)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_templates_are_valid_configs() {
+        for template in TEMPLATES {
+            let config: devc_config::DevContainerConfig = serde_json::from_str(template.config)
+                .unwrap_or_else(|e| panic!("{} does not parse: {}", template.name, e));
+            assert!(config.image.is_some(), "{} has no image", template.name);
+            // A fixed name would clash between projects made from one template
+            assert!(config.name.is_none(), "{} sets a name", template.name);
+        }
+    }
+
+    #[test]
+    fn test_write_template_requires_force_to_overwrite() {
+        let tmp = tempfile::tempdir().unwrap();
+
+        let path = write_template(tmp.path(), "rust", false).unwrap();
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("devcontainers/rust"));
+
+        let err = write_template(tmp.path(), "node", false).unwrap_err();
+        assert!(err.to_string().contains("--force"), "{}", err);
+
+        write_template(tmp.path(), "node", true).unwrap();
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("javascript-node"));
+
+        assert!(write_template(tmp.path(), "cobol", true).is_err());
+    }
+
+    #[test]
+    fn test_write_template_force_overwrites_root_config() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join(".devcontainer.json");
+        std::fs::write(&root, r#"{"image": "old"}"#).unwrap();
+
+        assert_eq!(write_template(tmp.path(), "python", true).unwrap(), root);
+        assert!(std::fs::read_to_string(&root).unwrap().contains("python"));
+        assert!(!tmp.path().join(".devcontainer").exists());
+    }
+}
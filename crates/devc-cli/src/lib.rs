@@ -1,4 +1,5 @@
 //! devc CLI library — exposes command modules for integration testing.
 
+pub mod color;
 pub mod commands;
 pub mod selector;
@@ -4,17 +4,20 @@
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
+mod color;
 mod commands;
 mod selector;
 
+use anyhow::Context;
 use clap::{Parser, Subcommand};
 use devc_config::GlobalConfig;
 use devc_core::ContainerManager;
 use devc_provider::{
     create_default_provider, create_provider, detect_available_providers, ProviderType,
 };
-use dialoguer::{theme::ColorfulTheme, Select};
+use dialoguer::Select;
 use selector::{select_container, SelectionContext};
+use std::path::PathBuf;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
 #[derive(Parser)]
@@ -25,10 +28,33 @@ struct Cli {
     #[arg(short, long, global = true)]
     verbose: bool,
 
+    /// Disable colored output (also honors the NO_COLOR environment variable)
+    #[arg(long, global = true)]
+    no_color: bool,
+
     /// Override default provider (docker or podman)
     #[arg(long, global = true, value_parser = ["docker", "podman"])]
     provider: Option<String>,
 
+    /// Image pull policy for image-based containers (overrides defaults.pull_policy)
+    #[arg(long, global = true, value_parser = ["always", "missing", "never"])]
+    pull: Option<String>,
+
+    /// Fail when devcontainer.json has keys or values the schema doesn't allow
+    /// (they are only warnings otherwise)
+    #[arg(long, global = true)]
+    strict: bool,
+
+    /// Read and save the global config at this path instead of the default
+    /// (also honors the DEVC_CONFIG environment variable)
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Overlay the named [profiles.<name>] table onto the global config
+    /// (also honors the DEVC_PROFILE environment variable)
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -42,6 +68,13 @@ enum Commands {
         /// Execute as root user instead of the default container user
         #[arg(long)]
         root: bool,
+        /// Working directory inside the container (defaults to workspaceFolder)
+        #[arg(short = 'w', long, value_name = "PATH")]
+        workdir: Option<String>,
+        /// Require this mount (type=bind,src=...,dst=...) to already be in the
+        /// container; mounts cannot be added to a running container (repeatable)
+        #[arg(long = "mount", value_name = "SPEC", value_parser = parse_mount_arg)]
+        mounts: Vec<devc_provider::MountConfig>,
         /// Command to execute
         #[arg(trailing_var_arg = true)]
         cmd: Vec<String>,
@@ -51,11 +84,36 @@ enum Commands {
     Shell {
         /// Container name or ID (interactive selection if not specified)
         container: Option<String>,
+        /// Compose service to open the shell in (defaults to the primary service)
+        #[arg(long)]
+        service: Option<String>,
+        /// Script to run in a login shell with a TTY, then exit with its status.
+        /// Unlike `exec`, profile files are sourced first
+        #[arg(short = 'c', long, conflicts_with = "cmd")]
+        command: Option<String>,
         /// Command to run in the shell (uses login shell PATH)
         #[arg(trailing_var_arg = true)]
         cmd: Vec<String>,
     },
 
+    /// Run postAttachCommand and open a shell in a running container
+    Attach {
+        /// Container name or ID (interactive selection if not specified)
+        container: Option<String>,
+    },
+
+    /// Copy files or directories between the host and a container
+    /// (`dir` copies the directory itself, `dir/.` only its contents)
+    Cp {
+        /// Source: a host path or CONTAINER:PATH
+        src: String,
+        /// Destination: a host path or CONTAINER:PATH
+        dest: String,
+        /// Stream a tar archive through stdin/stdout; use `-` as the host side
+        #[arg(long)]
+        archive: bool,
+    },
+
     /// Build a container
     Build {
         /// Container name or ID (optional, uses current directory if not specified)
@@ -63,6 +121,12 @@ enum Commands {
         /// Don't use cache when building the image
         #[arg(long)]
         no_cache: bool,
+        /// Build arg for this build, overriding devcontainer.json (repeatable)
+        #[arg(long = "build-arg", value_name = "KEY=VALUE", value_parser = parse_build_arg)]
+        build_args: Vec<(String, String)>,
+        /// Dockerfile stage to build, overriding devcontainer.json `build.target`
+        #[arg(long, value_name = "STAGE", value_parser = parse_build_target)]
+        target: Option<String>,
     },
 
     /// Start a container
@@ -75,15 +139,69 @@ enum Commands {
     Stop {
         /// Container name or ID (interactive selection if not specified)
         container: Option<String>,
+        /// Stop every running container
+        #[arg(long, conflicts_with = "container")]
+        all: bool,
+        /// Skip confirmation prompt (with --all)
+        #[arg(short = 'y', long, requires = "all")]
+        yes: bool,
+    },
+
+    /// Pause a running container, freezing its processes
+    Pause {
+        /// Container name or ID (interactive selection if not specified)
+        container: Option<String>,
+    },
+
+    /// Resume a paused container
+    Unpause {
+        /// Container name or ID (interactive selection if not specified)
+        container: Option<String>,
+    },
+
+    /// Snapshot a container's filesystem to an image (volumes are not included)
+    Freeze {
+        /// Container name or ID (interactive selection if not specified)
+        container: Option<String>,
+    },
+
+    /// Create a new container from an image made by `devc freeze`
+    Thaw {
+        /// Name for the new container
+        name: String,
+        /// Image tag printed by `devc freeze`
+        image: String,
     },
 
-    /// Remove a container
+    /// Remove a container (its built image is kept for quick recreation)
     Rm {
         /// Container name or ID (interactive selection if not specified)
         container: Option<String>,
         /// Force removal even if running
         #[arg(short, long)]
         force: bool,
+        /// Also remove the container's images unless another container uses them
+        #[arg(long)]
+        rmi: bool,
+        /// Keep the container's images (the default; for scripts that want to
+        /// say so explicitly)
+        #[arg(long, conflicts_with = "rmi")]
+        keep_image: bool,
+    },
+
+    /// Stop tracking a container without touching the runtime container
+    Forget {
+        /// Container name or ID (interactive selection if not specified)
+        container: Option<String>,
+    },
+
+    /// Pin a container to a provider so a different default never moves it
+    PinProvider {
+        /// Container name or ID
+        container: String,
+        /// Provider to pin it to
+        #[arg(value_parser = ["docker", "podman"])]
+        provider: String,
     },
 
     /// List containers
@@ -94,21 +212,111 @@ enum Commands {
         /// Sync status with container runtimes
         #[arg(long)]
         sync: bool,
+        /// Maximum number of containers to inspect at once when syncing
+        #[arg(long, value_name = "N", default_value_t = 4, requires = "sync")]
+        parallel: usize,
+        /// Only show containers with this label (KEY=VALUE or KEY; repeatable)
+        #[arg(long = "label", value_name = "KEY[=VALUE]")]
+        labels: Vec<String>,
+    },
+
+    /// List running containers (name, uptime, ports, image)
+    Ps {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show disk used by each container's writable layer and anonymous volumes
+    Du {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show a container's logs
+    Logs {
+        /// Container names or IDs; several are interleaved with a name prefix
+        /// (interactive selection if not specified)
+        containers: Vec<String>,
+        /// Compose service to show logs for (defaults to the primary service)
+        #[arg(long)]
+        service: Option<String>,
+        /// Only show this many lines from the end; --grep without it searches
+        /// the full history [default: 200]
+        #[arg(long)]
+        tail: Option<u64>,
+        /// Show the full log history instead of the last --tail lines
+        #[arg(long, conflicts_with = "tail")]
+        no_tail: bool,
+        /// Keep streaming new output
+        #[arg(short, long)]
+        follow: bool,
+        /// Only show lines matching this regular expression
+        #[arg(long, value_name = "PATTERN")]
+        grep: Option<String>,
+        /// Show lines that do not match --grep instead
+        #[arg(long, requires = "grep")]
+        invert: bool,
+        /// Show the logs of a container that has exited, with its exit code
+        #[arg(long, conflicts_with_all = ["follow", "service"])]
+        previous: bool,
+        /// Print one JSON object per line: {"ts", "stream", "message"}
+        #[arg(long, conflicts_with_all = ["previous", "service"])]
+        json: bool,
     },
 
     /// Initialize a new dev container from current directory
-    Init,
+    Init {
+        /// Write a starter .devcontainer/devcontainer.json from a built-in template first
+        #[arg(long, value_name = "NAME", value_parser = clap::builder::PossibleValuesParser::new(commands::TEMPLATES.iter().map(|t| t.name)))]
+        template: Option<String>,
+        /// List the built-in templates
+        #[arg(long, conflicts_with = "template")]
+        list: bool,
+        /// Overwrite an existing devcontainer.json with the template
+        #[arg(long, requires = "template")]
+        force: bool,
+    },
 
     /// Build, create, and start a container
     Up {
         /// Container name or ID (optional, uses current directory if not specified)
         container: Option<String>,
+        /// Build arg used if the image needs building, overriding devcontainer.json (repeatable)
+        #[arg(long = "build-arg", value_name = "KEY=VALUE", value_parser = parse_build_arg)]
+        build_args: Vec<(String, String)>,
+        /// Extra mount (type=bind,src=...,dst=...) added when the container is created (repeatable)
+        #[arg(long = "mount", value_name = "SPEC", value_parser = parse_mount_arg)]
+        mounts: Vec<devc_provider::MountConfig>,
+        /// Extra label (KEY=VALUE) set on the container when it is created (repeatable)
+        #[arg(long = "label", value_name = "KEY=VALUE", value_parser = parse_label_arg)]
+        labels: Vec<(String, String)>,
+        /// Return once the container is running and finish the lifecycle
+        /// commands in the background (logged under the devc data directory)
+        #[arg(short = 'd', long, conflicts_with = "rebuild")]
+        detach: bool,
+        /// Tear down the container and build it again, as `devc rebuild` does
+        #[arg(long)]
+        rebuild: bool,
+        /// Build without using the cache (with --rebuild)
+        #[arg(long, requires = "rebuild")]
+        no_cache: bool,
+        /// Skip the rebuild confirmation prompt
+        #[arg(short = 'y', long, requires = "rebuild")]
+        yes: bool,
     },
 
     /// Stop and remove a container
     Down {
         /// Container name or ID (interactive selection if not specified)
         container: Option<String>,
+        /// Bring down every running or created container
+        #[arg(long, conflicts_with = "container")]
+        all: bool,
+        /// Skip confirmation prompt (with --all)
+        #[arg(short = 'y', long, requires = "all")]
+        yes: bool,
     },
 
     /// Resize container PTY (fixes nested tmux after zoom)
@@ -124,12 +332,22 @@ enum Commands {
     },
 
     /// Show or edit global configuration
+    #[command(args_conflicts_with_subcommands = true)]
     Config {
+        #[command(subcommand)]
+        command: Option<ConfigCommands>,
         /// Open config in editor
         #[arg(short, long)]
         edit: bool,
     },
 
+    /// Show version information
+    Version {
+        /// Also show runtime, compose, and spec versions (useful for bug reports)
+        #[arg(long)]
+        full: bool,
+    },
+
     /// Adopt an existing devcontainer into devc management
     Adopt {
         /// Container name or ID (interactive selection if not specified)
@@ -142,22 +360,72 @@ enum Commands {
         container: Option<String>,
     },
 
-    /// Rebuild a container (destroy and rebuild, optionally on new provider)
+    /// Rebuild a container (destroy and rebuild; with --provider, migrate to that provider unless pinned)
     Rebuild {
         /// Container name or ID (interactive selection if not specified)
         container: Option<String>,
         /// Force rebuild without using cache
         #[arg(long)]
         no_cache: bool,
+        /// Build arg for this rebuild, overriding devcontainer.json (repeatable)
+        #[arg(long = "build-arg", value_name = "KEY=VALUE", value_parser = parse_build_arg)]
+        build_args: Vec<(String, String)>,
         /// Skip confirmation prompt
         #[arg(short = 'y', long)]
         yes: bool,
+        /// Open a shell in the new container once the rebuild finishes
+        #[arg(long)]
+        attach: bool,
+    },
+
+    /// Re-push agent configs into a running container without rebuilding
+    SyncAgents {
+        /// Container name or ID (interactive selection if not specified)
+        container: Option<String>,
+        /// Only sync these agents (comma-separated: codex,claude,cursor,gemini)
+        #[arg(long, value_delimiter = ',', value_name = "AGENT")]
+        only: Vec<String>,
+        /// Sync even agents that are disabled in the config
+        #[arg(long)]
+        force: bool,
     },
 
     /// Agent injection diagnostics and sync
+    ///
+    /// Without a subcommand, shows per-agent status inside a running container.
+    #[command(args_conflicts_with_subcommands = true)]
     Agents {
         #[command(subcommand)]
-        command: AgentCommands,
+        command: Option<AgentCommands>,
+        /// Container name or ID (interactive selection if not specified)
+        // A flag, so containers named like a subcommand can be addressed
+        #[arg(long)]
+        container: Option<String>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Clone a repository and bring up its dev container
+    ///
+    /// Offers to create a default devcontainer.json if the repository has none.
+    Clone {
+        /// Git URL of the repository
+        url: String,
+        /// Directory to clone into (defaults to the repository name)
+        dir: Option<PathBuf>,
+        /// Clone into this named volume instead of a host directory
+        #[arg(long, conflicts_with = "dir")]
+        volume: Option<String>,
+    },
+
+    /// Serve container operations as line-delimited JSON on a Unix socket
+    ///
+    /// Methods: list, stats, logs, up, down, stop. Runs until Ctrl+C.
+    Serve {
+        /// Path of the Unix socket to listen on
+        #[arg(long)]
+        socket: PathBuf,
     },
 }
 
@@ -175,20 +443,53 @@ enum AgentCommands {
     },
 }
 
-#[tokio::main]
-async fn main() {
-    if let Err(e) = run().await {
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// List the profiles defined in the global config and validate them
+    Profiles,
+    /// Check devcontainer.json without building: parse errors, unknown keys,
+    /// missing Dockerfile/compose files, and unresolved variables
+    Validate {
+        /// devcontainer.json file, or a workspace to check every config in
+        /// (defaults to the current directory)
+        path: Option<std::path::PathBuf>,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+    // The environment is only safe to change while no other thread can be
+    // reading it, so it is set up before the runtime starts its workers
+    let result =
+        export_config_env(&cli).and_then(|()| tokio::runtime::Runtime::new()?.block_on(run(cli)));
+    if let Err(e) = result {
         eprintln!("Error: {}", e);
         std::process::exit(1);
     }
 }
 
-async fn run() -> anyhow::Result<()> {
-    let cli = Cli::parse();
+/// Point every GlobalConfig::load() in this process (and in processes it
+/// spawns) at the `--config` file and `--profile` through the environment
+fn export_config_env(cli: &Cli) -> anyhow::Result<()> {
+    if let Some(path) = &cli.config {
+        let path = std::fs::canonicalize(path)
+            .with_context(|| format!("Cannot read config file {}", path.display()))?;
+        std::env::set_var(devc_config::CONFIG_ENV_VAR, path);
+    }
+    if let Some(profile) = &cli.profile {
+        std::env::set_var(devc_config::PROFILE_ENV_VAR, profile);
+    }
+    Ok(())
+}
 
+async fn run(cli: Cli) -> anyhow::Result<()> {
     // Check if we're launching the TUI (no command specified)
     let is_tui_mode = cli.command.is_none();
 
+    if cli.no_color {
+        color::disable();
+    }
+
     // Initialize logging only for CLI commands, not for TUI
     // The TUI handles its own tracing suppression, but the global subscriber
     // would still print on tokio worker threads
@@ -201,16 +502,54 @@ async fn run() -> anyhow::Result<()> {
 
         tracing_subscriber::registry()
             .with(filter)
-            .with(tracing_subscriber::fmt::layer().with_target(false))
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .with_target(false)
+                    .with_ansi(color::enabled()),
+            )
             .init();
     }
 
-    // Load global config
-    let mut config = GlobalConfig::load().unwrap_or_default();
+    // Load global config. A broken config file falls back to defaults, but a
+    // mistyped profile name or an explicitly chosen file that can't be read
+    // is an error rather than a silent no-op.
+    let mut config = match GlobalConfig::load() {
+        Ok(config) => config,
+        Err(e @ devc_config::ConfigError::UnknownProfile { .. }) => return Err(e.into()),
+        Err(e) if GlobalConfig::explicit_config_path().is_some() => return Err(e.into()),
+        Err(_) => GlobalConfig::default(),
+    };
 
     // Handle config command separately (doesn't need provider)
-    if let Some(Commands::Config { edit }) = &cli.command {
-        commands::config(*edit).await?;
+    if let Some(Commands::Config { command, edit }) = &cli.command {
+        match command {
+            Some(ConfigCommands::Profiles) => commands::config_profiles()?,
+            Some(ConfigCommands::Validate { path }) => {
+                commands::config_validate(path.as_deref(), cli.strict)?
+            }
+            None => commands::config(*edit).await?,
+        }
+        return Ok(());
+    }
+
+    // clap restricts --provider to docker/podman, so parsing can't fail
+    let requested_provider: Option<ProviderType> =
+        cli.provider.as_deref().and_then(|p| p.parse().ok());
+
+    // Listing templates needs neither a provider nor a workspace
+    if let Some(Commands::Init { list: true, .. }) = &cli.command {
+        commands::list_templates();
+        return Ok(());
+    }
+
+    // Handle version command separately (reports on the provider rather than requiring it)
+    if let Some(Commands::Version { full }) = &cli.command {
+        let provider = if *full {
+            Some(create_selected_provider(requested_provider, &config).await)
+        } else {
+            None
+        };
+        commands::version(provider).await?;
         return Ok(());
     }
 
@@ -222,7 +561,9 @@ async fn run() -> anyhow::Result<()> {
                 ProviderType::Docker => "docker".to_string(),
                 ProviderType::Podman => "podman".to_string(),
             };
-            if let Err(e) = config.save() {
+            let provider = config.defaults.provider.clone();
+            if let Err(e) = GlobalConfig::update_atomically(|cfg| cfg.defaults.provider = provider)
+            {
                 eprintln!("Warning: Could not save provider selection: {}", e);
             } else {
                 eprintln!("Provider '{}' saved to config", config.defaults.provider);
@@ -231,15 +572,15 @@ async fn run() -> anyhow::Result<()> {
     }
 
     // Try to create a provider
-    let provider_result = match cli.provider.as_deref() {
-        Some("docker") => create_provider(ProviderType::Docker, &config).await,
-        Some("podman") => create_provider(ProviderType::Podman, &config).await,
-        _ => create_default_provider(&config).await,
-    };
+    let provider_result = create_selected_provider(requested_provider, &config).await;
 
     // Handle TUI launch specially - allow starting in disconnected mode
     match cli.command {
         None => {
+            // The TUI syncs its own config into the manager, which would drop the override
+            if cli.pull.is_some() {
+                anyhow::bail!("--pull only applies to CLI commands, not the TUI");
+            }
             // Launch TUI - create disconnected manager if provider fails
             let manager = match provider_result {
                 Ok(provider) => ContainerManager::new(provider).await?,
@@ -249,12 +590,31 @@ async fn run() -> anyhow::Result<()> {
                 }
             };
             let workspace_dir = std::env::current_dir().ok();
-            devc_tui::run(manager, workspace_dir.as_deref()).await?;
+            devc_tui::run(manager, workspace_dir.as_deref(), requested_provider).await?;
         }
         Some(cmd) => {
             // CLI commands require a working provider
             let provider = provider_result?;
-            let manager = ContainerManager::new(provider).await?;
+            let mut manager = ContainerManager::new(provider).await?;
+            if let Some(ref pull) = cli.pull {
+                let mut global = manager.global_config().clone();
+                global.defaults.pull_policy = Some(pull.parse().map_err(anyhow::Error::msg)?);
+                manager.update_global_config(global);
+            }
+            manager.set_strict_config(cli.strict);
+            if let Commands::Build { build_args, .. }
+            | Commands::Up { build_args, .. }
+            | Commands::Rebuild { build_args, .. } = &cmd
+            {
+                manager.set_build_arg_overrides(build_args.iter().cloned().collect());
+            }
+            if let Commands::Build { target, .. } = &cmd {
+                manager.set_target_override(target.clone());
+            }
+            if let Commands::Up { mounts, labels, .. } = &cmd {
+                manager.set_mount_overrides(mounts.clone());
+                manager.set_label_overrides(labels.iter().cloned().collect());
+            }
 
             // Get containers for selection (only when needed)
             let get_containers = || async { manager.list().await };
@@ -263,6 +623,8 @@ async fn run() -> anyhow::Result<()> {
                 Commands::Exec {
                     container,
                     root,
+                    workdir,
+                    mounts,
                     cmd,
                 } => {
                     let name = match container {
@@ -277,16 +639,21 @@ async fn run() -> anyhow::Result<()> {
                         }
                     };
                     let cmd = if cmd.is_empty() {
-                        let input: String = dialoguer::Input::with_theme(&ColorfulTheme::default())
+                        let input: String = dialoguer::Input::with_theme(&*color::theme())
                             .with_prompt("Command to execute")
                             .interact_text()?;
                         shell_words::split(&input)?
                     } else {
                         cmd
                     };
-                    commands::exec(&manager, &name, cmd, root).await?;
+                    commands::exec(&manager, &name, cmd, root, workdir.as_deref(), &mounts).await?;
                 }
-                Commands::Shell { container, cmd } => {
+                Commands::Shell {
+                    container,
+                    service,
+                    command,
+                    cmd,
+                } => {
                     let name = match container {
                         Some(name) => name,
                         None => {
@@ -298,11 +665,31 @@ async fn run() -> anyhow::Result<()> {
                             )?
                         }
                     };
-                    commands::shell(&manager, &name, cmd).await?;
+                    let script =
+                        command.or_else(|| (!cmd.is_empty()).then(|| shell_words::join(&cmd)));
+                    commands::shell(&manager, &name, service.as_deref(), script.as_deref()).await?;
+                }
+                Commands::Attach { container } => {
+                    let name = match container {
+                        Some(name) => name,
+                        None => {
+                            let containers = get_containers().await?;
+                            select_container(
+                                &containers,
+                                SelectionContext::Running,
+                                "Select container to attach to:",
+                            )?
+                        }
+                    };
+                    commands::attach(&manager, &name).await?;
+                }
+                Commands::Cp { src, dest, archive } => {
+                    commands::copy(&manager, &src, &dest, archive).await?;
                 }
                 Commands::Build {
                     container,
                     no_cache,
+                    ..
                 } => {
                     commands::build(&manager, container, no_cache).await?;
                 }
@@ -320,7 +707,10 @@ async fn run() -> anyhow::Result<()> {
                     };
                     commands::start(&manager, &name).await?;
                 }
-                Commands::Stop { container } => {
+                Commands::Stop { all: true, yes, .. } => {
+                    commands::stop_all(&manager, yes).await?;
+                }
+                Commands::Stop { container, .. } => {
                     let name = match container {
                         Some(name) => name,
                         None => {
@@ -334,7 +724,57 @@ async fn run() -> anyhow::Result<()> {
                     };
                     commands::stop(&manager, &name).await?;
                 }
-                Commands::Rm { container, force } => {
+                Commands::Pause { container } => {
+                    let name = match container {
+                        Some(name) => name,
+                        None => {
+                            let containers = get_containers().await?;
+                            select_container(
+                                &containers,
+                                SelectionContext::Running,
+                                "Select container to pause:",
+                            )?
+                        }
+                    };
+                    commands::pause(&manager, &name).await?;
+                }
+                Commands::Unpause { container } => {
+                    let name = match container {
+                        Some(name) => name,
+                        None => {
+                            let containers = get_containers().await?;
+                            select_container(
+                                &containers,
+                                SelectionContext::Paused,
+                                "Select container to unpause:",
+                            )?
+                        }
+                    };
+                    commands::unpause(&manager, &name).await?;
+                }
+                Commands::Freeze { container } => {
+                    let name = match container {
+                        Some(name) => name,
+                        None => {
+                            let containers = get_containers().await?;
+                            select_container(
+                                &containers,
+                                SelectionContext::Running,
+                                "Select container to freeze:",
+                            )?
+                        }
+                    };
+                    commands::freeze(&manager, &name).await?;
+                }
+                Commands::Thaw { name, image } => {
+                    commands::thaw(&manager, &name, &image).await?;
+                }
+                Commands::Rm {
+                    container,
+                    force,
+                    rmi,
+                    keep_image: _,
+                } => {
                     let name = match container {
                         Some(name) => name,
                         None => {
@@ -346,15 +786,95 @@ async fn run() -> anyhow::Result<()> {
                             )?
                         }
                     };
-                    commands::remove(&manager, &name, force).await?;
+                    commands::remove(&manager, &name, force, rmi).await?;
+                }
+                Commands::Forget { container } => {
+                    let name = match container {
+                        Some(name) => name,
+                        None => {
+                            let containers = get_containers().await?;
+                            select_container(
+                                &containers,
+                                SelectionContext::Any,
+                                "Select container to forget:",
+                            )?
+                        }
+                    };
+                    commands::forget(&manager, &name).await?;
+                }
+                Commands::PinProvider {
+                    container,
+                    provider,
+                } => {
+                    // clap restricts the provider to docker/podman, so parsing can't fail
+                    let provider: ProviderType = provider.parse().map_err(anyhow::Error::msg)?;
+                    commands::pin_provider(&manager, &container, provider).await?;
+                }
+                Commands::List {
+                    discover,
+                    sync,
+                    parallel,
+                    labels,
+                } => {
+                    commands::list(&manager, discover, sync, parallel, &labels).await?;
+                }
+                Commands::Ps { json } => {
+                    commands::ps(&manager, json).await?;
                 }
-                Commands::List { discover, sync } => {
-                    commands::list(&manager, discover, sync).await?;
+                Commands::Du { json } => {
+                    commands::du(&manager, json).await?;
+                }
+                Commands::Logs {
+                    containers,
+                    service,
+                    tail,
+                    no_tail,
+                    follow,
+                    grep,
+                    invert,
+                    previous,
+                    json,
+                } => {
+                    let names = if containers.is_empty() {
+                        let containers = get_containers().await?;
+                        vec![select_container(
+                            &containers,
+                            SelectionContext::Any,
+                            "Select container to show logs for:",
+                        )?]
+                    } else {
+                        containers
+                    };
+                    commands::logs(
+                        &manager,
+                        &names,
+                        service.as_deref(),
+                        match tail {
+                            // A match further back than the default tail shouldn't be missed
+                            None if no_tail || grep.is_some() => None,
+                            tail => Some(tail.unwrap_or(devc_core::DEFAULT_LOG_TAIL)),
+                        },
+                        follow,
+                        grep.as_deref(),
+                        invert,
+                        previous,
+                        json,
+                    )
+                    .await?;
                 }
-                Commands::Init => {
-                    commands::init(&manager).await?;
+                Commands::Init {
+                    template, force, ..
+                } => {
+                    commands::init(&manager, template.as_deref(), force).await?;
                 }
-                Commands::Up { container } => {
+                Commands::Up {
+                    container,
+                    detach,
+                    rebuild,
+                    no_cache,
+                    yes,
+                    ..
+                } => {
                     let container = match container {
                         Some(name) => Some(name),
                         None => {
@@ -379,9 +899,27 @@ async fn run() -> anyhow::Result<()> {
                             }
                         }
                     };
-                    commands::up(&manager, container).await?;
+                    // The background process reconnects the same way
+                    let mut global_args = Vec::new();
+                    if let Some(ref provider) = cli.provider {
+                        global_args.extend(["--provider".to_string(), provider.clone()]);
+                    }
+                    if cli.verbose {
+                        global_args.push("--verbose".to_string());
+                    }
+                    let rebuild = rebuild.then_some(commands::UpRebuild { no_cache, yes });
+                    commands::up(
+                        &manager,
+                        container,
+                        detach.then_some(&global_args[..]),
+                        rebuild,
+                    )
+                    .await?;
+                }
+                Commands::Down { all: true, yes, .. } => {
+                    commands::down_all(&manager, yes).await?;
                 }
-                Commands::Down { container } => {
+                Commands::Down { container, .. } => {
                     let name = match container {
                         Some(name) => name,
                         None => {
@@ -403,6 +941,7 @@ async fn run() -> anyhow::Result<()> {
                     commands::resize(&manager, container, cols, rows).await?;
                 }
                 Commands::Config { .. } => unreachable!(), // Handled above
+                Commands::Version { .. } => unreachable!(), // Handled above
                 Commands::Adopt { container } => {
                     commands::adopt(&manager, container).await?;
                 }
@@ -413,6 +952,8 @@ async fn run() -> anyhow::Result<()> {
                     container,
                     no_cache,
                     yes,
+                    attach,
+                    ..
                 } => {
                     let name = match container {
                         Some(name) => name,
@@ -425,10 +966,47 @@ async fn run() -> anyhow::Result<()> {
                             )?
                         }
                     };
-                    commands::rebuild(&manager, &name, no_cache, yes).await?;
+                    commands::rebuild(&manager, &name, no_cache, yes, attach).await?;
+                }
+                Commands::SyncAgents {
+                    container,
+                    only,
+                    force,
+                } => {
+                    let selection = commands::agent_sync_selection(&only, force)?;
+                    let container = match container {
+                        Some(name) => Some(name),
+                        None => {
+                            let containers = get_containers().await?;
+                            Some(select_container(
+                                &containers,
+                                SelectionContext::Running,
+                                "Select running container to sync agents:",
+                            )?)
+                        }
+                    };
+                    commands::sync_agents(&manager, container, selection).await?;
                 }
-                Commands::Agents { command } => match command {
-                    AgentCommands::Doctor { container } => {
+                Commands::Agents {
+                    command,
+                    container,
+                    json,
+                } => match command {
+                    None => {
+                        let container = match container {
+                            Some(name) => Some(name),
+                            None => {
+                                let containers = get_containers().await?;
+                                Some(select_container(
+                                    &containers,
+                                    SelectionContext::Running,
+                                    "Select running container to inspect agents:",
+                                )?)
+                            }
+                        };
+                        commands::agents_status(&manager, container, json).await?;
+                    }
+                    Some(AgentCommands::Doctor { container }) => {
                         let container = match container {
                             Some(name) => Some(name),
                             None if std::io::IsTerminal::is_terminal(&std::io::stdin()) => {
@@ -445,7 +1023,7 @@ async fn run() -> anyhow::Result<()> {
                         };
                         commands::agents_doctor(&manager, container).await?;
                     }
-                    AgentCommands::Sync { container } => {
+                    Some(AgentCommands::Sync { container }) => {
                         let container = match container {
                             Some(name) => Some(name),
                             None => {
@@ -460,6 +1038,12 @@ async fn run() -> anyhow::Result<()> {
                         commands::agents_sync(&manager, container).await?;
                     }
                 },
+                Commands::Clone { url, dir, volume } => {
+                    commands::clone(&manager, &url, dir, volume).await?;
+                }
+                Commands::Serve { socket } => {
+                    commands::serve(manager, &socket).await?;
+                }
             }
         }
     }
@@ -467,6 +1051,52 @@ async fn run() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Parse a `--build-arg KEY=VALUE` value
+fn parse_build_arg(raw: &str) -> Result<(String, String), String> {
+    match raw.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("expected KEY=VALUE, got '{}'", raw)),
+    }
+}
+
+/// Parse a `--target STAGE` value
+fn parse_build_target(raw: &str) -> Result<String, String> {
+    let stage = raw.trim();
+    if stage.is_empty() {
+        return Err("target stage must not be empty".to_string());
+    }
+    Ok(stage.to_string())
+}
+
+/// Parse a `--label KEY=VALUE` value, refusing devc's own label keys
+fn parse_label_arg(raw: &str) -> Result<(String, String), String> {
+    let (key, value) = parse_build_arg(raw)?;
+    if devc_provider::is_reserved_label(&key) {
+        return Err(format!(
+            "'{}' is reserved: labels starting with '{}' are managed by devc",
+            key,
+            devc_provider::RESERVED_LABEL_PREFIX
+        ));
+    }
+    Ok((key, value))
+}
+
+/// Parse and validate a `--mount SPEC` value
+fn parse_mount_arg(raw: &str) -> Result<devc_provider::MountConfig, String> {
+    devc_core::parse_mount_spec(raw).map_err(|e| e.to_string())
+}
+
+/// Create the provider requested with `--provider`, or the configured default
+async fn create_selected_provider(
+    requested: Option<ProviderType>,
+    config: &GlobalConfig,
+) -> devc_provider::Result<Box<dyn devc_provider::ContainerProvider>> {
+    match requested {
+        Some(provider_type) => create_provider(provider_type, config).await,
+        None => create_default_provider(config).await,
+    }
+}
+
 /// Detect available providers and prompt user to select one if multiple are available
 async fn detect_and_select_provider(config: &GlobalConfig) -> anyhow::Result<Option<ProviderType>> {
     eprintln!("First run detected - checking for container providers...");
@@ -505,7 +1135,7 @@ async fn detect_and_select_provider(config: &GlobalConfig) -> anyhow::Result<Opt
             // Check if we're in a terminal that supports interactive selection
             if std::io::IsTerminal::is_terminal(&std::io::stdin()) {
                 let items = vec!["Docker (recommended)", "Podman"];
-                let selection = Select::with_theme(&ColorfulTheme::default())
+                let selection = Select::with_theme(&*color::theme())
                     .with_prompt("Select your preferred container provider")
                     .items(&items)
                     .default(0)
@@ -3,7 +3,7 @@
 use anyhow::{bail, Result};
 use crossterm::{
     cursor::{self, MoveToColumn, MoveUp},
-    event::{self, Event, KeyCode, KeyEvent},
+    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
     style::{Color, ResetColor, SetForegroundColor},
     terminal::{self, Clear, ClearType},
     ExecutableCommand,
@@ -16,6 +16,8 @@ use std::io::{stdout, Write};
 pub enum SelectionContext {
     /// Only running containers (for shell, run, stop)
     Running,
+    /// Only paused containers (for unpause)
+    Paused,
     /// Startable containers: Stopped, Built, Created (for start)
     Startable,
     /// Non-running containers for `up` command
@@ -38,13 +40,17 @@ impl SelectionContext {
     fn matches(&self, container: &ContainerState) -> bool {
         match self {
             SelectionContext::Running => container.status == DevcContainerStatus::Running,
+            SelectionContext::Paused => container.status == DevcContainerStatus::Paused,
             SelectionContext::Startable => matches!(
                 container.status,
                 DevcContainerStatus::Stopped
                     | DevcContainerStatus::Built
                     | DevcContainerStatus::Created
             ),
-            SelectionContext::Uppable => container.status != DevcContainerStatus::Running,
+            SelectionContext::Uppable => !matches!(
+                container.status,
+                DevcContainerStatus::Running | DevcContainerStatus::Paused
+            ),
             SelectionContext::Any => true,
         }
     }
@@ -53,6 +59,7 @@ impl SelectionContext {
     fn description(&self) -> &'static str {
         match self {
             SelectionContext::Running => "running",
+            SelectionContext::Paused => "paused",
             SelectionContext::Startable => "startable",
             SelectionContext::Uppable => "non-running",
             SelectionContext::Any => "available",
@@ -88,6 +95,7 @@ fn status_symbol(status: &DevcContainerStatus) -> &'static str {
     match status {
         DevcContainerStatus::Available => "◌",
         DevcContainerStatus::Running => "●",
+        DevcContainerStatus::Paused => "‖",
         DevcContainerStatus::Stopped => "○",
         DevcContainerStatus::Building => "◐",
         DevcContainerStatus::Built => "◑",
@@ -102,6 +110,7 @@ fn status_color(status: &DevcContainerStatus) -> Color {
     match status {
         DevcContainerStatus::Available => Color::DarkGrey,
         DevcContainerStatus::Running => Color::Green,
+        DevcContainerStatus::Paused => Color::Magenta,
         DevcContainerStatus::Stopped => Color::DarkGrey,
         DevcContainerStatus::Building => Color::Yellow,
         DevcContainerStatus::Built => Color::Cyan,
@@ -138,81 +147,191 @@ pub fn select_container(
     let _guard = RawModeGuard::new()?;
     let mut stdout = stdout();
 
+    let display_names = display_name_map(&filtered);
+    let mut query = String::new();
+    let mut matches = fuzzy_filter(&filtered, &display_names, &query);
     let mut selected: usize = 0;
-    let total = filtered.len();
 
     // Initial render
-    render_selector(&mut stdout, &filtered, selected, prompt)?;
+    let mut lines = render_selector(
+        &mut stdout,
+        &matches,
+        &display_names,
+        selected,
+        prompt,
+        &query,
+    )?;
 
     // Event loop
     loop {
         if event::poll(std::time::Duration::from_millis(100))? {
-            if let Event::Key(KeyEvent { code, .. }) = event::read()? {
+            if let Event::Key(KeyEvent {
+                code, modifiers, ..
+            }) = event::read()?
+            {
+                let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+                let up = code == KeyCode::Up || (ctrl && code == KeyCode::Char('p'));
+                let down = code == KeyCode::Down || (ctrl && code == KeyCode::Char('n'));
                 match code {
-                    KeyCode::Up | KeyCode::Char('k') => {
+                    _ if up => {
                         if selected > 0 {
                             selected -= 1;
                         } else {
-                            selected = total - 1; // Wrap to bottom
+                            selected = matches.len().saturating_sub(1); // Wrap to bottom
                         }
                     }
-                    KeyCode::Down | KeyCode::Char('j') => {
-                        if selected < total - 1 {
+                    _ if down => {
+                        if selected + 1 < matches.len() {
                             selected += 1;
                         } else {
                             selected = 0; // Wrap to top
                         }
                     }
                     KeyCode::Enter => {
-                        // Clear the selector UI
-                        clear_selector(&mut stdout, total)?;
-                        return Ok(filtered[selected].id.clone());
+                        if let Some(container) = matches.get(selected) {
+                            let id = container.id.clone();
+                            clear_selector(&mut stdout, lines)?;
+                            return Ok(id);
+                        }
+                    }
+                    KeyCode::Esc if !query.is_empty() => {
+                        query.clear();
+                        matches = fuzzy_filter(&filtered, &display_names, &query);
+                        selected = 0;
                     }
-                    KeyCode::Esc | KeyCode::Char('q') => {
+                    KeyCode::Esc => {
                         // Clear the selector UI
-                        clear_selector(&mut stdout, total)?;
+                        clear_selector(&mut stdout, lines)?;
+                        bail!("Selection cancelled");
+                    }
+                    KeyCode::Char('c') if ctrl => {
+                        clear_selector(&mut stdout, lines)?;
                         bail!("Selection cancelled");
                     }
+                    KeyCode::Backspace => {
+                        query.pop();
+                        matches = fuzzy_filter(&filtered, &display_names, &query);
+                        selected = 0;
+                    }
+                    KeyCode::Char(c) if !ctrl => {
+                        query.push(c);
+                        matches = fuzzy_filter(&filtered, &display_names, &query);
+                        selected = 0;
+                    }
                     _ => {}
                 }
 
-                // Re-render after key press
-                rerender_selector(&mut stdout, &filtered, selected, total)?;
+                // Redraw after key press; the list can shrink or grow
+                clear_selector(&mut stdout, lines)?;
+                lines = render_selector(
+                    &mut stdout,
+                    &matches,
+                    &display_names,
+                    selected,
+                    prompt,
+                    &query,
+                )?;
             }
         }
     }
 }
 
-/// Render the selector UI
+/// Narrow containers to those whose display name fuzzily matches `query`
+///
+/// A container matches when the query's characters appear in its name in
+/// order (case-insensitive). Tighter matches sort first; ties keep the
+/// original order.
+fn fuzzy_filter<'a>(
+    containers: &'a [ContainerState],
+    display_names: &std::collections::HashMap<String, String>,
+    query: &str,
+) -> Vec<&'a ContainerState> {
+    let mut scored: Vec<(usize, &ContainerState)> = containers
+        .iter()
+        .filter_map(|c| {
+            let name = display_names
+                .get(&c.id)
+                .map(String::as_str)
+                .unwrap_or(&c.name);
+            fuzzy_score(query, name).map(|score| (score, c))
+        })
+        .collect();
+    scored.sort_by_key(|(score, _)| *score);
+    scored.into_iter().map(|(_, c)| c).collect()
+}
+
+/// Score a subsequence match of `query` in `candidate`: the number of
+/// skipped characters between the first and last matched one, plus where
+/// the match starts. Lower is better; `None` means no match.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<usize> {
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut start = None;
+    let mut gaps = 0;
+    let mut pos = 0;
+    for q in query.to_lowercase().chars() {
+        let offset = candidate[pos..].iter().position(|&c| c == q)?;
+        if start.is_none() {
+            start = Some(pos + offset);
+        } else {
+            gaps += offset;
+        }
+        pos += offset + 1;
+    }
+    Some(gaps + start.unwrap_or(0))
+}
+
+/// Render the selector UI, returning how many lines it occupies
 fn render_selector(
     stdout: &mut std::io::Stdout,
-    containers: &[ContainerState],
+    containers: &[&ContainerState],
+    display_names: &std::collections::HashMap<String, String>,
     selected: usize,
     prompt: &str,
-) -> Result<()> {
-    let display_names = display_name_map(containers);
-
+    query: &str,
+) -> Result<usize> {
     // Hide cursor during selection
     stdout.execute(cursor::Hide)?;
 
-    // Print prompt (use \r\n in raw mode for proper line breaks)
-    write!(stdout, "{}\r\n", prompt)?;
+    // Print prompt with the filter typed so far (use \r\n in raw mode for proper line breaks)
+    write!(stdout, "{} {}\r\n", prompt, query)?;
 
     // Print each container option
     for (i, container) in containers.iter().enumerate() {
-        render_line(stdout, container, &display_names, i == selected)?;
+        render_line(stdout, container, display_names, i == selected)?;
+    }
+    if containers.is_empty() {
+        set_color(stdout, Color::DarkGrey)?;
+        write!(stdout, "  (no matches)\r\n")?;
+        reset_color(stdout)?;
     }
 
     // Print help line
     write!(stdout, "\r\n")?;
-    stdout.execute(SetForegroundColor(Color::DarkGrey))?;
+    set_color(stdout, Color::DarkGrey)?;
     write!(
         stdout,
-        "[↑/↓ or j/k to move, Enter to select, Esc to cancel]"
+        "[type to filter, ↑/↓ or Ctrl+P/N to move, Enter to select, Esc to clear/cancel]"
     )?;
-    stdout.execute(ResetColor)?;
+    reset_color(stdout)?;
     stdout.flush()?;
 
+    // prompt + rows + empty + help
+    Ok(containers.len().max(1) + 3)
+}
+
+/// Set the foreground color unless color output is disabled
+fn set_color(stdout: &mut std::io::Stdout, color: Color) -> Result<()> {
+    if crate::color::enabled() {
+        stdout.execute(SetForegroundColor(color))?;
+    }
+    Ok(())
+}
+
+/// Reset the foreground color unless color output is disabled
+fn reset_color(stdout: &mut std::io::Stdout) -> Result<()> {
+    if crate::color::enabled() {
+        stdout.execute(ResetColor)?;
+    }
     Ok(())
 }
 
@@ -224,20 +343,20 @@ fn render_line(
     is_selected: bool,
 ) -> Result<()> {
     if is_selected {
-        stdout.execute(SetForegroundColor(Color::White))?;
+        set_color(stdout, Color::White)?;
         write!(stdout, "> ")?;
     } else {
         write!(stdout, "  ")?;
     }
 
     // Status symbol with color
-    stdout.execute(SetForegroundColor(status_color(&container.status)))?;
+    set_color(stdout, status_color(&container.status))?;
     write!(stdout, "{}", status_symbol(&container.status))?;
-    stdout.execute(ResetColor)?;
+    reset_color(stdout)?;
 
     // Container name
     if is_selected {
-        stdout.execute(SetForegroundColor(Color::White))?;
+        set_color(stdout, Color::White)?;
     }
     let display_name = display_names
         .get(&container.id)
@@ -246,67 +365,32 @@ fn render_line(
     write!(stdout, " {:<24}", display_name)?;
 
     // Status text
-    stdout.execute(SetForegroundColor(Color::DarkGrey))?;
+    set_color(stdout, Color::DarkGrey)?;
     write!(stdout, "{}", container.status)?;
-    stdout.execute(ResetColor)?;
+    reset_color(stdout)?;
 
     // Use \r\n in raw mode for proper line breaks
     write!(stdout, "\r\n")?;
     Ok(())
 }
 
-/// Re-render the selector after a key press (moves cursor up and redraws)
-fn rerender_selector(
-    stdout: &mut std::io::Stdout,
-    containers: &[ContainerState],
-    selected: usize,
-    total: usize,
-) -> Result<()> {
-    let display_names = display_name_map(containers);
-
-    // Move up from help line to first container line:
-    // help -> empty (1) -> containers (total) = total + 1 lines
-    let lines_to_move = total + 1;
-    stdout.execute(MoveUp(lines_to_move as u16))?;
-    stdout.execute(MoveToColumn(0))?;
-
-    // Redraw each container line
-    for (i, container) in containers.iter().enumerate() {
-        stdout.execute(Clear(ClearType::CurrentLine))?;
-        render_line(stdout, container, &display_names, i == selected)?;
-    }
-
-    // Redraw empty line and help text
-    stdout.execute(Clear(ClearType::CurrentLine))?;
-    write!(stdout, "\r\n")?;
-    stdout.execute(Clear(ClearType::CurrentLine))?;
-    stdout.execute(SetForegroundColor(Color::DarkGrey))?;
-    write!(
-        stdout,
-        "[↑/↓ or j/k to move, Enter to select, Esc to cancel]"
-    )?;
-    stdout.execute(ResetColor)?;
-    stdout.flush()?;
-
-    Ok(())
-}
-
 /// Clear the selector UI and show cursor
-fn clear_selector(stdout: &mut std::io::Stdout, total: usize) -> Result<()> {
-    // Layout: prompt (1) + containers (total) + empty (1) + help (1, cursor here)
-    // Move up to prompt: total + 2 lines
-    let lines_to_move = total + 2;
-    stdout.execute(MoveUp(lines_to_move as u16))?;
+///
+/// `lines` is what [`render_selector`] returned; the cursor sits on the last
+/// (help) line.
+fn clear_selector(stdout: &mut std::io::Stdout, lines: usize) -> Result<()> {
+    // Move up from the help line to the prompt
+    stdout.execute(MoveUp((lines - 1) as u16))?;
     stdout.execute(MoveToColumn(0))?;
 
-    // Clear all lines (prompt + containers + empty + help = total + 3 lines)
-    for _ in 0..(total + 3) {
+    // Clear every line the selector drew
+    for _ in 0..lines {
         stdout.execute(Clear(ClearType::CurrentLine))?;
         write!(stdout, "\r\n")?;
     }
 
     // Move back up to where prompt was
-    stdout.execute(MoveUp((total + 3) as u16))?;
+    stdout.execute(MoveUp(lines as u16))?;
     stdout.execute(MoveToColumn(0))?;
 
     // Show cursor again
@@ -383,6 +467,32 @@ mod tests {
         assert_eq!(filtered.len(), 2); // everything except Running
     }
 
+    #[test]
+    fn test_fuzzy_score_subsequence() {
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+        assert_eq!(fuzzy_score("api", "api-server"), Some(0));
+        assert_eq!(fuzzy_score("API", "my-api"), Some(3));
+        assert_eq!(fuzzy_score("ar", "rust-app"), None);
+        assert!(fuzzy_score("rs", "rust-server") < fuzzy_score("rs", "rubber-sole"));
+    }
+
+    #[test]
+    fn test_fuzzy_filter_ranks_tighter_matches_first() {
+        let containers = vec![
+            make_container("frontend-web", DevcContainerStatus::Running),
+            make_container("backend", DevcContainerStatus::Running),
+            make_container("web", DevcContainerStatus::Running),
+        ];
+        let names = display_name_map(&containers);
+
+        let matched: Vec<&str> = fuzzy_filter(&containers, &names, "web")
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(matched, ["web", "frontend-web"]);
+        assert_eq!(fuzzy_filter(&containers, &names, "").len(), 3);
+    }
+
     #[test]
     fn test_filter_any() {
         let containers = vec![
@@ -35,9 +35,39 @@ fn test_version_flag() {
         .success();
 }
 
+#[test]
+fn test_version_full_without_runtime() {
+    // Reports the provider as unavailable rather than failing when there is no runtime
+    Command::cargo_bin("devc")
+        .unwrap()
+        .args(["version", "--full"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(env!("CARGO_PKG_VERSION")))
+        .stdout(predicate::str::contains("Spec:"))
+        .stdout(predicate::str::contains("Provider:"));
+}
+
 #[test]
 fn test_subcommand_help() {
-    for subcmd in &["build", "shell", "exec", "start", "stop", "list", "init"] {
+    for subcmd in &[
+        "build",
+        "shell",
+        "attach",
+        "exec",
+        "start",
+        "stop",
+        "list",
+        "ps",
+        "du",
+        "logs",
+        "forget",
+        "init",
+        "sync-agents",
+        "version",
+        "serve",
+        "clone",
+    ] {
         Command::cargo_bin("devc")
             .unwrap()
             .args([subcmd, "--help"])
@@ -72,6 +102,41 @@ fn test_init_no_devcontainer_fails() {
         .stderr(predicate::str::contains("devcontainer.json"));
 }
 
+#[test]
+fn test_init_template_list() {
+    Command::cargo_bin("devc")
+        .unwrap()
+        .args(["init", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("base-ubuntu"))
+        .stdout(predicate::str::contains("python"));
+
+    Command::cargo_bin("devc")
+        .unwrap()
+        .args(["init", "--template", "cobol"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value"));
+}
+
+#[test]
+fn test_no_color_flag_is_global() {
+    Command::cargo_bin("devc")
+        .unwrap()
+        .args(["--no-color", "init", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("rust"));
+
+    Command::cargo_bin("devc")
+        .unwrap()
+        .args(["logs", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--no-color"));
+}
+
 #[test]
 fn test_config_shows_output() {
     Command::cargo_bin("devc")
@@ -81,6 +146,51 @@ fn test_config_shows_output() {
         .success();
 }
 
+#[test]
+fn test_config_rejects_broken_explicit_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("ci.toml");
+    std::fs::write(&path, "[defaults\n").unwrap();
+
+    Command::cargo_bin("devc")
+        .unwrap()
+        .arg("--config")
+        .arg(&path)
+        .arg("config")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("ci.toml"));
+}
+
+#[test]
+fn test_config_profiles_lists_and_validates() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("config.toml"),
+        "[profiles.work.defaults]\nprovider = \"podman\"\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("devc")
+        .unwrap()
+        .env("DEVC_CONFIG_DIR", dir.path())
+        .args(["config", "profiles", "--profile", "work"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("* work  defaults.provider"));
+
+    Command::cargo_bin("devc")
+        .unwrap()
+        .env("DEVC_CONFIG_DIR", dir.path())
+        .env("DEVC_PROFILE", "home")
+        .args(["config", "profiles"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Unknown profile 'home' (available: work)",
+        ));
+}
+
 #[test]
 fn test_list_succeeds() {
     if !docker_available() {
@@ -105,6 +215,173 @@ fn test_up_help() {
         .stdout(predicate::str::contains("Build, create, and start"));
 }
 
+#[test]
+fn test_rebuild_provider_flag_in_help() {
+    Command::cargo_bin("devc")
+        .unwrap()
+        .args(["rebuild", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("migrate to that provider"))
+        .stdout(predicate::str::contains("--provider"));
+}
+
+#[test]
+fn test_shell_command_flag() {
+    Command::cargo_bin("devc")
+        .unwrap()
+        .args(["shell", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--command"));
+
+    // A script and a trailing command are alternatives
+    Command::cargo_bin("devc")
+        .unwrap()
+        .args(["shell", "app", "--command", "echo hi", "ls"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_build_arg_flag() {
+    for cmd in ["build", "up", "rebuild"] {
+        Command::cargo_bin("devc")
+            .unwrap()
+            .args([cmd, "--help"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("--build-arg"));
+    }
+
+    Command::cargo_bin("devc")
+        .unwrap()
+        .args(["build", "--build-arg", "NOVALUE"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected KEY=VALUE"));
+}
+
+#[test]
+fn test_build_target_flag() {
+    Command::cargo_bin("devc")
+        .unwrap()
+        .args(["build", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--target"));
+
+    Command::cargo_bin("devc")
+        .unwrap()
+        .args(["build", "--target", " "])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must not be empty"));
+}
+
+#[test]
+fn test_mount_flag() {
+    for cmd in ["up", "exec"] {
+        Command::cargo_bin("devc")
+            .unwrap()
+            .args([cmd, "--help"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("--mount"));
+    }
+
+    Command::cargo_bin("devc")
+        .unwrap()
+        .args([
+            "up",
+            "--mount",
+            "type=bind,src=/definitely/not/here,dst=/data",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("does not exist"));
+}
+
+#[test]
+fn test_label_flag_rejects_reserved_keys() {
+    Command::cargo_bin("devc")
+        .unwrap()
+        .args(["up", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--label"));
+
+    Command::cargo_bin("devc")
+        .unwrap()
+        .args(["up", "--label", "devc.managed=false"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("reserved"));
+}
+
+#[test]
+fn test_all_flag_on_stop_and_down() {
+    for cmd in ["stop", "down"] {
+        Command::cargo_bin("devc")
+            .unwrap()
+            .args([cmd, "--help"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("--all"));
+
+        // A name and --all are mutually exclusive, and -y only applies to --all
+        Command::cargo_bin("devc")
+            .unwrap()
+            .args([cmd, "myapp", "--all"])
+            .assert()
+            .failure();
+        Command::cargo_bin("devc")
+            .unwrap()
+            .args([cmd, "myapp", "-y"])
+            .assert()
+            .failure();
+    }
+}
+
+#[test]
+fn test_logs_grep_flags() {
+    Command::cargo_bin("devc")
+        .unwrap()
+        .args(["logs", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--grep"))
+        .stdout(predicate::str::contains("--invert"));
+
+    // --invert only makes sense alongside a pattern
+    Command::cargo_bin("devc")
+        .unwrap()
+        .args(["logs", "app", "--invert"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--grep"));
+}
+
+#[test]
+fn test_logs_tail_defaults() {
+    Command::cargo_bin("devc")
+        .unwrap()
+        .args(["logs", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[default: 200]"))
+        .stdout(predicate::str::contains("--no-tail"));
+
+    // An explicit tail and --no-tail contradict each other
+    Command::cargo_bin("devc")
+        .unwrap()
+        .args(["logs", "app", "--tail", "10", "--no-tail"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--no-tail"));
+}
+
 #[test]
 fn test_exec_root_flag_in_help() {
     Command::cargo_bin("devc")
@@ -121,7 +121,7 @@ async fn test_remove_force_running() {
     let manager = test_manager(mock, store);
 
     // force remove on a running container should succeed
-    let result = commands::remove(&manager, &name, true).await;
+    let result = commands::remove(&manager, &name, true, false).await;
     assert!(result.is_ok(), "remove failed: {:?}", result.err());
 
     // Verify that Remove was called on the provider
@@ -151,7 +151,7 @@ async fn test_remove_no_force_running_fails() {
     let manager = test_manager(mock, store);
 
     // remove without force on a running container should fail
-    let result = commands::remove(&manager, &name, false).await;
+    let result = commands::remove(&manager, &name, false, false).await;
     assert!(result.is_err());
     let err_msg = result.unwrap_err().to_string();
     assert!(
@@ -174,7 +174,7 @@ async fn test_list_with_containers() {
     let mock = MockProvider::new(ProviderType::Docker);
     let manager = test_manager(mock, store);
 
-    let result = commands::list(&manager, false, false).await;
+    let result = commands::list(&manager, false, false, 4, &[]).await;
     assert!(result.is_ok());
 }
 
@@ -185,10 +185,62 @@ async fn test_list_empty() {
     let manager = test_manager(mock, store);
 
     // Should succeed and print "No containers found"
-    let result = commands::list(&manager, false, false).await;
+    let result = commands::list(&manager, false, false, 4, &[]).await;
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn test_ps_inspects_only_running_containers() {
+    use devc_core::test_support::{mock_container_details, MockCall};
+    use devc_provider::{ContainerStatus, PortInfo};
+
+    let tmp_running = tempfile::tempdir().unwrap();
+    let tmp_stopped = tempfile::tempdir().unwrap();
+    let running = make_container(
+        "web",
+        DevcContainerStatus::Running,
+        Some("cid-web"),
+        tmp_running.path(),
+    );
+    let stopped = make_container(
+        "api",
+        DevcContainerStatus::Stopped,
+        Some("cid-api"),
+        tmp_stopped.path(),
+    );
+    let store = store_with(vec![running, stopped]);
+
+    let mock = MockProvider::new(ProviderType::Docker);
+    let mut details = mock_container_details("cid-web", ContainerStatus::Running);
+    details.started_at = Some(0);
+    details.ports.push(PortInfo {
+        container_port: 3000,
+        host_port: Some(3000),
+        protocol: "tcp".to_string(),
+        host_ip: None,
+    });
+    *mock.inspect_result.lock().unwrap() = Ok(details);
+    let calls = mock.calls.clone();
+    let manager = test_manager(mock, store);
+
+    commands::ps(&manager, false).await.unwrap();
+    commands::ps(&manager, true).await.unwrap();
+
+    let inspected: Vec<_> = calls
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|c| match c {
+            MockCall::Inspect { id } => Some(id.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        inspected,
+        vec!["cid-web".to_string(), "cid-web".to_string()]
+    );
+}
+
 #[tokio::test]
 async fn test_down_calls_provider() {
     let tmp = tempfile::tempdir().unwrap();
@@ -218,6 +270,95 @@ async fn test_down_calls_provider() {
     );
 }
 
+#[tokio::test]
+async fn test_stop_all_stops_only_running() {
+    use devc_core::test_support::MockCall;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let store = store_with(vec![
+        make_container(
+            "one",
+            DevcContainerStatus::Running,
+            Some("cid-one"),
+            &tmp.path().join("one"),
+        ),
+        make_container(
+            "two",
+            DevcContainerStatus::Running,
+            Some("cid-two"),
+            &tmp.path().join("two"),
+        ),
+        make_container(
+            "idle",
+            DevcContainerStatus::Stopped,
+            Some("cid-idle"),
+            &tmp.path().join("idle"),
+        ),
+    ]);
+    let mock = MockProvider::new(ProviderType::Docker);
+    let calls = mock.calls.clone();
+    let manager = test_manager(mock, store);
+
+    commands::stop_all(&manager, true).await.unwrap();
+
+    let mut stopped: Vec<String> = calls
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|c| match c {
+            MockCall::Stop { id } => Some(id.clone()),
+            _ => None,
+        })
+        .collect();
+    stopped.sort();
+    assert_eq!(stopped, vec!["cid-one".to_string(), "cid-two".to_string()]);
+}
+
+#[tokio::test]
+async fn test_down_all_skips_runtime_for_adopted() {
+    use devc_core::test_support::MockCall;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let mut adopted = make_container(
+        "adopted",
+        DevcContainerStatus::Running,
+        Some("cid-adopted"),
+        &tmp.path().join("adopted"),
+    );
+    adopted.source = devc_provider::DevcontainerSource::VsCode;
+    let store = store_with(vec![
+        make_container(
+            "created",
+            DevcContainerStatus::Created,
+            Some("cid-created"),
+            &tmp.path().join("created"),
+        ),
+        adopted,
+    ]);
+    let mock = MockProvider::new(ProviderType::Docker);
+    let calls = mock.calls.clone();
+    let manager = test_manager(mock, store);
+
+    commands::down_all(&manager, true).await.unwrap();
+
+    let recorded = calls.lock().unwrap();
+    assert!(
+        recorded
+            .iter()
+            .any(|c| matches!(c, MockCall::Remove { id, .. } if id == "cid-created")),
+        "Expected the created container to be removed, got: {:?}",
+        *recorded,
+    );
+    assert!(
+        !recorded.iter().any(|c| matches!(
+            c,
+            MockCall::Stop { id } | MockCall::Remove { id, .. } if id == "cid-adopted"
+        )),
+        "Adopted container was torn down: {:?}",
+        *recorded,
+    );
+}
+
 #[tokio::test]
 async fn test_config_shows_defaults() {
     // config(false) should succeed -- it reads/prints the config file
@@ -291,6 +432,81 @@ fn write_devcontainer_with_post_attach(workspace: &std::path::Path) {
     .unwrap();
 }
 
+#[tokio::test]
+async fn test_exec_workdir_overrides_workspace_folder() {
+    let tmp = tempfile::tempdir().unwrap();
+    let devcontainer_dir = tmp.path().join(".devcontainer");
+    std::fs::create_dir_all(&devcontainer_dir).unwrap();
+    std::fs::write(
+        devcontainer_dir.join("devcontainer.json"),
+        r#"{"image": "ubuntu:22.04", "workspaceFolder": "/workspaces/app"}"#,
+    )
+    .unwrap();
+    let cs = make_container(
+        "myapp",
+        DevcContainerStatus::Running,
+        Some("cid123"),
+        tmp.path(),
+    );
+    let name = cs.name.clone();
+    let store = store_with(vec![cs]);
+    let manager = test_manager_minimal(MockProvider::new(ProviderType::Docker), store);
+
+    let cmd = vec!["pwd".to_string()];
+    let prepared = commands::exec_prepare(&manager, &name, cmd.clone(), false, None, &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        prepared.exec_config.working_dir.as_deref(),
+        Some("/workspaces/app")
+    );
+
+    let prepared = commands::exec_prepare(&manager, &name, cmd, false, Some("/tmp/build"), &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        prepared.exec_config.working_dir.as_deref(),
+        Some("/tmp/build")
+    );
+}
+
+/// Exec, shell and attach all refuse a paused container with a hint to resume it
+#[tokio::test]
+async fn test_paused_container_refuses_exec_shell_and_attach() {
+    use devc_core::test_support::MockCall;
+
+    let tmp = tempfile::tempdir().unwrap();
+    write_devcontainer_with_post_attach(tmp.path());
+    let cs = make_container(
+        "myapp",
+        DevcContainerStatus::Paused,
+        Some("cid123"),
+        tmp.path(),
+    );
+    let name = cs.name.clone();
+    let store = store_with(vec![cs]);
+    let mock = MockProvider::new(ProviderType::Docker);
+    let calls = mock.calls.clone();
+    let manager = test_manager_minimal(mock, store);
+
+    let errors = [
+        commands::exec_prepare(&manager, &name, vec!["pwd".to_string()], false, None, &[])
+            .await
+            .err(),
+        commands::shell_prepare(&manager, &name).await.err(),
+        commands::attach_prepare(&manager, &name).await.err(),
+    ];
+    for err in errors {
+        let err = err.expect("paused container should be refused").to_string();
+        assert!(err.contains("devc unpause myapp"), "{}", err);
+    }
+    assert!(calls
+        .lock()
+        .unwrap()
+        .iter()
+        .all(|c| !matches!(c, MockCall::Start { .. } | MockCall::Exec { .. })));
+}
+
 /// Drive `shell_prepare` against a Running container and assert the MockProvider
 /// recorded an Exec call for the postAttachCommand shell.
 #[tokio::test]
@@ -371,3 +587,322 @@ async fn test_shell_runs_post_attach_after_start() {
         *recorded
     );
 }
+
+/// `attach_prepare` on a Running container runs postAttachCommand.
+#[tokio::test]
+async fn test_attach_runs_post_attach_on_running_container() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_devcontainer_with_post_attach(tmp.path());
+
+    let cs = make_container(
+        "myapp",
+        DevcContainerStatus::Running,
+        Some("cid123"),
+        tmp.path(),
+    );
+    let name = cs.name.clone();
+    let store = store_with(vec![cs]);
+
+    let mock = MockProvider::new(ProviderType::Docker);
+    let calls = mock.calls.clone();
+    let manager = test_manager_minimal(mock, store);
+
+    commands::attach_prepare(&manager, &name)
+        .await
+        .expect("attach_prepare");
+
+    let recorded = calls.lock().unwrap();
+    let ran_post_attach = recorded.iter().any(|c| {
+        matches!(
+            c,
+            devc_core::test_support::MockCall::Exec { cmd, .. }
+                if cmd == &["/bin/sh".to_string(), "-lc".to_string(), "echo post-attach".to_string()]
+        )
+    });
+    assert!(
+        ran_post_attach,
+        "Expected postAttachCommand (`echo post-attach`) to be executed; got: {:?}",
+        *recorded
+    );
+}
+
+/// `attach_prepare` on a Stopped container fails without starting it or
+/// running postAttachCommand.
+#[tokio::test]
+async fn test_attach_requires_running_container() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_devcontainer_with_post_attach(tmp.path());
+
+    let cs = make_container(
+        "myapp",
+        DevcContainerStatus::Stopped,
+        Some("cid123"),
+        tmp.path(),
+    );
+    let name = cs.name.clone();
+    let store = store_with(vec![cs]);
+
+    let mock = MockProvider::new(ProviderType::Docker);
+    let calls = mock.calls.clone();
+    let manager = test_manager_minimal(mock, store);
+
+    let err = commands::attach_prepare(&manager, &name)
+        .await
+        .err()
+        .expect("attach to stopped container should fail");
+    assert!(err.to_string().contains("not running"), "got: {}", err);
+
+    let recorded = calls.lock().unwrap();
+    assert!(
+        recorded.is_empty(),
+        "Expected no provider calls; got: {:?}",
+        *recorded
+    );
+}
+
+/// Compose container whose mock `compose ps` reports a running `app` (primary),
+/// a running `db` and an exited `cache` service.
+fn compose_fixture(workspace: &std::path::Path) -> (ContainerState, MockProvider) {
+    let devcontainer_dir = workspace.join(".devcontainer");
+    std::fs::create_dir_all(&devcontainer_dir).unwrap();
+    std::fs::write(
+        devcontainer_dir.join("devcontainer.json"),
+        r#"{"dockerComposeFile": "docker-compose.yml", "service": "app"}"#,
+    )
+    .unwrap();
+
+    let mut cs = make_container(
+        "composeapp",
+        DevcContainerStatus::Running,
+        Some("app-cid"),
+        workspace,
+    );
+    cs.compose_project = Some("composeapp".to_string());
+    cs.compose_service = Some("app".to_string());
+
+    let mock = MockProvider::new(ProviderType::Docker);
+    let svc = |name: &str, cid: &str, status| devc_provider::ComposeServiceInfo {
+        service_name: name.to_string(),
+        container_id: devc_provider::ContainerId::new(cid),
+        status,
+    };
+    *mock.compose_ps_result.lock().unwrap() = Ok(vec![
+        svc("app", "app-cid", devc_provider::ContainerStatus::Running),
+        svc("db", "db-cid", devc_provider::ContainerStatus::Running),
+        svc("cache", "cache-cid", devc_provider::ContainerStatus::Exited),
+    ]);
+    (cs, mock)
+}
+
+#[tokio::test]
+async fn test_resolve_service_container_companion() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (cs, mock) = compose_fixture(tmp.path());
+    let manager = test_manager_minimal(mock, store_with(vec![cs]));
+
+    let id = commands::resolve_service_container(&manager, "composeapp", "db")
+        .await
+        .unwrap();
+    assert_eq!(id.as_deref(), Some("db-cid"));
+
+    // The primary service goes through the normal shell path
+    let id = commands::resolve_service_container(&manager, "composeapp", "app")
+        .await
+        .unwrap();
+    assert_eq!(id, None);
+}
+
+#[tokio::test]
+async fn test_resolve_service_container_rejects_stopped_or_unknown() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (cs, mock) = compose_fixture(tmp.path());
+    let manager = test_manager_minimal(mock, store_with(vec![cs]));
+
+    let err = commands::resolve_service_container(&manager, "composeapp", "cache")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("not running"), "got: {}", err);
+
+    let err = commands::resolve_service_container(&manager, "composeapp", "nope")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("not found"), "got: {}", err);
+}
+
+#[tokio::test]
+async fn test_resolve_service_container_requires_compose() {
+    let tmp = tempfile::tempdir().unwrap();
+    let cs = make_container(
+        "plain",
+        DevcContainerStatus::Running,
+        Some("cid123"),
+        tmp.path(),
+    );
+    let manager = test_manager_minimal(
+        MockProvider::new(ProviderType::Docker),
+        store_with(vec![cs]),
+    );
+
+    let err = commands::resolve_service_container(&manager, "plain", "db")
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("not a compose project"),
+        "got: {}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn test_logs_previous_reads_exited_container() {
+    let tmp = tempfile::tempdir().unwrap();
+    let cs = make_container(
+        "crashy",
+        DevcContainerStatus::Stopped,
+        Some("cid-exited"),
+        tmp.path(),
+    );
+    let name = cs.name.clone();
+    let store = store_with(vec![cs]);
+    let mock = MockProvider::new(ProviderType::Docker);
+    let mut details = devc_core::test_support::mock_container_details(
+        "cid-exited",
+        devc_provider::ContainerStatus::Exited,
+    );
+    details.exit_code = Some(137);
+    *mock.inspect_result.lock().unwrap() = Ok(details);
+    *mock.logs_output.lock().unwrap() = "booting\npanic: out of memory\n".to_string();
+    let calls = mock.calls.clone();
+    let manager = test_manager(mock, store);
+
+    let result = commands::logs(
+        &manager,
+        &[name],
+        None,
+        None,
+        false,
+        None,
+        false,
+        true,
+        false,
+    )
+    .await;
+    assert!(result.is_ok(), "logs --previous failed: {:?}", result.err());
+    assert!(calls
+        .lock()
+        .unwrap()
+        .contains(&devc_core::test_support::MockCall::Logs {
+            id: "cid-exited".to_string()
+        }));
+}
+
+#[tokio::test]
+async fn test_logs_json_reads_container_logs() {
+    let tmp = tempfile::tempdir().unwrap();
+    let cs = make_container(
+        "shipper",
+        DevcContainerStatus::Running,
+        Some("cid-json"),
+        tmp.path(),
+    );
+    let name = cs.name.clone();
+    let store = store_with(vec![cs]);
+    let mock = MockProvider::new(ProviderType::Docker);
+    *mock.logs_output.lock().unwrap() = "2026-03-01T12:00:00Z ready\n".to_string();
+    let calls = mock.calls.clone();
+    let manager = test_manager(mock, store);
+
+    let result = commands::logs(
+        &manager,
+        &[name],
+        None,
+        None,
+        false,
+        None,
+        false,
+        false,
+        true,
+    )
+    .await;
+    assert!(result.is_ok(), "logs --json failed: {:?}", result.err());
+    assert!(calls
+        .lock()
+        .unwrap()
+        .contains(&devc_core::test_support::MockCall::Logs {
+            id: "cid-json".to_string()
+        }));
+}
+
+#[tokio::test]
+async fn test_logs_previous_reports_removed_container() {
+    let tmp = tempfile::tempdir().unwrap();
+    let cs = make_container(
+        "crashy",
+        DevcContainerStatus::Stopped,
+        Some("cid-gone"),
+        tmp.path(),
+    );
+    let name = cs.name.clone();
+    let store = store_with(vec![cs]);
+    let mock = MockProvider::new(ProviderType::Docker);
+    *mock.inspect_result.lock().unwrap() = Err(devc_provider::ProviderError::ContainerNotFound(
+        "cid-gone".to_string(),
+    ));
+    let manager = test_manager(mock, store);
+
+    let err = commands::logs(
+        &manager,
+        &[name],
+        None,
+        None,
+        false,
+        None,
+        false,
+        true,
+        false,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("was removed"), "{}", err);
+}
+
+/// `up --rebuild -y` replaces the existing container instead of just starting it
+#[tokio::test]
+async fn test_up_rebuild_goes_through_rebuild() {
+    let tmp = tempfile::tempdir().unwrap();
+    let cs = make_container(
+        "rebuilt",
+        DevcContainerStatus::Running,
+        Some("old-cid"),
+        tmp.path(),
+    );
+    let name = cs.name.clone();
+    let store = store_with(vec![cs]);
+    let mock = MockProvider::new(ProviderType::Docker);
+    let calls = mock.calls.clone();
+    let manager = test_manager_minimal(mock, store);
+
+    let result = commands::up(
+        &manager,
+        Some(name),
+        None,
+        Some(commands::UpRebuild {
+            no_cache: false,
+            yes: true,
+        }),
+    )
+    .await;
+    assert!(result.is_ok(), "up --rebuild failed: {:?}", result.err());
+
+    use devc_core::test_support::MockCall;
+    let calls = calls.lock().unwrap();
+    let removed_old = calls
+        .iter()
+        .position(|c| matches!(c, MockCall::Remove { id, .. } if id == "old-cid"))
+        .expect("the old container should be removed");
+    let created = calls
+        .iter()
+        .position(|c| matches!(c, MockCall::Create { .. }))
+        .expect("a new container should be created");
+    assert!(removed_old < created);
+}
@@ -5,8 +5,13 @@
 use crate::{ConfigError, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr};
 use std::path::{Path, PathBuf};
 
+/// The dev container specification devc implements: devcontainer.json plus
+/// OCI-distributed Features, as published at containers.dev
+pub const DEVCONTAINER_SPEC_LEVEL: &str = "devcontainer.json + Features (containers.dev)";
+
 /// Complete devcontainer.json configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -39,23 +44,28 @@ pub struct DevContainerConfig {
     /// Environment variables for the container
     pub container_env: Option<HashMap<String, String>>,
 
-    /// User to run as in the container
+    /// User that exec, shells and lifecycle commands run as; defaults to
+    /// `containerUser`
     pub remote_user: Option<String>,
 
-    /// Container user
+    /// User the container's main process runs as; defaults to the image's user
     pub container_user: Option<String>,
 
     /// Working directory inside the container
     pub workspace_folder: Option<String>,
 
+    /// Mount for the workspace, replacing the default bind mount of the local folder
+    pub workspace_mount: Option<String>,
+
     /// Mounts to add to the container
     pub mounts: Option<Vec<Mount>>,
 
     /// Ports to forward
     pub forward_ports: Option<Vec<PortMapping>>,
 
-    /// App ports (ports that are always forwarded)
-    pub app_port: Option<IntOrArray>,
+    /// App ports (ports that are always forwarded); entries may be ranges
+    /// (`"8000-8010"`) and carry a protocol suffix (`"53/udp"`)
+    pub app_port: Option<AppPort>,
 
     /// Per-port attributes (label, protocol, onAutoForward)
     pub ports_attributes: Option<HashMap<String, PortAttributesEntry>>,
@@ -92,12 +102,26 @@ pub struct DevContainerConfig {
     /// Linux capabilities to add
     pub cap_add: Option<Vec<String>>,
 
+    /// Linux capabilities to drop (devc extension)
+    pub cap_drop: Option<Vec<String>>,
+
     /// Security options
     pub security_opt: Option<Vec<String>>,
 
     /// Whether to override the default command
     pub override_command: Option<bool>,
 
+    /// Minimum host resources the container expects (only `gpu` is acted on)
+    pub host_requirements: Option<HostRequirements>,
+
+    /// CPU and memory caps for the container (devc extension)
+    pub resources: Option<Resources>,
+
+    /// Extra labels set on the created container (devc extension);
+    /// `devc.*` keys are reserved and ignored, as is the whole map for
+    /// compose configs
+    pub labels: Option<HashMap<String, String>>,
+
     /// Environment variables for tools running in the container (not set at container creation)
     pub remote_env: Option<HashMap<String, String>>,
 
@@ -108,6 +132,9 @@ pub struct DevContainerConfig {
     /// devcontainer features to install
     pub features: Option<HashMap<String, FeatureConfig>>,
 
+    /// Feature IDs (without version) to install first, in this order
+    pub override_feature_install_order: Option<Vec<String>>,
+
     // VSCode specific (we parse but may not use all)
     /// VSCode extensions to install
     pub customizations: Option<Customizations>,
@@ -131,6 +158,20 @@ pub struct DevContainerConfig {
     #[serde(rename = "devc.dotfiles")]
     pub dotfiles: Option<DotfilesConfig>,
 
+    /// User for create-phase lifecycle commands (devc extension)
+    #[serde(rename = "devc.lifecycleUser")]
+    pub lifecycle_user: Option<String>,
+
+    /// Host working directory for initializeCommand (devc extension):
+    /// `"workspace"` (default), `"config"`, or a path relative to the workspace
+    #[serde(rename = "devc.initializeCommandCwd")]
+    pub initialize_command_cwd: Option<String>,
+
+    /// Bake onCreateCommand/updateContentCommand into a committed image at
+    /// build time, for image-based configs (devc extension)
+    #[serde(rename = "devc.prebuild")]
+    pub prebuild: Option<bool>,
+
     /// Additional options we don't explicitly handle
     #[serde(flatten)]
     pub extra: HashMap<String, serde_json::Value>,
@@ -155,13 +196,16 @@ pub struct BuildConfig {
     /// Cache from images
     pub cache_from: Option<StringOrArray>,
 
+    /// BuildKit secrets as `id=<id>,src=<path>` (devc extension)
+    pub secrets: Option<Vec<String>>,
+
     /// Additional options
     #[serde(flatten)]
     pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// Mount configuration
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum Mount {
     /// String format: "type=bind,source=/path,target=/path"
@@ -170,6 +214,47 @@ pub enum Mount {
     Object(MountObject),
 }
 
+/// Mount types accepted in the object form
+const MOUNT_TYPES: &[&str] = &["bind", "volume", "tmpfs"];
+
+// Hand-written so a malformed mount object reports the offending field instead
+// of serde's generic "did not match any variant of untagged enum" error.
+impl<'de> Deserialize<'de> for Mount {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        match serde_json::Value::deserialize(deserializer)? {
+            serde_json::Value::String(s) => Ok(Mount::String(s)),
+            value @ serde_json::Value::Object(_) => {
+                let obj = MountObject::deserialize(value)
+                    .map_err(|e| D::Error::custom(format!("invalid mount object: {}", e)))?;
+                if let Some(ref t) = obj.mount_type {
+                    if !MOUNT_TYPES.contains(&t.as_str()) {
+                        return Err(D::Error::custom(format!(
+                            "invalid mount object: unknown `type` \"{}\", expected one of {}",
+                            t,
+                            MOUNT_TYPES.join(", ")
+                        )));
+                    }
+                }
+                if obj.target.is_empty() {
+                    return Err(D::Error::custom(
+                        "invalid mount object: `target` must not be empty",
+                    ));
+                }
+                Ok(Mount::Object(obj))
+            }
+            other => Err(D::Error::custom(format!(
+                "invalid mount: expected a string or object, got {}",
+                other
+            ))),
+        }
+    }
+}
+
 /// Mount object configuration
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -191,6 +276,8 @@ pub struct MountObject {
 pub enum PortMapping {
     /// Simple port number
     Number(u16),
+    /// Port string with an optional range and protocol suffix, as in `appPort`
+    String(String),
     /// Object with label
     Object(PortObject),
 }
@@ -203,6 +290,8 @@ pub struct PortObject {
     pub label: Option<String>,
     pub protocol: Option<String>,
     pub on_auto_forward: Option<String>,
+    /// Host address to listen on (devc extension, defaults to loopback)
+    pub bind_address: Option<String>,
 }
 
 /// Command can be a string, array, or object with parallel commands
@@ -225,12 +314,120 @@ pub enum StringOrArray {
     Array(Vec<String>),
 }
 
-/// Integer or array of integers
+/// `appPort` value: a single port spec or an array of them
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
-pub enum IntOrArray {
-    Int(u16),
-    Array(Vec<u16>),
+pub enum AppPort {
+    Single(PortSpec),
+    Array(Vec<PortSpec>),
+}
+
+impl AppPort {
+    fn specs(&self) -> &[PortSpec] {
+        match self {
+            AppPort::Single(spec) => std::slice::from_ref(spec),
+            AppPort::Array(specs) => specs,
+        }
+    }
+}
+
+/// A port number, or a string with an optional range and protocol suffix
+/// (`"8080"`, `"8000-8010"`, `"53/udp"`, `"6000-6002/udp"`); ranges span at
+/// most [`MAX_PORT_RANGE`] ports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PortSpec {
+    Number(u16),
+    String(String),
+}
+
+impl PortSpec {
+    /// The individual ports this spec covers, or None if it is malformed
+    pub fn expand(&self) -> Option<Vec<(u16, PortTransport)>> {
+        match self {
+            PortSpec::Number(port) => Some(vec![(*port, PortTransport::Tcp)]),
+            PortSpec::String(s) => parse_port_spec(s),
+        }
+    }
+}
+
+/// Largest port range a single spec may expand to; each port gets its own
+/// forwarder, so `"1-65535"` would start tens of thousands of them
+pub const MAX_PORT_RANGE: usize = 1000;
+
+fn parse_port_spec(spec: &str) -> Option<Vec<(u16, PortTransport)>> {
+    let (ports, transport) = match spec.trim().split_once('/') {
+        Some((ports, proto)) => (ports, PortTransport::parse(proto)?),
+        None => (spec.trim(), PortTransport::Tcp),
+    };
+    let (start, end) = match ports.split_once('-') {
+        Some((start, end)) => (start.trim().parse().ok()?, end.trim().parse().ok()?),
+        None => {
+            let port = ports.trim().parse().ok()?;
+            (port, port)
+        }
+    };
+    if start == 0 || start > end || usize::from(end - start) >= MAX_PORT_RANGE {
+        return None;
+    }
+    Some((start..=end).map(|port| (port, transport)).collect())
+}
+
+/// Why a port string is ignored, or None if it parses
+fn port_spec_problem(spec: &str) -> Option<String> {
+    if parse_port_spec(spec).is_some() {
+        return None;
+    }
+    let ports = spec.split_once('/').map_or(spec, |(ports, _)| ports);
+    let range = ports.split_once('-').and_then(|(start, end)| {
+        Some((
+            start.trim().parse::<u16>().ok()?,
+            end.trim().parse::<u16>().ok()?,
+        ))
+    });
+    Some(match range {
+        Some((start, end)) if start < end => format!(
+            "\"{}\" spans more than {} ports and is ignored",
+            spec, MAX_PORT_RANGE
+        ),
+        _ if ports.contains(':') => format!(
+            "\"{}\" is ignored: host:port forms are not supported, only a port or port range",
+            spec
+        ),
+        _ => format!("\"{}\" is not a port or port range and is ignored", spec),
+    })
+}
+
+/// Transport protocol of a port
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PortTransport {
+    #[default]
+    Tcp,
+    Udp,
+}
+
+impl PortTransport {
+    fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "tcp" => Some(PortTransport::Tcp),
+            "udp" => Some(PortTransport::Udp),
+            _ => None,
+        }
+    }
+
+    /// Lowercase name, as used by `docker run -p` and socat ("tcp", "udp")
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PortTransport::Tcp => "tcp",
+            PortTransport::Udp => "udp",
+        }
+    }
+}
+
+impl std::fmt::Display for PortTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Feature configuration - can be boolean, string, or object
@@ -245,6 +442,107 @@ pub enum FeatureConfig {
     Options(HashMap<String, serde_json::Value>),
 }
 
+/// `hostRequirements` from devcontainer.json
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct HostRequirements {
+    pub cpus: Option<u32>,
+    pub memory: Option<String>,
+    pub storage: Option<String>,
+    pub gpu: Option<GpuRequirement>,
+}
+
+/// `resources` (devc extension): limits passed as `--cpus` / `--memory`.
+///
+/// Unlike `hostRequirements`, which states what the container needs at
+/// minimum, these cap what it may use so it cannot starve the host.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Resources {
+    /// Number of CPUs, fractions allowed (e.g. `1.5`)
+    pub cpus: Option<f64>,
+    /// Memory limit such as `"512m"`, `"4g"` or `"2GiB"`
+    pub memory: Option<String>,
+}
+
+impl Resources {
+    /// Validated CPU limit
+    pub fn cpu_limit(&self) -> Result<Option<f64>> {
+        match self.cpus {
+            Some(cpus) if !(cpus.is_finite() && cpus > 0.0) => Err(ConfigError::Invalid(format!(
+                "resources.cpus must be a positive number, got {}",
+                cpus
+            ))),
+            cpus => Ok(cpus),
+        }
+    }
+
+    /// Validated memory limit in bytes
+    pub fn memory_limit(&self) -> Result<Option<u64>> {
+        self.memory
+            .as_deref()
+            .map(|memory| {
+                parse_memory_size(memory).ok_or_else(|| {
+                    ConfigError::Invalid(format!(
+                        "resources.memory '{}' is not a size like 512m or 4g",
+                        memory
+                    ))
+                })
+            })
+            .transpose()
+    }
+}
+
+/// Parse a memory size the way Docker does: a number with an optional
+/// binary unit (`k`, `m`, `g`, `t`), optionally followed by `b` or `ib`.
+/// A bare number is bytes. Returns None for malformed or zero sizes.
+pub fn parse_memory_size(s: &str) -> Option<u64> {
+    let s = s.trim().to_ascii_lowercase();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let unit = unit.trim();
+    let unit = unit
+        .strip_suffix("ib")
+        .or_else(|| unit.strip_suffix('b'))
+        .unwrap_or(unit);
+    let multiplier: u64 = match unit {
+        "" => 1,
+        "k" => 1 << 10,
+        "m" => 1 << 20,
+        "g" => 1 << 30,
+        "t" => 1 << 40,
+        _ => return None,
+    };
+    let bytes = (number * multiplier as f64).round();
+    (bytes >= 1.0 && bytes < u64::MAX as f64).then_some(bytes as u64)
+}
+
+/// `hostRequirements.gpu`: `true`/`false`, `"optional"`, or `{ cores, memory }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GpuRequirement {
+    Flag(bool),
+    Mode(String),
+    Detailed {
+        cores: Option<u32>,
+        memory: Option<String>,
+    },
+}
+
+impl GpuRequirement {
+    /// Whether the container wants GPUs at all
+    pub fn is_requested(&self) -> bool {
+        !matches!(self, Self::Flag(false))
+    }
+
+    /// Whether GPUs are only used if the host has them
+    pub fn is_optional(&self) -> bool {
+        matches!(self, Self::Mode(mode) if mode == "optional")
+    }
+}
+
 /// VSCode customizations
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Customizations {
@@ -274,57 +572,59 @@ pub struct DotfilesConfig {
     pub target_path: Option<String>,
 }
 
+/// The workspace's top-level config: `.devcontainer/devcontainer.json`, or the
+/// root `.devcontainer.json` the spec also allows when the folder form is absent
+fn top_level_config(dir: &Path) -> Option<PathBuf> {
+    [
+        dir.join(".devcontainer/devcontainer.json"),
+        dir.join(".devcontainer.json"),
+    ]
+    .into_iter()
+    .find(|path| path.exists())
+}
+
+/// Paths of every devcontainer.json in a workspace, in the order
+/// [`DevContainerConfig::load_all_from_dir`] loads them
+pub fn config_paths_in_dir(dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = top_level_config(dir).into_iter().collect();
+
+    // Check subdirectories in .devcontainer (sorted for determinism)
+    if let Ok(entries) = std::fs::read_dir(dir.join(".devcontainer")) {
+        let mut subdirs: Vec<_> = entries.flatten().filter(|e| e.path().is_dir()).collect();
+        subdirs.sort_by_key(|e| e.file_name());
+        paths.extend(
+            subdirs
+                .into_iter()
+                .map(|entry| entry.path().join("devcontainer.json"))
+                .filter(|path| path.exists()),
+        );
+    }
+
+    paths
+}
+
 impl DevContainerConfig {
     /// Load ALL devcontainer.json configs from a directory
     ///
     /// Returns all valid configs found in standard locations:
-    /// 1. `.devcontainer/devcontainer.json`
-    /// 2. `.devcontainer.json`
-    /// 3. `.devcontainer/<folder>/devcontainer.json` (all subdirs, sorted by name)
+    /// 1. `.devcontainer/devcontainer.json`, or else `.devcontainer.json`
+    /// 2. `.devcontainer/<folder>/devcontainer.json` (all subdirs, sorted by name)
     ///
-    /// Invalid configs are skipped with a warning. Returns an empty Vec if none found.
+    /// Only one top-level config is returned: when both exist the folder form
+    /// wins, as it does for [`Self::load_from_dir`], so the same project is not
+    /// registered twice. Invalid configs are skipped with a warning. Returns an
+    /// empty Vec if none found.
     pub fn load_all_from_dir(dir: &Path) -> Vec<(Self, PathBuf)> {
-        let mut results = Vec::new();
-
-        // Check top-level candidates
-        let candidates = [
-            dir.join(".devcontainer/devcontainer.json"),
-            dir.join(".devcontainer.json"),
-        ];
-
-        for path in &candidates {
-            if path.exists() {
-                match Self::load_from(path) {
-                    Ok(config) => results.push((config, path.clone())),
-                    Err(e) => tracing::warn!("Skipping invalid config {}: {}", path.display(), e),
-                }
-            }
-        }
-
-        // Check subdirectories in .devcontainer (sorted for determinism)
-        let devcontainer_dir = dir.join(".devcontainer");
-        if devcontainer_dir.is_dir() {
-            if let Ok(entries) = std::fs::read_dir(&devcontainer_dir) {
-                let mut subdirs: Vec<_> = entries.flatten().filter(|e| e.path().is_dir()).collect();
-                subdirs.sort_by_key(|e| e.file_name());
-
-                for entry in subdirs {
-                    let config_path = entry.path().join("devcontainer.json");
-                    if config_path.exists() {
-                        match Self::load_from(&config_path) {
-                            Ok(config) => results.push((config, config_path)),
-                            Err(e) => tracing::warn!(
-                                "Skipping invalid config {}: {}",
-                                config_path.display(),
-                                e
-                            ),
-                        }
-                    }
+        config_paths_in_dir(dir)
+            .into_iter()
+            .filter_map(|path| match Self::load_from(&path) {
+                Ok(config) => Some((config, path)),
+                Err(e) => {
+                    tracing::warn!("Skipping invalid config {}: {}", path.display(), e);
+                    None
                 }
-            }
-        }
-
-        results
+            })
+            .collect()
     }
 
     /// Load devcontainer.json from a directory
@@ -334,16 +634,9 @@ impl DevContainerConfig {
     /// 2. `.devcontainer.json`
     /// 3. `.devcontainer/<folder>/devcontainer.json` (returns first found)
     pub fn load_from_dir(dir: &Path) -> Result<(Self, PathBuf)> {
-        let candidates = [
-            dir.join(".devcontainer/devcontainer.json"),
-            dir.join(".devcontainer.json"),
-        ];
-
-        for path in &candidates {
-            if path.exists() {
-                let config = Self::load_from(path)?;
-                return Ok((config, path.clone()));
-            }
+        if let Some(path) = top_level_config(dir) {
+            let config = Self::load_from(&path)?;
+            return Ok((config, path));
         }
 
         // Check for subdirectories in .devcontainer
@@ -387,6 +680,86 @@ impl DevContainerConfig {
         })
     }
 
+    /// Load devcontainer.json from a specific file, checking it against the schema
+    ///
+    /// Schema issues are returned for the caller to report; with `strict`
+    /// they fail the load instead.
+    pub fn load_validated(path: &Path, strict: bool) -> Result<(Self, Vec<SchemaIssue>)> {
+        let content = std::fs::read_to_string(path).map_err(|e| ConfigError::ReadError {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+
+        Self::parse_validated(&content, path, strict)
+    }
+
+    /// Parse devcontainer.json content, checking it against the schema
+    pub fn parse_validated(
+        content: &str,
+        path: &Path,
+        strict: bool,
+    ) -> Result<(Self, Vec<SchemaIssue>)> {
+        let config = Self::parse(content, path)?;
+        let issues = schema_issues(&strip_json_comments(content));
+        if strict && !issues.is_empty() {
+            return Err(ConfigError::Schema {
+                path: path.to_path_buf(),
+                issues,
+            });
+        }
+        Ok((config, issues))
+    }
+
+    /// Files this config refers to that don't exist: the Dockerfile, an
+    /// explicit build context, and compose files, all relative to the
+    /// directory holding `config_path`
+    pub fn missing_references(&self, config_path: &Path) -> Vec<String> {
+        let config_dir = config_path.parent().unwrap_or(Path::new("."));
+        let mut missing = Vec::new();
+
+        if let ImageSource::Dockerfile { path, context, .. } = self.image_source() {
+            let dockerfile = config_dir.join(&path);
+            if !dockerfile.is_file() {
+                missing.push(format!("Dockerfile not found: {}", dockerfile.display()));
+            }
+            if let Some(context) = context {
+                let context = config_dir.join(context);
+                if !context.is_dir() {
+                    missing.push(format!("build context not found: {}", context.display()));
+                }
+            }
+        }
+
+        let compose_files = match &self.docker_compose_file {
+            Some(StringOrArray::String(file)) => vec![file.clone()],
+            Some(StringOrArray::Array(files)) => files.clone(),
+            None => Vec::new(),
+        };
+        for file in compose_files {
+            let file = config_dir.join(file);
+            if !file.is_file() {
+                missing.push(format!("compose file not found: {}", file.display()));
+            }
+        }
+
+        missing
+    }
+
+    /// `${...}` references that variable substitution can't resolve (see
+    /// [`crate::unresolved_variables`]), in the fields
+    /// [`Self::substitute_variables`] expands
+    ///
+    /// Other fields, notably `customizations`, are passed through untouched
+    /// and may hold other tools' variables.
+    pub fn unresolved_variables(&self) -> Vec<String> {
+        let mut out = Vec::new();
+        self.clone()
+            .for_each_substituted(&mut |s| out.extend(crate::unresolved_variables(s)));
+        out.sort();
+        out.dedup();
+        out
+    }
+
     /// Get the effective image source (image, dockerfile, or compose)
     pub fn image_source(&self) -> ImageSource {
         if let Some(ref image) = self.image {
@@ -396,6 +769,7 @@ impl DevContainerConfig {
                 path: dockerfile.clone(),
                 context: None,
                 args: None,
+                target: None,
             }
         } else if let Some(ref build) = self.build {
             ImageSource::Dockerfile {
@@ -405,6 +779,7 @@ impl DevContainerConfig {
                     .unwrap_or_else(|| "Dockerfile".to_string()),
                 context: build.context.clone(),
                 args: build.args.clone(),
+                target: build.target.clone(),
             }
         } else if self.docker_compose_file.is_some() {
             ImageSource::Compose
@@ -413,31 +788,91 @@ impl DevContainerConfig {
         }
     }
 
-    /// Get the remote user (with fallback)
-    pub fn effective_user(&self) -> Option<&str> {
+    /// User for exec, shells and lifecycle commands: `remoteUser`, falling
+    /// back to `containerUser`
+    pub fn effective_remote_user(&self) -> Option<&str> {
         self.remote_user
             .as_deref()
             .or(self.container_user.as_deref())
     }
 
-    /// Get all forward ports as a flat list
-    pub fn forward_ports_list(&self) -> Vec<u16> {
+    /// User for the container's main process: `containerUser` only, since
+    /// `remoteUser` never changes what the container itself runs as
+    pub fn effective_container_user(&self) -> Option<&str> {
+        self.container_user.as_deref()
+    }
+
+    /// Get the user for create-phase lifecycle commands
+    ///
+    /// `onCreateCommand`, `updateContentCommand` and `postCreateCommand` (and their
+    /// feature counterparts) run as `devc.lifecycleUser` when set, so setup can run
+    /// as root while shells and later phases keep using [`Self::effective_remote_user`].
+    pub fn create_phase_user(&self) -> Option<&str> {
+        self.lifecycle_user
+            .as_deref()
+            .or(self.effective_remote_user())
+    }
+
+    /// Extensions listed under `customizations.vscode.extensions`
+    ///
+    /// devc doesn't install them; they are only shown as a hint of the
+    /// tooling the config expects.
+    pub fn vscode_extensions(&self) -> &[String] {
+        self.customizations
+            .as_ref()
+            .and_then(|c| c.vscode.as_ref())
+            .and_then(|v| v.extensions.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Resolve the container's dotfiles settings
+    ///
+    /// The top-level `devc.dotfiles` key wins; otherwise `customizations.devc.dotfiles`
+    /// is used. Returns `None` when neither is present or the latter is malformed.
+    pub fn dotfiles_config(&self) -> Option<DotfilesConfig> {
+        if let Some(ref dotfiles) = self.dotfiles {
+            return Some(dotfiles.clone());
+        }
+        let value = self
+            .customizations
+            .as_ref()?
+            .other
+            .get("devc")?
+            .get("dotfiles")?;
+        serde_json::from_value(value.clone()).ok()
+    }
+
+    /// Resolve `shutdownAction` for quitting the TUI
+    ///
+    /// Unlike VS Code, devc leaves containers running unless the config asks
+    /// otherwise, so an absent or unrecognized value resolves to `None`.
+    pub fn effective_shutdown_action(&self) -> ShutdownAction {
+        match self.shutdown_action.as_deref() {
+            Some("stopContainer") => ShutdownAction::StopContainer,
+            Some("stopCompose") => ShutdownAction::StopCompose,
+            _ => ShutdownAction::None,
+        }
+    }
+
+    /// Get all forward ports as a flat list, with port ranges expanded
+    ///
+    /// Malformed port strings and ranges over [`MAX_PORT_RANGE`] ports are
+    /// skipped; schema validation reports them.
+    pub fn forward_ports_list(&self) -> Vec<(u16, PortTransport)> {
         let mut ports = Vec::new();
 
         if let Some(ref forward) = self.forward_ports {
             for mapping in forward {
                 match mapping {
-                    PortMapping::Number(p) => ports.push(*p),
-                    PortMapping::Object(obj) => ports.push(obj.port),
+                    PortMapping::Number(p) => ports.push((*p, PortTransport::Tcp)),
+                    PortMapping::String(s) => ports.extend(parse_port_spec(s).unwrap_or_default()),
+                    PortMapping::Object(obj) => ports.push((obj.port, PortTransport::Tcp)),
                 }
             }
         }
 
         if let Some(ref app) = self.app_port {
-            match app {
-                IntOrArray::Int(p) => ports.push(*p),
-                IntOrArray::Array(arr) => ports.extend(arr),
-            }
+            ports.extend(app.specs().iter().filter_map(PortSpec::expand).flatten());
         }
 
         ports
@@ -446,10 +881,15 @@ impl DevContainerConfig {
     /// Get auto-forward configuration for ports declared in the devcontainer config.
     ///
     /// Returns a list of `PortForwardConfig` from `forwardPorts`, `appPort`, and `portsAttributes`:
-    /// - `forwardPorts` numeric entries default to `Notify`
+    /// - `forwardPorts` numeric and string entries default to `Notify`; string
+    ///   ranges expand to one entry per port
     /// - `forwardPorts` object entries map `onAutoForward` to the enum, carrying label/protocol
-    /// - `appPort` entries always use `Silent` (always forwarded quietly)
-    /// - `portsAttributes` entries override/supplement label, protocol, and action
+    /// - `appPort` entries always use `Silent` (always forwarded quietly); ranges
+    ///   expand to one entry per port
+    /// - `portsAttributes` entries override/supplement label, protocol, action, and
+    ///   bind address; range keys only override ports already declared
+    /// - `bindAddress` (a devc extension) picks the host address to listen on;
+    ///   missing or unparseable values fall back to loopback
     pub fn auto_forward_config(&self) -> Vec<PortForwardConfig> {
         let mut result = Vec::new();
 
@@ -459,17 +899,34 @@ impl DevContainerConfig {
                     PortMapping::Number(p) => {
                         result.push(PortForwardConfig {
                             port: *p,
+                            transport: PortTransport::Tcp,
                             action: AutoForwardAction::Notify,
                             label: None,
                             protocol: None,
+                            bind_address: PortForwardConfig::DEFAULT_BIND_ADDRESS,
                         });
                     }
+                    PortMapping::String(s) => {
+                        for (port, transport) in parse_port_spec(s).unwrap_or_default() {
+                            result.push(PortForwardConfig {
+                                port,
+                                transport,
+                                action: AutoForwardAction::Notify,
+                                label: None,
+                                protocol: None,
+                                bind_address: PortForwardConfig::DEFAULT_BIND_ADDRESS,
+                            });
+                        }
+                    }
                     PortMapping::Object(obj) => {
                         result.push(PortForwardConfig {
                             port: obj.port,
+                            transport: PortTransport::Tcp,
                             action: parse_auto_forward_action(obj.on_auto_forward.as_deref()),
                             label: obj.label.clone(),
                             protocol: obj.protocol.clone(),
+                            bind_address: parse_bind_address(obj.bind_address.as_deref())
+                                .unwrap_or(PortForwardConfig::DEFAULT_BIND_ADDRESS),
                         });
                     }
                 }
@@ -477,53 +934,54 @@ impl DevContainerConfig {
         }
 
         if let Some(ref app) = self.app_port {
-            match app {
-                IntOrArray::Int(p) => {
-                    result.push(PortForwardConfig {
-                        port: *p,
-                        action: AutoForwardAction::Silent,
-                        label: None,
-                        protocol: None,
-                    });
-                }
-                IntOrArray::Array(arr) => {
-                    for p in arr {
-                        result.push(PortForwardConfig {
-                            port: *p,
-                            action: AutoForwardAction::Silent,
-                            label: None,
-                            protocol: None,
-                        });
-                    }
-                }
+            for (port, transport) in app.specs().iter().filter_map(PortSpec::expand).flatten() {
+                result.push(PortForwardConfig {
+                    port,
+                    transport,
+                    action: AutoForwardAction::Silent,
+                    label: None,
+                    protocol: None,
+                    bind_address: PortForwardConfig::DEFAULT_BIND_ADDRESS,
+                });
             }
         }
 
         // Merge portsAttributes overrides
         if let Some(ref attrs) = self.ports_attributes {
             for (key, entry) in attrs {
-                let port: u16 = match key.parse() {
-                    Ok(p) => p,
-                    Err(_) => continue,
+                let Some(ports) = parse_port_spec(key) else {
+                    continue;
                 };
-                if let Some(existing) = result.iter_mut().find(|c| c.port == port) {
-                    if let Some(ref label) = entry.label {
-                        existing.label = Some(label.clone());
-                    }
-                    if let Some(ref protocol) = entry.protocol {
-                        existing.protocol = Some(protocol.clone());
-                    }
-                    if entry.on_auto_forward.is_some() {
-                        existing.action =
-                            parse_auto_forward_action(entry.on_auto_forward.as_deref());
+                let is_range = ports.len() > 1;
+                for (port, transport) in ports {
+                    if let Some(existing) = result
+                        .iter_mut()
+                        .find(|c| c.port == port && c.transport == transport)
+                    {
+                        if let Some(ref label) = entry.label {
+                            existing.label = Some(label.clone());
+                        }
+                        if let Some(ref protocol) = entry.protocol {
+                            existing.protocol = Some(protocol.clone());
+                        }
+                        if entry.on_auto_forward.is_some() {
+                            existing.action =
+                                parse_auto_forward_action(entry.on_auto_forward.as_deref());
+                        }
+                        if let Some(addr) = parse_bind_address(entry.bind_address.as_deref()) {
+                            existing.bind_address = addr;
+                        }
+                    } else if !is_range {
+                        result.push(PortForwardConfig {
+                            port,
+                            transport,
+                            action: parse_auto_forward_action(entry.on_auto_forward.as_deref()),
+                            label: entry.label.clone(),
+                            protocol: entry.protocol.clone(),
+                            bind_address: parse_bind_address(entry.bind_address.as_deref())
+                                .unwrap_or(PortForwardConfig::DEFAULT_BIND_ADDRESS),
+                        });
                     }
-                } else {
-                    result.push(PortForwardConfig {
-                        port,
-                        action: parse_auto_forward_action(entry.on_auto_forward.as_deref()),
-                        label: entry.label.clone(),
-                        protocol: entry.protocol.clone(),
-                    });
                 }
             }
         }
@@ -533,77 +991,61 @@ impl DevContainerConfig {
 
     /// Apply variable substitution to all string fields that support it
     pub fn substitute_variables(&mut self, ctx: &crate::SubstitutionContext) {
-        use crate::substitute::{substitute, substitute_map, substitute_opt, substitute_vec};
+        self.for_each_substituted(&mut |s| *s = crate::substitute::substitute(s, ctx));
+    }
 
-        self.workspace_folder = substitute_opt(&self.workspace_folder, ctx);
+    /// Visit every string variable substitution applies to
+    fn for_each_substituted(&mut self, f: &mut dyn FnMut(&mut String)) {
+        let strings = [&mut self.workspace_folder, &mut self.workspace_mount];
+        strings.into_iter().flatten().for_each(&mut *f);
 
-        if let Some(ref env) = self.container_env {
-            self.container_env = Some(substitute_map(env, ctx));
-        }
-        if let Some(ref env) = self.remote_env {
-            self.remote_env = Some(substitute_map(env, ctx));
+        for env in [&mut self.container_env, &mut self.remote_env]
+            .into_iter()
+            .flatten()
+        {
+            env.values_mut().for_each(&mut *f);
         }
 
-        if let Some(ref args) = self.run_args {
-            self.run_args = Some(substitute_vec(args, ctx));
+        if let Some(ref mut args) = self.run_args {
+            args.iter_mut().for_each(&mut *f);
         }
 
         // Substitute in mounts
         if let Some(ref mut mounts) = self.mounts {
             for mount in mounts.iter_mut() {
                 match mount {
-                    Mount::String(s) => *s = substitute(s, ctx),
+                    Mount::String(s) => f(s),
                     Mount::Object(obj) => {
-                        obj.source = substitute_opt(&obj.source, ctx);
-                        obj.target = substitute(&obj.target, ctx);
+                        obj.source.iter_mut().for_each(&mut *f);
+                        f(&mut obj.target);
                     }
                 }
             }
         }
 
         // Substitute in lifecycle commands
-        fn substitute_command(cmd: &mut Command, ctx: &crate::SubstitutionContext) {
-            use crate::substitute::substitute;
+        let commands = [
+            &mut self.initialize_command,
+            &mut self.on_create_command,
+            &mut self.update_content_command,
+            &mut self.post_create_command,
+            &mut self.post_start_command,
+            &mut self.post_attach_command,
+        ];
+        for cmd in commands.into_iter().flatten() {
             match cmd {
-                Command::String(s) => *s = substitute(s, ctx),
-                Command::Array(arr) => {
-                    for s in arr.iter_mut() {
-                        *s = substitute(s, ctx);
-                    }
-                }
+                Command::String(s) => f(s),
+                Command::Array(arr) => arr.iter_mut().for_each(&mut *f),
                 Command::Object(map) => {
                     for value in map.values_mut() {
                         match value {
-                            StringOrArray::String(s) => *s = substitute(s, ctx),
-                            StringOrArray::Array(arr) => {
-                                for s in arr.iter_mut() {
-                                    *s = substitute(s, ctx);
-                                }
-                            }
+                            StringOrArray::String(s) => f(s),
+                            StringOrArray::Array(arr) => arr.iter_mut().for_each(&mut *f),
                         }
                     }
                 }
             }
         }
-
-        if let Some(ref mut cmd) = self.initialize_command {
-            substitute_command(cmd, ctx);
-        }
-        if let Some(ref mut cmd) = self.on_create_command {
-            substitute_command(cmd, ctx);
-        }
-        if let Some(ref mut cmd) = self.update_content_command {
-            substitute_command(cmd, ctx);
-        }
-        if let Some(ref mut cmd) = self.post_create_command {
-            substitute_command(cmd, ctx);
-        }
-        if let Some(ref mut cmd) = self.post_start_command {
-            substitute_command(cmd, ctx);
-        }
-        if let Some(ref mut cmd) = self.post_attach_command {
-            substitute_command(cmd, ctx);
-        }
     }
 }
 
@@ -622,13 +1064,34 @@ pub enum AutoForwardAction {
     OpenBrowserOnce,
 }
 
+/// What to do with a container when the tool that started it exits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownAction {
+    /// Leave the container running
+    None,
+    /// Stop the primary container
+    StopContainer,
+    /// Bring down every service in the compose project
+    StopCompose,
+}
+
 /// Configuration for a single auto-forwarded port
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PortForwardConfig {
     pub port: u16,
+    /// TCP unless the port was declared with a `/udp` suffix
+    pub transport: PortTransport,
     pub action: AutoForwardAction,
     pub label: Option<String>,
+    /// Application protocol from `portsAttributes` (`http`/`https`)
     pub protocol: Option<String>,
+    /// Host address the forwarder listens on
+    pub bind_address: IpAddr,
+}
+
+impl PortForwardConfig {
+    /// Forwards are only reachable from this machine unless configured otherwise
+    pub const DEFAULT_BIND_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
 }
 
 /// Attributes for a port from the `portsAttributes` field
@@ -638,6 +1101,23 @@ pub struct PortAttributesEntry {
     pub label: Option<String>,
     pub protocol: Option<String>,
     pub on_auto_forward: Option<String>,
+    /// Host address to listen on (devc extension, defaults to loopback)
+    pub bind_address: Option<String>,
+}
+
+/// Parse a `bindAddress` value; `localhost` is accepted as loopback.
+fn parse_bind_address(value: Option<&str>) -> Option<IpAddr> {
+    let value = value?.trim();
+    if value == "localhost" {
+        return Some(PortForwardConfig::DEFAULT_BIND_ADDRESS);
+    }
+    match value.parse() {
+        Ok(addr) => Some(addr),
+        Err(_) => {
+            tracing::warn!("Ignoring invalid bindAddress {:?}", value);
+            None
+        }
+    }
 }
 
 /// Parse an `onAutoForward` string into an `AutoForwardAction`.
@@ -661,6 +1141,7 @@ pub enum ImageSource {
         path: String,
         context: Option<String>,
         args: Option<HashMap<String, String>>,
+        target: Option<String>,
     },
     /// Docker Compose
     Compose,
@@ -668,7 +1149,301 @@ pub enum ImageSource {
     None,
 }
 
+/// A devcontainer.json key or value that the schema does not allow
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaIssue {
+    /// 1-based line of the offending key
+    pub line: usize,
+    /// 1-based column of the offending key, in characters
+    pub column: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for SchemaIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+/// Expected shape of a schema property
+#[derive(Clone, Copy)]
+enum Expect {
+    /// Modeled by `DevContainerConfig`; serde already checks the type
+    Modeled,
+    String,
+    Bool,
+    Array,
+    Object,
+    OneOf(&'static [&'static str]),
+}
+
+/// Top-level devcontainer.json properties: the spec's, plus devc extensions
+const TOP_LEVEL_PROPERTIES: &[(&str, Expect)] = &[
+    ("$schema", Expect::String),
+    ("name", Expect::Modeled),
+    ("image", Expect::Modeled),
+    ("dockerFile", Expect::Modeled),
+    ("dockerfile", Expect::Modeled),
+    ("context", Expect::String),
+    ("build", Expect::Modeled),
+    ("dockerComposeFile", Expect::Modeled),
+    ("service", Expect::Modeled),
+    ("runServices", Expect::Array),
+    ("runArgs", Expect::Modeled),
+    ("containerEnv", Expect::Modeled),
+    ("remoteUser", Expect::Modeled),
+    ("containerUser", Expect::Modeled),
+    ("updateRemoteUserUID", Expect::Bool),
+    (
+        "userEnvProbe",
+        Expect::OneOf(&[
+            "none",
+            "loginShell",
+            "loginInteractiveShell",
+            "interactiveShell",
+        ]),
+    ),
+    ("workspaceFolder", Expect::Modeled),
+    ("workspaceMount", Expect::Modeled),
+    ("mounts", Expect::Modeled),
+    ("forwardPorts", Expect::Modeled),
+    ("appPort", Expect::Modeled),
+    ("portsAttributes", Expect::Modeled),
+    ("otherPortsAttributes", Expect::Object),
+    ("initializeCommand", Expect::Modeled),
+    ("initCommand", Expect::Modeled),
+    ("onCreateCommand", Expect::Modeled),
+    ("updateContentCommand", Expect::Modeled),
+    ("postCreateCommand", Expect::Modeled),
+    ("postStartCommand", Expect::Modeled),
+    ("postAttachCommand", Expect::Modeled),
+    (
+        "waitFor",
+        Expect::OneOf(&[
+            "initializeCommand",
+            "onCreateCommand",
+            "updateContentCommand",
+            "postCreateCommand",
+            "postStartCommand",
+            "postAttachCommand",
+        ]),
+    ),
+    ("init", Expect::Modeled),
+    ("privileged", Expect::Modeled),
+    ("capAdd", Expect::Modeled),
+    ("capDrop", Expect::Modeled),
+    ("securityOpt", Expect::Modeled),
+    ("overrideCommand", Expect::Modeled),
+    ("hostRequirements", Expect::Modeled),
+    ("resources", Expect::Modeled),
+    ("labels", Expect::Modeled),
+    ("remoteEnv", Expect::Modeled),
+    (
+        "shutdownAction",
+        Expect::OneOf(&["none", "stopContainer", "stopCompose"]),
+    ),
+    ("features", Expect::Modeled),
+    ("overrideFeatureInstallOrder", Expect::Modeled),
+    ("secrets", Expect::Object),
+    ("customizations", Expect::Modeled),
+    ("extensions", Expect::Modeled),
+    ("settings", Expect::Modeled),
+    ("devc.preBuildScripts", Expect::Modeled),
+    ("devc.postBuildScripts", Expect::Modeled),
+    ("devc.dotfiles", Expect::Modeled),
+    ("devc.lifecycleUser", Expect::Modeled),
+    ("devc.initializeCommandCwd", Expect::Modeled),
+    ("devc.prebuild", Expect::Modeled),
+];
+
+/// Properties of the `build` object
+const BUILD_PROPERTIES: &[(&str, Expect)] = &[
+    ("dockerfile", Expect::Modeled),
+    ("context", Expect::Modeled),
+    ("args", Expect::Modeled),
+    ("target", Expect::Modeled),
+    ("cacheFrom", Expect::Modeled),
+    ("options", Expect::Array),
+    ("secrets", Expect::Modeled),
+];
+
+/// Check comment-free devcontainer.json content for keys the schema doesn't
+/// know and values it doesn't allow
+///
+/// Content that isn't valid JSON yields no issues; parsing reports that.
+fn schema_issues(content: &str) -> Vec<SchemaIssue> {
+    let Ok(serde_json::Value::Object(root)) = serde_json::from_str(content) else {
+        return Vec::new();
+    };
+    let keys = locate_keys(content);
+    let issue_at = |path: &[&str], message: String| {
+        let offset = keys
+            .iter()
+            .find(|(p, _)| p.iter().map(String::as_str).eq(path.iter().copied()))
+            .map_or(0, |(_, offset)| *offset);
+        let (line, column) = line_col(content, offset);
+        SchemaIssue {
+            line,
+            column,
+            message,
+        }
+    };
+
+    let mut issues = Vec::new();
+    check_object(&root, TOP_LEVEL_PROPERTIES, &[], &mut issues, &issue_at);
+    if let Some(serde_json::Value::Object(build)) = root.get("build") {
+        check_object(build, BUILD_PROPERTIES, &["build"], &mut issues, &issue_at);
+    }
+    check_port_specs(&root, &mut issues, &issue_at);
+    issues.sort_by_key(|i| (i.line, i.column));
+    issues
+}
+
+fn check_object(
+    object: &serde_json::Map<String, serde_json::Value>,
+    properties: &[(&str, Expect)],
+    parent: &[&str],
+    issues: &mut Vec<SchemaIssue>,
+    issue_at: &dyn Fn(&[&str], String) -> SchemaIssue,
+) {
+    for (key, value) in object {
+        let path: Vec<&str> = parent.iter().copied().chain([key.as_str()]).collect();
+        let Some((_, expect)) = properties.iter().find(|(name, _)| name == key) else {
+            issues.push(issue_at(
+                &path,
+                format!("unknown property \"{}\"", path.join(".")),
+            ));
+            continue;
+        };
+        let problem = match (expect, value) {
+            (Expect::Modeled, _)
+            | (Expect::String, serde_json::Value::String(_))
+            | (Expect::Bool, serde_json::Value::Bool(_))
+            | (Expect::Array, serde_json::Value::Array(_))
+            | (Expect::Object, serde_json::Value::Object(_)) => None,
+            (Expect::OneOf(allowed), serde_json::Value::String(v)) => (!allowed
+                .contains(&v.as_str()))
+            .then(|| format!("expected one of {}", allowed.join(", "))),
+            (Expect::String | Expect::OneOf(_), _) => Some("expected a string".to_string()),
+            (Expect::Bool, _) => Some("expected a boolean".to_string()),
+            (Expect::Array, _) => Some("expected an array".to_string()),
+            (Expect::Object, _) => Some("expected an object".to_string()),
+        };
+        if let Some(problem) = problem {
+            issues.push(issue_at(
+                &path,
+                format!("invalid \"{}\": {}", path.join("."), problem),
+            ));
+        }
+    }
+}
+
+/// Port strings in `forwardPorts`, `appPort` and `portsAttributes` keys that
+/// would otherwise be skipped without a word
+fn check_port_specs(
+    root: &serde_json::Map<String, serde_json::Value>,
+    issues: &mut Vec<SchemaIssue>,
+    issue_at: &dyn Fn(&[&str], String) -> SchemaIssue,
+) {
+    let strings = |key: &str| -> Vec<String> {
+        match root.get(key) {
+            Some(serde_json::Value::String(s)) => vec![s.clone()],
+            Some(serde_json::Value::Array(items)) => items
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect(),
+            _ => Vec::new(),
+        }
+    };
+    for key in ["forwardPorts", "appPort"] {
+        for spec in strings(key) {
+            if let Some(problem) = port_spec_problem(&spec) {
+                issues.push(issue_at(&[key], format!("{} entry {}", key, problem)));
+            }
+        }
+    }
+    if let Some(serde_json::Value::Object(attrs)) = root.get("portsAttributes") {
+        for spec in attrs.keys() {
+            if let Some(problem) = port_spec_problem(spec) {
+                issues.push(issue_at(
+                    &["portsAttributes", spec],
+                    format!("portsAttributes key {}", problem),
+                ));
+            }
+        }
+    }
+}
+
+/// Byte offsets of every object key in JSON text, with the path of keys
+/// leading to it (array levels appear as `[]`)
+fn locate_keys(content: &str) -> Vec<(Vec<String>, usize)> {
+    struct Frame {
+        is_object: bool,
+        expect_key: bool,
+        key: Option<String>,
+    }
+
+    let bytes = content.as_bytes();
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut keys = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                if let Some(frame) = stack.last_mut().filter(|f| f.is_object && f.expect_key) {
+                    let key = content.get(start + 1..i).unwrap_or_default().to_string();
+                    frame.expect_key = false;
+                    frame.key = Some(key);
+                    let path = stack
+                        .iter()
+                        .map(|f| match (&f.key, f.is_object) {
+                            (Some(key), true) => key.clone(),
+                            _ => "[]".to_string(),
+                        })
+                        .collect();
+                    keys.push((path, start));
+                }
+            }
+            b'{' | b'[' => stack.push(Frame {
+                is_object: bytes[i] == b'{',
+                expect_key: true,
+                key: None,
+            }),
+            b'}' | b']' => {
+                stack.pop();
+            }
+            b',' => {
+                if let Some(frame) = stack.last_mut() {
+                    frame.expect_key = true;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    keys
+}
+
+/// 1-based line and character column of a byte offset
+fn line_col(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
 /// Strip JSON comments (// and /* */) for JSONC support
+///
+/// Comments become spaces (newlines are kept) so line and column positions
+/// in the result match the original text.
 fn strip_json_comments(input: &str) -> String {
     let mut result = String::with_capacity(input.len());
     let mut chars = input.chars().peekable();
@@ -702,25 +1477,30 @@ fn strip_json_comments(input: &str) -> String {
         if c == '/' {
             if let Some(&next) = chars.peek() {
                 if next == '/' {
-                    // Line comment - skip to end of line
+                    // Line comment - blank to end of line
                     chars.next();
+                    result.push_str("  ");
                     while let Some(&nc) = chars.peek() {
                         if nc == '\n' {
                             break;
                         }
+                        result.push(' ');
                         chars.next();
                     }
                     continue;
                 } else if next == '*' {
-                    // Block comment - skip to */
+                    // Block comment - blank to */, keeping line breaks
                     chars.next();
+                    result.push_str("  ");
                     while let Some(nc) = chars.next() {
                         if nc == '*' {
                             if let Some(&'/') = chars.peek() {
                                 chars.next();
+                                result.push_str("  ");
                                 break;
                             }
                         }
+                        result.push(if nc == '\n' { '\n' } else { ' ' });
                     }
                     continue;
                 }
@@ -756,11 +1536,16 @@ mod tests {
                 "ghcr.io/devcontainers/features/node:1": {
                     "version": "18"
                 }
-            }
+            },
+            "overrideFeatureInstallOrder": ["ghcr.io/devcontainers/features/node"]
         }"#;
         let config: DevContainerConfig = serde_json::from_str(json).unwrap();
         assert!(config.features.is_some());
         assert_eq!(config.features.as_ref().unwrap().len(), 2);
+        assert_eq!(
+            config.override_feature_install_order.as_deref(),
+            Some(&["ghcr.io/devcontainers/features/node".to_string()][..])
+        );
     }
 
     #[test]
@@ -780,6 +1565,60 @@ mod tests {
         assert_eq!(build.dockerfile, Some("Dockerfile".to_string()));
     }
 
+    #[test]
+    fn test_parse_resources() {
+        let config: DevContainerConfig = serde_json::from_str(
+            r#"{"image": "ubuntu", "resources": {"cpus": 1.5, "memory": "4g"}}"#,
+        )
+        .unwrap();
+        let resources = config.resources.unwrap();
+        assert_eq!(resources.cpu_limit().unwrap(), Some(1.5));
+        assert_eq!(resources.memory_limit().unwrap(), Some(4 << 30));
+
+        let bad = Resources {
+            cpus: Some(0.0),
+            memory: Some("lots".to_string()),
+        };
+        assert!(bad.cpu_limit().is_err());
+        assert!(bad.memory_limit().is_err());
+    }
+
+    #[test]
+    fn test_parse_memory_size() {
+        assert_eq!(parse_memory_size("1024"), Some(1024));
+        assert_eq!(parse_memory_size("512m"), Some(512 << 20));
+        assert_eq!(parse_memory_size("512MB"), Some(512 << 20));
+        assert_eq!(parse_memory_size("2GiB"), Some(2 << 30));
+        assert_eq!(parse_memory_size("1.5g"), Some(3 << 29));
+        assert_eq!(parse_memory_size(" 8 gb "), Some(8 << 30));
+        for bad in ["", "g", "4x", "-1g", "0", "1.2.3m", "4gbb"] {
+            assert_eq!(parse_memory_size(bad), None, "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_parse_host_requirements_gpu() {
+        let gpu = |json: &str| {
+            serde_json::from_str::<DevContainerConfig>(json)
+                .unwrap()
+                .host_requirements
+                .and_then(|h| h.gpu)
+        };
+        let required = gpu(r#"{"hostRequirements": {"gpu": true, "cpus": 4}}"#).unwrap();
+        assert!(required.is_requested() && !required.is_optional());
+        let optional = gpu(r#"{"hostRequirements": {"gpu": "optional"}}"#).unwrap();
+        assert!(optional.is_requested() && optional.is_optional());
+        let detailed = gpu(r#"{"hostRequirements": {"gpu": {"cores": 2, "memory": "8gb"}}}"#);
+        assert!(matches!(
+            detailed,
+            Some(GpuRequirement::Detailed { cores: Some(2), .. })
+        ));
+        assert!(!gpu(r#"{"hostRequirements": {"gpu": false}}"#)
+            .unwrap()
+            .is_requested());
+        assert!(gpu(r#"{"image": "ubuntu"}"#).is_none());
+    }
+
     #[test]
     fn test_strip_comments() {
         let input = r#"{
@@ -792,6 +1631,69 @@ mod tests {
         assert_eq!(config.name, Some("test".to_string()));
     }
 
+    #[test]
+    fn test_schema_issues_report_line_and_column() {
+        let input = "{\n  // comment with a \"quote\"\n  \"image\": \"ubuntu\",\n  /* block\n  */ \"forwardPort\": [3000],\n  \"build\": { \"dockerfile\": \"Dockerfile\", \"contxt\": \"..\" },\n  \"waitFor\": \"postCreate\"\n}";
+        let (config, issues) =
+            DevContainerConfig::parse_validated(input, Path::new("devcontainer.json"), false)
+                .unwrap();
+        assert_eq!(config.image.as_deref(), Some("ubuntu"));
+        let rendered: Vec<String> = issues.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "5:6: unknown property \"forwardPort\"",
+                "6:42: unknown property \"build.contxt\"",
+                "7:3: invalid \"waitFor\": expected one of initializeCommand, onCreateCommand, updateContentCommand, postCreateCommand, postStartCommand, postAttachCommand",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_schema_issues_accept_spec_and_devc_keys() {
+        let input = r#"{
+            "$schema": "https://example.com/schema.json",
+            "image": "ubuntu",
+            "userEnvProbe": "loginShell",
+            "updateRemoteUserUID": true,
+            "shutdownAction": "stopContainer",
+            "devc.lifecycleUser": "root",
+            "features": { "ghcr.io/devcontainers/features/git:1": { "unknownOption": 1 } }
+        }"#;
+        let (_, issues) =
+            DevContainerConfig::parse_validated(input, Path::new("devcontainer.json"), false)
+                .unwrap();
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn test_schema_issues_report_ignored_port_strings() {
+        let input = "{\n  \"image\": \"ubuntu\",\n  \"forwardPorts\": [3000, \"db:5432\", \"8000-8002\"],\n  \"appPort\": \"8080:3000\",\n  \"portsAttributes\": { \"1-65535\": { \"label\": \"all\" } }\n}";
+        let (_, issues) =
+            DevContainerConfig::parse_validated(input, Path::new("devcontainer.json"), false)
+                .unwrap();
+        let rendered: Vec<String> = issues.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "3:3: forwardPorts entry \"db:5432\" is ignored: host:port forms are not supported, only a port or port range",
+                "4:3: appPort entry \"8080:3000\" is ignored: host:port forms are not supported, only a port or port range",
+                "5:24: portsAttributes key \"1-65535\" spans more than 1000 ports and is ignored",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strict_schema_validation_fails() {
+        let input = "{\"image\": \"ubuntu\", \"updateRemoteUserUID\": \"yes\"}";
+        let err = DevContainerConfig::parse_validated(input, Path::new("devcontainer.json"), true)
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::Schema { ref issues, .. } if issues.len() == 1));
+        assert!(err
+            .to_string()
+            .contains("1:21: invalid \"updateRemoteUserUID\": expected a boolean"));
+    }
+
     #[test]
     fn test_command_variants() {
         // String command
@@ -818,6 +1720,7 @@ mod tests {
             "init": true,
             "privileged": false,
             "capAdd": ["SYS_PTRACE", "NET_ADMIN"],
+            "capDrop": ["MKNOD"],
             "securityOpt": ["seccomp=unconfined"],
             "overrideCommand": false,
             "remoteEnv": {"EDITOR": "vim"},
@@ -827,6 +1730,7 @@ mod tests {
         assert_eq!(config.init, Some(true));
         assert_eq!(config.privileged, Some(false));
         assert_eq!(config.cap_add.as_ref().unwrap().len(), 2);
+        assert_eq!(config.cap_drop, Some(vec!["MKNOD".to_string()]));
         assert_eq!(
             config.security_opt.as_ref().unwrap()[0],
             "seccomp=unconfined"
@@ -837,6 +1741,29 @@ mod tests {
             "vim"
         );
         assert_eq!(config.shutdown_action, Some("stopContainer".to_string()));
+        assert_eq!(
+            config.effective_shutdown_action(),
+            ShutdownAction::StopContainer
+        );
+    }
+
+    #[test]
+    fn test_effective_shutdown_action() {
+        let parse = |json: &str| -> ShutdownAction {
+            serde_json::from_str::<DevContainerConfig>(json)
+                .unwrap()
+                .effective_shutdown_action()
+        };
+        assert_eq!(
+            parse(r#"{"shutdownAction": "stopCompose"}"#),
+            ShutdownAction::StopCompose
+        );
+        assert_eq!(parse(r#"{"shutdownAction": "none"}"#), ShutdownAction::None);
+        assert_eq!(
+            parse(r#"{"shutdownAction": "bogus"}"#),
+            ShutdownAction::None
+        );
+        assert_eq!(parse("{}"), ShutdownAction::None);
     }
 
     #[test]
@@ -874,9 +1801,11 @@ mod tests {
     ) -> PortForwardConfig {
         PortForwardConfig {
             port,
+            transport: PortTransport::Tcp,
             action,
             label: label.map(String::from),
             protocol: protocol.map(String::from),
+            bind_address: PortForwardConfig::DEFAULT_BIND_ADDRESS,
         }
     }
 
@@ -934,6 +1863,90 @@ mod tests {
         assert_eq!(fwd[1], pfc(8080, AutoForwardAction::Silent, None, None));
     }
 
+    #[test]
+    fn test_parse_port_spec() {
+        use PortTransport::{Tcp, Udp};
+        assert_eq!(parse_port_spec("8080"), Some(vec![(8080, Tcp)]));
+        assert_eq!(parse_port_spec("53/udp"), Some(vec![(53, Udp)]));
+        assert_eq!(parse_port_spec("53/UDP"), Some(vec![(53, Udp)]));
+        assert_eq!(
+            parse_port_spec("8000-8002"),
+            Some(vec![(8000, Tcp), (8001, Tcp), (8002, Tcp)])
+        );
+        assert_eq!(
+            parse_port_spec("6000-6001/udp"),
+            Some(vec![(6000, Udp), (6001, Udp)])
+        );
+        assert_eq!(parse_port_spec("9000/tcp"), Some(vec![(9000, Tcp)]));
+        assert_eq!(parse_port_spec("8010-8000"), None);
+        assert_eq!(parse_port_spec("53/sctp"), None);
+        assert_eq!(parse_port_spec("http"), None);
+        assert_eq!(parse_port_spec("0"), None);
+        assert_eq!(parse_port_spec("1-65535"), None);
+        assert_eq!(parse_port_spec("8080:3000"), None);
+    }
+
+    #[test]
+    fn test_auto_forward_config_app_port_ranges_and_udp() {
+        let json = r#"{"appPort": [3000, "8000-8002", "53/udp", "bogus"]}"#;
+        let config: DevContainerConfig = serde_json::from_str(json).unwrap();
+        let fwd = config.auto_forward_config();
+        let ports: Vec<_> = fwd.iter().map(|c| (c.port, c.transport)).collect();
+        assert_eq!(
+            ports,
+            vec![
+                (3000, PortTransport::Tcp),
+                (8000, PortTransport::Tcp),
+                (8001, PortTransport::Tcp),
+                (8002, PortTransport::Tcp),
+                (53, PortTransport::Udp),
+            ]
+        );
+        assert!(fwd.iter().all(|c| c.action == AutoForwardAction::Silent));
+        assert_eq!(config.forward_ports_list(), ports);
+    }
+
+    #[test]
+    fn test_forward_ports_string_ranges_and_udp() {
+        let json = r#"{"forwardPorts": [3000, "3001-3002", "5353/udp", "db:5432"]}"#;
+        let config: DevContainerConfig = serde_json::from_str(json).unwrap();
+        let fwd = config.auto_forward_config();
+        let ports: Vec<_> = fwd.iter().map(|c| (c.port, c.transport)).collect();
+        assert_eq!(
+            ports,
+            vec![
+                (3000, PortTransport::Tcp),
+                (3001, PortTransport::Tcp),
+                (3002, PortTransport::Tcp),
+                (5353, PortTransport::Udp),
+            ]
+        );
+        assert!(fwd.iter().all(|c| c.action == AutoForwardAction::Notify));
+        assert_eq!(config.forward_ports_list(), ports);
+    }
+
+    #[test]
+    fn test_auto_forward_config_ports_attributes_range() {
+        let json = r#"{
+            "appPort": ["8000-8001", "8001/udp"],
+            "portsAttributes": {
+                "8000-8001": {"label": "Cluster"},
+                "9000-9100": {"label": "Unused range"},
+                "8001/udp": {"onAutoForward": "ignore"}
+            }
+        }"#;
+        let config: DevContainerConfig = serde_json::from_str(json).unwrap();
+        let fwd = config.auto_forward_config();
+        // A range key labels declared ports but adds no new ones
+        assert_eq!(fwd.len(), 3);
+        assert_eq!(fwd[0].label.as_deref(), Some("Cluster"));
+        assert_eq!(fwd[1].label.as_deref(), Some("Cluster"));
+        assert_eq!(fwd[1].action, AutoForwardAction::Silent);
+        assert_eq!(fwd[2].transport, PortTransport::Udp);
+        assert_eq!(fwd[2].label, None);
+        assert_eq!(fwd[2].action, AutoForwardAction::Ignore);
+    }
+
     #[test]
     fn test_auto_forward_config_empty() {
         let json = r#"{"image": "ubuntu:22.04"}"#;
@@ -1025,6 +2038,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bind_address_from_ports_attributes() {
+        let json = r#"{
+            "forwardPorts": [3000, {"port": 4000, "bindAddress": "::"}, 5000],
+            "portsAttributes": {
+                "3000": {"bindAddress": "0.0.0.0"},
+                "5000": {"bindAddress": "not-an-address"},
+                "6000": {"bindAddress": "localhost"}
+            }
+        }"#;
+        let config: DevContainerConfig = serde_json::from_str(json).unwrap();
+        let fwd = config.auto_forward_config();
+        let addrs: Vec<(u16, String)> = fwd
+            .iter()
+            .map(|c| (c.port, c.bind_address.to_string()))
+            .collect();
+        assert_eq!(
+            addrs,
+            vec![
+                (3000, "0.0.0.0".to_string()),
+                (4000, "::".to_string()),
+                (5000, "127.0.0.1".to_string()),
+                (6000, "127.0.0.1".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn test_ports_attributes_overrides_forward_ports_label() {
         let json = r#"{
@@ -1070,6 +2110,244 @@ mod tests {
         assert!(config.post_attach_command.is_some());
     }
 
+    #[test]
+    fn test_create_phase_user() {
+        let json = r#"{
+            "image": "ubuntu:22.04",
+            "remoteUser": "vscode",
+            "devc.lifecycleUser": "root"
+        }"#;
+        let config: DevContainerConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.create_phase_user(), Some("root"));
+        assert_eq!(config.effective_remote_user(), Some("vscode"));
+
+        let json = r#"{"image": "ubuntu:22.04", "remoteUser": "vscode"}"#;
+        let config: DevContainerConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.create_phase_user(), Some("vscode"));
+    }
+
+    #[test]
+    fn test_remote_and_container_user_resolution() {
+        let json = r#"{"image": "ubuntu:22.04", "containerUser": "app", "remoteUser": "vscode"}"#;
+        let config: DevContainerConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.effective_container_user(), Some("app"));
+        assert_eq!(config.effective_remote_user(), Some("vscode"));
+
+        // remoteUser defaults to containerUser, never the other way around
+        let json = r#"{"image": "ubuntu:22.04", "containerUser": "app"}"#;
+        let config: DevContainerConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.effective_remote_user(), Some("app"));
+        let json = r#"{"image": "ubuntu:22.04", "remoteUser": "vscode"}"#;
+        let config: DevContainerConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.effective_container_user(), None);
+    }
+
+    #[test]
+    fn test_missing_references() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("devcontainer.json");
+        std::fs::write(dir.path().join("Dockerfile"), "FROM ubuntu\n").unwrap();
+
+        let json = r#"{"build": {"dockerfile": "Dockerfile", "context": ".."}}"#;
+        let config: DevContainerConfig = serde_json::from_str(json).unwrap();
+        assert!(config.missing_references(&config_path).is_empty());
+
+        let json = r#"{"build": {"dockerfile": "Containerfile", "context": "nope"}}"#;
+        let config: DevContainerConfig = serde_json::from_str(json).unwrap();
+        let missing = config.missing_references(&config_path);
+        assert_eq!(missing.len(), 2, "{:?}", missing);
+        assert!(missing[0].starts_with("Dockerfile not found"));
+        assert!(missing[1].starts_with("build context not found"));
+
+        let json = r#"{"dockerComposeFile": "compose.yml", "service": "app"}"#;
+        let config: DevContainerConfig = serde_json::from_str(json).unwrap();
+        let missing = config.missing_references(&config_path);
+        assert!(
+            missing[0].starts_with("compose file not found"),
+            "{:?}",
+            missing
+        );
+    }
+
+    #[test]
+    fn test_config_unresolved_variables() {
+        let json = r#"{
+            "image": "ubuntu",
+            "workspaceFolder": "/workspaces/${localWorkspaceFolderBasename}",
+            "containerEnv": {"A": "${localRoot}", "B": "${localRoot}"}
+        }"#;
+        let config: DevContainerConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.unresolved_variables(), ["${localRoot}"]);
+
+        // Shell variables in lifecycle commands are left for the shell
+        let json = r#"{
+            "image": "ubuntu",
+            "postCreateCommand": "echo ${HOME} && export PATH=${PATH}:/x",
+            "postStartCommand": {"a": ["sh", "-c", "cd ${containerWorkspaceFolder}/${SUBDIR}"]}
+        }"#;
+        let config: DevContainerConfig = serde_json::from_str(json).unwrap();
+        assert!(config.unresolved_variables().is_empty());
+
+        // Fields devc doesn't substitute may use other tools' variables
+        let json = r#"{
+            "image": "ubuntu",
+            "customizations": {"vscode": {"settings": {"x": "${workspaceFolder}/.venv"}}}
+        }"#;
+        let config: DevContainerConfig = serde_json::from_str(json).unwrap();
+        assert!(config.unresolved_variables().is_empty());
+    }
+
+    #[test]
+    fn test_initialize_command_cwd() {
+        let json = r#"{
+            "image": "ubuntu:22.04",
+            "initializeCommand": "./setup.sh",
+            "devc.initializeCommandCwd": "config"
+        }"#;
+        let config: DevContainerConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.initialize_command_cwd, Some("config".to_string()));
+    }
+
+    #[test]
+    fn test_mounts_string_and_object_forms() {
+        let json = r#"{
+            "image": "ubuntu:22.04",
+            "mounts": [
+                "source=/host/cache,target=/cache,type=bind",
+                {"source": "${localWorkspaceFolder}/data", "target": "/data", "type": "bind", "readOnly": true},
+                {"source": "node-modules", "target": "/workspace/node_modules", "type": "volume"}
+            ]
+        }"#;
+        let config: DevContainerConfig = serde_json::from_str(json).unwrap();
+        let mounts = config.mounts.unwrap();
+        assert_eq!(
+            mounts[0],
+            Mount::String("source=/host/cache,target=/cache,type=bind".to_string())
+        );
+        assert_eq!(
+            mounts[1],
+            Mount::Object(MountObject {
+                mount_type: Some("bind".to_string()),
+                source: Some("${localWorkspaceFolder}/data".to_string()),
+                target: "/data".to_string(),
+                read_only: Some(true),
+            })
+        );
+        assert_eq!(
+            mounts[2],
+            Mount::Object(MountObject {
+                mount_type: Some("volume".to_string()),
+                source: Some("node-modules".to_string()),
+                target: "/workspace/node_modules".to_string(),
+                read_only: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_substitute_variables_expands_mount_sources() {
+        std::env::set_var("DEVC_TEST_MOUNT_HOME", "/home/dev");
+        let json = r#"{
+            "image": "ubuntu:22.04",
+            "mounts": [
+                "source=${localEnv:DEVC_TEST_MOUNT_HOME}/.cache,target=/home/vscode/.cache,type=bind",
+                {"source": "${localWorkspaceFolder}/data", "target": "${containerWorkspaceFolder}/data", "type": "bind"}
+            ]
+        }"#;
+        let mut config: DevContainerConfig = serde_json::from_str(json).unwrap();
+        let ctx = crate::SubstitutionContext::new(
+            "/home/dev/project".to_string(),
+            "/workspaces/project".to_string(),
+        );
+        config.substitute_variables(&ctx);
+        std::env::remove_var("DEVC_TEST_MOUNT_HOME");
+
+        let mounts = config.mounts.unwrap();
+        assert_eq!(
+            mounts[0],
+            Mount::String(
+                "source=/home/dev/.cache,target=/home/vscode/.cache,type=bind".to_string()
+            )
+        );
+        match &mounts[1] {
+            Mount::Object(obj) => {
+                assert_eq!(obj.source.as_deref(), Some("/home/dev/project/data"));
+                assert_eq!(obj.target, "/workspaces/project/data");
+            }
+            other => panic!("expected object mount, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_invalid_mount_object_names_field() {
+        let parse = |mount: &str| {
+            let json = format!(r#"{{"image": "x", "mounts": [{}]}}"#, mount);
+            serde_json::from_str::<DevContainerConfig>(&json)
+                .unwrap_err()
+                .to_string()
+        };
+
+        let err = parse(r#"{"source": "vol", "type": "volume"}"#);
+        assert!(err.contains("missing field `target`"), "{}", err);
+
+        let err = parse(r#"{"source": "vol", "target": "/data", "type": "nfs"}"#);
+        assert!(err.contains("unknown `type` \"nfs\""), "{}", err);
+
+        let err = parse(r#"{"target": "/data", "readOnly": "yes"}"#);
+        assert!(err.contains("invalid mount object"), "{}", err);
+
+        let err = parse("42");
+        assert!(err.contains("expected a string or object"), "{}", err);
+    }
+
+    #[test]
+    fn test_vscode_extensions() {
+        let config: DevContainerConfig = serde_json::from_str(
+            r#"{"customizations": {"vscode": {"extensions": ["rust-lang.rust-analyzer", "tamasfe.even-better-toml"]}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.vscode_extensions(),
+            ["rust-lang.rust-analyzer", "tamasfe.even-better-toml"]
+        );
+
+        let config: DevContainerConfig =
+            serde_json::from_str(r#"{"customizations": {"vscode": {"settings": {}}}}"#).unwrap();
+        assert!(config.vscode_extensions().is_empty());
+    }
+
+    #[test]
+    fn test_dotfiles_config_from_customizations() {
+        let json = r#"{
+            "image": "ubuntu:22.04",
+            "customizations": {
+                "devc": {
+                    "dotfiles": {"repository": "https://github.com/team/dots", "installCommand": "make"}
+                }
+            }
+        }"#;
+        let config: DevContainerConfig = serde_json::from_str(json).unwrap();
+        let dotfiles = config.dotfiles_config().unwrap();
+        assert_eq!(
+            dotfiles.repository.as_deref(),
+            Some("https://github.com/team/dots")
+        );
+        assert_eq!(dotfiles.install_command.as_deref(), Some("make"));
+
+        let json = r#"{
+            "image": "ubuntu:22.04",
+            "devc.dotfiles": {"localPath": "~/dots"},
+            "customizations": {"devc": {"dotfiles": {"repository": "https://example.com/x"}}}
+        }"#;
+        let config: DevContainerConfig = serde_json::from_str(json).unwrap();
+        let dotfiles = config.dotfiles_config().unwrap();
+        assert_eq!(dotfiles.local_path.as_deref(), Some("~/dots"));
+        assert!(dotfiles.repository.is_none());
+
+        let config: DevContainerConfig = serde_json::from_str(r#"{"image": "x"}"#).unwrap();
+        assert!(config.dotfiles_config().is_none());
+    }
+
     #[test]
     fn test_load_all_from_dir_multiple_subdirs() {
         let tmp = tempfile::tempdir().unwrap();
@@ -1093,6 +2371,45 @@ mod tests {
         assert!(results[2].1.ends_with("python/devcontainer.json"));
     }
 
+    #[test]
+    fn test_load_from_dir_root_config() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp.path().join(".devcontainer.json"),
+            r#"{"image": "alpine:3"}"#,
+        )
+        .unwrap();
+
+        let (config, path) = DevContainerConfig::load_from_dir(tmp.path()).unwrap();
+        assert_eq!(config.image.as_deref(), Some("alpine:3"));
+        assert_eq!(path, tmp.path().join(".devcontainer.json"));
+
+        let results = DevContainerConfig::load_all_from_dir(tmp.path());
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].1, tmp.path().join(".devcontainer.json"));
+    }
+
+    #[test]
+    fn test_folder_config_takes_precedence_over_root() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dc = tmp.path().join(".devcontainer");
+        std::fs::create_dir_all(&dc).unwrap();
+        std::fs::write(dc.join("devcontainer.json"), r#"{"image": "ubuntu:22.04"}"#).unwrap();
+        std::fs::write(
+            tmp.path().join(".devcontainer.json"),
+            r#"{"image": "alpine:3"}"#,
+        )
+        .unwrap();
+
+        let (config, path) = DevContainerConfig::load_from_dir(tmp.path()).unwrap();
+        assert_eq!(config.image.as_deref(), Some("ubuntu:22.04"));
+        assert_eq!(path, dc.join("devcontainer.json"));
+
+        let results = DevContainerConfig::load_all_from_dir(tmp.path());
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].1, dc.join("devcontainer.json"));
+    }
+
     #[test]
     fn test_load_all_from_dir_single() {
         let tmp = tempfile::tempdir().unwrap();
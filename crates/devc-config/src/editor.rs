@@ -0,0 +1,139 @@
+//! Opening config files in the user's editor
+
+use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
+
+#[cfg(windows)]
+const PLATFORM_EDITOR: &str = "notepad";
+#[cfg(not(windows))]
+const PLATFORM_EDITOR: &str = "vi";
+
+/// The editor command to launch, plus a warning when a preferred choice was
+/// skipped because its program isn't installed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Editor {
+    /// Command line, e.g. `vim` or `code --wait`
+    pub command: String,
+    pub warning: Option<String>,
+}
+
+/// Pick the editor: `defaults.editor`, then `$VISUAL`, then `$EDITOR`, then
+/// `vi` (`notepad` on Windows)
+pub fn editor(configured: Option<&str>) -> Editor {
+    choose_editor(
+        configured,
+        std::env::var("VISUAL").ok().as_deref(),
+        std::env::var("EDITOR").ok().as_deref(),
+        |program| find_program(program).is_some(),
+    )
+}
+
+/// Candidates that are set but whose program can't be found are skipped, so
+/// a typo in the config falls back to a working editor instead of failing
+/// once the terminal has been handed over
+fn choose_editor(
+    configured: Option<&str>,
+    visual: Option<&str>,
+    env_editor: Option<&str>,
+    exists: impl Fn(&str) -> bool,
+) -> Editor {
+    let candidates = [
+        ("defaults.editor", configured),
+        ("$VISUAL", visual),
+        ("$EDITOR", env_editor),
+    ];
+    let mut warning = None;
+    for (source, command) in candidates {
+        let Some(command) = command.map(str::trim).filter(|c| !c.is_empty()) else {
+            continue;
+        };
+        let program = command.split_whitespace().next().unwrap_or(command);
+        if exists(program) {
+            return Editor {
+                command: command.to_string(),
+                warning,
+            };
+        }
+        warning.get_or_insert_with(|| {
+            format!("Editor '{}' from {} not found on PATH", program, source)
+        });
+    }
+    Editor {
+        command: PLATFORM_EDITOR.to_string(),
+        warning,
+    }
+}
+
+/// Resolve `program` the way the OS would: paths as-is, bare names via `PATH`
+fn find_program(program: &str) -> Option<PathBuf> {
+    let path = Path::new(program);
+    if path.components().count() > 1 {
+        return path.is_file().then(|| path.to_path_buf());
+    }
+    let dirs = std::env::var_os("PATH")?;
+    std::env::split_paths(&dirs).find_map(|dir| {
+        let candidate = dir.join(program);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        #[cfg(windows)]
+        {
+            let exe = candidate.with_extension("exe");
+            if exe.is_file() {
+                return Some(exe);
+            }
+        }
+        None
+    })
+}
+
+/// Open `path` with an editor command line and wait for it to exit
+pub fn open_in_editor(command: &str, path: &Path) -> std::io::Result<ExitStatus> {
+    let mut parts = command.split_whitespace();
+    let program = parts.next().unwrap_or(PLATFORM_EDITOR);
+    std::process::Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_configured_editor_wins_over_environment() {
+        let editor = choose_editor(Some("code --wait"), Some("emacs"), Some("nano"), |_| true);
+        assert_eq!(editor.command, "code --wait");
+        assert_eq!(editor.warning, None);
+    }
+
+    #[test]
+    fn test_visual_then_editor_then_platform_default() {
+        let editor = choose_editor(None, Some("emacs"), Some("nano"), |_| true);
+        assert_eq!(editor.command, "emacs");
+        let editor = choose_editor(None, Some(""), Some("nano"), |_| true);
+        assert_eq!(editor.command, "nano");
+        let editor = choose_editor(None, None, None, |_| true);
+        assert_eq!(editor.command, PLATFORM_EDITOR);
+    }
+
+    #[test]
+    fn test_missing_configured_editor_warns_and_falls_back() {
+        let editor = choose_editor(Some("subl -w"), None, Some("nano"), |p| p == "nano");
+        assert_eq!(editor.command, "nano");
+        assert_eq!(
+            editor.warning.as_deref(),
+            Some("Editor 'subl' from defaults.editor not found on PATH")
+        );
+    }
+
+    #[test]
+    fn test_find_program_accepts_paths_and_bare_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("my-editor");
+        std::fs::write(&script, "").unwrap();
+        assert_eq!(find_program(script.to_str().unwrap()), Some(script));
+        assert_eq!(find_program("devc-no-such-editor-xyz"), None);
+    }
+}
@@ -1,5 +1,6 @@
 //! Error types for configuration parsing
 
+use crate::SchemaIssue;
 use std::path::PathBuf;
 use thiserror::Error;
 
@@ -23,12 +24,24 @@ pub enum ConfigError {
         source: serde_json::Error,
     },
 
+    #[error("{} does not match the devcontainer.json schema: {}", path.display(), join_issues(issues))]
+    Schema {
+        path: PathBuf,
+        issues: Vec<SchemaIssue>,
+    },
+
     #[error("Config file not found: {0}")]
     NotFound(PathBuf),
 
     #[error("Invalid configuration: {0}")]
     Invalid(String),
 
+    #[error("Unknown profile '{name}' (available: {})", if available.is_empty() { "none".to_string() } else { available.join(", ") })]
+    UnknownProfile {
+        name: String,
+        available: Vec<String>,
+    },
+
     #[error("Failed to determine config directory")]
     NoConfigDir,
 
@@ -42,4 +55,12 @@ pub enum ConfigError {
     },
 }
 
+fn join_issues(issues: &[SchemaIssue]) -> String {
+    issues
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 pub type Result<T> = std::result::Result<T, ConfigError>;
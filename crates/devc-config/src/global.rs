@@ -5,7 +5,7 @@
 use crate::{ConfigError, Result};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
@@ -19,6 +19,80 @@ pub struct GlobalConfig {
     pub providers: ProvidersConfig,
     pub credentials: CredentialsConfig,
     pub agents: AgentsConfig,
+    pub ui: UiConfig,
+    pub features: FeaturesConfig,
+    /// Named overlays (`[profiles.<name>]`), selected with `--profile` or
+    /// `DEVC_PROFILE`. Kept as raw tables so only the keys they set override
+    /// the base config.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, toml::Table>,
+}
+
+/// Environment variable naming the profile to overlay at load time
+pub const PROFILE_ENV_VAR: &str = "DEVC_PROFILE";
+
+/// Environment variable naming a config file to use instead of the default
+pub const CONFIG_ENV_VAR: &str = "DEVC_CONFIG";
+
+/// TUI presentation settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiConfig {
+    /// Initial ordering of the container list
+    pub sort_order: SortOrder,
+    /// Show full container and image IDs instead of 12-character short IDs
+    pub full_ids: bool,
+    /// Group the container list under a header per workspace folder
+    pub group_by_workspace: bool,
+    /// Prefix each line in the logs view with its timestamp
+    pub log_timestamps: bool,
+}
+
+/// Container list ordering
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// Running containers first, then by lifecycle state, then by name
+    #[default]
+    Status,
+    /// Alphabetical by name
+    Name,
+    /// Most recently used first
+    Recent,
+}
+
+impl std::fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Status => "status",
+            Self::Name => "name",
+            Self::Recent => "recent",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Dev container feature download settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FeaturesConfig {
+    /// Credentials for OCI feature registries, keyed by registry host
+    /// (`ghcr.io`, `registry.example.com:5000`); used instead of
+    /// `~/.docker/config.json` for that registry
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub registry_auth: HashMap<String, RegistryAuth>,
+}
+
+/// Credentials for one registry
+///
+/// Either `username` and `password` (an access token usually works as the
+/// password), or a `token` sent as a bearer token without the token exchange.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RegistryAuth {
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub token: Option<String>,
 }
 
 /// Agent injection configuration
@@ -90,6 +164,9 @@ pub struct DefaultsConfig {
     /// Only needed when terminal resize support is required (e.g., with Podman).
     /// When disabled, `devc ssh` falls back to `docker/podman exec -it`.
     pub ssh_enabled: Option<bool>,
+    /// How interactive shells keep the container's terminal size in sync.
+    /// Unset follows `ssh_enabled`: `ssh` when it is on, `exec` otherwise.
+    pub resize_mode: Option<ResizeMode>,
     /// Path to SSH private key for container authentication
     pub ssh_key_path: Option<String>,
     /// Forward URL open requests from container to host browser (default: true)
@@ -102,6 +179,73 @@ pub struct DefaultsConfig {
     /// `silent`). Does NOT cause browsers to open for ports that were never
     /// asked to open one — it only governs configured browser-open requests.
     pub auto_open_browser: Option<bool>,
+    /// When to pull the base image of image-based configs (default: `always`)
+    pub pull_policy: Option<PullPolicy>,
+    /// Ask before running `shutdownAction` for containers when quitting the TUI (default: true)
+    pub confirm_shutdown: Option<bool>,
+    /// Stop running containers unused for this many minutes while the TUI is open (0 = disabled)
+    pub idle_stop_minutes: Option<u32>,
+    /// Warn when a Dockerfile build context exceeds this many MB after
+    /// `.dockerignore` is applied (default: 500, 0 = never warn)
+    pub build_context_warn_mb: Option<u64>,
+    /// Editor for `devc config --edit` and the TUI's devcontainer.json edit,
+    /// e.g. `code --wait`; overrides `$VISUAL` and `$EDITOR`
+    pub editor: Option<String>,
+}
+
+/// Mechanism that carries terminal resizes into a container's shell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResizeMode {
+    /// SSH to a dropbear daemon injected into the container. Window changes
+    /// reach the remote PTY automatically, at the cost of a second process
+    /// in the container and dropbear baked into the image.
+    Ssh,
+    /// Plain `docker/podman exec -it`. Nothing extra runs in the container,
+    /// but window changes only arrive when the host side forwards SIGWINCH
+    /// to the exec session (or `devc resize` is run by hand).
+    Exec,
+}
+
+impl DefaultsConfig {
+    /// Resize mode in effect: `resize_mode` if set, else SSH when `ssh_enabled`
+    pub fn effective_resize_mode(&self) -> ResizeMode {
+        self.resize_mode
+            .unwrap_or(if self.ssh_enabled.unwrap_or(false) {
+                ResizeMode::Ssh
+            } else {
+                ResizeMode::Exec
+            })
+    }
+}
+
+/// Image pull policy for image-based devcontainers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PullPolicy {
+    /// Pull on every build, even if the image exists locally
+    #[default]
+    Always,
+    /// Pull only when the image is not present locally
+    Missing,
+    /// Never pull; fail if the image is not present locally
+    Never,
+}
+
+impl std::str::FromStr for PullPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "always" => Ok(Self::Always),
+            "missing" => Ok(Self::Missing),
+            "never" => Ok(Self::Never),
+            other => Err(format!(
+                "invalid pull policy '{}': expected always, missing or never",
+                other
+            )),
+        }
+    }
 }
 
 impl Default for DefaultsConfig {
@@ -115,10 +259,16 @@ impl Default for DefaultsConfig {
             // Default to false - SSH injection is only needed for terminal resize support
             // and adds complexity to the build. Users who need terminal resize can enable it.
             ssh_enabled: Some(false),
+            resize_mode: None,
             ssh_key_path: None,
             url_forwarding: Some(true),
             auto_forward_ports: Some(true),
             auto_open_browser: Some(true),
+            pull_policy: None,
+            confirm_shutdown: Some(true),
+            idle_stop_minutes: Some(0),
+            build_context_warn_mb: Some(500),
+            editor: None,
         }
     }
 }
@@ -206,14 +356,79 @@ fn default_podman_socket() -> String {
 }
 
 impl GlobalConfig {
-    /// Load global configuration from the default path
+    /// Load global configuration from the default path, with the profile
+    /// named by `DEVC_PROFILE` (if any) applied
+    ///
+    /// A file chosen through `DEVC_CONFIG` must exist; the default one falls
+    /// back to defaults when missing.
     pub fn load() -> Result<Self> {
-        let path = Self::config_path()?;
-        Self::load_from(&path)
+        let config = match Self::explicit_config_path() {
+            Some(path) => Self::load_existing(&path)?,
+            None => Self::load_from(&Self::config_path()?)?,
+        };
+        config.with_selected_profile()
+    }
+
+    /// The config file chosen through `DEVC_CONFIG` (`--config`), if any
+    pub fn explicit_config_path() -> Option<PathBuf> {
+        std::env::var_os(CONFIG_ENV_VAR)
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+    }
+
+    /// Load from a path that has to exist, rather than defaulting when missing
+    pub fn load_existing(path: &Path) -> Result<Self> {
+        std::fs::metadata(path).map_err(|e| ConfigError::ReadError {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        Self::load_from(path)
+    }
+
+    fn with_selected_profile(self) -> Result<Self> {
+        match Self::selected_profile() {
+            Some(name) => self.with_profile(&name),
+            None => Ok(self),
+        }
+    }
+
+    /// The profile selected through `DEVC_PROFILE`
+    pub fn selected_profile() -> Option<String> {
+        std::env::var(PROFILE_ENV_VAR)
+            .ok()
+            .filter(|name| !name.is_empty())
+    }
+
+    /// This config with the named profile's keys overlaid on it
+    ///
+    /// Tables merge key by key, so a profile setting `defaults.provider` leaves
+    /// the other `[defaults]` keys alone. The result keeps `profiles` as is.
+    pub fn with_profile(&self, name: &str) -> Result<Self> {
+        let overlay = self
+            .profiles
+            .get(name)
+            .ok_or_else(|| ConfigError::UnknownProfile {
+                name: name.to_string(),
+                available: self.profiles.keys().cloned().collect(),
+            })?;
+
+        let mut merged =
+            toml::Table::try_from(self).map_err(|e| ConfigError::Invalid(e.to_string()))?;
+        merge_tables(&mut merged, overlay);
+        // A profile can't redefine the profiles themselves
+        merged.insert(
+            "profiles".to_string(),
+            toml::Table::try_from(&self.profiles)
+                .map_err(|e| ConfigError::Invalid(e.to_string()))?
+                .into(),
+        );
+        merged
+            .try_into()
+            .map_err(|e| ConfigError::Invalid(format!("profile '{}': {}", name, e)))
     }
 
     /// Load global configuration from a specific path
-    pub fn load_from(path: &PathBuf) -> Result<Self> {
+    pub fn load_from(path: &Path) -> Result<Self> {
         if !path.exists() {
             tracing::debug!("Config file not found at {:?}, using defaults", path);
             let default_config = Self::default();
@@ -225,12 +440,12 @@ impl GlobalConfig {
         }
 
         let content = std::fs::read_to_string(path).map_err(|e| ConfigError::ReadError {
-            path: path.clone(),
+            path: path.to_path_buf(),
             source: e,
         })?;
 
         let config: Self = toml::from_str(&content).map_err(|e| ConfigError::TomlParseError {
-            path: path.clone(),
+            path: path.to_path_buf(),
             source: e,
         })?;
 
@@ -272,23 +487,79 @@ impl GlobalConfig {
     /// Load latest config from disk, apply a mutation, and persist atomically.
     ///
     /// This is the safest API for live settings updates when multiple devc
-    /// processes may write concurrently.
+    /// processes may write concurrently. The mutator sees the config with the
+    /// selected profile applied, and so does the caller afterwards.
     pub fn update_atomically<F>(mutator: F) -> Result<Self>
     where
         F: FnOnce(&mut Self),
     {
         let path = Self::config_path()?;
-        Self::update_atomically_at(&path, mutator)
+        Self::update_with_profile_at(&path, Self::selected_profile().as_deref(), mutator)
+    }
+
+    /// Like `update_atomically_at`, but with the named profile applied to what
+    /// the mutator sees
+    ///
+    /// Only the keys the mutator changes are written back, so values that come
+    /// from the profile don't leak into the base config. A changed key the
+    /// profile overrides is written to `profiles.<name>` instead, where it
+    /// takes effect.
+    pub fn update_with_profile_at<F>(path: &Path, profile: Option<&str>, mutator: F) -> Result<Self>
+    where
+        F: FnOnce(&mut Self),
+    {
+        let Some(profile) = profile else {
+            return Self::update_atomically_at(path, mutator);
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| ConfigError::WriteError {
+                path: path.to_path_buf(),
+                source: e,
+            })?;
+        }
+
+        with_path_lock(path, || {
+            let base = Self::load_from(path)?;
+            let before = base.with_profile(profile)?;
+            let mut after = before.clone();
+            mutator(&mut after);
+
+            let to_table = |config: &Self| {
+                toml::Table::try_from(config).map_err(|e| ConfigError::Invalid(e.to_string()))
+            };
+            let mut latest = to_table(&base)?;
+            let mut overlay = base.profiles.get(profile).cloned().unwrap_or_default();
+            apply_changes_with_overlay(
+                &mut latest,
+                &mut overlay,
+                &to_table(&before)?,
+                &to_table(&after)?,
+            );
+            if let Some(toml::Value::Table(profiles)) = latest.get_mut("profiles") {
+                profiles.insert(profile.to_string(), toml::Value::Table(overlay));
+            }
+            let latest: Self = latest
+                .try_into()
+                .map_err(|e| ConfigError::Invalid(e.to_string()))?;
+
+            let content =
+                toml::to_string_pretty(&latest).map_err(|e| ConfigError::Invalid(e.to_string()))?;
+            atomic_write(path, content.as_bytes()).map_err(|e| ConfigError::WriteError {
+                path: path.to_path_buf(),
+                source: e,
+            })?;
+            latest.with_profile(profile)
+        })
     }
 
     /// Like `update_atomically`, but for tests/custom paths.
-    pub fn update_atomically_at<F>(path: &PathBuf, mutator: F) -> Result<Self>
+    pub fn update_atomically_at<F>(path: &Path, mutator: F) -> Result<Self>
     where
         F: FnOnce(&mut Self),
     {
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent).map_err(|e| ConfigError::WriteError {
-                path: path.clone(),
+                path: path.to_path_buf(),
                 source: e,
             })?;
         }
@@ -299,18 +570,22 @@ impl GlobalConfig {
             let content =
                 toml::to_string_pretty(&latest).map_err(|e| ConfigError::Invalid(e.to_string()))?;
             atomic_write(path, content.as_bytes()).map_err(|e| ConfigError::WriteError {
-                path: path.clone(),
+                path: path.to_path_buf(),
                 source: e,
             })?;
             Ok(latest)
         })
     }
 
-    /// Get the default config file path.
+    /// Get the config file path.
     ///
-    /// Checks `DEVC_CONFIG_DIR` first, then `DEVC_STATE_DIR/config/`,
-    /// then falls back to the XDG/directories crate default.
+    /// Uses the file named by `DEVC_CONFIG` if set, then checks
+    /// `DEVC_CONFIG_DIR`, then `DEVC_STATE_DIR/config/`, then falls back to
+    /// the XDG/directories crate default.
     pub fn config_path() -> Result<PathBuf> {
+        if let Some(path) = Self::explicit_config_path() {
+            return Ok(path);
+        }
         if let Ok(dir) = std::env::var("DEVC_CONFIG_DIR") {
             return Ok(PathBuf::from(dir).join("config.toml"));
         }
@@ -418,6 +693,100 @@ fn acquire_lock(path: &Path) -> std::io::Result<PathLockGuard> {
     ))
 }
 
+/// Recursively overlay `overlay` onto `base`: nested tables merge, anything
+/// else replaces the base value
+fn merge_tables(base: &mut toml::Table, overlay: &toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(overlay_table)) => {
+                merge_tables(base_table, overlay_table);
+            }
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Write to `target` whatever differs between `before` and `after`: changed
+/// and added keys are set, removed ones dropped, nested tables compared key by
+/// key
+fn apply_changes(target: &mut toml::Table, before: &toml::Table, after: &toml::Table) {
+    for (key, value) in after {
+        match (before.get(key), value) {
+            (Some(old), new) if old == new => {}
+            (Some(toml::Value::Table(old)), toml::Value::Table(new)) => {
+                let entry = target
+                    .entry(key.clone())
+                    .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+                if !entry.is_table() {
+                    *entry = toml::Value::Table(toml::Table::new());
+                }
+                if let toml::Value::Table(target) = entry {
+                    apply_changes(target, old, new);
+                }
+            }
+            _ => {
+                target.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    for key in before.keys() {
+        if !after.contains_key(key) {
+            target.remove(key);
+        }
+    }
+}
+
+/// Like `apply_changes`, but keys that `overlay` (a profile) sets are changed
+/// in the overlay rather than in `target`, so the edit isn't masked by it
+fn apply_changes_with_overlay(
+    target: &mut toml::Table,
+    overlay: &mut toml::Table,
+    before: &toml::Table,
+    after: &toml::Table,
+) {
+    let mut plain_before = toml::Table::new();
+    let mut plain_after = toml::Table::new();
+    for (key, value) in after {
+        let old = before.get(key);
+        if old == Some(value) {
+            continue;
+        }
+        match (overlay.get_mut(key), old, value) {
+            (
+                Some(toml::Value::Table(overlay)),
+                Some(toml::Value::Table(old)),
+                toml::Value::Table(new),
+            ) => {
+                let entry = target
+                    .entry(key.clone())
+                    .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+                if !entry.is_table() {
+                    *entry = toml::Value::Table(toml::Table::new());
+                }
+                if let toml::Value::Table(target) = entry {
+                    apply_changes_with_overlay(target, overlay, old, new);
+                }
+            }
+            (Some(overlaid), _, _) => *overlaid = value.clone(),
+            (None, _, _) => {
+                if let Some(old) = old {
+                    plain_before.insert(key.clone(), old.clone());
+                }
+                plain_after.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    for key in before.keys() {
+        if !after.contains_key(key) {
+            overlay.remove(key);
+            target.remove(key);
+        }
+    }
+    apply_changes(target, &plain_before, &plain_after);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -433,6 +802,59 @@ mod tests {
         assert!(config.is_first_run(), "Default config should be first run");
     }
 
+    #[test]
+    fn test_profile_overlays_base_config() {
+        let config: GlobalConfig = toml::from_str(
+            r#"
+[defaults]
+provider = "docker"
+shell = "/bin/zsh"
+
+[providers.docker]
+socket = "/var/run/docker.sock"
+
+[profiles.work.defaults]
+provider = "podman"
+
+[profiles.work.providers.podman]
+socket = "/run/user/1000/podman/podman.sock"
+"#,
+        )
+        .unwrap();
+
+        let work = config.with_profile("work").unwrap();
+        assert_eq!(work.defaults.provider, "podman");
+        // Keys the profile doesn't set keep their base values
+        assert_eq!(work.defaults.shell, "/bin/zsh");
+        assert_eq!(work.providers.docker.socket, "/var/run/docker.sock");
+        assert_eq!(
+            work.providers.podman.socket,
+            "/run/user/1000/podman/podman.sock"
+        );
+        assert_eq!(work.profiles.len(), 1);
+        assert_eq!(config.defaults.provider, "docker");
+    }
+
+    #[test]
+    fn test_unknown_profile_lists_available() {
+        let config: GlobalConfig =
+            toml::from_str("[profiles.home.defaults]\nprovider = \"docker\"\n").unwrap();
+        let err = config.with_profile("laptop").unwrap_err();
+        assert!(matches!(err, ConfigError::UnknownProfile { .. }));
+        assert_eq!(
+            err.to_string(),
+            "Unknown profile 'laptop' (available: home)"
+        );
+    }
+
+    #[test]
+    fn test_profile_with_invalid_value_is_rejected() {
+        let config: GlobalConfig =
+            toml::from_str("[profiles.bad.ui]\nsort_order = \"sideways\"\n").unwrap();
+        let err = config.with_profile("bad").unwrap_err().to_string();
+        assert!(err.contains("profile 'bad'"), "{}", err);
+    }
+
     #[test]
     fn test_parse_config() {
         let toml = r#"
@@ -466,6 +888,91 @@ env_forward = ["OPENAI_API_KEY"]
         );
     }
 
+    #[test]
+    fn test_parse_pull_policy() {
+        let config: GlobalConfig = toml::from_str("[defaults]\npull_policy = \"never\"\n").unwrap();
+        assert_eq!(config.defaults.pull_policy, Some(PullPolicy::Never));
+        assert_eq!(GlobalConfig::default().defaults.pull_policy, None);
+        assert_eq!("missing".parse::<PullPolicy>(), Ok(PullPolicy::Missing));
+        assert!("sometimes".parse::<PullPolicy>().is_err());
+    }
+
+    #[test]
+    fn test_resize_mode_follows_ssh_enabled_unless_set() {
+        let mut defaults = DefaultsConfig::default();
+        assert_eq!(defaults.effective_resize_mode(), ResizeMode::Exec);
+        defaults.ssh_enabled = Some(true);
+        assert_eq!(defaults.effective_resize_mode(), ResizeMode::Ssh);
+
+        let config: GlobalConfig =
+            toml::from_str("[defaults]\nssh_enabled = true\nresize_mode = \"exec\"\n").unwrap();
+        assert_eq!(config.defaults.resize_mode, Some(ResizeMode::Exec));
+        assert_eq!(config.defaults.effective_resize_mode(), ResizeMode::Exec);
+    }
+
+    #[test]
+    fn test_parse_build_context_warn_mb() {
+        let config: GlobalConfig =
+            toml::from_str("[defaults]\nbuild_context_warn_mb = 0\n").unwrap();
+        assert_eq!(config.defaults.build_context_warn_mb, Some(0));
+        assert_eq!(
+            GlobalConfig::default().defaults.build_context_warn_mb,
+            Some(500)
+        );
+    }
+
+    #[test]
+    fn test_parse_ui_sort_order() {
+        let config: GlobalConfig = toml::from_str("[ui]\nsort_order = \"recent\"\n").unwrap();
+        assert_eq!(config.ui.sort_order, SortOrder::Recent);
+        assert_eq!(GlobalConfig::default().ui.sort_order, SortOrder::Status);
+        assert!(toml::from_str::<GlobalConfig>("[ui]\nsort_order = \"size\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_ui_full_ids() {
+        let config: GlobalConfig = toml::from_str("[ui]\nfull_ids = true\n").unwrap();
+        assert!(config.ui.full_ids);
+        assert!(!GlobalConfig::default().ui.full_ids);
+    }
+
+    #[test]
+    fn test_parse_ui_group_by_workspace() {
+        let config: GlobalConfig = toml::from_str("[ui]\ngroup_by_workspace = true\n").unwrap();
+        assert!(config.ui.group_by_workspace);
+        assert!(!GlobalConfig::default().ui.group_by_workspace);
+    }
+
+    #[test]
+    fn test_parse_ui_log_timestamps() {
+        let config: GlobalConfig = toml::from_str("[ui]\nlog_timestamps = true\n").unwrap();
+        assert!(config.ui.log_timestamps);
+        assert!(!GlobalConfig::default().ui.log_timestamps);
+    }
+
+    #[test]
+    fn test_parse_features_registry_auth() {
+        let config: GlobalConfig = toml::from_str(
+            r#"
+[features.registry_auth."registry.example.com"]
+username = "ci"
+password = "secret"
+
+[features.registry_auth."ghcr.io"]
+token = "ghp_abc"
+"#,
+        )
+        .unwrap();
+        let auth = &config.features.registry_auth;
+        assert_eq!(auth["registry.example.com"].username.as_deref(), Some("ci"));
+        assert_eq!(
+            auth["registry.example.com"].password.as_deref(),
+            Some("secret")
+        );
+        assert_eq!(auth["ghcr.io"].token.as_deref(), Some("ghp_abc"));
+        assert!(GlobalConfig::default().features.registry_auth.is_empty());
+    }
+
     #[test]
     fn test_save_load_round_trip() {
         let tmp = tempfile::tempdir().unwrap();
@@ -489,6 +996,20 @@ env_forward = ["OPENAI_API_KEY"]
         assert_eq!(config.defaults.shell, "/bin/bash");
     }
 
+    #[test]
+    fn test_load_existing_requires_the_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("ci.toml");
+        assert!(matches!(
+            GlobalConfig::load_existing(&path),
+            Err(ConfigError::ReadError { .. })
+        ));
+
+        std::fs::write(&path, "[defaults]\nprovider = \"podman\"\n").unwrap();
+        let config = GlobalConfig::load_existing(&path).unwrap();
+        assert_eq!(config.defaults.provider, "podman");
+    }
+
     #[test]
     fn test_load_malformed_toml_fails() {
         let tmp = tempfile::tempdir().unwrap();
@@ -542,6 +1063,81 @@ env_forward = ["OPENAI_API_KEY"]
         assert_eq!(updated.defaults.shell, "/bin/zsh");
     }
 
+    #[test]
+    fn test_update_with_profile_writes_only_changed_keys() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("config.toml");
+        std::fs::write(
+            &path,
+            r#"
+[defaults]
+provider = "docker"
+shell = "/bin/bash"
+
+[profiles.work.defaults]
+provider = "podman"
+shell = "/bin/zsh"
+"#,
+        )
+        .unwrap();
+
+        let updated = GlobalConfig::update_with_profile_at(&path, Some("work"), |cfg| {
+            // The mutator sees the profile's values
+            assert_eq!(cfg.defaults.provider, "podman");
+            cfg.defaults.user = Some("dev".to_string());
+        })
+        .unwrap();
+        assert_eq!(updated.defaults.provider, "podman");
+        assert_eq!(updated.defaults.user.as_deref(), Some("dev"));
+
+        let base = GlobalConfig::load_from(&path).unwrap();
+        assert_eq!(base.defaults.provider, "docker");
+        assert_eq!(base.defaults.shell, "/bin/bash");
+        assert_eq!(base.defaults.user.as_deref(), Some("dev"));
+        assert_eq!(
+            base.with_profile("work").unwrap().defaults.shell,
+            "/bin/zsh"
+        );
+    }
+
+    /// Changing a key the profile overrides updates the profile, so the new
+    /// value isn't masked when the profile is applied again
+    #[test]
+    fn test_update_with_profile_writes_overridden_keys_to_profile() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("config.toml");
+        std::fs::write(
+            &path,
+            r#"
+[defaults]
+provider = "docker"
+shell = "/bin/bash"
+
+[profiles.work.defaults]
+provider = "podman"
+"#,
+        )
+        .unwrap();
+
+        let updated = GlobalConfig::update_with_profile_at(&path, Some("work"), |cfg| {
+            cfg.defaults.provider = "docker".to_string();
+            cfg.defaults.shell = "/bin/zsh".to_string();
+        })
+        .unwrap();
+        assert_eq!(updated.defaults.provider, "docker");
+        assert_eq!(updated.defaults.shell, "/bin/zsh");
+
+        let base = GlobalConfig::load_from(&path).unwrap();
+        assert_eq!(base.defaults.provider, "docker");
+        assert_eq!(base.defaults.shell, "/bin/zsh");
+        let work = base.with_profile("work").unwrap();
+        assert_eq!(work.defaults.provider, "docker");
+        assert_eq!(
+            base.profiles["work"]["defaults"]["provider"].as_str(),
+            Some("docker")
+        );
+    }
+
     #[test]
     fn test_save_to_atomic_replaces_existing_file() {
         let tmp = tempfile::tempdir().unwrap();
@@ -5,11 +5,13 @@
 //! - devcontainer.json files (VSCode compatible)
 
 mod devcontainer;
+mod editor;
 mod error;
 mod global;
 mod substitute;
 
 pub use devcontainer::*;
+pub use editor::*;
 pub use error::*;
 pub use global::*;
 pub use substitute::*;
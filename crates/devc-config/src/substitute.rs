@@ -119,6 +119,53 @@ fn basename(path: &str) -> String {
         .unwrap_or_default()
 }
 
+/// Variables in `input` that [`substitute`] can't resolve: unknown names in
+/// the devcontainer namespaces (`local*`, `container*`, `devcontainer*`),
+/// which are left as-is, and `localEnv` references to unset host variables
+/// without a default, which become empty
+///
+/// Other names, like `${HOME}` in a lifecycle command, are left for the
+/// shell and never reported.
+pub fn unresolved_variables(input: &str) -> Vec<String> {
+    let mut unresolved = Vec::new();
+    let mut rest = input;
+    while let Some(start) = rest.find("${") {
+        let after = &rest[start + 2..];
+        let mut depth = 1;
+        let end = after.char_indices().find_map(|(i, c)| {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            (depth == 0).then_some(i)
+        });
+        let var = &after[..end.unwrap_or(after.len())];
+        if !is_resolvable(var) {
+            unresolved.push(format!("${{{}}}", var));
+        }
+        rest = end.map_or("", |i| &after[i + 1..]);
+    }
+    unresolved
+}
+
+fn is_resolvable(var: &str) -> bool {
+    match var {
+        "localWorkspaceFolder"
+        | "containerWorkspaceFolder"
+        | "localWorkspaceFolderBasename"
+        | "containerWorkspaceFolderBasename"
+        | "devcontainerId" => true,
+        _ if var.starts_with("containerEnv:") => true,
+        _ if var.starts_with("localEnv:") => var
+            .strip_prefix("localEnv:")
+            .is_some_and(|rest| rest.contains(':') || std::env::var_os(rest).is_some()),
+        _ => !["local", "container", "devcontainer"]
+            .iter()
+            .any(|namespace| var.starts_with(namespace)),
+    }
+}
+
 /// Substitute variables in an optional string
 pub fn substitute_opt(input: &Option<String>, ctx: &SubstitutionContext) -> Option<String> {
     input.as_ref().map(|s| substitute(s, ctx))
@@ -187,6 +234,25 @@ mod tests {
         std::env::remove_var("DEVC_TEST_VAR");
     }
 
+    #[test]
+    fn test_unresolved_variables() {
+        std::env::set_var("DEVC_TEST_SET", "1");
+        assert!(unresolved_variables("${localWorkspaceFolder}/${containerEnv:PATH}").is_empty());
+        assert!(unresolved_variables("${localEnv:DEVC_TEST_SET}").is_empty());
+        assert!(unresolved_variables("${localEnv:DEVC_TEST_UNSET:fallback}").is_empty());
+        assert_eq!(
+            unresolved_variables("a ${localEnv:DEVC_TEST_UNSET} b ${localWorkspaceRoot}"),
+            ["${localEnv:DEVC_TEST_UNSET}", "${localWorkspaceRoot}"]
+        );
+        assert_eq!(
+            unresolved_variables("${containerFolder"),
+            ["${containerFolder}"]
+        );
+        // Shell variables are the shell's business
+        assert!(unresolved_variables("echo ${HOME} && export PATH=${PATH}:/x").is_empty());
+        std::env::remove_var("DEVC_TEST_SET");
+    }
+
     #[test]
     fn test_container_env_deferred() {
         let ctx = test_ctx();
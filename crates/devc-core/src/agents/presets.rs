@@ -34,6 +34,22 @@ impl fmt::Display for AgentKind {
     }
 }
 
+impl std::str::FromStr for AgentKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AgentKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                format!(
+                    "unknown agent '{}': expected one of codex, claude, cursor, gemini",
+                    s
+                )
+            })
+    }
+}
+
 /// Built-in defaults for each supported agent.
 #[derive(Debug, Clone)]
 pub struct AgentPreset {
@@ -111,4 +127,13 @@ mod tests {
             assert!(!preset.default_install_command.is_empty());
         }
     }
+
+    #[test]
+    fn test_agent_kind_from_str() {
+        for kind in AgentKind::ALL {
+            assert_eq!(kind.as_str().parse::<AgentKind>(), Ok(kind));
+        }
+        assert_eq!("Claude".parse::<AgentKind>(), Ok(AgentKind::Claude));
+        assert!("copilot".parse::<AgentKind>().is_err());
+    }
 }
@@ -84,8 +84,14 @@ impl EnhancedBuildContext {
     ///
     /// Copies the original build context and appends dropbear installation
     /// to the Dockerfile. If the original Dockerfile has a USER instruction,
-    /// restores that user after the root-privileged installation.
-    pub fn from_dockerfile(original_context: &Path, dockerfile_name: &str) -> Result<Self> {
+    /// restores that user after the root-privileged installation. With a
+    /// `target` stage, the installation goes on top of that stage; build the
+    /// result without a target.
+    pub fn from_dockerfile(
+        original_context: &Path,
+        dockerfile_name: &str,
+        target: Option<&str>,
+    ) -> Result<Self> {
         let temp_dir = tempfile::tempdir()?;
 
         // Copy the entire build context to temp directory
@@ -97,14 +103,7 @@ impl EnhancedBuildContext {
 
         // Find the last USER instruction to restore after dropbear install
         // This handles cases where the Dockerfile switches to a non-root user
-        let last_user = original_content
-            .lines()
-            .rev()
-            .find(|line| {
-                let trimmed = line.trim();
-                trimmed.to_uppercase().starts_with("USER ") && !trimmed.starts_with('#')
-            })
-            .map(|line| line.trim().to_string());
+        let last_user = last_user(&original_content, target);
 
         // Build the user restore instruction if needed
         let user_restore = last_user
@@ -112,8 +111,11 @@ impl EnhancedBuildContext {
             .unwrap_or_default();
 
         let enhanced_content = format!(
-            "{}\n\n# Added by devc for SSH support{}{}",
-            original_content, DROPBEAR_INSTALL_SCRIPT, user_restore
+            "{}\n{}\n# Added by devc for SSH support{}{}",
+            original_content,
+            stage_from(target),
+            DROPBEAR_INSTALL_SCRIPT,
+            user_restore
         );
 
         std::fs::write(&dockerfile_path, enhanced_content)?;
@@ -171,10 +173,12 @@ impl EnhancedBuildContext {
     /// Create an enhanced build context from a Dockerfile with features.
     ///
     /// Copies original context, appends feature layers and optional SSH,
-    /// then restores the original USER.
+    /// then restores the original USER. With a `target` stage, the layers go
+    /// on top of that stage; build the result without a target.
     pub fn from_dockerfile_with_features(
         original_context: &Path,
         dockerfile_name: &str,
+        target: Option<&str>,
         features: &[ResolvedFeature],
         inject_ssh: bool,
         remote_user: &str,
@@ -191,14 +195,7 @@ impl EnhancedBuildContext {
         let original_content = std::fs::read_to_string(&dockerfile_path)?;
 
         // Find the last USER instruction to restore after features + SSH
-        let last_user = original_content
-            .lines()
-            .rev()
-            .find(|line| {
-                let trimmed = line.trim();
-                trimmed.to_uppercase().starts_with("USER ") && !trimmed.starts_with('#')
-            })
-            .map(|line| line.trim().to_string());
+        let last_user = last_user(&original_content, target);
 
         let feature_layers = generate_all_feature_layers(features, "feature", remote_user);
 
@@ -216,8 +213,12 @@ impl EnhancedBuildContext {
             .unwrap_or_default();
 
         let enhanced_content = format!(
-            "{}\n\nUSER root\n# Install devcontainer features\n{}{}{}",
-            original_content, feature_layers, ssh_section, user_restore
+            "{}\n{}\nUSER root\n# Install devcontainer features\n{}{}{}",
+            original_content,
+            stage_from(target),
+            feature_layers,
+            ssh_section,
+            user_restore
         );
 
         std::fs::write(&dockerfile_path, enhanced_content)?;
@@ -242,6 +243,37 @@ impl EnhancedBuildContext {
     }
 }
 
+/// The devc layers are appended after the last stage, so building an
+/// earlier `target` stage would leave them out. Start a new final stage
+/// from the target instead.
+fn stage_from(target: Option<&str>) -> String {
+    target
+        .map(|target| format!("FROM {} AS devc_enhanced\n", target))
+        .unwrap_or_default()
+}
+
+/// The last USER instruction of the Dockerfile, or of its `target` stage
+fn last_user(dockerfile: &str, target: Option<&str>) -> Option<String> {
+    let mut in_stage = target.is_none();
+    let mut user = None;
+    for line in dockerfile.lines().map(str::trim) {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if let (Some(target), Some(first)) = (target, words.first()) {
+            if first.eq_ignore_ascii_case("FROM") {
+                in_stage = matches!(
+                    words[..],
+                    [_, _, .., as_, name] if as_.eq_ignore_ascii_case("AS") && name.eq_ignore_ascii_case(target)
+                );
+                continue;
+            }
+        }
+        if in_stage && line.to_uppercase().starts_with("USER ") {
+            user = Some(line.to_string());
+        }
+    }
+    user
+}
+
 /// Recursively copy a directory
 fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
     if !dst.exists() {
@@ -406,7 +438,8 @@ mod tests {
         let dockerfile_content = "FROM fedora:latest\n# USER vscode\nRUN echo hello\n";
         std::fs::write(temp_dir.path().join("Dockerfile"), dockerfile_content).unwrap();
 
-        let ctx = EnhancedBuildContext::from_dockerfile(temp_dir.path(), "Dockerfile").unwrap();
+        let ctx =
+            EnhancedBuildContext::from_dockerfile(temp_dir.path(), "Dockerfile", None).unwrap();
         let enhanced = std::fs::read_to_string(ctx.context_path().join("Dockerfile")).unwrap();
 
         // Commented USER should NOT trigger user restore
@@ -463,7 +496,8 @@ SHELL ["/bin/bash", "-c"]
 "#;
         std::fs::write(temp_dir.path().join("Dockerfile"), dockerfile_content).unwrap();
 
-        let ctx = EnhancedBuildContext::from_dockerfile(temp_dir.path(), "Dockerfile").unwrap();
+        let ctx =
+            EnhancedBuildContext::from_dockerfile(temp_dir.path(), "Dockerfile", None).unwrap();
         let enhanced = std::fs::read_to_string(ctx.context_path().join("Dockerfile")).unwrap();
 
         // Should contain USER root for installation
@@ -496,7 +530,8 @@ RUN dnf install -y bash
 "#;
         std::fs::write(temp_dir.path().join("Dockerfile"), dockerfile_content).unwrap();
 
-        let ctx = EnhancedBuildContext::from_dockerfile(temp_dir.path(), "Dockerfile").unwrap();
+        let ctx =
+            EnhancedBuildContext::from_dockerfile(temp_dir.path(), "Dockerfile", None).unwrap();
         let enhanced = std::fs::read_to_string(ctx.context_path().join("Dockerfile")).unwrap();
 
         // Should contain USER root for installation
@@ -611,6 +646,7 @@ RUN dnf install -y bash
         let ctx = EnhancedBuildContext::from_dockerfile_with_features(
             ctx_dir.path(),
             "Dockerfile",
+            None,
             &features,
             false,
             "developer",
@@ -637,6 +673,42 @@ RUN dnf install -y bash
         assert!(last_user_line.contains("developer"));
     }
 
+    #[test]
+    fn test_from_dockerfile_with_features_on_target_stage() {
+        let (_tmp, features) = make_test_features();
+
+        let ctx_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            ctx_dir.path().join("Dockerfile"),
+            "FROM python:3.12 AS dev\nUSER developer\n\nFROM dev AS prod\nUSER app\n",
+        )
+        .unwrap();
+
+        let ctx = EnhancedBuildContext::from_dockerfile_with_features(
+            ctx_dir.path(),
+            "Dockerfile",
+            Some("dev"),
+            &features,
+            true,
+            "developer",
+        )
+        .unwrap();
+
+        let dockerfile = std::fs::read_to_string(ctx.context_path().join("Dockerfile")).unwrap();
+        // Features and SSH go on a new last stage built from the target
+        let (original, appended) = dockerfile.split_once("FROM dev AS devc_enhanced").unwrap();
+        assert!(original.contains("FROM dev AS prod"));
+        assert!(appended.contains("COPY feature-0-node-1/"));
+        assert!(appended.contains("dropbear"));
+        // The user restored is the target stage's, not the last stage's
+        let last_user_line = appended
+            .lines()
+            .rev()
+            .find(|l| l.trim().to_uppercase().starts_with("USER "))
+            .unwrap();
+        assert_eq!(last_user_line, "USER developer");
+    }
+
     #[test]
     fn test_from_dockerfile_with_features_and_ssh() {
         let (_tmp, features) = make_test_features();
@@ -651,6 +723,7 @@ RUN dnf install -y bash
         let ctx = EnhancedBuildContext::from_dockerfile_with_features(
             ctx_dir.path(),
             "Dockerfile",
+            None,
             &features,
             true,
             "developer",
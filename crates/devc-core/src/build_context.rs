@@ -0,0 +1,220 @@
+//! Build context size estimation, honoring `.dockerignore`
+
+use std::path::Path;
+use std::time::Instant;
+
+/// One `.dockerignore` line: a glob over the context-relative path, or an
+/// exception (`!pattern`) that re-includes matches of earlier lines
+struct IgnoreRule {
+    pattern: String,
+    exception: bool,
+}
+
+/// Parsed `.dockerignore`; the last matching rule decides
+pub(crate) struct DockerIgnore {
+    rules: Vec<IgnoreRule>,
+}
+
+impl DockerIgnore {
+    /// Load `<context>/.dockerignore`, or an empty rule set when there is none
+    pub(crate) fn load(context: &Path) -> Self {
+        let content = std::fs::read_to_string(context.join(".dockerignore")).unwrap_or_default();
+        Self::parse(&content)
+    }
+
+    pub(crate) fn parse(content: &str) -> Self {
+        let rules = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let (exception, pattern) = match line.strip_prefix('!') {
+                    Some(rest) => (true, rest.trim()),
+                    None => (false, line),
+                };
+                let pattern = pattern.trim_matches('/');
+                let pattern = pattern.strip_prefix("./").unwrap_or(pattern);
+                (!pattern.is_empty()).then(|| IgnoreRule {
+                    pattern: pattern.to_string(),
+                    exception,
+                })
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Whether a context-relative path (using `/` separators) is excluded.
+    /// A pattern also matches everything below a matching directory.
+    pub(crate) fn is_excluded(&self, path: &str) -> bool {
+        let mut excluded = false;
+        for rule in &self.rules {
+            if matches_path_or_parent(&rule.pattern, path) {
+                excluded = !rule.exception;
+            }
+        }
+        excluded
+    }
+
+    fn has_exceptions(&self) -> bool {
+        self.rules.iter().any(|r| r.exception)
+    }
+}
+
+fn matches_path_or_parent(pattern: &str, path: &str) -> bool {
+    glob_match(pattern.as_bytes(), path.as_bytes())
+        || path
+            .match_indices('/')
+            .any(|(i, _)| glob_match(pattern.as_bytes(), &path.as_bytes()[..i]))
+}
+
+/// Match `text` against a Docker-style glob: `*` and `?` stay within one path
+/// segment, `**` crosses segments, and `[...]` is a character class
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((b'*', rest)) if rest.first() == Some(&b'*') => {
+            // `**/` may also match zero directories
+            let rest = &rest[1..];
+            let rest_after_slash = rest.strip_prefix(b"/").unwrap_or(rest);
+            (0..=text.len()).any(|i| {
+                glob_match(rest, &text[i..])
+                    || (rest_after_slash.len() != rest.len()
+                        && glob_match(rest_after_slash, &text[i..]))
+            })
+        }
+        Some((b'*', rest)) => (0..=text.len())
+            .take_while(|&i| i == 0 || text[i - 1] != b'/')
+            .any(|i| glob_match(rest, &text[i..])),
+        Some((b'?', rest)) => {
+            matches!(text.first(), Some(&c) if c != b'/') && glob_match(rest, &text[1..])
+        }
+        Some((b'[', rest)) => {
+            let Some(end) = rest.iter().position(|&c| c == b']') else {
+                return text.first() == Some(&b'[') && glob_match(rest, &text[1..]);
+            };
+            let Some(&c) = text.first() else {
+                return false;
+            };
+            let (negated, class) = match rest[..end].split_first() {
+                Some((b'^' | b'!', class)) => (true, class),
+                _ => (false, &rest[..end]),
+            };
+            let mut in_class = false;
+            let mut i = 0;
+            while i < class.len() {
+                if i + 2 < class.len() && class[i + 1] == b'-' {
+                    in_class |= (class[i]..=class[i + 2]).contains(&c);
+                    i += 3;
+                } else {
+                    in_class |= class[i] == c;
+                    i += 1;
+                }
+            }
+            c != b'/' && in_class != negated && glob_match(&rest[end + 1..], &text[1..])
+        }
+        Some((b'\\', rest)) if !rest.is_empty() => {
+            text.first() == Some(&rest[0]) && glob_match(&rest[1..], &text[1..])
+        }
+        Some((&p, rest)) => text.first() == Some(&p) && glob_match(rest, &text[1..]),
+    }
+}
+
+/// Total size in bytes of the files `docker build` would send from `context`,
+/// skipping what `.dockerignore` excludes. Stops counting once the total passes
+/// `limit`, so an oversized context is not walked in full, and gives up with
+/// None once `deadline` passes.
+pub(crate) fn context_size(context: &Path, limit: u64, deadline: Instant) -> Option<u64> {
+    let ignore = DockerIgnore::load(context);
+    let mut total = 0;
+    let mut pending = vec![(context.to_path_buf(), String::new())];
+    while let Some((dir, rel_dir)) = pending.pop() {
+        if Instant::now() >= deadline {
+            return None;
+        }
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let rel = if rel_dir.is_empty() {
+                name
+            } else {
+                format!("{}/{}", rel_dir, name)
+            };
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let excluded = ignore.is_excluded(&rel);
+            if file_type.is_dir() {
+                // An exception further down could re-include part of an excluded directory
+                if !excluded || ignore.has_exceptions() {
+                    pending.push((entry.path(), rel));
+                }
+            } else if !excluded {
+                total += entry.metadata().map(|m| m.len()).unwrap_or(0);
+                if total > limit {
+                    return Some(total);
+                }
+            }
+        }
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match(b"*.log", b"build.log"));
+        assert!(!glob_match(b"*.log", b"logs/build.log"));
+        assert!(glob_match(b"**/*.log", b"build.log"));
+        assert!(glob_match(b"**/*.log", b"a/b/build.log"));
+        assert!(glob_match(b"node_modules", b"node_modules"));
+        assert!(glob_match(b"file?.txt", b"file1.txt"));
+        assert!(!glob_match(b"file?.txt", b"file/.txt"));
+        assert!(glob_match(b"[a-c]at", b"bat"));
+        assert!(!glob_match(b"[^a-c]at", b"bat"));
+    }
+
+    #[test]
+    fn test_dockerignore_rules() {
+        let ignore =
+            DockerIgnore::parse("# comment\n/node_modules/\n*.md\n!README.md\ntarget\n**/*.tmp\n");
+        assert!(ignore.is_excluded("node_modules"));
+        assert!(ignore.is_excluded("node_modules/pkg/index.js"));
+        assert!(ignore.is_excluded("CHANGELOG.md"));
+        assert!(!ignore.is_excluded("README.md"));
+        assert!(ignore.is_excluded("target/debug/app"));
+        assert!(ignore.is_excluded("src/cache/x.tmp"));
+        assert!(!ignore.is_excluded("src/main.rs"));
+        assert!(!DockerIgnore::parse("").is_excluded("anything"));
+    }
+
+    #[test]
+    fn test_context_size_respects_dockerignore() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("src")).unwrap();
+        std::fs::create_dir_all(tmp.path().join("node_modules/pkg")).unwrap();
+        std::fs::write(tmp.path().join("Dockerfile"), vec![b'x'; 10]).unwrap();
+        std::fs::write(tmp.path().join("src/main.rs"), vec![b'x'; 20]).unwrap();
+        std::fs::write(tmp.path().join("node_modules/pkg/big.js"), vec![b'x'; 1000]).unwrap();
+        let far = Instant::now() + std::time::Duration::from_secs(60);
+
+        assert_eq!(context_size(tmp.path(), u64::MAX, far), Some(1030));
+
+        std::fs::write(tmp.path().join(".dockerignore"), "node_modules\n").unwrap();
+        // .dockerignore itself is part of the context
+        assert_eq!(
+            context_size(tmp.path(), u64::MAX, far),
+            Some(1030 - 1000 + 13)
+        );
+
+        // Counting stops once past the limit
+        std::fs::remove_file(tmp.path().join(".dockerignore")).unwrap();
+        assert!(context_size(tmp.path(), 5, far).unwrap() > 5);
+        // A walk past its deadline gives up
+        assert_eq!(context_size(tmp.path(), u64::MAX, Instant::now()), None);
+    }
+}
@@ -44,7 +44,7 @@ fn default_workspace_folder(workspace_path: &Path) -> String {
 /// - Replaces spaces and special chars with hyphens
 /// - Collapses multiple hyphens
 /// - Trims leading/trailing hyphens
-fn sanitize_name(name: &str) -> String {
+pub(crate) fn sanitize_name(name: &str) -> String {
     let sanitized: String = name
         .chars()
         .map(|c| {
@@ -248,7 +248,9 @@ impl Container {
                     image
                 )));
             }
-            ImageSource::Dockerfile { path, args, .. } => (path, args.unwrap_or_default(), None),
+            ImageSource::Dockerfile {
+                path, args, target, ..
+            } => (path, args.unwrap_or_default(), target),
             ImageSource::Compose => {
                 return Err(CoreError::InvalidState(
                     "Cannot build standalone image for Compose project (use 'up' instead)"
@@ -266,6 +268,19 @@ impl Container {
         labels.insert("devc.managed".to_string(), "true".to_string());
         labels.insert("devc.project".to_string(), self.name.clone());
 
+        let secrets = self
+            .devcontainer
+            .build
+            .as_ref()
+            .and_then(|b| b.secrets.as_ref())
+            .map(|specs| {
+                specs
+                    .iter()
+                    .map(|spec| resolve_secret_spec(spec, &context))
+                    .collect()
+            })
+            .unwrap_or_default();
+
         Ok(BuildConfig {
             context,
             dockerfile,
@@ -276,6 +291,7 @@ impl Container {
             labels,
             no_cache: false,
             pull: true,
+            secrets,
         })
     }
 
@@ -294,16 +310,22 @@ impl Container {
         image: &str,
         feature_props: Option<&MergedFeatureProperties>,
     ) -> CreateContainerConfig {
-        let mut mounts = vec![MountConfig {
-            mount_type: MountType::Bind,
-            source: self.workspace_path.to_string_lossy().to_string(),
-            target: self
-                .devcontainer
-                .workspace_folder
-                .clone()
-                .unwrap_or_else(|| default_workspace_folder(&self.workspace_path)),
-            read_only: false,
-        }];
+        let workspace_mount = self
+            .devcontainer
+            .workspace_mount
+            .as_deref()
+            .and_then(parse_mount_string)
+            .unwrap_or_else(|| MountConfig {
+                mount_type: MountType::Bind,
+                source: self.workspace_path.to_string_lossy().to_string(),
+                target: self
+                    .devcontainer
+                    .workspace_folder
+                    .clone()
+                    .unwrap_or_else(|| default_workspace_folder(&self.workspace_path)),
+                read_only: false,
+            });
+        let mut mounts = vec![workspace_mount];
 
         // Add configured mounts
         if let Some(ref configured_mounts) = self.devcontainer.mounts {
@@ -315,17 +337,7 @@ impl Container {
                         }
                     }
                     devc_config::Mount::Object(obj) => {
-                        let mount_type = match obj.mount_type.as_deref() {
-                            Some("volume") => MountType::Volume,
-                            Some("tmpfs") => MountType::Tmpfs,
-                            _ => MountType::Bind,
-                        };
-                        mounts.push(MountConfig {
-                            mount_type,
-                            source: obj.source.clone().unwrap_or_default(),
-                            target: obj.target.clone(),
-                            read_only: obj.read_only.unwrap_or(false),
-                        });
+                        mounts.push(mount_config_from_object(obj));
                     }
                 }
             }
@@ -354,22 +366,10 @@ impl Container {
                         }
                     }
                     devc_config::Mount::Object(obj) => {
-                        let mount_type = match obj.mount_type.as_deref() {
-                            Some("volume") => MountType::Volume,
-                            Some("tmpfs") => MountType::Tmpfs,
-                            _ => MountType::Bind,
-                        };
-                        let source = obj
-                            .source
-                            .as_deref()
-                            .map(|s| subst_var(s, &sub_ctx))
-                            .unwrap_or_default();
-                        mounts.push(MountConfig {
-                            mount_type,
-                            source,
-                            target: subst_var(&obj.target, &sub_ctx),
-                            read_only: obj.read_only.unwrap_or(false),
-                        });
+                        let mut config = mount_config_from_object(obj);
+                        config.source = subst_var(&config.source, &sub_ctx);
+                        config.target = subst_var(&config.target, &sub_ctx);
+                        mounts.push(config);
                     }
                 }
             }
@@ -377,11 +377,11 @@ impl Container {
 
         // Build port mappings
         let mut ports = Vec::new();
-        for port in self.devcontainer.forward_ports_list() {
+        for (port, transport) in self.devcontainer.forward_ports_list() {
             ports.push(PortConfig {
                 host_port: Some(port),
                 container_port: port,
-                protocol: "tcp".to_string(),
+                protocol: transport.to_string(),
                 host_ip: Some("127.0.0.1".to_string()),
             });
         }
@@ -413,10 +413,10 @@ impl Container {
             self.config_path.to_string_lossy().to_string(),
         );
 
-        // Get user
+        // The main process runs as containerUser; remoteUser only applies to exec
         let user = self
             .devcontainer
-            .effective_user()
+            .effective_container_user()
             .map(|s| s.to_string())
             .or_else(|| self.global_config.defaults.user.clone());
 
@@ -439,6 +439,9 @@ impl Container {
             ])
         };
 
+        // Invalid limits are reported by resource_limits() before create
+        let (cpus, memory) = self.resource_limits().unwrap_or_default();
+
         CreateContainerConfig {
             image: image.to_string(),
             name: Some(self.container_name()),
@@ -452,6 +455,7 @@ impl Container {
             mounts,
             ports,
             labels,
+            user_labels: self.devcontainer.labels.clone().unwrap_or_default(),
             hostname: Some(self.name.clone()),
             tty: true,
             stdin_open: true,
@@ -469,7 +473,7 @@ impl Container {
                 }
                 caps
             },
-            cap_drop: Vec::new(),
+            cap_drop: self.devcontainer.cap_drop.clone().unwrap_or_default(),
             security_opt: {
                 let mut opts = self.devcontainer.security_opt.clone().unwrap_or_default();
                 if let Some(props) = feature_props {
@@ -482,10 +486,27 @@ impl Container {
                 opts
             },
             init: self.devcontainer.init.unwrap_or(false) || feature_props.is_some_and(|p| p.init),
+            gpus: self
+                .devcontainer
+                .host_requirements
+                .as_ref()
+                .and_then(|h| h.gpu.as_ref())
+                .filter(|gpu| gpu.is_requested())
+                .map(|_| "all".to_string()),
+            cpus,
+            memory,
             extra_args: self.devcontainer.run_args.clone().unwrap_or_default(),
         }
     }
 
+    /// CPU and memory caps from the `resources` extension, validated
+    pub fn resource_limits(&self) -> Result<(Option<f64>, Option<u64>)> {
+        match &self.devcontainer.resources {
+            Some(resources) => Ok((resources.cpu_limit()?, resources.memory_limit()?)),
+            None => Ok((None, None)),
+        }
+    }
+
     /// Get exec configuration for running a command
     pub fn exec_config(&self, cmd: Vec<String>, tty: bool, stdin: bool) -> ExecConfig {
         self.exec_config_with_feature_env(cmd, tty, stdin, None)
@@ -524,7 +545,10 @@ impl Container {
             cmd,
             env,
             working_dir: self.devcontainer.workspace_folder.clone(),
-            user: self.devcontainer.effective_user().map(|s| s.to_string()),
+            user: self
+                .devcontainer
+                .effective_remote_user()
+                .map(|s| s.to_string()),
             tty,
             stdin,
             privileged: false,
@@ -574,6 +598,33 @@ impl Container {
         Some(files.iter().map(|f| config_dir.join(f)).collect())
     }
 
+    /// Resolve the host directory initializeCommand runs in
+    ///
+    /// Honors `devc.initializeCommandCwd`: `"workspace"` (the default) is the
+    /// workspace root, `"config"` is the directory holding devcontainer.json,
+    /// and anything else is a path relative to the workspace root. The
+    /// directory must already exist on the host.
+    pub fn initialize_command_dir(&self) -> Result<PathBuf> {
+        let dir = match self.devcontainer.initialize_command_cwd.as_deref() {
+            None | Some("workspace") => self.workspace_path.clone(),
+            Some("config") => self
+                .config_path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| self.workspace_path.clone()),
+            Some(path) => self.workspace_path.join(path),
+        };
+        if !dir.is_dir() {
+            return Err(CoreError::Config(devc_config::ConfigError::Invalid(
+                format!(
+                    "initializeCommand working directory does not exist: {}",
+                    dir.display()
+                ),
+            )));
+        }
+        Ok(dir)
+    }
+
     fn runtime_suffix(&self) -> String {
         let dc = self.devcontainer_id.chars().take(8).collect::<String>();
         let cfg = short_hash(&self.config_path.to_string_lossy(), 8);
@@ -581,6 +632,35 @@ impl Container {
     }
 }
 
+/// Resolve a relative `src`/`source` path in a secret spec like "id=npm,src=.npmrc"
+/// against `base` (the devcontainer.json directory), leaving other fields untouched.
+fn resolve_secret_spec(spec: &str, base: &Path) -> String {
+    spec.split(',')
+        .map(|part| match part.split_once('=') {
+            Some((key @ ("src" | "source"), value)) if Path::new(value).is_relative() => {
+                format!("{}={}", key, base.join(value).display())
+            }
+            _ => part.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Convert an object-form mount into a provider mount
+fn mount_config_from_object(obj: &devc_config::MountObject) -> MountConfig {
+    let mount_type = match obj.mount_type.as_deref() {
+        Some("volume") => MountType::Volume,
+        Some("tmpfs") => MountType::Tmpfs,
+        _ => MountType::Bind,
+    };
+    MountConfig {
+        mount_type,
+        source: obj.source.clone().unwrap_or_default(),
+        target: obj.target.clone(),
+        read_only: obj.read_only.unwrap_or(false),
+    }
+}
+
 /// Parse a mount string like "type=bind,source=/path,target=/path"
 fn parse_mount_string(s: &str) -> Option<MountConfig> {
     let mut mount_type = MountType::Bind;
@@ -591,6 +671,10 @@ fn parse_mount_string(s: &str) -> Option<MountConfig> {
     for part in s.split(',') {
         let parts: Vec<&str> = part.splitn(2, '=').collect();
         if parts.len() != 2 {
+            // A bare `readonly`/`ro` flag means read-only, as for docker --mount
+            if matches!(part, "readonly" | "ro") {
+                read_only = true;
+            }
             continue;
         }
 
@@ -621,6 +705,47 @@ fn parse_mount_string(s: &str) -> Option<MountConfig> {
     })
 }
 
+/// Parse and validate an ad-hoc `--mount` spec like `type=bind,src=./data,dst=/data`.
+///
+/// Stricter than devcontainer.json mounts: unknown options, a relative target
+/// or a bind source that does not exist are rejected, and bind sources are
+/// made absolute.
+pub fn parse_mount_spec(spec: &str) -> Result<MountConfig> {
+    let invalid = |reason: String| CoreError::InvalidMount(format!("{} ('{}')", reason, spec));
+
+    for part in spec.split(',') {
+        match part.split_once('=') {
+            Some(("type", "bind" | "volume" | "tmpfs")) => {}
+            Some(("type", other)) => {
+                return Err(invalid(format!("unsupported mount type '{}'", other)))
+            }
+            Some(("source" | "src" | "target" | "dst" | "destination" | "readonly" | "ro", _)) => {}
+            None if matches!(part, "readonly" | "ro") => {}
+            _ => return Err(invalid(format!("unknown mount option '{}'", part))),
+        }
+    }
+
+    let mut mount = parse_mount_string(spec).ok_or_else(|| invalid("missing dst".to_string()))?;
+    if !mount.target.starts_with('/') {
+        return Err(invalid("dst must be an absolute path".to_string()));
+    }
+    match mount.mount_type {
+        MountType::Bind if mount.source.is_empty() => {
+            return Err(invalid("bind mounts need a src".to_string()));
+        }
+        MountType::Bind => {
+            let source = std::fs::canonicalize(&mount.source)
+                .map_err(|_| invalid(format!("src '{}' does not exist", mount.source)))?;
+            mount.source = source.to_string_lossy().to_string();
+        }
+        MountType::Volume if mount.source.is_empty() => {
+            return Err(invalid("volume mounts need a src".to_string()));
+        }
+        _ => {}
+    }
+    Ok(mount)
+}
+
 /// Run a single host command, optionally capturing output to a channel
 async fn run_single_host_command(
     program: &str,
@@ -1102,6 +1227,11 @@ mod tests {
             image: Some("ubuntu:22.04".to_string()),
             privileged: Some(true),
             cap_add: Some(vec!["SYS_PTRACE".to_string()]),
+            cap_drop: Some(vec!["MKNOD".to_string()]),
+            resources: Some(devc_config::Resources {
+                cpus: Some(2.0),
+                memory: Some("1g".to_string()),
+            }),
             security_opt: Some(vec!["seccomp=unconfined".to_string()]),
             init: Some(true),
             run_args: Some(vec!["--shm-size=1g".to_string()]),
@@ -1120,6 +1250,9 @@ mod tests {
         let create = container.create_config("ubuntu:22.04");
         assert!(create.privileged);
         assert_eq!(create.cap_add, vec!["SYS_PTRACE"]);
+        assert_eq!(create.cap_drop, vec!["MKNOD"]);
+        assert_eq!(create.cpus, Some(2.0));
+        assert_eq!(create.memory, Some(1 << 30));
         assert_eq!(create.security_opt, vec!["seccomp=unconfined"]);
         assert!(create.init);
         assert_eq!(create.extra_args, vec!["--shm-size=1g"]);
@@ -1149,6 +1282,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_override_command_default_keeps_alive() {
+        for override_command in [None, Some(true)] {
+            let config = DevContainerConfig {
+                image: Some("ubuntu:22.04".to_string()),
+                override_command,
+                ..Default::default()
+            };
+
+            let container = Container {
+                name: "test".to_string(),
+                workspace_path: PathBuf::from("/tmp/test"),
+                devcontainer: config,
+                config_path: PathBuf::from("/tmp/test/.devcontainer/devcontainer.json"),
+                global_config: GlobalConfig::default(),
+                devcontainer_id: "test".to_string(),
+            };
+
+            let create = container.create_config("ubuntu:22.04");
+            let cmd = create.cmd.expect("keep-alive command");
+            assert_eq!(cmd.last().map(String::as_str), Some("sleep infinity"));
+            assert!(create.entrypoint.is_none());
+        }
+    }
+
     #[test]
     fn test_exec_config_includes_remote_env() {
         let config = DevContainerConfig {
@@ -1200,6 +1358,52 @@ mod tests {
         assert!(!create.env.contains_key("EDITOR"));
     }
 
+    #[test]
+    fn test_container_user_and_remote_user_differ() {
+        let config = DevContainerConfig {
+            image: Some("ubuntu:22.04".to_string()),
+            container_user: Some("app".to_string()),
+            remote_user: Some("vscode".to_string()),
+            ..Default::default()
+        };
+
+        let container = Container {
+            name: "test".to_string(),
+            workspace_path: PathBuf::from("/tmp/test"),
+            devcontainer: config,
+            config_path: PathBuf::from("/tmp/test/.devcontainer/devcontainer.json"),
+            global_config: GlobalConfig::default(),
+            devcontainer_id: "test".to_string(),
+        };
+
+        let create = container.create_config("ubuntu:22.04");
+        assert_eq!(create.user.as_deref(), Some("app"));
+        let exec = container.exec_config(vec!["echo".to_string()], false, false);
+        assert_eq!(exec.user.as_deref(), Some("vscode"));
+    }
+
+    #[test]
+    fn test_remote_user_alone_leaves_container_user_unset() {
+        let config = DevContainerConfig {
+            image: Some("ubuntu:22.04".to_string()),
+            remote_user: Some("vscode".to_string()),
+            ..Default::default()
+        };
+
+        let container = Container {
+            name: "test".to_string(),
+            workspace_path: PathBuf::from("/tmp/test"),
+            devcontainer: config,
+            config_path: PathBuf::from("/tmp/test/.devcontainer/devcontainer.json"),
+            global_config: GlobalConfig::default(),
+            devcontainer_id: "test".to_string(),
+        };
+
+        assert_eq!(container.create_config("ubuntu:22.04").user, None);
+        let exec = container.exec_config(vec!["echo".to_string()], false, false);
+        assert_eq!(exec.user.as_deref(), Some("vscode"));
+    }
+
     // ==================== Additional sanitize_name tests ====================
 
     #[test]
@@ -1236,6 +1440,166 @@ mod tests {
         assert!(mount.is_none());
     }
 
+    #[test]
+    fn test_parse_mount_spec_validates() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().to_string_lossy().to_string();
+
+        let mount = parse_mount_spec(&format!("type=bind,src={},dst=/data,ro=true", src)).unwrap();
+        assert_eq!(mount.mount_type, MountType::Bind);
+        assert_eq!(
+            PathBuf::from(&mount.source),
+            std::fs::canonicalize(dir.path()).unwrap()
+        );
+        assert_eq!(mount.target, "/data");
+        assert!(mount.read_only);
+
+        let volume = parse_mount_spec("type=volume,src=cache,dst=/cache").unwrap();
+        assert_eq!(volume.mount_type, MountType::Volume);
+
+        for flag in ["readonly", "ro"] {
+            let mount =
+                parse_mount_spec(&format!("type=bind,src={},dst=/data,{}", src, flag)).unwrap();
+            assert!(mount.read_only, "bare {} should mean read-only", flag);
+        }
+
+        for bad in [
+            format!("type=bind,src={}", src),
+            format!("type=bind,src={},dst=data", src),
+            format!("type=bind,src={}/missing,dst=/data", src),
+            format!("type=bind,src={},dst=/data,bogus=1", src),
+            format!("type=nfs,src={},dst=/data", src),
+            "type=bind,dst=/data".to_string(),
+        ] {
+            assert!(
+                matches!(parse_mount_spec(&bad), Err(CoreError::InvalidMount(_))),
+                "{} should be rejected",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_create_config_normalizes_mount_forms() {
+        let config: DevContainerConfig = serde_json::from_str(
+            r#"{
+                "image": "ubuntu:22.04",
+                "mounts": [
+                    "type=bind,source=/host/a,target=/a,readonly=true",
+                    {"source": "/host/b", "target": "/b", "readOnly": true},
+                    {"source": "cache-vol", "target": "/cache", "type": "volume"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let container = Container {
+            name: "test".to_string(),
+            workspace_path: PathBuf::from("/tmp/test"),
+            devcontainer: config,
+            config_path: PathBuf::from("/tmp/test/.devcontainer/devcontainer.json"),
+            global_config: GlobalConfig::default(),
+            devcontainer_id: "test".to_string(),
+        };
+
+        let create = container.create_config("ubuntu:22.04");
+        let mounts = &create.mounts[1..];
+        assert_eq!(mounts.len(), 3);
+        for (mount, target) in mounts[..2].iter().zip(["/a", "/b"]) {
+            assert_eq!(mount.mount_type, MountType::Bind);
+            assert_eq!(mount.target, target);
+            assert!(mount.read_only);
+        }
+        assert_eq!(mounts[2].mount_type, MountType::Volume);
+        assert_eq!(mounts[2].source, "cache-vol");
+        assert_eq!(mounts[2].target, "/cache");
+        assert!(!mounts[2].read_only);
+    }
+
+    #[test]
+    fn test_create_config_expands_variables_in_mounts() {
+        std::env::set_var("DEVC_TEST_MOUNT_CACHE", "/home/dev/.cache");
+        let mut config: DevContainerConfig = serde_json::from_str(
+            r#"{
+                "image": "ubuntu:22.04",
+                "mounts": [
+                    "type=bind,source=${localEnv:DEVC_TEST_MOUNT_CACHE},target=/root/.cache",
+                    {"source": "${localWorkspaceFolder}/.data", "target": "${containerWorkspaceFolder}/.data", "type": "bind"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let ctx = SubstitutionContext::new("/tmp/test".to_string(), "/workspaces/test".to_string());
+        config.substitute_variables(&ctx);
+        std::env::remove_var("DEVC_TEST_MOUNT_CACHE");
+        let container = Container {
+            name: "test".to_string(),
+            workspace_path: PathBuf::from("/tmp/test"),
+            devcontainer: config,
+            config_path: PathBuf::from("/tmp/test/.devcontainer/devcontainer.json"),
+            global_config: GlobalConfig::default(),
+            devcontainer_id: "test".to_string(),
+        };
+
+        let create = container.create_config("ubuntu:22.04");
+        let mounts = &create.mounts[1..];
+        assert_eq!(mounts.len(), 2);
+        assert_eq!(mounts[0].mount_type, MountType::Bind);
+        assert_eq!(mounts[0].source, "/home/dev/.cache");
+        assert_eq!(mounts[0].target, "/root/.cache");
+        assert_eq!(mounts[1].mount_type, MountType::Bind);
+        assert_eq!(mounts[1].source, "/tmp/test/.data");
+        assert_eq!(mounts[1].target, "/workspaces/test/.data");
+    }
+
+    #[test]
+    fn test_create_config_workspace_mount_replaces_bind() {
+        let config: DevContainerConfig = serde_json::from_str(
+            r#"{
+                "image": "ubuntu:22.04",
+                "workspaceMount": "source=app-src,target=/workspaces/app,type=volume"
+            }"#,
+        )
+        .unwrap();
+        let container = Container {
+            name: "test".to_string(),
+            workspace_path: PathBuf::from("/tmp/test"),
+            devcontainer: config,
+            config_path: PathBuf::from("/tmp/test/.devcontainer/devcontainer.json"),
+            global_config: GlobalConfig::default(),
+            devcontainer_id: "test".to_string(),
+        };
+
+        let create = container.create_config("ubuntu:22.04");
+        assert_eq!(create.mounts.len(), 1);
+        assert_eq!(create.mounts[0].mount_type, MountType::Volume);
+        assert_eq!(create.mounts[0].source, "app-src");
+        assert_eq!(create.mounts[0].target, "/workspaces/app");
+    }
+
+    #[test]
+    fn test_create_config_app_port_ranges_and_udp() {
+        let config: DevContainerConfig = serde_json::from_str(
+            r#"{"image": "ubuntu:22.04", "appPort": ["8000-8001", "53/udp"]}"#,
+        )
+        .unwrap();
+        let container = Container {
+            name: "test".to_string(),
+            workspace_path: PathBuf::from("/tmp/test"),
+            devcontainer: config,
+            config_path: PathBuf::from("/tmp/test/.devcontainer/devcontainer.json"),
+            global_config: GlobalConfig::default(),
+            devcontainer_id: "test".to_string(),
+        };
+
+        let create = container.create_config("ubuntu:22.04");
+        let ports: Vec<_> = create
+            .ports
+            .iter()
+            .map(|p| (p.container_port, p.protocol.as_str()))
+            .collect();
+        assert_eq!(ports, vec![(8000, "tcp"), (8001, "tcp"), (53, "udp")]);
+    }
+
     // ==================== create_config default env vars ====================
 
     #[test]
@@ -1339,6 +1703,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_config_resolves_secret_paths() {
+        let config: DevContainerConfig = serde_json::from_str(
+            r#"{
+                "build": {
+                    "dockerfile": "Dockerfile",
+                    "secrets": ["id=npm,src=.npmrc", "id=gh,src=/run/secrets/gh", "id=env,env=TOKEN"]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let container = Container {
+            name: "test".to_string(),
+            workspace_path: PathBuf::from("/tmp/test"),
+            devcontainer: config,
+            config_path: PathBuf::from("/tmp/test/.devcontainer/devcontainer.json"),
+            global_config: GlobalConfig::default(),
+            devcontainer_id: "test".to_string(),
+        };
+
+        let build = container.build_config().unwrap();
+        assert_eq!(
+            build.secrets,
+            vec![
+                "id=npm,src=/tmp/test/.devcontainer/.npmrc",
+                "id=gh,src=/run/secrets/gh",
+                "id=env,env=TOKEN",
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_run_host_command_string() {
         let dir = std::env::temp_dir();
@@ -1408,6 +1804,46 @@ mod tests {
         assert!(lines.iter().any(|l| l.contains("stderr_line")));
     }
 
+    #[tokio::test]
+    async fn test_initialize_command_dir_honored() {
+        let workspace = tempfile::tempdir().unwrap();
+        let config_dir = workspace.path().join(".devcontainer");
+        std::fs::create_dir(&config_dir).unwrap();
+        let mut container = Container {
+            name: "init-cwd".to_string(),
+            workspace_path: workspace.path().to_path_buf(),
+            devcontainer: DevContainerConfig {
+                initialize_command_cwd: Some("config".to_string()),
+                ..Default::default()
+            },
+            config_path: config_dir.join("devcontainer.json"),
+            global_config: GlobalConfig::default(),
+            devcontainer_id: "test".to_string(),
+        };
+
+        let dir = container.initialize_command_dir().unwrap();
+        assert_eq!(dir, config_dir);
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let cmd = devc_config::Command::String("pwd".to_string());
+        run_host_command(&cmd, &dir, Some(&tx)).await.unwrap();
+        drop(tx);
+        let mut lines = Vec::new();
+        while let Some(line) = rx.recv().await {
+            lines.push(line);
+        }
+        assert!(lines.iter().any(|l| l.ends_with("/.devcontainer")));
+
+        container.devcontainer.initialize_command_cwd = None;
+        assert_eq!(
+            container.initialize_command_dir().unwrap(),
+            workspace.path()
+        );
+
+        container.devcontainer.initialize_command_cwd = Some("missing".to_string());
+        assert!(container.initialize_command_dir().is_err());
+    }
+
     #[tokio::test]
     async fn test_run_host_command_object_separators() {
         let dir = std::env::temp_dir();
@@ -1485,6 +1921,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_root_config_workspace_and_name() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config_path = tmp.path().join(".devcontainer.json");
+        std::fs::write(&config_path, r#"{"image": "alpine:3"}"#).unwrap();
+        let workspace_name = tmp.path().file_name().unwrap().to_string_lossy();
+
+        let container = Container::from_workspace(tmp.path()).unwrap();
+        assert_eq!(container.config_path, config_path);
+        assert_eq!(container.workspace_path, tmp.path());
+        assert_eq!(container.name, sanitize_name(&workspace_name));
+
+        let container = Container::from_config(&config_path).unwrap();
+        assert_eq!(container.workspace_path, tmp.path());
+        assert_eq!(container.name, sanitize_name(&workspace_name));
+    }
+
     #[test]
     fn test_from_config_explicit_name_overrides() {
         let tmp = tempfile::tempdir().unwrap();
@@ -1,7 +1,7 @@
 //! Dotfiles injection into containers
 
 use crate::{CoreError, Result};
-use devc_config::{DotfilesConfig, GlobalConfig};
+use devc_config::{DevContainerConfig, DotfilesConfig, GlobalConfig};
 use devc_provider::{ContainerId, ContainerProvider, ExecConfig};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -87,6 +87,15 @@ impl DotfilesManager {
         }
     }
 
+    /// Resolve the dotfiles settings for a single container
+    ///
+    /// The devcontainer's `devc.dotfiles` or `customizations.devc.dotfiles`
+    /// take precedence over the global config.
+    pub fn for_container(devcontainer: &DevContainerConfig, global: &GlobalConfig) -> Self {
+        let config = devcontainer.dotfiles_config().unwrap_or_default();
+        Self::from_devcontainer_config(&config, global)
+    }
+
     /// Check if dotfiles are configured
     pub fn is_configured(&self) -> bool {
         !matches!(self.config, DotfilesSource::None)
@@ -519,6 +528,42 @@ mod tests {
         assert_eq!(manager.install_command.as_deref(), Some("./install.sh"));
     }
 
+    #[test]
+    fn test_for_container_precedence() {
+        let mut global = GlobalConfig::default();
+        global.defaults.dotfiles_repo = Some("https://github.com/global/dots".to_string());
+        let repo_of = |m: &DotfilesManager| match m.config {
+            DotfilesSource::Repository(ref url) => url.clone(),
+            ref other => panic!("expected repository, got {:?}", other),
+        };
+
+        // Nothing per-container: global applies
+        let plain: DevContainerConfig = serde_json::from_str(r#"{"image": "x"}"#).unwrap();
+        let manager = DotfilesManager::for_container(&plain, &global);
+        assert_eq!(repo_of(&manager), "https://github.com/global/dots");
+
+        // customizations.devc.dotfiles overrides global
+        let custom: DevContainerConfig = serde_json::from_str(
+            r#"{"image": "x", "customizations": {"devc": {"dotfiles": {
+                "repository": "https://github.com/team/dots", "targetPath": "~/.team"
+            }}}}"#,
+        )
+        .unwrap();
+        let manager = DotfilesManager::for_container(&custom, &global);
+        assert_eq!(repo_of(&manager), "https://github.com/team/dots");
+        assert_eq!(manager.target_path, "~/.team");
+
+        // A local path overrides a global repository too
+        let local: DevContainerConfig = serde_json::from_str(
+            r#"{"image": "x", "customizations": {"devc": {"dotfiles": {"localPath": "/tmp/dots"}}}}"#,
+        )
+        .unwrap();
+        let manager = DotfilesManager::for_container(&local, &global);
+        assert!(
+            matches!(manager.config, DotfilesSource::Local(ref p) if p == Path::new("/tmp/dots"))
+        );
+    }
+
     #[tokio::test]
     async fn test_inject_from_repo_host_bad_url() {
         use crate::test_support::MockProvider;
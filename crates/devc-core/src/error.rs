@@ -22,6 +22,12 @@ pub enum CoreError {
     #[error("Build failed: {0}")]
     BuildFailed(String),
 
+    #[error("Image not available locally: {0} (pull policy is 'never'; pull it manually or use --pull missing)")]
+    ImageNotLocal(String),
+
+    #[error("Invalid mount: {0}")]
+    InvalidMount(String),
+
     #[error("Exec failed: {0}")]
     ExecFailed(String),
 
@@ -43,6 +49,9 @@ pub enum CoreError {
     #[error("Feature dependency cycle detected: {0}")]
     FeatureDependencyCycle(String),
 
+    #[error("overrideFeatureInstallOrder conflicts with dependsOn: {0}")]
+    FeatureInstallOrderConflict(String),
+
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -54,6 +63,9 @@ pub enum CoreError {
 
     #[error("Not connected to container provider: {0}")]
     NotConnected(String),
+
+    #[error("Workspace config not found: {0} (was the workspace moved or deleted? Use 'devc forget' to stop tracking it)")]
+    WorkspaceMissing(String),
 }
 
 pub type Result<T> = std::result::Result<T, CoreError>;
@@ -1,7 +1,9 @@
-//! Feature download: OCI registry and local path handling
+//! Feature download: OCI registry, tarball, and local path handling
 
-use super::resolve::{FeatureMetadata, FeatureSource};
+use super::progress::FeatureStatus;
+use super::resolve::{FeatureMetadata, FeatureSource, WORKSPACE_FEATURES_DIR};
 use crate::{CoreError, Result};
+use devc_config::RegistryAuth;
 use std::collections::HashMap;
 use std::path::{Component, Path, PathBuf};
 use tokio::sync::mpsc;
@@ -73,20 +75,48 @@ fn safe_unpack<R: std::io::Read>(
 ///
 /// For OCI features, downloads from the registry and caches locally.
 /// For local features, validates the path and returns it directly.
+/// For local tarball features, extracts the archive into the cache.
 /// For tarball URL features, downloads and extracts the tarball.
+/// For workspace features, uses `<config_dir>/features/<name>` when it has an
+/// install.sh and otherwise resolves the fallback source.
+///
+/// OCI registries are authenticated with `registry_auth` (from
+/// `features.registry_auth`) when it has an entry for the registry, and with
+/// `~/.docker/config.json` credentials otherwise.
 pub async fn download_feature(
     source: &FeatureSource,
     config_dir: &Path,
     cache_dir: &Path,
+    registry_auth: &HashMap<String, RegistryAuth>,
     progress: &Option<mpsc::UnboundedSender<String>>,
 ) -> Result<PathBuf> {
+    fetch_feature(source, config_dir, cache_dir, registry_auth, progress)
+        .await
+        .map(|(dir, _)| dir)
+}
+
+/// Like [`download_feature`], also reporting whether the directory is local
+/// to the workspace, came from the cache, or was just downloaded
+pub async fn fetch_feature(
+    source: &FeatureSource,
+    config_dir: &Path,
+    cache_dir: &Path,
+    registry_auth: &HashMap<String, RegistryAuth>,
+    progress: &Option<mpsc::UnboundedSender<String>>,
+) -> Result<(PathBuf, FeatureStatus)> {
     match source {
         FeatureSource::Oci {
             registry,
             namespace,
             name,
             tag,
-        } => download_oci_feature(registry, namespace, name, tag, cache_dir, progress).await,
+        } => {
+            let credential = registry_credential(registry, registry_auth).await;
+            download_oci_feature(
+                registry, namespace, name, tag, credential, cache_dir, progress,
+            )
+            .await
+        }
         FeatureSource::Local { path } => {
             let resolved = if path.is_relative() {
                 config_dir.join(path)
@@ -104,11 +134,35 @@ pub async fn download_feature(
                 });
             }
 
-            Ok(resolved)
+            Ok((resolved, FeatureStatus::Local))
+        }
+        FeatureSource::LocalTarball { path } => {
+            let resolved = if path.is_relative() {
+                config_dir.join(path)
+            } else {
+                path.clone()
+            };
+            extract_local_tarball_feature(&resolved, cache_dir, progress)
+                .map(|dir| (dir, FeatureStatus::Local))
         }
         FeatureSource::TarballUrl { url } => {
             download_tarball_feature(url, cache_dir, progress).await
         }
+        FeatureSource::Workspace { name, fallback } => {
+            let dir = config_dir.join(WORKSPACE_FEATURES_DIR).join(name);
+            if dir.join("install.sh").exists() {
+                send_progress(progress, &format!("Feature {}: workspace", name));
+                return Ok((dir, FeatureStatus::Local));
+            }
+            Box::pin(fetch_feature(
+                fallback,
+                config_dir,
+                cache_dir,
+                registry_auth,
+                progress,
+            ))
+            .await
+        }
     }
 }
 
@@ -118,9 +172,10 @@ async fn download_oci_feature(
     namespace: &str,
     name: &str,
     tag: &str,
+    credential: Option<RegistryCredential>,
     cache_dir: &Path,
     progress: &Option<mpsc::UnboundedSender<String>>,
-) -> Result<PathBuf> {
+) -> Result<(PathBuf, FeatureStatus)> {
     let feature_cache = cache_dir
         .join(registry)
         .join(namespace)
@@ -130,7 +185,7 @@ async fn download_oci_feature(
     // Check cache
     if feature_cache.join("install.sh").exists() {
         send_progress(progress, &format!("Feature {}/{}: cached", namespace, name));
-        return Ok(feature_cache);
+        return Ok((feature_cache, FeatureStatus::Cached));
     }
 
     send_progress(
@@ -138,13 +193,13 @@ async fn download_oci_feature(
         &format!("Downloading feature {}/{}:{}...", namespace, name, tag),
     );
 
-    let base_url = format!("https://{}", registry);
+    let base_url = registry_base_url(registry);
     let repo = format!("{}/{}", namespace, name);
 
     let client = reqwest::Client::new();
 
-    // Step 1: Get auth token
-    let token = get_auth_token(&client, &base_url, &repo, registry)
+    // Step 1: Work out how to authorize with the registry
+    let auth = authorize_registry(&client, &base_url, &repo, credential)
         .await
         .map_err(|e| CoreError::FeatureDownloadFailed {
             feature: format!("{}/{}/{}:{}", registry, namespace, name, tag),
@@ -153,9 +208,8 @@ async fn download_oci_feature(
 
     // Step 2: Get manifest
     let manifest_url = format!("{}/v2/{}/manifests/{}", base_url, repo, tag);
-    let manifest_resp = client
-        .get(&manifest_url)
-        .header("Authorization", format!("Bearer {}", token))
+    let manifest_resp = auth
+        .apply(client.get(&manifest_url))
         .header("Accept", "application/vnd.oci.image.manifest.v1+json")
         .send()
         .await
@@ -202,9 +256,8 @@ async fn download_oci_feature(
         .build()
         .unwrap_or_else(|_| reqwest::Client::new());
 
-    let blob_resp = no_redirect_client
-        .get(&blob_url)
-        .header("Authorization", format!("Bearer {}", token))
+    let blob_resp = auth
+        .apply(no_redirect_client.get(&blob_url))
         .send()
         .await
         .map_err(|e| CoreError::FeatureDownloadFailed {
@@ -278,7 +331,7 @@ async fn download_oci_feature(
         &format!("Feature {}/{}: downloaded", namespace, name),
     );
 
-    Ok(feature_cache)
+    Ok((feature_cache, FeatureStatus::Downloaded))
 }
 
 /// Compute a deterministic cache key for a tarball URL.
@@ -305,7 +358,7 @@ async fn download_tarball_feature(
     url: &str,
     cache_dir: &Path,
     progress: &Option<mpsc::UnboundedSender<String>>,
-) -> Result<PathBuf> {
+) -> Result<(PathBuf, FeatureStatus)> {
     // Enforce HTTPS for remote URLs (allow localhost for local development)
     if !url.starts_with("https://")
         && !url.starts_with("http://localhost")
@@ -324,7 +377,7 @@ async fn download_tarball_feature(
     // Check cache
     if feature_cache.join("install.sh").exists() {
         send_progress(progress, &format!("Feature {}: cached", url));
-        return Ok(feature_cache);
+        return Ok((feature_cache, FeatureStatus::Cached));
     }
 
     send_progress(progress, &format!("Downloading feature {}...", url));
@@ -357,16 +410,7 @@ async fn download_tarball_feature(
     // Extract tarball (auto-detect gzip)
     std::fs::create_dir_all(&feature_cache)?;
 
-    let extract_result = if is_gzip(&bytes) {
-        let decoder = flate2::read::GzDecoder::new(std::io::Cursor::new(&bytes));
-        let mut archive = tar::Archive::new(decoder);
-        safe_unpack(&mut archive, &feature_cache)
-    } else {
-        let mut archive = tar::Archive::new(std::io::Cursor::new(&bytes));
-        safe_unpack(&mut archive, &feature_cache)
-    };
-
-    extract_result.map_err(|e| {
+    unpack_tarball(&bytes, &feature_cache).map_err(|e| {
         let _ = std::fs::remove_dir_all(&feature_cache);
         CoreError::FeatureDownloadFailed {
             feature: url.to_string(),
@@ -384,6 +428,74 @@ async fn download_tarball_feature(
 
     send_progress(progress, &format!("Feature {}: downloaded", url));
 
+    Ok((feature_cache, FeatureStatus::Downloaded))
+}
+
+/// Unpack a tar archive, gzip-compressed or not, into `dest`.
+fn unpack_tarball(bytes: &[u8], dest: &Path) -> std::result::Result<(), String> {
+    if is_gzip(bytes) {
+        let decoder = flate2::read::GzDecoder::new(std::io::Cursor::new(bytes));
+        safe_unpack(&mut tar::Archive::new(decoder), dest)
+    } else {
+        safe_unpack(&mut tar::Archive::new(std::io::Cursor::new(bytes)), dest)
+    }
+}
+
+/// Extract a feature archive from the host filesystem.
+///
+/// Caches under `{cache_dir}/local/{hash}/`, keyed on the archive's path, size
+/// and modification time so an edited archive is re-extracted. Unlike URL
+/// tarballs, the archive must carry `devcontainer-feature.json` as well as
+/// `install.sh`, matching what an OCI feature provides.
+fn extract_local_tarball_feature(
+    archive_path: &Path,
+    cache_dir: &Path,
+    progress: &Option<mpsc::UnboundedSender<String>>,
+) -> Result<PathBuf> {
+    let feature = archive_path.display().to_string();
+    let fail = |reason: String| CoreError::FeatureDownloadFailed {
+        feature: feature.clone(),
+        reason,
+    };
+
+    let file_meta = std::fs::metadata(archive_path)
+        .map_err(|e| fail(format!("Cannot read feature archive: {}", e)))?;
+    let modified = file_meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let hash = tarball_cache_key(&format!("{}:{}:{}", feature, file_meta.len(), modified));
+    let feature_cache = cache_dir.join("local").join(&hash);
+
+    if feature_cache.join("install.sh").exists()
+        && feature_cache.join("devcontainer-feature.json").exists()
+    {
+        send_progress(progress, &format!("Feature {}: cached", feature));
+        return Ok(feature_cache);
+    }
+
+    let bytes = std::fs::read(archive_path)
+        .map_err(|e| fail(format!("Cannot read feature archive: {}", e)))?;
+    std::fs::create_dir_all(&feature_cache)?;
+    unpack_tarball(&bytes, &feature_cache).map_err(|e| {
+        let _ = std::fs::remove_dir_all(&feature_cache);
+        fail(format!("Failed to extract tarball: {}", e))
+    })?;
+
+    for required in ["devcontainer-feature.json", "install.sh"] {
+        if !feature_cache.join(required).exists() {
+            let _ = std::fs::remove_dir_all(&feature_cache);
+            return Err(fail(format!(
+                "Feature archive does not contain {}",
+                required
+            )));
+        }
+    }
+
+    send_progress(progress, &format!("Feature {}: extracted", feature));
+
     Ok(feature_cache)
 }
 
@@ -400,19 +512,94 @@ pub fn read_feature_metadata(feature_dir: &Path) -> FeatureMetadata {
     }
 }
 
-/// Get an authentication token from the OCI registry.
+/// Credentials for a registry, before knowing which scheme it wants
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RegistryCredential {
+    /// Bearer token used as is
+    Token(String),
+    /// Username and password, for basic auth or the token exchange
+    Login(String, String),
+}
+
+/// How requests to a registry are authorized
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RegistryAuthorization {
+    Anonymous,
+    Bearer(String),
+    Basic(String, String),
+}
+
+impl RegistryAuthorization {
+    fn apply(&self, req: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self {
+            Self::Anonymous => req,
+            Self::Bearer(token) => req.bearer_auth(token),
+            Self::Basic(user, pass) => req.basic_auth(user, Some(pass)),
+        }
+    }
+}
+
+/// Credentials for `registry`: its `features.registry_auth` entry, falling
+/// back to `~/.docker/config.json`.
+async fn registry_credential(
+    registry: &str,
+    registry_auth: &HashMap<String, RegistryAuth>,
+) -> Option<RegistryCredential> {
+    if let Some(auth) = registry_auth.get(registry) {
+        if let Some(token) = &auth.token {
+            return Some(RegistryCredential::Token(token.clone()));
+        }
+        if let (Some(user), Some(pass)) = (&auth.username, &auth.password) {
+            return Some(RegistryCredential::Login(user.clone(), pass.clone()));
+        }
+        tracing::warn!(
+            "features.registry_auth for {} needs a token or a username and password",
+            registry
+        );
+    }
+    crate::credentials::host::resolve_credential_for_registry(registry)
+        .await
+        .map(|(user, pass)| RegistryCredential::Login(user, pass))
+}
+
+/// Base URL for a registry; ones on this machine are reached over plain
+/// HTTP, as local tarball URLs are.
+fn registry_base_url(registry: &str) -> String {
+    let local = ["localhost", "127.0.0.1", "[::1]"].iter().any(|host| {
+        registry == *host
+            || registry
+                .strip_prefix(host)
+                .is_some_and(|rest| rest.starts_with(':'))
+    });
+    if local {
+        format!("http://{}", registry)
+    } else {
+        format!("https://{}", registry)
+    }
+}
+
+/// Work out how to authorize pulls from a registry.
 ///
-/// Follows the Docker v2 token auth flow:
-/// 1. GET /v2/ → 401 with WWW-Authenticate header
-/// 2. Parse realm, service from WWW-Authenticate
-/// 3. GET <realm>?service=<service>&scope=repository:<repo>:pull
-async fn get_auth_token(
+/// A configured token is used directly. Otherwise follows the Docker v2 auth
+/// flow:
+/// 1. GET /v2/ → 200 (anonymous) or 401 with WWW-Authenticate header
+/// 2. `Basic` challenges use the username and password directly
+/// 3. `Bearer` challenges exchange them (or nothing, anonymously) at
+///    <realm>?service=<service>&scope=repository:<repo>:pull
+async fn authorize_registry(
     client: &reqwest::Client,
     base_url: &str,
     repo: &str,
-    registry: &str,
-) -> std::result::Result<String, String> {
-    // Try to get the WWW-Authenticate header
+    credential: Option<RegistryCredential>,
+) -> std::result::Result<RegistryAuthorization, String> {
+    let login = match credential {
+        Some(RegistryCredential::Token(token)) => {
+            return Ok(RegistryAuthorization::Bearer(token));
+        }
+        Some(RegistryCredential::Login(user, pass)) => Some((user, pass)),
+        None => None,
+    };
+
     let v2_url = format!("{}/v2/", base_url);
     let resp = client
         .get(&v2_url)
@@ -420,9 +607,9 @@ async fn get_auth_token(
         .await
         .map_err(|e| format!("Failed to reach registry: {}", e))?;
 
-    if resp.status() == 200 {
+    if resp.status().is_success() {
         // No auth needed (unusual but possible)
-        return Ok(String::new());
+        return Ok(RegistryAuthorization::Anonymous);
     }
 
     let www_auth = resp
@@ -432,17 +619,26 @@ async fn get_auth_token(
         .ok_or_else(|| "No WWW-Authenticate header in 401 response".to_string())?
         .to_string();
 
-    let (realm, service) = parse_www_authenticate(&www_auth)?;
+    if www_auth
+        .get(..6)
+        .is_some_and(|scheme| scheme.eq_ignore_ascii_case("basic "))
+    {
+        return login
+            .map(|(user, pass)| RegistryAuthorization::Basic(user, pass))
+            .ok_or_else(|| {
+                "registry requires a login; run `docker login` or set features.registry_auth"
+                    .to_string()
+            });
+    }
 
-    // Try to get credentials from docker config for private registries
-    let creds = crate::credentials::host::resolve_credential_for_registry(registry).await;
+    let (realm, service) = parse_www_authenticate(&www_auth)?;
 
     let scope = format!("repository:{}:pull", repo);
     let mut token_req = client
         .get(&realm)
         .query(&[("service", &service), ("scope", &scope)]);
 
-    if let Some((user, pass)) = creds {
+    if let Some((user, pass)) = login {
         token_req = token_req.basic_auth(user, Some(pass));
     }
 
@@ -460,10 +656,12 @@ async fn get_auth_token(
         .await
         .map_err(|e| format!("Failed to parse token response: {}", e))?;
 
+    // The spec allows `access_token` in place of `token`
     token_json
         .get("token")
+        .or_else(|| token_json.get("access_token"))
         .and_then(|t| t.as_str())
-        .map(|s| s.to_string())
+        .map(|s| RegistryAuthorization::Bearer(s.to_string()))
         .ok_or_else(|| "No token field in response".to_string())
 }
 
@@ -578,12 +776,57 @@ mod tests {
             },
             tmp.path(),
             tmp.path(),
+            &HashMap::new(),
             &None,
         ));
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), tmp.path().join("./my-feature"));
     }
 
+    #[test]
+    fn test_workspace_feature_resolves_from_config_dir() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let feature_dir = config_dir.path().join("features").join("my-local");
+        std::fs::create_dir_all(&feature_dir).unwrap();
+        std::fs::write(feature_dir.join("install.sh"), "#!/bin/bash\necho hi").unwrap();
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let source = super::super::resolve::parse_feature_ref("feature:my-local");
+        let result = rt.block_on(download_feature(
+            &source,
+            config_dir.path(),
+            config_dir.path(),
+            &HashMap::new(),
+            &None,
+        ));
+        assert_eq!(result.unwrap(), feature_dir);
+    }
+
+    #[test]
+    fn test_workspace_feature_falls_back_to_oci() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let cache_dir = tempfile::tempdir().unwrap();
+        // A cached OCI artifact stands in for the registry
+        let cached = cache_dir
+            .path()
+            .join("ghcr.io/devcontainers/features/node/1");
+        std::fs::create_dir_all(&cached).unwrap();
+        std::fs::write(cached.join("install.sh"), "#!/bin/bash\necho hi").unwrap();
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let source = super::super::resolve::parse_feature_ref(
+            "feature:ghcr.io/devcontainers/features/node:1",
+        );
+        let result = rt.block_on(download_feature(
+            &source,
+            config_dir.path(),
+            cache_dir.path(),
+            &HashMap::new(),
+            &None,
+        ));
+        assert_eq!(result.unwrap(), cached);
+    }
+
     #[test]
     fn test_local_feature_missing_install_sh() {
         let tmp = tempfile::tempdir().unwrap();
@@ -597,6 +840,7 @@ mod tests {
             },
             tmp.path(),
             tmp.path(),
+            &HashMap::new(),
             &None,
         ));
         assert!(result.is_err());
@@ -878,7 +1122,8 @@ mod tests {
 
         server.await.unwrap();
 
-        let feature_dir = result.expect("download should succeed");
+        let (feature_dir, status) = result.expect("download should succeed");
+        assert_eq!(status, FeatureStatus::Downloaded);
         assert!(feature_dir.join("install.sh").exists());
         assert!(feature_dir.join("devcontainer-feature.json").exists());
 
@@ -888,6 +1133,229 @@ mod tests {
         // Verify caching: second call should return cached path
         // (server is gone so it would fail if it tried to download again)
         let result2 = download_tarball_feature(&url, cache_dir.path(), &None).await;
-        assert_eq!(result2.unwrap(), feature_dir);
+        assert_eq!(result2.unwrap(), (feature_dir, FeatureStatus::Cached));
+    }
+
+    #[test]
+    fn test_registry_base_url_uses_http_only_locally() {
+        assert_eq!(registry_base_url("ghcr.io"), "https://ghcr.io");
+        assert_eq!(registry_base_url("127.0.0.1:5000"), "http://127.0.0.1:5000");
+        assert_eq!(registry_base_url("localhost"), "http://localhost");
+        assert_eq!(
+            registry_base_url("localhost.example.com"),
+            "https://localhost.example.com"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_oci_download_sends_configured_registry_auth() {
+        use base64::Engine;
+        use std::sync::{Arc, Mutex};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let layer = {
+            let mut archive = tar::Builder::new(Vec::new());
+            let install_sh = b"#!/bin/sh\necho private\n";
+            let mut header = tar::Header::new_gnu();
+            header.set_size(install_sh.len() as u64);
+            header.set_mode(0o755);
+            header.set_cksum();
+            archive
+                .append_data(&mut header, "install.sh", &install_sh[..])
+                .unwrap();
+            archive.into_inner().unwrap()
+        };
+
+        let listener = match tokio::net::TcpListener::bind("127.0.0.1:0").await {
+            Ok(listener) => listener,
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => return,
+            Err(e) => panic!("failed to bind local test server: {}", e),
+        };
+        let registry = format!("127.0.0.1:{}", listener.local_addr().unwrap().port());
+
+        // A registry that only answers with the token it issued for ci:secret
+        // (path, Authorization header) of every request
+        type Seen = Arc<Mutex<Vec<(String, Option<String>)>>>;
+        let seen: Seen = Arc::default();
+        let server = {
+            let seen = seen.clone();
+            let registry = registry.clone();
+            tokio::spawn(async move {
+                let basic = format!(
+                    "Basic {}",
+                    base64::engine::general_purpose::STANDARD.encode("ci:secret")
+                );
+                loop {
+                    let Ok((mut socket, _)) = listener.accept().await else {
+                        break;
+                    };
+                    let mut buf = vec![0u8; 8192];
+                    let n = socket.read(&mut buf).await.unwrap_or(0);
+                    let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                    let path = request
+                        .split_whitespace()
+                        .nth(1)
+                        .unwrap_or_default()
+                        .to_string();
+                    let auth = request.lines().find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("authorization")
+                            .then(|| value.trim().to_string())
+                    });
+                    seen.lock().unwrap().push((path.clone(), auth.clone()));
+
+                    let bearer = auth.as_deref() == Some("Bearer t0k");
+                    let (status, headers, body): (&str, String, Vec<u8>) = if path == "/v2/" {
+                        (
+                            "401 Unauthorized",
+                            format!(
+                                "WWW-Authenticate: Bearer realm=\"http://{}/token\",service=\"mock\"\r\n",
+                                registry
+                            ),
+                            Vec::new(),
+                        )
+                    } else if path.starts_with("/token") && auth.as_deref() == Some(&basic) {
+                        ("200 OK", String::new(), br#"{"token":"t0k"}"#.to_vec())
+                    } else if path == "/v2/team/private/manifests/1" && bearer {
+                        let manifest = r#"{"schemaVersion":2,"layers":[{"mediaType":"application/vnd.devcontainers.layer.v1+tar","digest":"sha256:abc","size":1}]}"#;
+                        ("200 OK", String::new(), manifest.as_bytes().to_vec())
+                    } else if path == "/v2/team/private/blobs/sha256:abc" && bearer {
+                        ("200 OK", String::new(), layer.clone())
+                    } else {
+                        ("401 Unauthorized", String::new(), Vec::new())
+                    };
+                    let head = format!(
+                        "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n",
+                        status,
+                        headers,
+                        body.len()
+                    );
+                    let _ = socket.write_all(head.as_bytes()).await;
+                    let _ = socket.write_all(&body).await;
+                    let _ = socket.flush().await;
+                }
+            })
+        };
+
+        let source = FeatureSource::Oci {
+            registry: registry.clone(),
+            namespace: "team".to_string(),
+            name: "private".to_string(),
+            tag: "1".to_string(),
+        };
+        let registry_auth = HashMap::from([(
+            registry.clone(),
+            RegistryAuth {
+                username: Some("ci".to_string()),
+                password: Some("secret".to_string()),
+                token: None,
+            },
+        )]);
+        let config_dir = tempfile::tempdir().unwrap();
+        let cache_dir = tempfile::tempdir().unwrap();
+        let result = download_feature(
+            &source,
+            config_dir.path(),
+            cache_dir.path(),
+            &registry_auth,
+            &None,
+        )
+        .await;
+        server.abort();
+
+        let feature_dir = result.expect("download from the private registry should succeed");
+        assert!(feature_dir.join("install.sh").exists());
+        let seen = seen.lock().unwrap();
+        assert!(seen.iter().any(|(path, auth)| path.starts_with("/token")
+            && auth.as_deref().is_some_and(|a| a.starts_with("Basic "))));
+        assert!(seen
+            .iter()
+            .any(|(path, auth)| path.contains("/blobs/") && auth.as_deref() == Some("Bearer t0k")));
+    }
+
+    fn build_feature_tgz(files: &[(&str, &[u8])]) -> Vec<u8> {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+
+        let encoder = GzEncoder::new(Vec::new(), Compression::default());
+        let mut archive = tar::Builder::new(encoder);
+        for (name, data) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o755);
+            header.set_cksum();
+            archive.append_data(&mut header, name, *data).unwrap();
+        }
+        archive.into_inner().unwrap().finish().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_local_tarball_feature_extract() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let cache_dir = tempfile::tempdir().unwrap();
+        let tgz = build_feature_tgz(&[
+            ("install.sh", b"#!/bin/sh\necho local\n"),
+            (
+                "devcontainer-feature.json",
+                br#"{"id": "local-tgz", "version": "0.1.0"}"#,
+            ),
+        ]);
+        std::fs::write(config_dir.path().join("local-tgz.tgz"), tgz).unwrap();
+
+        let source = super::super::resolve::parse_feature_ref("./local-tgz.tgz");
+        let feature_dir = download_feature(
+            &source,
+            config_dir.path(),
+            cache_dir.path(),
+            &HashMap::new(),
+            &None,
+        )
+        .await
+        .expect("local tarball should extract");
+        assert!(feature_dir.starts_with(cache_dir.path()));
+        assert!(feature_dir.join("install.sh").exists());
+        assert_eq!(
+            read_feature_metadata(&feature_dir).id.as_deref(),
+            Some("local-tgz")
+        );
+
+        // Unchanged archive is served from the cache
+        let again = download_feature(
+            &source,
+            config_dir.path(),
+            cache_dir.path(),
+            &HashMap::new(),
+            &None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(again, feature_dir);
+    }
+
+    #[tokio::test]
+    async fn test_local_tarball_feature_requires_metadata() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let cache_dir = tempfile::tempdir().unwrap();
+        let tgz = build_feature_tgz(&[("install.sh", b"#!/bin/sh\n")]);
+        std::fs::write(config_dir.path().join("bare.tgz"), tgz).unwrap();
+
+        let err = download_feature(
+            &FeatureSource::LocalTarball {
+                path: PathBuf::from("./bare.tgz"),
+            },
+            config_dir.path(),
+            cache_dir.path(),
+            &HashMap::new(),
+            &None,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("devcontainer-feature.json"));
+        assert!(!cache_dir
+            .path()
+            .join("local")
+            .read_dir()
+            .unwrap()
+            .any(|_| true));
     }
 }
@@ -8,10 +8,12 @@ pub mod compose_override;
 pub mod dockerfile;
 pub mod download;
 pub mod install;
+pub mod progress;
 pub mod resolve;
 
 use crate::{CoreError, Result};
-use devc_config::FeatureConfig;
+use devc_config::{FeatureConfig, RegistryAuth};
+use progress::{FeatureEvent, FeatureProgress, FeatureStatus};
 use resolve::{
     feature_options, merge_options_with_defaults, order_features, parse_depends_on_value,
     parse_feature_ref, ResolvedFeature,
@@ -19,17 +21,24 @@ use resolve::{
 pub use resolve::{merge_feature_properties, MergedFeatureProperties};
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::sync::Mutex;
 use tokio::sync::mpsc;
 
 /// Resolve, download, and order all features from a devcontainer config.
 ///
 /// Returns an ordered list of ResolvedFeature ready for Dockerfile generation.
 /// Features disabled with `false` are filtered out. Transitive `dependsOn`
-/// dependencies are automatically pulled in.
+/// dependencies are automatically pulled in, and `install_order`
+/// (`overrideFeatureInstallOrder`) is applied when ordering. Each feature's
+/// status is sent as a [`FeatureEvent`] on `events`; without a listener, a
+/// line per finished feature goes to `progress` instead.
 pub async fn resolve_and_prepare_features(
     features: &HashMap<String, FeatureConfig>,
+    install_order: &[String],
     config_dir: &Path,
+    registry_auth: &HashMap<String, RegistryAuth>,
     progress: &Option<mpsc::UnboundedSender<String>>,
+    events: Option<&mpsc::UnboundedSender<FeatureEvent>>,
 ) -> Result<Vec<ResolvedFeature>> {
     if features.is_empty() {
         return Ok(vec![]);
@@ -65,19 +74,48 @@ pub async fn resolve_and_prepare_features(
     let mut resolved_map: HashMap<String, ResolvedFeature> = HashMap::new();
     // Track declaration order: user features first, then transitive deps in discovery order
     let mut declaration_order: Vec<String> = Vec::new();
+    let feature_progress = Mutex::new(FeatureProgress::default());
 
     while !worklist.is_empty() {
         // Download all worklist items in parallel
         let mut download_futures = Vec::new();
         for (id, _) in &worklist {
+            let index = report_feature_status(
+                &feature_progress,
+                id,
+                FeatureStatus::Fetching,
+                progress,
+                events,
+            );
             let id = id.clone();
             let source = parse_feature_ref(&id);
             let cache_dir = cache_dir.clone();
             let config_dir = config_dir.to_path_buf();
-            let progress = progress.clone();
+            let feature_progress = &feature_progress;
             download_futures.push(async move {
-                let dir =
-                    download::download_feature(&source, &config_dir, &cache_dir, &progress).await?;
+                // Collect this feature's own lines so they don't interleave
+                // with the others; listeners get them tagged with the feature
+                let (raw_tx, mut raw_rx) = mpsc::unbounded_channel();
+                let result = download::fetch_feature(
+                    &source,
+                    &config_dir,
+                    &cache_dir,
+                    registry_auth,
+                    &Some(raw_tx),
+                )
+                .await;
+                while let Ok(line) = raw_rx.try_recv() {
+                    tracing::debug!("{}", line);
+                    if let Some(tx) = events {
+                        let _ = tx.send(FeatureEvent::Line { index, line });
+                    }
+                }
+                let status = match &result {
+                    Ok((_, status)) => *status,
+                    Err(_) => FeatureStatus::Failed,
+                };
+                report_feature_status(feature_progress, &id, status, progress, events);
+                let (dir, _) = result?;
                 Ok::<(String, std::path::PathBuf), CoreError>((id, dir))
             });
         }
@@ -137,10 +175,236 @@ pub async fn resolve_and_prepare_features(
         .filter_map(|id| resolved_map.remove(&id))
         .collect();
 
-    let ordered = order_features(resolved)?;
+    let ordered = order_features(resolved, install_order)?;
     Ok(ordered)
 }
 
+/// Record a feature's new status and report it, returning the feature's index
+fn report_feature_status(
+    feature_progress: &Mutex<FeatureProgress>,
+    id: &str,
+    status: FeatureStatus,
+    progress: &Option<mpsc::UnboundedSender<String>>,
+    events: Option<&mpsc::UnboundedSender<FeatureEvent>>,
+) -> usize {
+    let (index, done, total) = {
+        let mut feature_progress = feature_progress.lock().unwrap();
+        let index = feature_progress.set(id, status);
+        (index, feature_progress.done(), feature_progress.total())
+    };
+    match (events, progress) {
+        (Some(tx), _) => {
+            let _ = tx.send(FeatureEvent::Status {
+                index,
+                total,
+                id: id.to_string(),
+                status,
+            });
+        }
+        (None, Some(tx)) if status != FeatureStatus::Fetching => {
+            let _ = tx.send(format!("Feature {}: {} ({}/{})", id, status, done, total));
+        }
+        (None, _) => {}
+    }
+    index
+}
+
+/// State metadata keys recording a container's last feature resolution
+const FEATURES_HASH_KEY: &str = "features_hash";
+const RESOLVED_FEATURES_KEY: &str = "resolved_features";
+const FEATURE_PROPERTIES_KEY: &str = "feature_properties";
+const RESOLVED_AT_KEY: &str = "features_resolved_at";
+
+/// How long a resolution is reused when it includes features that can change
+/// upstream without the reference changing (OCI tags, tarball URLs)
+const MUTABLE_REF_TTL: chrono::Duration = chrono::Duration::hours(24);
+
+/// Deterministic 64-bit FNV-1a hash, stable across runs for persisted keys.
+fn fnv1a64(input: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    let mut hash = OFFSET_BASIS;
+    for b in input {
+        hash ^= u64::from(*b);
+        hash = hash.wrapping_mul(PRIME);
+    }
+    hash
+}
+
+/// Hash of the files under a local feature directory (or of a local tarball),
+/// covering relative paths and contents; missing paths hash as empty
+fn local_content_hash(path: &Path) -> u64 {
+    fn collect(dir: &Path, root: &Path, out: &mut Vec<u8>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        let mut paths: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+        paths.sort();
+        for path in paths {
+            if path.is_dir() {
+                collect(&path, root, out);
+            } else if let Ok(bytes) = std::fs::read(&path) {
+                let relative = path.strip_prefix(root).unwrap_or(&path);
+                out.extend_from_slice(relative.to_string_lossy().as_bytes());
+                out.push(0);
+                out.extend_from_slice(&fnv1a64(&bytes).to_le_bytes());
+            }
+        }
+    }
+
+    let mut content = Vec::new();
+    if path.is_dir() {
+        collect(path, path, &mut content);
+    } else if let Ok(bytes) = std::fs::read(path) {
+        content = bytes;
+    }
+    fnv1a64(&content)
+}
+
+/// Where a local feature reference points on disk, if it is one
+fn local_feature_path(id: &str, config_dir: &Path) -> Option<std::path::PathBuf> {
+    match parse_feature_ref(id) {
+        resolve::FeatureSource::Local { path } | resolve::FeatureSource::LocalTarball { path } => {
+            Some(config_dir.join(path))
+        }
+        resolve::FeatureSource::Workspace { name, .. } => {
+            let dir = config_dir.join(resolve::WORKSPACE_FEATURES_DIR).join(name);
+            dir.is_dir().then_some(dir)
+        }
+        _ => None,
+    }
+}
+
+/// Whether a feature reference can resolve to different content over time:
+/// anything remote that isn't pinned to a digest
+fn is_mutable_ref(id: &str) -> bool {
+    match parse_feature_ref(id) {
+        resolve::FeatureSource::Local { .. } | resolve::FeatureSource::LocalTarball { .. } => false,
+        _ => !id.contains("@sha256:"),
+    }
+}
+
+/// Hash of a devcontainer.json `features` section and its install order override.
+///
+/// Goes through `serde_json::Value`, whose objects are key-sorted, so the
+/// `HashMap` iteration order doesn't affect the result. An empty override
+/// leaves the hash as it was before overrides were supported. Local features
+/// (relative to `config_dir`) contribute the hash of their files, so editing
+/// one invalidates the resolution too.
+pub fn features_hash(
+    features: Option<&HashMap<String, FeatureConfig>>,
+    install_order: &[String],
+    config_dir: &Path,
+) -> String {
+    let mut canonical = serde_json::to_value(features)
+        .map(|v| v.to_string())
+        .unwrap_or_default();
+    if !install_order.is_empty() {
+        canonical.push_str(&serde_json::to_string(install_order).unwrap_or_default());
+    }
+    if let Some(features) = features {
+        let mut ids: Vec<&String> = features.keys().collect();
+        ids.sort();
+        for id in ids {
+            if let Some(path) = local_feature_path(id, config_dir) {
+                canonical.push_str(&format!("\n{}={:016x}", id, local_content_hash(&path)));
+            }
+        }
+    }
+    format!("{:016x}", fnv1a64(canonical.as_bytes()))
+}
+
+/// The features resolved for a container, with their merged properties, the
+/// hash of the `features` section they came from and when they were resolved
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedFeatureSet {
+    pub features: Vec<ResolvedFeature>,
+    pub properties: MergedFeatureProperties,
+    pub hash: String,
+    pub resolved_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl ResolvedFeatureSet {
+    /// Resolve and download a `features` section from scratch
+    pub async fn resolve(
+        features: Option<&HashMap<String, FeatureConfig>>,
+        install_order: &[String],
+        config_dir: &Path,
+        registry_auth: &HashMap<String, RegistryAuth>,
+        progress: &Option<mpsc::UnboundedSender<String>>,
+        events: Option<&mpsc::UnboundedSender<FeatureEvent>>,
+    ) -> Result<Self> {
+        let resolved = match features {
+            Some(map) => {
+                resolve_and_prepare_features(
+                    map,
+                    install_order,
+                    config_dir,
+                    registry_auth,
+                    progress,
+                    events,
+                )
+                .await?
+            }
+            None => vec![],
+        };
+        Ok(Self {
+            properties: merge_feature_properties(&resolved),
+            features: resolved,
+            hash: features_hash(features, install_order, config_dir),
+            resolved_at: chrono::Utc::now(),
+        })
+    }
+
+    /// The resolution recorded in `metadata`, if it was made from a `features`
+    /// section with this hash and its feature directories still exist
+    pub fn from_metadata(metadata: &HashMap<String, String>, hash: &str) -> Option<Self> {
+        if metadata.get(FEATURES_HASH_KEY)? != hash {
+            return None;
+        }
+        let features: Vec<ResolvedFeature> =
+            serde_json::from_str(metadata.get(RESOLVED_FEATURES_KEY)?).ok()?;
+        if !features.iter().all(|f| f.dir.is_dir()) {
+            return None;
+        }
+        let resolved_at = metadata
+            .get(RESOLVED_AT_KEY)
+            .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())?
+            .with_timezone(&chrono::Utc);
+        if features.iter().any(|f| is_mutable_ref(&f.id))
+            && chrono::Utc::now() - resolved_at > MUTABLE_REF_TTL
+        {
+            return None;
+        }
+        let properties = serde_json::from_str(metadata.get(FEATURE_PROPERTIES_KEY)?).ok()?;
+        Some(Self {
+            features,
+            properties,
+            hash: hash.to_string(),
+            resolved_at,
+        })
+    }
+
+    /// Record this resolution in container state metadata
+    pub fn record(&self, metadata: &mut HashMap<String, String>) {
+        if let Ok(json) = serde_json::to_string(&self.properties) {
+            metadata.insert(FEATURE_PROPERTIES_KEY.to_string(), json);
+        }
+        match serde_json::to_string(&self.features) {
+            Ok(json) => {
+                metadata.insert(RESOLVED_FEATURES_KEY.to_string(), json);
+                metadata.insert(FEATURES_HASH_KEY.to_string(), self.hash.clone());
+                metadata.insert(RESOLVED_AT_KEY.to_string(), self.resolved_at.to_rfc3339());
+            }
+            Err(_) => {
+                metadata.remove(RESOLVED_FEATURES_KEY);
+                metadata.remove(FEATURES_HASH_KEY);
+                metadata.remove(RESOLVED_AT_KEY);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -151,13 +415,76 @@ mod tests {
         let features = HashMap::new();
         let result = rt.block_on(resolve_and_prepare_features(
             &features,
+            &[],
             Path::new("/tmp"),
+            &HashMap::new(),
             &None,
+            None,
         ));
         assert!(result.is_ok());
         assert!(result.unwrap().is_empty());
     }
 
+    #[test]
+    fn test_features_hash_tracks_content_not_order() {
+        let mut a = HashMap::new();
+        let mut b = HashMap::new();
+        for (id, version) in [("node:1", "20"), ("python:1", "3.12"), ("go:1", "1.22")] {
+            a.insert(id.to_string(), FeatureConfig::Version(version.to_string()));
+        }
+        for (id, version) in [("go:1", "1.22"), ("node:1", "20"), ("python:1", "3.12")] {
+            b.insert(id.to_string(), FeatureConfig::Version(version.to_string()));
+        }
+        let dir = Path::new("/nonexistent");
+        assert_eq!(
+            features_hash(Some(&a), &[], dir),
+            features_hash(Some(&b), &[], dir)
+        );
+
+        b.insert(
+            "node:1".to_string(),
+            FeatureConfig::Version("22".to_string()),
+        );
+        assert_ne!(
+            features_hash(Some(&a), &[], dir),
+            features_hash(Some(&b), &[], dir)
+        );
+    }
+
+    #[test]
+    fn test_recorded_resolution_of_tagged_feature_expires() {
+        let dir = tempfile::tempdir().unwrap();
+        let feature = |id: &str| ResolvedFeature {
+            id: id.to_string(),
+            dir: dir.path().to_path_buf(),
+            options: HashMap::new(),
+            metadata: Default::default(),
+        };
+        let record = |id: &str, age: chrono::Duration| {
+            let set = ResolvedFeatureSet {
+                features: vec![feature(id)],
+                hash: "h".to_string(),
+                resolved_at: chrono::Utc::now() - age,
+                ..Default::default()
+            };
+            let mut metadata = HashMap::new();
+            set.record(&mut metadata);
+            metadata
+        };
+        let day = chrono::Duration::hours(25);
+
+        let tagged = "ghcr.io/devcontainers/features/node:1";
+        assert!(
+            ResolvedFeatureSet::from_metadata(&record(tagged, chrono::Duration::zero()), "h")
+                .is_some()
+        );
+        assert!(ResolvedFeatureSet::from_metadata(&record(tagged, day), "h").is_none());
+
+        let pinned = "ghcr.io/devcontainers/features/node@sha256:abc123";
+        assert!(ResolvedFeatureSet::from_metadata(&record(pinned, day), "h").is_some());
+        assert!(ResolvedFeatureSet::from_metadata(&record("./local", day), "h").is_some());
+    }
+
     #[test]
     fn test_resolve_all_disabled() {
         let rt = tokio::runtime::Runtime::new().unwrap();
@@ -168,8 +495,11 @@ mod tests {
         );
         let result = rt.block_on(resolve_and_prepare_features(
             &features,
+            &[],
             Path::new("/tmp"),
+            &HashMap::new(),
             &None,
+            None,
         ));
         assert!(result.is_ok());
         assert!(result.unwrap().is_empty());
@@ -191,7 +521,14 @@ mod tests {
         let mut features = HashMap::new();
         features.insert("./my-feature".to_string(), FeatureConfig::Bool(true));
 
-        let result = rt.block_on(resolve_and_prepare_features(&features, tmp.path(), &None));
+        let result = rt.block_on(resolve_and_prepare_features(
+            &features,
+            &[],
+            tmp.path(),
+            &HashMap::new(),
+            &None,
+            None,
+        ));
         assert!(result.is_ok());
         let resolved = result.unwrap();
         assert_eq!(resolved.len(), 1);
@@ -226,7 +563,14 @@ mod tests {
         let mut features = HashMap::new();
         features.insert("./feature-a".to_string(), FeatureConfig::Bool(true));
 
-        let result = rt.block_on(resolve_and_prepare_features(&features, tmp.path(), &None));
+        let result = rt.block_on(resolve_and_prepare_features(
+            &features,
+            &[],
+            tmp.path(),
+            &HashMap::new(),
+            &None,
+            None,
+        ));
         assert!(result.is_ok());
         let resolved = result.unwrap();
         assert_eq!(
@@ -245,6 +589,81 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resolve_reports_typed_events_per_change() {
+        let tmp = tempfile::tempdir().unwrap();
+        create_local_feature(
+            tmp.path(),
+            "feature-a",
+            r#"{"id": "feature-a", "dependsOn": {"./feature-e": {}}}"#,
+        );
+        create_local_feature(tmp.path(), "feature-e", r#"{"id": "feature-e"}"#);
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let mut features = HashMap::new();
+        features.insert("./feature-a".to_string(), FeatureConfig::Bool(true));
+        let (progress_tx, mut progress_rx) = mpsc::unbounded_channel();
+        let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+        rt.block_on(resolve_and_prepare_features(
+            &features,
+            &[],
+            tmp.path(),
+            &HashMap::new(),
+            &Some(progress_tx),
+            Some(&events_tx),
+        ))
+        .unwrap();
+
+        let status = |index, total, id: &str, status| FeatureEvent::Status {
+            index,
+            total,
+            id: id.to_string(),
+            status,
+        };
+        let mut events = Vec::new();
+        while let Ok(event) = events_rx.try_recv() {
+            events.push(event);
+        }
+        assert_eq!(
+            events,
+            vec![
+                status(0, 1, "./feature-a", FeatureStatus::Fetching),
+                status(0, 1, "./feature-a", FeatureStatus::Local),
+                // The dependency joins the same map
+                status(1, 2, "./feature-e", FeatureStatus::Fetching),
+                status(1, 2, "./feature-e", FeatureStatus::Local),
+            ]
+        );
+        // A listener replaces the plain lines
+        assert!(progress_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_resolve_without_listener_prints_finished_features() {
+        let tmp = tempfile::tempdir().unwrap();
+        create_local_feature(tmp.path(), "feature-a", r#"{"id": "feature-a"}"#);
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let mut features = HashMap::new();
+        features.insert("./feature-a".to_string(), FeatureConfig::Bool(true));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        rt.block_on(resolve_and_prepare_features(
+            &features,
+            &[],
+            tmp.path(),
+            &HashMap::new(),
+            &Some(tx),
+            None,
+        ))
+        .unwrap();
+
+        let mut lines = Vec::new();
+        while let Ok(line) = rx.try_recv() {
+            lines.push(line);
+        }
+        assert_eq!(lines, vec!["Feature ./feature-a: local (1/1)"]);
+    }
+
     #[test]
     fn test_resolve_transitive_chain() {
         // A depends on B, B depends on C (all local). User requests A.
@@ -273,7 +692,14 @@ mod tests {
         let mut features = HashMap::new();
         features.insert("./feature-a".to_string(), FeatureConfig::Bool(true));
 
-        let result = rt.block_on(resolve_and_prepare_features(&features, tmp.path(), &None));
+        let result = rt.block_on(resolve_and_prepare_features(
+            &features,
+            &[],
+            tmp.path(),
+            &HashMap::new(),
+            &None,
+            None,
+        ));
         assert!(result.is_ok());
         let resolved = result.unwrap();
         assert_eq!(resolved.len(), 3);
@@ -311,7 +737,14 @@ mod tests {
         );
         features.insert("./feature-b".to_string(), FeatureConfig::Options(b_opts));
 
-        let result = rt.block_on(resolve_and_prepare_features(&features, tmp.path(), &None));
+        let result = rt.block_on(resolve_and_prepare_features(
+            &features,
+            &[],
+            tmp.path(),
+            &HashMap::new(),
+            &None,
+            None,
+        ));
         assert!(result.is_ok());
         let resolved = result.unwrap();
 
@@ -0,0 +1,227 @@
+//! Structured progress for parallel feature downloads
+//!
+//! Features download concurrently, so their individual status lines would
+//! interleave. Instead, `resolve_and_prepare_features` sends a
+//! [`FeatureEvent`] whenever a feature's status changes, and the download's
+//! own messages as separate [`FeatureEvent::Line`]s. UIs fold the events into
+//! a [`FeatureProgress`] and render it as a stable table.
+
+use std::fmt;
+
+/// Where a single feature is in the download step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeatureStatus {
+    Fetching,
+    /// A directory or archive in the workspace, nothing to fetch
+    Local,
+    Cached,
+    Downloaded,
+    Failed,
+}
+
+impl FeatureStatus {
+    fn is_done(self) -> bool {
+        matches!(self, Self::Local | Self::Cached | Self::Downloaded)
+    }
+}
+
+impl fmt::Display for FeatureStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Fetching => "fetching",
+            Self::Local => "local",
+            Self::Cached => "cached",
+            Self::Downloaded => "downloaded",
+            Self::Failed => "failed",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// A change in the feature download step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeatureEvent {
+    /// A feature's status changed. `index` is its position in request order
+    /// and `total` the number of features known so far, which grows as
+    /// `dependsOn` pulls more in.
+    Status {
+        index: usize,
+        total: usize,
+        id: String,
+        status: FeatureStatus,
+    },
+    /// A message from one feature's download, for verbose output
+    Line { index: usize, line: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    id: String,
+    status: FeatureStatus,
+    lines: Vec<String>,
+}
+
+/// Status of every feature being downloaded, in the order they were requested
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeatureProgress {
+    entries: Vec<Entry>,
+}
+
+impl FeatureProgress {
+    /// Set a feature's status, adding it at the end if it's new, and return
+    /// its index
+    pub fn set(&mut self, id: &str, status: FeatureStatus) -> usize {
+        match self.entries.iter().position(|entry| entry.id == id) {
+            Some(index) => {
+                self.entries[index].status = status;
+                index
+            }
+            None => {
+                self.entries.push(Entry {
+                    id: id.to_string(),
+                    status,
+                    lines: Vec::new(),
+                });
+                self.entries.len() - 1
+            }
+        }
+    }
+
+    /// Fold an event received from another task into this map
+    pub fn apply(&mut self, event: FeatureEvent) {
+        match event {
+            FeatureEvent::Status {
+                index, id, status, ..
+            } => match self.entries.get_mut(index) {
+                Some(entry) => {
+                    entry.id = id;
+                    entry.status = status;
+                }
+                None => {
+                    self.set(&id, status);
+                }
+            },
+            FeatureEvent::Line { index, line } => {
+                if let Some(entry) = self.entries.get_mut(index) {
+                    entry.lines.push(line);
+                }
+            }
+        }
+    }
+
+    /// Number of features that finished without an error
+    pub fn done(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.status.is_done())
+            .count()
+    }
+
+    pub fn total(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Short summary, e.g. `Features 1/2`
+    pub fn summary(&self) -> String {
+        format!("Features {}/{}", self.done(), self.total())
+    }
+
+    /// The summary followed by one row per feature; with `verbose`, each
+    /// row is followed by that feature's own download messages
+    pub fn table(&self, verbose: bool) -> Vec<String> {
+        let width = self
+            .entries
+            .iter()
+            .map(|entry| entry.id.len())
+            .max()
+            .unwrap_or(0);
+        let mut rows = vec![self.summary()];
+        for entry in &self.entries {
+            rows.push(format!("  {:<width$}  {}", entry.id, entry.status));
+            if verbose {
+                rows.extend(entry.lines.iter().map(|line| format!("      {}", line)));
+            }
+        }
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(index: usize, total: usize, id: &str, status: FeatureStatus) -> FeatureEvent {
+        FeatureEvent::Status {
+            index,
+            total,
+            id: id.to_string(),
+            status,
+        }
+    }
+
+    #[test]
+    fn test_set_keeps_request_order() {
+        let mut progress = FeatureProgress::default();
+        assert_eq!(
+            progress.set(
+                "ghcr.io/devcontainers/features/node:1",
+                FeatureStatus::Fetching
+            ),
+            0
+        );
+        assert_eq!(progress.set("./local-feature", FeatureStatus::Fetching), 1);
+        assert_eq!(
+            progress.set(
+                "ghcr.io/devcontainers/features/node:1",
+                FeatureStatus::Cached
+            ),
+            0
+        );
+        assert_eq!(progress.summary(), "Features 1/2");
+    }
+
+    #[test]
+    fn test_apply_matches_set() {
+        let mut sent = FeatureProgress::default();
+        sent.set("node", FeatureStatus::Fetching);
+        sent.set("git", FeatureStatus::Downloaded);
+        sent.set("https://example.com/f.tgz", FeatureStatus::Failed);
+
+        let mut received = FeatureProgress::default();
+        received.apply(status(0, 1, "node", FeatureStatus::Fetching));
+        received.apply(status(1, 2, "git", FeatureStatus::Fetching));
+        received.apply(status(1, 2, "git", FeatureStatus::Downloaded));
+        received.apply(status(
+            2,
+            3,
+            "https://example.com/f.tgz",
+            FeatureStatus::Failed,
+        ));
+        assert_eq!(received, sent);
+    }
+
+    #[test]
+    fn test_table_shows_lines_only_when_verbose() {
+        let mut progress = FeatureProgress::default();
+        progress.apply(status(0, 1, "node", FeatureStatus::Cached));
+        progress.apply(status(1, 2, "git-lfs", FeatureStatus::Fetching));
+        progress.apply(FeatureEvent::Line {
+            index: 0,
+            line: "Feature devcontainers/node: cached".to_string(),
+        });
+
+        assert_eq!(
+            progress.table(false),
+            vec!["Features 1/2", "  node     cached", "  git-lfs  fetching"]
+        );
+        assert_eq!(
+            progress.table(true),
+            vec![
+                "Features 1/2",
+                "  node     cached",
+                "      Feature devcontainers/node: cached",
+                "  git-lfs  fetching",
+            ]
+        );
+    }
+}
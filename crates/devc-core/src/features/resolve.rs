@@ -4,7 +4,7 @@ use crate::CoreError;
 use devc_config::{Command, FeatureConfig, Mount};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Where a feature comes from
 #[derive(Debug, Clone)]
@@ -18,12 +18,26 @@ pub enum FeatureSource {
     },
     /// Local directory path
     Local { path: PathBuf },
+    /// Local `.tgz` / `.tar.gz` / `.tar` feature archive
+    LocalTarball { path: PathBuf },
     /// HTTP/HTTPS tarball URL
     TarballUrl { url: String },
+    /// `feature:<name>`: `features/<name>` next to devcontainer.json if it
+    /// exists, otherwise `fallback` (the name as an OCI reference)
+    Workspace {
+        name: String,
+        fallback: Box<FeatureSource>,
+    },
 }
 
+/// Prefix for features kept in the workspace's `.devcontainer/features` dir
+pub const WORKSPACE_FEATURE_PREFIX: &str = "feature:";
+
+/// Directory, relative to the config dir, holding workspace-local features
+pub const WORKSPACE_FEATURES_DIR: &str = "features";
+
 /// Metadata from devcontainer-feature.json inside a feature tarball
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default, rename_all = "camelCase")]
 pub struct FeatureMetadata {
     pub id: Option<String>,
@@ -66,7 +80,7 @@ pub struct FeatureMetadata {
 }
 
 /// A single option definition from devcontainer-feature.json
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct FeatureOptionDef {
     #[serde(default)]
     pub default: Option<serde_json::Value>,
@@ -227,7 +241,7 @@ pub fn merge_options_with_defaults(
 }
 
 /// A fully resolved feature ready for Dockerfile generation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResolvedFeature {
     /// The original feature ID string (e.g. "ghcr.io/devcontainers/features/node:1")
     pub id: String,
@@ -241,13 +255,31 @@ pub struct ResolvedFeature {
 
 /// Parse a feature reference string into a FeatureSource.
 ///
-/// Local paths start with `.` or `/`.
+/// Local paths start with `.` or `/`; those ending in a tarball extension are
+/// archives, the rest directories.
 /// URLs starting with `http://` or `https://` are treated as tarball URLs.
+/// `feature:<name>` looks in the workspace's features directory first, unless
+/// the name would lead out of it (`..` or a leading `/`), in which case it is
+/// only an OCI reference.
 /// Everything else is treated as an OCI reference.
 pub fn parse_feature_ref(id: &str) -> FeatureSource {
-    if id.starts_with('.') || id.starts_with('/') {
-        FeatureSource::Local {
-            path: PathBuf::from(id),
+    if let Some(name) = id.strip_prefix(WORKSPACE_FEATURE_PREFIX) {
+        if !is_workspace_feature_name(name) {
+            return parse_oci_ref(name);
+        }
+        FeatureSource::Workspace {
+            name: name.to_string(),
+            fallback: Box::new(parse_oci_ref(name)),
+        }
+    } else if id.starts_with('.') || id.starts_with('/') {
+        if strip_tarball_extension(id).is_some() {
+            FeatureSource::LocalTarball {
+                path: PathBuf::from(id),
+            }
+        } else {
+            FeatureSource::Local {
+                path: PathBuf::from(id),
+            }
         }
     } else if id.starts_with("https://") || id.starts_with("http://") {
         FeatureSource::TarballUrl {
@@ -258,6 +290,15 @@ pub fn parse_feature_ref(id: &str) -> FeatureSource {
     }
 }
 
+/// Whether `name` stays inside the workspace features directory when joined to it
+fn is_workspace_feature_name(name: &str) -> bool {
+    let path = Path::new(name);
+    !name.is_empty()
+        && path
+            .components()
+            .all(|c| matches!(c, std::path::Component::Normal(_)))
+}
+
 /// Parse an OCI feature reference like `ghcr.io/devcontainers/features/node:1`
 fn parse_oci_ref(id: &str) -> FeatureSource {
     // Split tag
@@ -351,7 +392,14 @@ pub fn parse_depends_on_value(value: &serde_json::Value) -> Option<HashMap<Strin
 /// Hard dependencies (`dependsOn`) must be satisfied — cycles among them produce an error.
 /// Soft dependencies (`installsAfter`) are best-effort — cycles are broken by falling back
 /// to declaration order.
-pub fn order_features(features: Vec<ResolvedFeature>) -> crate::Result<Vec<ResolvedFeature>> {
+///
+/// `install_order` is devcontainer.json's `overrideFeatureInstallOrder`: the features it
+/// lists install in that order, ahead of the others where dependencies allow, and win over
+/// `installsAfter`. Listing a feature before one it `dependsOn` is an error.
+pub fn order_features(
+    features: Vec<ResolvedFeature>,
+    install_order: &[String],
+) -> crate::Result<Vec<ResolvedFeature>> {
     if features.len() <= 1 {
         return Ok(features);
     }
@@ -388,7 +436,16 @@ pub fn order_features(features: Vec<ResolvedFeature>) -> crate::Result<Vec<Resol
         None
     };
 
+    // Position of each feature in overrideFeatureInstallOrder, if listed
+    let mut override_rank: Vec<Option<usize>> = vec![None; n];
+    for (rank, id) in install_order.iter().enumerate() {
+        if let Some(i) = find_idx(id, n) {
+            override_rank[i].get_or_insert(rank);
+        }
+    }
+
     // Build edges from dependsOn (hard)
+    let mut hard_deps: Vec<Vec<usize>> = vec![vec![]; n];
     for (i, f) in features.iter().enumerate() {
         if let Some(ref deps) = f.metadata.depends_on {
             for dep_id in deps.keys() {
@@ -396,6 +453,7 @@ pub fn order_features(features: Vec<ResolvedFeature>) -> crate::Result<Vec<Resol
                     dependents[j].push(i);
                     is_hard_edge[j].push(true);
                     after_count[i] += 1;
+                    hard_deps[i].push(j);
                 }
             }
         }
@@ -406,6 +464,12 @@ pub fn order_features(features: Vec<ResolvedFeature>) -> crate::Result<Vec<Resol
         if let Some(ref install_after) = f.metadata.install_after {
             for dep_id in install_after {
                 if let Some(j) = find_idx(dep_id, i) {
+                    // The override wins over installsAfter between listed features
+                    if let (Some(ri), Some(rj)) = (override_rank[i], override_rank[j]) {
+                        if ri < rj {
+                            continue;
+                        }
+                    }
                     // Avoid duplicate edge if already added via dependsOn
                     if !dependents[j].contains(&i) {
                         dependents[j].push(i);
@@ -418,8 +482,37 @@ pub fn order_features(features: Vec<ResolvedFeature>) -> crate::Result<Vec<Resol
         }
     }
 
-    // Kahn's algorithm, using declaration order as tiebreaker
+    // Chain the listed features in override order, after checking that no
+    // listed feature (transitively) depends on one listed after it
+    let mut listed: Vec<usize> = (0..n).filter(|&i| override_rank[i].is_some()).collect();
+    listed.sort_by_key(|&i| override_rank[i]);
+    for (pos, &earlier) in listed.iter().enumerate() {
+        for &later in &listed[pos + 1..] {
+            if depends_on_transitively(&hard_deps, earlier, later) {
+                return Err(CoreError::FeatureInstallOrderConflict(format!(
+                    "{} is listed before {}, but depends on it",
+                    features[earlier].id, features[later].id
+                )));
+            }
+        }
+    }
+    for pair in listed.windows(2) {
+        let (earlier, later) = (pair[0], pair[1]);
+        if !dependents[earlier].contains(&later) {
+            dependents[earlier].push(later);
+            is_hard_edge[earlier].push(false);
+            after_count[later] += 1;
+        }
+    }
+
+    // Listed features first in override order, then declaration order
+    let priority: Vec<(bool, usize, usize)> = (0..n)
+        .map(|i| (override_rank[i].is_none(), override_rank[i].unwrap_or(0), i))
+        .collect();
+
+    // Kahn's algorithm, using priority as tiebreaker
     let mut queue: Vec<usize> = (0..n).filter(|&i| after_count[i] == 0).collect();
+    queue.sort_by_key(|&i| priority[i]);
     let mut result_indices = Vec::with_capacity(n);
 
     while let Some(idx) = queue.first().copied() {
@@ -436,7 +529,7 @@ pub fn order_features(features: Vec<ResolvedFeature>) -> crate::Result<Vec<Resol
         for &(dep, _) in &deps {
             after_count[dep] -= 1;
             if after_count[dep] == 0 {
-                let pos = queue.partition_point(|&x| x < dep);
+                let pos = queue.partition_point(|&x| priority[x] < priority[dep]);
                 queue.insert(pos, dep);
             }
         }
@@ -473,10 +566,10 @@ pub fn order_features(features: Vec<ResolvedFeature>) -> crate::Result<Vec<Resol
             ));
         }
 
-        // Only soft cycles — break by appending in declaration order
-        for i in stuck {
-            result_indices.push(i);
-        }
+        // Only soft cycles — break by appending in priority order
+        let mut stuck = stuck;
+        stuck.sort_by_key(|&i| priority[i]);
+        result_indices.extend(stuck);
     }
 
     // Reorder
@@ -491,17 +584,46 @@ pub fn order_features(features: Vec<ResolvedFeature>) -> crate::Result<Vec<Resol
     Ok(ordered)
 }
 
+/// Whether feature `from` reaches `to` through `dependsOn` edges
+fn depends_on_transitively(hard_deps: &[Vec<usize>], from: usize, to: usize) -> bool {
+    let mut seen = vec![false; hard_deps.len()];
+    let mut stack = vec![from];
+    while let Some(i) = stack.pop() {
+        for &dep in &hard_deps[i] {
+            if dep == to {
+                return true;
+            }
+            if !seen[dep] {
+                seen[dep] = true;
+                stack.push(dep);
+            }
+        }
+    }
+    false
+}
+
+/// Strip a `.tar.gz`, `.tgz`, or `.tar` suffix, if present
+fn strip_tarball_extension(name: &str) -> Option<&str> {
+    name.strip_suffix(".tar.gz")
+        .or_else(|| name.strip_suffix(".tgz"))
+        .or_else(|| name.strip_suffix(".tar"))
+}
+
 /// Extract the short feature ID (last path segment, no tag) for matching installsAfter
 fn extract_feature_short_id(id: &str) -> String {
-    // URL features: strip query string, take last path segment, strip tarball extensions
-    if id.starts_with("https://") || id.starts_with("http://") {
+    if let Some(name) = id.strip_prefix(WORKSPACE_FEATURE_PREFIX) {
+        return extract_feature_short_id(name);
+    }
+
+    // URL and local archive features: strip query string, take last path segment,
+    // strip tarball extensions
+    if id.starts_with("https://")
+        || id.starts_with("http://")
+        || ((id.starts_with('.') || id.starts_with('/')) && strip_tarball_extension(id).is_some())
+    {
         let without_query = id.split('?').next().unwrap_or(id);
         let segment = without_query.rsplit('/').next().unwrap_or(without_query);
-        let name = segment
-            .strip_suffix(".tar.gz")
-            .or_else(|| segment.strip_suffix(".tgz"))
-            .or_else(|| segment.strip_suffix(".tar"))
-            .unwrap_or(segment);
+        let name = strip_tarball_extension(segment).unwrap_or(segment);
         return name.to_string();
     }
 
@@ -593,6 +715,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_workspace_feature() {
+        match parse_feature_ref("feature:my-local") {
+            FeatureSource::Workspace { name, fallback } => {
+                assert_eq!(name, "my-local");
+                assert!(matches!(
+                    *fallback,
+                    FeatureSource::Oci { ref name, ref tag, .. } if name == "my-local" && tag == "latest"
+                ));
+            }
+            other => panic!("Expected Workspace source, got {:?}", other),
+        }
+        assert_eq!(extract_feature_short_id("feature:my-local"), "my-local");
+        // Names leading out of the features directory never look there
+        for id in [
+            "feature:../../etc",
+            "feature:/etc/passwd",
+            "feature:a/../../b",
+        ] {
+            assert!(
+                !matches!(parse_feature_ref(id), FeatureSource::Workspace { .. }),
+                "{} should not be a workspace feature",
+                id
+            );
+        }
+        assert_eq!(
+            extract_feature_short_id("feature:ghcr.io/devcontainers/features/node:1"),
+            "node"
+        );
+    }
+
+    #[test]
+    fn test_parse_local_tarball() {
+        for id in [
+            "./features/node.tgz",
+            "./node.tar.gz",
+            "/opt/features/node.tar",
+        ] {
+            match parse_feature_ref(id) {
+                FeatureSource::LocalTarball { path } => assert_eq!(path, PathBuf::from(id)),
+                other => panic!("Expected LocalTarball source for {}, got {:?}", id, other),
+            }
+        }
+        assert_eq!(extract_feature_short_id("./features/node.tgz"), "node");
+    }
+
     #[test]
     fn test_feature_options_bool_true() {
         let opts = feature_options(&FeatureConfig::Bool(true));
@@ -633,7 +801,7 @@ mod tests {
             make_test_feature("b", None),
             make_test_feature("c", None),
         ];
-        let ordered = order_features(features).unwrap();
+        let ordered = order_features(features, &[]).unwrap();
         let ids: Vec<&str> = ordered.iter().map(|f| f.id.as_str()).collect();
         assert_eq!(ids, vec!["a", "b", "c"]);
     }
@@ -647,7 +815,7 @@ mod tests {
             ),
             make_test_feature("ghcr.io/devcontainers/features/common-utils:1", None),
         ];
-        let ordered = order_features(features).unwrap();
+        let ordered = order_features(features, &[]).unwrap();
         let ids: Vec<&str> = ordered.iter().map(|f| f.id.as_str()).collect();
         // common-utils should come first because node depends on it
         assert_eq!(
@@ -665,7 +833,7 @@ mod tests {
             make_test_feature("a", Some(vec!["unknown".to_string()])),
             make_test_feature("b", None),
         ];
-        let ordered = order_features(features).unwrap();
+        let ordered = order_features(features, &[]).unwrap();
         let ids: Vec<&str> = ordered.iter().map(|f| f.id.as_str()).collect();
         // Unknown dep is ignored, original order preserved
         assert_eq!(ids, vec!["a", "b"]);
@@ -1354,7 +1522,7 @@ mod tests {
             make_test_feature_with_depends_on("E", None, None),
         ];
 
-        let ordered = order_features(features).unwrap();
+        let ordered = order_features(features, &[]).unwrap();
         let ids: Vec<&str> = ordered.iter().map(|f| f.id.as_str()).collect();
 
         // Verify topological constraints: each feature appears after its deps
@@ -1379,7 +1547,7 @@ mod tests {
             make_test_feature_with_depends_on("B", None, Some(b_deps)),
         ];
 
-        let result = order_features(features);
+        let result = order_features(features, &[]);
         assert!(result.is_err());
         let err = result.unwrap_err();
         match err {
@@ -1399,7 +1567,7 @@ mod tests {
             make_test_feature("B", Some(vec!["A".to_string()])),
         ];
 
-        let result = order_features(features);
+        let result = order_features(features, &[]);
         assert!(result.is_ok(), "soft cycle should not error");
         let ordered = result.unwrap();
         assert_eq!(ordered.len(), 2);
@@ -1417,11 +1585,68 @@ mod tests {
             make_test_feature("C", Some(vec!["A".to_string()])),
         ];
 
-        let ordered = order_features(features).unwrap();
+        let ordered = order_features(features, &[]).unwrap();
         let ids: Vec<&str> = ordered.iter().map(|f| f.id.as_str()).collect();
 
         let pos = |id: &str| ids.iter().position(|&x| x == id).unwrap();
         assert!(pos("B") < pos("A"), "B must come before A (hard dep)");
         assert!(pos("A") < pos("C"), "A must come before C (soft dep)");
     }
+
+    #[test]
+    fn test_order_features_install_order_override() {
+        // The override puts python before node and wins over node's installsAfter,
+        // while go still follows its dependsOn on common-utils
+        let mut go_deps = HashMap::new();
+        go_deps.insert("common-utils".to_string(), serde_json::json!({}));
+        let features = vec![
+            make_test_feature("ghcr.io/devcontainers/features/node:1", None),
+            make_test_feature_with_depends_on(
+                "ghcr.io/devcontainers/features/go:1",
+                None,
+                Some(go_deps),
+            ),
+            make_test_feature(
+                "ghcr.io/devcontainers/features/python:1",
+                Some(vec!["node".to_string()]),
+            ),
+            make_test_feature("ghcr.io/devcontainers/features/common-utils:2", None),
+        ];
+        let install_order = vec![
+            "ghcr.io/devcontainers/features/go".to_string(),
+            "ghcr.io/devcontainers/features/python".to_string(),
+            "node".to_string(),
+        ];
+
+        let ordered = order_features(features, &install_order).unwrap();
+        let ids: Vec<String> = ordered
+            .iter()
+            .map(|f| extract_feature_short_id(&f.id))
+            .collect();
+        assert_eq!(ids, ["common-utils", "go", "python", "node"]);
+    }
+
+    #[test]
+    fn test_order_features_install_order_conflicts_with_depends_on() {
+        // A depends on C through B, so listing A before C can't be honored
+        let mut a_deps = HashMap::new();
+        a_deps.insert("B".to_string(), serde_json::json!({}));
+        let mut b_deps = HashMap::new();
+        b_deps.insert("C".to_string(), serde_json::json!({}));
+        let features = vec![
+            make_test_feature_with_depends_on("A", None, Some(a_deps)),
+            make_test_feature_with_depends_on("B", None, Some(b_deps)),
+            make_test_feature_with_depends_on("C", None, None),
+        ];
+
+        match order_features(features, &["A".to_string(), "C".to_string()]) {
+            Err(CoreError::FeatureInstallOrderConflict(msg)) => {
+                assert_eq!(msg, "A is listed before C, but depends on it");
+            }
+            other => panic!(
+                "Expected FeatureInstallOrderConflict, got: {:?}",
+                other.map(|_| ())
+            ),
+        }
+    }
 }
@@ -11,6 +11,7 @@
 pub mod agents;
 pub mod browser_forward;
 mod build;
+mod build_context;
 mod container;
 pub mod credentials;
 mod dotfiles;
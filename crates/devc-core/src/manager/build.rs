@@ -1,12 +1,16 @@
 //! Build and rebuild operations for ContainerManager
 
-use crate::{features, CoreError, DevcContainerStatus, EnhancedBuildContext, Result};
-use devc_config::ImageSource;
-use devc_provider::ContainerProvider;
-use std::path::Path;
+use crate::features::MergedFeatureProperties;
+use crate::{
+    run_feature_lifecycle_commands_with_output, run_lifecycle_command_with_env_and_output,
+    Container, CoreError, DevcContainerStatus, EnhancedBuildContext, Result, PREBUILT_METADATA_KEY,
+    PREVIOUS_IMAGE_METADATA_KEY, THAWED_FROM_METADATA_KEY,
+};
+use devc_config::{ImageSource, PullPolicy};
+use devc_provider::{ContainerId, ContainerProvider, ImageId};
 use tokio::sync::mpsc;
 
-use super::{send_stage, BuildStage, ContainerManager};
+use super::{merge_remote_env, send_stage, BuildStage, ContainerManager, UpOptions};
 
 // Send a progress message to the channel, or log via tracing if no channel.
 fn emit(progress: &Option<mpsc::UnboundedSender<String>>, msg: String) {
@@ -30,6 +34,88 @@ async fn dispatch_build(
     }
 }
 
+// Dispatch a pull to the provider, streaming its output when a channel is given.
+async fn dispatch_pull(
+    provider: &dyn ContainerProvider,
+    image: &str,
+    progress: &Option<mpsc::UnboundedSender<String>>,
+) -> std::result::Result<ImageId, devc_provider::ProviderError> {
+    if let Some(tx) = progress {
+        provider.pull_with_progress(image, tx.clone()).await
+    } else {
+        provider.pull(image).await
+    }
+}
+
+// How long the build context size check may hold up a build
+const CONTEXT_WALK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+// Warn when the build context is larger than `limit_mb` after `.dockerignore`.
+// Purely advisory: a failed walk is ignored, and one still running after
+// CONTEXT_WALK_TIMEOUT gives up without a warning.
+async fn warn_large_context(
+    context: &std::path::Path,
+    limit_mb: u64,
+    progress: &Option<mpsc::UnboundedSender<String>>,
+) {
+    if limit_mb == 0 {
+        return;
+    }
+    let limit = limit_mb.saturating_mul(1024 * 1024);
+    let dir = context.to_path_buf();
+    let deadline = std::time::Instant::now() + CONTEXT_WALK_TIMEOUT;
+    let Ok(Some(size)) = tokio::task::spawn_blocking(move || {
+        crate::build_context::context_size(&dir, limit, deadline)
+    })
+    .await
+    else {
+        return;
+    };
+    if size <= limit {
+        return;
+    }
+    let hint = if context.join(".dockerignore").exists() {
+        "consider excluding more paths in its .dockerignore"
+    } else {
+        "consider adding a .dockerignore"
+    };
+    let msg = format!(
+        "Warning: build context {} is over {} MB; {} (set defaults.build_context_warn_mb = 0 to silence)",
+        context.display(),
+        limit_mb,
+        hint
+    );
+    tracing::warn!("{}", msg);
+    if let Some(tx) = progress {
+        let _ = tx.send(msg);
+    }
+}
+
+// Look up the base image locally when the pull policy allows skipping the pull.
+// Returns the local image ID when no pull is needed, or None to pull.
+async fn local_base_image(
+    provider: &dyn ContainerProvider,
+    image: &str,
+    policy: PullPolicy,
+) -> Result<Option<ImageId>> {
+    match policy {
+        PullPolicy::Always => Ok(None),
+        PullPolicy::Missing => Ok(provider.local_image(image).await?),
+        PullPolicy::Never => match provider.local_image(image).await? {
+            Some(id) => Ok(Some(id)),
+            None => Err(CoreError::ImageNotLocal(image.to_string())),
+        },
+    }
+}
+
+// Whether `devc.prebuild` has any commands to bake into the image.
+fn has_prebuild_commands(container: &Container, feature_props: &MergedFeatureProperties) -> bool {
+    container.devcontainer.on_create_command.is_some()
+        || container.devcontainer.update_content_command.is_some()
+        || !feature_props.on_create_commands.is_empty()
+        || !feature_props.update_content_commands.is_empty()
+}
+
 impl ContainerManager {
     /// Build a container image
     pub async fn build(&self, id: &str) -> Result<String> {
@@ -73,8 +159,10 @@ impl ContainerManager {
 
         let provider = self.require_container_provider(&container_state)?;
 
-        // Load container config
+        // Load container config (not the cached image user: the rebuilt image
+        // may have a different USER)
         let container = self.load_container(&container_state.config_path)?;
+        self.check_config_schema(&container_state.config_path)?;
 
         // Update status to building
         {
@@ -87,7 +175,7 @@ impl ContainerManager {
         send_stage(stage.as_ref(), BuildStage::BuildingImage);
 
         // Check if SSH injection is enabled
-        let inject_ssh = self.global_config.defaults.ssh_enabled.unwrap_or(false);
+        let inject_ssh = self.uses_ssh();
 
         // Log SSH injection status
         if inject_ssh {
@@ -102,24 +190,15 @@ impl ContainerManager {
             );
         }
 
-        // Resolve devcontainer features
-        let config_dir = container
-            .config_path
-            .parent()
-            .unwrap_or(Path::new("."))
-            .to_path_buf();
-        let progress_for_features = progress.clone();
-        let resolved_features = if let Some(ref feature_map) = container.devcontainer.features {
-            features::resolve_and_prepare_features(feature_map, &config_dir, &progress_for_features)
-                .await?
-        } else {
-            vec![]
-        };
+        // Resolve devcontainer features (a no-cache build always re-resolves)
+        let feature_set = self
+            .resolve_features(&container_state, &container, &progress, no_cache)
+            .await?;
+        let resolved_features = &feature_set.features;
         let has_features = !resolved_features.is_empty();
-        let feature_properties = features::merge_feature_properties(&resolved_features);
         let remote_user = container
             .devcontainer
-            .effective_user()
+            .effective_remote_user()
             .unwrap_or("root")
             .to_string();
 
@@ -133,9 +212,24 @@ impl ContainerManager {
             );
         }
 
+        let prebuild = container.devcontainer.prebuild == Some(true);
+        let image_based = matches!(container.devcontainer.image_source(), ImageSource::Image(_));
+        if prebuild && !image_based {
+            tracing::warn!("devc.prebuild only applies to image-based configs; ignoring it");
+        }
+
         // Check if we need to build or pull
         let image_id = match container.devcontainer.image_source() {
             ImageSource::Image(image) => {
+                let pull_policy = self.global_config.defaults.pull_policy.unwrap_or_default();
+                let local_image = match local_base_image(provider, &image, pull_policy).await {
+                    Ok(local) => local,
+                    Err(e) => {
+                        self.set_status(id, DevcContainerStatus::Failed).await?;
+                        return Err(e);
+                    }
+                };
+
                 if has_features || inject_ssh {
                     emit(
                         &progress,
@@ -148,7 +242,7 @@ impl ContainerManager {
                     let enhanced_ctx = if has_features {
                         EnhancedBuildContext::from_image_with_features(
                             &image,
-                            &resolved_features,
+                            resolved_features,
                             inject_ssh,
                             &remote_user,
                         )?
@@ -169,7 +263,8 @@ impl ContainerManager {
                             ("devc.base_image".to_string(), image.clone()),
                         ]),
                         no_cache,
-                        pull: true,
+                        pull: local_image.is_none(),
+                        secrets: Vec::new(),
                     };
 
                     let result = dispatch_build(provider, &build_config, &progress).await;
@@ -180,9 +275,12 @@ impl ContainerManager {
                             return Err(e.into());
                         }
                     }
+                } else if let Some(local_id) = local_image {
+                    emit(&progress, format!("Using local image: {}", image));
+                    local_id.0
                 } else {
                     emit(&progress, format!("Pulling image: {}", image));
-                    let result = provider.pull(&image).await;
+                    let result = dispatch_pull(provider, &image, &progress).await;
                     match result {
                         Ok(id) => id.0,
                         Err(e) => {
@@ -195,6 +293,23 @@ impl ContainerManager {
             ImageSource::Dockerfile { .. } => {
                 let mut build_config = container.build_config()?;
                 build_config.no_cache = no_cache;
+                build_config.build_args.extend(
+                    self.build_arg_overrides
+                        .iter()
+                        .map(|(k, v)| (k.clone(), v.clone())),
+                );
+                if let Some(ref target) = self.target_override {
+                    build_config.target = Some(target.clone());
+                }
+                warn_large_context(
+                    &build_config.context,
+                    self.global_config
+                        .defaults
+                        .build_context_warn_mb
+                        .unwrap_or(500),
+                    &progress,
+                )
+                .await;
 
                 if has_features || inject_ssh {
                     emit(
@@ -209,7 +324,8 @@ impl ContainerManager {
                         EnhancedBuildContext::from_dockerfile_with_features(
                             &build_config.context,
                             &build_config.dockerfile,
-                            &resolved_features,
+                            build_config.target.as_deref(),
+                            resolved_features,
                             inject_ssh,
                             &remote_user,
                         )?
@@ -217,11 +333,14 @@ impl ContainerManager {
                         EnhancedBuildContext::from_dockerfile(
                             &build_config.context,
                             &build_config.dockerfile,
+                            build_config.target.as_deref(),
                         )?
                     };
 
                     build_config.context = enhanced_ctx.context_path().to_path_buf();
                     build_config.dockerfile = enhanced_ctx.dockerfile_name().to_string();
+                    // The enhanced Dockerfile's last stage builds on the target
+                    build_config.target = None;
 
                     let result = dispatch_build(provider, &build_config, &progress).await;
                     match result {
@@ -256,15 +375,17 @@ impl ContainerManager {
                     &progress,
                     "Compose project: build will happen during 'up'".to_string(),
                 );
+                if !self.build_arg_overrides.is_empty() {
+                    tracing::warn!(
+                        "--build-arg is ignored for compose projects; set build args in the compose file"
+                    );
+                }
                 {
                     let mut state = self.state.write().await;
                     if let Some(cs) = state.get_mut(id) {
                         cs.image_id = Some("compose".to_string());
                         cs.status = DevcContainerStatus::Built;
-                        if let Ok(props_json) = serde_json::to_string(&feature_properties) {
-                            cs.metadata
-                                .insert("feature_properties".to_string(), props_json);
-                        }
+                        feature_set.record(&mut cs.metadata);
                     }
                 }
                 self.save_state().await?;
@@ -278,15 +399,52 @@ impl ContainerManager {
             }
         };
 
+        let prebuilt =
+            prebuild && image_based && has_prebuild_commands(&container, &feature_set.properties);
+        let image_id = if prebuilt {
+            emit(
+                &progress,
+                "Prebuilding: running onCreate/updateContent in a temporary container..."
+                    .to_string(),
+            );
+            match self
+                .prebuild_image(
+                    &container,
+                    provider,
+                    &image_id,
+                    &feature_set.properties,
+                    &progress,
+                )
+                .await
+            {
+                Ok(id) => id,
+                Err(e) => {
+                    self.set_status(id, DevcContainerStatus::Failed).await?;
+                    return Err(e);
+                }
+            }
+        } else {
+            image_id
+        };
+
         // Update state with image ID and feature properties
         {
             let mut state = self.state.write().await;
             if let Some(cs) = state.get_mut(id) {
+                if let Some(old) = cs.image_id.take().filter(|old| *old != image_id) {
+                    cs.metadata
+                        .insert(PREVIOUS_IMAGE_METADATA_KEY.to_string(), old);
+                }
                 cs.image_id = Some(image_id.clone());
                 cs.status = DevcContainerStatus::Built;
-                if let Ok(props_json) = serde_json::to_string(&feature_properties) {
+                feature_set.record(&mut cs.metadata);
+                // A fresh image no longer holds the thawed snapshot's state
+                cs.metadata.remove(THAWED_FROM_METADATA_KEY);
+                if prebuilt {
                     cs.metadata
-                        .insert("feature_properties".to_string(), props_json);
+                        .insert(PREBUILT_METADATA_KEY.to_string(), "true".to_string());
+                } else {
+                    cs.metadata.remove(PREBUILT_METADATA_KEY);
                 }
             }
         }
@@ -295,6 +453,185 @@ impl ContainerManager {
         Ok(image_id)
     }
 
+    /// Bake the create-phase commands into an image (`devc.prebuild`)
+    ///
+    /// Runs the feature and config onCreate/updateContent commands in a
+    /// temporary container from `image_id`, then commits it and removes the
+    /// container. The temporary container mounts only the workspace and has
+    /// no devc labels, containerEnv, published ports or runArgs, so the commit
+    /// holds filesystem changes outside the workspace (packages, caches in the
+    /// home directory) and nothing that would tie the image to this container.
+    /// containerEnv is passed to the commands instead. Returns the committed
+    /// image ID.
+    async fn prebuild_image(
+        &self,
+        container: &Container,
+        provider: &dyn ContainerProvider,
+        image_id: &str,
+        feature_props: &MergedFeatureProperties,
+        progress: &Option<mpsc::UnboundedSender<String>>,
+    ) -> Result<String> {
+        let mut create_config =
+            container.create_config_with_features(image_id, Some(feature_props));
+        let name = format!("{}-prebuild", container.container_name());
+        create_config.name = Some(name.clone());
+        let container_env = std::mem::take(&mut create_config.env);
+        // The workspace mount always comes first; volumes hold user data
+        create_config.mounts.truncate(1);
+        create_config.labels.clear();
+        create_config.user_labels.clear();
+        create_config.ports.clear();
+        create_config.extra_args.clear();
+        provider.remove_by_name(&name).await?;
+        let temp_id = provider.create(&create_config).await?;
+
+        let result = match self
+            .run_prebuild_commands(
+                container,
+                provider,
+                &temp_id,
+                feature_props,
+                &container_env,
+                progress,
+            )
+            .await
+        {
+            Ok(()) => {
+                let tag = format!("devc/{}:prebuilt", container.container_name());
+                provider
+                    .commit(&temp_id, &tag)
+                    .await
+                    .map(|id| id.0)
+                    .map_err(Into::into)
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = provider.remove(&temp_id, true).await {
+            tracing::warn!("Failed to remove prebuild container {}: {}", name, e);
+        }
+        result
+    }
+
+    async fn run_prebuild_commands(
+        &self,
+        container: &Container,
+        provider: &dyn ContainerProvider,
+        container_id: &ContainerId,
+        feature_props: &MergedFeatureProperties,
+        container_env: &std::collections::HashMap<String, String>,
+        progress: &Option<mpsc::UnboundedSender<String>>,
+    ) -> Result<()> {
+        provider.start(container_id).await?;
+
+        let devcontainer = &container.devcontainer;
+        let user = devcontainer.create_phase_user();
+        let workspace_folder = devcontainer.workspace_folder.as_deref();
+        let mut env = container_env.clone();
+        env.extend(
+            merge_remote_env(devcontainer.remote_env.as_ref(), &feature_props.remote_env)
+                .unwrap_or_default(),
+        );
+        let remote_env = Some(env);
+        let phases = [
+            (
+                &feature_props.on_create_commands,
+                devcontainer.on_create_command.as_ref(),
+                "onCreate",
+            ),
+            (
+                &feature_props.update_content_commands,
+                devcontainer.update_content_command.as_ref(),
+                "updateContent",
+            ),
+        ];
+        for (feature_cmds, cmd, tag) in phases {
+            let feature_tag = format!("feature:{}", tag);
+            run_feature_lifecycle_commands_with_output(
+                provider,
+                container_id,
+                feature_cmds,
+                Self::lifecycle_exec_opts(
+                    user,
+                    workspace_folder,
+                    remote_env.as_ref(),
+                    progress.as_ref(),
+                    Some(&feature_tag),
+                ),
+            )
+            .await?;
+            if let Some(cmd) = cmd {
+                run_lifecycle_command_with_env_and_output(
+                    provider,
+                    container_id,
+                    cmd,
+                    Self::lifecycle_exec_opts(
+                        user,
+                        workspace_folder,
+                        remote_env.as_ref(),
+                        progress.as_ref(),
+                        Some(tag),
+                    ),
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove the image a container was built from before its latest build.
+    ///
+    /// Refuses while any tracked container still uses that image. Returns the
+    /// removed image ID.
+    pub async fn remove_previous_image(&self, id: &str) -> Result<String> {
+        let container_state = {
+            let state = self.state.read().await;
+            state
+                .get(id)
+                .cloned()
+                .ok_or_else(|| CoreError::ContainerNotFound(id.to_string()))?
+        };
+        let image = container_state
+            .previous_image_id()
+            .ok_or_else(|| {
+                CoreError::InvalidState(format!(
+                    "{} has no previous image recorded",
+                    container_state.name
+                ))
+            })?
+            .to_string();
+
+        let users: Vec<String> = {
+            let state = self.state.read().await;
+            state
+                .list()
+                .iter()
+                .filter(|cs| cs.image_id.as_deref() == Some(image.as_str()))
+                .map(|cs| cs.name.clone())
+                .collect()
+        };
+        if !users.is_empty() {
+            return Err(CoreError::InvalidState(format!(
+                "image {} is still used by {}",
+                image,
+                users.join(", ")
+            )));
+        }
+
+        let provider = self.require_container_provider(&container_state)?;
+        provider
+            .remove_image(&ImageId::new(image.clone()), false)
+            .await?;
+
+        {
+            let mut state = self.state.write().await;
+            if let Some(cs) = state.get_mut(id) {
+                cs.metadata.remove(PREVIOUS_IMAGE_METADATA_KEY);
+            }
+        }
+        self.save_state().await?;
+        Ok(image)
+    }
+
     /// Rebuild a container, optionally migrating to current provider
     ///
     /// This will:
@@ -332,10 +669,6 @@ impl ContainerManager {
         stage: Option<mpsc::UnboundedSender<BuildStage>>,
     ) -> Result<()> {
         send_stage(stage.as_ref(), BuildStage::Starting);
-        let new_provider = self.provider_type().ok_or_else(|| {
-            CoreError::NotConnected("Cannot rebuild: no provider available".to_string())
-        })?;
-
         let container_state = {
             let state = self.state.read().await;
             state
@@ -343,6 +676,9 @@ impl ContainerManager {
                 .cloned()
                 .ok_or_else(|| CoreError::ContainerNotFound(id.to_string()))?
         };
+        let new_provider = self.rebuild_provider_for(&container_state).ok_or_else(|| {
+            CoreError::NotConnected("Cannot rebuild: no provider available".to_string())
+        })?;
 
         let old_provider = container_state.provider;
         let provider_changed = old_provider != new_provider;
@@ -366,6 +702,8 @@ impl ContainerManager {
                 if let Some(cs) = state.get_mut(id) {
                     cs.provider = new_provider;
                     cs.image_id = None;
+                    // The old image lives in the other provider's store
+                    cs.metadata.remove(PREVIOUS_IMAGE_METADATA_KEY);
                     cs.container_id = None;
                     cs.status = DevcContainerStatus::Configured;
                 }
@@ -374,7 +712,7 @@ impl ContainerManager {
         }
 
         // 3. Run initializeCommand on host before build (per spec)
-        let container = self.load_container(&container_state.config_path)?;
+        let container = self.load_container_for_state(&container_state)?;
         if let Some(ref cmd) = container.devcontainer.initialize_command {
             send_stage(stage.as_ref(), BuildStage::InitializeCommandHost);
             emit(
@@ -382,7 +720,7 @@ impl ContainerManager {
                 "Running initializeCommand on host...".to_string(),
             );
             let output = progress.as_ref();
-            crate::run_host_command(cmd, &container.workspace_path, output).await?;
+            crate::run_host_command(cmd, &container.initialize_command_dir()?, output).await?;
         }
 
         // 4. Rebuild image
@@ -392,8 +730,16 @@ impl ContainerManager {
         // 5. Create and start container
         let progress_ref = progress.as_ref();
         let stage_ref = stage.as_ref();
-        self.up_with_progress_inner(id, progress_ref, progress_ref, stage_ref, None, false)
-            .await?;
+        self.up_with_progress_inner(
+            id,
+            UpOptions {
+                progress: progress_ref,
+                output: progress_ref,
+                stage: stage_ref,
+                ..Default::default()
+            },
+        )
+        .await?;
 
         send_stage(stage.as_ref(), BuildStage::AgentSetup);
         let results = self.setup_agents_for_container(id).await?;
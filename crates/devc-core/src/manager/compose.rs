@@ -5,14 +5,36 @@ use crate::{
     run_lifecycle_command_with_env_and_output, Container, CoreError, DevcContainerStatus, Result,
     SshManager,
 };
-use devc_provider::{ContainerId, ContainerProvider};
+use devc_provider::{
+    Capability, ComposeServiceInfo, ContainerId, ContainerProvider, ProviderError, ProviderType,
+};
 use std::path::Path;
 use std::time::Duration;
 use tokio::sync::mpsc;
 
-use super::{compose_file_strs, merge_remote_env, send_progress, ContainerManager};
+use super::{
+    compose_file_strs, merge_remote_env, send_progress, send_ready_after, ContainerManager,
+};
 
 impl ContainerManager {
+    /// Fail before touching a compose project when the provider has no compose
+    /// support, rather than partway through `compose up`
+    pub(crate) async fn require_compose(&self, provider: &dyn ContainerProvider) -> Result<()> {
+        if provider.supports(Capability::Compose).await {
+            return Ok(());
+        }
+        let hint = match provider.info().provider_type {
+            ProviderType::Docker => "install the Docker Compose plugin",
+            ProviderType::Podman => "install podman-compose or the docker-compose plugin",
+        };
+        Err(ProviderError::Unsupported(format!(
+            "compose is not available for {}; {} to use compose-based devcontainers",
+            provider.info().provider_type,
+            hint
+        ))
+        .into())
+    }
+
     pub(crate) fn compose_resolve_timeout() -> Duration {
         let secs = std::env::var("DEVC_COMPOSE_RESOLVE_TIMEOUT_SECS")
             .ok()
@@ -42,6 +64,92 @@ impl ContainerManager {
             .map_err(|e| CoreError::InvalidState(e.to_string()))
     }
 
+    /// List the services of a compose project along with their runtime container IDs
+    ///
+    /// If the primary service's container was recreated since it was last
+    /// recorded, the stored container ID is updated to match.
+    pub async fn compose_services(&self, id: &str) -> Result<Vec<ComposeServiceInfo>> {
+        let container_state = self
+            .get(id)
+            .await?
+            .ok_or_else(|| CoreError::ContainerNotFound(id.to_string()))?;
+        let provider = self.require_container_provider(&container_state)?;
+        let container = self.load_container_for_state(&container_state)?;
+        let compose_files = container
+            .compose_files()
+            .ok_or_else(|| CoreError::InvalidState("Not a compose project".to_string()))?;
+        self.require_compose(provider).await?;
+
+        let owned = compose_file_strs(&compose_files);
+        let refs: Vec<&str> = owned.iter().map(|s| s.as_str()).collect();
+        let services = provider
+            .compose_ps(
+                &refs,
+                &container.compose_project_name(),
+                &container.workspace_path,
+            )
+            .await?;
+
+        let primary_cid = container_state.compose_service.as_deref().and_then(|name| {
+            services
+                .iter()
+                .find(|s| s.service_name == name && !s.container_id.0.is_empty())
+                .map(|s| s.container_id.0.clone())
+        });
+        if let Some(cid) = primary_cid {
+            if container_state.container_id.as_deref() != Some(cid.as_str()) {
+                {
+                    let mut state = self.state.write().await;
+                    if let Some(cs) = state.get_mut(id) {
+                        cs.container_id = Some(cid);
+                    }
+                }
+                self.save_state().await?;
+            }
+        }
+        Ok(services)
+    }
+
+    /// Get the logs of one service in a compose project
+    ///
+    /// The primary service goes through [`ContainerManager::logs`]; any other
+    /// service is looked up with `compose ps`, so stopped services still work.
+    pub async fn compose_service_logs(
+        &self,
+        id: &str,
+        service: &str,
+        tail: Option<u64>,
+    ) -> Result<Vec<String>> {
+        let container_state = self
+            .get(id)
+            .await?
+            .ok_or_else(|| CoreError::ContainerNotFound(id.to_string()))?;
+        if container_state.compose_project.is_none() {
+            return Err(CoreError::InvalidState(format!(
+                "Container '{}' is not a compose project",
+                container_state.name
+            )));
+        }
+        if container_state.compose_service.as_deref() == Some(service) {
+            return self.logs(id, tail).await;
+        }
+
+        let services = self.compose_services(id).await?;
+        let svc = services
+            .iter()
+            .find(|s| s.service_name == service)
+            .ok_or_else(|| {
+                let names: Vec<&str> = services.iter().map(|s| s.service_name.as_str()).collect();
+                CoreError::InvalidState(format!(
+                    "Service '{}' not found in compose project (available: {})",
+                    service,
+                    names.join(", ")
+                ))
+            })?;
+        let provider = self.require_container_provider(&container_state)?;
+        Self::read_logs(provider, &svc.container_id, tail).await
+    }
+
     /// Handle Docker Compose `up` flow
     ///
     /// 1. Run `compose up -d --build` to start all services
@@ -61,7 +169,7 @@ impl ContainerManager {
         if container_state.container_id.is_none() {
             if let Some(ref cmd) = container.devcontainer.initialize_command {
                 send_progress(progress, "Running initializeCommand on host...");
-                crate::run_host_command(cmd, &container.workspace_path, output).await?;
+                crate::run_host_command(cmd, &container.initialize_command_dir()?, output).await?;
             }
         }
 
@@ -76,23 +184,17 @@ impl ContainerManager {
         let mut owned = compose_file_strs(&compose_files);
 
         // Resolve devcontainer features for compose override + exec-based install
-        let config_dir = container
-            .config_path
-            .parent()
-            .unwrap_or(Path::new("."))
-            .to_path_buf();
         let progress_opt: Option<mpsc::UnboundedSender<String>> = progress.cloned();
-        let resolved_features = if let Some(ref feature_map) = container.devcontainer.features {
-            features::resolve_and_prepare_features(feature_map, &config_dir, &progress_opt).await?
-        } else {
-            vec![]
-        };
-        let feature_props = features::merge_feature_properties(&resolved_features);
+        let feature_set = self
+            .resolve_features(container_state, container, &progress_opt, false)
+            .await?;
+        let resolved_features = &feature_set.features;
+        let feature_props = &feature_set.properties;
 
         // Generate compose override file if features declare container properties
         let override_file = if feature_props.has_container_properties() {
             let yaml =
-                features::compose_override::generate_compose_override(service_name, &feature_props);
+                features::compose_override::generate_compose_override(service_name, feature_props);
             if let Some(yaml) = yaml {
                 let path = container.workspace_path.join(".devc-compose-override.yml");
                 std::fs::write(&path, &yaml)?;
@@ -158,7 +260,7 @@ impl ContainerManager {
         self.inject_credentials_nonfatal(
             provider,
             &container_id,
-            container.devcontainer.effective_user(),
+            container.devcontainer.effective_remote_user(),
             &container.workspace_path,
         )
         .await;
@@ -166,11 +268,14 @@ impl ContainerManager {
         // 3. Install features via exec if any were resolved
         if !resolved_features.is_empty() {
             send_progress(progress, "Installing features...");
-            let remote_user = container.devcontainer.effective_user().unwrap_or("root");
+            let remote_user = container
+                .devcontainer
+                .effective_remote_user()
+                .unwrap_or("root");
             features::install::install_features_via_exec(
                 provider,
                 &container_id,
-                &resolved_features,
+                resolved_features,
                 remote_user,
                 progress,
             )
@@ -185,14 +290,15 @@ impl ContainerManager {
                 cs.image_id = Some("compose".to_string());
                 cs.compose_project = Some(project_name.clone());
                 cs.compose_service = Some(service_name.to_string());
-                cs.status = DevcContainerStatus::Running;
+                cs.set_status(DevcContainerStatus::Running);
+                feature_set.record(&mut cs.metadata);
             }
         }
         self.save_state().await?;
 
         // 5. Run lifecycle commands targeting the dev service container
         //    Feature lifecycle commands run BEFORE devcontainer.json commands (per spec)
-        let user = container.devcontainer.effective_user();
+        let user = container.devcontainer.effective_remote_user();
         let workspace_folder = container.devcontainer.workspace_folder.as_deref();
         let merged_env = merge_remote_env(
             container.devcontainer.remote_env.as_ref(),
@@ -200,9 +306,16 @@ impl ContainerManager {
         );
         let remote_env = merged_env.as_ref();
 
+        let create_user = container.devcontainer.create_phase_user();
+
         let opts = |tag: &'static str| {
             Self::lifecycle_exec_opts(user, workspace_folder, remote_env, output, Some(tag))
         };
+        let create_opts = |tag: &'static str| {
+            Self::lifecycle_exec_opts(create_user, workspace_folder, remote_env, output, Some(tag))
+        };
+        let wait_for = container.devcontainer.wait_for.as_deref();
+        send_ready_after(progress, wait_for, "initializeCommand");
 
         if !feature_props.on_create_commands.is_empty() {
             send_progress(progress, "Running feature onCreateCommand(s)...");
@@ -210,7 +323,7 @@ impl ContainerManager {
                 provider,
                 &container_id,
                 &feature_props.on_create_commands,
-                opts("feature:onCreate"),
+                create_opts("feature:onCreate"),
             )
             .await?;
         }
@@ -221,10 +334,11 @@ impl ContainerManager {
                 provider,
                 &container_id,
                 cmd,
-                opts("onCreate"),
+                create_opts("onCreate"),
             )
             .await?;
         }
+        send_ready_after(progress, wait_for, "onCreateCommand");
 
         if !feature_props.update_content_commands.is_empty() {
             send_progress(progress, "Running feature updateContentCommand(s)...");
@@ -232,7 +346,7 @@ impl ContainerManager {
                 provider,
                 &container_id,
                 &feature_props.update_content_commands,
-                opts("feature:updateContent"),
+                create_opts("feature:updateContent"),
             )
             .await?;
         }
@@ -243,10 +357,11 @@ impl ContainerManager {
                 provider,
                 &container_id,
                 cmd,
-                opts("updateContent"),
+                create_opts("updateContent"),
             )
             .await?;
         }
+        send_ready_after(progress, wait_for, "updateContentCommand");
 
         if !feature_props.post_create_commands.is_empty() {
             send_progress(progress, "Running feature postCreateCommand(s)...");
@@ -254,7 +369,7 @@ impl ContainerManager {
                 provider,
                 &container_id,
                 &feature_props.post_create_commands,
-                opts("feature:postCreate"),
+                create_opts("feature:postCreate"),
             )
             .await?;
         }
@@ -265,19 +380,20 @@ impl ContainerManager {
                 provider,
                 &container_id,
                 cmd,
-                opts("postCreate"),
+                create_opts("postCreate"),
             )
             .await?;
         }
+        send_ready_after(progress, wait_for, "postCreateCommand");
 
         // Setup SSH if enabled
-        if self.global_config.defaults.ssh_enabled.unwrap_or(false) {
+        if self.uses_ssh() {
             send_progress(progress, "Setting up SSH...");
             let ssh_manager = SshManager::new()?;
             ssh_manager.ensure_keys_exist()?;
 
             match ssh_manager
-                .setup_container(provider, &container_id, user)
+                .setup_container(provider, &container_id, id, user)
                 .await
             {
                 Ok(()) => {
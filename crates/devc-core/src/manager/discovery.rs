@@ -26,6 +26,21 @@ impl ContainerManager {
                 return Ok(None);
             }
 
+            // The workspace is tracked through its other top-level config form
+            // (`.devcontainer.json` vs `.devcontainer/devcontainer.json`): move
+            // that entry to this config rather than tracking the workspace twice
+            let shadowed = other_top_level_config(&container.config_path)
+                .and_then(|other| state.find_by_config_path(&other))
+                .map(|cs| cs.id.clone());
+            if let Some(existing) = shadowed {
+                if let Some(cs) = state.get_mut(&existing) {
+                    cs.config_path = container.config_path.clone();
+                }
+                drop(state);
+                self.save_state().await?;
+                return Ok(None);
+            }
+
             let container_state = ContainerState::new(
                 container.name.clone(),
                 provider_type,
@@ -77,7 +92,10 @@ impl ContainerManager {
         let mut unregistered = Vec::new();
 
         for (_config, config_path) in all_configs {
-            if state.find_by_config_path(&config_path).is_some() {
+            let registered = |path: &Path| state.find_by_config_path(path).is_some();
+            if registered(&config_path)
+                || other_top_level_config(&config_path).is_some_and(|other| registered(&other))
+            {
                 continue; // already registered
             }
             match self.load_container(&config_path) {
@@ -200,7 +218,8 @@ impl ContainerManager {
         let status = match details.status {
             ContainerStatus::Running => DevcContainerStatus::Running,
             ContainerStatus::Exited | ContainerStatus::Dead => DevcContainerStatus::Stopped,
-            ContainerStatus::Created | ContainerStatus::Paused => DevcContainerStatus::Created,
+            ContainerStatus::Paused => DevcContainerStatus::Paused,
+            ContainerStatus::Created => DevcContainerStatus::Created,
             _ => DevcContainerStatus::Stopped,
         };
 
@@ -214,7 +233,7 @@ impl ContainerManager {
         // Extract remote_user and workspace_folder from devcontainer.json if available
         if container_state.config_path.exists() {
             if let Ok(c) = Container::from_config(&container_state.config_path) {
-                if let Some(user) = c.devcontainer.effective_user() {
+                if let Some(user) = c.devcontainer.effective_remote_user() {
                     container_state
                         .metadata
                         .insert("remote_user".to_string(), user.to_string());
@@ -252,7 +271,7 @@ impl ContainerManager {
         if container_state.status == DevcContainerStatus::Running
             && container_state.config_path.exists()
         {
-            if let Ok(container) = self.load_container(&container_state.config_path) {
+            if let Ok(container) = self.load_container_for_state(&container_state) {
                 let cid = ContainerId::new(container_id);
 
                 // Credentials setup before lifecycle so commands can access private registries/repos
@@ -260,7 +279,7 @@ impl ContainerManager {
                     provider,
                     &cid,
                     &self.global_config,
-                    container.devcontainer.effective_user(),
+                    container.devcontainer.effective_remote_user(),
                     &container_state.workspace_path,
                 )
                 .await
@@ -314,3 +333,16 @@ fn find_devcontainer_config(workspace: &std::path::Path) -> Result<std::path::Pa
     // If not found, return a default path (will be created later if needed)
     Ok(devcontainer_dir)
 }
+
+/// The other top-level form of a workspace config: `.devcontainer.json` for
+/// `.devcontainer/devcontainer.json` and vice versa (None for subfolder configs)
+fn other_top_level_config(config_path: &Path) -> Option<PathBuf> {
+    let parent = config_path.parent()?;
+    match config_path.file_name()?.to_str()? {
+        ".devcontainer.json" => Some(parent.join(".devcontainer/devcontainer.json")),
+        "devcontainer.json" if parent.file_name()? == ".devcontainer" => {
+            Some(parent.parent()?.join(".devcontainer.json"))
+        }
+        _ => None,
+    }
+}
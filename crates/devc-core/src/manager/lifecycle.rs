@@ -8,8 +8,8 @@ use devc_provider::{ContainerId, ContainerProvider, ContainerStatus};
 use tokio::sync::mpsc;
 
 use super::{
-    get_feature_properties, merge_remote_env, send_progress, send_stage, BuildStage,
-    ContainerManager,
+    get_feature_properties, merge_remote_env, send_progress, send_ready_after, send_stage,
+    BuildStage, ContainerManager,
 };
 
 pub(crate) struct LifecycleChannels<'a> {
@@ -47,6 +47,8 @@ impl ContainerManager {
     /// 7. SSH setup (if enabled)
     /// 8. Dotfiles injection
     ///
+    /// Steps 1-4 are skipped when the image was made by `devc.prebuild`, and
+    /// steps 1-6 when the container was made by `devc thaw`.
     /// Used by `up()` for newly created containers.
     pub(crate) async fn run_first_create_lifecycle(
         &self,
@@ -64,16 +66,34 @@ impl ContainerManager {
                 .ok_or_else(|| CoreError::ContainerNotFound(id.to_string()))?
         };
         let feature_props = get_feature_properties(&container_state);
-        let user = container.devcontainer.effective_user();
+        // Create-phase commands may run as a different user than the shell
+        let user = container.devcontainer.create_phase_user();
         let workspace_folder = container.devcontainer.workspace_folder.as_deref();
         let merged_env = merge_remote_env(
             container.devcontainer.remote_env.as_ref(),
             &feature_props.remote_env,
         );
         let remote_env = merged_env.as_ref();
+        let wait_for = container.devcontainer.wait_for.as_deref();
+        // A thawed snapshot already holds the results of every create command
+        let thawed = container_state.is_thawed();
+        // A prebuilt image already ran onCreate and updateContent
+        let prebuilt = thawed || container_state.is_prebuilt();
+        send_ready_after(channels.progress, wait_for, "initializeCommand");
+        if thawed {
+            send_progress(
+                channels.progress,
+                "Container was thawed from a snapshot; skipping create commands",
+            );
+        } else if prebuilt {
+            send_progress(
+                channels.progress,
+                "Image is prebuilt; skipping onCreate and updateContent",
+            );
+        }
 
         // Feature onCreateCommands run first (per spec)
-        if !feature_props.on_create_commands.is_empty() {
+        if !prebuilt && !feature_props.on_create_commands.is_empty() {
             send_stage(channels.stage, BuildStage::LifecycleFeatureOnCreate);
             send_progress(channels.progress, "Running feature onCreateCommand(s)...");
             let details = provider.inspect(container_id).await?;
@@ -95,7 +115,12 @@ impl ContainerManager {
             .await?;
         }
 
-        if let Some(ref cmd) = container.devcontainer.on_create_command {
+        if let Some(cmd) = container
+            .devcontainer
+            .on_create_command
+            .as_ref()
+            .filter(|_| !prebuilt)
+        {
             send_stage(channels.stage, BuildStage::LifecycleOnCreate);
             send_progress(channels.progress, "Running onCreate command...");
             let details = provider.inspect(container_id).await?;
@@ -116,9 +141,10 @@ impl ContainerManager {
             )
             .await?;
         }
+        send_ready_after(channels.progress, wait_for, "onCreateCommand");
 
         // Feature updateContentCommands run first (per spec)
-        if !feature_props.update_content_commands.is_empty() {
+        if !prebuilt && !feature_props.update_content_commands.is_empty() {
             send_stage(channels.stage, BuildStage::LifecycleFeatureUpdateContent);
             send_progress(
                 channels.progress,
@@ -143,7 +169,12 @@ impl ContainerManager {
             .await?;
         }
 
-        if let Some(ref cmd) = container.devcontainer.update_content_command {
+        if let Some(cmd) = container
+            .devcontainer
+            .update_content_command
+            .as_ref()
+            .filter(|_| !prebuilt)
+        {
             send_stage(channels.stage, BuildStage::LifecycleUpdateContent);
             send_progress(channels.progress, "Running updateContentCommand...");
             let details = provider.inspect(container_id).await?;
@@ -164,9 +195,10 @@ impl ContainerManager {
             )
             .await?;
         }
+        send_ready_after(channels.progress, wait_for, "updateContentCommand");
 
         // Feature postCreateCommands run first (per spec)
-        if !feature_props.post_create_commands.is_empty() {
+        if !thawed && !feature_props.post_create_commands.is_empty() {
             send_stage(channels.stage, BuildStage::LifecycleFeaturePostCreate);
             send_progress(channels.progress, "Running feature postCreateCommand(s)...");
             let details = provider.inspect(container_id).await?;
@@ -188,7 +220,12 @@ impl ContainerManager {
             .await?;
         }
 
-        if let Some(ref cmd) = container.devcontainer.post_create_command {
+        if let Some(cmd) = container
+            .devcontainer
+            .post_create_command
+            .as_ref()
+            .filter(|_| !thawed)
+        {
             send_stage(channels.stage, BuildStage::LifecyclePostCreate);
             send_progress(channels.progress, "Running postCreateCommand...");
             let details = provider.inspect(container_id).await?;
@@ -209,9 +246,10 @@ impl ContainerManager {
             )
             .await?;
         }
+        send_ready_after(channels.progress, wait_for, "postCreateCommand");
 
         // Setup SSH if enabled (for proper TTY/resize support)
-        if self.global_config.defaults.ssh_enabled.unwrap_or(false) {
+        if self.uses_ssh() {
             send_stage(channels.stage, BuildStage::SetupSsh);
             send_progress(channels.progress, "Setting up SSH...");
             let details = provider.inspect(container_id).await?;
@@ -222,9 +260,9 @@ impl ContainerManager {
             let ssh_manager = SshManager::new()?;
             ssh_manager.ensure_keys_exist()?;
 
-            let user = container.devcontainer.effective_user();
+            let user = container.devcontainer.effective_remote_user();
             match ssh_manager
-                .setup_container(provider, container_id, user)
+                .setup_container(provider, container_id, id, user)
                 .await
             {
                 Ok(()) => {
@@ -255,12 +293,9 @@ impl ContainerManager {
             }
         }
 
-        // Inject dotfiles
-        let dotfiles_manager = if let Some(ref dotfiles_config) = container.devcontainer.dotfiles {
-            DotfilesManager::from_devcontainer_config(dotfiles_config, &self.global_config)
-        } else {
-            DotfilesManager::from_global_config(&self.global_config)
-        };
+        // Inject dotfiles, honoring any per-container override
+        let dotfiles_manager =
+            DotfilesManager::for_container(&container.devcontainer, &self.global_config);
 
         if dotfiles_manager.is_configured() {
             send_stage(channels.stage, BuildStage::InstallDotfiles);
@@ -269,7 +304,7 @@ impl ContainerManager {
                 .inject_with_progress(
                     provider,
                     container_id,
-                    container.devcontainer.effective_user(),
+                    container.devcontainer.effective_remote_user(),
                     channels.progress,
                     channels.output,
                 )
@@ -300,7 +335,7 @@ impl ContainerManager {
 
         let provider = self.require_container_provider(&container_state)?;
 
-        let container = self.load_container(&container_state.config_path)?;
+        let container = self.load_container_for_state(&container_state)?;
         let container_id_str = container_state
             .container_id
             .as_ref()
@@ -319,7 +354,7 @@ impl ContainerManager {
                 &cid,
                 &feature_props.post_attach_commands,
                 LifecycleExecOpts {
-                    user: container.devcontainer.effective_user(),
+                    user: container.devcontainer.effective_remote_user(),
                     working_dir: container.devcontainer.workspace_folder.as_deref(),
                     env: merged_env.as_ref(),
                     output,
@@ -335,7 +370,7 @@ impl ContainerManager {
                 &cid,
                 cmd,
                 LifecycleExecOpts {
-                    user: container.devcontainer.effective_user(),
+                    user: container.devcontainer.effective_remote_user(),
                     working_dir: container.devcontainer.workspace_folder.as_deref(),
                     env: merged_env.as_ref(),
                     output,
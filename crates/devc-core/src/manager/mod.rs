@@ -9,11 +9,15 @@ use crate::features;
 use crate::{
     run_feature_lifecycle_commands_with_output, run_lifecycle_command_with_env_and_output,
     Container, ContainerState, CoreError, DevcContainerStatus, LifecycleExecOpts, Result,
-    StateStore,
+    StateStore, FROZEN_IMAGES_METADATA_KEY, LIFECYCLE_PENDING_METADATA_KEY,
+    PINNED_PROVIDER_METADATA_KEY, PREVIOUS_IMAGE_METADATA_KEY, RUNTIME_NAME_METADATA_KEY,
+    THAWED_FROM_METADATA_KEY,
 };
-use devc_config::GlobalConfig;
+use devc_config::{DevContainerConfig, GlobalConfig, ResizeMode, ShutdownAction};
 use devc_provider::{
-    ContainerId, ContainerProvider, ContainerStatus, DevcontainerSource, LogConfig, ProviderType,
+    ContainerDetails, ContainerDiskUsage, ContainerId, ContainerProvider, ContainerStatus,
+    DevcontainerSource, ImageId, ImageInfo, LogConfig, LogStream, ProviderCapabilities,
+    ProviderError, ProviderType,
 };
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
@@ -35,6 +39,32 @@ pub struct ContainerManager {
     connection_error: Option<String>,
     /// Optional state file path override (used by tests).
     state_path_override: Option<PathBuf>,
+    /// Per-invocation `--build-arg` values, overriding devcontainer.json `build.args`
+    build_arg_overrides: HashMap<String, String>,
+    /// Per-invocation `--target` stage, overriding devcontainer.json `build.target`
+    target_override: Option<String>,
+    /// Per-invocation `--mount` values, added to the container when it is created
+    mount_overrides: Vec<devc_provider::MountConfig>,
+    /// Per-invocation `--label` values, added to the container when it is created
+    label_overrides: HashMap<String, String>,
+    /// Fail on devcontainer.json schema issues instead of warning (`--strict`)
+    strict_config: bool,
+    /// Receives structured feature download progress, when a UI listens for it
+    feature_events: Option<mpsc::UnboundedSender<features::progress::FeatureEvent>>,
+}
+
+/// Where [`ContainerManager::up_with_progress_inner`] reports and how far it goes
+#[derive(Default, Clone, Copy)]
+pub(crate) struct UpOptions<'a> {
+    pub progress: Option<&'a mpsc::UnboundedSender<String>>,
+    pub output: Option<&'a mpsc::UnboundedSender<String>>,
+    pub stage: Option<&'a mpsc::UnboundedSender<BuildStage>>,
+    pub build_output: Option<&'a mpsc::UnboundedSender<String>>,
+    /// Inject agents after the post-start phase
+    pub run_agent_injection: bool,
+    /// Return as soon as the container is running, deferring the lifecycle
+    /// commands to a later `up`
+    pub until_running: bool,
 }
 
 /// Resolved context for exec/shell — container ID, feature env, credential info.
@@ -129,6 +159,12 @@ impl ContainerManager {
             global_config,
             connection_error: None,
             state_path_override,
+            build_arg_overrides: HashMap::new(),
+            target_override: None,
+            mount_overrides: Vec::new(),
+            label_overrides: HashMap::new(),
+            strict_config: false,
+            feature_events: None,
         })
     }
 
@@ -149,6 +185,12 @@ impl ContainerManager {
             global_config,
             connection_error: None,
             state_path_override: Some(Self::test_state_path()),
+            build_arg_overrides: HashMap::new(),
+            target_override: None,
+            mount_overrides: Vec::new(),
+            label_overrides: HashMap::new(),
+            strict_config: false,
+            feature_events: None,
         }
     }
 
@@ -171,6 +213,12 @@ impl ContainerManager {
             global_config,
             connection_error: None,
             state_path_override: Some(Self::test_state_path()),
+            build_arg_overrides: HashMap::new(),
+            target_override: None,
+            mount_overrides: Vec::new(),
+            label_overrides: HashMap::new(),
+            strict_config: false,
+            feature_events: None,
         }
     }
 
@@ -188,6 +236,12 @@ impl ContainerManager {
             global_config,
             connection_error: Some(error),
             state_path_override: Some(Self::test_state_path()),
+            build_arg_overrides: HashMap::new(),
+            target_override: None,
+            mount_overrides: Vec::new(),
+            label_overrides: HashMap::new(),
+            strict_config: false,
+            feature_events: None,
         }
     }
 
@@ -217,6 +271,12 @@ impl ContainerManager {
             global_config,
             connection_error: Some(error),
             state_path_override,
+            build_arg_overrides: HashMap::new(),
+            target_override: None,
+            mount_overrides: Vec::new(),
+            label_overrides: HashMap::new(),
+            strict_config: false,
+            feature_events: None,
         })
     }
 
@@ -253,9 +313,25 @@ impl ContainerManager {
 
     /// Get the provider matching a container's stored provider type
     fn require_container_provider(&self, cs: &ContainerState) -> Result<&dyn ContainerProvider> {
+        if cs.provider_pinned() && !self.providers.contains_key(&cs.provider) {
+            return Err(CoreError::NotConnected(format!(
+                "'{}' is pinned to {}, which is not available",
+                cs.name, cs.provider
+            )));
+        }
         self.require_provider_for(cs.provider)
     }
 
+    /// Provider a rebuild puts the container on: its pinned provider, or the
+    /// default one (migrating it if that differs)
+    pub fn rebuild_provider_for(&self, cs: &ContainerState) -> Option<ProviderType> {
+        if cs.provider_pinned() {
+            Some(cs.provider)
+        } else {
+            self.provider_type()
+        }
+    }
+
     /// Get the default provider, returning an error if not connected
     fn require_provider(&self) -> Result<&dyn ContainerProvider> {
         let pt = self.default_provider_type.ok_or_else(|| {
@@ -271,11 +347,86 @@ impl ContainerManager {
     /// Load a Container from config, using this manager's GlobalConfig
     /// instead of loading from disk (so test overrides are respected).
     fn load_container(&self, config_path: &Path) -> Result<Container> {
+        if !config_path.exists() {
+            return Err(CoreError::WorkspaceMissing(
+                config_path.display().to_string(),
+            ));
+        }
         let mut container = Container::from_config(config_path)?;
         container.global_config = self.global_config.clone();
         Ok(container)
     }
 
+    /// Check a devcontainer.json against the schema, warning about each issue
+    /// (or failing on them with `--strict`)
+    fn check_config_schema(&self, config_path: &Path) -> Result<()> {
+        let (_, issues) = DevContainerConfig::load_validated(config_path, self.strict_config)?;
+        for issue in issues {
+            tracing::warn!("{}:{}", config_path.display(), issue);
+        }
+        Ok(())
+    }
+
+    /// Load the container config for a tracked container, falling back to the
+    /// user detected from its image when devcontainer.json names none.
+    /// For exec and lifecycle commands only: `create` detects the user afresh,
+    /// since the image may have been rebuilt with a different USER
+    fn load_container_for_state(&self, container_state: &ContainerState) -> Result<Container> {
+        let mut container = self.load_container(&container_state.config_path)?;
+        if container.devcontainer.effective_remote_user().is_none() {
+            if let Some(user) = container_state.metadata.get("image_user") {
+                container.devcontainer.container_user = Some(user.clone());
+            }
+        }
+        Ok(container)
+    }
+
+    /// Resolve a container's features, reusing the resolution recorded in its
+    /// state while the `features` section is unchanged (unless `fresh`)
+    pub(crate) async fn resolve_features(
+        &self,
+        container_state: &ContainerState,
+        container: &Container,
+        progress: &Option<mpsc::UnboundedSender<String>>,
+        fresh: bool,
+    ) -> Result<features::ResolvedFeatureSet> {
+        let feature_map = container.devcontainer.features.as_ref();
+        let install_order = container
+            .devcontainer
+            .override_feature_install_order
+            .as_deref()
+            .unwrap_or_default();
+        let config_dir = container
+            .config_path
+            .parent()
+            .unwrap_or(Path::new("."))
+            .to_path_buf();
+        if !fresh {
+            let hash = features::features_hash(feature_map, install_order, &config_dir);
+            if let Some(cached) =
+                features::ResolvedFeatureSet::from_metadata(&container_state.metadata, &hash)
+            {
+                if !cached.features.is_empty() {
+                    send_progress(
+                        progress.as_ref(),
+                        "Features unchanged, reusing previous resolution",
+                    );
+                }
+                return Ok(cached);
+            }
+        }
+
+        features::ResolvedFeatureSet::resolve(
+            feature_map,
+            install_order,
+            &config_dir,
+            &self.global_config.features.registry_auth,
+            progress,
+            self.feature_events.as_ref(),
+        )
+        .await
+    }
+
     /// Get the default provider type (None if disconnected)
     pub fn provider_type(&self) -> Option<ProviderType> {
         self.default_provider_type
@@ -293,6 +444,15 @@ impl ContainerManager {
         self.providers.get(&pt).map(|p| p.as_ref())
     }
 
+    /// Capabilities of each connected provider
+    pub async fn provider_capabilities(&self) -> Vec<(ProviderType, ProviderCapabilities)> {
+        let mut capabilities = Vec::new();
+        for (pt, provider) in &self.providers {
+            capabilities.push((*pt, provider.capabilities().await));
+        }
+        capabilities
+    }
+
     /// Get runtime command args for a container's provider (for PTY shell, socat, etc.)
     /// Returns (program, prefix_args) so callers can build:
     /// `program [prefix_args...] exec [flags...] container_id [cmd...]`
@@ -306,6 +466,11 @@ impl ContainerManager {
         &self.global_config
     }
 
+    /// Whether shells reach containers over SSH (dropbear) rather than plain exec
+    pub(crate) fn uses_ssh(&self) -> bool {
+        self.global_config.defaults.effective_resize_mode() == ResizeMode::Ssh
+    }
+
     /// Replace the manager's in-memory global config snapshot.
     ///
     /// This allows long-lived clients (like the TUI) to apply freshly saved
@@ -314,6 +479,44 @@ impl ContainerManager {
         self.global_config = global_config;
     }
 
+    /// Set build args for this invocation; they take precedence over
+    /// devcontainer.json `build.args` in every image build
+    pub fn set_build_arg_overrides(&mut self, build_args: HashMap<String, String>) {
+        self.build_arg_overrides = build_args;
+    }
+
+    /// Set the Dockerfile stage to build for this invocation, overriding
+    /// devcontainer.json `build.target`
+    pub fn set_target_override(&mut self, target: Option<String>) {
+        self.target_override = target;
+    }
+
+    /// Make devcontainer.json schema issues fail builds instead of warning
+    pub fn set_strict_config(&mut self, strict: bool) {
+        self.strict_config = strict;
+    }
+
+    /// Send feature download progress to `events` as it happens, instead of
+    /// as plain lines on each operation's progress channel
+    pub fn set_feature_events(
+        &mut self,
+        events: Option<mpsc::UnboundedSender<features::progress::FeatureEvent>>,
+    ) {
+        self.feature_events = events;
+    }
+
+    /// Set extra mounts for this invocation, added alongside devcontainer.json
+    /// `mounts` when a container is created
+    pub fn set_mount_overrides(&mut self, mounts: Vec<devc_provider::MountConfig>) {
+        self.mount_overrides = mounts;
+    }
+
+    /// Set extra labels for this invocation, added on top of devcontainer.json
+    /// `labels` when a container is created
+    pub fn set_label_overrides(&mut self, labels: HashMap<String, String>) {
+        self.label_overrides = labels;
+    }
+
     /// Set up credential forwarding for a container and return status.
     ///
     /// This is idempotent — safe to call before every shell/exec.
@@ -343,9 +546,13 @@ impl ContainerManager {
         let cid = ContainerId::new(container_id);
 
         let user = self
-            .load_container(&container_state.config_path)
+            .load_container_for_state(&container_state)
             .ok()
-            .and_then(|c| c.devcontainer.effective_user().map(|s| s.to_string()));
+            .and_then(|c| {
+                c.devcontainer
+                    .effective_remote_user()
+                    .map(|s| s.to_string())
+            });
 
         crate::credentials::setup_credentials(
             provider,
@@ -394,6 +601,11 @@ impl ContainerManager {
                 .ok_or_else(|| CoreError::ContainerNotFound(id.to_string()))?
         };
 
+        if container_state.status == DevcContainerStatus::Paused {
+            return Err(CoreError::InvalidState(
+                "Container is paused; unpause it first".to_string(),
+            ));
+        }
         if container_state.status != DevcContainerStatus::Running {
             return Err(CoreError::InvalidState(
                 "Container is not running".to_string(),
@@ -413,9 +625,13 @@ impl ContainerManager {
 
         // Set up credential forwarding (non-fatal)
         let user = self
-            .load_container(&container_state.config_path)
+            .load_container_for_state(&container_state)
             .ok()
-            .and_then(|c| c.devcontainer.effective_user().map(|s| s.to_string()));
+            .and_then(|c| {
+                c.devcontainer
+                    .effective_remote_user()
+                    .map(|s| s.to_string())
+            });
 
         let (gh_token, docker_registries, git_hosts, git_identity_injected) =
             match crate::credentials::setup_credentials(
@@ -455,12 +671,7 @@ impl ContainerManager {
             None
         };
 
-        // Touch last-used timestamp
-        {
-            let mut state = self.state.write().await;
-            state.touch(id);
-        }
-        self.save_state().await?;
+        self.touch(id).await?;
 
         Ok(ExecEnv {
             container_id: live_container_id.0,
@@ -487,7 +698,7 @@ impl ContainerManager {
             container_state.compose_project.as_ref(),
             container_state.compose_service.as_deref(),
         ) {
-            let container = self.load_container(&container_state.config_path)?;
+            let container = self.load_container_for_state(container_state)?;
             let compose_files = container.compose_files().ok_or_else(|| {
                 CoreError::InvalidState("No dockerComposeFile specified".to_string())
             })?;
@@ -557,9 +768,13 @@ impl ContainerManager {
         let cid = ContainerId::new(container_id);
 
         let user = self
-            .load_container(&container_state.config_path)
+            .load_container_for_state(&container_state)
             .ok()
-            .and_then(|c| c.devcontainer.effective_user().map(|s| s.to_string()));
+            .and_then(|c| {
+                c.devcontainer
+                    .effective_remote_user()
+                    .map(|s| s.to_string())
+            });
 
         Ok(crate::agents::setup_agents(provider, &cid, &self.global_config, user.as_deref()).await)
     }
@@ -592,9 +807,13 @@ impl ContainerManager {
         let cid = ContainerId::new(container_id);
 
         let user = self
-            .load_container(&container_state.config_path)
+            .load_container_for_state(&container_state)
             .ok()
-            .and_then(|c| c.devcontainer.effective_user().map(|s| s.to_string()));
+            .and_then(|c| {
+                c.devcontainer
+                    .effective_remote_user()
+                    .map(|s| s.to_string())
+            });
 
         Ok(crate::agents::setup_agents_with_selection(
             provider,
@@ -631,9 +850,13 @@ impl ContainerManager {
         let cid = ContainerId::new(container_id);
 
         let user = self
-            .load_container(&container_state.config_path)
+            .load_container_for_state(&container_state)
             .ok()
-            .and_then(|c| c.devcontainer.effective_user().map(|s| s.to_string()));
+            .and_then(|c| {
+                c.devcontainer
+                    .effective_remote_user()
+                    .map(|s| s.to_string())
+            });
 
         Ok(
             crate::agents::inspect_agents(provider, &cid, &self.global_config, user.as_deref())
@@ -698,6 +921,93 @@ impl ContainerManager {
         Ok(state.get(id).cloned())
     }
 
+    /// Record activity on a container by bumping its last-used timestamp
+    pub async fn touch(&self, id: &str) -> Result<()> {
+        {
+            let mut state = self.state.write().await;
+            state.touch(id);
+        }
+        self.save_state().await
+    }
+
+    /// Whether anything is attached to a running container from outside:
+    /// `devc shell`/`devc ssh` sessions, `docker exec`, or an editor's server.
+    ///
+    /// The runtime starts exec'd processes with parent PID 0, while processes
+    /// the container starts itself descend from PID 1 (daemons a lifecycle
+    /// command left behind are reparented there too).
+    pub async fn has_exec_sessions(&self, id: &str) -> Result<bool> {
+        let container_state = self
+            .get(id)
+            .await?
+            .ok_or_else(|| CoreError::ContainerNotFound(id.to_string()))?;
+        let provider = self.require_container_provider(&container_state)?;
+        let container_id = container_state
+            .container_id
+            .as_ref()
+            .ok_or_else(|| CoreError::InvalidState("Container not created yet".to_string()))?;
+        let config = devc_provider::ExecConfig {
+            cmd: vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                // Skips PID 1 and this script, itself an exec'd process
+                "for s in /proc/[0-9]*/status; do \
+                   pid=${s#/proc/}; pid=${pid%/status}; \
+                   case $pid in 1|$$) continue ;; esac; \
+                   grep -qs '^PPid:[[:space:]]*0$' \"$s\" && echo \"$pid\"; \
+                 done; true"
+                    .to_string(),
+            ],
+            env: HashMap::new(),
+            working_dir: None,
+            user: Some("root".to_string()),
+            tty: false,
+            stdin: false,
+            privileged: false,
+        };
+        let result = provider
+            .exec(&ContainerId::new(container_id), &config)
+            .await?;
+        if result.exit_code != 0 {
+            return Err(CoreError::ExecFailed(format!(
+                "session check exited with code {}",
+                result.exit_code
+            )));
+        }
+        Ok(!result.output.trim().is_empty())
+    }
+
+    /// Pin a container to a provider, so a different default provider never
+    /// moves it on rebuild
+    ///
+    /// Moving to another provider drops the built image, which lives in the
+    /// old provider's store; a runtime container must be removed first.
+    pub async fn pin_provider(&self, id: &str, provider: ProviderType) -> Result<()> {
+        {
+            let mut state = self.state.write().await;
+            let cs = state
+                .get_mut(id)
+                .ok_or_else(|| CoreError::ContainerNotFound(id.to_string()))?;
+            if cs.provider != provider {
+                if cs.container_id.is_some() {
+                    return Err(CoreError::InvalidState(format!(
+                        "'{}' has a {} container; run 'devc down' before pinning it to {}",
+                        cs.name, cs.provider, provider
+                    )));
+                }
+                cs.provider = provider;
+                cs.image_id = None;
+                cs.metadata.remove(PREVIOUS_IMAGE_METADATA_KEY);
+                if cs.status == DevcContainerStatus::Built {
+                    cs.status = DevcContainerStatus::Configured;
+                }
+            }
+            cs.metadata
+                .insert(PINNED_PROVIDER_METADATA_KEY.to_string(), "true".to_string());
+        }
+        self.save_state().await
+    }
+
     /// Initialize a new container from a workspace
     pub async fn init(&self, workspace_path: &Path) -> Result<ContainerState> {
         let provider_type = self.provider_type().ok_or_else(|| {
@@ -754,8 +1064,17 @@ impl ContainerManager {
             .get("feature_properties")
             .and_then(|json| serde_json::from_str::<features::MergedFeatureProperties>(json).ok());
 
+        container.resource_limits()?;
         let mut create_config =
             container.create_config_with_features(image_id, feature_props.as_ref());
+        create_config
+            .mounts
+            .extend(self.mount_overrides.iter().cloned());
+        create_config.user_labels.extend(
+            self.label_overrides
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone())),
+        );
 
         // Add tmpfs mount for credential cache if credential forwarding is enabled
         if self.global_config.credentials.docker || self.global_config.credentials.git {
@@ -785,19 +1104,63 @@ impl ContainerManager {
             }
         }
 
+        // hostRequirements.gpu: only pass GPUs through when the runtime can supply them
+        if create_config.gpus.is_some() && !provider.gpu_available().await {
+            create_config.gpus = None;
+            let optional = container
+                .devcontainer
+                .host_requirements
+                .as_ref()
+                .and_then(|h| h.gpu.as_ref())
+                .is_some_and(|gpu| gpu.is_optional());
+            if !optional {
+                tracing::warn!(
+                    "hostRequirements.gpu is set but no GPU runtime was found for {}; creating without GPUs",
+                    container_state.provider
+                );
+            }
+        }
+
         // Clean up any orphaned container with the same name before creating
         // This handles cases where state has container_id=null but a container exists
-        let container_name = container.container_name();
+        let container_name = match container_state.metadata.get(RUNTIME_NAME_METADATA_KEY) {
+            Some(name) => name.clone(),
+            None => container.container_name(),
+        };
+        create_config.name = Some(container_name.clone());
         provider.remove_by_name(&container_name).await?;
 
         let container_id = provider.create(&create_config).await?;
 
+        // Without remoteUser/containerUser, remember the image's USER so that
+        // exec, SSH and lifecycle commands run as it, as VS Code does
+        let image_user = if container.devcontainer.effective_remote_user().is_none() {
+            match provider.inspect(&container_id).await {
+                Ok(details) => details.user.as_deref().and_then(image_user_name),
+                Err(e) => {
+                    tracing::debug!("Could not inspect new container for its user: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
         // Update state with container ID
         {
             let mut state = self.state.write().await;
             if let Some(cs) = state.get_mut(id) {
                 cs.container_id = Some(container_id.0.clone());
                 cs.status = DevcContainerStatus::Created;
+                cs.last_used = chrono::Utc::now();
+                match image_user {
+                    Some(user) => {
+                        cs.metadata.insert("image_user".to_string(), user);
+                    }
+                    None => {
+                        cs.metadata.remove("image_user");
+                    }
+                }
             }
         }
         self.save_state().await?;
@@ -907,11 +1270,12 @@ impl ContainerManager {
         // Handle compose start: bring up all services
         let is_compose = container_state.compose_project.is_some()
             || self
-                .load_container(&container_state.config_path)
+                .load_container_for_state(&container_state)
                 .map(|c| c.is_compose())
                 .unwrap_or(false);
         if is_compose {
-            let container = self.load_container(&container_state.config_path)?;
+            self.require_compose(provider).await?;
+            let container = self.load_container_for_state(&container_state)?;
             if let Some(compose_files) = container.compose_files() {
                 let owned = compose_file_strs(&compose_files);
                 let compose_file_refs: Vec<&str> = owned.iter().map(|s| s.as_str()).collect();
@@ -950,11 +1314,12 @@ impl ContainerManager {
                 self.set_status(id, DevcContainerStatus::Running).await?;
 
                 // Ensure SSH daemon is running if SSH was set up
-                if container_state
-                    .metadata
-                    .get("ssh_available")
-                    .map(|v| v == "true")
-                    .unwrap_or(false)
+                if self.uses_ssh()
+                    && container_state
+                        .metadata
+                        .get("ssh_available")
+                        .map(|v| v == "true")
+                        .unwrap_or(false)
                 {
                     self.ensure_ssh_daemon_running(provider, &cid).await?;
                 }
@@ -971,7 +1336,7 @@ impl ContainerManager {
                         &cid,
                         &feature_props.post_start_commands,
                         LifecycleExecOpts {
-                            user: container.devcontainer.effective_user(),
+                            user: container.devcontainer.effective_remote_user(),
                             working_dir: container.devcontainer.workspace_folder.as_deref(),
                             env: merged_env.as_ref(),
                             output,
@@ -986,7 +1351,7 @@ impl ContainerManager {
                         &cid,
                         cmd,
                         LifecycleExecOpts {
-                            user: container.devcontainer.effective_user(),
+                            user: container.devcontainer.effective_remote_user(),
                             working_dir: container.devcontainer.workspace_folder.as_deref(),
                             env: merged_env.as_ref(),
                             output,
@@ -1018,18 +1383,19 @@ impl ContainerManager {
         self.set_status(id, DevcContainerStatus::Running).await?;
 
         // Ensure SSH daemon is running if SSH was set up for this container
-        if container_state
-            .metadata
-            .get("ssh_available")
-            .map(|v| v == "true")
-            .unwrap_or(false)
+        if self.uses_ssh()
+            && container_state
+                .metadata
+                .get("ssh_available")
+                .map(|v| v == "true")
+                .unwrap_or(false)
         {
             self.ensure_ssh_daemon_running(provider, &ContainerId::new(container_id))
                 .await?;
         }
 
         // Run post-start commands (feature commands first, then devcontainer.json)
-        let container = self.load_container(&container_state.config_path)?;
+        let container = self.load_container_for_state(&container_state)?;
         let feature_props = get_feature_properties(&container_state);
         let merged_env = merge_remote_env(
             container.devcontainer.remote_env.as_ref(),
@@ -1042,7 +1408,7 @@ impl ContainerManager {
                 &cid,
                 &feature_props.post_start_commands,
                 LifecycleExecOpts {
-                    user: container.devcontainer.effective_user(),
+                    user: container.devcontainer.effective_remote_user(),
                     working_dir: container.devcontainer.workspace_folder.as_deref(),
                     env: merged_env.as_ref(),
                     output,
@@ -1057,7 +1423,7 @@ impl ContainerManager {
                 &cid,
                 cmd,
                 LifecycleExecOpts {
-                    user: container.devcontainer.effective_user(),
+                    user: container.devcontainer.effective_remote_user(),
                     working_dir: container.devcontainer.workspace_folder.as_deref(),
                     env: merged_env.as_ref(),
                     output,
@@ -1075,18 +1441,20 @@ impl ContainerManager {
     }
 
     /// Ensure the SSH daemon (dropbear) is running in the container
+    ///
+    /// Recreates the host key first if it went missing (e.g. the image was
+    /// rebuilt without it); dropbear won't start without one.
     async fn ensure_ssh_daemon_running(
         &self,
         provider: &dyn ContainerProvider,
         container_id: &ContainerId,
     ) -> Result<()> {
-        let script = r#"
-if ! pgrep -x dropbear >/dev/null 2>&1; then
-    /usr/sbin/dropbear -s -r /etc/dropbear/dropbear_ed25519_host_key -p 127.0.0.1:2222 2>/dev/null
-fi
-"#;
         let config = devc_provider::ExecConfig {
-            cmd: vec!["/bin/sh".to_string(), "-c".to_string(), script.to_string()],
+            cmd: vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                crate::ssh::DROPBEAR_START_SCRIPT.to_string(),
+            ],
             env: std::collections::HashMap::new(),
             working_dir: None,
             user: Some("root".to_string()),
@@ -1096,10 +1464,23 @@ fi
         };
 
         match provider.exec(container_id, &config).await {
-            Ok(_) => {
+            Ok(result) if result.exit_code == 0 => {
+                if crate::ssh::host_key_generated(&result.output) {
+                    tracing::info!("Generated missing dropbear host key");
+                } else {
+                    tracing::debug!("Reusing existing dropbear host key");
+                }
                 tracing::debug!("SSH daemon check/start completed");
                 Ok(())
             }
+            Ok(result) => {
+                tracing::warn!(
+                    "Failed to start SSH daemon (exit {}): {}",
+                    result.exit_code,
+                    result.output.trim()
+                );
+                Ok(())
+            }
             Err(e) => {
                 tracing::warn!("Failed to ensure SSH daemon is running: {}", e);
                 // Don't fail the start if SSH daemon can't be started
@@ -1129,7 +1510,7 @@ fi
 
         // Handle compose stop: bring down all services
         if let Some(ref compose_project) = container_state.compose_project {
-            let container = self.load_container(&container_state.config_path)?;
+            let container = self.load_container_for_state(&container_state)?;
             if let Some(compose_files) = container.compose_files() {
                 let owned = compose_file_strs(&compose_files);
                 let compose_file_refs: Vec<&str> = owned.iter().map(|s| s.as_str()).collect();
@@ -1162,6 +1543,10 @@ fi
             .as_ref()
             .ok_or_else(|| CoreError::InvalidState("Container not created".to_string()))?;
 
+        // Frozen processes can't handle the stop signal
+        if container_state.status == DevcContainerStatus::Paused {
+            provider.unpause(&ContainerId::new(container_id)).await?;
+        }
         provider
             .stop(&ContainerId::new(container_id), Some(10))
             .await?;
@@ -1171,8 +1556,274 @@ fi
         Ok(())
     }
 
+    /// Pause a running container, freezing its processes until [`Self::unpause`]
+    ///
+    /// For compose projects only the primary service container is paused.
+    pub async fn pause(&self, id: &str) -> Result<()> {
+        self.set_paused(id, true).await
+    }
+
+    /// Resume a paused container
+    pub async fn unpause(&self, id: &str) -> Result<()> {
+        self.set_paused(id, false).await
+    }
+
+    async fn set_paused(&self, id: &str, pause: bool) -> Result<()> {
+        let container_state = {
+            let state = self.state.read().await;
+            state
+                .get(id)
+                .cloned()
+                .ok_or_else(|| CoreError::ContainerNotFound(id.to_string()))?
+        };
+
+        let provider = self.require_container_provider(&container_state)?;
+
+        let (from, to) = if pause {
+            (DevcContainerStatus::Running, DevcContainerStatus::Paused)
+        } else {
+            (DevcContainerStatus::Paused, DevcContainerStatus::Running)
+        };
+        if container_state.status != from {
+            return Err(CoreError::InvalidState(format!(
+                "Container cannot be {} in {} state",
+                if pause { "paused" } else { "unpaused" },
+                container_state.status
+            )));
+        }
+
+        let container_id = ContainerId::new(
+            container_state
+                .container_id
+                .as_ref()
+                .ok_or_else(|| CoreError::InvalidState("Container not created".to_string()))?,
+        );
+        if pause {
+            provider.pause(&container_id).await?;
+        } else {
+            provider.unpause(&container_id).await?;
+        }
+
+        self.set_status(id, to).await
+    }
+
+    /// Commit a container's filesystem to a new image and record it on the
+    /// container, for reproducing its current state later with [`Self::thaw`]
+    ///
+    /// The image holds the container's writable layer only; volumes and bind
+    /// mounts are not captured. Returns the image tag.
+    pub async fn freeze(&self, id: &str) -> Result<String> {
+        let container_state = {
+            let state = self.state.read().await;
+            state
+                .get(id)
+                .cloned()
+                .ok_or_else(|| CoreError::ContainerNotFound(id.to_string()))?
+        };
+        // Only a single container's filesystem can be snapshotted
+        if container_state.compose_project.is_some() {
+            return Err(CoreError::InvalidState(format!(
+                "'{}' is a compose project, which cannot be frozen",
+                container_state.name
+            )));
+        }
+
+        let provider = self.require_container_provider(&container_state)?;
+        let container_id = ContainerId::new(
+            container_state
+                .container_id
+                .as_ref()
+                .ok_or_else(|| CoreError::InvalidState("Container not created".to_string()))?,
+        );
+
+        let tag = format!(
+            "devc/{}:frozen-{}",
+            crate::container::sanitize_name(&container_state.name),
+            chrono::Utc::now().format("%Y%m%d-%H%M%S")
+        );
+        provider.commit(&container_id, &tag).await?;
+
+        {
+            let mut state = self.state.write().await;
+            if let Some(cs) = state.get_mut(id) {
+                let mut frozen = cs.frozen_images();
+                frozen.push(tag.clone());
+                if let Ok(json) = serde_json::to_string(&frozen) {
+                    cs.metadata
+                        .insert(FROZEN_IMAGES_METADATA_KEY.to_string(), json);
+                }
+            }
+        }
+        self.save_state().await?;
+
+        Ok(tag)
+    }
+
+    /// Create a new tracked container called `name` from an image made by
+    /// [`Self::freeze`]
+    ///
+    /// The new container shares the frozen container's config and workspace
+    /// but gets its own runtime name. It is created, not started, and skips
+    /// the create-phase lifecycle commands already captured in the image.
+    ///
+    /// Snapshots are found by the `devc.*` labels the image inherits from its
+    /// container, so one can still be thawed after its source is removed.
+    pub async fn thaw(&self, name: &str, image: &str) -> Result<ContainerState> {
+        if let Some(existing) = self.state.read().await.find_by_name(name) {
+            return Err(CoreError::ContainerExists(existing.name.clone()));
+        }
+        // A still-tracked source says which runtime holds the image
+        let source = {
+            let state = self.state.read().await;
+            state
+                .list()
+                .into_iter()
+                .find(|cs| cs.frozen_images().iter().any(|tag| tag == image))
+                .cloned()
+        };
+        let (provider_type, snapshot) = self.find_frozen_image(source.as_ref(), image).await?;
+        let label = |key: &str| snapshot.labels.get(key).cloned();
+        let (Some(project), Some(config_path), Some(workspace_path)) = (
+            label("devc.project"),
+            label("devc.config"),
+            label("devc.workspace"),
+        ) else {
+            return Err(CoreError::InvalidState(format!(
+                "'{}' is missing the devc labels needed to thaw it",
+                image
+            )));
+        };
+
+        let new_state = {
+            let mut state = self.state.write().await;
+            if let Some(existing) = state.find_by_name(name) {
+                return Err(CoreError::ContainerExists(existing.name.clone()));
+            }
+
+            let mut new_state = ContainerState::new(
+                name.to_string(),
+                provider_type,
+                PathBuf::from(config_path),
+                PathBuf::from(workspace_path),
+            );
+            new_state.image_id = Some(image.to_string());
+            new_state.status = DevcContainerStatus::Built;
+            if let Some(source) = &source {
+                new_state.source = source.source.clone();
+                for key in [
+                    "feature_properties",
+                    "image_user",
+                    "remote_user",
+                    "workspace_folder",
+                ] {
+                    if let Some(value) = source.metadata.get(key) {
+                        new_state.metadata.insert(key.to_string(), value.clone());
+                    }
+                }
+            }
+            let runtime_name = format!(
+                "devc_{}__{}",
+                crate::container::sanitize_name(name),
+                new_state.short_id()
+            );
+            new_state
+                .metadata
+                .insert(RUNTIME_NAME_METADATA_KEY.to_string(), runtime_name);
+            new_state
+                .metadata
+                .insert(THAWED_FROM_METADATA_KEY.to_string(), project);
+            // The image only exists in this runtime
+            new_state
+                .metadata
+                .insert(PINNED_PROVIDER_METADATA_KEY.to_string(), "true".to_string());
+
+            state.add(new_state.clone());
+            new_state
+        };
+        self.save_state().await?;
+
+        if let Err(e) = self.create(&new_state.id).await {
+            self.forget(&new_state.id).await?;
+            return Err(e);
+        }
+
+        let state = self.state.read().await;
+        Ok(state.get(&new_state.id).cloned().unwrap_or(new_state))
+    }
+
+    /// Find a `devc freeze` image by tag, on the source container's runtime if
+    /// it is still tracked and on every connected runtime otherwise
+    async fn find_frozen_image(
+        &self,
+        source: Option<&ContainerState>,
+        image: &str,
+    ) -> Result<(ProviderType, ImageInfo)> {
+        let not_frozen = || {
+            CoreError::InvalidState(format!("'{}' is not an image made by 'devc freeze'", image))
+        };
+        if !image.contains(":frozen-") {
+            return Err(not_frozen());
+        }
+        let provider_types: Vec<ProviderType> = match source {
+            Some(cs) => vec![cs.provider],
+            None => self.providers.keys().copied().collect(),
+        };
+        for pt in provider_types {
+            let images = self
+                .require_provider_for(pt)?
+                .list_images("devc.managed")
+                .await?;
+            if let Some(found) = images.into_iter().find(|i| i.tag == image) {
+                return Ok((pt, found));
+            }
+        }
+        Err(not_frozen())
+    }
+
+    /// Apply the container's `shutdownAction` from devcontainer.json
+    ///
+    /// `stopCompose` brings the whole compose project down, `stopContainer` stops
+    /// only the primary container, and `none` (or no setting) does nothing.
+    /// Returns the action that was applied.
+    pub async fn shutdown(&self, id: &str) -> Result<ShutdownAction> {
+        let container_state = {
+            let state = self.state.read().await;
+            state
+                .get(id)
+                .cloned()
+                .ok_or_else(|| CoreError::ContainerNotFound(id.to_string()))?
+        };
+        let container = self.load_container_for_state(&container_state)?;
+        let action = container.devcontainer.effective_shutdown_action();
+
+        match action {
+            ShutdownAction::None => {}
+            ShutdownAction::StopCompose => self.stop(id).await?,
+            ShutdownAction::StopContainer if container_state.compose_project.is_some() => {
+                // Leave companion services running; start() brings the primary back via compose
+                let provider = self.require_container_provider(&container_state)?;
+                if let Some(ref container_id) = container_state.container_id {
+                    provider
+                        .stop(&ContainerId::new(container_id), Some(10))
+                        .await?;
+                }
+                self.set_status(id, DevcContainerStatus::Stopped).await?;
+            }
+            ShutdownAction::StopContainer => self.stop(id).await?,
+        }
+
+        Ok(action)
+    }
+
     /// Remove a container completely (removes from state store too)
-    pub async fn remove(&self, id: &str, force: bool) -> Result<()> {
+    ///
+    /// The built image is kept so the container can be recreated quickly.
+    /// With `remove_image`, the images of a devc-created container (current
+    /// and previous) are deleted as well, unless another tracked container
+    /// still uses them. Images are never force-removed; one that can't be
+    /// removed is left behind with a warning, since the container is gone by
+    /// then.
+    pub async fn remove(&self, id: &str, force: bool, remove_image: bool) -> Result<()> {
         let container_state = {
             let state = self.state.read().await;
             state
@@ -1215,9 +1866,52 @@ fi
         }
         self.save_state_with_tombstones(&[id.to_string()]).await?;
 
+        // The host key was kept for rebuilds; nothing will rebuild this one
+        if let Err(e) = crate::SshManager::new().and_then(|ssh| ssh.forget_host_key(id)) {
+            tracing::warn!("Failed to forget SSH host key: {}", e);
+        }
+
+        if remove_image && container_state.source == DevcontainerSource::Devc {
+            self.remove_unused_images(&container_state).await;
+        }
+
         Ok(())
     }
 
+    /// Delete a removed container's current and previous images, skipping
+    /// any that another tracked container still uses and warning about any
+    /// the runtime refuses to remove
+    async fn remove_unused_images(&self, container_state: &ContainerState) {
+        let in_use: HashSet<String> = {
+            let state = self.state.read().await;
+            state
+                .list()
+                .iter()
+                .filter_map(|cs| cs.image_id.clone())
+                .collect()
+        };
+        let provider = match self.require_container_provider(container_state) {
+            Ok(provider) => provider,
+            Err(e) => {
+                tracing::warn!("Not removing images of '{}': {}", container_state.name, e);
+                return;
+            }
+        };
+        let images = [
+            container_state.image_id.as_deref(),
+            container_state.previous_image_id(),
+        ];
+        for image in images.into_iter().flatten() {
+            if in_use.contains(image) {
+                tracing::info!("Keeping image {} (still used by another container)", image);
+                continue;
+            }
+            if let Err(e) = provider.remove_image(&ImageId::new(image), false).await {
+                tracing::warn!("Failed to remove image {}: {}", image, e);
+            }
+        }
+    }
+
     /// Stop and remove the runtime container, but keep the state so it can be recreated with `up`
     pub async fn down(&self, id: &str) -> Result<()> {
         let container_state = {
@@ -1240,7 +1934,7 @@ fi
 
             // Handle compose teardown
             if let Some(ref compose_project) = container_state.compose_project {
-                let container = self.load_container(&container_state.config_path)?;
+                let container = self.load_container_for_state(&container_state)?;
                 if let Some(compose_files) = container.compose_files() {
                     let owned = compose_file_strs(&compose_files);
                     let compose_file_refs: Vec<&str> = owned.iter().map(|s| s.as_str()).collect();
@@ -1302,8 +1996,14 @@ fi
 
     /// Build, create, and start a container (full lifecycle)
     pub async fn up(&self, id: &str) -> Result<()> {
-        self.up_with_progress_inner(id, None, None, None, None, true)
-            .await
+        self.up_with_progress_inner(
+            id,
+            UpOptions {
+                run_agent_injection: true,
+                ..Default::default()
+            },
+        )
+        .await
     }
 
     /// Build, create, and start a container with progress updates
@@ -1314,19 +2014,55 @@ fi
         output: Option<&mpsc::UnboundedSender<String>>,
         build_output: Option<&mpsc::UnboundedSender<String>>,
     ) -> Result<()> {
-        self.up_with_progress_inner(id, progress, output, None, build_output, true)
-            .await
+        self.up_with_progress_inner(
+            id,
+            UpOptions {
+                progress,
+                output,
+                build_output,
+                run_agent_injection: true,
+                ..Default::default()
+            },
+        )
+        .await
     }
 
-    pub(crate) async fn up_with_progress_inner(
+    /// Build, create, and start a container, stopping once it is running
+    ///
+    /// Credential forwarding, the first-create lifecycle and the post-start
+    /// phase are skipped; a later `up` on the same container runs them. The
+    /// container is marked running meanwhile. Not supported for compose
+    /// projects.
+    pub async fn up_until_running(
         &self,
         id: &str,
-        progress: Option<&mpsc::UnboundedSender<String>>,
         output: Option<&mpsc::UnboundedSender<String>>,
-        stage: Option<&mpsc::UnboundedSender<BuildStage>>,
-        build_output: Option<&mpsc::UnboundedSender<String>>,
-        run_agent_injection: bool,
     ) -> Result<()> {
+        self.up_with_progress_inner(
+            id,
+            UpOptions {
+                output,
+                build_output: output,
+                until_running: true,
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    pub(crate) async fn up_with_progress_inner(
+        &self,
+        id: &str,
+        options: UpOptions<'_>,
+    ) -> Result<()> {
+        let UpOptions {
+            progress,
+            output,
+            stage,
+            build_output,
+            run_agent_injection,
+            until_running,
+        } = options;
         let container_state = {
             let state = self.state.read().await;
             state
@@ -1337,16 +2073,52 @@ fi
 
         let provider = self.require_container_provider(&container_state)?;
 
-        let container = self.load_container(&container_state.config_path)?;
-        if let Some(ref wait_for) = container.devcontainer.wait_for {
-            tracing::info!(
-                "waitFor is set to '{}' (async lifecycle deferral not yet implemented)",
-                wait_for
+        let container = self.load_container_for_state(&container_state)?;
+        // A build checks the config itself
+        if container.is_compose() || container_state.image_id.is_some() {
+            self.check_config_schema(&container_state.config_path)?;
+        }
+
+        for (flag, is_set) in [
+            ("--mount", !self.mount_overrides.is_empty()),
+            ("--label", !self.label_overrides.is_empty()),
+        ] {
+            if !is_set {
+                continue;
+            }
+            if container.is_compose() {
+                tracing::warn!(
+                    "{} is ignored for compose projects; set it in the compose file",
+                    flag
+                );
+            } else if container_state.container_id.is_some() {
+                return Err(CoreError::InvalidState(format!(
+                    "'{}' already has a container and {} only applies when one is created; run 'devc down {}' first",
+                    container_state.name, flag, container_state.name
+                )));
+            }
+        }
+        if container.is_compose()
+            && container
+                .devcontainer
+                .labels
+                .as_ref()
+                .is_some_and(|labels| !labels.is_empty())
+        {
+            tracing::warn!(
+                "devcontainer.json labels are ignored for compose projects; set them on the service in the compose file"
             );
         }
 
         // Handle Docker Compose projects
         if container.is_compose() {
+            if until_running {
+                return Err(CoreError::InvalidState(format!(
+                    "'{}' is a compose project, which cannot defer its lifecycle commands",
+                    container_state.name
+                )));
+            }
+            self.require_compose(provider).await?;
             return self
                 .up_compose(id, &container, &container_state, provider, progress, output)
                 .await;
@@ -1358,7 +2130,7 @@ fi
             if let Some(ref cmd) = container.devcontainer.initialize_command {
                 send_stage(stage, BuildStage::InitializeCommandHost);
                 send_progress(progress, "Running initializeCommand on host...");
-                crate::run_host_command(cmd, &container.workspace_path, output).await?;
+                crate::run_host_command(cmd, &container.initialize_command_dir()?, output).await?;
             }
             send_stage(stage, BuildStage::BuildingImage);
             send_progress(progress, "Building image...");
@@ -1398,11 +2170,29 @@ fi
             })?);
 
         // Start container early so credentials can be injected before lifecycle commands
-        {
-            let details = provider.inspect(&container_id).await?;
-            if details.status != devc_provider::ContainerStatus::Running {
-                provider.start(&container_id).await?;
+        match provider.inspect(&container_id).await?.status {
+            ContainerStatus::Running => {}
+            // A paused container can't be started, only resumed
+            ContainerStatus::Paused => {
+                provider.unpause(&container_id).await?;
+                self.set_status(id, DevcContainerStatus::Running).await?;
             }
+            _ => provider.start(&container_id).await?,
+        }
+        if until_running {
+            let mut state = self.state.write().await;
+            if let Some(cs) = state.get_mut(id) {
+                if cs.status == DevcContainerStatus::Created {
+                    cs.metadata.insert(
+                        LIFECYCLE_PENDING_METADATA_KEY.to_string(),
+                        "true".to_string(),
+                    );
+                }
+                cs.set_status(DevcContainerStatus::Running);
+            }
+            drop(state);
+            self.save_state().await?;
+            return Ok(());
         }
 
         // Set up credential forwarding before lifecycle commands so they can
@@ -1410,13 +2200,16 @@ fi
         self.inject_credentials_nonfatal(
             provider,
             &container_id,
-            container.devcontainer.effective_user(),
+            container.devcontainer.effective_remote_user(),
             &container_state.workspace_path,
         )
         .await;
 
-        // Run first-create lifecycle if this is a newly created container
-        if container_state.status == DevcContainerStatus::Created {
+        // Run first-create lifecycle if this is a newly created container,
+        // or `up --detach` deferred it
+        if container_state.status == DevcContainerStatus::Created
+            || container_state.lifecycle_pending()
+        {
             let verbose_output = if stage.is_some() { output } else { None };
             self.run_first_create_lifecycle(
                 id,
@@ -1430,6 +2223,14 @@ fi
                 },
             )
             .await?;
+            if container_state.lifecycle_pending() {
+                let mut state = self.state.write().await;
+                if let Some(cs) = state.get_mut(id) {
+                    cs.metadata.remove(LIFECYCLE_PENDING_METADATA_KEY);
+                }
+                drop(state);
+                self.save_state().await?;
+            }
         }
 
         // Start container (idempotent) and run post-start phase
@@ -1457,21 +2258,76 @@ fi
                 .ok_or_else(|| CoreError::ContainerNotFound(id.to_string()))?
         };
 
+        let new_status = self.probe_status(&container_state).await;
+        if new_status != container_state.status {
+            self.set_status(id, new_status).await?;
+        }
+
+        Ok(new_status)
+    }
+
+    /// Sync the status of every managed container, inspecting at most
+    /// `parallel` containers at a time
+    ///
+    /// All inspections finish before any state is written; changed statuses
+    /// are then applied under a single write lock and saved once, so
+    /// concurrent probes never race on the state file.
+    pub async fn sync_statuses(
+        &self,
+        parallel: usize,
+    ) -> Result<Vec<(String, DevcContainerStatus)>> {
+        use futures::stream::{self, StreamExt};
+
+        let containers = self.list().await?;
+        let probed: Vec<(String, DevcContainerStatus, DevcContainerStatus)> =
+            stream::iter(containers)
+                .map(|cs| async move {
+                    let new_status = self.probe_status(&cs).await;
+                    (cs.id, cs.status, new_status)
+                })
+                .buffer_unordered(parallel.max(1))
+                .collect()
+                .await;
+
+        let mut changed = false;
+        {
+            let mut state = self.state.write().await;
+            for (id, old_status, new_status) in &probed {
+                if new_status != old_status {
+                    if let Some(cs) = state.get_mut(id) {
+                        cs.set_status(*new_status);
+                        changed = true;
+                    }
+                }
+            }
+        }
+        if changed {
+            self.save_state().await?;
+        }
+
+        Ok(probed
+            .into_iter()
+            .map(|(id, _, status)| (id, status))
+            .collect())
+    }
+
+    /// Inspect a container and map its runtime status to a devc status,
+    /// without touching state
+    async fn probe_status(&self, container_state: &ContainerState) -> DevcContainerStatus {
         // Look up the provider matching the container's own type.
         // Fall back to current status if the provider isn't available.
-        let provider = match self.require_container_provider(&container_state) {
+        let provider = match self.require_container_provider(container_state) {
             Ok(p) => p,
-            Err(_) => return Ok(container_state.status),
+            Err(_) => return container_state.status,
         };
 
-        let new_status = if let Some(ref container_id) = container_state.container_id {
+        if let Some(ref container_id) = container_state.container_id {
             match provider.inspect(&ContainerId::new(container_id)).await {
                 Ok(details) => match details.status {
                     ContainerStatus::Running => DevcContainerStatus::Running,
                     ContainerStatus::Exited | ContainerStatus::Dead => DevcContainerStatus::Stopped,
-                    ContainerStatus::Created | ContainerStatus::Paused => {
-                        DevcContainerStatus::Created
-                    }
+                    ContainerStatus::Paused => DevcContainerStatus::Paused,
+                    ContainerStatus::Created => DevcContainerStatus::Created,
                     _ => container_state.status,
                 },
                 Err(_) => {
@@ -1485,13 +2341,7 @@ fi
             }
         } else {
             container_state.status
-        };
-
-        if new_status != container_state.status {
-            self.set_status(id, new_status).await?;
         }
-
-        Ok(new_status)
     }
 
     /// Get container logs
@@ -1499,8 +2349,47 @@ fi
     /// Returns logs as a vector of lines. If tail is specified, only returns
     /// that many lines from the end.
     pub async fn logs(&self, id: &str, tail: Option<u64>) -> Result<Vec<String>> {
-        use tokio::io::AsyncBufReadExt;
+        self.logs_with_timestamps(id, tail, false).await
+    }
+
+    /// Get container logs, optionally prefixing each line with the runtime's
+    /// RFC 3339 timestamp
+    pub async fn logs_with_timestamps(
+        &self,
+        id: &str,
+        tail: Option<u64>,
+        timestamps: bool,
+    ) -> Result<Vec<String>> {
+        let config = LogConfig {
+            timestamps,
+            ..log_config(tail, false)
+        };
+        let log_stream = self.open_log_stream(id, &config).await?;
+        read_log_lines(log_stream).await
+    }
+
+    /// Open a container's log stream, optionally following new output
+    pub async fn log_stream(&self, id: &str, tail: Option<u64>, follow: bool) -> Result<LogStream> {
+        self.open_log_stream(id, &log_config(tail, follow)).await
+    }
+
+    /// Open a container's log stream with timestamped lines and stderr
+    /// delivered separately, for turning into [`devc_provider::LogLine`]s
+    pub async fn structured_log_stream(
+        &self,
+        id: &str,
+        tail: Option<u64>,
+        follow: bool,
+    ) -> Result<LogStream> {
+        let config = LogConfig {
+            timestamps: true,
+            demux: true,
+            ..log_config(tail, follow)
+        };
+        self.open_log_stream(id, &config).await
+    }
 
+    async fn open_log_stream(&self, id: &str, config: &LogConfig) -> Result<LogStream> {
         let container_state = {
             let state = self.state.read().await;
             state
@@ -1510,36 +2399,87 @@ fi
         };
 
         let provider = self.require_container_provider(&container_state)?;
+        let container_id = log_container_id(&container_state)?;
 
-        let container_id = container_state
-            .container_id
-            .as_ref()
-            .ok_or_else(|| CoreError::InvalidState("Container has no container ID".to_string()))?;
+        Ok(provider
+            .logs(&ContainerId::new(container_id), config)
+            .await?)
+    }
 
-        let config = LogConfig {
-            follow: false,
-            stdout: true,
-            stderr: true,
-            tail,
-            timestamps: false,
-            since: None,
-            until: None,
+    /// Logs of a container that may have exited, with its runtime details so
+    /// the caller can report how it ended.
+    ///
+    /// Fails with a clear message when the runtime container was removed,
+    /// since its logs went with it.
+    pub async fn previous_logs(
+        &self,
+        id: &str,
+        tail: Option<u64>,
+    ) -> Result<(ContainerDetails, Vec<String>)> {
+        let container_state = {
+            let state = self.state.read().await;
+            state
+                .get(id)
+                .cloned()
+                .ok_or_else(|| CoreError::ContainerNotFound(id.to_string()))?
+        };
+        let provider = self.require_container_provider(&container_state)?;
+        let container_id = ContainerId::new(log_container_id(&container_state)?);
+
+        let details = match provider.inspect(&container_id).await {
+            Ok(details) => details,
+            Err(ProviderError::ContainerNotFound(_)) => {
+                return Err(CoreError::InvalidState(format!(
+                    "the container for {} was removed; no logs are available",
+                    container_state.name
+                )));
+            }
+            Err(e) => return Err(e.into()),
         };
+        let lines = Self::read_logs(provider, &container_id, tail).await?;
+        Ok((details, lines))
+    }
 
+    /// Read a container's logs to completion as lines
+    pub(crate) async fn read_logs(
+        provider: &dyn ContainerProvider,
+        container_id: &ContainerId,
+        tail: Option<u64>,
+    ) -> Result<Vec<String>> {
         let log_stream = provider
-            .logs(&ContainerId::new(container_id), &config)
+            .logs(container_id, &log_config(tail, false))
             .await?;
+        read_log_lines(log_stream).await
+    }
 
-        // Read all lines from the stream
-        let reader = tokio::io::BufReader::new(log_stream.stream);
-        let mut lines = reader.lines();
-        let mut result = Vec::new();
-
-        while let Some(line) = lines.next_line().await? {
-            result.push(line);
+    /// Disk usage of each managed container that exists, keyed by state ID
+    ///
+    /// Queries each provider once; containers the runtime doesn't report are
+    /// left out.
+    pub async fn disk_usage(&self) -> Result<HashMap<String, ContainerDiskUsage>> {
+        let states = self.list().await?;
+        let mut usage = HashMap::new();
+        for pt in states.iter().map(|cs| cs.provider).collect::<HashSet<_>>() {
+            // Containers on a provider that isn't connected just get no usage
+            let Some(provider) = self.providers.get(&pt) else {
+                tracing::warn!("{} provider not available; skipping its disk usage", pt);
+                continue;
+            };
+            let reported = provider.disk_usage().await?;
+            for cs in states.iter().filter(|cs| cs.provider == pt) {
+                let Some(container_id) = cs.container_id.as_deref() else {
+                    continue;
+                };
+                // `system df` prints short IDs
+                if let Some(u) = reported.iter().find(|u| {
+                    !u.id.0.is_empty()
+                        && (container_id.starts_with(&u.id.0) || u.id.0.starts_with(container_id))
+                }) {
+                    usage.insert(cs.id.clone(), u.clone());
+                }
+            }
         }
-
-        Ok(result)
+        Ok(usage)
     }
 
     /// Helper to set container status
@@ -1547,7 +2487,7 @@ fi
         {
             let mut state = self.state.write().await;
             if let Some(cs) = state.get_mut(id) {
-                cs.status = status;
+                cs.set_status(status);
             }
         }
         self.save_state().await?;
@@ -1562,7 +2502,7 @@ fi
         &self,
         state: &ContainerState,
     ) -> Result<devc_config::DevContainerConfig> {
-        let container = self.load_container(&state.config_path)?;
+        let container = self.load_container_for_state(state)?;
         Ok(container.devcontainer)
     }
 }
@@ -1578,6 +2518,53 @@ pub(crate) fn compose_file_strs(files: &[std::path::PathBuf]) -> Vec<String> {
         .collect()
 }
 
+/// Runtime container ID to read logs from, or why there are none
+fn log_container_id(container_state: &ContainerState) -> Result<&str> {
+    container_state.container_id.as_deref().ok_or_else(|| {
+        CoreError::InvalidState(format!(
+            "{} has no container (removed or never created); no logs are available",
+            container_state.name
+        ))
+    })
+}
+
+/// Log options for reading both output streams
+fn log_config(tail: Option<u64>, follow: bool) -> LogConfig {
+    LogConfig {
+        follow,
+        stdout: true,
+        stderr: true,
+        tail,
+        timestamps: false,
+        since: None,
+        until: None,
+        demux: false,
+    }
+}
+
+/// Normalize an image's configured `USER` to a user name.
+///
+/// Drops any `:group` suffix; root is ignored since it is already the default.
+fn image_user_name(user: &str) -> Option<String> {
+    let name = user.split(':').next().unwrap_or_default().trim();
+    match name {
+        "" | "root" | "0" => None,
+        _ => Some(name.to_string()),
+    }
+}
+
+/// Read a finite log stream to completion as lines
+async fn read_log_lines(log_stream: LogStream) -> Result<Vec<String>> {
+    use tokio::io::AsyncBufReadExt;
+
+    let mut lines = tokio::io::BufReader::new(log_stream.stream).lines();
+    let mut result = Vec::new();
+    while let Some(line) = lines.next_line().await? {
+        result.push(line);
+    }
+    Ok(result)
+}
+
 /// Extract merged feature properties from container state metadata.
 pub(crate) fn get_feature_properties(state: &ContainerState) -> features::MergedFeatureProperties {
     state
@@ -1609,6 +2596,25 @@ pub(crate) fn send_progress(progress: Option<&mpsc::UnboundedSender<String>>, ms
     }
 }
 
+/// Progress message sent once the lifecycle phase named by `waitFor` has
+/// finished: the container is usable while the remaining phases run
+pub const READY_FINALIZING_PROGRESS: &str = "Container ready, finalizing...";
+
+/// How many lines `devc logs` and the TUI logs view fetch unless asked for
+/// more; full history can be huge for long-running containers
+pub const DEFAULT_LOG_TAIL: u64 = 200;
+
+/// Send [`READY_FINALIZING_PROGRESS`] if `phase` is the one `waitFor` names
+pub(crate) fn send_ready_after(
+    progress: Option<&mpsc::UnboundedSender<String>>,
+    wait_for: Option<&str>,
+    phase: &str,
+) {
+    if wait_for == Some(phase) {
+        send_progress(progress, READY_FINALIZING_PROGRESS);
+    }
+}
+
 pub(crate) fn send_stage(stage: Option<&mpsc::UnboundedSender<BuildStage>>, value: BuildStage) {
     if let Some(tx) = stage {
         let _ = tx.send(value);
@@ -1619,7 +2625,9 @@ pub(crate) fn send_stage(stage: Option<&mpsc::UnboundedSender<BuildStage>>, valu
 mod tests {
     use super::*;
     use crate::test_support::*;
-    use devc_provider::{ContainerStatus, ProviderError, ProviderType};
+    use devc_provider::{
+        Capability, ComposeServiceInfo, ContainerStatus, ImageId, ProviderError, ProviderType,
+    };
 
     /// Create a test workspace with a devcontainer.json that uses an image
     fn create_test_workspace() -> tempfile::TempDir {
@@ -1831,6 +2839,180 @@ mod tests {
         assert!(recorded.iter().any(|c| matches!(c, MockCall::Pull { .. })));
     }
 
+    /// capAdd recorded in a container's feature properties
+    async fn cap_add(mgr: &ContainerManager, id: &str) -> Vec<String> {
+        let cs = mgr.get(id).await.unwrap().unwrap();
+        get_feature_properties(&cs).cap_add
+    }
+
+    #[tokio::test]
+    async fn test_build_reuses_feature_resolution_until_features_change() {
+        let workspace = create_test_workspace();
+        let devcontainer_dir = workspace.path().join(".devcontainer");
+        let feature_dir = devcontainer_dir.join("my-feature");
+        std::fs::create_dir_all(&feature_dir).unwrap();
+        std::fs::write(feature_dir.join("install.sh"), "#!/bin/sh\necho ok").unwrap();
+        let write_feature = |cap: &str| {
+            std::fs::write(
+                feature_dir.join("devcontainer-feature.json"),
+                format!(r#"{{"id": "my-feature", "capAdd": ["{}"]}}"#, cap),
+            )
+            .unwrap();
+        };
+        let write_config = |features: &str| {
+            std::fs::write(
+                devcontainer_dir.join("devcontainer.json"),
+                format!(r#"{{"image": "ubuntu:22.04", "features": {}}}"#, features),
+            )
+            .unwrap();
+        };
+        write_feature("SYS_PTRACE");
+        write_config(r#"{"./my-feature": {}}"#);
+
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Configured,
+            None,
+            None,
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+        let mgr = test_manager_with_state(MockProvider::new(ProviderType::Docker), state);
+
+        mgr.build(&id).await.unwrap();
+        assert_eq!(cap_add(&mgr, &id).await, vec!["SYS_PTRACE".to_string()]);
+
+        // Same features section and feature files: the recorded resolution is reused
+        write_config(r#"{"./my-feature": {}}"#);
+        mgr.build(&id).await.unwrap();
+        assert_eq!(cap_add(&mgr, &id).await, vec!["SYS_PTRACE".to_string()]);
+
+        // Editing the local feature invalidates it
+        write_feature("NET_ADMIN");
+        mgr.build(&id).await.unwrap();
+        assert_eq!(cap_add(&mgr, &id).await, vec!["NET_ADMIN".to_string()]);
+
+        // So does a changed features section
+        write_feature("SYS_ADMIN");
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        let hash_before = cs.metadata["features_hash"].clone();
+        write_config(r#"{"./my-feature": {"version": "2"}}"#);
+        mgr.build(&id).await.unwrap();
+        assert_eq!(cap_add(&mgr, &id).await, vec!["SYS_ADMIN".to_string()]);
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert_ne!(cs.metadata["features_hash"], hash_before);
+    }
+
+    #[tokio::test]
+    async fn test_build_strict_config_rejects_unknown_keys() {
+        let workspace = create_test_workspace();
+        std::fs::write(
+            workspace.path().join(".devcontainer/devcontainer.json"),
+            "{\n  \"image\": \"ubuntu:22.04\",\n  \"postCreateCommmand\": \"make\"\n}",
+        )
+        .unwrap();
+        let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
+
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Configured,
+            None,
+            None,
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mut mgr = test_manager_with_state(mock, state);
+        // Lenient by default: the typo is only a warning
+        mgr.build(&id).await.unwrap();
+
+        mgr.set_strict_config(true);
+        let err = mgr.build(&id).await.unwrap_err();
+        assert!(
+            matches!(
+                err,
+                CoreError::Config(devc_config::ConfigError::Schema { .. })
+            ),
+            "{:?}",
+            err
+        );
+        assert!(err
+            .to_string()
+            .contains("3:3: unknown property \"postCreateCommmand\""));
+        let pulls = calls
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|c| matches!(c, MockCall::Pull { .. }))
+            .count();
+        assert_eq!(pulls, 1, "strict failure should happen before building");
+    }
+
+    #[tokio::test]
+    async fn test_build_pull_policy_missing_uses_local_image() {
+        let workspace = create_test_workspace();
+        let mock = MockProvider::new(ProviderType::Docker);
+        *mock.local_image_result.lock().unwrap() = Ok(Some(ImageId::new("sha256:local")));
+        let calls = mock.calls.clone();
+
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Configured,
+            None,
+            None,
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mut mgr = test_manager_with_state(mock, state);
+        let mut config = mgr.global_config().clone();
+        config.defaults.pull_policy = Some(devc_config::PullPolicy::Missing);
+        mgr.update_global_config(config);
+
+        assert_eq!(mgr.build(&id).await.unwrap(), "sha256:local");
+        let recorded = calls.lock().unwrap();
+        assert!(recorded
+            .iter()
+            .any(|c| matches!(c, MockCall::LocalImage { .. })));
+        assert!(!recorded.iter().any(|c| matches!(c, MockCall::Pull { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_build_pull_policy_never_fails_without_local_image() {
+        let workspace = create_test_workspace();
+        let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
+
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Configured,
+            None,
+            None,
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mut mgr = test_manager_with_state(mock, state);
+        let mut config = mgr.global_config().clone();
+        config.defaults.pull_policy = Some(devc_config::PullPolicy::Never);
+        mgr.update_global_config(config);
+
+        let err = mgr.build(&id).await.unwrap_err();
+        assert!(matches!(err, CoreError::ImageNotLocal(_)), "got {}", err);
+        assert!(!calls
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|c| matches!(c, MockCall::Pull { .. })));
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert_eq!(cs.status, DevcContainerStatus::Failed);
+    }
+
     #[tokio::test]
     async fn test_build_sets_failed_on_error() {
         let workspace = create_test_workspace();
@@ -1959,332 +3141,568 @@ mod tests {
         assert!(!recorded.iter().any(|c| matches!(c, MockCall::Pull { .. })));
     }
 
-    // ==================== Create ====================
-
     #[tokio::test]
-    async fn test_create_requires_image_id() {
+    async fn test_build_arg_overrides_take_precedence() {
         let workspace = create_test_workspace();
-        let mock = MockProvider::new(ProviderType::Docker);
-
-        let mut state = StateStore::new();
-        let cs = make_container_state(
-            workspace.path(),
-            DevcContainerStatus::Configured,
-            None, // no image_id
-            None,
-        );
-        let id = cs.id.clone();
-        state.add(cs);
-
-        let mgr = test_manager_with_state(mock, state);
-        let result = mgr.create(&id).await;
-        assert!(result.is_err());
-        let err_msg = format!("{}", result.unwrap_err());
-        assert!(err_msg.contains("not built yet"));
-    }
+        std::fs::write(
+            workspace.path().join(".devcontainer/devcontainer.json"),
+            r#"{"build": {"dockerfile": "Dockerfile", "args": {"VERSION": "1", "DISTRO": "jammy"}}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            workspace.path().join(".devcontainer/Dockerfile"),
+            "FROM ubuntu:22.04\n",
+        )
+        .unwrap();
 
-    #[tokio::test]
-    async fn test_create_sets_container_id() {
-        let workspace = create_test_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
 
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Built,
-            Some("sha256:image123"),
+            DevcContainerStatus::Configured,
+            None,
             None,
         );
         let id = cs.id.clone();
         state.add(cs);
 
-        let mgr = test_manager_with_state(mock, state);
-        let container_id = mgr.create(&id).await.unwrap();
-        assert_eq!(container_id.0, "mock_container_id");
+        let mut mgr = test_manager_with_state(mock, state);
+        mgr.set_build_arg_overrides(HashMap::from([
+            ("VERSION".to_string(), "2".to_string()),
+            ("TOKEN".to_string(), "abc".to_string()),
+        ]));
+        mgr.build(&id).await.unwrap();
 
-        let cs = mgr.get(&id).await.unwrap().unwrap();
-        assert_eq!(cs.container_id, Some("mock_container_id".to_string()));
-        assert_eq!(cs.status, DevcContainerStatus::Created);
+        let recorded = calls.lock().unwrap();
+        let build_args = recorded
+            .iter()
+            .find_map(|c| match c {
+                MockCall::Build { build_args, .. } => Some(build_args.clone()),
+                _ => None,
+            })
+            .expect("build should have been called");
+        assert_eq!(
+            build_args,
+            HashMap::from([
+                ("VERSION".to_string(), "2".to_string()),
+                ("DISTRO".to_string(), "jammy".to_string()),
+                ("TOKEN".to_string(), "abc".to_string()),
+            ])
+        );
     }
 
     #[tokio::test]
-    async fn test_create_cleans_orphan() {
+    async fn test_build_target_flag_overrides_config() {
         let workspace = create_test_workspace();
+        std::fs::write(
+            workspace.path().join(".devcontainer/devcontainer.json"),
+            r#"{"build": {"dockerfile": "Dockerfile", "target": "dev"}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            workspace.path().join(".devcontainer/Dockerfile"),
+            "FROM ubuntu:22.04 AS dev\nFROM dev AS release\n",
+        )
+        .unwrap();
+
         let mock = MockProvider::new(ProviderType::Docker);
         let calls = mock.calls.clone();
 
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Built,
-            Some("sha256:image123"),
+            DevcContainerStatus::Configured,
+            None,
             None,
         );
         let id = cs.id.clone();
         state.add(cs);
 
-        let mgr = test_manager_with_state(mock, state);
-        mgr.create(&id).await.unwrap();
+        let mut mgr = test_manager_with_state(mock, state);
+        let built_target = || {
+            calls
+                .lock()
+                .unwrap()
+                .iter()
+                .rev()
+                .find_map(|c| match c {
+                    MockCall::Build { target, .. } => Some(target.clone()),
+                    _ => None,
+                })
+                .expect("build should have been called")
+        };
 
-        // Should have called remove_by_name before create
-        let recorded = calls.lock().unwrap();
-        assert!(recorded
-            .iter()
-            .any(|c| matches!(c, MockCall::RemoveByName { .. })));
-    }
+        mgr.build(&id).await.unwrap();
+        assert_eq!(built_target(), Some("dev".to_string()));
 
-    // ==================== Start / Stop ====================
+        mgr.set_target_override(Some("release".to_string()));
+        mgr.build(&id).await.unwrap();
+        assert_eq!(built_target(), Some("release".to_string()));
+    }
 
     #[tokio::test]
-    async fn test_start_sets_running() {
+    async fn test_build_warns_on_large_context_without_blocking() {
         let workspace = create_test_workspace();
+        let devcontainer = workspace.path().join(".devcontainer");
+        std::fs::write(
+            devcontainer.join("devcontainer.json"),
+            r#"{"build": {"dockerfile": "Dockerfile"}}"#,
+        )
+        .unwrap();
+        std::fs::write(devcontainer.join("Dockerfile"), "FROM ubuntu:22.04\n").unwrap();
+        std::fs::write(devcontainer.join("data.bin"), vec![0u8; 2 * 1024 * 1024]).unwrap();
+
         let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
 
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Created,
-            Some("sha256:img"),
-            Some("container123"),
+            DevcContainerStatus::Configured,
+            None,
+            None,
         );
         let id = cs.id.clone();
         state.add(cs);
 
-        let mgr = test_manager_with_state(mock, state);
-        mgr.start(&id).await.unwrap();
+        let mut config = GlobalConfig::default();
+        config.defaults.build_context_warn_mb = Some(1);
+        let mgr = ContainerManager::new_for_testing(Box::new(mock), config, state);
+        async fn build_output(mgr: &ContainerManager, id: &str) -> Vec<String> {
+            let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+            mgr.build_with_progress(id, false, tx).await.unwrap();
+            let mut lines = Vec::new();
+            while let Some(line) = rx.recv().await {
+                lines.push(line);
+            }
+            lines
+        }
 
-        let cs = mgr.get(&id).await.unwrap().unwrap();
-        assert_eq!(cs.status, DevcContainerStatus::Running);
+        let lines = build_output(&mgr, &id).await;
+        assert!(
+            lines
+                .iter()
+                .any(|l| l.contains("build context") && l.contains("add")),
+            "expected a context size warning, got: {:?}",
+            lines
+        );
+        assert!(calls
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|c| matches!(c, MockCall::BuildWithProgress { .. })));
+
+        std::fs::write(devcontainer.join(".dockerignore"), "*.bin\n").unwrap();
+        let lines = build_output(&mgr, &id).await;
+        assert!(
+            !lines.iter().any(|l| l.contains("build context")),
+            "excluded files should not count, got: {:?}",
+            lines
+        );
     }
 
     #[tokio::test]
-    async fn test_start_idempotent_when_running() {
+    async fn test_remove_previous_image_after_rebuild() {
         let workspace = create_test_workspace();
+        std::fs::write(
+            workspace.path().join(".devcontainer/devcontainer.json"),
+            r#"{"build": {"dockerfile": "Dockerfile"}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            workspace.path().join(".devcontainer/Dockerfile"),
+            "FROM ubuntu:22.04\n",
+        )
+        .unwrap();
+
         let mock = MockProvider::new(ProviderType::Docker);
         let calls = mock.calls.clone();
+        let build_result = mock.build_result.clone();
+        *build_result.lock().unwrap() = Ok(ImageId::new("sha256:old"));
 
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Running, // already running
-            Some("sha256:img"),
-            Some("container123"),
+            DevcContainerStatus::Configured,
+            None,
+            None,
         );
         let id = cs.id.clone();
         state.add(cs);
+        let mut other = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Built,
+            Some("sha256:old"),
+            None,
+        );
+        other.name = "other".to_string();
+        let other_id = other.id.clone();
+        state.add(other);
 
         let mgr = test_manager_with_state(mock, state);
-        // start() should succeed when already running (idempotent)
-        mgr.start(&id).await.unwrap();
+        mgr.build(&id).await.unwrap();
+        // Rebuilding to the same image records nothing
+        mgr.build(&id).await.unwrap();
+        assert!(mgr.remove_previous_image(&id).await.is_err());
 
-        // Should NOT have called provider.start() since container is already running
-        let recorded = calls.lock().unwrap();
-        assert!(!recorded.iter().any(|c| matches!(c, MockCall::Start { .. })));
+        *build_result.lock().unwrap() = Ok(ImageId::new("sha256:new"));
+        mgr.build(&id).await.unwrap();
+        assert_eq!(
+            mgr.get(&id).await.unwrap().unwrap().previous_image_id(),
+            Some("sha256:old")
+        );
+
+        let err = mgr.remove_previous_image(&id).await.unwrap_err();
+        assert!(err.to_string().contains("still used by other"), "{}", err);
+        assert!(!calls
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|c| matches!(c, MockCall::RemoveImage { .. })));
+
+        mgr.forget(&other_id).await.unwrap();
+        assert_eq!(mgr.remove_previous_image(&id).await.unwrap(), "sha256:old");
+        assert!(calls.lock().unwrap().contains(&MockCall::RemoveImage {
+            id: "sha256:old".to_string(),
+            force: false,
+        }));
+        assert_eq!(
+            mgr.get(&id).await.unwrap().unwrap().previous_image_id(),
+            None
+        );
     }
 
+    // ==================== Create ====================
+
     #[tokio::test]
-    async fn test_start_invalid_state_fails() {
+    async fn test_create_requires_image_id() {
         let workspace = create_test_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
 
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Building, // can't start from Building
-            Some("sha256:img"),
-            Some("container123"),
+            DevcContainerStatus::Configured,
+            None, // no image_id
+            None,
         );
         let id = cs.id.clone();
         state.add(cs);
 
         let mgr = test_manager_with_state(mock, state);
-        let result = mgr.start(&id).await;
+        let result = mgr.create(&id).await;
         assert!(result.is_err());
         let err_msg = format!("{}", result.unwrap_err());
-        assert!(err_msg.contains("cannot be started"));
+        assert!(err_msg.contains("not built yet"));
     }
 
     #[tokio::test]
-    async fn test_start_runs_post_start() {
+    async fn test_create_sets_container_id() {
         let workspace = create_test_workspace();
-        // Add a postStartCommand
-        std::fs::write(
-            workspace.path().join(".devcontainer/devcontainer.json"),
-            r#"{"image": "ubuntu:22.04", "postStartCommand": "echo hello"}"#,
-        )
-        .unwrap();
-
         let mock = MockProvider::new(ProviderType::Docker);
-        let calls = mock.calls.clone();
 
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Stopped,
-            Some("sha256:img"),
-            Some("container123"),
+            DevcContainerStatus::Built,
+            Some("sha256:image123"),
+            None,
         );
         let id = cs.id.clone();
         state.add(cs);
 
         let mgr = test_manager_with_state(mock, state);
-        mgr.start(&id).await.unwrap();
+        let container_id = mgr.create(&id).await.unwrap();
+        assert_eq!(container_id.0, "mock_container_id");
 
-        // Should have called exec for postStartCommand
-        let recorded = calls.lock().unwrap();
-        assert!(recorded.iter().any(|c| matches!(c, MockCall::Exec { .. })));
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert_eq!(cs.container_id, Some("mock_container_id".to_string()));
+        assert_eq!(cs.status, DevcContainerStatus::Created);
     }
 
     #[tokio::test]
-    async fn test_stop_sets_stopped() {
-        let workspace = create_test_workspace();
+    async fn test_create_passes_gpus_only_when_available() {
+        for (available, expected) in [(true, Some("all".to_string())), (false, None)] {
+            let workspace = create_test_workspace();
+            std::fs::write(
+                workspace.path().join(".devcontainer/devcontainer.json"),
+                r#"{"image": "ubuntu:22.04", "hostRequirements": {"gpu": true}}"#,
+            )
+            .unwrap();
+            let mock = MockProvider::new(ProviderType::Docker);
+            *mock.gpu_available.lock().unwrap() = available;
+            let calls = mock.calls.clone();
+
+            let mut state = StateStore::new();
+            let cs = make_container_state(
+                workspace.path(),
+                DevcContainerStatus::Built,
+                Some("sha256:image123"),
+                None,
+            );
+            let id = cs.id.clone();
+            state.add(cs);
+
+            let mgr = test_manager_with_state(mock, state);
+            mgr.create(&id).await.unwrap();
+
+            let calls = calls.lock().unwrap();
+            let gpus = calls.iter().find_map(|c| match c {
+                MockCall::Create { gpus, .. } => Some(gpus.clone()),
+                _ => None,
+            });
+            assert_eq!(gpus, Some(expected));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_detects_image_user_for_exec() {
+        let (workspace, _marker) = create_lifecycle_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
+        let mut details = mock_container_details("container123", ContainerStatus::Created);
+        details.user = Some("vscode:vscode".to_string());
+        *mock.inspect_result.lock().unwrap() = Ok(details);
+        let calls = mock.calls.clone();
 
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Running,
-            Some("sha256:img"),
-            Some("container123"),
+            DevcContainerStatus::Built,
+            Some("sha256:image123"),
+            None,
         );
         let id = cs.id.clone();
         state.add(cs);
 
-        let mgr = test_manager_with_state(mock, state);
-        mgr.stop(&id).await.unwrap();
+        let mgr = test_manager_no_creds(mock, state);
+        mgr.create(&id).await.unwrap();
+        {
+            let state = mgr.state.read().await;
+            let cs = state.get(&id).unwrap();
+            assert_eq!(
+                cs.metadata.get("image_user").map(String::as_str),
+                Some("vscode")
+            );
+        }
 
-        let cs = mgr.get(&id).await.unwrap().unwrap();
-        assert_eq!(cs.status, DevcContainerStatus::Stopped);
+        mgr.run_post_attach_command(&id).await.unwrap();
+
+        let recorded = calls.lock().unwrap();
+        let users: Vec<Option<String>> = recorded
+            .iter()
+            .filter_map(|c| match c {
+                MockCall::Exec { user, .. } => Some(user.clone()),
+                _ => None,
+            })
+            .collect();
+        assert!(!users.is_empty(), "postAttachCommand should have run");
+        assert!(
+            users.iter().all(|u| u.as_deref() == Some("vscode")),
+            "lifecycle commands should run as the image user; got {:?}",
+            users
+        );
     }
 
+    /// Recreating after the image's USER changed must not pass the stale
+    /// cached user to the runtime, and picks up the new one
     #[tokio::test]
-    async fn test_stop_invalid_state_fails() {
-        let workspace = create_test_workspace();
+    async fn test_create_redetects_image_user_after_rebuild() {
+        let (workspace, _marker) = create_lifecycle_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
+        let mut details = mock_container_details("container123", ContainerStatus::Created);
+        details.user = Some("newuser".to_string());
+        *mock.inspect_result.lock().unwrap() = Ok(details);
+        let calls = mock.calls.clone();
 
         let mut state = StateStore::new();
-        let cs = make_container_state(
+        let mut cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Stopped, // already stopped
-            Some("sha256:img"),
-            Some("container123"),
+            DevcContainerStatus::Built,
+            Some("sha256:image123"),
+            None,
         );
+        cs.metadata
+            .insert("image_user".to_string(), "olduser".to_string());
         let id = cs.id.clone();
         state.add(cs);
 
-        let mgr = test_manager_with_state(mock, state);
-        let result = mgr.stop(&id).await;
-        assert!(result.is_err());
-        let err_msg = format!("{}", result.unwrap_err());
-        assert!(err_msg.contains("cannot be stopped"));
-    }
+        let mgr = test_manager_no_creds(mock, state);
+        mgr.create(&id).await.unwrap();
 
-    // ==================== Remove ====================
+        let create_user = calls.lock().unwrap().iter().find_map(|c| match c {
+            MockCall::Create { user, .. } => Some(user.clone()),
+            _ => None,
+        });
+        assert_eq!(create_user, Some(None));
+        let state = mgr.state.read().await;
+        assert_eq!(
+            state
+                .get(&id)
+                .unwrap()
+                .metadata
+                .get("image_user")
+                .map(String::as_str),
+            Some("newuser")
+        );
+    }
 
     #[tokio::test]
-    async fn test_remove_from_state() {
+    async fn test_create_rejects_invalid_resource_limits() {
         let workspace = create_test_workspace();
+        std::fs::write(
+            workspace.path().join(".devcontainer/devcontainer.json"),
+            r#"{"image": "ubuntu:22.04", "resources": {"memory": "4 bananas"}}"#,
+        )
+        .unwrap();
         let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
 
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Stopped,
-            Some("sha256:img"),
-            Some("container123"),
+            DevcContainerStatus::Built,
+            Some("sha256:image123"),
+            None,
         );
         let id = cs.id.clone();
         state.add(cs);
 
-        let mgr = test_manager_with_state(mock, state);
-        mgr.remove(&id, false).await.unwrap();
-
-        let cs = mgr.get(&id).await.unwrap();
-        assert!(cs.is_none(), "Container should be removed from state");
+        let mgr = test_manager_no_creds(mock, state);
+        let err = mgr.create(&id).await.unwrap_err();
+        assert!(err.to_string().contains("resources.memory"), "{}", err);
+        assert!(!calls
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|c| matches!(c, MockCall::Create { .. })));
     }
 
     #[tokio::test]
-    async fn test_remove_force_running() {
-        let workspace = create_test_workspace();
+    async fn test_mount_overrides_apply_only_at_create() {
+        let (workspace, _marker) = create_lifecycle_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
 
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Running,
-            Some("sha256:img"),
-            Some("container123"),
+            DevcContainerStatus::Built,
+            Some("sha256:image123"),
+            None,
         );
         let id = cs.id.clone();
         state.add(cs);
 
-        let mgr = test_manager_with_state(mock, state);
-        // Force remove should work even on running containers
-        mgr.remove(&id, true).await.unwrap();
+        let mut mgr = test_manager_no_creds(mock, state);
+        mgr.set_mount_overrides(vec![devc_provider::MountConfig {
+            mount_type: devc_provider::MountType::Volume,
+            source: "scratch".to_string(),
+            target: "/scratch".to_string(),
+            read_only: false,
+        }]);
+        mgr.create(&id).await.unwrap();
 
-        let cs = mgr.get(&id).await.unwrap();
-        assert!(cs.is_none());
+        let mount_targets = calls
+            .lock()
+            .unwrap()
+            .iter()
+            .find_map(|c| match c {
+                MockCall::Create { mount_targets, .. } => Some(mount_targets.clone()),
+                _ => None,
+            })
+            .expect("create should have been called");
+        assert!(mount_targets.contains(&"/scratch".to_string()));
+
+        // The container now exists, so another up cannot add the mount
+        let err = mgr.up(&id).await.unwrap_err();
+        assert!(
+            err.to_string().contains("devc down"),
+            "unexpected error: {}",
+            err
+        );
     }
 
     #[tokio::test]
-    async fn test_remove_no_force_running_fails() {
-        let workspace = create_test_workspace();
+    async fn test_label_overrides_reach_create() {
+        let (workspace, _marker) = create_lifecycle_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
 
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Running,
-            Some("sha256:img"),
-            Some("container123"),
+            DevcContainerStatus::Built,
+            Some("sha256:image123"),
+            None,
         );
         let id = cs.id.clone();
         state.add(cs);
 
-        let mgr = test_manager_with_state(mock, state);
-        let result = mgr.remove(&id, false).await;
-        assert!(result.is_err());
-        let err_msg = format!("{}", result.unwrap_err());
-        assert!(err_msg.contains("cannot be removed"));
+        let mut mgr = test_manager_no_creds(mock, state);
+        mgr.set_label_overrides(HashMap::from([("team".to_string(), "infra".to_string())]));
+        mgr.create(&id).await.unwrap();
+
+        let user_labels = calls
+            .lock()
+            .unwrap()
+            .iter()
+            .find_map(|c| match c {
+                MockCall::Create { user_labels, .. } => Some(user_labels.clone()),
+                _ => None,
+            })
+            .expect("create should have been called");
+        assert_eq!(user_labels.get("team").map(String::as_str), Some("infra"));
+    }
+
+    #[test]
+    fn test_image_user_name() {
+        assert_eq!(image_user_name("vscode"), Some("vscode".to_string()));
+        assert_eq!(image_user_name("node:node"), Some("node".to_string()));
+        assert_eq!(image_user_name("1000"), Some("1000".to_string()));
+        assert_eq!(image_user_name("root"), None);
+        assert_eq!(image_user_name("0:0"), None);
+        assert_eq!(image_user_name(""), None);
     }
 
     #[tokio::test]
-    async fn test_remove_disconnected_removes_state() {
+    async fn test_create_cleans_orphan() {
         let workspace = create_test_workspace();
+        let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
+
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Stopped,
-            Some("sha256:img"),
-            Some("container123"),
+            DevcContainerStatus::Built,
+            Some("sha256:image123"),
+            None,
         );
         let id = cs.id.clone();
         state.add(cs);
 
-        let mgr = ContainerManager::disconnected_for_testing(
-            GlobalConfig::default(),
-            state,
-            "no provider".to_string(),
-        );
+        let mgr = test_manager_with_state(mock, state);
+        mgr.create(&id).await.unwrap();
 
-        // Should still remove from state even without provider
-        mgr.remove(&id, false).await.unwrap();
-        let cs = mgr.get(&id).await.unwrap();
-        assert!(cs.is_none());
+        // Should have called remove_by_name before create
+        let recorded = calls.lock().unwrap();
+        assert!(recorded
+            .iter()
+            .any(|c| matches!(c, MockCall::RemoveByName { .. })));
     }
 
-    // ==================== Down ====================
+    // ==================== Start / Stop ====================
 
     #[tokio::test]
-    async fn test_down_stops_and_removes() {
+    async fn test_start_sets_running() {
         let workspace = create_test_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
-        let calls = mock.calls.clone();
 
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Running,
+            DevcContainerStatus::Created,
             Some("sha256:img"),
             Some("container123"),
         );
@@ -2292,32 +3710,22 @@ mod tests {
         state.add(cs);
 
         let mgr = test_manager_with_state(mock, state);
-        mgr.down(&id).await.unwrap();
-
-        // Should have called stop + remove on provider
-        {
-            let recorded = calls.lock().unwrap();
-            assert!(recorded.iter().any(|c| matches!(c, MockCall::Stop { .. })));
-            assert!(recorded
-                .iter()
-                .any(|c| matches!(c, MockCall::Remove { force: true, .. })));
-        }
+        mgr.start(&id).await.unwrap();
 
-        // State should still exist but container_id cleared
         let cs = mgr.get(&id).await.unwrap().unwrap();
-        assert!(cs.container_id.is_none());
-        assert_eq!(cs.status, DevcContainerStatus::Built);
+        assert_eq!(cs.status, DevcContainerStatus::Running);
     }
 
     #[tokio::test]
-    async fn test_down_clears_ssh_metadata() {
+    async fn test_start_skips_ssh_daemon_in_exec_resize_mode() {
         let workspace = create_test_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
 
         let mut state = StateStore::new();
         let mut cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Running,
+            DevcContainerStatus::Created,
             Some("sha256:img"),
             Some("container123"),
         );
@@ -2326,22 +3734,31 @@ mod tests {
         let id = cs.id.clone();
         state.add(cs);
 
-        let mgr = test_manager_with_state(mock, state);
-        mgr.down(&id).await.unwrap();
+        let mut mgr = test_manager_with_state(mock, state);
+        let mut config = mgr.global_config().clone();
+        config.defaults.ssh_enabled = Some(true);
+        config.defaults.resize_mode = Some(ResizeMode::Exec);
+        mgr.update_global_config(config);
+        mgr.start(&id).await.unwrap();
 
-        let cs = mgr.get(&id).await.unwrap().unwrap();
-        assert!(!cs.metadata.contains_key("ssh_available"));
+        let recorded = calls.lock().unwrap();
+        assert!(!recorded.iter().any(|c| matches!(
+            c,
+            MockCall::Exec { cmd, .. }
+                if cmd.last().map(String::as_str) == Some(crate::ssh::DROPBEAR_START_SCRIPT)
+        )));
     }
 
     #[tokio::test]
-    async fn test_down_sets_built_status() {
+    async fn test_start_idempotent_when_running() {
         let workspace = create_test_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
 
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Running,
+            DevcContainerStatus::Running, // already running
             Some("sha256:img"),
             Some("container123"),
         );
@@ -2349,66 +3766,71 @@ mod tests {
         state.add(cs);
 
         let mgr = test_manager_with_state(mock, state);
-        mgr.down(&id).await.unwrap();
+        // start() should succeed when already running (idempotent)
+        mgr.start(&id).await.unwrap();
 
-        let cs = mgr.get(&id).await.unwrap().unwrap();
-        assert_eq!(cs.status, DevcContainerStatus::Built);
+        // Should NOT have called provider.start() since container is already running
+        let recorded = calls.lock().unwrap();
+        assert!(!recorded.iter().any(|c| matches!(c, MockCall::Start { .. })));
     }
 
     #[tokio::test]
-    async fn test_down_no_image_sets_configured() {
+    async fn test_start_invalid_state_fails() {
         let workspace = create_test_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
 
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Running,
-            None, // no image
+            DevcContainerStatus::Building, // can't start from Building
+            Some("sha256:img"),
             Some("container123"),
         );
         let id = cs.id.clone();
         state.add(cs);
 
         let mgr = test_manager_with_state(mock, state);
-        mgr.down(&id).await.unwrap();
-
-        let cs = mgr.get(&id).await.unwrap().unwrap();
-        assert_eq!(cs.status, DevcContainerStatus::Configured);
+        let result = mgr.start(&id).await;
+        assert!(result.is_err());
+        let err_msg = format!("{}", result.unwrap_err());
+        assert!(err_msg.contains("cannot be started"));
     }
 
-    // ==================== Rebuild ====================
-
     #[tokio::test]
-    async fn test_rebuild_disconnected_fails() {
+    async fn test_start_runs_post_start() {
         let workspace = create_test_workspace();
+        // Add a postStartCommand
+        std::fs::write(
+            workspace.path().join(".devcontainer/devcontainer.json"),
+            r#"{"image": "ubuntu:22.04", "postStartCommand": "echo hello"}"#,
+        )
+        .unwrap();
+
+        let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
+
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Running,
+            DevcContainerStatus::Stopped,
             Some("sha256:img"),
             Some("container123"),
         );
         let id = cs.id.clone();
         state.add(cs);
 
-        let mgr = ContainerManager::disconnected_for_testing(
-            GlobalConfig::default(),
-            state,
-            "no provider".to_string(),
-        );
+        let mgr = test_manager_with_state(mock, state);
+        mgr.start(&id).await.unwrap();
 
-        let result = mgr.rebuild(&id, false).await;
-        assert!(result.is_err());
+        // Should have called exec for postStartCommand
+        let recorded = calls.lock().unwrap();
+        assert!(recorded.iter().any(|c| matches!(c, MockCall::Exec { .. })));
     }
 
-    // ==================== Sync Status ====================
-
     #[tokio::test]
-    async fn test_sync_running_stays_running() {
+    async fn test_stop_sets_stopped() {
         let workspace = create_test_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
-        // Default inspect returns Running
 
         let mut state = StateStore::new();
         let cs = make_container_state(
@@ -2421,23 +3843,21 @@ mod tests {
         state.add(cs);
 
         let mgr = test_manager_with_state(mock, state);
-        let status = mgr.sync_status(&id).await.unwrap();
-        assert_eq!(status, DevcContainerStatus::Running);
+        mgr.stop(&id).await.unwrap();
+
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert_eq!(cs.status, DevcContainerStatus::Stopped);
     }
 
     #[tokio::test]
-    async fn test_sync_running_to_stopped() {
+    async fn test_stop_invalid_state_fails() {
         let workspace = create_test_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
-        *mock.inspect_result.lock().unwrap() = Ok(mock_container_details(
-            "container123",
-            ContainerStatus::Exited,
-        ));
 
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Running,
+            DevcContainerStatus::Stopped, // already stopped
             Some("sha256:img"),
             Some("container123"),
         );
@@ -2445,20 +3865,51 @@ mod tests {
         state.add(cs);
 
         let mgr = test_manager_with_state(mock, state);
-        let status = mgr.sync_status(&id).await.unwrap();
-        assert_eq!(status, DevcContainerStatus::Stopped);
+        let result = mgr.stop(&id).await;
+        assert!(result.is_err());
+        let err_msg = format!("{}", result.unwrap_err());
+        assert!(err_msg.contains("cannot be stopped"));
     }
 
     #[tokio::test]
-    async fn test_sync_container_disappeared() {
+    async fn test_ensure_ssh_daemon_recreates_missing_host_key() {
+        let mgr = test_manager(MockProvider::new(ProviderType::Docker));
+        let mock = MockProvider::new(ProviderType::Docker);
+        *mock.exec_output.lock().unwrap() = "hostkey:generated\n".to_string();
+        let calls = mock.calls.clone();
+
+        mgr.ensure_ssh_daemon_running(&mock, &ContainerId::new("c1"))
+            .await
+            .unwrap();
+
+        let calls = calls.lock().unwrap();
+        let script = calls
+            .iter()
+            .find_map(|c| match c {
+                MockCall::Exec { cmd, user, .. } => {
+                    assert_eq!(user.as_deref(), Some("root"));
+                    cmd.last().cloned()
+                }
+                _ => None,
+            })
+            .expect("ssh daemon script should be exec'd");
+        // The key is created before dropbear is started with it
+        let keygen = script.find("dropbearkey").unwrap();
+        let start = script.find("/usr/sbin/dropbear").unwrap();
+        assert!(keygen < start);
+    }
+
+    // ==================== Remove ====================
+
+    #[tokio::test]
+    async fn test_remove_from_state() {
         let workspace = create_test_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
-        *mock.inspect_result.lock().unwrap() = Err(ProviderError::ContainerNotFound("gone".into()));
 
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Running,
+            DevcContainerStatus::Stopped,
             Some("sha256:img"),
             Some("container123"),
         );
@@ -2466,561 +3917,1677 @@ mod tests {
         state.add(cs);
 
         let mgr = test_manager_with_state(mock, state);
-        let status = mgr.sync_status(&id).await.unwrap();
-        // Container had an image, so should be Built
-        assert_eq!(status, DevcContainerStatus::Built);
+        mgr.remove(&id, false, false).await.unwrap();
+
+        let cs = mgr.get(&id).await.unwrap();
+        assert!(cs.is_none(), "Container should be removed from state");
     }
 
     #[tokio::test]
-    async fn test_sync_no_container_id_returns_current() {
+    async fn test_remove_force_running() {
         let workspace = create_test_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
+
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Built,
+            DevcContainerStatus::Running,
             Some("sha256:img"),
-            None, // no container_id → returns current status without inspecting
+            Some("container123"),
         );
         let id = cs.id.clone();
         state.add(cs);
 
         let mgr = test_manager_with_state(mock, state);
-        let status = mgr.sync_status(&id).await.unwrap();
-        assert_eq!(status, DevcContainerStatus::Built);
-    }
-
-    // ==================== List / Get ====================
+        // Force remove should work even on running containers
+        mgr.remove(&id, true, false).await.unwrap();
 
-    #[tokio::test]
-    async fn test_list_empty() {
-        let mock = MockProvider::new(ProviderType::Docker);
-        let mgr = test_manager(mock);
-        let list = mgr.list().await.unwrap();
-        assert!(list.is_empty());
+        let cs = mgr.get(&id).await.unwrap();
+        assert!(cs.is_none());
     }
 
     #[tokio::test]
-    async fn test_list_returns_all() {
+    async fn test_remove_keeps_image_unless_requested() {
         let workspace = create_test_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
 
         let mut state = StateStore::new();
-        let cs1 = make_container_state(
+        let kept = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Running,
-            Some("sha256:img"),
-            Some("c1"),
+            DevcContainerStatus::Stopped,
+            Some("sha256:kept"),
+            Some("container1"),
         );
-        state.add(cs1);
-
-        let workspace2 = create_test_workspace();
-        let cs2 = make_container_state(
-            workspace2.path(),
+        let mut removed = make_container_state(
+            workspace.path(),
             DevcContainerStatus::Stopped,
-            Some("sha256:img2"),
-            Some("c2"),
+            Some("sha256:img"),
+            Some("container2"),
         );
-        state.add(cs2);
-
+        removed.metadata.insert(
+            PREVIOUS_IMAGE_METADATA_KEY.to_string(),
+            "sha256:old".to_string(),
+        );
+        let mut sharing = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Stopped,
+            Some("sha256:img"),
+            Some("container3"),
+        );
+        sharing.name = "other".to_string();
+        let (kept_id, removed_id) = (kept.id.clone(), removed.id.clone());
+        state.add(kept);
+        state.add(removed);
+        state.add(sharing);
+
         let mgr = test_manager_with_state(mock, state);
-        let list = mgr.list().await.unwrap();
-        assert_eq!(list.len(), 2);
+        mgr.remove(&kept_id, false, false).await.unwrap();
+        let removed_images = |calls: &[MockCall]| -> Vec<String> {
+            calls
+                .iter()
+                .filter_map(|c| match c {
+                    MockCall::RemoveImage { id, .. } => Some(id.clone()),
+                    _ => None,
+                })
+                .collect()
+        };
+        assert!(removed_images(&calls.lock().unwrap()).is_empty());
+
+        // The current image is shared, so only the previous one goes
+        mgr.remove(&removed_id, false, true).await.unwrap();
+        assert_eq!(removed_images(&calls.lock().unwrap()), ["sha256:old"]);
     }
 
     #[tokio::test]
-    async fn test_get_by_name() {
+    async fn test_remove_image_failure_only_warns() {
         let workspace = create_test_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
+        *mock.remove_image_result.lock().unwrap() =
+            Err(ProviderError::RuntimeError("image is in use".into()));
+        let calls = mock.calls.clone();
 
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Configured,
-            None,
-            None,
+            DevcContainerStatus::Running,
+            Some("sha256:img"),
+            Some("container1"),
         );
+        let id = cs.id.clone();
         state.add(cs);
 
         let mgr = test_manager_with_state(mock, state);
-        let found = mgr.get_by_name("test").await.unwrap();
-        assert!(found.is_some());
-
-        let not_found = mgr.get_by_name("nonexistent").await.unwrap();
-        assert!(not_found.is_none());
-    }
-
-    #[tokio::test]
-    async fn test_get_nonexistent() {
-        let mock = MockProvider::new(ProviderType::Docker);
-        let mgr = test_manager(mock);
-        let result = mgr.get("nonexistent-id").await.unwrap();
-        assert!(result.is_none());
+        mgr.remove(&id, true, true).await.unwrap();
+        assert!(mgr.get(&id).await.unwrap().is_none());
+        // A forced container removal still leaves the image removal unforced
+        assert!(calls.lock().unwrap().contains(&MockCall::RemoveImage {
+            id: "sha256:img".to_string(),
+            force: false,
+        }));
     }
 
-    // ==================== Compose ====================
-
     #[tokio::test]
-    async fn test_up_compose_calls_compose_up() {
+    async fn test_remove_no_force_running_fails() {
         let workspace = create_test_workspace();
-        // Write a compose-based devcontainer.json
-        std::fs::write(
-            workspace.path().join(".devcontainer/devcontainer.json"),
-            r#"{"dockerComposeFile": "docker-compose.yml", "service": "app", "workspaceFolder": "/workspace"}"#,
-        )
-        .unwrap();
-        // Create a dummy compose file (content doesn't matter for mock)
-        std::fs::write(
-            workspace.path().join(".devcontainer/docker-compose.yml"),
-            "version: '3'\nservices:\n  app:\n    image: ubuntu:22.04\n",
-        )
-        .unwrap();
-
         let mock = MockProvider::new(ProviderType::Docker);
-        let calls = mock.calls.clone();
-        *mock.compose_resolve_service_id_result.lock().unwrap() =
-            Ok(ContainerId::new("compose_container_123"));
 
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Configured,
-            None,
-            None,
+            DevcContainerStatus::Running,
+            Some("sha256:img"),
+            Some("container123"),
         );
         let id = cs.id.clone();
         state.add(cs);
 
         let mgr = test_manager_with_state(mock, state);
-        mgr.up(&id).await.unwrap();
-
-        // Verify compose_up was called
-        {
-            let recorded = calls.lock().unwrap();
-            assert!(recorded
-                .iter()
-                .any(|c| matches!(c, MockCall::ComposeUp { .. })));
-            assert!(recorded
-                .iter()
-                .any(|c| matches!(c, MockCall::ComposeResolveServiceId { .. })));
-        }
-
-        // Verify state was updated with compose metadata
-        let cs = mgr.get(&id).await.unwrap().unwrap();
-        assert_eq!(cs.container_id, Some("compose_container_123".to_string()));
-        assert!(cs.compose_project.as_ref().unwrap().starts_with("devc-"));
-        assert_eq!(cs.compose_service, Some("app".to_string()));
-        assert_eq!(cs.status, DevcContainerStatus::Running);
+        let result = mgr.remove(&id, false, false).await;
+        assert!(result.is_err());
+        let err_msg = format!("{}", result.unwrap_err());
+        assert!(err_msg.contains("cannot be removed"));
     }
 
     #[tokio::test]
-    async fn test_down_compose_calls_compose_down() {
+    async fn test_remove_disconnected_removes_state() {
         let workspace = create_test_workspace();
-        // Write a compose-based devcontainer.json
-        std::fs::write(
-            workspace.path().join(".devcontainer/devcontainer.json"),
-            r#"{"dockerComposeFile": "docker-compose.yml", "service": "app"}"#,
-        )
-        .unwrap();
-        std::fs::write(
-            workspace.path().join(".devcontainer/docker-compose.yml"),
-            "version: '3'\nservices:\n  app:\n    image: ubuntu:22.04\n",
-        )
-        .unwrap();
-
-        let mock = MockProvider::new(ProviderType::Docker);
-        let calls = mock.calls.clone();
-
         let mut state = StateStore::new();
-        let mut cs = make_container_state(
+        let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Running,
-            Some("compose"),
-            Some("compose_container_123"),
+            DevcContainerStatus::Stopped,
+            Some("sha256:img"),
+            Some("container123"),
         );
-        cs.compose_project = Some("devc-test".to_string());
-        cs.compose_service = Some("app".to_string());
         let id = cs.id.clone();
         state.add(cs);
 
-        let mgr = test_manager_with_state(mock, state);
-        mgr.down(&id).await.unwrap();
-
-        // Verify compose_down was called
-        {
-            let recorded = calls.lock().unwrap();
-            assert!(recorded
-                .iter()
-                .any(|c| matches!(c, MockCall::ComposeDown { .. })));
-            // Should NOT call individual stop/remove
-            assert!(!recorded.iter().any(|c| matches!(c, MockCall::Stop { .. })));
-            assert!(!recorded
-                .iter()
-                .any(|c| matches!(c, MockCall::Remove { .. })));
-        }
+        let mgr = ContainerManager::disconnected_for_testing(
+            GlobalConfig::default(),
+            state,
+            "no provider".to_string(),
+        );
 
-        // State should be cleaned up
-        let cs = mgr.get(&id).await.unwrap().unwrap();
-        assert!(cs.container_id.is_none());
-        assert!(cs.compose_project.is_none());
-        assert!(cs.compose_service.is_none());
+        // Should still remove from state even without provider
+        mgr.remove(&id, false, false).await.unwrap();
+        let cs = mgr.get(&id).await.unwrap();
+        assert!(cs.is_none());
     }
 
     #[tokio::test]
-    async fn test_down_non_compose_uses_stop_remove() {
+    async fn test_deleted_workspace_reports_workspace_missing() {
         let workspace = create_test_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
-        let calls = mock.calls.clone();
-
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Running,
+            DevcContainerStatus::Stopped,
             Some("sha256:img"),
             Some("container123"),
         );
         let id = cs.id.clone();
         state.add(cs);
+        std::fs::remove_dir_all(workspace.path()).unwrap();
 
         let mgr = test_manager_with_state(mock, state);
-        mgr.down(&id).await.unwrap();
-
-        // Should call stop + remove, NOT compose_down
-        let recorded = calls.lock().unwrap();
-        assert!(recorded.iter().any(|c| matches!(c, MockCall::Stop { .. })));
-        assert!(recorded
-            .iter()
-            .any(|c| matches!(c, MockCall::Remove { .. })));
-        assert!(!recorded
-            .iter()
-            .any(|c| matches!(c, MockCall::ComposeDown { .. })));
-    }
+        let err = mgr.build(&id).await.unwrap_err();
+        assert!(matches!(err, CoreError::WorkspaceMissing(_)));
+        assert!(err.to_string().contains("devc forget"));
 
-    // ==================== Compose Start / Stop ====================
-
-    /// Helper: create a compose workspace with devcontainer.json + docker-compose.yml.
-    fn create_compose_workspace() -> tempfile::TempDir {
-        create_compose_workspace_with_fields("")
+        // Removing the entry must not need the config
+        mgr.remove(&id, false, false).await.unwrap();
+        assert!(mgr.get(&id).await.unwrap().is_none());
     }
 
-    /// Like `create_compose_workspace` but appends `extra_devcontainer_fields` (e.g.
-    /// `, "onCreateCommand": "echo foo"`) inside the devcontainer.json object.
-    fn create_compose_workspace_with_fields(extra_devcontainer_fields: &str) -> tempfile::TempDir {
-        let tmp = tempfile::tempdir().unwrap();
-        let devcontainer_dir = tmp.path().join(".devcontainer");
-        std::fs::create_dir_all(&devcontainer_dir).unwrap();
-        let json = format!(
-            r#"{{"dockerComposeFile": "docker-compose.yml", "service": "app", "workspaceFolder": "/workspace"{}}}"#,
-            extra_devcontainer_fields,
-        );
-        std::fs::write(devcontainer_dir.join("devcontainer.json"), json).unwrap();
-        std::fs::write(
-            devcontainer_dir.join("docker-compose.yml"),
-            "version: '3'\nservices:\n  app:\n    image: ubuntu:22.04\n",
-        )
-        .unwrap();
-        tmp
-    }
+    // ==================== Down ====================
 
     #[tokio::test]
-    async fn test_compose_start_calls_compose_up_and_sets_container_id() {
-        let workspace = create_compose_workspace();
-
+    async fn test_down_stops_and_removes() {
+        let workspace = create_test_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
         let calls = mock.calls.clone();
-        *mock.compose_resolve_service_id_result.lock().unwrap() =
-            Ok(ContainerId::new("compose_start_abc"));
 
         let mut state = StateStore::new();
-        // Use Stopped status — can_start() requires Created or Stopped
-        let mut cs =
-            make_container_state(workspace.path(), DevcContainerStatus::Stopped, None, None);
-        cs.compose_project = Some("devc-test".to_string());
-        cs.compose_service = Some("app".to_string());
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            Some("sha256:img"),
+            Some("container123"),
+        );
         let id = cs.id.clone();
         state.add(cs);
 
         let mgr = test_manager_with_state(mock, state);
-        mgr.start(&id).await.unwrap();
+        mgr.down(&id).await.unwrap();
 
-        // Verify compose_up and compose service resolution were called
+        // Should have called stop + remove on provider
         {
             let recorded = calls.lock().unwrap();
+            assert!(recorded.iter().any(|c| matches!(c, MockCall::Stop { .. })));
             assert!(recorded
                 .iter()
-                .any(|c| matches!(c, MockCall::ComposeUp { .. })));
-            assert!(recorded
-                .iter()
-                .any(|c| matches!(c, MockCall::ComposeResolveServiceId { .. })));
+                .any(|c| matches!(c, MockCall::Remove { force: true, .. })));
         }
 
-        // Verify container_id was set from the matched service
+        // State should still exist but container_id cleared
         let cs = mgr.get(&id).await.unwrap().unwrap();
-        assert_eq!(cs.container_id, Some("compose_start_abc".to_string()));
-        assert_eq!(cs.status, DevcContainerStatus::Running);
+        assert!(cs.container_id.is_none());
+        assert_eq!(cs.status, DevcContainerStatus::Built);
     }
 
     #[tokio::test]
-    async fn test_compose_start_service_not_found_returns_error() {
-        let workspace = create_compose_workspace();
-
+    async fn test_down_clears_ssh_metadata() {
+        let workspace = create_test_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
-        *mock.compose_resolve_service_id_result.lock().unwrap() =
-            Err(devc_provider::ProviderError::RuntimeError(
-                "Service 'app' not found in compose project".to_string(),
-            ));
 
         let mut state = StateStore::new();
-        let mut cs =
-            make_container_state(workspace.path(), DevcContainerStatus::Stopped, None, None);
-        cs.compose_project = Some("devc-test".to_string());
-        cs.compose_service = Some("app".to_string());
+        let mut cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            Some("sha256:img"),
+            Some("container123"),
+        );
+        cs.metadata
+            .insert("ssh_available".to_string(), "true".to_string());
         let id = cs.id.clone();
         state.add(cs);
 
         let mgr = test_manager_with_state(mock, state);
-        let result = mgr.start(&id).await;
-        assert!(result.is_err());
-        let err_msg = format!("{}", result.unwrap_err());
-        assert!(err_msg.contains("not found"));
+        mgr.down(&id).await.unwrap();
+
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert!(!cs.metadata.contains_key("ssh_available"));
     }
 
     #[tokio::test]
-    async fn test_compose_stop_calls_compose_down() {
-        let workspace = create_compose_workspace();
-
+    async fn test_down_sets_built_status() {
+        let workspace = create_test_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
-        let calls = mock.calls.clone();
 
         let mut state = StateStore::new();
-        let mut cs = make_container_state(
+        let cs = make_container_state(
             workspace.path(),
             DevcContainerStatus::Running,
-            Some("compose"),
-            Some("compose_container_456"),
+            Some("sha256:img"),
+            Some("container123"),
         );
-        cs.compose_project = Some("devc-test".to_string());
-        cs.compose_service = Some("app".to_string());
         let id = cs.id.clone();
         state.add(cs);
 
         let mgr = test_manager_with_state(mock, state);
-        mgr.stop(&id).await.unwrap();
+        mgr.down(&id).await.unwrap();
 
-        // Should call compose_down, NOT individual stop
-        {
-            let recorded = calls.lock().unwrap();
-            assert!(recorded
-                .iter()
-                .any(|c| matches!(c, MockCall::ComposeDown { .. })));
-            assert!(!recorded.iter().any(|c| matches!(c, MockCall::Stop { .. })));
-        }
-
-        // container_id should be cleared
         let cs = mgr.get(&id).await.unwrap().unwrap();
-        assert!(cs.container_id.is_none());
-        assert_eq!(cs.status, DevcContainerStatus::Stopped);
+        assert_eq!(cs.status, DevcContainerStatus::Built);
     }
 
     #[tokio::test]
-    async fn test_init_from_config_new() {
-        let tmp = tempfile::tempdir().unwrap();
-        let dc = tmp.path().join(".devcontainer");
-        std::fs::create_dir_all(&dc).unwrap();
-        let config_path = dc.join("devcontainer.json");
-        std::fs::write(&config_path, r#"{"image": "ubuntu:22.04"}"#).unwrap();
-
+    async fn test_down_no_image_sets_configured() {
+        let workspace = create_test_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
-        let mgr = test_manager(mock);
 
-        let result = mgr.init_from_config(&config_path).await.unwrap();
-        assert!(result.is_some());
-        let cs = result.unwrap();
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            None, // no image
+            Some("container123"),
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_with_state(mock, state);
+        mgr.down(&id).await.unwrap();
+
+        let cs = mgr.get(&id).await.unwrap().unwrap();
         assert_eq!(cs.status, DevcContainerStatus::Configured);
-        assert_eq!(cs.config_path, config_path);
     }
 
+    // ==================== Rebuild ====================
+
     #[tokio::test]
-    async fn test_init_from_config_duplicate_returns_none() {
-        let tmp = tempfile::tempdir().unwrap();
-        let dc = tmp.path().join(".devcontainer");
-        std::fs::create_dir_all(&dc).unwrap();
-        let config_path = dc.join("devcontainer.json");
-        std::fs::write(&config_path, r#"{"image": "ubuntu:22.04"}"#).unwrap();
+    async fn test_rebuild_disconnected_fails() {
+        let workspace = create_test_workspace();
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            Some("sha256:img"),
+            Some("container123"),
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = ContainerManager::disconnected_for_testing(
+            GlobalConfig::default(),
+            state,
+            "no provider".to_string(),
+        );
+
+        let result = mgr.rebuild(&id, false).await;
+        assert!(result.is_err());
+    }
+
+    // ==================== Sync Status ====================
 
+    #[tokio::test]
+    async fn test_sync_running_stays_running() {
+        let workspace = create_test_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
-        let mgr = test_manager(mock);
+        // Default inspect returns Running
 
-        // First call registers
-        let first = mgr.init_from_config(&config_path).await.unwrap();
-        assert!(first.is_some());
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            Some("sha256:img"),
+            Some("container123"),
+        );
+        let id = cs.id.clone();
+        state.add(cs);
 
-        // Second call returns None (already registered)
-        let second = mgr.init_from_config(&config_path).await.unwrap();
-        assert!(second.is_none());
+        let mgr = test_manager_with_state(mock, state);
+        let status = mgr.sync_status(&id).await.unwrap();
+        assert_eq!(status, DevcContainerStatus::Running);
     }
 
     #[tokio::test]
-    async fn test_auto_discover_registers_all() {
-        let tmp = tempfile::tempdir().unwrap();
-        let dc = tmp.path().join(".devcontainer");
-        std::fs::create_dir_all(dc.join("python")).unwrap();
-        std::fs::create_dir_all(dc.join("node")).unwrap();
-        std::fs::write(dc.join("devcontainer.json"), r#"{"image": "ubuntu:22.04"}"#).unwrap();
-        std::fs::write(
-            dc.join("python/devcontainer.json"),
-            r#"{"image": "python:3.12"}"#,
-        )
-        .unwrap();
-        std::fs::write(dc.join("node/devcontainer.json"), r#"{"image": "node:20"}"#).unwrap();
-
+    async fn test_sync_running_to_stopped() {
+        let workspace = create_test_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
-        let mgr = test_manager(mock);
+        *mock.inspect_result.lock().unwrap() = Ok(mock_container_details(
+            "container123",
+            ContainerStatus::Exited,
+        ));
 
-        let newly = mgr.auto_discover_configs(tmp.path()).await.unwrap();
-        assert_eq!(newly.len(), 3);
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            Some("sha256:img"),
+            Some("container123"),
+        );
+        let id = cs.id.clone();
+        state.add(cs);
 
-        // All three should be in the list
-        let all = mgr.list().await.unwrap();
-        assert_eq!(all.len(), 3);
+        let mgr = test_manager_with_state(mock, state);
+        let status = mgr.sync_status(&id).await.unwrap();
+        assert_eq!(status, DevcContainerStatus::Stopped);
     }
 
     #[tokio::test]
-    async fn test_auto_discover_skips_existing() {
-        let tmp = tempfile::tempdir().unwrap();
-        let dc = tmp.path().join(".devcontainer");
-        std::fs::create_dir_all(dc.join("python")).unwrap();
-        std::fs::write(dc.join("devcontainer.json"), r#"{"image": "ubuntu:22.04"}"#).unwrap();
-        std::fs::write(
-            dc.join("python/devcontainer.json"),
-            r#"{"image": "python:3.12"}"#,
-        )
-        .unwrap();
-
+    async fn test_sync_running_to_paused() {
+        let workspace = create_test_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
-        let mgr = test_manager(mock);
+        *mock.inspect_result.lock().unwrap() = Ok(mock_container_details(
+            "container123",
+            ContainerStatus::Paused,
+        ));
 
-        // First discovery registers both
-        let first = mgr.auto_discover_configs(tmp.path()).await.unwrap();
-        assert_eq!(first.len(), 2);
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            Some("sha256:img"),
+            Some("container123"),
+        );
+        let id = cs.id.clone();
+        state.add(cs);
 
-        // Second discovery registers none (already tracked)
-        let second = mgr.auto_discover_configs(tmp.path()).await.unwrap();
-        assert_eq!(second.len(), 0);
+        let mgr = test_manager_with_state(mock, state);
+        let status = mgr.sync_status(&id).await.unwrap();
+        assert_eq!(status, DevcContainerStatus::Paused);
+    }
 
-        // Total should still be 2
-        let all = mgr.list().await.unwrap();
-        assert_eq!(all.len(), 2);
+    #[tokio::test]
+    async fn test_sync_container_disappeared() {
+        let workspace = create_test_workspace();
+        let mock = MockProvider::new(ProviderType::Docker);
+        *mock.inspect_result.lock().unwrap() = Err(ProviderError::ContainerNotFound("gone".into()));
+
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            Some("sha256:img"),
+            Some("container123"),
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_with_state(mock, state);
+        let status = mgr.sync_status(&id).await.unwrap();
+        // Container had an image, so should be Built
+        assert_eq!(status, DevcContainerStatus::Built);
     }
 
-    #[test]
-    fn test_merge_remote_env_both_empty() {
-        let feature_env = HashMap::new();
-        let result = merge_remote_env(None, &feature_env);
-        assert!(result.is_none());
+    #[tokio::test]
+    async fn test_sync_no_container_id_returns_current() {
+        let workspace = create_test_workspace();
+        let mock = MockProvider::new(ProviderType::Docker);
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Built,
+            Some("sha256:img"),
+            None, // no container_id → returns current status without inspecting
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_with_state(mock, state);
+        let status = mgr.sync_status(&id).await.unwrap();
+        assert_eq!(status, DevcContainerStatus::Built);
     }
 
-    #[test]
-    fn test_merge_remote_env_feature_only() {
-        let mut feature_env = HashMap::new();
-        feature_env.insert("FOO".to_string(), "bar".to_string());
-        let result = merge_remote_env(None, &feature_env).unwrap();
-        assert_eq!(result.get("FOO").unwrap(), "bar");
-        assert_eq!(result.len(), 1);
+    #[tokio::test]
+    async fn test_sync_statuses_parallel_persists_every_change() {
+        let workspace = create_test_workspace();
+        let mock = MockProvider::new(ProviderType::Docker);
+        *mock.inspect_result.lock().unwrap() = Ok(mock_container_details(
+            "container123",
+            ContainerStatus::Exited,
+        ));
+
+        let mut state = StateStore::new();
+        let mut ids = Vec::new();
+        for i in 0..8 {
+            let mut cs = make_container_state(
+                workspace.path(),
+                DevcContainerStatus::Running,
+                Some("sha256:img"),
+                Some(&format!("container{i}")),
+            );
+            cs.name = format!("test-{i}");
+            ids.push(cs.id.clone());
+            state.add(cs);
+        }
+
+        let mgr = test_manager_with_state(mock, state);
+        let synced = mgr.sync_statuses(3).await.unwrap();
+        assert_eq!(synced.len(), ids.len());
+        assert!(synced
+            .iter()
+            .all(|(_, status)| *status == DevcContainerStatus::Stopped));
+
+        let path = mgr.state_path_override.clone().unwrap();
+        let on_disk = StateStore::load_from(&path).unwrap();
+        for id in &ids {
+            assert_eq!(
+                mgr.get(id).await.unwrap().unwrap().status,
+                DevcContainerStatus::Stopped
+            );
+            assert_eq!(
+                on_disk.get(id).unwrap().status,
+                DevcContainerStatus::Stopped
+            );
+        }
     }
 
-    #[test]
-    fn test_merge_remote_env_devcontainer_only() {
-        let feature_env = HashMap::new();
-        let mut dc_env = HashMap::new();
-        dc_env.insert("EDITOR".to_string(), "vim".to_string());
-        let result = merge_remote_env(Some(&dc_env), &feature_env).unwrap();
-        assert_eq!(result.get("EDITOR").unwrap(), "vim");
-        assert_eq!(result.len(), 1);
+    // ==================== List / Get ====================
+
+    #[tokio::test]
+    async fn test_list_empty() {
+        let mock = MockProvider::new(ProviderType::Docker);
+        let mgr = test_manager(mock);
+        let list = mgr.list().await.unwrap();
+        assert!(list.is_empty());
     }
 
-    #[test]
-    fn test_merge_remote_env_devcontainer_wins() {
-        let mut feature_env = HashMap::new();
-        feature_env.insert("EDITOR".to_string(), "nano".to_string());
-        feature_env.insert("FEATURE_VAR".to_string(), "hello".to_string());
-        let mut dc_env = HashMap::new();
-        dc_env.insert("EDITOR".to_string(), "vim".to_string());
-        dc_env.insert("DC_VAR".to_string(), "world".to_string());
-        let result = merge_remote_env(Some(&dc_env), &feature_env).unwrap();
-        assert_eq!(
-            result.get("EDITOR").unwrap(),
-            "vim",
-            "devcontainer.json should win"
+    #[tokio::test]
+    async fn test_list_returns_all() {
+        let workspace = create_test_workspace();
+        let mock = MockProvider::new(ProviderType::Docker);
+
+        let mut state = StateStore::new();
+        let cs1 = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            Some("sha256:img"),
+            Some("c1"),
+        );
+        state.add(cs1);
+
+        let workspace2 = create_test_workspace();
+        let cs2 = make_container_state(
+            workspace2.path(),
+            DevcContainerStatus::Stopped,
+            Some("sha256:img2"),
+            Some("c2"),
+        );
+        state.add(cs2);
+
+        let mgr = test_manager_with_state(mock, state);
+        let list = mgr.list().await.unwrap();
+        assert_eq!(list.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_by_name() {
+        let workspace = create_test_workspace();
+        let mock = MockProvider::new(ProviderType::Docker);
+
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Configured,
+            None,
+            None,
+        );
+        state.add(cs);
+
+        let mgr = test_manager_with_state(mock, state);
+        let found = mgr.get_by_name("test").await.unwrap();
+        assert!(found.is_some());
+
+        let not_found = mgr.get_by_name("nonexistent").await.unwrap();
+        assert!(not_found.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_nonexistent() {
+        let mock = MockProvider::new(ProviderType::Docker);
+        let mgr = test_manager(mock);
+        let result = mgr.get("nonexistent-id").await.unwrap();
+        assert!(result.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_disk_usage_matches_short_ids() {
+        let workspace = create_test_workspace();
+        let mock = MockProvider::new(ProviderType::Docker);
+        let usage = |id: &str, name: &str| ContainerDiskUsage {
+            id: ContainerId::new(id),
+            name: name.to_string(),
+            image: "ubuntu:22.04".to_string(),
+            writable_bytes: 1_000,
+            image_bytes: Some(80_000_000),
+            volumes: Vec::new(),
+        };
+        *mock.disk_usage_result.lock().unwrap() = Ok(vec![
+            usage("abc123def456", "test"),
+            usage("999999999999", "unrelated"),
+        ]);
+
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            Some("sha256:img"),
+            Some("abc123def4567890"),
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+        let workspace2 = create_test_workspace();
+        state.add(make_container_state(
+            workspace2.path(),
+            DevcContainerStatus::Configured,
+            None,
+            None,
+        ));
+
+        let mut podman = make_container_state(
+            workspace2.path(),
+            DevcContainerStatus::Running,
+            Some("sha256:img"),
+            Some("abc123def4567890"),
+        );
+        podman.provider = ProviderType::Podman;
+        state.add(podman);
+
+        // The Podman container's provider isn't connected; it is skipped
+        let mgr = test_manager_with_state(mock, state);
+        let result = mgr.disk_usage().await.unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[&id].name, "test");
+    }
+
+    // ==================== Compose ====================
+
+    #[tokio::test]
+    async fn test_up_compose_calls_compose_up() {
+        let workspace = create_test_workspace();
+        // Write a compose-based devcontainer.json
+        std::fs::write(
+            workspace.path().join(".devcontainer/devcontainer.json"),
+            r#"{"dockerComposeFile": "docker-compose.yml", "service": "app", "workspaceFolder": "/workspace"}"#,
+        )
+        .unwrap();
+        // Create a dummy compose file (content doesn't matter for mock)
+        std::fs::write(
+            workspace.path().join(".devcontainer/docker-compose.yml"),
+            "version: '3'\nservices:\n  app:\n    image: ubuntu:22.04\n",
+        )
+        .unwrap();
+
+        let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
+        *mock.compose_resolve_service_id_result.lock().unwrap() =
+            Ok(ContainerId::new("compose_container_123"));
+
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Configured,
+            None,
+            None,
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_with_state(mock, state);
+        mgr.up(&id).await.unwrap();
+
+        // Verify compose_up was called
+        {
+            let recorded = calls.lock().unwrap();
+            assert!(recorded
+                .iter()
+                .any(|c| matches!(c, MockCall::ComposeUp { .. })));
+            assert!(recorded
+                .iter()
+                .any(|c| matches!(c, MockCall::ComposeResolveServiceId { .. })));
+        }
+
+        // Verify state was updated with compose metadata
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert_eq!(cs.container_id, Some("compose_container_123".to_string()));
+        assert!(cs.compose_project.as_ref().unwrap().starts_with("devc-"));
+        assert_eq!(cs.compose_service, Some("app".to_string()));
+        assert_eq!(cs.status, DevcContainerStatus::Running);
+    }
+
+    #[tokio::test]
+    async fn test_down_compose_calls_compose_down() {
+        let workspace = create_test_workspace();
+        // Write a compose-based devcontainer.json
+        std::fs::write(
+            workspace.path().join(".devcontainer/devcontainer.json"),
+            r#"{"dockerComposeFile": "docker-compose.yml", "service": "app"}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            workspace.path().join(".devcontainer/docker-compose.yml"),
+            "version: '3'\nservices:\n  app:\n    image: ubuntu:22.04\n",
+        )
+        .unwrap();
+
+        let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
+
+        let mut state = StateStore::new();
+        let mut cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            Some("compose"),
+            Some("compose_container_123"),
+        );
+        cs.compose_project = Some("devc-test".to_string());
+        cs.compose_service = Some("app".to_string());
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_with_state(mock, state);
+        mgr.down(&id).await.unwrap();
+
+        // Verify compose_down was called
+        {
+            let recorded = calls.lock().unwrap();
+            assert!(recorded
+                .iter()
+                .any(|c| matches!(c, MockCall::ComposeDown { .. })));
+            // Should NOT call individual stop/remove
+            assert!(!recorded.iter().any(|c| matches!(c, MockCall::Stop { .. })));
+            assert!(!recorded
+                .iter()
+                .any(|c| matches!(c, MockCall::Remove { .. })));
+        }
+
+        // State should be cleaned up
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert!(cs.container_id.is_none());
+        assert!(cs.compose_project.is_none());
+        assert!(cs.compose_service.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_without_action_leaves_container_running() {
+        let workspace = create_test_workspace();
+        let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
+
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            Some("sha256:img"),
+            Some("container123"),
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_with_state(mock, state);
+        assert_eq!(mgr.shutdown(&id).await.unwrap(), ShutdownAction::None);
+
+        assert!(!calls
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|c| matches!(c, MockCall::Stop { .. })));
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert_eq!(cs.status, DevcContainerStatus::Running);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_stop_container_in_compose_skips_compose_down() {
+        let workspace = create_test_workspace();
+        std::fs::write(
+            workspace.path().join(".devcontainer/devcontainer.json"),
+            r#"{"dockerComposeFile": "docker-compose.yml", "service": "app", "shutdownAction": "stopContainer"}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            workspace.path().join(".devcontainer/docker-compose.yml"),
+            "version: '3'\nservices:\n  app:\n    image: ubuntu:22.04\n",
+        )
+        .unwrap();
+
+        let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
+
+        let mut state = StateStore::new();
+        let mut cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            Some("compose"),
+            Some("compose_container_123"),
+        );
+        cs.compose_project = Some("devc-test".to_string());
+        cs.compose_service = Some("app".to_string());
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_with_state(mock, state);
+        assert_eq!(
+            mgr.shutdown(&id).await.unwrap(),
+            ShutdownAction::StopContainer
+        );
+
+        {
+            let recorded = calls.lock().unwrap();
+            assert!(recorded.iter().any(|c| matches!(c, MockCall::Stop { .. })));
+            assert!(!recorded
+                .iter()
+                .any(|c| matches!(c, MockCall::ComposeDown { .. })));
+        }
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert_eq!(cs.status, DevcContainerStatus::Stopped);
+        assert_eq!(cs.compose_project, Some("devc-test".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_stop_compose_runs_compose_down() {
+        let workspace = create_test_workspace();
+        std::fs::write(
+            workspace.path().join(".devcontainer/devcontainer.json"),
+            r#"{"dockerComposeFile": "docker-compose.yml", "service": "app", "shutdownAction": "stopCompose"}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            workspace.path().join(".devcontainer/docker-compose.yml"),
+            "version: '3'\nservices:\n  app:\n    image: ubuntu:22.04\n",
+        )
+        .unwrap();
+
+        let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
+
+        let mut state = StateStore::new();
+        let mut cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            Some("compose"),
+            Some("compose_container_123"),
+        );
+        cs.compose_project = Some("devc-test".to_string());
+        cs.compose_service = Some("app".to_string());
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_with_state(mock, state);
+        assert_eq!(
+            mgr.shutdown(&id).await.unwrap(),
+            ShutdownAction::StopCompose
+        );
+
+        assert!(calls
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|c| matches!(c, MockCall::ComposeDown { .. })));
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert_eq!(cs.status, DevcContainerStatus::Stopped);
+    }
+
+    #[tokio::test]
+    async fn test_down_non_compose_uses_stop_remove() {
+        let workspace = create_test_workspace();
+        let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
+
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            Some("sha256:img"),
+            Some("container123"),
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_with_state(mock, state);
+        mgr.down(&id).await.unwrap();
+
+        // Should call stop + remove, NOT compose_down
+        let recorded = calls.lock().unwrap();
+        assert!(recorded.iter().any(|c| matches!(c, MockCall::Stop { .. })));
+        assert!(recorded
+            .iter()
+            .any(|c| matches!(c, MockCall::Remove { .. })));
+        assert!(!recorded
+            .iter()
+            .any(|c| matches!(c, MockCall::ComposeDown { .. })));
+    }
+
+    // ==================== Compose Start / Stop ====================
+
+    /// Helper: create a compose workspace with devcontainer.json + docker-compose.yml.
+    fn create_compose_workspace() -> tempfile::TempDir {
+        create_compose_workspace_with_fields("")
+    }
+
+    /// Like `create_compose_workspace` but appends `extra_devcontainer_fields` (e.g.
+    /// `, "onCreateCommand": "echo foo"`) inside the devcontainer.json object.
+    fn create_compose_workspace_with_fields(extra_devcontainer_fields: &str) -> tempfile::TempDir {
+        let tmp = tempfile::tempdir().unwrap();
+        let devcontainer_dir = tmp.path().join(".devcontainer");
+        std::fs::create_dir_all(&devcontainer_dir).unwrap();
+        let json = format!(
+            r#"{{"dockerComposeFile": "docker-compose.yml", "service": "app", "workspaceFolder": "/workspace"{}}}"#,
+            extra_devcontainer_fields,
+        );
+        std::fs::write(devcontainer_dir.join("devcontainer.json"), json).unwrap();
+        std::fs::write(
+            devcontainer_dir.join("docker-compose.yml"),
+            "version: '3'\nservices:\n  app:\n    image: ubuntu:22.04\n",
+        )
+        .unwrap();
+        tmp
+    }
+
+    #[tokio::test]
+    async fn test_compose_requires_compose_capability() {
+        let workspace = create_compose_workspace();
+
+        let mock = MockProvider::new(ProviderType::Podman);
+        let calls = mock.calls.clone();
+        mock.capabilities.lock().unwrap().compose = false;
+
+        let mut state = StateStore::new();
+        let mut cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Configured,
+            None,
+            None,
+        );
+        cs.provider = ProviderType::Podman;
+        let id = cs.id.clone();
+        state.add(cs);
+        let mgr = test_manager_with_state(mock, state);
+
+        let err = mgr.up(&id).await.unwrap_err().to_string();
+        assert!(
+            err.contains("compose is not available for podman"),
+            "{}",
+            err
+        );
+        assert!(mgr.compose_services(&id).await.is_err());
+        assert!(
+            !calls
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|c| matches!(c, MockCall::ComposeUp { .. } | MockCall::ComposePs { .. })),
+            "no compose command should run without compose support"
+        );
+        assert!(
+            calls.lock().unwrap().iter().all(|c| !matches!(
+                c,
+                MockCall::Supports { capability } if *capability != Capability::Compose
+            )),
+            "only compose support should be probed"
+        );
+        assert_eq!(
+            mgr.get(&id).await.unwrap().unwrap().status,
+            DevcContainerStatus::Configured
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compose_start_calls_compose_up_and_sets_container_id() {
+        let workspace = create_compose_workspace();
+
+        let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
+        *mock.compose_resolve_service_id_result.lock().unwrap() =
+            Ok(ContainerId::new("compose_start_abc"));
+
+        let mut state = StateStore::new();
+        // Use Stopped status — can_start() requires Created or Stopped
+        let mut cs =
+            make_container_state(workspace.path(), DevcContainerStatus::Stopped, None, None);
+        cs.compose_project = Some("devc-test".to_string());
+        cs.compose_service = Some("app".to_string());
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_with_state(mock, state);
+        mgr.start(&id).await.unwrap();
+
+        // Verify compose_up and compose service resolution were called
+        {
+            let recorded = calls.lock().unwrap();
+            assert!(recorded
+                .iter()
+                .any(|c| matches!(c, MockCall::ComposeUp { .. })));
+            assert!(recorded
+                .iter()
+                .any(|c| matches!(c, MockCall::ComposeResolveServiceId { .. })));
+        }
+
+        // Verify container_id was set from the matched service
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert_eq!(cs.container_id, Some("compose_start_abc".to_string()));
+        assert_eq!(cs.status, DevcContainerStatus::Running);
+    }
+
+    #[tokio::test]
+    async fn test_compose_start_service_not_found_returns_error() {
+        let workspace = create_compose_workspace();
+
+        let mock = MockProvider::new(ProviderType::Docker);
+        *mock.compose_resolve_service_id_result.lock().unwrap() =
+            Err(devc_provider::ProviderError::RuntimeError(
+                "Service 'app' not found in compose project".to_string(),
+            ));
+
+        let mut state = StateStore::new();
+        let mut cs =
+            make_container_state(workspace.path(), DevcContainerStatus::Stopped, None, None);
+        cs.compose_project = Some("devc-test".to_string());
+        cs.compose_service = Some("app".to_string());
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_with_state(mock, state);
+        let result = mgr.start(&id).await;
+        assert!(result.is_err());
+        let err_msg = format!("{}", result.unwrap_err());
+        assert!(err_msg.contains("not found"));
+    }
+
+    fn compose_logs_manager(
+        workspace: &std::path::Path,
+    ) -> (
+        ContainerManager,
+        String,
+        Arc<std::sync::Mutex<Vec<MockCall>>>,
+    ) {
+        let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
+        *mock.compose_ps_result.lock().unwrap() = Ok(vec![
+            ComposeServiceInfo {
+                service_name: "app".to_string(),
+                container_id: ContainerId::new("app_cid"),
+                status: ContainerStatus::Running,
+            },
+            ComposeServiceInfo {
+                service_name: "db".to_string(),
+                container_id: ContainerId::new("db_cid"),
+                status: ContainerStatus::Exited,
+            },
+        ]);
+
+        let mut state = StateStore::new();
+        let mut cs = make_container_state(
+            workspace,
+            DevcContainerStatus::Running,
+            None,
+            Some("app_cid"),
+        );
+        cs.compose_project = Some("devc-test".to_string());
+        cs.compose_service = Some("app".to_string());
+        let id = cs.id.clone();
+        state.add(cs);
+        (test_manager_with_state(mock, state), id, calls)
+    }
+
+    #[tokio::test]
+    async fn test_compose_service_logs_resolves_service_container() {
+        let workspace = create_compose_workspace();
+        let (mgr, id, calls) = compose_logs_manager(workspace.path());
+
+        mgr.compose_service_logs(&id, "db", Some(10)).await.unwrap();
+        mgr.compose_service_logs(&id, "app", None).await.unwrap();
+
+        let log_ids: Vec<String> = calls
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|c| match c {
+                MockCall::Logs { id } => Some(id.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(log_ids, vec!["db_cid".to_string(), "app_cid".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_previous_logs_of_exited_container() {
+        let workspace = create_test_workspace();
+        let mock = MockProvider::new(ProviderType::Docker);
+        let mut details = mock_container_details("cid_exited", ContainerStatus::Exited);
+        details.exit_code = Some(1);
+        *mock.inspect_result.lock().unwrap() = Ok(details);
+        *mock.logs_output.lock().unwrap() = "starting\nfatal: bad config\n".to_string();
+
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Stopped,
+            Some("sha256:img"),
+            Some("cid_exited"),
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+        let mut never_created =
+            make_container_state(workspace.path(), DevcContainerStatus::Built, None, None);
+        never_created.name = "fresh".to_string();
+        let fresh_id = never_created.id.clone();
+        state.add(never_created);
+
+        let mgr = test_manager_with_state(mock, state);
+        let (details, lines) = mgr.previous_logs(&id, None).await.unwrap();
+        assert_eq!(details.exit_code, Some(1));
+        assert_eq!(lines, vec!["starting", "fatal: bad config"]);
+
+        let err = mgr.previous_logs(&fresh_id, None).await.unwrap_err();
+        assert!(err.to_string().contains("no logs are available"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_compose_services_updates_recreated_primary_id() {
+        let workspace = create_compose_workspace();
+        let (mgr, id, _calls) = compose_logs_manager(workspace.path());
+        {
+            let mut state = mgr.state.write().await;
+            state.get_mut(&id).unwrap().container_id = Some("stale_cid".to_string());
+        }
+
+        let services = mgr.compose_services(&id).await.unwrap();
+        assert_eq!(services.len(), 2);
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert_eq!(cs.container_id.as_deref(), Some("app_cid"));
+    }
+
+    #[tokio::test]
+    async fn test_compose_service_logs_unknown_service_fails() {
+        let workspace = create_compose_workspace();
+        let (mgr, id, _calls) = compose_logs_manager(workspace.path());
+
+        let err = mgr
+            .compose_service_logs(&id, "cache", None)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Service 'cache' not found"), "{}", err);
+        assert!(err.contains("app, db"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_compose_stop_calls_compose_down() {
+        let workspace = create_compose_workspace();
+
+        let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
+
+        let mut state = StateStore::new();
+        let mut cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            Some("compose"),
+            Some("compose_container_456"),
+        );
+        cs.compose_project = Some("devc-test".to_string());
+        cs.compose_service = Some("app".to_string());
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_with_state(mock, state);
+        mgr.stop(&id).await.unwrap();
+
+        // Should call compose_down, NOT individual stop
+        {
+            let recorded = calls.lock().unwrap();
+            assert!(recorded
+                .iter()
+                .any(|c| matches!(c, MockCall::ComposeDown { .. })));
+            assert!(!recorded.iter().any(|c| matches!(c, MockCall::Stop { .. })));
+        }
+
+        // container_id should be cleared
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert!(cs.container_id.is_none());
+        assert_eq!(cs.status, DevcContainerStatus::Stopped);
+    }
+
+    #[tokio::test]
+    async fn test_init_from_config_new() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dc = tmp.path().join(".devcontainer");
+        std::fs::create_dir_all(&dc).unwrap();
+        let config_path = dc.join("devcontainer.json");
+        std::fs::write(&config_path, r#"{"image": "ubuntu:22.04"}"#).unwrap();
+
+        let mock = MockProvider::new(ProviderType::Docker);
+        let mgr = test_manager(mock);
+
+        let result = mgr.init_from_config(&config_path).await.unwrap();
+        assert!(result.is_some());
+        let cs = result.unwrap();
+        assert_eq!(cs.status, DevcContainerStatus::Configured);
+        assert_eq!(cs.config_path, config_path);
+    }
+
+    #[tokio::test]
+    async fn test_init_from_config_duplicate_returns_none() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dc = tmp.path().join(".devcontainer");
+        std::fs::create_dir_all(&dc).unwrap();
+        let config_path = dc.join("devcontainer.json");
+        std::fs::write(&config_path, r#"{"image": "ubuntu:22.04"}"#).unwrap();
+
+        let mock = MockProvider::new(ProviderType::Docker);
+        let mgr = test_manager(mock);
+
+        // First call registers
+        let first = mgr.init_from_config(&config_path).await.unwrap();
+        assert!(first.is_some());
+
+        // Second call returns None (already registered)
+        let second = mgr.init_from_config(&config_path).await.unwrap();
+        assert!(second.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_root_config_init_and_discover_dedup() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root_config = tmp.path().join(".devcontainer.json");
+        std::fs::write(&root_config, r#"{"image": "alpine:3"}"#).unwrap();
+
+        let mock = MockProvider::new(ProviderType::Docker);
+        let mgr = test_manager(mock);
+
+        let cs = mgr.init(tmp.path()).await.unwrap();
+        assert_eq!(cs.config_path, root_config);
+        assert!(mgr
+            .auto_discover_configs(tmp.path())
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Adding the folder form shadows the root config: the existing entry
+        // moves to it rather than a second entry being added
+        let dc = tmp.path().join(".devcontainer");
+        std::fs::create_dir_all(&dc).unwrap();
+        std::fs::write(dc.join("devcontainer.json"), r#"{"image": "ubuntu:22.04"}"#).unwrap();
+        assert!(mgr.find_unregistered_configs(tmp.path()).await.is_empty());
+        let discovered = mgr.auto_discover_configs(tmp.path()).await.unwrap();
+        assert!(discovered.is_empty());
+        let all = mgr.list().await.unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].id, cs.id);
+        assert_eq!(all[0].config_path, dc.join("devcontainer.json"));
+    }
+
+    #[tokio::test]
+    async fn test_auto_discover_registers_all() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dc = tmp.path().join(".devcontainer");
+        std::fs::create_dir_all(dc.join("python")).unwrap();
+        std::fs::create_dir_all(dc.join("node")).unwrap();
+        std::fs::write(dc.join("devcontainer.json"), r#"{"image": "ubuntu:22.04"}"#).unwrap();
+        std::fs::write(
+            dc.join("python/devcontainer.json"),
+            r#"{"image": "python:3.12"}"#,
+        )
+        .unwrap();
+        std::fs::write(dc.join("node/devcontainer.json"), r#"{"image": "node:20"}"#).unwrap();
+
+        let mock = MockProvider::new(ProviderType::Docker);
+        let mgr = test_manager(mock);
+
+        let newly = mgr.auto_discover_configs(tmp.path()).await.unwrap();
+        assert_eq!(newly.len(), 3);
+
+        // All three should be in the list
+        let all = mgr.list().await.unwrap();
+        assert_eq!(all.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_auto_discover_skips_existing() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dc = tmp.path().join(".devcontainer");
+        std::fs::create_dir_all(dc.join("python")).unwrap();
+        std::fs::write(dc.join("devcontainer.json"), r#"{"image": "ubuntu:22.04"}"#).unwrap();
+        std::fs::write(
+            dc.join("python/devcontainer.json"),
+            r#"{"image": "python:3.12"}"#,
+        )
+        .unwrap();
+
+        let mock = MockProvider::new(ProviderType::Docker);
+        let mgr = test_manager(mock);
+
+        // First discovery registers both
+        let first = mgr.auto_discover_configs(tmp.path()).await.unwrap();
+        assert_eq!(first.len(), 2);
+
+        // Second discovery registers none (already tracked)
+        let second = mgr.auto_discover_configs(tmp.path()).await.unwrap();
+        assert_eq!(second.len(), 0);
+
+        // Total should still be 2
+        let all = mgr.list().await.unwrap();
+        assert_eq!(all.len(), 2);
+    }
+
+    #[test]
+    fn test_merge_remote_env_both_empty() {
+        let feature_env = HashMap::new();
+        let result = merge_remote_env(None, &feature_env);
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_merge_remote_env_feature_only() {
+        let mut feature_env = HashMap::new();
+        feature_env.insert("FOO".to_string(), "bar".to_string());
+        let result = merge_remote_env(None, &feature_env).unwrap();
+        assert_eq!(result.get("FOO").unwrap(), "bar");
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test]
+    fn test_merge_remote_env_devcontainer_only() {
+        let feature_env = HashMap::new();
+        let mut dc_env = HashMap::new();
+        dc_env.insert("EDITOR".to_string(), "vim".to_string());
+        let result = merge_remote_env(Some(&dc_env), &feature_env).unwrap();
+        assert_eq!(result.get("EDITOR").unwrap(), "vim");
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test]
+    fn test_merge_remote_env_devcontainer_wins() {
+        let mut feature_env = HashMap::new();
+        feature_env.insert("EDITOR".to_string(), "nano".to_string());
+        feature_env.insert("FEATURE_VAR".to_string(), "hello".to_string());
+        let mut dc_env = HashMap::new();
+        dc_env.insert("EDITOR".to_string(), "vim".to_string());
+        dc_env.insert("DC_VAR".to_string(), "world".to_string());
+        let result = merge_remote_env(Some(&dc_env), &feature_env).unwrap();
+        assert_eq!(
+            result.get("EDITOR").unwrap(),
+            "vim",
+            "devcontainer.json should win"
+        );
+        assert_eq!(result.get("FEATURE_VAR").unwrap(), "hello");
+        assert_eq!(result.get("DC_VAR").unwrap(), "world");
+        assert_eq!(result.len(), 3);
+    }
+
+    // ==================== Lifecycle Event Ordering ====================
+
+    /// Create a workspace with all lifecycle commands configured.
+    /// Returns (tempdir, marker_file_path) where marker_file_path is the
+    /// file that initializeCommand will `touch` on the host.
+    fn create_lifecycle_workspace() -> (tempfile::TempDir, std::path::PathBuf) {
+        let tmp = tempfile::tempdir().unwrap();
+        let marker = tmp.path().join("init_marker");
+        let devcontainer_dir = tmp.path().join(".devcontainer");
+        std::fs::create_dir_all(&devcontainer_dir).unwrap();
+        let config = format!(
+            r#"{{
+                "image": "ubuntu:22.04",
+                "initializeCommand": "touch {}",
+                "onCreateCommand": "echo on-create",
+                "updateContentCommand": "echo update-content",
+                "postCreateCommand": "echo post-create",
+                "postStartCommand": "echo post-start",
+                "postAttachCommand": "echo post-attach"
+            }}"#,
+            marker.display()
+        );
+        std::fs::write(devcontainer_dir.join("devcontainer.json"), config).unwrap();
+        (tmp, marker)
+    }
+
+    /// Build a manager with credentials and SSH disabled to keep lifecycle tests focused.
+    fn test_manager_no_creds(mock: MockProvider, state: StateStore) -> ContainerManager {
+        let mut global_config = GlobalConfig::default();
+        global_config.credentials.docker = false;
+        global_config.credentials.git = false;
+        ContainerManager::new_for_testing(Box::new(mock), global_config, state)
+    }
+
+    /// Filter mock calls to only Exec calls, returning just the command vectors.
+    fn exec_commands(calls: &[MockCall]) -> Vec<Vec<String>> {
+        calls
+            .iter()
+            .filter_map(|c| {
+                if let MockCall::Exec { cmd, .. } = c {
+                    Some(cmd.clone())
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Extract the shell command string from an Exec call like
+    /// ["/bin/sh", "-c", "echo foo"] or ["/bin/sh", "-lc", "echo foo"].
+    fn shell_cmd(cmd: &[String]) -> &str {
+        assert_eq!(cmd.len(), 3);
+        assert_eq!(cmd[0], "/bin/sh");
+        assert!(
+            cmd[1] == "-c" || cmd[1] == "-lc",
+            "expected /bin/sh with -c or -lc, got: {:?}",
+            cmd
+        );
+        &cmd[2]
+    }
+
+    #[tokio::test]
+    async fn test_up_lifecycle_event_order() {
+        let (workspace, marker) = create_lifecycle_workspace();
+        let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
+
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Configured,
+            None,
+            None,
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_no_creds(mock, state);
+        mgr.up(&id).await.unwrap();
+
+        // initializeCommand ran on host
+        assert!(
+            marker.exists(),
+            "initializeCommand marker file should exist"
+        );
+
+        let recorded = calls.lock().unwrap();
+
+        // initializeCommand ran before any provider call (marker file exists and
+        // first provider call is Pull, confirming host command ran first)
+        assert!(
+            matches!(&recorded[0], MockCall::Pull { image } if image == "ubuntu:22.04"),
+            "First provider call should be Pull; got {:?}",
+            &recorded[0]
+        );
+
+        // Verify the provider call order: Pull → RemoveByName → Create → lifecycle execs → start phase
+        let call_types: Vec<&str> = recorded
+            .iter()
+            .map(|c| match c {
+                MockCall::Pull { .. } => "Pull",
+                MockCall::Create { .. } => "Create",
+                MockCall::Start { .. } => "Start",
+                MockCall::Inspect { .. } => "Inspect",
+                MockCall::Exec { .. } => "Exec",
+                MockCall::RemoveByName { .. } => "RemoveByName",
+                _ => "Other",
+            })
+            .collect();
+
+        // Pull must come before Create
+        let pull_idx = call_types.iter().position(|&t| t == "Pull").unwrap();
+        let create_idx = call_types.iter().position(|&t| t == "Create").unwrap();
+        assert!(pull_idx < create_idx, "Pull must come before Create");
+
+        // Verify exec commands run in the correct lifecycle order
+        let execs = exec_commands(&recorded);
+        assert!(execs.len() >= 4, "Expected at least 4 exec calls (onCreate, updateContent, postCreate, postStart), got {}", execs.len());
+
+        let lifecycle_cmds: Vec<&str> = execs.iter().map(|cmd| shell_cmd(cmd)).collect();
+        let on_create_idx = lifecycle_cmds
+            .iter()
+            .position(|&c| c == "echo on-create")
+            .expect("onCreateCommand should have run");
+        let update_content_idx = lifecycle_cmds
+            .iter()
+            .position(|&c| c == "echo update-content")
+            .expect("updateContentCommand should have run");
+        let post_create_idx = lifecycle_cmds
+            .iter()
+            .position(|&c| c == "echo post-create")
+            .expect("postCreateCommand should have run");
+        let post_start_idx = lifecycle_cmds
+            .iter()
+            .position(|&c| c == "echo post-start")
+            .expect("postStartCommand should have run");
+
+        assert!(
+            on_create_idx < update_content_idx,
+            "onCreateCommand must run before updateContentCommand"
+        );
+        assert!(
+            update_content_idx < post_create_idx,
+            "updateContentCommand must run before postCreateCommand"
+        );
+        assert!(
+            post_create_idx < post_start_idx,
+            "postCreateCommand must run before postStartCommand"
+        );
+
+        // postAttachCommand must NOT run during up
+        assert!(
+            !lifecycle_cmds.contains(&"echo post-attach"),
+            "postAttachCommand should NOT run during up"
+        );
+
+        // All execs happen after Create
+        let first_exec_overall = recorded
+            .iter()
+            .position(|c| matches!(c, MockCall::Exec { .. }))
+            .unwrap();
+        assert!(
+            create_idx < first_exec_overall,
+            "Create must come before any Exec"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_up_credentials_before_lifecycle() {
+        let (workspace, _marker) = create_lifecycle_workspace();
+        let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
+
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Configured,
+            None,
+            None,
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+
+        // Use test_manager_with_state (credentials enabled by default)
+        let mgr = test_manager_with_state(mock, state);
+        mgr.up(&id).await.unwrap();
+
+        let recorded = calls.lock().unwrap();
+        let execs = exec_commands(&recorded);
+        let cmds: Vec<&str> = execs.iter().map(|cmd| shell_cmd(cmd)).collect();
+
+        // Credential-related exec calls (contain "devc-creds") must appear
+        // before lifecycle exec calls (echo on-create, etc.)
+        let first_cred_idx = cmds
+            .iter()
+            .position(|c| c.contains("devc-creds"))
+            .expect("Expected credential injection exec calls");
+        let first_lifecycle_idx = cmds
+            .iter()
+            .position(|c| *c == "echo on-create")
+            .expect("Expected onCreateCommand exec call");
+
+        assert!(
+            first_cred_idx < first_lifecycle_idx,
+            "Credential injection (index {}) must run before lifecycle commands (index {})\nExec order: {:?}",
+            first_cred_idx,
+            first_lifecycle_idx,
+            cmds
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_runs_initialize_before_build() {
+        let (workspace, marker) = create_lifecycle_workspace();
+        let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
+
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            Some("sha256:old_image"),
+            Some("old_container_123"),
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_no_creds(mock, state);
+        mgr.rebuild(&id, false).await.unwrap();
+
+        // initializeCommand marker must exist
+        assert!(
+            marker.exists(),
+            "initializeCommand marker file should exist"
+        );
+
+        let recorded = calls.lock().unwrap();
+
+        // Down phase (Stop + Remove) must come before Pull (build)
+        let stop_idx = recorded
+            .iter()
+            .position(|c| matches!(c, MockCall::Stop { .. }))
+            .unwrap();
+        let remove_idx = recorded
+            .iter()
+            .position(|c| matches!(c, MockCall::Remove { .. }))
+            .unwrap();
+        let pull_idx = recorded
+            .iter()
+            .position(|c| matches!(c, MockCall::Pull { .. }))
+            .unwrap();
+
+        assert!(stop_idx < pull_idx, "Stop must come before Pull (build)");
+        assert!(
+            remove_idx < pull_idx,
+            "Remove must come before Pull (build)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_with_progress_runs_initialize_before_build() {
+        let (workspace, marker) = create_lifecycle_workspace();
+        let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
+
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            Some("sha256:old_image"),
+            Some("old_container_123"),
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_no_creds(mock, state);
+        let (tx, _rx) = mpsc::unbounded_channel();
+        mgr.rebuild_with_progress(&id, false, tx, None)
+            .await
+            .unwrap();
+
+        // initializeCommand marker must exist
+        assert!(
+            marker.exists(),
+            "initializeCommand marker file should exist"
+        );
+
+        let recorded = calls.lock().unwrap();
+
+        // Down phase (Stop + Remove) must come before Pull (build)
+        let stop_idx = recorded
+            .iter()
+            .position(|c| matches!(c, MockCall::Stop { .. }))
+            .unwrap();
+        let remove_idx = recorded
+            .iter()
+            .position(|c| matches!(c, MockCall::Remove { .. }))
+            .unwrap();
+        let pull_idx = recorded
+            .iter()
+            .position(|c| matches!(c, MockCall::PullWithProgress { .. }))
+            .unwrap();
+
+        assert!(stop_idx < pull_idx, "Stop must come before Pull (build)");
+        assert!(
+            remove_idx < pull_idx,
+            "Remove must come before Pull (build)"
         );
-        assert_eq!(result.get("FEATURE_VAR").unwrap(), "hello");
-        assert_eq!(result.get("DC_VAR").unwrap(), "world");
-        assert_eq!(result.len(), 3);
     }
 
-    // ==================== Lifecycle Event Ordering ====================
+    #[tokio::test]
+    async fn test_up_reports_ready_after_wait_for_phase() {
+        async fn up_progress(config: &str) -> Vec<String> {
+            let workspace = tempfile::tempdir().unwrap();
+            let devcontainer_dir = workspace.path().join(".devcontainer");
+            std::fs::create_dir_all(&devcontainer_dir).unwrap();
+            std::fs::write(devcontainer_dir.join("devcontainer.json"), config).unwrap();
+
+            let mut state = StateStore::new();
+            let cs = make_container_state(
+                workspace.path(),
+                DevcContainerStatus::Configured,
+                None,
+                None,
+            );
+            let id = cs.id.clone();
+            state.add(cs);
+            let mgr = test_manager_no_creds(MockProvider::new(ProviderType::Docker), state);
+
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            mgr.up_with_progress(&id, Some(&tx), None, None)
+                .await
+                .unwrap();
+            drop(tx);
+            let mut messages = Vec::new();
+            while let Some(msg) = rx.recv().await {
+                messages.push(msg);
+            }
+            messages
+        }
 
-    /// Create a workspace with all lifecycle commands configured.
-    /// Returns (tempdir, marker_file_path) where marker_file_path is the
-    /// file that initializeCommand will `touch` on the host.
-    fn create_lifecycle_workspace() -> (tempfile::TempDir, std::path::PathBuf) {
-        let tmp = tempfile::tempdir().unwrap();
-        let marker = tmp.path().join("init_marker");
-        let devcontainer_dir = tmp.path().join(".devcontainer");
-        std::fs::create_dir_all(&devcontainer_dir).unwrap();
-        let config = format!(
-            r#"{{
+        let messages = up_progress(
+            r#"{
                 "image": "ubuntu:22.04",
-                "initializeCommand": "touch {}",
                 "onCreateCommand": "echo on-create",
-                "updateContentCommand": "echo update-content",
                 "postCreateCommand": "echo post-create",
-                "postStartCommand": "echo post-start",
-                "postAttachCommand": "echo post-attach"
-            }}"#,
-            marker.display()
+                "waitFor": "onCreateCommand"
+            }"#,
+        )
+        .await;
+        let position = |text: &str| messages.iter().position(|m| m == text).unwrap();
+        let ready = position(READY_FINALIZING_PROGRESS);
+        assert!(position("Running onCreate command...") < ready);
+        assert!(ready < position("Running postCreateCommand..."));
+        assert_eq!(
+            messages
+                .iter()
+                .filter(|m| *m == READY_FINALIZING_PROGRESS)
+                .count(),
+            1
         );
-        std::fs::write(devcontainer_dir.join("devcontainer.json"), config).unwrap();
-        (tmp, marker)
-    }
-
-    /// Build a manager with credentials and SSH disabled to keep lifecycle tests focused.
-    fn test_manager_no_creds(mock: MockProvider, state: StateStore) -> ContainerManager {
-        let mut global_config = GlobalConfig::default();
-        global_config.credentials.docker = false;
-        global_config.credentials.git = false;
-        ContainerManager::new_for_testing(Box::new(mock), global_config, state)
-    }
-
-    /// Filter mock calls to only Exec calls, returning just the command vectors.
-    fn exec_commands(calls: &[MockCall]) -> Vec<Vec<String>> {
-        calls
-            .iter()
-            .filter_map(|c| {
-                if let MockCall::Exec { cmd, .. } = c {
-                    Some(cmd.clone())
-                } else {
-                    None
-                }
-            })
-            .collect()
-    }
 
-    /// Extract the shell command string from an Exec call like
-    /// ["/bin/sh", "-c", "echo foo"] or ["/bin/sh", "-lc", "echo foo"].
-    fn shell_cmd(cmd: &[String]) -> &str {
-        assert_eq!(cmd.len(), 3);
-        assert_eq!(cmd[0], "/bin/sh");
-        assert!(
-            cmd[1] == "-c" || cmd[1] == "-lc",
-            "expected /bin/sh with -c or -lc, got: {:?}",
-            cmd
-        );
-        &cmd[2]
+        let messages =
+            up_progress(r#"{"image": "ubuntu:22.04", "postCreateCommand": "echo post-create"}"#)
+                .await;
+        assert!(!messages.iter().any(|m| m == READY_FINALIZING_PROGRESS));
     }
 
     #[tokio::test]
-    async fn test_up_lifecycle_event_order() {
+    async fn test_rebuild_full_lifecycle_order() {
         let (workspace, marker) = create_lifecycle_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
         let calls = mock.calls.clone();
@@ -3028,17 +5595,16 @@ mod tests {
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Configured,
-            None,
-            None,
+            DevcContainerStatus::Running,
+            Some("sha256:old_image"),
+            Some("old_container_123"),
         );
         let id = cs.id.clone();
         state.add(cs);
 
         let mgr = test_manager_no_creds(mock, state);
-        mgr.up(&id).await.unwrap();
+        mgr.rebuild(&id, false).await.unwrap();
 
-        // initializeCommand ran on host
         assert!(
             marker.exists(),
             "initializeCommand marker file should exist"
@@ -3046,38 +5612,37 @@ mod tests {
 
         let recorded = calls.lock().unwrap();
 
-        // initializeCommand ran before any provider call (marker file exists and
-        // first provider call is Pull, confirming host command ran first)
-        assert!(
-            matches!(&recorded[0], MockCall::Pull { image } if image == "ubuntu:22.04"),
-            "First provider call should be Pull; got {:?}",
-            &recorded[0]
-        );
+        // Phase 1: Down
+        let stop_idx = recorded
+            .iter()
+            .position(|c| matches!(c, MockCall::Stop { .. }))
+            .unwrap();
+        let remove_idx = recorded
+            .iter()
+            .position(|c| matches!(c, MockCall::Remove { .. }))
+            .unwrap();
 
-        // Verify the provider call order: Pull → RemoveByName → Create → lifecycle execs → start phase
-        let call_types: Vec<&str> = recorded
+        // Phase 2: Build (Pull)
+        let pull_idx = recorded
             .iter()
-            .map(|c| match c {
-                MockCall::Pull { .. } => "Pull",
-                MockCall::Create { .. } => "Create",
-                MockCall::Start { .. } => "Start",
-                MockCall::Inspect { .. } => "Inspect",
-                MockCall::Exec { .. } => "Exec",
-                MockCall::RemoveByName { .. } => "RemoveByName",
-                _ => "Other",
-            })
-            .collect();
+            .position(|c| matches!(c, MockCall::Pull { .. }))
+            .unwrap();
 
-        // Pull must come before Create
-        let pull_idx = call_types.iter().position(|&t| t == "Pull").unwrap();
-        let create_idx = call_types.iter().position(|&t| t == "Create").unwrap();
-        assert!(pull_idx < create_idx, "Pull must come before Create");
+        // Phase 3: Create
+        let create_idx = recorded
+            .iter()
+            .position(|c| matches!(c, MockCall::Create { .. }))
+            .unwrap();
 
-        // Verify exec commands run in the correct lifecycle order
-        let execs = exec_commands(&recorded);
-        assert!(execs.len() >= 4, "Expected at least 4 exec calls (onCreate, updateContent, postCreate, postStart), got {}", execs.len());
+        // Verify overall phase ordering: Down → Build → Create → lifecycle execs
+        assert!(stop_idx < remove_idx, "Stop before Remove");
+        assert!(remove_idx < pull_idx, "Remove before Pull");
+        assert!(pull_idx < create_idx, "Pull before Create");
 
+        // Verify exec commands run in correct lifecycle order
+        let execs = exec_commands(&recorded);
         let lifecycle_cmds: Vec<&str> = execs.iter().map(|cmd| shell_cmd(cmd)).collect();
+
         let on_create_idx = lifecycle_cmds
             .iter()
             .position(|&c| c == "echo on-create")
@@ -3097,36 +5662,160 @@ mod tests {
 
         assert!(
             on_create_idx < update_content_idx,
-            "onCreateCommand must run before updateContentCommand"
+            "onCreate before updateContent"
         );
         assert!(
             update_content_idx < post_create_idx,
-            "updateContentCommand must run before postCreateCommand"
+            "updateContent before postCreate"
         );
         assert!(
             post_create_idx < post_start_idx,
-            "postCreateCommand must run before postStartCommand"
+            "postCreate before postStart"
+        );
+
+        // postAttachCommand must NOT run during rebuild
+        assert!(
+            !lifecycle_cmds.contains(&"echo post-attach"),
+            "postAttachCommand should NOT run during rebuild"
+        );
+
+        // All execs must come after Create
+        let first_exec_overall = recorded
+            .iter()
+            .position(|c| matches!(c, MockCall::Exec { .. }))
+            .unwrap();
+        assert!(
+            create_idx < first_exec_overall,
+            "Create must come before any Exec"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_start_runs_post_start_not_on_create() {
+        let (workspace, _marker) = create_lifecycle_workspace();
+        let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
+
+        let mut state = StateStore::new();
+        // Stopped container — already existed, not freshly Created
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Stopped,
+            Some("sha256:img"),
+            Some("container123"),
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_no_creds(mock, state);
+        mgr.start(&id).await.unwrap();
+
+        let recorded = calls.lock().unwrap();
+        let execs = exec_commands(&recorded);
+        let lifecycle_cmds: Vec<&str> = execs.iter().map(|cmd| shell_cmd(cmd)).collect();
+
+        // postStartCommand should run
+        assert!(
+            lifecycle_cmds.contains(&"echo post-start"),
+            "postStartCommand should run on start; got {:?}",
+            lifecycle_cmds
+        );
+
+        // onCreateCommand should NOT run (container was Stopped, not Created)
+        assert!(
+            !lifecycle_cmds.contains(&"echo on-create"),
+            "onCreateCommand should NOT run on start of existing container"
+        );
+
+        // updateContentCommand and postCreateCommand should NOT run either
+        assert!(
+            !lifecycle_cmds.contains(&"echo update-content"),
+            "updateContentCommand should NOT run on start"
+        );
+        assert!(
+            !lifecycle_cmds.contains(&"echo post-create"),
+            "postCreateCommand should NOT run on start"
+        );
+
+        // postAttachCommand should NOT run during start
+        assert!(
+            !lifecycle_cmds.contains(&"echo post-attach"),
+            "postAttachCommand should NOT run on start"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_post_start_streams_output() {
+        let (workspace, _marker) = create_lifecycle_workspace();
+        let mock = MockProvider::new(ProviderType::Docker);
+        // Every exec emits this on the progress channel via exec_with_progress.
+        *mock.exec_output.lock().unwrap() = "post-start-output".to_string();
+
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Stopped,
+            Some("sha256:img"),
+            Some("container123"),
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_no_creds(mock, state);
+        let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+        mgr.start_with_channels(&id, None, Some(&tx)).await.unwrap();
+        drop(tx);
+
+        let mut lines = Vec::new();
+        while let Some(line) = rx.recv().await {
+            lines.push(line);
+        }
+
+        assert!(
+            lines.iter().any(|l| l == "[postStart] post-start-output"),
+            "expected `[postStart] post-start-output` in streamed output, got: {:?}",
+            lines
         );
+    }
 
-        // postAttachCommand must NOT run during up
-        assert!(
-            !lifecycle_cmds.contains(&"echo post-attach"),
-            "postAttachCommand should NOT run during up"
+    #[tokio::test]
+    async fn test_post_start_streams_feature_output() {
+        let (workspace, _marker, feature_json) = create_lifecycle_workspace_with_features();
+        let mock = MockProvider::new(ProviderType::Docker);
+        *mock.exec_output.lock().unwrap() = "feat-ps-output".to_string();
+
+        let mut state = StateStore::new();
+        let cs = make_container_state_with_features(
+            workspace.path(),
+            DevcContainerStatus::Stopped,
+            Some("sha256:img"),
+            Some("container123"),
+            &feature_json,
         );
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_no_creds(mock, state);
+        let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+        mgr.start_with_channels(&id, None, Some(&tx)).await.unwrap();
+        drop(tx);
+
+        let mut lines = Vec::new();
+        while let Some(line) = rx.recv().await {
+            lines.push(line);
+        }
 
-        // All execs happen after Create
-        let first_exec_overall = recorded
-            .iter()
-            .position(|c| matches!(c, MockCall::Exec { .. }))
-            .unwrap();
         assert!(
-            create_idx < first_exec_overall,
-            "Create must come before any Exec"
+            lines
+                .iter()
+                .any(|l| l == "[feature:postStart] feat-ps-output"),
+            "expected `[feature:postStart] feat-ps-output` line, got: {:?}",
+            lines
         );
     }
 
     #[tokio::test]
-    async fn test_up_credentials_before_lifecycle() {
+    async fn test_post_attach_command_runs() {
         let (workspace, _marker) = create_lifecycle_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
         let calls = mock.calls.clone();
@@ -3134,143 +5823,227 @@ mod tests {
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Configured,
-            None,
-            None,
+            DevcContainerStatus::Running,
+            Some("sha256:img"),
+            Some("container123"),
         );
         let id = cs.id.clone();
         state.add(cs);
 
-        // Use test_manager_with_state (credentials enabled by default)
-        let mgr = test_manager_with_state(mock, state);
-        mgr.up(&id).await.unwrap();
+        let mgr = test_manager_no_creds(mock, state);
+        mgr.run_post_attach_command(&id).await.unwrap();
 
         let recorded = calls.lock().unwrap();
         let execs = exec_commands(&recorded);
-        let cmds: Vec<&str> = execs.iter().map(|cmd| shell_cmd(cmd)).collect();
-
-        // Credential-related exec calls (contain "devc-creds") must appear
-        // before lifecycle exec calls (echo on-create, etc.)
-        let first_cred_idx = cmds
-            .iter()
-            .position(|c| c.contains("devc-creds"))
-            .expect("Expected credential injection exec calls");
-        let first_lifecycle_idx = cmds
-            .iter()
-            .position(|c| *c == "echo on-create")
-            .expect("Expected onCreateCommand exec call");
+        let lifecycle_cmds: Vec<&str> = execs.iter().map(|cmd| shell_cmd(cmd)).collect();
 
         assert!(
-            first_cred_idx < first_lifecycle_idx,
-            "Credential injection (index {}) must run before lifecycle commands (index {})\nExec order: {:?}",
-            first_cred_idx,
-            first_lifecycle_idx,
-            cmds
+            lifecycle_cmds.contains(&"echo post-attach"),
+            "postAttachCommand should have run; got {:?}",
+            lifecycle_cmds
         );
+
+        // No other lifecycle commands should run
+        assert!(!lifecycle_cmds.contains(&"echo on-create"));
+        assert!(!lifecycle_cmds.contains(&"echo update-content"));
+        assert!(!lifecycle_cmds.contains(&"echo post-create"));
+        assert!(!lifecycle_cmds.contains(&"echo post-start"));
     }
 
     #[tokio::test]
-    async fn test_rebuild_runs_initialize_before_build() {
-        let (workspace, marker) = create_lifecycle_workspace();
+    async fn test_post_attach_streams_output() {
+        let (workspace, _marker) = create_lifecycle_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
-        let calls = mock.calls.clone();
+        *mock.exec_output.lock().unwrap() = "post-attach-output".to_string();
 
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
             DevcContainerStatus::Running,
-            Some("sha256:old_image"),
-            Some("old_container_123"),
+            Some("sha256:img"),
+            Some("container123"),
         );
         let id = cs.id.clone();
         state.add(cs);
 
         let mgr = test_manager_no_creds(mock, state);
-        mgr.rebuild(&id, false).await.unwrap();
+        let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+        mgr.run_post_attach_command_with_output(&id, Some(&tx))
+            .await
+            .unwrap();
+        drop(tx);
+
+        let mut lines = Vec::new();
+        while let Some(line) = rx.recv().await {
+            lines.push(line);
+        }
 
-        // initializeCommand marker must exist
         assert!(
-            marker.exists(),
-            "initializeCommand marker file should exist"
+            lines.iter().any(|l| l == "[postAttach] post-attach-output"),
+            "expected `[postAttach] post-attach-output` line, got: {:?}",
+            lines
         );
+    }
 
-        let recorded = calls.lock().unwrap();
+    #[tokio::test]
+    async fn test_post_attach_streams_feature_output() {
+        let (workspace, _marker, feature_json) = create_lifecycle_workspace_with_features();
+        let mock = MockProvider::new(ProviderType::Docker);
+        *mock.exec_output.lock().unwrap() = "feat-pa-output".to_string();
 
-        // Down phase (Stop + Remove) must come before Pull (build)
-        let stop_idx = recorded
-            .iter()
-            .position(|c| matches!(c, MockCall::Stop { .. }))
-            .unwrap();
-        let remove_idx = recorded
-            .iter()
-            .position(|c| matches!(c, MockCall::Remove { .. }))
-            .unwrap();
-        let pull_idx = recorded
-            .iter()
-            .position(|c| matches!(c, MockCall::Pull { .. }))
+        let mut state = StateStore::new();
+        let cs = make_container_state_with_features(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            Some("sha256:img"),
+            Some("container123"),
+            &feature_json,
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_no_creds(mock, state);
+        let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+        mgr.run_post_attach_command_with_output(&id, Some(&tx))
+            .await
             .unwrap();
+        drop(tx);
+
+        let mut lines = Vec::new();
+        while let Some(line) = rx.recv().await {
+            lines.push(line);
+        }
 
-        assert!(stop_idx < pull_idx, "Stop must come before Pull (build)");
         assert!(
-            remove_idx < pull_idx,
-            "Remove must come before Pull (build)"
+            lines
+                .iter()
+                .any(|l| l == "[feature:postAttach] feat-pa-output"),
+            "expected `[feature:postAttach] feat-pa-output` line, got: {:?}",
+            lines
         );
     }
 
     #[tokio::test]
-    async fn test_rebuild_with_progress_runs_initialize_before_build() {
-        let (workspace, marker) = create_lifecycle_workspace();
+    async fn test_up_on_create_only_on_first_create() {
+        let (workspace, _marker) = create_lifecycle_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
         let calls = mock.calls.clone();
 
         let mut state = StateStore::new();
+        // Start as Configured with no image/container — will go through full up flow
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Running,
-            Some("sha256:old_image"),
-            Some("old_container_123"),
+            DevcContainerStatus::Configured,
+            None,
+            None,
         );
         let id = cs.id.clone();
         state.add(cs);
 
         let mgr = test_manager_no_creds(mock, state);
-        let (tx, _rx) = mpsc::unbounded_channel();
-        mgr.rebuild_with_progress(&id, false, tx, None)
-            .await
-            .unwrap();
 
-        // initializeCommand marker must exist
+        // First up: full lifecycle including onCreate
+        mgr.up(&id).await.unwrap();
+
+        let recorded_after_up = calls.lock().unwrap().clone();
+        let execs_after_up = exec_commands(&recorded_after_up);
+        let cmds_after_up: Vec<&str> = execs_after_up.iter().map(|cmd| shell_cmd(cmd)).collect();
+
         assert!(
-            marker.exists(),
-            "initializeCommand marker file should exist"
+            cmds_after_up.contains(&"echo on-create"),
+            "onCreate should run on first up"
+        );
+        assert!(
+            cmds_after_up.contains(&"echo post-create"),
+            "postCreate should run on first up"
         );
 
-        let recorded = calls.lock().unwrap();
-
-        // Down phase (Stop + Remove) must come before Pull (build)
-        let stop_idx = recorded
-            .iter()
-            .position(|c| matches!(c, MockCall::Stop { .. }))
-            .unwrap();
-        let remove_idx = recorded
-            .iter()
-            .position(|c| matches!(c, MockCall::Remove { .. }))
-            .unwrap();
-        let pull_idx = recorded
+        let on_create_count = cmds_after_up
             .iter()
-            .position(|c| matches!(c, MockCall::Pull { .. }))
-            .unwrap();
+            .filter(|&&c| c == "echo on-create")
+            .count();
+        assert_eq!(
+            on_create_count, 1,
+            "onCreateCommand should run exactly once during up"
+        );
+
+        // Now call start — should NOT re-run onCreate/postCreate
+        calls.lock().unwrap().clear();
+        mgr.start(&id).await.unwrap();
+
+        let recorded_after_start = calls.lock().unwrap().clone();
+        let execs_after_start = exec_commands(&recorded_after_start);
+        let cmds_after_start: Vec<&str> =
+            execs_after_start.iter().map(|cmd| shell_cmd(cmd)).collect();
+
+        assert!(
+            !cmds_after_start.contains(&"echo on-create"),
+            "onCreateCommand should NOT run on subsequent start"
+        );
+        assert!(
+            !cmds_after_start.contains(&"echo post-create"),
+            "postCreateCommand should NOT run on subsequent start"
+        );
+        // postStartCommand should still run
+        assert!(
+            cmds_after_start.contains(&"echo post-start"),
+            "postStartCommand should run on start"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_up_until_running_defers_lifecycle_to_next_up() {
+        let (workspace, _marker) = create_lifecycle_workspace();
+        let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
+
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Configured,
+            None,
+            None,
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_no_creds(mock, state);
+        mgr.up_until_running(&id, None).await.unwrap();
+
+        {
+            let recorded = calls.lock().unwrap();
+            assert!(recorded
+                .iter()
+                .any(|c| matches!(c, MockCall::Create { .. })));
+            let execs = exec_commands(&recorded);
+            let cmds: Vec<&str> = execs.iter().map(|cmd| shell_cmd(cmd)).collect();
+            assert!(!cmds.contains(&"echo on-create"));
+            assert!(!cmds.contains(&"echo post-start"));
+        }
+        // Running, with the first-create phase left for the follow-up up
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert_eq!(cs.status, DevcContainerStatus::Running);
+        assert!(cs.lifecycle_pending());
 
-        assert!(stop_idx < pull_idx, "Stop must come before Pull (build)");
-        assert!(
-            remove_idx < pull_idx,
-            "Remove must come before Pull (build)"
-        );
+        calls.lock().unwrap().clear();
+        mgr.up(&id).await.unwrap();
+        assert!(!mgr.get(&id).await.unwrap().unwrap().lifecycle_pending());
+
+        let recorded = calls.lock().unwrap();
+        assert!(!recorded
+            .iter()
+            .any(|c| matches!(c, MockCall::Build { .. } | MockCall::Create { .. })));
+        let execs = exec_commands(&recorded);
+        let cmds: Vec<&str> = execs.iter().map(|cmd| shell_cmd(cmd)).collect();
+        assert!(cmds.contains(&"echo on-create"));
+        assert!(cmds.contains(&"echo post-start"));
     }
 
+    // ==================== New Lifecycle Tests ====================
+
     #[tokio::test]
-    async fn test_rebuild_full_lifecycle_order() {
-        let (workspace, marker) = create_lifecycle_workspace();
+    async fn test_pause_and_unpause() {
+        let (workspace, _marker) = create_lifecycle_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
         let calls = mock.calls.clone();
 
@@ -3278,111 +6051,62 @@ mod tests {
         let cs = make_container_state(
             workspace.path(),
             DevcContainerStatus::Running,
-            Some("sha256:old_image"),
-            Some("old_container_123"),
+            Some("sha256:img"),
+            Some("container123"),
         );
         let id = cs.id.clone();
         state.add(cs);
 
         let mgr = test_manager_no_creds(mock, state);
-        mgr.rebuild(&id, false).await.unwrap();
+        mgr.pause(&id).await.unwrap();
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert_eq!(cs.status, DevcContainerStatus::Paused);
+        assert!(mgr.pause(&id).await.is_err(), "already paused");
 
-        assert!(
-            marker.exists(),
-            "initializeCommand marker file should exist"
-        );
+        mgr.unpause(&id).await.unwrap();
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert_eq!(cs.status, DevcContainerStatus::Running);
+        assert!(mgr.unpause(&id).await.is_err(), "not paused");
 
         let recorded = calls.lock().unwrap();
-
-        // Phase 1: Down
-        let stop_idx = recorded
-            .iter()
-            .position(|c| matches!(c, MockCall::Stop { .. }))
-            .unwrap();
-        let remove_idx = recorded
-            .iter()
-            .position(|c| matches!(c, MockCall::Remove { .. }))
-            .unwrap();
-
-        // Phase 2: Build (Pull)
-        let pull_idx = recorded
-            .iter()
-            .position(|c| matches!(c, MockCall::Pull { .. }))
-            .unwrap();
-
-        // Phase 3: Create
-        let create_idx = recorded
-            .iter()
-            .position(|c| matches!(c, MockCall::Create { .. }))
-            .unwrap();
-
-        // Verify overall phase ordering: Down → Build → Create → lifecycle execs
-        assert!(stop_idx < remove_idx, "Stop before Remove");
-        assert!(remove_idx < pull_idx, "Remove before Pull");
-        assert!(pull_idx < create_idx, "Pull before Create");
-
-        // Verify exec commands run in correct lifecycle order
-        let execs = exec_commands(&recorded);
-        let lifecycle_cmds: Vec<&str> = execs.iter().map(|cmd| shell_cmd(cmd)).collect();
-
-        let on_create_idx = lifecycle_cmds
-            .iter()
-            .position(|&c| c == "echo on-create")
-            .expect("onCreateCommand should have run");
-        let update_content_idx = lifecycle_cmds
-            .iter()
-            .position(|&c| c == "echo update-content")
-            .expect("updateContentCommand should have run");
-        let post_create_idx = lifecycle_cmds
-            .iter()
-            .position(|&c| c == "echo post-create")
-            .expect("postCreateCommand should have run");
-        let post_start_idx = lifecycle_cmds
+        assert!(recorded
             .iter()
-            .position(|&c| c == "echo post-start")
-            .expect("postStartCommand should have run");
-
-        assert!(
-            on_create_idx < update_content_idx,
-            "onCreate before updateContent"
-        );
-        assert!(
-            update_content_idx < post_create_idx,
-            "updateContent before postCreate"
-        );
-        assert!(
-            post_create_idx < post_start_idx,
-            "postCreate before postStart"
-        );
-
-        // postAttachCommand must NOT run during rebuild
-        assert!(
-            !lifecycle_cmds.contains(&"echo post-attach"),
-            "postAttachCommand should NOT run during rebuild"
-        );
-
-        // All execs must come after Create
-        let first_exec_overall = recorded
+            .any(|c| matches!(c, MockCall::Pause { id } if id == "container123")));
+        assert!(recorded
             .iter()
-            .position(|c| matches!(c, MockCall::Exec { .. }))
-            .unwrap();
-        assert!(
-            create_idx < first_exec_overall,
-            "Create must come before any Exec"
-        );
+            .any(|c| matches!(c, MockCall::Unpause { id } if id == "container123")));
+    }
+
+    /// An image as `devc freeze` leaves it, labelled like its source container
+    fn frozen_image(tag: &str, cs: &ContainerState) -> ImageInfo {
+        ImageInfo {
+            tag: tag.to_string(),
+            labels: HashMap::from([
+                ("devc.managed".to_string(), "true".to_string()),
+                ("devc.project".to_string(), cs.name.clone()),
+                (
+                    "devc.config".to_string(),
+                    cs.config_path.to_string_lossy().to_string(),
+                ),
+                (
+                    "devc.workspace".to_string(),
+                    cs.workspace_path.to_string_lossy().to_string(),
+                ),
+            ]),
+        }
     }
 
     #[tokio::test]
-    async fn test_start_runs_post_start_not_on_create() {
+    async fn test_freeze_then_thaw_creates_separate_container() {
         let (workspace, _marker) = create_lifecycle_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
         let calls = mock.calls.clone();
+        let images = mock.list_images_result.clone();
 
         let mut state = StateStore::new();
-        // Stopped container — already existed, not freshly Created
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Stopped,
+            DevcContainerStatus::Running,
             Some("sha256:img"),
             Some("container123"),
         );
@@ -3390,53 +6114,52 @@ mod tests {
         state.add(cs);
 
         let mgr = test_manager_no_creds(mock, state);
-        mgr.start(&id).await.unwrap();
-
-        let recorded = calls.lock().unwrap();
-        let execs = exec_commands(&recorded);
-        let lifecycle_cmds: Vec<&str> = execs.iter().map(|cmd| shell_cmd(cmd)).collect();
-
-        // postStartCommand should run
-        assert!(
-            lifecycle_cmds.contains(&"echo post-start"),
-            "postStartCommand should run on start; got {:?}",
-            lifecycle_cmds
-        );
-
-        // onCreateCommand should NOT run (container was Stopped, not Created)
-        assert!(
-            !lifecycle_cmds.contains(&"echo on-create"),
-            "onCreateCommand should NOT run on start of existing container"
-        );
-
-        // updateContentCommand and postCreateCommand should NOT run either
-        assert!(
-            !lifecycle_cmds.contains(&"echo update-content"),
-            "updateContentCommand should NOT run on start"
-        );
-        assert!(
-            !lifecycle_cmds.contains(&"echo post-create"),
-            "postCreateCommand should NOT run on start"
+        let tag = mgr.freeze(&id).await.unwrap();
+        assert!(tag.starts_with("devc/test:frozen-"), "{}", tag);
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert_eq!(cs.frozen_images(), vec![tag.clone()]);
+        *images.lock().unwrap() = Ok(vec![frozen_image(&tag, &cs)]);
+
+        let thawed = mgr.thaw("repro", &tag).await.unwrap();
+        assert_eq!(thawed.status, DevcContainerStatus::Created);
+        assert_eq!(thawed.image_id.as_deref(), Some(tag.as_str()));
+        assert_eq!(thawed.config_path, cs.config_path);
+        assert!(thawed.provider_pinned());
+        assert_eq!(
+            thawed
+                .metadata
+                .get(THAWED_FROM_METADATA_KEY)
+                .map(String::as_str),
+            Some("test")
         );
 
-        // postAttachCommand should NOT run during start
-        assert!(
-            !lifecycle_cmds.contains(&"echo post-attach"),
-            "postAttachCommand should NOT run on start"
-        );
+        let recorded = calls.lock().unwrap();
+        assert!(recorded.iter().any(
+            |c| matches!(c, MockCall::Commit { id, tag: t } if id == "container123" && *t == tag)
+        ));
+        // The new container gets its own runtime name, leaving the original alone
+        let runtime_name = format!("devc_repro__{}", thawed.short_id());
+        assert!(recorded.iter().any(|c| matches!(
+            c,
+            MockCall::Create { image, name, .. }
+                if *image == tag && name.as_deref() == Some(runtime_name.as_str())
+        )));
+        assert!(recorded
+            .iter()
+            .all(|c| !matches!(c, MockCall::Remove { .. } | MockCall::Stop { .. })));
     }
 
     #[tokio::test]
-    async fn test_post_start_streams_output() {
+    async fn test_thawed_container_skips_create_commands() {
         let (workspace, _marker) = create_lifecycle_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
-        // Every exec emits this on the progress channel via exec_with_progress.
-        *mock.exec_output.lock().unwrap() = "post-start-output".to_string();
+        let calls = mock.calls.clone();
+        let images = mock.list_images_result.clone();
 
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Stopped,
+            DevcContainerStatus::Running,
             Some("sha256:img"),
             Some("container123"),
         );
@@ -3444,104 +6167,213 @@ mod tests {
         state.add(cs);
 
         let mgr = test_manager_no_creds(mock, state);
-        let (tx, mut rx) = mpsc::unbounded_channel::<String>();
-        mgr.start_with_channels(&id, None, Some(&tx)).await.unwrap();
-        drop(tx);
+        let tag = mgr.freeze(&id).await.unwrap();
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        *images.lock().unwrap() = Ok(vec![frozen_image(&tag, &cs)]);
+        let thawed = mgr.thaw("repro", &tag).await.unwrap();
+        assert!(thawed.is_thawed());
+        calls.lock().unwrap().clear();
 
-        let mut lines = Vec::new();
-        while let Some(line) = rx.recv().await {
-            lines.push(line);
+        mgr.up(&thawed.id).await.unwrap();
+
+        let recorded = calls.lock().unwrap();
+        let execs = exec_commands(&recorded);
+        let cmds: Vec<&str> = execs.iter().map(|cmd| shell_cmd(cmd)).collect();
+        for cmd in ["echo on-create", "echo update-content", "echo post-create"] {
+            assert!(!cmds.contains(&cmd), "{} should not rerun", cmd);
         }
+        assert!(cmds.contains(&"echo post-start"));
+    }
 
-        assert!(
-            lines.iter().any(|l| l == "[postStart] post-start-output"),
-            "expected `[postStart] post-start-output` in streamed output, got: {:?}",
-            lines
+    #[tokio::test]
+    async fn test_thaw_finds_snapshot_of_forgotten_container() {
+        let (workspace, _marker) = create_lifecycle_workspace();
+        let source = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            Some("sha256:img"),
+            Some("container123"),
+        );
+        let tag = "devc/test:frozen-20240101-000000";
+        let mock = MockProvider::new(ProviderType::Docker);
+        *mock.list_images_result.lock().unwrap() = Ok(vec![frozen_image(tag, &source)]);
+
+        // The source container is no longer tracked
+        let mgr = test_manager_no_creds(mock, StateStore::new());
+        let thawed = mgr.thaw("repro", tag).await.unwrap();
+        assert_eq!(thawed.config_path, source.config_path);
+        assert_eq!(thawed.workspace_path, source.workspace_path);
+        assert_eq!(thawed.provider, ProviderType::Docker);
+        assert_eq!(
+            thawed
+                .metadata
+                .get(THAWED_FROM_METADATA_KEY)
+                .map(String::as_str),
+            Some("test")
         );
     }
 
     #[tokio::test]
-    async fn test_post_start_streams_feature_output() {
-        let (workspace, _marker, feature_json) = create_lifecycle_workspace_with_features();
+    async fn test_freeze_rejects_compose_project() {
+        let (workspace, _marker) = create_lifecycle_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
-        *mock.exec_output.lock().unwrap() = "feat-ps-output".to_string();
+        let calls = mock.calls.clone();
 
         let mut state = StateStore::new();
-        let cs = make_container_state_with_features(
+        let mut cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Stopped,
-            Some("sha256:img"),
+            DevcContainerStatus::Running,
+            Some("compose"),
             Some("container123"),
-            &feature_json,
         );
+        cs.compose_project = Some("proj".to_string());
         let id = cs.id.clone();
         state.add(cs);
 
         let mgr = test_manager_no_creds(mock, state);
-        let (tx, mut rx) = mpsc::unbounded_channel::<String>();
-        mgr.start_with_channels(&id, None, Some(&tx)).await.unwrap();
-        drop(tx);
-
-        let mut lines = Vec::new();
-        while let Some(line) = rx.recv().await {
-            lines.push(line);
-        }
-
-        assert!(
-            lines
-                .iter()
-                .any(|l| l == "[feature:postStart] feat-ps-output"),
-            "expected `[feature:postStart] feat-ps-output` line, got: {:?}",
-            lines
-        );
+        assert!(matches!(
+            mgr.freeze(&id).await,
+            Err(CoreError::InvalidState(_))
+        ));
+        assert!(calls
+            .lock()
+            .unwrap()
+            .iter()
+            .all(|c| !matches!(c, MockCall::Commit { .. })));
     }
 
     #[tokio::test]
-    async fn test_post_attach_command_runs() {
-        let (workspace, _marker) = create_lifecycle_workspace();
+    async fn test_prebuild_bakes_create_commands_into_image() {
+        let tmp = tempfile::tempdir().unwrap();
+        let devcontainer_dir = tmp.path().join(".devcontainer");
+        std::fs::create_dir_all(&devcontainer_dir).unwrap();
+        std::fs::write(
+            devcontainer_dir.join("devcontainer.json"),
+            r#"{
+                "image": "ubuntu:22.04",
+                "devc.prebuild": true,
+                "containerEnv": {"FOO": "bar"},
+                "mounts": ["source=devc-data,target=/data,type=volume"],
+                "labels": {"team": "web"},
+                "onCreateCommand": "echo on-create",
+                "updateContentCommand": "echo update-content",
+                "postCreateCommand": "echo post-create"
+            }"#,
+        )
+        .unwrap();
         let mock = MockProvider::new(ProviderType::Docker);
         let calls = mock.calls.clone();
 
         let mut state = StateStore::new();
-        let cs = make_container_state(
+        let cs = make_container_state(tmp.path(), DevcContainerStatus::Configured, None, None);
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_no_creds(mock, state);
+        mgr.up(&id).await.unwrap();
+
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert!(cs.is_prebuilt());
+        assert_eq!(cs.image_id.as_deref(), Some("sha256:mock_committed_id"));
+
+        let recorded = calls.lock().unwrap();
+        let commit_idx = recorded
+            .iter()
+            .position(|c| matches!(c, MockCall::Commit { tag, .. } if tag.ends_with(":prebuilt")))
+            .expect("prebuild should commit the temporary container");
+        // Only the workspace is mounted into the temporary container, so
+        // volumes can't be touched and only the filesystem is committed
+        let temp_create = recorded[..commit_idx]
+            .iter()
+            .find_map(|c| match c {
+                MockCall::Create {
+                    mount_targets,
+                    user_labels,
+                    ..
+                } => Some((mount_targets, user_labels)),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(temp_create.0.len(), 1);
+        assert!(!temp_create.0.contains(&"/data".to_string()));
+        assert!(temp_create.1.is_empty());
+        assert!(recorded
+            .iter()
+            .any(|c| matches!(c, MockCall::Remove { force: true, .. })));
+        assert!(recorded[commit_idx..].iter().any(
+            |c| matches!(c, MockCall::Create { image, .. } if image == "sha256:mock_committed_id")
+        ));
+
+        // onCreate/updateContent ran once, before the commit; postCreate after it
+        let execs: Vec<(usize, String)> = recorded
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| match c {
+                MockCall::Exec { cmd, .. } if cmd.len() == 3 => Some((i, cmd[2].clone())),
+                _ => None,
+            })
+            .collect();
+        for (cmd, before_commit) in [
+            ("echo on-create", true),
+            ("echo update-content", true),
+            ("echo post-create", false),
+        ] {
+            let runs: Vec<usize> = execs
+                .iter()
+                .filter(|(_, c)| c == cmd)
+                .map(|(i, _)| *i)
+                .collect();
+            assert_eq!(runs.len(), 1, "{} should run once", cmd);
+            assert_eq!(runs[0] < commit_idx, before_commit, "{}", cmd);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_thaw_rejects_unknown_image_and_taken_name() {
+        let (workspace, _marker) = create_lifecycle_workspace();
+        let mut state = StateStore::new();
+        let mut cs = make_container_state(
             workspace.path(),
             DevcContainerStatus::Running,
             Some("sha256:img"),
             Some("container123"),
         );
-        let id = cs.id.clone();
-        state.add(cs);
-
-        let mgr = test_manager_no_creds(mock, state);
-        mgr.run_post_attach_command(&id).await.unwrap();
-
-        let recorded = calls.lock().unwrap();
-        let execs = exec_commands(&recorded);
-        let lifecycle_cmds: Vec<&str> = execs.iter().map(|cmd| shell_cmd(cmd)).collect();
-
-        assert!(
-            lifecycle_cmds.contains(&"echo post-attach"),
-            "postAttachCommand should have run; got {:?}",
-            lifecycle_cmds
+        cs.metadata.insert(
+            FROZEN_IMAGES_METADATA_KEY.to_string(),
+            r#"["devc/test:frozen-1"]"#.to_string(),
         );
+        state.add(cs);
 
-        // No other lifecycle commands should run
-        assert!(!lifecycle_cmds.contains(&"echo on-create"));
-        assert!(!lifecycle_cmds.contains(&"echo update-content"));
-        assert!(!lifecycle_cmds.contains(&"echo post-create"));
-        assert!(!lifecycle_cmds.contains(&"echo post-start"));
+        let mgr = test_manager_no_creds(MockProvider::new(ProviderType::Docker), state);
+        assert!(matches!(
+            mgr.thaw("repro", "ubuntu:22.04").await,
+            Err(CoreError::InvalidState(_))
+        ));
+        assert!(matches!(
+            mgr.thaw("test", "devc/test:frozen-1").await,
+            Err(CoreError::ContainerExists(_))
+        ));
+        assert_eq!(mgr.list().await.unwrap().len(), 1);
     }
 
     #[tokio::test]
-    async fn test_post_attach_streams_output() {
+    async fn test_up_resumes_paused_container() {
         let (workspace, _marker) = create_lifecycle_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
-        *mock.exec_output.lock().unwrap() = "post-attach-output".to_string();
+        // Paused until unpaused, running after
+        mock.inspect_responses
+            .lock()
+            .unwrap()
+            .push(Ok(mock_container_details(
+                "container123",
+                ContainerStatus::Paused,
+            )));
+        let calls = mock.calls.clone();
 
         let mut state = StateStore::new();
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Running,
+            DevcContainerStatus::Paused,
             Some("sha256:img"),
             Some("container123"),
         );
@@ -3549,132 +6381,70 @@ mod tests {
         state.add(cs);
 
         let mgr = test_manager_no_creds(mock, state);
-        let (tx, mut rx) = mpsc::unbounded_channel::<String>();
-        mgr.run_post_attach_command_with_output(&id, Some(&tx))
-            .await
-            .unwrap();
-        drop(tx);
-
-        let mut lines = Vec::new();
-        while let Some(line) = rx.recv().await {
-            lines.push(line);
-        }
+        mgr.up(&id).await.unwrap();
 
-        assert!(
-            lines.iter().any(|l| l == "[postAttach] post-attach-output"),
-            "expected `[postAttach] post-attach-output` line, got: {:?}",
-            lines
-        );
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert_eq!(cs.status, DevcContainerStatus::Running);
+        let recorded = calls.lock().unwrap();
+        assert!(recorded
+            .iter()
+            .any(|c| matches!(c, MockCall::Unpause { id } if id == "container123")));
+        assert!(!recorded.iter().any(|c| matches!(c, MockCall::Start { .. })));
     }
 
     #[tokio::test]
-    async fn test_post_attach_streams_feature_output() {
-        let (workspace, _marker, feature_json) = create_lifecycle_workspace_with_features();
-        let mock = MockProvider::new(ProviderType::Docker);
-        *mock.exec_output.lock().unwrap() = "feat-pa-output".to_string();
-
+    async fn test_exec_context_rejects_paused_container() {
+        let (workspace, _marker) = create_lifecycle_workspace();
         let mut state = StateStore::new();
-        let cs = make_container_state_with_features(
+        let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Running,
+            DevcContainerStatus::Paused,
             Some("sha256:img"),
             Some("container123"),
-            &feature_json,
         );
         let id = cs.id.clone();
         state.add(cs);
 
-        let mgr = test_manager_no_creds(mock, state);
-        let (tx, mut rx) = mpsc::unbounded_channel::<String>();
-        mgr.run_post_attach_command_with_output(&id, Some(&tx))
-            .await
-            .unwrap();
-        drop(tx);
-
-        let mut lines = Vec::new();
-        while let Some(line) = rx.recv().await {
-            lines.push(line);
+        let mgr = test_manager_no_creds(MockProvider::new(ProviderType::Docker), state);
+        match mgr.prepare_exec_context(&id).await {
+            Err(CoreError::InvalidState(msg)) => assert!(msg.contains("paused"), "{}", msg),
+            other => panic!("expected a paused error, got {:?}", other.map(|_| ())),
         }
-
-        assert!(
-            lines
-                .iter()
-                .any(|l| l == "[feature:postAttach] feat-pa-output"),
-            "expected `[feature:postAttach] feat-pa-output` line, got: {:?}",
-            lines
-        );
     }
 
     #[tokio::test]
-    async fn test_up_on_create_only_on_first_create() {
+    async fn test_stop_unpauses_paused_container_first() {
         let (workspace, _marker) = create_lifecycle_workspace();
         let mock = MockProvider::new(ProviderType::Docker);
         let calls = mock.calls.clone();
 
         let mut state = StateStore::new();
-        // Start as Configured with no image/container — will go through full up flow
         let cs = make_container_state(
             workspace.path(),
-            DevcContainerStatus::Configured,
-            None,
-            None,
+            DevcContainerStatus::Paused,
+            Some("sha256:img"),
+            Some("container123"),
         );
         let id = cs.id.clone();
         state.add(cs);
 
         let mgr = test_manager_no_creds(mock, state);
+        mgr.stop(&id).await.unwrap();
 
-        // First up: full lifecycle including onCreate
-        mgr.up(&id).await.unwrap();
-
-        let recorded_after_up = calls.lock().unwrap().clone();
-        let execs_after_up = exec_commands(&recorded_after_up);
-        let cmds_after_up: Vec<&str> = execs_after_up.iter().map(|cmd| shell_cmd(cmd)).collect();
-
-        assert!(
-            cmds_after_up.contains(&"echo on-create"),
-            "onCreate should run on first up"
-        );
-        assert!(
-            cmds_after_up.contains(&"echo post-create"),
-            "postCreate should run on first up"
-        );
-
-        let on_create_count = cmds_after_up
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert_eq!(cs.status, DevcContainerStatus::Stopped);
+        let recorded = calls.lock().unwrap();
+        let unpause = recorded
             .iter()
-            .filter(|&&c| c == "echo on-create")
-            .count();
-        assert_eq!(
-            on_create_count, 1,
-            "onCreateCommand should run exactly once during up"
-        );
-
-        // Now call start — should NOT re-run onCreate/postCreate
-        calls.lock().unwrap().clear();
-        mgr.start(&id).await.unwrap();
-
-        let recorded_after_start = calls.lock().unwrap().clone();
-        let execs_after_start = exec_commands(&recorded_after_start);
-        let cmds_after_start: Vec<&str> =
-            execs_after_start.iter().map(|cmd| shell_cmd(cmd)).collect();
-
-        assert!(
-            !cmds_after_start.contains(&"echo on-create"),
-            "onCreateCommand should NOT run on subsequent start"
-        );
-        assert!(
-            !cmds_after_start.contains(&"echo post-create"),
-            "postCreateCommand should NOT run on subsequent start"
-        );
-        // postStartCommand should still run
-        assert!(
-            cmds_after_start.contains(&"echo post-start"),
-            "postStartCommand should run on start"
-        );
+            .position(|c| matches!(c, MockCall::Unpause { .. }))
+            .expect("unpause before stop");
+        let stop = recorded
+            .iter()
+            .position(|c| matches!(c, MockCall::Stop { .. }))
+            .unwrap();
+        assert!(unpause < stop);
     }
 
-    // ==================== New Lifecycle Tests ====================
-
     #[tokio::test]
     async fn test_stop_runs_no_lifecycle_commands() {
         let (workspace, _marker) = create_lifecycle_workspace();
@@ -4175,6 +6945,55 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_create_phase_commands_use_lifecycle_user() {
+        let (workspace, _marker, feature_json) = create_lifecycle_workspace_with_features();
+        let config_path = workspace.path().join(".devcontainer/devcontainer.json");
+        let config = std::fs::read_to_string(&config_path).unwrap().replace(
+            r#""remoteUser": "devuser","#,
+            r#""remoteUser": "devuser", "devc.lifecycleUser": "root","#,
+        );
+        std::fs::write(&config_path, config).unwrap();
+
+        let mock = MockProvider::new(ProviderType::Docker);
+        let calls = mock.calls.clone();
+
+        let mut state = StateStore::new();
+        let cs = make_container_state_with_features(
+            workspace.path(),
+            DevcContainerStatus::Built,
+            Some("sha256:mock_image_id"),
+            None,
+            &feature_json,
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = test_manager_no_creds(mock, state);
+        mgr.up(&id).await.unwrap();
+
+        let recorded = calls.lock().unwrap();
+        let mut checked = 0;
+        for call in recorded.iter() {
+            if let MockCall::Exec { cmd, user, .. } = call {
+                let shell = shell_cmd(cmd);
+                let expected = if shell.ends_with("-on-create")
+                    || shell.ends_with("-update-content")
+                    || shell.ends_with("-post-create")
+                {
+                    "root"
+                } else if shell.ends_with("-post-start") {
+                    "devuser"
+                } else {
+                    continue;
+                };
+                assert_eq!(user.as_deref(), Some(expected), "wrong user for {}", shell);
+                checked += 1;
+            }
+        }
+        assert_eq!(checked, 8, "all create and start phase commands should run");
+    }
+
     // ==================== Duplicate Name Lookup ====================
 
     #[tokio::test]
@@ -4375,6 +7194,102 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_pinned_provider_survives_default_change_on_rebuild() {
+        let workspace = create_test_workspace();
+        let podman_mock = MockProvider::new(ProviderType::Podman);
+        let docker_mock = MockProvider::new(ProviderType::Docker);
+        let docker_calls = docker_mock.calls.clone();
+        let podman_calls = podman_mock.calls.clone();
+
+        let mut state = StateStore::new();
+        let mut cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Built,
+            Some("img123"),
+            None,
+        );
+        cs.provider = ProviderType::Docker;
+        let id = cs.id.clone();
+        state.add(cs);
+
+        let mgr = ContainerManager::new_for_testing_multi(
+            vec![Box::new(podman_mock), Box::new(docker_mock)],
+            ProviderType::Podman,
+            GlobalConfig::default(),
+            state,
+        );
+        mgr.pin_provider(&id, ProviderType::Docker).await.unwrap();
+        mgr.rebuild(&id, false).await.unwrap();
+
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert_eq!(cs.provider, ProviderType::Docker);
+        assert!(cs.provider_pinned());
+        assert!(docker_calls
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|c| matches!(c, MockCall::Create { .. })));
+        assert!(!podman_calls
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|c| matches!(c, MockCall::Create { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_pin_provider_moves_unstarted_container() {
+        let workspace = create_test_workspace();
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Built,
+            Some("img123"),
+            None,
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+        let mgr = test_manager_with_state(MockProvider::new(ProviderType::Docker), state);
+
+        mgr.pin_provider(&id, ProviderType::Podman).await.unwrap();
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert_eq!(cs.provider, ProviderType::Podman);
+        assert_eq!(cs.image_id, None);
+        assert_eq!(cs.status, DevcContainerStatus::Configured);
+
+        // Podman isn't connected, so operations fail instead of using Docker
+        let err = mgr.build(&id).await.unwrap_err();
+        assert!(
+            err.to_string().contains("pinned to podman"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pin_provider_refuses_with_runtime_container() {
+        let workspace = create_test_workspace();
+        let mut state = StateStore::new();
+        let cs = make_container_state(
+            workspace.path(),
+            DevcContainerStatus::Running,
+            Some("img123"),
+            Some("ctr123"),
+        );
+        let id = cs.id.clone();
+        state.add(cs);
+        let mgr = test_manager_with_state(MockProvider::new(ProviderType::Docker), state);
+
+        let err = mgr
+            .pin_provider(&id, ProviderType::Podman)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("devc down"), "{}", err);
+        let cs = mgr.get(&id).await.unwrap().unwrap();
+        assert_eq!(cs.provider, ProviderType::Docker);
+        assert!(!cs.provider_pinned());
+    }
+
     // ==================== Adopt — remote_user metadata ====================
 
     /// Helper: create workspace with remoteUser in devcontainer.json
@@ -4674,7 +7589,7 @@ mod tests {
         state.add(cs);
 
         let mgr = test_manager_with_state(mock, state);
-        mgr.remove(&id, true).await.unwrap();
+        mgr.remove(&id, true, false).await.unwrap();
 
         {
             let recorded = calls.lock().unwrap();
@@ -4713,7 +7628,7 @@ mod tests {
         state.add(cs);
 
         let mgr = test_manager_with_state(mock, state);
-        mgr.remove(&id, true).await.unwrap();
+        mgr.remove(&id, true, false).await.unwrap();
 
         let recorded = calls.lock().unwrap();
         assert!(
@@ -5099,10 +8014,18 @@ mod tests {
     fn test_mock_assert_call_order() {
         let mock = MockProvider::new(ProviderType::Docker);
         mock.calls.lock().unwrap().extend(vec![
-            MockCall::Build { tag: "t".into() },
+            MockCall::Build {
+                tag: "t".into(),
+                build_args: HashMap::new(),
+                target: None,
+            },
             MockCall::Create {
                 image: "i".into(),
                 name: None,
+                gpus: None,
+                mount_targets: vec![],
+                user_labels: HashMap::new(),
+                user: None,
             },
             MockCall::Start { id: "x".into() },
             MockCall::Exec {
@@ -6,10 +6,12 @@
 //! # Security
 //!
 //! - SSH keys are stored in the user's data directory with 0600 permissions
-//! - Container host keys are ephemeral (generated per-container)
-//! - Host key verification is disabled for container connections since they are
-//!   over stdio (not network), but this means MITM protection relies on the
-//!   security of the container runtime's exec mechanism
+//! - Container host keys are generated once per devc container and kept in the
+//!   data directory, so a rebuild re-injects the same key instead of making a
+//!   new one
+//! - Host keys are recorded in a devc-owned `known_hosts` under a per-container
+//!   alias; connections run over the runtime's exec stdio (not the network), so
+//!   this catches a swapped container rather than a network MITM
 
 use crate::{CoreError, Result};
 use devc_config::GlobalConfig;
@@ -19,6 +21,61 @@ use std::ffi::OsString;
 use std::path::PathBuf;
 use std::process::Command;
 
+/// Create the dropbear host key if it is missing, then start dropbear on
+/// 127.0.0.1:2222 unless it is already running.
+///
+/// We run dropbear as a daemon (internal only) because inetd mode doesn't work
+/// over pipes from podman exec. The script prints `hostkey:generated` or
+/// `hostkey:reused`; see [`host_key_generated`].
+pub(crate) const DROPBEAR_START_SCRIPT: &str = r#"
+set -e
+mkdir -p /etc/dropbear
+if [ -f /etc/dropbear/dropbear_ed25519_host_key ]; then
+    echo "hostkey:reused"
+else
+    dropbearkey -t ed25519 -f /etc/dropbear/dropbear_ed25519_host_key >/dev/null 2>&1
+    echo "hostkey:generated"
+fi
+if ! pgrep -x dropbear >/dev/null 2>&1; then
+    /usr/sbin/dropbear -s -r /etc/dropbear/dropbear_ed25519_host_key -p 127.0.0.1:2222 2>/dev/null
+fi
+"#;
+
+/// Whether [`DROPBEAR_START_SCRIPT`] had to create the host key
+pub(crate) fn host_key_generated(output: &str) -> bool {
+    output.lines().any(|l| l.trim() == "hostkey:generated")
+}
+
+/// Print the dropbear host key's public line and the base64 private key as
+/// `hostkey-private:<base64>`
+const READ_HOST_KEY_SCRIPT: &str = r#"
+set -e
+dropbearkey -y -f /etc/dropbear/dropbear_ed25519_host_key | grep '^ssh-'
+echo "hostkey-private:$(base64 < /etc/dropbear/dropbear_ed25519_host_key | tr -d '\n')"
+"#;
+
+/// `known_hosts` alias for a devc container, used as ssh's `HostKeyAlias`
+pub fn host_key_alias(container_id: &str) -> String {
+    format!("devc-{}", container_id)
+}
+
+/// Replace (or add) the `known_hosts` entry for `alias`
+fn update_known_hosts(content: &str, alias: &str, public_key: Option<&str>) -> String {
+    let mut lines: Vec<&str> = content
+        .lines()
+        .filter(|l| l.split_whitespace().next() != Some(alias))
+        .collect();
+    let entry = public_key.map(|key| format!("{} {}", alias, key.trim()));
+    if let Some(ref entry) = entry {
+        lines.push(entry);
+    }
+    let mut out = lines.join("\n");
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out
+}
+
 /// Manages SSH keys and container SSH setup
 pub struct SshManager {
     /// Path to the private key
@@ -64,6 +121,45 @@ impl SshManager {
         &self.pub_key_path
     }
 
+    /// Directory holding the client keypair, host keys, and `known_hosts`
+    fn ssh_dir(&self) -> PathBuf {
+        self.key_path
+            .parent()
+            .map(PathBuf::from)
+            .unwrap_or_default()
+    }
+
+    /// devc-owned `known_hosts` with one entry per container host key
+    pub fn known_hosts_path(&self) -> PathBuf {
+        self.ssh_dir().join("known_hosts")
+    }
+
+    /// Where the dropbear host key of a devc container is kept between rebuilds
+    pub fn host_key_path(&self, container_id: &str) -> PathBuf {
+        self.ssh_dir()
+            .join("hosts")
+            .join(container_id)
+            .join("dropbear_ed25519_host_key")
+    }
+
+    /// Drop a container's saved host key and `known_hosts` entry
+    pub fn forget_host_key(&self, container_id: &str) -> Result<()> {
+        if let Some(dir) = self.host_key_path(container_id).parent() {
+            if dir.exists() {
+                std::fs::remove_dir_all(dir)?;
+            }
+        }
+        let known_hosts = self.known_hosts_path();
+        if known_hosts.exists() {
+            let content = std::fs::read_to_string(&known_hosts)?;
+            std::fs::write(
+                &known_hosts,
+                update_known_hosts(&content, &host_key_alias(container_id), None),
+            )?;
+        }
+        Ok(())
+    }
+
     /// Ensure SSH keypair exists, generating if necessary
     pub fn ensure_keys_exist(&self) -> Result<()> {
         if self.key_path.exists() && self.pub_key_path.exists() {
@@ -123,12 +219,16 @@ impl SshManager {
     ///
     /// This:
     /// 1. Installs dropbear if not present (should be pre-installed via enhanced build)
-    /// 2. Generates host key
+    /// 2. Restores the container's saved host key, or generates and saves one
     /// 3. Copies public key to authorized_keys
+    ///
+    /// `devc_id` is the devc container's ID: its host key is restored from, or
+    /// saved to, [`Self::host_key_path`] so it survives rebuilds.
     pub async fn setup_container(
         &self,
         provider: &dyn ContainerProvider,
         container_id: &ContainerId,
+        devc_id: &str,
         user: Option<&str>,
     ) -> Result<()> {
         let user = user.unwrap_or("root");
@@ -194,25 +294,31 @@ fi
                 })?;
         }
 
-        // Generate dropbear host key and start daemon
-        // We run dropbear as a daemon on 127.0.0.1:2222 (internal only)
-        // because inetd mode doesn't work over pipes from podman exec
-        let hostkey_script = r#"
-set -e
-mkdir -p /etc/dropbear
-if [ ! -f /etc/dropbear/dropbear_ed25519_host_key ]; then
-    dropbearkey -t ed25519 -f /etc/dropbear/dropbear_ed25519_host_key >/dev/null 2>&1
-fi
-# Start dropbear daemon if not already running
-if ! pgrep -x dropbear >/dev/null 2>&1; then
-    /usr/sbin/dropbear -s -r /etc/dropbear/dropbear_ed25519_host_key -p 127.0.0.1:2222 2>/dev/null
-fi
-"#;
+        // Restore the host key from an earlier build so clients keep trusting it
+        let host_key_path = self.host_key_path(devc_id);
+        if let Ok(saved) = std::fs::read(&host_key_path) {
+            // The private key goes over stdin so it never shows up in a process list
+            let restore_script = "set -e\nmkdir -p /etc/dropbear\numask 077\ncat > /etc/dropbear/dropbear_ed25519_host_key\nchmod 600 /etc/dropbear/dropbear_ed25519_host_key";
+            if let Err(e) = self
+                .exec_with_stdin(provider, container_id, restore_script, &saved, Some("root"))
+                .await
+            {
+                tracing::warn!("Failed to restore saved SSH host key: {}", e);
+            }
+        }
 
-        self.exec_in_container(provider, container_id, hostkey_script, Some("root"))
+        // Generate dropbear host key if still missing and start daemon
+        self.exec_in_container(provider, container_id, DROPBEAR_START_SCRIPT, Some("root"))
             .await
             .map_err(|e| CoreError::SshSetupError(format!("Failed to setup dropbear: {}", e)))?;
 
+        if let Err(e) = self
+            .save_host_key(provider, container_id, devc_id, &host_key_path)
+            .await
+        {
+            tracing::warn!("Failed to save SSH host key: {}", e);
+        }
+
         // Setup authorized_keys for the user
         // Use base64 encoding to safely pass the key content without shell escaping issues
         let home_dir = if user == "root" {
@@ -261,6 +367,50 @@ chown -R {user}:{user} {home}/.ssh 2>/dev/null || true
         Ok(())
     }
 
+    /// Keep the container's host key on the host and record it in `known_hosts`
+    async fn save_host_key(
+        &self,
+        provider: &dyn ContainerProvider,
+        container_id: &ContainerId,
+        devc_id: &str,
+        host_key_path: &std::path::Path,
+    ) -> Result<()> {
+        let output = self
+            .exec_output_in_container(provider, container_id, READ_HOST_KEY_SCRIPT, Some("root"))
+            .await?;
+        let public_key = output
+            .lines()
+            .find(|l| l.starts_with("ssh-"))
+            .ok_or_else(|| CoreError::SshSetupError("No public host key in output".into()))?;
+        let private_b64 = output
+            .lines()
+            .find_map(|l| l.strip_prefix("hostkey-private:"))
+            .ok_or_else(|| CoreError::SshSetupError("No private host key in output".into()))?;
+        let private = base64::Engine::decode(
+            &base64::engine::general_purpose::STANDARD,
+            private_b64.trim(),
+        )
+        .map_err(|e| CoreError::SshSetupError(format!("Invalid host key encoding: {}", e)))?;
+
+        if let Some(parent) = host_key_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(host_key_path, private)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(host_key_path, std::fs::Permissions::from_mode(0o600))?;
+        }
+
+        let known_hosts = self.known_hosts_path();
+        let content = std::fs::read_to_string(&known_hosts).unwrap_or_default();
+        std::fs::write(
+            &known_hosts,
+            update_known_hosts(&content, &host_key_alias(devc_id), Some(public_key)),
+        )?;
+        Ok(())
+    }
+
     /// Validate a username to prevent command injection
     fn validate_username(user: &str) -> Result<()> {
         // Standard Unix username: starts with lowercase letter or underscore,
@@ -354,6 +504,61 @@ chown -R {user}:{user} {home}/.ssh 2>/dev/null || true
         script: &str,
         user: Option<&str>,
     ) -> Result<()> {
+        self.exec_output_in_container(provider, container_id, script, user)
+            .await
+            .map(|_| ())
+    }
+
+    /// Execute a script in the container with `input` piped to its stdin
+    ///
+    /// An interactive exec reports no exit code, so the script (which should
+    /// `set -e`) is followed by a marker that only prints if it got that far.
+    async fn exec_with_stdin(
+        &self,
+        provider: &dyn ContainerProvider,
+        container_id: &ContainerId,
+        script: &str,
+        input: &[u8],
+        user: Option<&str>,
+    ) -> Result<()> {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        const DONE: &str = "devc:stdin-script-done";
+
+        let config = ExecConfig {
+            cmd: vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                format!("{}\necho {}", script, DONE),
+            ],
+            user: user.map(|s| s.to_string()),
+            stdin: true,
+            ..Default::default()
+        };
+        let mut stream = provider.exec_interactive(container_id, &config).await?;
+        if let Some(mut stdin) = stream.stdin.take() {
+            stdin.write_all(input).await?;
+            stdin.shutdown().await?;
+        }
+        let mut output = String::new();
+        stream.output.read_to_string(&mut output).await?;
+
+        if !output.contains(DONE) {
+            return Err(CoreError::SshSetupError(format!(
+                "Command failed: {}",
+                output.trim()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Execute a script in the container, returning its output
+    async fn exec_output_in_container(
+        &self,
+        provider: &dyn ContainerProvider,
+        container_id: &ContainerId,
+        script: &str,
+        user: Option<&str>,
+    ) -> Result<String> {
         let config = ExecConfig {
             cmd: vec!["/bin/sh".to_string(), "-c".to_string(), script.to_string()],
             env: HashMap::new(),
@@ -373,7 +578,7 @@ chown -R {user}:{user} {home}/.ssh 2>/dev/null || true
             )));
         }
 
-        Ok(())
+        Ok(result.output)
     }
 }
 
@@ -382,6 +587,13 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    #[test]
+    fn test_host_key_generated() {
+        assert!(host_key_generated("hostkey:generated\n"));
+        assert!(!host_key_generated("hostkey:reused\n"));
+        assert!(!host_key_generated(""));
+    }
+
     #[test]
     fn test_validate_username_length_boundary() {
         // 32 chars should be ok
@@ -420,6 +632,52 @@ mod tests {
         assert_eq!(manager.pub_key_path(), &PathBuf::from("/tmp/test_key.pub"));
     }
 
+    #[test]
+    fn test_update_known_hosts_replaces_alias_entry() {
+        let content = "devc-a ssh-ed25519 OLD\nother ssh-rsa KEEP\n";
+        assert_eq!(
+            update_known_hosts(content, "devc-a", Some("ssh-ed25519 NEW root@c\n")),
+            "other ssh-rsa KEEP\ndevc-a ssh-ed25519 NEW root@c\n"
+        );
+        assert_eq!(
+            update_known_hosts(content, "devc-a", None),
+            "other ssh-rsa KEEP\n"
+        );
+        assert_eq!(update_known_hosts("", "devc-a", None), "");
+    }
+
+    #[tokio::test]
+    async fn test_host_key_saved_and_forgotten() {
+        use crate::test_support::MockProvider;
+        use devc_provider::ProviderType;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let manager = SshManager::with_key_path(tmp.path().join("id_ed25519"));
+        let mock = MockProvider::new(ProviderType::Docker);
+        *mock.exec_output.lock().unwrap() = format!(
+            "ssh-ed25519 AAAAC3Nz root@box\nhostkey-private:{}\n",
+            base64::Engine::encode(&base64::engine::general_purpose::STANDARD, b"secret")
+        );
+
+        let host_key = manager.host_key_path("abc");
+        manager
+            .save_host_key(&mock, &ContainerId::new("c1"), "abc", &host_key)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(&host_key).unwrap(), b"secret");
+        assert_eq!(
+            std::fs::read_to_string(manager.known_hosts_path()).unwrap(),
+            "devc-abc ssh-ed25519 AAAAC3Nz root@box\n"
+        );
+
+        manager.forget_host_key("abc").unwrap();
+        assert!(!host_key.exists());
+        assert_eq!(
+            std::fs::read_to_string(manager.known_hosts_path()).unwrap(),
+            ""
+        );
+    }
+
     #[test]
     fn test_ssh_manager_paths_with_extension() {
         // Test that .pub is appended, not replacing extension
@@ -266,6 +266,31 @@ pub struct ContainerState {
     pub source: DevcontainerSource,
 }
 
+/// Container metadata key holding the image ID replaced by the latest build
+pub const PREVIOUS_IMAGE_METADATA_KEY: &str = "previous_image_id";
+
+/// Container metadata key set when the user pinned the container's provider
+pub const PINNED_PROVIDER_METADATA_KEY: &str = "provider_pinned";
+
+/// Container metadata key listing the image tags `devc freeze` committed from
+/// the container (a JSON array)
+pub const FROZEN_IMAGES_METADATA_KEY: &str = "frozen_images";
+
+/// Container metadata key overriding the runtime container name derived from
+/// the config, so a thawed container doesn't collide with its original
+pub const RUNTIME_NAME_METADATA_KEY: &str = "runtime_name";
+
+/// Container metadata key naming the container a thawed container came from
+pub const THAWED_FROM_METADATA_KEY: &str = "thawed_from";
+
+/// Container metadata key set when the container's image was made by
+/// `devc.prebuild`, so onCreate/updateContent already ran inside it
+pub const PREBUILT_METADATA_KEY: &str = "prebuilt";
+
+/// Container metadata key set by `devc up --detach` while the first-create
+/// lifecycle commands of a running container still have to run
+pub const LIFECYCLE_PENDING_METADATA_KEY: &str = "lifecycle_pending";
+
 fn default_devc_source() -> DevcontainerSource {
     DevcontainerSource::Devc
 }
@@ -286,6 +311,8 @@ pub enum DevcContainerStatus {
     Created,
     /// Container is running
     Running,
+    /// Container is paused (processes frozen, still holding its resources)
+    Paused,
     /// Container stopped
     Stopped,
     /// Container failed (build or runtime error)
@@ -301,6 +328,7 @@ impl std::fmt::Display for DevcContainerStatus {
             Self::Built => write!(f, "built"),
             Self::Created => write!(f, "created"),
             Self::Running => write!(f, "running"),
+            Self::Paused => write!(f, "paused"),
             Self::Stopped => write!(f, "stopped"),
             Self::Failed => write!(f, "failed"),
         }
@@ -477,6 +505,15 @@ impl ContainerState {
         }
     }
 
+    /// Change the status, counting a start as use of the container so the
+    /// idle timer runs from when it came up rather than from its last session
+    pub fn set_status(&mut self, status: DevcContainerStatus) {
+        if status == DevcContainerStatus::Running && self.status != DevcContainerStatus::Running {
+            self.last_used = Utc::now();
+        }
+        self.status = status;
+    }
+
     /// Check if the container can be started
     pub fn can_start(&self) -> bool {
         matches!(
@@ -487,7 +524,10 @@ impl ContainerState {
 
     /// Check if the container can be stopped
     pub fn can_stop(&self) -> bool {
-        matches!(self.status, DevcContainerStatus::Running)
+        matches!(
+            self.status,
+            DevcContainerStatus::Running | DevcContainerStatus::Paused
+        )
     }
 
     /// Check if the container can be removed
@@ -496,10 +536,63 @@ impl ContainerState {
             self.status,
             DevcContainerStatus::Available
                 | DevcContainerStatus::Running
+                | DevcContainerStatus::Paused
                 | DevcContainerStatus::Building
         )
     }
 
+    /// Whether the workspace this entry points at is gone
+    ///
+    /// Adopted containers may never have had a devcontainer.json, so only a
+    /// devc-created entry counts its missing config.
+    pub fn workspace_missing(&self) -> bool {
+        !self.workspace_path.is_dir()
+            || (self.source == DevcontainerSource::Devc && !self.config_path.is_file())
+    }
+
+    /// Image this container was built from before its latest build, if that
+    /// build produced a different image
+    pub fn previous_image_id(&self) -> Option<&str> {
+        self.metadata
+            .get(PREVIOUS_IMAGE_METADATA_KEY)
+            .map(String::as_str)
+    }
+
+    /// Whether `provider` was pinned by the user, so a different default
+    /// provider never migrates this container
+    pub fn provider_pinned(&self) -> bool {
+        self.metadata
+            .get(PINNED_PROVIDER_METADATA_KEY)
+            .is_some_and(|v| v == "true")
+    }
+
+    /// Whether the image already has onCreate/updateContent baked in
+    pub fn is_prebuilt(&self) -> bool {
+        self.metadata
+            .get(PREBUILT_METADATA_KEY)
+            .is_some_and(|v| v == "true")
+    }
+
+    /// Whether the container is running but its first-create lifecycle
+    /// commands were deferred to a later `up`
+    pub fn lifecycle_pending(&self) -> bool {
+        self.metadata.contains_key(LIFECYCLE_PENDING_METADATA_KEY)
+    }
+
+    /// Whether the container was made by `devc thaw`, so its image already
+    /// holds the results of the create-phase lifecycle commands
+    pub fn is_thawed(&self) -> bool {
+        self.metadata.contains_key(THAWED_FROM_METADATA_KEY)
+    }
+
+    /// Image tags committed from this container by `devc freeze`, oldest first
+    pub fn frozen_images(&self) -> Vec<String> {
+        self.metadata
+            .get(FROZEN_IMAGES_METADATA_KEY)
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default()
+    }
+
     /// Get a short display ID
     pub fn short_id(&self) -> &str {
         if self.id.len() > 8 {
@@ -712,6 +805,18 @@ mod tests {
         assert!(updated.last_used > original_last_used);
     }
 
+    #[test]
+    fn test_set_status_running_updates_last_used() {
+        let mut cs = make_state("started", DevcContainerStatus::Stopped);
+        cs.last_used -= chrono::Duration::hours(1);
+        let stale = cs.last_used;
+
+        cs.set_status(DevcContainerStatus::Stopped);
+        assert_eq!(cs.last_used, stale);
+        cs.set_status(DevcContainerStatus::Running);
+        assert!(cs.last_used > stale);
+    }
+
     #[test]
     fn test_can_start_states() {
         assert!(make_state("a", DevcContainerStatus::Created).can_start());
@@ -757,6 +862,30 @@ mod tests {
         assert_eq!(short, &cs.id[..8]);
     }
 
+    #[test]
+    fn test_workspace_missing() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = tmp.path().join("devcontainer.json");
+        std::fs::write(&config, "{}").unwrap();
+        let mut cs = ContainerState::new(
+            "ws".to_string(),
+            ProviderType::Docker,
+            config.clone(),
+            tmp.path().to_path_buf(),
+        );
+        assert!(!cs.workspace_missing());
+
+        std::fs::remove_file(&config).unwrap();
+        assert!(cs.workspace_missing());
+
+        // Adopted containers don't need a config file on disk
+        cs.source = DevcontainerSource::VsCode;
+        assert!(!cs.workspace_missing());
+
+        cs.workspace_path = tmp.path().join("gone");
+        assert!(cs.workspace_missing());
+    }
+
     #[test]
     fn test_find_by_config_path() {
         let mut store = StateStore::new();
@@ -18,6 +18,8 @@ use tokio::sync::mpsc;
 pub enum MockCall {
     Build {
         tag: String,
+        build_args: HashMap<String, String>,
+        target: Option<String>,
     },
     BuildWithProgress {
         tag: String,
@@ -25,9 +27,26 @@ pub enum MockCall {
     Pull {
         image: String,
     },
+    PullWithProgress {
+        image: String,
+    },
+    LocalImage {
+        image: String,
+    },
+    ListImages {
+        label: String,
+    },
+    RemoveImage {
+        id: String,
+        force: bool,
+    },
     Create {
         image: String,
         name: Option<String>,
+        gpus: Option<String>,
+        mount_targets: Vec<String>,
+        user_labels: HashMap<String, String>,
+        user: Option<String>,
     },
     Start {
         id: String,
@@ -35,6 +54,16 @@ pub enum MockCall {
     Stop {
         id: String,
     },
+    Pause {
+        id: String,
+    },
+    Unpause {
+        id: String,
+    },
+    Commit {
+        id: String,
+        tag: String,
+    },
     Remove {
         id: String,
         force: bool,
@@ -54,6 +83,7 @@ pub enum MockCall {
     Inspect {
         id: String,
     },
+    DiskUsage,
     List {
         all: bool,
     },
@@ -61,6 +91,12 @@ pub enum MockCall {
         id: String,
     },
     Ping,
+    Version,
+    ComposeVersion,
+    GpuAvailable,
+    Supports {
+        capability: Capability,
+    },
     ComposeUp {
         project: String,
     },
@@ -93,6 +129,10 @@ pub struct MockProvider {
     pub build_result: Arc<Mutex<Result<ImageId>>>,
     /// Result for pull calls
     pub pull_result: Arc<Mutex<Result<ImageId>>>,
+    /// Result for local_image calls (image not present by default)
+    pub local_image_result: Arc<Mutex<Result<Option<ImageId>>>>,
+    /// Result for list_images calls
+    pub list_images_result: Arc<Mutex<Result<Vec<ImageInfo>>>>,
     /// Result for create calls
     pub create_result: Arc<Mutex<Result<ContainerId>>>,
     /// Result for start calls
@@ -103,6 +143,9 @@ pub struct MockProvider {
     pub remove_result: Arc<Mutex<Result<()>>>,
     /// Result for remove_by_name calls
     pub remove_by_name_result: Arc<Mutex<Result<()>>>,
+    pub remove_image_result: Arc<Mutex<Result<()>>>,
+    /// Result for commit calls
+    pub commit_result: Arc<Mutex<Result<ImageId>>>,
     /// Exit code and output for exec calls
     pub exec_exit_code: Arc<Mutex<i64>>,
     pub exec_output: Arc<Mutex<String>>,
@@ -110,14 +153,28 @@ pub struct MockProvider {
     pub exec_error: Arc<Mutex<Option<ProviderError>>>,
     /// Per-call exec response queue: (exit_code, output). Popped before falling back to exec_exit_code/exec_output.
     pub exec_responses: Arc<Mutex<Vec<(i64, String)>>>,
+    /// Output returned by logs calls
+    pub logs_output: Arc<Mutex<String>>,
+    /// Stderr returned by logs calls that ask for it separately
+    pub logs_stderr: Arc<Mutex<String>>,
     /// Result for inspect calls
     pub inspect_result: Arc<Mutex<Result<ContainerDetails>>>,
     /// Per-call inspect response queue. Popped before falling back to inspect_result.
     pub inspect_responses: Arc<Mutex<Vec<Result<ContainerDetails>>>>,
+    /// Result for disk_usage calls
+    pub disk_usage_result: Arc<Mutex<Result<Vec<ContainerDiskUsage>>>>,
     /// Result for list calls
     pub list_result: Arc<Mutex<Result<Vec<ContainerInfo>>>>,
     /// Result for ping calls
     pub ping_result: Arc<Mutex<Result<()>>>,
+    /// Result for version calls
+    pub version_result: Arc<Mutex<Result<String>>>,
+    /// Result for compose_version calls
+    pub compose_version_result: Arc<Mutex<Result<String>>>,
+    /// Result for gpu_available calls
+    pub gpu_available: Arc<Mutex<bool>>,
+    /// Result for supports calls (everything but GPUs by default)
+    pub capabilities: Arc<Mutex<ProviderCapabilities>>,
     /// Result for discover calls
     pub discover_result: Arc<Mutex<Result<Vec<DiscoveredContainer>>>>,
     /// Result for copy_into calls
@@ -142,20 +199,38 @@ impl MockProvider {
             calls: Arc::new(Mutex::new(Vec::new())),
             build_result: Arc::new(Mutex::new(Ok(ImageId::new("sha256:mock_image_id")))),
             pull_result: Arc::new(Mutex::new(Ok(ImageId::new("sha256:mock_pulled_id")))),
+            local_image_result: Arc::new(Mutex::new(Ok(None))),
+            list_images_result: Arc::new(Mutex::new(Ok(Vec::new()))),
             create_result: Arc::new(Mutex::new(Ok(ContainerId::new("mock_container_id")))),
             start_result: Arc::new(Mutex::new(Ok(()))),
             stop_result: Arc::new(Mutex::new(Ok(()))),
             remove_result: Arc::new(Mutex::new(Ok(()))),
             remove_by_name_result: Arc::new(Mutex::new(Ok(()))),
+            remove_image_result: Arc::new(Mutex::new(Ok(()))),
+            commit_result: Arc::new(Mutex::new(Ok(ImageId::new("sha256:mock_committed_id")))),
             exec_exit_code: Arc::new(Mutex::new(0)),
             exec_output: Arc::new(Mutex::new(String::new())),
+            logs_output: Arc::new(Mutex::new(String::new())),
+            logs_stderr: Arc::new(Mutex::new(String::new())),
             exec_error: Arc::new(Mutex::new(None)),
             inspect_result: Arc::new(Mutex::new(Ok(mock_container_details(
                 "mock_container_id",
                 ContainerStatus::Running,
             )))),
+            disk_usage_result: Arc::new(Mutex::new(Ok(Vec::new()))),
             list_result: Arc::new(Mutex::new(Ok(Vec::new()))),
             ping_result: Arc::new(Mutex::new(Ok(()))),
+            version_result: Arc::new(Mutex::new(Ok("1.0.0-mock".to_string()))),
+            compose_version_result: Arc::new(Mutex::new(Ok(
+                "Docker Compose 2.0.0-mock".to_string()
+            ))),
+            gpu_available: Arc::new(Mutex::new(false)),
+            capabilities: Arc::new(Mutex::new(ProviderCapabilities {
+                compose: true,
+                buildkit: true,
+                gpu: false,
+                stats: true,
+            })),
             discover_result: Arc::new(Mutex::new(Ok(Vec::new()))),
             copy_into_result: Arc::new(Mutex::new(Ok(()))),
             copy_from_result: Arc::new(Mutex::new(Ok(()))),
@@ -237,17 +312,29 @@ fn mock_call_name(call: &MockCall) -> String {
         MockCall::Build { .. } => "Build",
         MockCall::BuildWithProgress { .. } => "BuildWithProgress",
         MockCall::Pull { .. } => "Pull",
+        MockCall::PullWithProgress { .. } => "PullWithProgress",
+        MockCall::LocalImage { .. } => "LocalImage",
+        MockCall::ListImages { .. } => "ListImages",
+        MockCall::RemoveImage { .. } => "RemoveImage",
         MockCall::Create { .. } => "Create",
         MockCall::Start { .. } => "Start",
         MockCall::Stop { .. } => "Stop",
+        MockCall::Pause { .. } => "Pause",
+        MockCall::Unpause { .. } => "Unpause",
+        MockCall::Commit { .. } => "Commit",
         MockCall::Remove { .. } => "Remove",
         MockCall::RemoveByName { .. } => "RemoveByName",
         MockCall::Exec { .. } => "Exec",
         MockCall::ExecInteractive { .. } => "ExecInteractive",
         MockCall::Inspect { .. } => "Inspect",
+        MockCall::DiskUsage => "DiskUsage",
         MockCall::List { .. } => "List",
         MockCall::Logs { .. } => "Logs",
         MockCall::Ping => "Ping",
+        MockCall::Version => "Version",
+        MockCall::ComposeVersion => "ComposeVersion",
+        MockCall::GpuAvailable => "GpuAvailable",
+        MockCall::Supports { .. } => "Supports",
         MockCall::ComposeUp { .. } => "ComposeUp",
         MockCall::ComposeDown { .. } => "ComposeDown",
         MockCall::ComposePs { .. } => "ComposePs",
@@ -275,6 +362,10 @@ fn clone_provider_error(e: &ProviderError) -> ProviderError {
         ProviderError::ContainerNotFound(s) => ProviderError::ContainerNotFound(s.clone()),
         ProviderError::ImageNotFound(s) => ProviderError::ImageNotFound(s.clone()),
         ProviderError::BuildError(s) => ProviderError::BuildError(s.clone()),
+        ProviderError::BuildFailed { step, excerpt } => ProviderError::BuildFailed {
+            step: step.clone(),
+            excerpt: excerpt.clone(),
+        },
         ProviderError::ExecError(s) => ProviderError::ExecError(s.clone()),
         ProviderError::RuntimeError(s) => ProviderError::RuntimeError(s.clone()),
         ProviderError::ConfigError(s) => ProviderError::ConfigError(s.clone()),
@@ -297,8 +388,10 @@ pub fn mock_container_details(id: &str, status: ContainerStatus) -> ContainerDet
         started_at: None,
         finished_at: None,
         exit_code: None,
+        restart_count: 0,
         labels: HashMap::new(),
         env: Vec::new(),
+        user: None,
         mounts: Vec::new(),
         ports: Vec::new(),
         network_settings: NetworkSettings::default(),
@@ -350,6 +443,8 @@ impl ContainerProvider for MockProvider {
     async fn build(&self, config: &BuildConfig) -> Result<ImageId> {
         self.record(MockCall::Build {
             tag: config.tag.clone(),
+            build_args: config.build_args.clone(),
+            target: config.target.clone(),
         });
         clone_result(&self.build_result)
     }
@@ -372,10 +467,47 @@ impl ContainerProvider for MockProvider {
         clone_result(&self.pull_result)
     }
 
+    async fn pull_with_progress(
+        &self,
+        image: &str,
+        _progress: mpsc::UnboundedSender<String>,
+    ) -> Result<ImageId> {
+        self.record(MockCall::PullWithProgress {
+            image: image.to_string(),
+        });
+        clone_result(&self.pull_result)
+    }
+
+    async fn local_image(&self, image: &str) -> Result<Option<ImageId>> {
+        self.record(MockCall::LocalImage {
+            image: image.to_string(),
+        });
+        clone_result(&self.local_image_result)
+    }
+
+    async fn list_images(&self, label: &str) -> Result<Vec<ImageInfo>> {
+        self.record(MockCall::ListImages {
+            label: label.to_string(),
+        });
+        clone_result(&self.list_images_result)
+    }
+
+    async fn remove_image(&self, id: &ImageId, force: bool) -> Result<()> {
+        self.record(MockCall::RemoveImage {
+            id: id.0.clone(),
+            force,
+        });
+        clone_result(&self.remove_image_result)
+    }
+
     async fn create(&self, config: &CreateContainerConfig) -> Result<ContainerId> {
         self.record(MockCall::Create {
             image: config.image.clone(),
             name: config.name.clone(),
+            gpus: config.gpus.clone(),
+            mount_targets: config.mounts.iter().map(|m| m.target.clone()).collect(),
+            user_labels: config.user_labels.clone(),
+            user: config.user.clone(),
         });
         clone_result(&self.create_result)
     }
@@ -390,6 +522,24 @@ impl ContainerProvider for MockProvider {
         clone_result(&self.stop_result)
     }
 
+    async fn pause(&self, id: &ContainerId) -> Result<()> {
+        self.record(MockCall::Pause { id: id.0.clone() });
+        Ok(())
+    }
+
+    async fn unpause(&self, id: &ContainerId) -> Result<()> {
+        self.record(MockCall::Unpause { id: id.0.clone() });
+        Ok(())
+    }
+
+    async fn commit(&self, id: &ContainerId, tag: &str) -> Result<ImageId> {
+        self.record(MockCall::Commit {
+            id: id.0.clone(),
+            tag: tag.to_string(),
+        });
+        clone_result(&self.commit_result)
+    }
+
     async fn remove(&self, id: &ContainerId, force: bool) -> Result<()> {
         self.record(MockCall::Remove {
             id: id.0.clone(),
@@ -469,10 +619,21 @@ impl ContainerProvider for MockProvider {
         clone_result(&self.inspect_result)
     }
 
-    async fn logs(&self, id: &ContainerId, _config: &LogConfig) -> Result<LogStream> {
+    async fn disk_usage(&self) -> Result<Vec<ContainerDiskUsage>> {
+        self.record(MockCall::DiskUsage);
+        clone_result(&self.disk_usage_result)
+    }
+
+    async fn logs(&self, id: &ContainerId, config: &LogConfig) -> Result<LogStream> {
         self.record(MockCall::Logs { id: id.0.clone() });
+        let output = self.logs_output.lock().unwrap().clone();
+        let stderr = self.logs_stderr.lock().unwrap().clone();
         Ok(LogStream {
-            stream: Box::pin(EmptyReader),
+            stream: Box::pin(std::io::Cursor::new(output.into_bytes())),
+            stderr: config.demux.then(|| {
+                Box::pin(std::io::Cursor::new(stderr.into_bytes()))
+                    as Pin<Box<dyn AsyncRead + Send>>
+            }),
             _child: None,
         })
     }
@@ -482,6 +643,35 @@ impl ContainerProvider for MockProvider {
         clone_result(&self.ping_result)
     }
 
+    async fn version(&self) -> Result<String> {
+        self.record(MockCall::Version);
+        clone_result(&self.version_result)
+    }
+
+    async fn compose_version(&self) -> Result<String> {
+        self.record(MockCall::ComposeVersion);
+        clone_result(&self.compose_version_result)
+    }
+
+    async fn gpu_available(&self) -> bool {
+        self.record(MockCall::GpuAvailable);
+        *self.gpu_available.lock().unwrap()
+    }
+
+    async fn supports(&self, capability: Capability) -> bool {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(MockCall::Supports { capability });
+        let capabilities = self.capabilities.lock().unwrap();
+        match capability {
+            Capability::Compose => capabilities.compose,
+            Capability::Buildkit => capabilities.buildkit,
+            Capability::Gpu => capabilities.gpu,
+            Capability::Stats => capabilities.stats,
+        }
+    }
+
     fn runtime_args(&self) -> (String, Vec<String>) {
         (self.provider_type.to_string(), vec![])
     }
@@ -201,9 +201,14 @@ async fn test_integration_oci_feature_download() {
         _ => panic!("Expected OCI source"),
     }
 
-    let result =
-        features::download::download_feature(&source, config_dir.path(), cache_dir.path(), &None)
-            .await;
+    let result = features::download::download_feature(
+        &source,
+        config_dir.path(),
+        cache_dir.path(),
+        &HashMap::new(),
+        &None,
+    )
+    .await;
 
     let feature_dir = match result {
         Ok(dir) => dir,
@@ -232,9 +237,14 @@ async fn test_integration_oci_feature_download() {
     assert_eq!(metadata.id.as_deref(), Some("git"));
 
     // Verify caching: second download should be instant (returns cached path)
-    let result2 =
-        features::download::download_feature(&source, config_dir.path(), cache_dir.path(), &None)
-            .await;
+    let result2 = features::download::download_feature(
+        &source,
+        config_dir.path(),
+        cache_dir.path(),
+        &HashMap::new(),
+        &None,
+    )
+    .await;
     let feature_dir2 = result2.expect("cached download should succeed");
     assert_eq!(feature_dir, feature_dir2, "Should return same cached path");
 }
@@ -291,8 +301,11 @@ async fn test_e2e_multiple_features_install() {
 
     let resolved = features::resolve_and_prepare_features(
         container.devcontainer.features.as_ref().unwrap(),
+        &[],
         &config_dir,
+        &HashMap::new(),
         &progress,
+        None,
     )
     .await
     .expect("feature resolution should succeed");
@@ -320,7 +333,10 @@ async fn test_e2e_multiple_features_install() {
     }
 
     // Build the enhanced image with features
-    let remote_user = container.devcontainer.effective_user().unwrap_or("root");
+    let remote_user = container
+        .devcontainer
+        .effective_remote_user()
+        .unwrap_or("root");
     let enhanced_ctx = EnhancedBuildContext::from_image_with_features(
         "mcr.microsoft.com/devcontainers/base:ubuntu",
         &resolved,
@@ -359,6 +375,7 @@ async fn test_e2e_multiple_features_install() {
         labels: HashMap::new(),
         no_cache: true,
         pull: true,
+        secrets: Vec::new(),
     };
 
     eprintln!("Building image with 3 features (this may take a while)...");
@@ -495,8 +512,11 @@ async fn test_e2e_feature_container_properties() {
     let config_dir = container.config_path.parent().unwrap().to_path_buf();
     let resolved = features::resolve_and_prepare_features(
         container.devcontainer.features.as_ref().unwrap(),
+        &[],
         &config_dir,
+        &HashMap::new(),
         &None,
+        None,
     )
     .await
     .expect("feature resolution should succeed");
@@ -515,7 +535,10 @@ async fn test_e2e_feature_container_properties() {
     );
 
     // Build the enhanced image
-    let remote_user = container.devcontainer.effective_user().unwrap_or("root");
+    let remote_user = container
+        .devcontainer
+        .effective_remote_user()
+        .unwrap_or("root");
     let enhanced_ctx = EnhancedBuildContext::from_image_with_features(
         "mcr.microsoft.com/devcontainers/base:ubuntu",
         &resolved,
@@ -535,6 +558,7 @@ async fn test_e2e_feature_container_properties() {
         labels: HashMap::new(),
         no_cache: true,
         pull: true,
+        secrets: Vec::new(),
     };
 
     eprintln!("Building image with Go feature...");
@@ -662,8 +686,11 @@ async fn test_e2e_feature_mounts() {
     let config_dir = container.config_path.parent().unwrap().to_path_buf();
     let resolved = features::resolve_and_prepare_features(
         container.devcontainer.features.as_ref().unwrap(),
+        &[],
         &config_dir,
+        &HashMap::new(),
         &None,
+        None,
     )
     .await
     .expect("feature resolution should succeed");
@@ -685,7 +712,10 @@ async fn test_e2e_feature_mounts() {
     }
 
     // Build the enhanced image
-    let remote_user = container.devcontainer.effective_user().unwrap_or("root");
+    let remote_user = container
+        .devcontainer
+        .effective_remote_user()
+        .unwrap_or("root");
     let enhanced_ctx = EnhancedBuildContext::from_image_with_features(
         "ubuntu:22.04",
         &resolved,
@@ -705,6 +735,7 @@ async fn test_e2e_feature_mounts() {
         labels: HashMap::new(),
         no_cache: false,
         pull: false,
+        secrets: Vec::new(),
     };
 
     eprintln!("Building image with local mount feature...");
@@ -822,8 +853,11 @@ async fn test_e2e_feature_lifecycle_commands() {
     let config_dir = container.config_path.parent().unwrap().to_path_buf();
     let resolved = features::resolve_and_prepare_features(
         container.devcontainer.features.as_ref().unwrap(),
+        &[],
         &config_dir,
+        &HashMap::new(),
         &None,
+        None,
     )
     .await
     .expect("feature resolution should succeed");
@@ -844,7 +878,10 @@ async fn test_e2e_feature_lifecycle_commands() {
     );
 
     // Build the enhanced image
-    let remote_user = container.devcontainer.effective_user().unwrap_or("root");
+    let remote_user = container
+        .devcontainer
+        .effective_remote_user()
+        .unwrap_or("root");
     let enhanced_ctx = EnhancedBuildContext::from_image_with_features(
         "ubuntu:22.04",
         &resolved,
@@ -864,6 +901,7 @@ async fn test_e2e_feature_lifecycle_commands() {
         labels: HashMap::new(),
         no_cache: false,
         pull: false,
+        secrets: Vec::new(),
     };
 
     eprintln!("Building image with lifecycle feature...");
@@ -1072,8 +1110,11 @@ services:
     let config_dir = container.config_path.parent().unwrap().to_path_buf();
     let resolved = features::resolve_and_prepare_features(
         container.devcontainer.features.as_ref().unwrap(),
+        &[],
         &config_dir,
+        &HashMap::new(),
         &None,
+        None,
     )
     .await
     .expect("feature resolution should succeed");
@@ -1263,8 +1304,11 @@ async fn test_e2e_docker_in_docker_feature_install() {
 
     let resolved = features::resolve_and_prepare_features(
         container.devcontainer.features.as_ref().unwrap(),
+        &[],
         &config_dir,
+        &HashMap::new(),
         &progress,
+        None,
     )
     .await
     .expect("feature resolution should succeed");
@@ -1300,7 +1344,10 @@ async fn test_e2e_docker_in_docker_feature_install() {
     );
 
     // Build the enhanced image with the feature
-    let remote_user = container.devcontainer.effective_user().unwrap_or("root");
+    let remote_user = container
+        .devcontainer
+        .effective_remote_user()
+        .unwrap_or("root");
     let enhanced_ctx = EnhancedBuildContext::from_image_with_features(
         "mcr.microsoft.com/devcontainers/base:ubuntu",
         &resolved,
@@ -1320,6 +1367,7 @@ async fn test_e2e_docker_in_docker_feature_install() {
         labels: HashMap::new(),
         no_cache: true,
         pull: true,
+        secrets: Vec::new(),
     };
 
     eprintln!("Building image with docker-in-docker feature (this may take a while)...");
@@ -1539,9 +1587,14 @@ async fn test_integration_tarball_url_download() {
         _ => panic!("Expected TarballUrl source"),
     }
 
-    let result =
-        features::download::download_feature(&source, config_dir.path(), cache_dir.path(), &None)
-            .await;
+    let result = features::download::download_feature(
+        &source,
+        config_dir.path(),
+        cache_dir.path(),
+        &HashMap::new(),
+        &None,
+    )
+    .await;
 
     let feature_dir = result.expect("tarball download should succeed");
 
@@ -1560,9 +1613,14 @@ async fn test_integration_tarball_url_download() {
     assert_eq!(metadata.id.as_deref(), Some("url-test-feature"));
 
     // Verify caching — server won't accept another request if it already served 2
-    let result2 =
-        features::download::download_feature(&source, config_dir.path(), cache_dir.path(), &None)
-            .await;
+    let result2 = features::download::download_feature(
+        &source,
+        config_dir.path(),
+        cache_dir.path(),
+        &HashMap::new(),
+        &None,
+    )
+    .await;
     let feature_dir2 = result2.expect("cached download should succeed");
     assert_eq!(feature_dir, feature_dir2, "Should return same cached path");
 
@@ -1621,8 +1679,11 @@ async fn test_e2e_tarball_url_feature_install() {
     let config_dir = container.config_path.parent().unwrap().to_path_buf();
     let resolved = features::resolve_and_prepare_features(
         container.devcontainer.features.as_ref().unwrap(),
+        &[],
         &config_dir,
+        &HashMap::new(),
         &None,
+        None,
     )
     .await
     .expect("feature resolution should succeed");
@@ -1634,7 +1695,10 @@ async fn test_e2e_tarball_url_feature_install() {
     );
 
     // Build the enhanced image with the tarball feature
-    let remote_user = container.devcontainer.effective_user().unwrap_or("root");
+    let remote_user = container
+        .devcontainer
+        .effective_remote_user()
+        .unwrap_or("root");
     let enhanced_ctx = EnhancedBuildContext::from_image_with_features(
         "ubuntu:22.04",
         &resolved,
@@ -1654,6 +1718,7 @@ async fn test_e2e_tarball_url_feature_install() {
         labels: HashMap::new(),
         no_cache: false,
         pull: false,
+        secrets: Vec::new(),
     };
 
     eprintln!("Building image with tarball URL feature...");
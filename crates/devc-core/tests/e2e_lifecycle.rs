@@ -338,7 +338,7 @@ async fn test_e2e_image_lifecycle_events() {
     );
 
     // Clean up: remove from state
-    mgr.remove(&id, true).await.ok();
+    mgr.remove(&id, true, false).await.ok();
     let _ = std::fs::remove_file(&host_marker);
 }
 
@@ -442,6 +442,6 @@ async fn test_e2e_compose_lifecycle_events() {
     );
 
     // Clean up
-    mgr.remove(&id, true).await.ok();
+    mgr.remove(&id, true, false).await.ok();
     let _ = std::fs::remove_file(&host_marker);
 }
@@ -3,7 +3,7 @@
 //! Test A runs without Docker (config-only parsing).
 //! Tests B and C require a container runtime (Docker or Podman) and are `#[ignore]`.
 
-use devc_config::{AutoForwardAction, PortForwardConfig};
+use devc_config::{AutoForwardAction, PortForwardConfig, PortTransport};
 use devc_core::test_support::TestComposeGuard;
 use devc_core::Container;
 use devc_provider::{CliProvider, ContainerProvider, ExecConfig};
@@ -142,9 +142,11 @@ fn pfc(
 ) -> PortForwardConfig {
     PortForwardConfig {
         port,
+        transport: PortTransport::Tcp,
         action,
         label: label.map(String::from),
         protocol: protocol.map(String::from),
+        bind_address: PortForwardConfig::DEFAULT_BIND_ADDRESS,
     }
 }
 
@@ -0,0 +1,333 @@
+//! Step tracking for build output, used to explain where a build failed
+//!
+//! Understands BuildKit plain progress (`#7 [2/4] RUN ...`, `#7 ERROR: ...`),
+//! podman/buildah (`STEP 2/4: RUN ...`) and the legacy Docker builder
+//! (`Step 2/4 : RUN ...`).
+
+use std::collections::VecDeque;
+
+/// How many trailing output lines of the failing step go into the excerpt
+const EXCERPT_LINES: usize = 10;
+
+/// Parse a step marker line into a normalized step name ("2/4: RUN make")
+///
+/// BuildKit stage names are kept ("builder 2/4: RUN make"); internal BuildKit
+/// vertices such as `[internal] load metadata` are not steps.
+pub fn build_step_marker(line: &str) -> Option<String> {
+    match split_buildkit(line) {
+        Some((_, rest)) => bracketed_step(rest),
+        None => legacy_step(line),
+    }
+}
+
+/// Split a BuildKit plain-progress line into its vertex number and the rest
+fn split_buildkit(line: &str) -> Option<(&str, &str)> {
+    let line = line.strip_prefix('#')?;
+    let end = line.find(|c: char| !c.is_ascii_digit())?;
+    let rest = line[end..].strip_prefix(' ')?;
+    (end > 0).then(|| (&line[..end], rest))
+}
+
+fn bracketed_step(rest: &str) -> Option<String> {
+    let rest = rest.strip_prefix('[')?;
+    let (label, instruction) = rest.split_once(']')?;
+    let count = label.split_whitespace().last()?;
+    is_step_count(count).then(|| format!("{}: {}", label.trim(), instruction.trim()))
+}
+
+fn legacy_step(line: &str) -> Option<String> {
+    let rest = line
+        .trim_start()
+        .strip_prefix("STEP ")
+        .or_else(|| line.trim_start().strip_prefix("Step "))?;
+    let (count, instruction) = rest.split_once(':')?;
+    let count = count.trim();
+    is_step_count(count).then(|| format!("{}: {}", count, instruction.trim()))
+}
+
+/// ` ---> 1234abcd` (legacy builder) or `--> 1234abcd` (podman), printed once
+/// a step's layer is committed
+fn is_layer_done(line: &str) -> bool {
+    let line = line.trim_start();
+    line.strip_prefix("--->")
+        .or_else(|| line.strip_prefix("-->"))
+        .map(str::trim)
+        .is_some_and(|id| !id.is_empty() && id.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// "2/4"
+fn is_step_count(s: &str) -> bool {
+    s.split_once('/').is_some_and(|(n, m)| {
+        !n.is_empty()
+            && !m.is_empty()
+            && n.chars().all(|c| c.is_ascii_digit())
+            && m.chars().all(|c| c.is_ascii_digit())
+    })
+}
+
+struct Step {
+    /// BuildKit vertex number; None for podman and the legacy builder
+    vertex: Option<String>,
+    name: String,
+    tail: VecDeque<String>,
+    /// Whether the output said the step completed
+    done: bool,
+}
+
+impl Step {
+    fn push(&mut self, line: &str) {
+        if self.tail.len() == EXCERPT_LINES {
+            self.tail.pop_front();
+        }
+        self.tail.push_back(line.to_string());
+    }
+}
+
+/// Follows build output line by line, remembering each step's recent output
+#[derive(Default)]
+pub(crate) struct BuildStepTracker {
+    steps: Vec<Step>,
+    /// Step receiving unprefixed output (podman and the legacy builder)
+    current: Option<usize>,
+    failed: Option<usize>,
+}
+
+impl BuildStepTracker {
+    pub(crate) fn observe(&mut self, line: &str) {
+        let line = line.trim_end();
+
+        if let Some((vertex, rest)) = split_buildkit(line) {
+            if let Some(name) = bracketed_step(rest) {
+                if !self
+                    .steps
+                    .iter()
+                    .any(|s| s.vertex.as_deref() == Some(vertex))
+                {
+                    self.steps.push(Step {
+                        vertex: Some(vertex.to_string()),
+                        name,
+                        tail: VecDeque::new(),
+                        done: false,
+                    });
+                }
+                return;
+            }
+            let Some(idx) = self
+                .steps
+                .iter()
+                .position(|s| s.vertex.as_deref() == Some(vertex))
+            else {
+                return;
+            };
+            if rest.starts_with("ERROR") {
+                self.failed.get_or_insert(idx);
+                self.steps[idx].push(rest);
+            } else if rest.starts_with("DONE") || rest.starts_with("CACHED") {
+                self.steps[idx].done = true;
+            } else if let Some((timestamp, output)) = rest.split_once(' ') {
+                // Command output is prefixed with seconds since the step started
+                if timestamp.parse::<f64>().is_ok() {
+                    self.steps[idx].push(output);
+                }
+            }
+            return;
+        }
+
+        if let Some(name) = legacy_step(line) {
+            // podman and the legacy builder run one step at a time
+            if let Some(previous) = self.current {
+                self.steps[previous].done = true;
+            }
+            self.steps.push(Step {
+                vertex: None,
+                name,
+                tail: VecDeque::new(),
+                done: false,
+            });
+            self.current = Some(self.steps.len() - 1);
+            return;
+        }
+
+        let Some(current) = self.current else {
+            return;
+        };
+        if is_layer_done(line) || line.starts_with("COMMIT ") {
+            self.steps[current].done = true;
+        }
+        if let Some(rest) = line.strip_prefix("Error: building at STEP \"") {
+            // podman names the failing instruction rather than its number
+            let instruction = rest.split('"').next().unwrap_or_default();
+            let idx = self
+                .steps
+                .iter()
+                .rposition(|s| s.name.ends_with(instruction))
+                .unwrap_or(current);
+            self.failed.get_or_insert(idx);
+            self.steps[idx].push(line);
+        } else if line.contains("returned a non-zero code") {
+            self.failed.get_or_insert(current);
+            self.steps[current].push(line);
+        } else if !line.starts_with(" --->") && !line.starts_with("Removing intermediate container")
+        {
+            self.steps[current].push(line);
+        }
+    }
+
+    /// The failing step's name and output excerpt
+    ///
+    /// When the output never said which step failed, the last step that
+    /// never finished is blamed. None when every step finished, since the
+    /// build then failed outside any step (context upload, registry auth,
+    /// Dockerfile parse, export).
+    pub(crate) fn failure(&self) -> Option<(String, String)> {
+        let idx = self
+            .failed
+            .or_else(|| self.steps.iter().rposition(|s| !s.done))?;
+        let step = &self.steps[idx];
+        let excerpt = step.tail.iter().cloned().collect::<Vec<_>>().join("\n");
+        Some((step.name.clone(), excerpt))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure_for(output: &str) -> Option<(String, String)> {
+        let mut tracker = BuildStepTracker::default();
+        for line in output.lines() {
+            tracker.observe(line);
+        }
+        tracker.failure()
+    }
+
+    #[test]
+    fn test_build_step_marker() {
+        assert_eq!(
+            build_step_marker("#7 [2/4] RUN apt-get update").as_deref(),
+            Some("2/4: RUN apt-get update")
+        );
+        assert_eq!(
+            build_step_marker("#9 [builder 3/5] RUN make").as_deref(),
+            Some("builder 3/5: RUN make")
+        );
+        assert_eq!(
+            build_step_marker("STEP 2/4: RUN apt-get update").as_deref(),
+            Some("2/4: RUN apt-get update")
+        );
+        assert_eq!(
+            build_step_marker("Step 2/4 : RUN apt-get update").as_deref(),
+            Some("2/4: RUN apt-get update")
+        );
+        assert_eq!(
+            build_step_marker("#1 [internal] load build definition"),
+            None
+        );
+        assert_eq!(build_step_marker("#7 0.312 Reading package lists..."), None);
+        assert_eq!(build_step_marker("Step one: install"), None);
+    }
+
+    #[test]
+    fn test_buildkit_failure() {
+        let output = "\
+#5 [1/3] FROM docker.io/library/debian:bookworm
+#5 DONE 0.1s
+#6 [2/3] RUN apt-get update
+#6 0.201 Get:1 http://deb.debian.org/debian bookworm InRelease
+#6 DONE 3.2s
+#7 [3/3] RUN apt-get install -y nosuchpkg
+#7 0.312 Reading package lists...
+#7 0.901 E: Unable to locate package nosuchpkg
+#7 ERROR: process \"/bin/sh -c apt-get install -y nosuchpkg\" did not complete successfully: exit code: 100
+------
+ > [3/3] RUN apt-get install -y nosuchpkg:
+------
+ERROR: failed to solve: exit code: 100";
+        let (step, excerpt) = failure_for(output).unwrap();
+        assert_eq!(step, "3/3: RUN apt-get install -y nosuchpkg");
+        assert_eq!(
+            excerpt,
+            "Reading package lists...\n\
+             E: Unable to locate package nosuchpkg\n\
+             ERROR: process \"/bin/sh -c apt-get install -y nosuchpkg\" did not complete successfully: exit code: 100"
+        );
+    }
+
+    #[test]
+    fn test_podman_failure() {
+        let output = "\
+STEP 1/3: FROM debian:bookworm
+STEP 2/3: RUN false
+Error: building at STEP \"RUN false\": while running runtime: exit status 1";
+        let (step, excerpt) = failure_for(output).unwrap();
+        assert_eq!(step, "2/3: RUN false");
+        assert!(excerpt.starts_with("Error: building at STEP"));
+    }
+
+    #[test]
+    fn test_legacy_builder_failure() {
+        let output = "\
+Step 1/2 : FROM debian:bookworm
+ ---> 1234abcd
+Step 2/2 : RUN make
+ ---> Running in 5678ef
+make: *** No targets specified and no makefile found.  Stop.
+The command '/bin/sh -c make' returned a non-zero code: 2";
+        let (step, excerpt) = failure_for(output).unwrap();
+        assert_eq!(step, "2/2: RUN make");
+        assert_eq!(
+            excerpt,
+            "make: *** No targets specified and no makefile found.  Stop.\n\
+             The command '/bin/sh -c make' returned a non-zero code: 2"
+        );
+    }
+
+    #[test]
+    fn test_failure_without_error_marker_uses_unfinished_step() {
+        let (step, _) = failure_for("STEP 1/2: FROM debian\nSTEP 2/2: RUN ./setup.sh\n").unwrap();
+        assert_eq!(step, "2/2: RUN ./setup.sh");
+    }
+
+    #[test]
+    fn test_failure_after_all_steps_finished() {
+        let buildkit = "\
+#5 [1/2] FROM docker.io/library/debian:bookworm
+#5 CACHED
+#6 [2/2] RUN make
+#6 DONE 1.2s
+#7 exporting to image
+#7 ERROR: failed to push: unauthorized";
+        assert!(failure_for(buildkit).is_none());
+
+        let podman = "\
+STEP 1/2: FROM debian:bookworm
+STEP 2/2: RUN make
+--> 3f2a1b9c
+COMMIT devc/app
+Error: committing container for step: no space left on device";
+        assert!(failure_for(podman).is_none());
+
+        let legacy = "\
+Step 1/1 : FROM debian:bookworm
+ ---> 1234abcd
+Successfully built 1234abcd
+Error response from daemon: failed to tag image";
+        assert!(failure_for(legacy).is_none());
+    }
+
+    #[test]
+    fn test_failure_without_steps() {
+        assert!(failure_for("Error: no such file: Dockerfile").is_none());
+    }
+
+    #[test]
+    fn test_excerpt_keeps_last_lines() {
+        let mut output = String::from("STEP 1/1: RUN ./noisy.sh\n");
+        for i in 0..30 {
+            output.push_str(&format!("line {i}\n"));
+        }
+        let (_, excerpt) = failure_for(&output).unwrap();
+        assert_eq!(excerpt.lines().count(), EXCERPT_LINES);
+        assert!(excerpt.ends_with("line 29"));
+    }
+}
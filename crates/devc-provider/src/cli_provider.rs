@@ -6,21 +6,25 @@
 //! - Proper user context handling (no permissions issues)
 //! - Works with Docker alternatives (Colima, Rancher, Lima, OrbStack)
 
+use crate::build_log::BuildStepTracker;
+use crate::disk_usage;
+use crate::is_reserved_label;
 use crate::{
-    BuildConfig, ContainerDetails, ContainerId, ContainerInfo, ContainerProvider, ContainerStatus,
-    CreateContainerConfig, DevcontainerSource, DiscoveredContainer, ExecConfig, ExecResult,
-    ExecStream, ImageId, LogConfig, LogStream, MountInfo, MountType, NetworkInfo, NetworkSettings,
-    PortInfo, ProviderError, ProviderInfo, ProviderType, Result,
+    BuildConfig, Capability, ContainerDetails, ContainerDiskUsage, ContainerId, ContainerInfo,
+    ContainerProvider, ContainerStatus, CreateContainerConfig, DevcontainerSource,
+    DiscoveredContainer, ExecConfig, ExecResult, ExecStream, ImageId, ImageInfo, LogConfig,
+    LogStream, MountInfo, MountType, NetworkInfo, NetworkSettings, PortInfo, ProviderError,
+    ProviderInfo, ProviderType, Result,
 };
 use async_trait::async_trait;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::path::Path;
 use std::pin::Pin;
 use std::process::Stdio;
 use std::time::{Duration, Instant};
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, OnceCell};
 
 /// CLI-based container provider for Docker and Podman
 pub struct CliProvider {
@@ -30,6 +34,9 @@ pub struct CliProvider {
     cmd_prefix: Vec<String>,
     /// Provider type
     provider_type: ProviderType,
+    /// Result of the first `supports()` probe of each capability, indexed
+    /// by `Capability`
+    capabilities: [OnceCell<bool>; 4],
 }
 
 impl CliProvider {
@@ -39,6 +46,7 @@ impl CliProvider {
             cmd: "docker".to_string(),
             cmd_prefix: Vec::new(),
             provider_type: ProviderType::Docker,
+            capabilities: Default::default(),
         };
 
         // Test connection
@@ -52,6 +60,7 @@ impl CliProvider {
             cmd: "podman".to_string(),
             cmd_prefix: Vec::new(),
             provider_type: ProviderType::Podman,
+            capabilities: Default::default(),
         };
 
         // Test connection
@@ -65,6 +74,7 @@ impl CliProvider {
             cmd: "podman".to_string(),
             cmd_prefix: vec!["flatpak-spawn".to_string(), "--host".to_string()],
             provider_type: ProviderType::Podman,
+            capabilities: Default::default(),
         };
 
         // Test connection
@@ -74,8 +84,14 @@ impl CliProvider {
 
     /// Run a command and get output
     async fn run_cmd(&self, args: &[&str]) -> Result<String> {
+        self.run_cmd_with_env(args, &[]).await
+    }
+
+    /// Run a command with extra environment variables and get output
+    async fn run_cmd_with_env(&self, args: &[&str], env: &[(&str, &str)]) -> Result<String> {
         let mut cmd = self.build_command();
         cmd.args(args);
+        cmd.envs(env.iter().copied());
 
         let output = cmd
             .stdout(Stdio::piped())
@@ -193,10 +209,10 @@ impl CliProvider {
 
     /// Build a docker/podman cp source spec.
     ///
-    /// For directories append `/.` so contents are copied into destination.
-    /// For files use the file path directly.
+    /// For directories append `/.` so contents are copied into destination,
+    /// unless the path already ends in it. For files use the file path directly.
     fn cp_source_spec(src: &Path) -> String {
-        if src.is_dir() {
+        if src.is_dir() && !src.to_string_lossy().ends_with("/.") {
             format!("{}{}.", src.to_string_lossy(), std::path::MAIN_SEPARATOR)
         } else {
             src.to_string_lossy().to_string()
@@ -401,6 +417,11 @@ impl ContainerProvider for CliProvider {
             args.push("--pull");
         }
 
+        let target = config.target.as_ref().map(|t| format!("--target={}", t));
+        if let Some(ref target) = target {
+            args.push(target);
+        }
+
         // Add build args
         let build_args: Vec<String> = config
             .build_args
@@ -421,9 +442,32 @@ impl ContainerProvider for CliProvider {
             args.push(label);
         }
 
+        let secrets = secret_args(&config.secrets);
+        for secret in &secrets {
+            args.push(secret);
+        }
+
         args.push(&context);
 
-        let output = self.run_cmd(&args).await?;
+        let output = self
+            .run_cmd_with_env(&args, buildkit_env(&config.secrets))
+            .await
+            .map_err(|e| match e {
+                ProviderError::RuntimeError(msg)
+                    if !config.secrets.is_empty() && is_buildkit_missing(&msg) =>
+                {
+                    buildkit_required_error()
+                }
+                ProviderError::RuntimeError(msg) => {
+                    let mut steps = BuildStepTracker::default();
+                    msg.lines().for_each(|line| steps.observe(line));
+                    match steps.failure() {
+                        Some((step, excerpt)) => ProviderError::BuildFailed { step, excerpt },
+                        None => ProviderError::RuntimeError(msg),
+                    }
+                }
+                other => other,
+            })?;
         tracing::debug!("Build output: {}", output);
 
         // Get the image ID
@@ -455,6 +499,10 @@ impl ContainerProvider for CliProvider {
             args.push("--pull".to_string());
         }
 
+        if let Some(ref target) = config.target {
+            args.push(format!("--target={}", target));
+        }
+
         // Add build args
         for (k, v) in &config.build_args {
             args.push(format!("--build-arg={}={}", k, v));
@@ -465,6 +513,8 @@ impl ContainerProvider for CliProvider {
             args.push(format!("--label={}={}", k, v));
         }
 
+        args.extend(secret_args(&config.secrets));
+
         args.push(context.to_string());
 
         // Spawn the build command with streaming output
@@ -472,6 +522,7 @@ impl ContainerProvider for CliProvider {
         for arg in &args {
             cmd.arg(arg);
         }
+        cmd.envs(buildkit_env(&config.secrets).iter().copied());
 
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
@@ -486,6 +537,8 @@ impl ContainerProvider for CliProvider {
         // Podman writes build progress to stdout; Docker/BuildKit uses stderr.
         let mut stdout_lines = child.stdout.take().map(|s| BufReader::new(s).lines());
         let mut stderr_lines = child.stderr.take().map(|s| BufReader::new(s).lines());
+        let mut buildkit_missing = false;
+        let mut steps = BuildStepTracker::default();
 
         loop {
             tokio::select! {
@@ -496,7 +549,11 @@ impl ContainerProvider for CliProvider {
                     }
                 } => {
                     match result {
-                        Ok(Some(line)) => { let _ = progress.send(line); }
+                        Ok(Some(line)) => {
+                            buildkit_missing |= is_buildkit_missing(&line);
+                            steps.observe(&line);
+                            let _ = progress.send(line);
+                        }
                         _ => { stdout_lines = None; }
                     }
                 }
@@ -507,7 +564,11 @@ impl ContainerProvider for CliProvider {
                     }
                 } => {
                     match result {
-                        Ok(Some(line)) => { let _ = progress.send(line); }
+                        Ok(Some(line)) => {
+                            buildkit_missing |= is_buildkit_missing(&line);
+                            steps.observe(&line);
+                            let _ = progress.send(line);
+                        }
                         _ => { stderr_lines = None; }
                     }
                 }
@@ -524,6 +585,15 @@ impl ContainerProvider for CliProvider {
             .map_err(|e| ProviderError::RuntimeError(e.to_string()))?;
 
         if !status.success() {
+            if buildkit_missing && !config.secrets.is_empty() {
+                let err = buildkit_required_error();
+                let _ = progress.send(err.to_string());
+                return Err(err);
+            }
+            if let Some((step, excerpt)) = steps.failure() {
+                let _ = progress.send(format!("Build failed at step {}", step));
+                return Err(ProviderError::BuildFailed { step, excerpt });
+            }
             let _ = progress.send("Build failed".to_string());
             return Err(ProviderError::BuildError("Build failed".to_string()));
         }
@@ -544,6 +614,129 @@ impl ContainerProvider for CliProvider {
         Ok(ImageId::new(output.trim()))
     }
 
+    async fn pull_with_progress(
+        &self,
+        image: &str,
+        progress: mpsc::UnboundedSender<String>,
+    ) -> Result<ImageId> {
+        let mut child = self
+            .build_command()
+            .args(["pull", image])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| ProviderError::RuntimeError(e.to_string()))?;
+
+        // Docker reports layer progress on stdout, podman on stderr; drain both
+        // concurrently so neither pipe fills up and blocks the child.
+        let mut stdout_lines = child.stdout.take().map(|s| BufReader::new(s).lines());
+        let mut stderr_lines = child.stderr.take().map(|s| BufReader::new(s).lines());
+        // Only the last few stderr lines make it into the error; podman
+        // reports every layer there, so keeping them all grows without bound
+        let mut stderr_tail = VecDeque::with_capacity(PULL_ERROR_TAIL_LINES);
+
+        loop {
+            tokio::select! {
+                result = async {
+                    match stdout_lines.as_mut() {
+                        Some(lines) => lines.next_line().await,
+                        None => std::future::pending().await,
+                    }
+                } => {
+                    match result {
+                        Ok(Some(line)) => { let _ = progress.send(line); }
+                        _ => { stdout_lines = None; }
+                    }
+                }
+                result = async {
+                    match stderr_lines.as_mut() {
+                        Some(lines) => lines.next_line().await,
+                        None => std::future::pending().await,
+                    }
+                } => {
+                    match result {
+                        Ok(Some(line)) => {
+                            if stderr_tail.len() == PULL_ERROR_TAIL_LINES {
+                                stderr_tail.pop_front();
+                            }
+                            stderr_tail.push_back(line.clone());
+                            let _ = progress.send(line);
+                        }
+                        _ => { stderr_lines = None; }
+                    }
+                }
+            }
+            if stdout_lines.is_none() && stderr_lines.is_none() {
+                break;
+            }
+        }
+
+        let status = child
+            .wait()
+            .await
+            .map_err(|e| ProviderError::RuntimeError(e.to_string()))?;
+        if !status.success() {
+            let tail: Vec<String> = stderr_tail.into();
+            return Err(ProviderError::RuntimeError(tail.join("\n")));
+        }
+
+        let output = self
+            .run_cmd(&["inspect", "--format={{.Id}}", image])
+            .await?;
+        Ok(ImageId::new(output.trim()))
+    }
+
+    async fn local_image(&self, image: &str) -> Result<Option<ImageId>> {
+        match self
+            .run_cmd(&["image", "inspect", "--format={{.Id}}", image])
+            .await
+        {
+            Ok(output) => Ok(Some(ImageId::new(output.trim()))),
+            Err(ProviderError::RuntimeError(msg)) if is_image_missing(&msg) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn list_images(&self, label: &str) -> Result<Vec<ImageInfo>> {
+        let filter = format!("label={}", label);
+        let output = self
+            .run_cmd(&[
+                "images",
+                "--filter",
+                &filter,
+                "--format",
+                "{{.Repository}}:{{.Tag}}",
+            ])
+            .await?;
+        let tags = parse_image_tags(&output);
+        if tags.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut args = vec!["image", "inspect", "--format", "{{json .Config.Labels}}"];
+        args.extend(tags.iter().map(String::as_str));
+        let output = self.run_cmd(&args).await?;
+        Ok(tags
+            .into_iter()
+            .zip(output.lines())
+            .map(|(tag, labels)| ImageInfo {
+                tag,
+                labels: serde_json::from_str::<Option<HashMap<String, String>>>(labels.trim())
+                    .ok()
+                    .flatten()
+                    .unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    async fn remove_image(&self, id: &ImageId, force: bool) -> Result<()> {
+        if force {
+            self.run_cmd(&["rmi", "-f", &id.0]).await?;
+        } else {
+            self.run_cmd(&["rmi", &id.0]).await?;
+        }
+        Ok(())
+    }
+
     async fn create(&self, config: &CreateContainerConfig) -> Result<ContainerId> {
         let mut args = vec!["create".to_string()];
 
@@ -598,47 +791,39 @@ impl ContainerProvider for CliProvider {
 
         // Ports
         for port in &config.ports {
-            let port_str = match (port.host_port, &port.host_ip) {
+            let mut port_str = match (port.host_port, &port.host_ip) {
                 (Some(hp), Some(ip)) => format!("-p={}:{}:{}", ip, hp, port.container_port),
                 (Some(hp), None) => format!("-p={}:{}", hp, port.container_port),
                 (None, _) => format!("-p={}", port.container_port),
             };
+            // TCP is the runtime default, so only other protocols need the suffix
+            if !port.protocol.is_empty() && port.protocol != "tcp" {
+                port_str.push('/');
+                port_str.push_str(&port.protocol);
+            }
             args.push(port_str);
         }
 
-        // Labels
-        for (k, v) in &config.labels {
-            args.push(format!("--label={}={}", k, v));
-        }
+        args.extend(label_args(config));
 
         // Network mode
         if let Some(ref network) = config.network_mode {
             args.push(format!("--network={}", network));
         }
 
-        // Privileged
-        if config.privileged {
-            args.push("--privileged".to_string());
-        }
-
-        // Capabilities
-        for cap in &config.cap_add {
-            args.push(format!("--cap-add={}", cap));
-        }
-        for cap in &config.cap_drop {
-            args.push(format!("--cap-drop={}", cap));
-        }
-
-        // Security options
-        for opt in &config.security_opt {
-            args.push(format!("--security-opt={}", opt));
-        }
+        args.extend(privilege_args(config));
+        args.extend(resource_args(config));
 
         // Init process
         if config.init {
             args.push("--init".to_string());
         }
 
+        // GPU passthrough
+        if let Some(ref gpus) = config.gpus {
+            args.extend(gpu_args(self.provider_type, gpus));
+        }
+
         // Entrypoint override
         if let Some(ref entrypoint) = config.entrypoint {
             if let Some(ep) = entrypoint.first() {
@@ -704,6 +889,28 @@ impl ContainerProvider for CliProvider {
         Ok(())
     }
 
+    async fn pause(&self, id: &ContainerId) -> Result<()> {
+        self.run_cmd(&["pause", &id.0]).await?;
+        Ok(())
+    }
+
+    async fn unpause(&self, id: &ContainerId) -> Result<()> {
+        self.run_cmd(&["unpause", &id.0]).await?;
+        Ok(())
+    }
+
+    async fn commit(&self, id: &ContainerId, tag: &str) -> Result<ImageId> {
+        let output = self.run_cmd(&["commit", &id.0, tag]).await?;
+        // Podman prints copy progress before the ID; Docker prints just the ID
+        let image_id = output
+            .lines()
+            .rev()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .ok_or_else(|| ProviderError::RuntimeError("commit printed no image ID".into()))?;
+        Ok(ImageId::new(image_id))
+    }
+
     async fn remove(&self, id: &ContainerId, force: bool) -> Result<()> {
         if force {
             self.run_cmd(&["rm", "-f", &id.0]).await?;
@@ -834,8 +1041,39 @@ impl ContainerProvider for CliProvider {
     async fn inspect(&self, id: &ContainerId) -> Result<ContainerDetails> {
         // Use native runtime JSON output. Docker/Podman both return JSON here
         // (typically an array for one ID), and this is more portable than template mode.
-        let output = self.run_cmd(&["container", "inspect", &id.0]).await?;
-        parse_inspect_output(&output, id)
+        match self.run_cmd(&["container", "inspect", &id.0]).await {
+            Ok(output) => parse_inspect_output(&output, id),
+            Err(ProviderError::RuntimeError(msg)) if is_container_missing(&msg) => {
+                Err(ProviderError::ContainerNotFound(id.0.clone()))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn disk_usage(&self) -> Result<Vec<ContainerDiskUsage>> {
+        let report = match self.provider_type {
+            ProviderType::Docker => disk_usage::parse_docker_df(
+                &self
+                    .run_cmd(&["system", "df", "-v", "--format", "{{json .}}"])
+                    .await?,
+            )?,
+            ProviderType::Podman => {
+                disk_usage::parse_podman_df(&self.run_cmd(&["system", "df", "-v"]).await?)
+            }
+        };
+        let ids = report.container_ids();
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut args = vec![
+            "container",
+            "inspect",
+            "--format",
+            disk_usage::MOUNTS_FORMAT,
+        ];
+        args.extend(ids);
+        let mounts = self.run_cmd(&args).await?;
+        Ok(report.into_usage(&mounts))
     }
 
     async fn logs(&self, id: &ContainerId, config: &LogConfig) -> Result<LogStream> {
@@ -856,6 +1094,11 @@ impl ContainerProvider for CliProvider {
         let mut cmd = self.build_command();
         cmd.args(&args[..]);
         cmd.stdout(Stdio::piped());
+        // `logs` replays the container's stderr on its own stderr, which
+        // keeps the two streams apart without parsing the multiplexed format
+        if config.demux {
+            cmd.stderr(Stdio::piped());
+        }
 
         let mut child = cmd
             .kill_on_drop(true)
@@ -863,8 +1106,13 @@ impl ContainerProvider for CliProvider {
             .map_err(|e| ProviderError::RuntimeError(e.to_string()))?;
 
         let stdout = child.stdout.take().expect("stdout must exist when piped");
+        let stderr = child
+            .stderr
+            .take()
+            .map(|s| Box::pin(s) as Pin<Box<dyn tokio::io::AsyncRead + Send>>);
         Ok(LogStream {
             stream: Box::pin(stdout),
+            stderr,
             _child: Some(child),
         })
     }
@@ -874,6 +1122,58 @@ impl ContainerProvider for CliProvider {
         Ok(())
     }
 
+    async fn version(&self) -> Result<String> {
+        // Podman has no server half outside of remote mode
+        let format = match self.provider_type {
+            ProviderType::Docker => "{{.Server.Version}}",
+            ProviderType::Podman => "{{.Client.Version}}",
+        };
+        let output = self.run_cmd(&["version", "--format", format]).await?;
+        parse_version_output(&output)
+            .ok_or_else(|| ProviderError::RuntimeError(format!("{} reported no version", self.cmd)))
+    }
+
+    async fn compose_version(&self) -> Result<String> {
+        let output = self.run_cmd(&["compose", "version"]).await?;
+        parse_compose_version_output(&output).ok_or_else(|| {
+            ProviderError::RuntimeError(format!(
+                "could not parse `{} compose version` output",
+                self.cmd
+            ))
+        })
+    }
+
+    async fn gpu_available(&self) -> bool {
+        match self.provider_type {
+            ProviderType::Docker => self
+                .run_cmd(&["info", "--format", "{{json .Runtimes}}"])
+                .await
+                .is_ok_and(|runtimes| runtimes.contains("nvidia")),
+            // Podman reaches NVIDIA GPUs through CDI specs written by nvidia-ctk
+            ProviderType::Podman => NVIDIA_CDI_SPECS.iter().any(|p| Path::new(p).exists()),
+        }
+    }
+
+    async fn supports(&self, capability: Capability) -> bool {
+        *self.capabilities[capability as usize]
+            .get_or_init(|| async {
+                match capability {
+                    Capability::Compose => self.compose_version().await.is_ok(),
+                    Capability::Buildkit => match self.provider_type {
+                        ProviderType::Docker => self.run_cmd(&["buildx", "version"]).await.is_ok(),
+                        // Podman builds with Buildah, which handles secrets itself
+                        ProviderType::Podman => true,
+                    },
+                    Capability::Gpu => self.gpu_available().await,
+                    Capability::Stats => self
+                        .run_cmd(&["stats", "--no-stream", "--format", "{{.ID}}"])
+                        .await
+                        .is_ok(),
+                }
+            })
+            .await
+    }
+
     fn runtime_args(&self) -> (String, Vec<String>) {
         if self.cmd_prefix.is_empty() {
             (self.cmd.clone(), vec![])
@@ -1242,6 +1542,169 @@ impl ContainerProvider for CliProvider {
     }
 }
 
+/// Convert secret specs into `--secret=` build flags.
+///
+/// Only the spec (`id=...,src=...`) is passed on the command line; the secret
+/// contents are read by the runtime and never pass through devc.
+fn secret_args(secrets: &[String]) -> Vec<String> {
+    secrets.iter().map(|s| format!("--secret={}", s)).collect()
+}
+
+/// Create flags for privileged mode, capabilities and security options
+fn privilege_args(config: &CreateContainerConfig) -> Vec<String> {
+    let mut args = Vec::new();
+    if config.privileged {
+        args.push("--privileged".to_string());
+    }
+    args.extend(
+        config
+            .cap_add
+            .iter()
+            .map(|cap| format!("--cap-add={}", cap)),
+    );
+    args.extend(
+        config
+            .cap_drop
+            .iter()
+            .map(|cap| format!("--cap-drop={}", cap)),
+    );
+    args.extend(
+        config
+            .security_opt
+            .iter()
+            .map(|opt| format!("--security-opt={}", opt)),
+    );
+    args
+}
+
+/// Create flags for devc's management labels plus the user's labels.
+/// User labels may not set reserved keys; those are dropped with a warning.
+fn label_args(config: &CreateContainerConfig) -> Vec<String> {
+    let mut labels: BTreeMap<&str, &str> = BTreeMap::new();
+    for (key, value) in &config.user_labels {
+        if is_reserved_label(key) || config.labels.contains_key(key) {
+            tracing::warn!("Ignoring label '{}': reserved for devc", key);
+            continue;
+        }
+        labels.insert(key, value);
+    }
+    for (key, value) in &config.labels {
+        labels.insert(key, value);
+    }
+    labels
+        .into_iter()
+        .map(|(key, value)| format!("--label={}={}", key, value))
+        .collect()
+}
+
+/// Create flags capping CPU and memory
+fn resource_args(config: &CreateContainerConfig) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(cpus) = config.cpus {
+        args.push(format!("--cpus={}", cpus));
+    }
+    if let Some(memory) = config.memory {
+        args.push(format!("--memory={}", memory));
+    }
+    args
+}
+
+/// stderr lines of a failed `pull` kept for its error message
+const PULL_ERROR_TAIL_LINES: usize = 20;
+
+/// Locations of the CDI spec nvidia-ctk generates for Podman
+const NVIDIA_CDI_SPECS: &[&str] = &[
+    "/etc/cdi/nvidia.yaml",
+    "/etc/cdi/nvidia.json",
+    "/var/run/cdi/nvidia.yaml",
+    "/var/run/cdi/nvidia.json",
+];
+
+/// Create flags exposing GPUs. Docker takes `--gpus` directly; Podman
+/// addresses each GPU as a CDI device instead.
+fn gpu_args(provider_type: ProviderType, gpus: &str) -> Vec<String> {
+    match provider_type {
+        ProviderType::Docker => vec![format!("--gpus={}", gpus)],
+        ProviderType::Podman => gpus
+            .trim_start_matches("device=")
+            .split(',')
+            .map(|device| format!("--device=nvidia.com/gpu={}", device.trim()))
+            .collect(),
+    }
+}
+
+/// Environment needed for a build: secrets require BuildKit, which older
+/// Docker installs only enable when `DOCKER_BUILDKIT=1` is set.
+fn buildkit_env(secrets: &[String]) -> &'static [(&'static str, &'static str)] {
+    if secrets.is_empty() {
+        &[]
+    } else {
+        &[("DOCKER_BUILDKIT", "1")]
+    }
+}
+
+/// Whether an `image inspect` failure means the image simply isn't present
+/// locally (docker: "No such image", podman: "image not known").
+fn is_image_missing(stderr: &str) -> bool {
+    let lower = stderr.to_lowercase();
+    lower.contains("no such image") || lower.contains("image not known")
+}
+
+/// Parse `images --format {{.Repository}}:{{.Tag}}` output, skipping untagged
+/// images. Podman qualifies local-only names with `localhost/`; that's
+/// stripped so tags match what was committed.
+fn parse_image_tags(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.contains("<none>"))
+        .map(|line| line.strip_prefix("localhost/").unwrap_or(line).to_string())
+        .collect()
+}
+
+/// Whether a `container inspect` failure means the container no longer exists
+/// (docker: "No such container", podman: "no such container")
+fn is_container_missing(stderr: &str) -> bool {
+    stderr.to_lowercase().contains("no such container")
+}
+
+/// Whether build output indicates the runtime cannot handle `--secret`
+fn is_buildkit_missing(output: &str) -> bool {
+    let lower = output.to_lowercase();
+    lower.contains("unknown flag: --secret")
+        || (lower.contains("buildkit")
+            && (lower.contains("requires") || lower.contains("not supported")))
+}
+
+fn buildkit_required_error() -> ProviderError {
+    ProviderError::BuildError(
+        "build.secrets requires BuildKit, which this container runtime does not support \
+         (upgrade Docker or install the buildx plugin)"
+            .to_string(),
+    )
+}
+
+/// Parse `<runtime> version --format ...` output into a bare version string
+fn parse_version_output(output: &str) -> Option<String> {
+    let line = output.lines().map(str::trim).find(|l| !l.is_empty())?;
+    Some(line.trim_start_matches('v').to_string())
+}
+
+/// Parse `<runtime> compose version` output into "<implementation> <version>"
+///
+/// Handles Docker Compose ("Docker Compose version v2.29.1") and podman-compose,
+/// which also prints the podman version and, in older releases, a "using ..." line.
+fn parse_compose_version_output(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let (name, rest) = line.trim().split_once(" version")?;
+        if name.is_empty() || name.eq_ignore_ascii_case("podman") || name.starts_with("using") {
+            return None;
+        }
+        let version = rest.trim_start_matches(':').split_whitespace().next()?;
+        Some(format!("{} {}", name, version.trim_start_matches('v')))
+    })
+}
+
 /// Parse the pipe-delimited output of `docker/podman ps` into ContainerInfo items
 fn parse_list_output(output: &str) -> Vec<ContainerInfo> {
     let mut containers = Vec::new();
@@ -1348,6 +1811,13 @@ fn parse_inspect_output(output: &str, id: &ContainerId) -> Result<ContainerDetai
         })
         .unwrap_or_default();
 
+    // Configured user (empty means the runtime default, i.e. root)
+    let user = config
+        .and_then(|c| c.get("User"))
+        .and_then(serde_json::Value::as_str)
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string());
+
     // Parse mounts
     let mounts: Vec<MountInfo> = info
         .get("Mounts")
@@ -1495,6 +1965,11 @@ fn parse_inspect_output(output: &str, id: &ContainerId) -> Result<ContainerDetai
         .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
         .map(|dt| dt.timestamp());
 
+    let restart_count = info
+        .get("RestartCount")
+        .and_then(serde_json::Value::as_u64)
+        .unwrap_or(0);
+
     Ok(ContainerDetails {
         id: id.clone(),
         name,
@@ -1505,8 +1980,10 @@ fn parse_inspect_output(output: &str, id: &ContainerId) -> Result<ContainerDetai
         started_at,
         finished_at,
         exit_code,
+        restart_count,
         labels,
         env,
+        user,
         mounts,
         ports,
         network_settings,
@@ -1876,6 +2353,143 @@ mod tests {
 
     // ==================== parse_cli_labels tests ====================
 
+    #[test]
+    fn test_secret_args_and_buildkit_env() {
+        assert!(secret_args(&[]).is_empty());
+        assert!(buildkit_env(&[]).is_empty());
+
+        let secrets = vec!["id=npm,src=/home/me/.npmrc".to_string()];
+        assert_eq!(
+            secret_args(&secrets),
+            vec!["--secret=id=npm,src=/home/me/.npmrc".to_string()]
+        );
+        assert_eq!(buildkit_env(&secrets), &[("DOCKER_BUILDKIT", "1")]);
+    }
+
+    #[test]
+    fn test_privilege_args() {
+        assert!(privilege_args(&CreateContainerConfig::default()).is_empty());
+
+        let config = CreateContainerConfig {
+            privileged: true,
+            cap_add: vec!["SYS_PTRACE".to_string()],
+            cap_drop: vec!["MKNOD".to_string()],
+            security_opt: vec!["seccomp=unconfined".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            privilege_args(&config),
+            vec![
+                "--privileged".to_string(),
+                "--cap-add=SYS_PTRACE".to_string(),
+                "--cap-drop=MKNOD".to_string(),
+                "--security-opt=seccomp=unconfined".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_label_args_merges_user_labels_without_overriding_devc() {
+        let config = CreateContainerConfig {
+            labels: HashMap::from([
+                ("devc.managed".to_string(), "true".to_string()),
+                ("devc.project".to_string(), "app".to_string()),
+            ]),
+            user_labels: HashMap::from([
+                ("team".to_string(), "infra".to_string()),
+                ("devc.managed".to_string(), "false".to_string()),
+                ("devc.custom".to_string(), "x".to_string()),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(
+            label_args(&config),
+            vec![
+                "--label=devc.managed=true".to_string(),
+                "--label=devc.project=app".to_string(),
+                "--label=team=infra".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resource_args() {
+        assert!(resource_args(&CreateContainerConfig::default()).is_empty());
+
+        let config = CreateContainerConfig {
+            cpus: Some(1.5),
+            memory: Some(512 << 20),
+            ..Default::default()
+        };
+        assert_eq!(
+            resource_args(&config),
+            vec!["--cpus=1.5".to_string(), "--memory=536870912".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_gpu_args_per_runtime() {
+        assert_eq!(
+            gpu_args(ProviderType::Docker, "all"),
+            vec!["--gpus=all".to_string()]
+        );
+        assert_eq!(
+            gpu_args(ProviderType::Podman, "all"),
+            vec!["--device=nvidia.com/gpu=all".to_string()]
+        );
+        assert_eq!(
+            gpu_args(ProviderType::Podman, "device=0,1"),
+            vec![
+                "--device=nvidia.com/gpu=0".to_string(),
+                "--device=nvidia.com/gpu=1".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_buildkit_missing() {
+        assert!(is_buildkit_missing("unknown flag: --secret"));
+        assert!(is_buildkit_missing(
+            "ERROR: the --secret flag requires BuildKit"
+        ));
+        assert!(!is_buildkit_missing("Step 1/3 : FROM alpine"));
+        assert!(!is_buildkit_missing("#5 [internal] load build context"));
+    }
+
+    #[test]
+    fn test_is_image_missing() {
+        assert!(is_image_missing(
+            "Error response from daemon: No such image: alpine:3.19"
+        ));
+        assert!(is_image_missing("Error: alpine:3.19: image not known"));
+        assert!(!is_image_missing(
+            "Cannot connect to the Docker daemon at unix:///var/run/docker.sock"
+        ));
+    }
+
+    #[test]
+    fn test_parse_image_tags() {
+        let output = "devc/app:frozen-20240101-000000\n\
+                      localhost/devc/api:frozen-20240102-000000\n\
+                      <none>:<none>\n";
+        assert_eq!(
+            parse_image_tags(output),
+            vec![
+                "devc/app:frozen-20240101-000000",
+                "devc/api:frozen-20240102-000000"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_container_missing() {
+        assert!(is_container_missing(
+            "Error response from daemon: No such container: abc123"
+        ));
+        assert!(is_container_missing("Error: no such container abc123"));
+        assert!(!is_container_missing("permission denied"));
+    }
+
     #[test]
     fn test_parse_cli_labels_basic() {
         let labels = parse_cli_labels("foo=bar,baz=qux");
@@ -1958,6 +2572,35 @@ mod tests {
         assert!(!is_dc);
     }
 
+    #[test]
+    fn test_parse_version_output() {
+        assert_eq!(parse_version_output("27.1.1\n"), Some("27.1.1".to_string()));
+        assert_eq!(
+            parse_version_output("\nv4.9.3\n"),
+            Some("4.9.3".to_string())
+        );
+        assert_eq!(parse_version_output("  \n"), None);
+    }
+
+    #[test]
+    fn test_parse_compose_version_output() {
+        assert_eq!(
+            parse_compose_version_output("Docker Compose version v2.29.1-desktop.1\n"),
+            Some("Docker Compose 2.29.1-desktop.1".to_string())
+        );
+        assert_eq!(
+            parse_compose_version_output("podman-compose version 1.0.6\npodman version 4.9.3\n"),
+            Some("podman-compose 1.0.6".to_string())
+        );
+        assert_eq!(
+            parse_compose_version_output(
+                "['podman', '--version', '']\nusing podman version: 4.3.1\npodman-compose version: 1.0.3\n"
+            ),
+            Some("podman-compose 1.0.3".to_string())
+        );
+        assert_eq!(parse_compose_version_output("unknown command\n"), None);
+    }
+
     // ==================== parse_compose_ps_output tests ====================
 
     #[test]
@@ -2122,6 +2765,7 @@ mod tests {
             },
             "Config": {
                 "Image": "ubuntu:22.04",
+                "User": "vscode",
                 "Env": ["PATH=/usr/bin", "TERM=xterm"],
                 "Labels": {
                     "devc.managed": "true",
@@ -2179,6 +2823,7 @@ mod tests {
         assert_eq!(details.env.len(), 2);
         assert!(details.env.contains(&"PATH=/usr/bin".to_string()));
         assert!(details.env.contains(&"TERM=xterm".to_string()));
+        assert_eq!(details.user.as_deref(), Some("vscode"));
 
         // Mounts
         assert_eq!(details.mounts.len(), 2);
@@ -2224,6 +2869,7 @@ mod tests {
             "Created": "2024-02-01T15:00:00.000000000Z",
             "Name": "podman-container",
             "Image": "sha256:podimg789",
+            "RestartCount": 3,
             "State": {
                 "Status": "exited",
                 "Running": false,
@@ -2256,6 +2902,7 @@ mod tests {
         assert!(details.mounts.is_empty());
         assert!(details.ports.is_empty());
         assert!(details.finished_at.is_some());
+        assert_eq!(details.restart_count, 3);
     }
 
     #[test]
@@ -0,0 +1,336 @@
+//! Per-container disk usage from `system df -v`
+//!
+//! Docker can print the report as JSON; podman refuses `--format` together with
+//! `--verbose`, so its tables are parsed by column position. Neither says which
+//! volumes belong to which container, so that comes from `inspect` mounts.
+
+use crate::{parse_size, ContainerDiskUsage, ContainerId, ProviderError, Result, VolumeUsage};
+use std::collections::HashMap;
+
+/// `inspect` format pairing each container ID with its mounts
+pub(crate) const MOUNTS_FORMAT: &str = "{{.Id}} {{json .Mounts}}";
+
+/// What `system df -v` reports, before volumes are attributed to containers
+#[derive(Debug, Default)]
+pub(crate) struct DfReport {
+    images: Vec<DfImage>,
+    containers: Vec<DfContainer>,
+    volumes: HashMap<String, u64>,
+}
+
+#[derive(Debug)]
+struct DfImage {
+    id: String,
+    reference: String,
+    bytes: u64,
+}
+
+#[derive(Debug)]
+struct DfContainer {
+    id: String,
+    name: String,
+    image: String,
+    bytes: u64,
+}
+
+impl DfReport {
+    pub(crate) fn container_ids(&self) -> Vec<&str> {
+        self.containers.iter().map(|c| c.id.as_str()).collect()
+    }
+
+    /// Combine with `inspect --format MOUNTS_FORMAT` output for the same containers
+    pub(crate) fn into_usage(self, mounts_output: &str) -> Vec<ContainerDiskUsage> {
+        let mounts = parse_volume_mounts(mounts_output);
+        self.containers
+            .iter()
+            .map(|c| {
+                let volumes = mounts
+                    .iter()
+                    .find(|(id, _)| id.starts_with(&c.id))
+                    .map(|(_, names)| names.as_slice())
+                    .unwrap_or_default()
+                    .iter()
+                    .filter(|name| is_anonymous_volume(name))
+                    .map(|name| VolumeUsage {
+                        name: name.clone(),
+                        bytes: self.volumes.get(name).copied().unwrap_or(0),
+                    })
+                    .collect();
+                ContainerDiskUsage {
+                    id: ContainerId::new(&c.id),
+                    name: c.name.clone(),
+                    image: c.image.clone(),
+                    writable_bytes: c.bytes,
+                    image_bytes: self.image_bytes(&c.image),
+                    volumes,
+                }
+            })
+            .collect()
+    }
+
+    /// Containers name their image by reference (docker) or short ID (podman)
+    fn image_bytes(&self, image: &str) -> Option<u64> {
+        if image.is_empty() {
+            return None;
+        }
+        let image = image.strip_prefix("sha256:").unwrap_or(image);
+        self.images
+            .iter()
+            .find(|i| {
+                i.reference == image
+                    || i.reference == format!("{}:latest", image)
+                    || i.reference.ends_with(&format!("/{}", image))
+                    || (!i.id.is_empty() && (i.id.starts_with(image) || image.starts_with(&i.id)))
+            })
+            .map(|i| i.bytes)
+    }
+}
+
+/// Parse `docker system df -v --format '{{json .}}'`
+pub(crate) fn parse_docker_df(output: &str) -> Result<DfReport> {
+    let value: serde_json::Value = serde_json::from_str(output.trim()).map_err(|e| {
+        ProviderError::RuntimeError(format!("system df output was not valid JSON: {}", e))
+    })?;
+    let field = |entry: &serde_json::Value, key: &str| {
+        entry
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    let entries = |key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    let images = entries("Images")
+        .iter()
+        .map(|i| DfImage {
+            id: field(i, "ID").trim_start_matches("sha256:").to_string(),
+            reference: format!("{}:{}", field(i, "Repository"), field(i, "Tag")),
+            bytes: parse_size(&field(i, "Size")).unwrap_or(0),
+        })
+        .collect();
+    let containers = entries("Containers")
+        .iter()
+        .map(|c| DfContainer {
+            id: field(c, "ID"),
+            name: field(c, "Names"),
+            image: field(c, "Image"),
+            bytes: parse_size(&field(c, "Size")).unwrap_or(0),
+        })
+        .collect();
+    let volumes = entries("Volumes")
+        .iter()
+        .map(|v| (field(v, "Name"), parse_size(&field(v, "Size")).unwrap_or(0)))
+        .collect();
+
+    Ok(DfReport {
+        images,
+        containers,
+        volumes,
+    })
+}
+
+/// Parse the tables printed by `podman system df -v`
+pub(crate) fn parse_podman_df(output: &str) -> DfReport {
+    let mut report = DfReport::default();
+    let mut sections: Vec<(&str, Vec<&str>)> = Vec::new();
+    for line in output.lines() {
+        if let Some(title) = line.trim().strip_suffix("space usage:") {
+            sections.push((title.trim(), Vec::new()));
+        } else if let Some((_, lines)) = sections.last_mut() {
+            if !line.trim().is_empty() {
+                lines.push(line);
+            }
+        }
+    }
+
+    for (title, lines) in sections {
+        let rows = table_rows(&lines);
+        match title {
+            "Images" => {
+                report.images = rows
+                    .iter()
+                    .map(|r| DfImage {
+                        id: r.get("IMAGE ID").cloned().unwrap_or_default(),
+                        reference: format!(
+                            "{}:{}",
+                            r.get("REPOSITORY").map_or("", String::as_str),
+                            r.get("TAG").map_or("", String::as_str)
+                        ),
+                        bytes: r.get("SIZE").and_then(|s| parse_size(s)).unwrap_or(0),
+                    })
+                    .collect();
+            }
+            "Containers" => {
+                report.containers = rows
+                    .iter()
+                    .map(|r| DfContainer {
+                        id: r.get("CONTAINER ID").cloned().unwrap_or_default(),
+                        name: r.get("NAMES").cloned().unwrap_or_default(),
+                        image: r.get("IMAGE").cloned().unwrap_or_default(),
+                        bytes: r.get("SIZE").and_then(|s| parse_size(s)).unwrap_or(0),
+                    })
+                    .collect();
+            }
+            "Local Volumes" => {
+                report.volumes = rows
+                    .iter()
+                    .filter_map(|r| {
+                        let name = r.get("VOLUME NAME")?.clone();
+                        Some((name, r.get("SIZE").and_then(|s| parse_size(s)).unwrap_or(0)))
+                    })
+                    .collect();
+            }
+            _ => {}
+        }
+    }
+    report
+}
+
+/// Split a left-aligned table into rows keyed by header, using header offsets
+fn table_rows(lines: &[&str]) -> Vec<HashMap<String, String>> {
+    let Some((header, rows)) = lines.split_first() else {
+        return Vec::new();
+    };
+
+    // Headers are separated by two or more spaces ("CONTAINER ID  IMAGE")
+    let mut columns: Vec<(usize, String)> = Vec::new();
+    let mut start = None;
+    let bytes = header.as_bytes();
+    for (i, &b) in bytes.iter().enumerate() {
+        let gap = b == b' ' && bytes.get(i + 1).is_none_or(|&n| n == b' ');
+        match (start, gap) {
+            (None, false) if b != b' ' => start = Some(i),
+            (Some(s), true) => {
+                columns.push((s, header[s..i].to_string()));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        columns.push((s, header[s..].trim_end().to_string()));
+    }
+
+    rows.iter()
+        .map(|row| {
+            columns
+                .iter()
+                .enumerate()
+                .map(|(i, (offset, name))| {
+                    let end = columns.get(i + 1).map_or(row.len(), |(next, _)| *next);
+                    let value = row.get(*offset..end.min(row.len())).unwrap_or_default();
+                    (name.clone(), value.trim().to_string())
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Volume names per full container ID, from `inspect --format MOUNTS_FORMAT`
+fn parse_volume_mounts(output: &str) -> Vec<(String, Vec<String>)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (id, mounts) = line.trim().split_once(' ')?;
+            let mounts: Vec<serde_json::Value> = serde_json::from_str(mounts).ok()?;
+            let names = mounts
+                .iter()
+                .filter(|m| m.get("Type").and_then(|t| t.as_str()) == Some("volume"))
+                .filter_map(|m| m.get("Name").and_then(|n| n.as_str()))
+                .map(str::to_string)
+                .collect();
+            Some((id.to_string(), names))
+        })
+        .collect()
+}
+
+/// Anonymous volumes are named by a 64-character hex ID
+fn is_anonymous_volume(name: &str) -> bool {
+    name.len() == 64 && name.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANON: &str = "4f0c8d7b9a1e2f3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f7081920a1b";
+
+    fn mounts_output(id: &str) -> String {
+        format!(
+            r#"{} [{{"Type":"bind","Source":"/home/me/app","Destination":"/workspaces/app"}},{{"Type":"volume","Name":"{}","Destination":"/data"}},{{"Type":"volume","Name":"shared-cache","Destination":"/cache"}}]"#,
+            id, ANON
+        )
+    }
+
+    #[test]
+    fn test_parse_docker_df() {
+        let output = format!(
+            r#"{{"Images":[{{"ID":"sha256:aaa111","Repository":"devc-app","Tag":"latest","Size":"1.2GB"}}],
+            "Containers":[{{"ID":"f1b3c5d7e9a1","Names":"devc-app","Image":"devc-app:latest","Size":"12.5MB"}}],
+            "Volumes":[{{"Name":"{}","Size":"300MB"}},{{"Name":"shared-cache","Size":"2GB"}}]}}"#,
+            ANON
+        );
+        let report = parse_docker_df(&output).unwrap();
+        assert_eq!(report.container_ids(), vec!["f1b3c5d7e9a1"]);
+
+        let usage = report.into_usage(&mounts_output("f1b3c5d7e9a1ffffffff"));
+        assert_eq!(usage.len(), 1);
+        let u = &usage[0];
+        assert_eq!(u.name, "devc-app");
+        assert_eq!(u.writable_bytes, 12_500_000);
+        assert_eq!(u.image_bytes, Some(1_200_000_000));
+        // Named volumes are shared, so only the anonymous one is attributed
+        assert_eq!(
+            u.volumes,
+            vec![VolumeUsage {
+                name: ANON.to_string(),
+                bytes: 300_000_000
+            }]
+        );
+        assert_eq!(u.total_bytes(), 312_500_000);
+    }
+
+    #[test]
+    fn test_parse_podman_df() {
+        let output = format!(
+            "\
+Images space usage:
+
+REPOSITORY                TAG         IMAGE ID      CREATED      SIZE        SHARED SIZE  UNIQUE SIZE  CONTAINERS
+docker.io/library/alpine  latest      c1aabb73d233  2 weeks ago  7.63MB      0B           7.63MB       1
+
+Containers space usage:
+
+CONTAINER ID  IMAGE         COMMAND         LOCAL VOLUMES  SIZE        CREATED      STATUS      NAMES
+0a1b2c3d4e5f  c1aabb73d233  sleep infinity  1              1.2kB       2 hours ago  running     devc-app
+
+Local Volumes space usage:
+
+VOLUME NAME                                                       LINKS       SIZE
+{}  1           4.1MB
+",
+            ANON
+        );
+        let report = parse_podman_df(&output);
+        let usage = report.into_usage(&mounts_output("0a1b2c3d4e5f6789"));
+        assert_eq!(usage.len(), 1);
+        let u = &usage[0];
+        assert_eq!(u.id, ContainerId::new("0a1b2c3d4e5f"));
+        assert_eq!(u.name, "devc-app");
+        assert_eq!(u.writable_bytes, 1_200);
+        assert_eq!(u.image_bytes, Some(7_630_000));
+        assert_eq!(u.volumes.len(), 1);
+        assert_eq!(u.volumes[0].bytes, 4_100_000);
+    }
+
+    #[test]
+    fn test_parse_docker_df_invalid() {
+        assert!(parse_docker_df("not json").is_err());
+    }
+}
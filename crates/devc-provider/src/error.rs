@@ -16,6 +16,10 @@ pub enum ProviderError {
     #[error("Build failed: {0}")]
     BuildError(String),
 
+    /// A build step failed; `excerpt` holds the step's last output lines
+    #[error("Build failed at step {step}\n{excerpt}")]
+    BuildFailed { step: String, excerpt: String },
+
     #[error("Exec failed: {0}")]
     ExecError(String),
 
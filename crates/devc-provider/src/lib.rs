@@ -9,10 +9,13 @@
 //! - Proper user context handling (no permissions issues)
 //! - Works with Docker alternatives (Colima, Rancher, Lima, OrbStack)
 
+mod build_log;
 mod cli_provider;
+mod disk_usage;
 mod error;
 mod types;
 
+pub use build_log::build_step_marker;
 pub use cli_provider::CliProvider;
 pub use error::*;
 pub use types::*;
@@ -40,6 +43,23 @@ pub trait ContainerProvider: Send + Sync {
     /// Pull an image from a registry
     async fn pull(&self, image: &str) -> Result<ImageId>;
 
+    /// Pull an image with progress streaming
+    /// Raw pull output lines are sent to the provided channel
+    async fn pull_with_progress(
+        &self,
+        image: &str,
+        progress: mpsc::UnboundedSender<String>,
+    ) -> Result<ImageId>;
+
+    /// Look up an image in the local image store, returning its ID if present
+    async fn local_image(&self, image: &str) -> Result<Option<ImageId>>;
+
+    /// List tagged local images carrying `label` (a `key` or `key=value` filter)
+    async fn list_images(&self, label: &str) -> Result<Vec<ImageInfo>>;
+
+    /// Remove an image from the local image store
+    async fn remove_image(&self, id: &ImageId, force: bool) -> Result<()>;
+
     /// Create a container from an image
     async fn create(&self, config: &CreateContainerConfig) -> Result<ContainerId>;
 
@@ -49,6 +69,15 @@ pub trait ContainerProvider: Send + Sync {
     /// Stop a container
     async fn stop(&self, id: &ContainerId, timeout: Option<u32>) -> Result<()>;
 
+    /// Freeze all processes in a running container
+    async fn pause(&self, id: &ContainerId) -> Result<()>;
+
+    /// Resume a paused container
+    async fn unpause(&self, id: &ContainerId) -> Result<()>;
+
+    /// Save a container's filesystem (its writable layer) as an image tagged `tag`
+    async fn commit(&self, id: &ContainerId, tag: &str) -> Result<ImageId>;
+
     /// Remove a container
     async fn remove(&self, id: &ContainerId, force: bool) -> Result<()>;
 
@@ -78,6 +107,9 @@ pub trait ContainerProvider: Send + Sync {
     /// Get detailed information about a container
     async fn inspect(&self, id: &ContainerId) -> Result<ContainerDetails>;
 
+    /// Disk used by each container's writable layer and anonymous volumes
+    async fn disk_usage(&self) -> Result<Vec<ContainerDiskUsage>>;
+
     /// Get container logs
     async fn logs(&self, id: &ContainerId, config: &LogConfig) -> Result<LogStream>;
 
@@ -87,6 +119,36 @@ pub trait ContainerProvider: Send + Sync {
     /// Get provider information
     fn info(&self) -> ProviderInfo;
 
+    /// Get the runtime's version (e.g. "27.1.1"), as reported by the engine
+    async fn version(&self) -> Result<String>;
+
+    /// Get the compose implementation and its version
+    /// (e.g. "Docker Compose 2.29.1" or "podman-compose 1.0.6")
+    async fn compose_version(&self) -> Result<String>;
+
+    /// Whether the runtime can hand NVIDIA GPUs to containers
+    async fn gpu_available(&self) -> bool;
+
+    /// Whether the runtime supports one optional feature (probed on first
+    /// call, then cached)
+    async fn supports(&self, capability: Capability) -> bool;
+
+    /// Every optional feature this runtime supports
+    async fn capabilities(&self) -> ProviderCapabilities {
+        let (compose, buildkit, gpu, stats) = tokio::join!(
+            self.supports(Capability::Compose),
+            self.supports(Capability::Buildkit),
+            self.supports(Capability::Gpu),
+            self.supports(Capability::Stats),
+        );
+        ProviderCapabilities {
+            compose,
+            buildkit,
+            gpu,
+            stats,
+        }
+    }
+
     /// Discover all devcontainers (including those not managed by devc)
     /// Returns containers with devcontainer-related labels or mounts
     async fn discover_devcontainers(&self) -> Result<Vec<DiscoveredContainer>>;
@@ -146,6 +146,17 @@ pub struct BuildConfig {
     pub no_cache: bool,
     /// Pull base image
     pub pull: bool,
+    /// BuildKit secret specs passed through as `--secret` (e.g. `id=token,src=/path`)
+    pub secrets: Vec<String>,
+}
+
+/// Label keys under this prefix are devc's own management labels
+pub const RESERVED_LABEL_PREFIX: &str = "devc.";
+
+/// Whether a label key belongs to devc, so user-supplied labels can't
+/// change how devc recognizes its containers
+pub fn is_reserved_label(key: &str) -> bool {
+    key.starts_with(RESERVED_LABEL_PREFIX)
 }
 
 /// Configuration for creating a container
@@ -169,8 +180,10 @@ pub struct CreateContainerConfig {
     pub mounts: Vec<MountConfig>,
     /// Port mappings
     pub ports: Vec<PortConfig>,
-    /// Labels
+    /// Labels devc uses to track the container (`devc.*`)
     pub labels: HashMap<String, String>,
+    /// Labels requested by the user; reserved keys are dropped, see [`is_reserved_label`]
+    pub user_labels: HashMap<String, String>,
     /// Hostname
     pub hostname: Option<String>,
     /// Allocate TTY
@@ -189,6 +202,12 @@ pub struct CreateContainerConfig {
     pub security_opt: Vec<String>,
     /// Run an init process inside the container
     pub init: bool,
+    /// GPUs to expose (`all` or `device=0,1`), as for `docker run --gpus`
+    pub gpus: Option<String>,
+    /// CPU limit (`--cpus`)
+    pub cpus: Option<f64>,
+    /// Memory limit in bytes (`--memory`)
+    pub memory: Option<u64>,
     /// Extra arguments to pass to docker/podman create
     pub extra_args: Vec<String>,
 }
@@ -288,6 +307,93 @@ impl ContainerInfo {
     }
 }
 
+/// A tagged local image, for listing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageInfo {
+    /// `repository:tag` reference
+    pub tag: String,
+    pub labels: HashMap<String, String>,
+}
+
+/// Disk space used by a container, from `system df -v`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContainerDiskUsage {
+    pub id: ContainerId,
+    pub name: String,
+    pub image: String,
+    /// Size of the container's writable layer
+    pub writable_bytes: u64,
+    /// Size of the image it runs from, which other containers may share
+    pub image_bytes: Option<u64>,
+    /// Anonymous volumes mounted by the container
+    pub volumes: Vec<VolumeUsage>,
+}
+
+impl ContainerDiskUsage {
+    /// Space owned by this container alone: writable layer plus its anonymous volumes
+    pub fn total_bytes(&self) -> u64 {
+        self.writable_bytes + self.volumes.iter().map(|v| v.bytes).sum::<u64>()
+    }
+}
+
+/// Disk space used by a volume
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VolumeUsage {
+    pub name: String,
+    pub bytes: u64,
+}
+
+/// Format a byte count with decimal units, as the container runtimes do ("12.3MB")
+pub fn format_bytes(bytes: u64) -> String {
+    let b = bytes as f64;
+    if b >= 1e9 {
+        format!("{:.1}GB", b / 1e9)
+    } else if b >= 1e6 {
+        format!("{:.1}MB", b / 1e6)
+    } else if b >= 1e3 {
+        format!("{:.1}kB", b / 1e3)
+    } else {
+        format!("{}B", bytes)
+    }
+}
+
+/// Parse a runtime-formatted size ("0B", "7.8MB", "1.2kB", "3.4GiB")
+pub fn parse_size(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier = match unit.trim() {
+        "B" | "" => 1.0,
+        "kB" | "KB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        "TB" => 1e12,
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some((number * multiplier).round() as u64)
+}
+
+/// Format an uptime in seconds compactly, e.g. `45s`, `12m`, `3h 5m`, `2d 4h`
+pub fn format_uptime(secs: i64) -> String {
+    let secs = secs.max(0);
+    let (days, hours, mins) = (secs / 86_400, (secs % 86_400) / 3_600, (secs % 3_600) / 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, mins)
+    } else if mins > 0 {
+        format!("{}m", mins)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
 /// Detailed container information
 #[derive(Debug, Clone)]
 pub struct ContainerDetails {
@@ -300,13 +406,27 @@ pub struct ContainerDetails {
     pub started_at: Option<i64>,
     pub finished_at: Option<i64>,
     pub exit_code: Option<i64>,
+    /// Times the runtime has restarted the container under its restart policy
+    pub restart_count: u64,
     pub labels: HashMap<String, String>,
     pub env: Vec<String>,
+    /// User configured on the container (`Config.User`), usually inherited from the image
+    pub user: Option<String>,
     pub mounts: Vec<MountInfo>,
     pub ports: Vec<PortInfo>,
     pub network_settings: NetworkSettings,
 }
 
+impl ContainerDetails {
+    /// Seconds since the container started, while it is running
+    pub fn uptime_secs(&self, now: i64) -> Option<i64> {
+        (self.status == ContainerStatus::Running)
+            .then_some(self.started_at)
+            .flatten()
+            .map(|started| now - started)
+    }
+}
+
 /// Mount information
 #[derive(Debug, Clone)]
 pub struct MountInfo {
@@ -358,15 +478,70 @@ pub struct LogConfig {
     pub since: Option<i64>,
     /// Show logs until this time (unix timestamp)
     pub until: Option<i64>,
+    /// Deliver stderr separately in `LogStream::stderr` instead of alongside stdout
+    pub demux: bool,
 }
 
 /// Log stream
 pub struct LogStream {
     pub stream: Pin<Box<dyn AsyncRead + Send>>,
+    /// The container's stderr, when `LogConfig::demux` was requested
+    pub stderr: Option<Pin<Box<dyn AsyncRead + Send>>>,
     /// Holds the child process so it is killed when the stream is dropped.
     pub _child: Option<tokio::process::Child>,
 }
 
+/// Output stream a log line was written to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogSource {
+    Stdout,
+    Stderr,
+}
+
+/// One log line, as emitted by `devc logs --json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LogLine {
+    /// RFC 3339 timestamp recorded by the runtime
+    pub ts: Option<String>,
+    pub stream: LogSource,
+    pub message: String,
+}
+
+impl LogLine {
+    /// Split a line read with `LogConfig::timestamps` into its timestamp and message
+    pub fn from_timestamped(line: &str, stream: LogSource) -> Self {
+        match line.split_once(' ') {
+            Some((ts, message)) if is_log_timestamp(ts) => Self {
+                ts: Some(ts.to_string()),
+                stream,
+                message: message.to_string(),
+            },
+            // An empty message leaves the timestamp without a trailing space
+            _ if is_log_timestamp(line) => Self {
+                ts: Some(line.to_string()),
+                stream,
+                message: String::new(),
+            },
+            _ => Self {
+                ts: None,
+                stream,
+                message: line.to_string(),
+            },
+        }
+    }
+}
+
+/// Whether `s` looks like the RFC 3339 prefix `logs -t` puts on each line
+fn is_log_timestamp(s: &str) -> bool {
+    let b = s.as_bytes();
+    b.len() >= 20
+        && b[..4].iter().all(u8::is_ascii_digit)
+        && b[4] == b'-'
+        && b[10] == b'T'
+        && b[13] == b':'
+}
+
 /// Provider information
 #[derive(Debug, Clone)]
 pub struct ProviderInfo {
@@ -377,6 +552,28 @@ pub struct ProviderInfo {
     pub arch: String,
 }
 
+/// One optional runtime feature, see [`ProviderCapabilities`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    Compose,
+    Buildkit,
+    Gpu,
+    Stats,
+}
+
+/// Optional runtime features, each probed once per provider when first asked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProviderCapabilities {
+    /// `compose` subcommand (Docker Compose or podman-compose)
+    pub compose: bool,
+    /// BuildKit builds (`build.secrets`)
+    pub buildkit: bool,
+    /// NVIDIA GPUs can be handed to containers
+    pub gpu: bool,
+    /// `stats` works (needs cgroup access, which rootless setups may lack)
+    pub stats: bool,
+}
+
 /// Source of a discovered devcontainer
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DevcontainerSource {
@@ -434,6 +631,23 @@ mod tests {
         assert!("invalid".parse::<ProviderType>().is_err());
     }
 
+    #[test]
+    fn test_format_uptime() {
+        assert_eq!(format_uptime(-5), "0s");
+        assert_eq!(format_uptime(42), "42s");
+        assert_eq!(format_uptime(12 * 60 + 30), "12m");
+        assert_eq!(format_uptime(3 * 3600 + 5 * 60), "3h 5m");
+        assert_eq!(format_uptime(2 * 86_400 + 4 * 3600 + 59), "2d 4h");
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("0B"), Some(0));
+        assert_eq!(parse_size("1.5kB"), Some(1_500));
+        assert_eq!(parse_size("2GiB"), Some(2 * 1024 * 1024 * 1024));
+        assert_eq!(parse_size("N/A"), None);
+    }
+
     #[test]
     fn test_container_status_from_str() {
         assert_eq!(ContainerStatus::from("running"), ContainerStatus::Running);
@@ -448,6 +662,28 @@ mod tests {
         assert_eq!(ContainerStatus::from("removing"), ContainerStatus::Removing);
         assert_eq!(ContainerStatus::from("garbage"), ContainerStatus::Unknown);
     }
+
+    #[test]
+    fn test_log_line_from_timestamped() {
+        let line = LogLine::from_timestamped(
+            "2026-03-01T12:00:00.123456789Z listening on :8080",
+            LogSource::Stderr,
+        );
+        assert_eq!(line.ts.as_deref(), Some("2026-03-01T12:00:00.123456789Z"));
+        assert_eq!(line.message, "listening on :8080");
+        assert_eq!(
+            serde_json::to_string(&line).unwrap(),
+            r#"{"ts":"2026-03-01T12:00:00.123456789Z","stream":"stderr","message":"listening on :8080"}"#
+        );
+
+        let empty = LogLine::from_timestamped("2026-03-01T12:00:00Z", LogSource::Stdout);
+        assert_eq!(empty.ts.as_deref(), Some("2026-03-01T12:00:00Z"));
+        assert_eq!(empty.message, "");
+
+        let plain = LogLine::from_timestamped("no timestamp here", LogSource::Stdout);
+        assert_eq!(plain.ts, None);
+        assert_eq!(plain.message, "no timestamp here");
+    }
 }
 
 /// Information about a service within a Docker Compose project
@@ -1,23 +1,28 @@
 //! Main TUI application state and logic
 
+use crate::build_export;
 use crate::clipboard::copy_to_clipboard;
 use crate::compose_state::ComposeViewState;
 use crate::event::{Event, EventHandler};
-use crate::port_state::PortForwardingState;
-use crate::ports::{spawn_port_detector, PortDetectionUpdate};
+use crate::keymap::{self, Action, KeyContext};
+use crate::port_state::{ForwarderKey, PortForwardingState};
+use crate::ports::{spawn_port_detector, PortDetectionUpdate, PortScheme};
 use crate::settings::{ProviderDetailState, SettingsState};
 #[cfg(unix)]
 use crate::shell::PtyShell;
 use crate::shell::{ShellConfig, ShellExitReason};
 use crate::shell_state::{ShellSession, ShellState};
+use crate::toast::Toasts;
 use crate::tunnel::{
     check_socat_installed, install_socat, open_in_browser, spawn_forwarder, InstallResult,
+    PortForwarder,
 };
 use crate::{resume_tui, suspend_tui, ui};
 use crossterm::event::{KeyCode, KeyModifiers};
-use devc_config::GlobalConfig;
+use devc_config::{GlobalConfig, PortTransport, ShutdownAction, SortOrder};
 use devc_core::{
     agents::{AgentContainerPresence, AgentKind, AgentSyncResult, AgentSyncSelection},
+    features::progress::{FeatureEvent, FeatureProgress},
     BuildStage, Container, ContainerManager, ContainerState, DevcContainerStatus,
 };
 use devc_provider::{
@@ -29,7 +34,7 @@ use ratatui::widgets::TableState;
 use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::hash::{Hash, Hasher};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use thiserror::Error;
@@ -101,6 +106,8 @@ pub enum View {
     DiscoverDetail,
     /// Agent diagnostics/sync result popup
     AgentDiagnostics,
+    /// Read-only view of the resolved devcontainer.json
+    ConfigView,
 }
 
 /// Confirmation action
@@ -132,6 +139,12 @@ pub enum ConfirmAction {
         id: String,
         config_path: PathBuf,
     },
+    /// Remove the image a rebuild left behind
+    RemovePreviousImage {
+        id: String,
+        name: String,
+        image: String,
+    },
     /// Cancel an in-progress build/operation
     CancelBuild,
     /// Quit the application
@@ -161,6 +174,14 @@ pub enum ContainerOperation {
         id: String,
         name: String,
     },
+    Pausing {
+        id: String,
+        name: String,
+    },
+    Unpausing {
+        id: String,
+        name: String,
+    },
     Deleting {
         id: String,
         name: String,
@@ -169,6 +190,9 @@ pub enum ContainerOperation {
         id: String,
         name: String,
         progress: String,
+        /// The container is ready (its `waitFor` phase finished) and the
+        /// remaining lifecycle phases are still running
+        finalizing: bool,
     },
     Adopting {
         id: String,
@@ -178,6 +202,17 @@ pub enum ContainerOperation {
         id: String,
         name: String,
     },
+    RemovingImage {
+        id: String,
+        name: String,
+        image: String,
+    },
+    Syncing {
+        id: String,
+        name: String,
+        /// Status shown before the sync, to report what changed
+        previous: DevcContainerStatus,
+    },
 }
 
 impl ContainerOperation {
@@ -185,9 +220,22 @@ impl ContainerOperation {
         match self {
             ContainerOperation::Starting { name, .. } => format!("Starting {}...", name),
             ContainerOperation::Stopping { name, .. } => format!("Stopping {}...", name),
+            ContainerOperation::Pausing { name, .. } => format!("Pausing {}...", name),
+            ContainerOperation::Unpausing { name, .. } => format!("Unpausing {}...", name),
             ContainerOperation::Deleting { name, .. } => format!("Deleting {}...", name),
-            ContainerOperation::Up { name, progress, .. } => {
-                if progress.is_empty() {
+            ContainerOperation::Up {
+                name,
+                progress,
+                finalizing,
+                ..
+            } => {
+                if *finalizing {
+                    if progress.is_empty() || progress == devc_core::READY_FINALIZING_PROGRESS {
+                        format!("{} ready (finalizing)...", name)
+                    } else {
+                        format!("{} ready (finalizing): {}", name, progress)
+                    }
+                } else if progress.is_empty() {
                     format!("Starting up {}...", name)
                 } else {
                     progress.clone()
@@ -195,6 +243,10 @@ impl ContainerOperation {
             }
             ContainerOperation::Adopting { name, .. } => format!("Adopting {}...", name),
             ContainerOperation::Forgetting { name, .. } => format!("Forgetting {}...", name),
+            ContainerOperation::RemovingImage { name, .. } => {
+                format!("Removing previous image of {}...", name)
+            }
+            ContainerOperation::Syncing { name, .. } => format!("Checking {}...", name),
         }
     }
 }
@@ -218,8 +270,13 @@ pub enum AsyncEvent {
     BuildProgress(String),
     /// Structured build/rebuild stage
     BuildStage(BuildStage),
-    /// Final build/rebuild terminal state
-    BuildFinished { success: bool },
+    /// Feature download status change during a build or up
+    FeatureProgress(FeatureEvent),
+    /// Final build/rebuild terminal state, with the failing build step if known
+    BuildFinished {
+        success: bool,
+        failed_step: Option<String>,
+    },
     /// Port detection update (from manual ports popup)
     PortDetected(PortDetectionUpdate),
     /// Container operation completed (start/stop/delete/up)
@@ -247,6 +304,18 @@ pub enum AsyncEvent {
         selection: AgentSyncSelection,
         result: Result<Vec<AgentSyncResult>, String>,
     },
+    /// Disk usage lookup completed for a container (None if not reported)
+    DiskUsageComplete {
+        container_id: String,
+        usage: Option<devc_provider::ContainerDiskUsage>,
+    },
+    /// `compose ps` completed for a compose container
+    ComposeServicesComplete {
+        container_id: String,
+        result: Result<Vec<devc_provider::ComposeServiceInfo>, String>,
+    },
+    /// Capability probe of the connected providers finished
+    CapabilitiesDetected(Vec<(ProviderType, devc_provider::ProviderCapabilities)>),
 }
 
 /// Provider status information
@@ -265,14 +334,28 @@ pub struct App {
     pub manager: Arc<RwLock<ContainerManager>>,
     /// Global configuration
     pub config: GlobalConfig,
+    /// Current container list ordering (starts at `ui.sort_order`)
+    pub sort_order: SortOrder,
+    /// Show full container/image IDs instead of short ones (`ui.full_ids`)
+    pub full_ids: bool,
+    /// Group the container list by workspace folder (`ui.group_by_workspace`)
+    pub group_by_workspace: bool,
+    /// Show timestamps in the logs view (`ui.log_timestamps`)
+    pub log_timestamps: bool,
     /// Workspace directory for auto-discovery
     pub workspace_dir: Option<std::path::PathBuf>,
     /// Last time auto-discovery was run (for debouncing)
     pub last_discovery: std::time::Instant,
+    /// Last time idle containers were checked for auto-stop
+    pub last_idle_check: std::time::Instant,
+    /// Containers (by state id) that had a shell or forward at the last idle check
+    pub idle_check_active: HashSet<String>,
     /// Current tab
     pub tab: Tab,
     /// Current view within the tab
     pub view: View,
+    /// View to return to when the help overlay is closed
+    pub help_return_view: View,
     /// Active provider type (for new containers), None if disconnected
     pub active_provider: Option<ProviderType>,
     /// Provider statuses
@@ -283,6 +366,8 @@ pub struct App {
     pub connection_error: Option<String>,
     /// List of containers
     pub containers: Vec<ContainerState>,
+    /// IDs of tracked containers whose workspace no longer exists on disk
+    pub missing_workspaces: HashSet<String>,
     /// Currently selected container index
     pub selected: usize,
     /// Build output log
@@ -297,20 +382,41 @@ pub struct App {
     pub current_build_stage: Option<BuildStage>,
     /// Last stage marker written into the build output log
     pub last_stage_marker: Option<BuildStage>,
+    /// Build output line of the step that failed the build, highlighted in the view
+    pub build_failed_line: Option<usize>,
+    /// Feature downloads of the current build, drawn as a table in the build output
+    pub build_features: FeatureProgress,
+    /// Where that table sits in `build_output` (first line, line count)
+    build_features_rows: Option<(usize, usize)>,
+    /// Show each feature's own download messages under its table row
+    pub build_verbose: bool,
     /// Container logs
     pub logs: Vec<String>,
     /// Logs scroll position (line offset from top)
     pub logs_scroll: usize,
     /// Status message
     pub status_message: Option<String>,
+    /// Transient notifications for finished background operations
+    pub toasts: Toasts,
     /// Should quit
     pub should_quit: bool,
     /// Pending confirmation action
     pub confirm_action: Option<ConfirmAction>,
+    /// Containers (by state id) that devc started or brought up this session
+    pub session_started: HashSet<String>,
+    /// Containers (id, name) whose `shutdownAction` runs when quitting
+    pub shutdown_on_quit: Vec<(String, String)>,
     /// Is an operation in progress
     pub loading: bool,
     /// Rebuild no-cache toggle state (for rebuild confirmation dialog)
     pub rebuild_no_cache: bool,
+    /// Rebuild dialog toggle: open a shell in the container once the rebuild finishes
+    pub rebuild_attach: bool,
+    /// Container (state id) to open a shell in when the running rebuild succeeds
+    pub attach_after_rebuild: Option<String>,
+    /// The running build was cancelled: its remaining output and result are
+    /// dropped, and no new build starts until it reports back
+    pub build_cancelled: bool,
     /// Dialog focus state for keyboard navigation
     pub dialog_focus: DialogFocus,
     /// Settings state (for global settings)
@@ -329,8 +435,17 @@ pub struct App {
     pub discover_detail_scroll: usize,
     /// Detailed info for a managed container (from inspect)
     pub container_detail: Option<devc_provider::ContainerDetails>,
+    /// Disk usage for the managed container in the detail view
+    pub container_disk_usage: Option<devc_provider::ContainerDiskUsage>,
+    /// `customizations.vscode.extensions` of the container in the detail view,
+    /// read when the view opens rather than on every frame
+    pub vscode_extensions: Vec<String>,
     /// Scroll position for container detail view
     pub container_detail_scroll: usize,
+    /// Pretty-printed resolved devcontainer.json (config viewer)
+    pub config_view_lines: Vec<String>,
+    /// Scroll position for the config viewer
+    pub config_view_scroll: usize,
     /// Table state for containers view (tracks selection and scroll)
     pub containers_table_state: TableState,
     /// Table state for discovered containers view
@@ -345,6 +460,9 @@ pub struct App {
 
     // Shell session state
     pub shell_state: ShellState,
+    /// Container whose devcontainer.json should be opened in `$EDITOR`;
+    /// the run loop suspends the TUI for it on its next pass
+    pub pending_config_edit: Option<String>,
 
     // Container operation spinner state
     /// Current container operation in progress (shown as spinner modal)
@@ -368,6 +486,8 @@ pub struct App {
 
     // Compose service visibility state
     pub compose_state: ComposeViewState,
+    /// Probed provider capabilities; a provider missing here is assumed capable
+    pub provider_capabilities: HashMap<ProviderType, devc_provider::ProviderCapabilities>,
 
     // Unified async event channel
     /// Sender for background tasks to communicate with the main loop
@@ -390,11 +510,18 @@ impl App {
 
         Self {
             manager: Arc::new(RwLock::new(manager)),
+            sort_order: config.ui.sort_order,
+            full_ids: config.ui.full_ids,
+            group_by_workspace: config.ui.group_by_workspace,
+            log_timestamps: config.ui.log_timestamps,
             config,
             workspace_dir: None,
             last_discovery: std::time::Instant::now(),
+            last_idle_check: std::time::Instant::now(),
+            idle_check_active: HashSet::new(),
             tab: Tab::Containers,
             view: View::Main,
+            help_return_view: View::Main,
             active_provider: Some(ProviderType::Docker),
             providers: vec![
                 ProviderStatus {
@@ -415,6 +542,7 @@ impl App {
             selected_provider: 0,
             connection_error: None,
             containers: Vec::new(),
+            missing_workspaces: HashSet::new(),
             selected: 0,
             build_output: Vec::new(),
             build_output_scroll: 0,
@@ -422,13 +550,23 @@ impl App {
             build_complete: false,
             current_build_stage: None,
             last_stage_marker: None,
+            build_failed_line: None,
+            build_features: FeatureProgress::default(),
+            build_features_rows: None,
+            build_verbose: false,
             logs: Vec::new(),
             logs_scroll: 0,
             status_message: None,
+            toasts: Toasts::default(),
             should_quit: false,
             confirm_action: None,
+            session_started: HashSet::new(),
+            shutdown_on_quit: Vec::new(),
             loading: false,
             rebuild_no_cache: false,
+            rebuild_attach: false,
+            attach_after_rebuild: None,
+            build_cancelled: false,
             dialog_focus: DialogFocus::default(),
             settings_state: SettingsState::new(&GlobalConfig::default()),
             provider_detail_state: ProviderDetailState::new(),
@@ -438,7 +576,11 @@ impl App {
             discover_detail: None,
             discover_detail_scroll: 0,
             container_detail: None,
+            container_disk_usage: None,
+            vscode_extensions: Vec::new(),
             container_detail_scroll: 0,
+            config_view_lines: Vec::new(),
+            config_view_scroll: 0,
             containers_table_state: TableState::default().with_selected(0),
             discovered_table_state: TableState::default().with_selected(0),
             providers_table_state: TableState::default().with_selected(0),
@@ -446,6 +588,7 @@ impl App {
             port_state: PortForwardingState::new(),
             spinner_frame: 0,
             shell_state: ShellState::new(),
+            pending_config_edit: None,
             container_op: None,
             up_output: Vec::new(),
             up_output_expanded: false,
@@ -456,6 +599,7 @@ impl App {
             agent_diagnostics_selected: 0,
             agent_diagnostics_table_state: TableState::default().with_selected(0),
             compose_state: ComposeViewState::new(),
+            provider_capabilities: HashMap::new(),
             async_event_tx,
             async_event_rx,
         }
@@ -537,11 +681,24 @@ impl App {
     }
 
     /// Create a new application
+    ///
+    /// `provider_override` is the provider requested with `--provider`; when it
+    /// could not connect, the Providers tab preselects it so a retry targets it.
     pub async fn new(
-        manager: ContainerManager,
+        mut manager: ContainerManager,
         workspace_dir: Option<&std::path::Path>,
+        provider_override: Option<ProviderType>,
     ) -> AppResult<Self> {
         let (async_event_tx, async_event_rx) = mpsc::unbounded_channel();
+        // Feature download progress arrives on its own channel; hand it to the event loop
+        let (feature_tx, mut feature_rx) = mpsc::unbounded_channel();
+        let feature_event_tx = async_event_tx.clone();
+        tokio::spawn(async move {
+            while let Some(event) = feature_rx.recv().await {
+                let _ = feature_event_tx.send(AsyncEvent::FeatureProgress(event));
+            }
+        });
+        manager.set_feature_events(Some(feature_tx));
         let mut containers = manager.list().await?;
 
         // Append ephemeral Available entries for unregistered configs
@@ -552,8 +709,17 @@ impl App {
             }
         }
         let config = GlobalConfig::load().unwrap_or_default();
+        sort_containers(
+            &mut containers,
+            config.ui.sort_order,
+            config.ui.group_by_workspace,
+        );
         let active_provider = manager.provider_type();
         let connection_error = manager.connection_error().map(|s| s.to_string());
+        // Point out a session-only override so it isn't mistaken for the saved default
+        let status_message = provider_override
+            .filter(|pt| active_provider == Some(*pt) && config.defaults.provider != pt.to_string())
+            .map(|pt| format!("Using {} for this session (--provider)", pt));
         let mut settings_state = SettingsState::new(&config);
         let availability = devc_core::agents::host_agent_availability(&config);
         settings_state.apply_agent_host_availability(&availability, &config);
@@ -592,19 +758,29 @@ impl App {
 
         Ok(Self {
             manager: Arc::new(RwLock::new(manager)),
+            sort_order: config.ui.sort_order,
+            full_ids: config.ui.full_ids,
+            group_by_workspace: config.ui.group_by_workspace,
+            log_timestamps: config.ui.log_timestamps,
             config,
             workspace_dir: workspace_dir.map(|p| p.to_path_buf()),
             last_discovery: std::time::Instant::now(),
+            last_idle_check: std::time::Instant::now(),
+            idle_check_active: HashSet::new(),
             tab: Tab::Containers,
             view: View::Main,
+            help_return_view: View::Main,
             active_provider,
             providers,
-            selected_provider: if active_provider == Some(ProviderType::Podman) {
+            selected_provider: if active_provider.or(provider_override)
+                == Some(ProviderType::Podman)
+            {
                 1
             } else {
                 0
             },
             connection_error,
+            missing_workspaces: missing_workspaces(&containers),
             containers,
             selected: 0,
             build_output: Vec::new(),
@@ -613,13 +789,23 @@ impl App {
             build_complete: false,
             current_build_stage: None,
             last_stage_marker: None,
+            build_failed_line: None,
+            build_features: FeatureProgress::default(),
+            build_features_rows: None,
+            build_verbose: false,
             logs: Vec::new(),
             logs_scroll: 0,
-            status_message: None,
+            status_message,
+            toasts: Toasts::default(),
             should_quit: false,
             confirm_action: None,
+            session_started: HashSet::new(),
+            shutdown_on_quit: Vec::new(),
             loading: false,
             rebuild_no_cache: false,
+            rebuild_attach: false,
+            attach_after_rebuild: None,
+            build_cancelled: false,
             dialog_focus: DialogFocus::default(),
             settings_state,
             provider_detail_state: ProviderDetailState::new(),
@@ -629,7 +815,11 @@ impl App {
             discover_detail: None,
             discover_detail_scroll: 0,
             container_detail: None,
+            container_disk_usage: None,
+            vscode_extensions: Vec::new(),
             container_detail_scroll: 0,
+            config_view_lines: Vec::new(),
+            config_view_scroll: 0,
             containers_table_state: TableState::default().with_selected(0),
             discovered_table_state: TableState::default().with_selected(0),
             providers_table_state: TableState::default().with_selected(0),
@@ -637,6 +827,7 @@ impl App {
             port_state: PortForwardingState::new(),
             spinner_frame: 0,
             shell_state: ShellState::new(),
+            pending_config_edit: None,
             container_op: None,
             up_output: Vec::new(),
             up_output_expanded: false,
@@ -647,6 +838,7 @@ impl App {
             agent_diagnostics_selected: 0,
             agent_diagnostics_table_state: TableState::default().with_selected(0),
             compose_state: ComposeViewState::new(),
+            provider_capabilities: HashMap::new(),
             async_event_tx,
             async_event_rx,
         })
@@ -657,21 +849,64 @@ impl App {
         self.active_provider.is_some()
     }
 
+    /// Confirm removing the image the last rebuild replaced, unless a tracked
+    /// container still uses it
+    fn start_remove_previous_image_dialog(&mut self) {
+        if self.containers.is_empty() || self.container_op.is_some() {
+            return;
+        }
+        if !self.is_connected() {
+            self.status_message = Some("Not connected to provider".to_string());
+            return;
+        }
+        let container = &self.containers[self.selected];
+        let Some(image) = container.previous_image_id() else {
+            self.status_message = Some(format!("{} has no previous image", container.name));
+            return;
+        };
+        let users: Vec<&str> = self
+            .containers
+            .iter()
+            .filter(|c| c.image_id.as_deref() == Some(image))
+            .map(|c| c.name.as_str())
+            .collect();
+        if !users.is_empty() {
+            self.status_message = Some(format!(
+                "Cannot remove image {}: still used by {}",
+                crate::ui::format_id(image, false),
+                users.join(", ")
+            ));
+            return;
+        }
+        self.confirm_action = Some(ConfirmAction::RemovePreviousImage {
+            id: container.id.clone(),
+            name: container.name.clone(),
+            image: image.to_string(),
+        });
+        self.dialog_focus = DialogFocus::Cancel;
+        self.view = View::Confirm;
+    }
+
     /// Open the rebuild confirmation dialog for the selected container.
     /// Returns early if not connected or no containers.
-    fn start_rebuild_dialog(&mut self) {
+    async fn start_rebuild_dialog(&mut self) {
         if self.containers.is_empty() || !self.is_connected() {
             if !self.is_connected() {
                 self.status_message = Some("Not connected to provider".to_string());
             }
             return;
         }
+        if self.cancelled_build_pending() {
+            return;
+        }
         let container = &self.containers[self.selected];
         if container.status.is_available() {
             self.status_message = Some("Use 'b' to build or 'u' to build and start".to_string());
             return;
         }
-        if let Some(new_provider) = self.active_provider {
+        // A pinned container rebuilds on its own provider, not the active one
+        let new_provider = self.manager.read().await.rebuild_provider_for(container);
+        if let Some(new_provider) = new_provider {
             let old_provider = container.provider;
             let provider_change = if old_provider != new_provider {
                 Some((old_provider, new_provider))
@@ -680,6 +915,7 @@ impl App {
             };
 
             self.rebuild_no_cache = false;
+            self.rebuild_attach = false;
             self.dialog_focus = DialogFocus::Cancel;
             self.confirm_action = Some(ConfirmAction::Rebuild {
                 id: container.id.clone(),
@@ -698,6 +934,9 @@ impl App {
             return;
         }
 
+        if self.cancelled_build_pending() {
+            return;
+        }
         let container = &self.containers[self.selected];
         if !container.status.is_available() {
             self.status_message = Some("Already registered — use 'u' or 'R'".to_string());
@@ -712,6 +951,19 @@ impl App {
         self.view = View::Confirm;
     }
 
+    /// Whether a cancelled build is still running in the background. Its
+    /// events carry nothing to tell them apart, so a new build waits for it
+    /// to finish rather than having its output and result mixed in.
+    fn cancelled_build_pending(&mut self) -> bool {
+        if self.build_cancelled {
+            self.status_message = Some(
+                "The cancelled build is still running in the background; try again once it ends"
+                    .to_string(),
+            );
+        }
+        self.build_cancelled
+    }
+
     /// Create a CliProvider for the given provider type.
     /// Handles toolbox environment detection for Podman.
     async fn create_cli_provider(
@@ -736,6 +988,7 @@ impl App {
         terminal: &mut Terminal<B>,
     ) -> AppResult<()> {
         let mut events = Some(EventHandler::new(Duration::from_millis(250)));
+        self.probe_provider_capabilities();
 
         while !self.should_quit {
             // Handle shell mode specially - run shell session and return to TUI
@@ -752,6 +1005,11 @@ impl App {
                 }
             }
 
+            if let Some(id) = self.pending_config_edit.take() {
+                self.run_config_editor(terminal, &mut events, &id).await?;
+                continue;
+            }
+
             // Draw UI
             terminal.draw(|frame| ui::draw(frame, self))?;
 
@@ -789,23 +1047,49 @@ impl App {
     /// Handle a unified async event from background tasks
     pub async fn handle_async_event(&mut self, event: AsyncEvent) -> AppResult<()> {
         match event {
+            AsyncEvent::BuildProgress(_)
+            | AsyncEvent::BuildStage(_)
+            | AsyncEvent::FeatureProgress(_)
+                if self.build_cancelled => {}
+            AsyncEvent::BuildFinished { .. } if self.build_cancelled => {
+                self.build_cancelled = false;
+                self.status_message = Some("Cancelled build ended".to_string());
+                self.refresh_containers().await?;
+            }
             AsyncEvent::BuildProgress(line) => {
                 self.handle_build_progress(line).await?;
             }
             AsyncEvent::BuildStage(stage) => {
                 self.handle_build_stage(stage);
             }
-            AsyncEvent::BuildFinished { success } => {
-                self.handle_build_finished(success).await?;
+            AsyncEvent::FeatureProgress(event) => {
+                self.handle_feature_progress(event);
+            }
+            AsyncEvent::BuildFinished {
+                success,
+                failed_step,
+            } => {
+                self.handle_build_finished(success, failed_step).await?;
             }
             AsyncEvent::PortDetected(update) => {
+                if update.socat_missing {
+                    self.status_message = Some(SOCAT_MISSING_FOR_PROBE.to_string());
+                }
                 self.port_state.handle_port_update(update);
             }
             AsyncEvent::OperationComplete(result) => {
                 self.handle_operation_result(result).await?;
             }
             AsyncEvent::OperationProgress(msg) => {
-                if let Some(ContainerOperation::Up { progress, .. }) = self.container_op.as_mut() {
+                if let Some(ContainerOperation::Up {
+                    progress,
+                    finalizing,
+                    ..
+                }) = self.container_op.as_mut()
+                {
+                    if msg == devc_core::READY_FINALIZING_PROGRESS {
+                        *finalizing = true;
+                    }
                     *progress = msg.clone();
                     if self.up_output_expanded {
                         self.build_output.push(msg);
@@ -846,6 +1130,27 @@ impl App {
                 self.handle_agent_sync_complete(container_id, container_name, selection, result)
                     .await;
             }
+            AsyncEvent::DiskUsageComplete {
+                container_id,
+                usage,
+            } => {
+                if self.view == View::ContainerDetail
+                    && self.selected_container().map(|c| c.id.as_str())
+                        == Some(container_id.as_str())
+                {
+                    self.container_disk_usage = usage;
+                }
+            }
+            AsyncEvent::ComposeServicesComplete {
+                container_id,
+                result,
+            } => {
+                self.handle_compose_services_complete(container_id, result)
+                    .await?;
+            }
+            AsyncEvent::CapabilitiesDetected(capabilities) => {
+                self.provider_capabilities.extend(capabilities);
+            }
         }
         Ok(())
     }
@@ -1049,8 +1354,10 @@ impl App {
                 .port_state
                 .active_forwarders
                 .keys()
-                .filter(|(cid, _)| cid == &provider_cid)
-                .map(|(_, port)| *port)
+                .filter(|(cid, _, transport)| {
+                    cid == &provider_cid && *transport == PortTransport::Tcp
+                })
+                .map(|(_, port, _)| *port)
                 .collect();
 
             let rx = spawn_port_detector(
@@ -1062,6 +1369,38 @@ impl App {
             self.port_state
                 .auto_port_detectors
                 .insert(provider_cid.clone(), rx);
+            // UDP listeners never show up in /proc/net/tcp, so configured UDP
+            // ports are forwarded up front rather than on detection
+            for pfc in auto_fwd.iter().filter(|pfc| {
+                pfc.transport == PortTransport::Udp
+                    && pfc.action != devc_config::AutoForwardAction::Ignore
+            }) {
+                let key = (provider_cid.clone(), pfc.port, PortTransport::Udp);
+                if self.port_state.active_forwarders.contains_key(&key) {
+                    continue;
+                }
+                match spawn_forwarder(
+                    rt_args.0.clone(),
+                    rt_args.1.clone(),
+                    provider_cid.clone(),
+                    pfc.bind_address,
+                    pfc.port,
+                    pfc.port,
+                    PortTransport::Udp,
+                )
+                .await
+                {
+                    Ok(forwarder) => {
+                        if let Some(warning) = forwarder.exposure_warning() {
+                            self.status_message = Some(warning);
+                        }
+                        self.port_state.active_forwarders.insert(key, forwarder);
+                    }
+                    Err(e) => {
+                        tracing::debug!("UDP auto-forward of port {} failed: {}", pfc.port, e);
+                    }
+                }
+            }
             self.port_state
                 .auto_forward_configs
                 .insert(provider_cid.clone(), auto_fwd);
@@ -1092,6 +1431,9 @@ impl App {
             };
 
             while let Ok(update) = rx.try_recv() {
+                if update.socat_missing {
+                    self.status_message = Some(SOCAT_MISSING_FOR_PROBE.to_string());
+                }
                 let is_auto_all = self.port_state.auto_forward_all_containers.contains(&cid);
                 let config = self
                     .port_state
@@ -1101,7 +1443,9 @@ impl App {
                     .unwrap_or_default();
 
                 for detected in &update.ports {
-                    let matching_config = config.iter().find(|pfc| pfc.port == detected.port);
+                    let matching_config = config.iter().find(|pfc| {
+                        pfc.port == detected.port && pfc.transport == PortTransport::Tcp
+                    });
 
                     let should_forward = if let Some(pfc) = matching_config {
                         pfc.action != devc_config::AutoForwardAction::Ignore
@@ -1117,7 +1461,12 @@ impl App {
                     if self.port_state.auto_forwarded_ports.contains(&key) {
                         continue;
                     }
-                    if self.port_state.active_forwarders.contains_key(&key) {
+                    let forwarder_key = (cid.clone(), detected.port, PortTransport::Tcp);
+                    if self
+                        .port_state
+                        .active_forwarders
+                        .contains_key(&forwarder_key)
+                    {
                         self.port_state.auto_forwarded_ports.insert(key);
                         continue;
                     }
@@ -1133,15 +1482,20 @@ impl App {
                         rt_prog,
                         rt_prefix,
                         cid.clone(),
+                        matching_config.map_or(
+                            devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS,
+                            |pfc| pfc.bind_address,
+                        ),
                         detected.port,
                         detected.port,
+                        PortTransport::Tcp,
                     )
                     .await
                     {
                         Ok(forwarder) => {
                             self.port_state
                                 .active_forwarders
-                                .insert(key.clone(), forwarder);
+                                .insert(forwarder_key.clone(), forwarder);
                             self.port_state.auto_forwarded_ports.insert(key.clone());
                             // Notify is the default for ports with no explicit action — the
                             // user sees a status line and gets the port forwarded, no browser.
@@ -1167,12 +1521,22 @@ impl App {
                                 };
                                 self.status_message = Some(msg);
                             }
+                            if let Some(warning) = self
+                                .port_state
+                                .active_forwarders
+                                .get(&forwarder_key)
+                                .and_then(PortForwarder::exposure_warning)
+                            {
+                                self.status_message = Some(warning);
+                            }
                             let decision = crate::port_state::browser_open_decision(
                                 configured_action,
                                 global_auto_open,
                                 self.port_state.auto_opened_ports.contains(&key),
                             );
-                            let protocol = matching_config.and_then(|pfc| pfc.protocol.as_deref());
+                            let protocol = matching_config
+                                .and_then(|pfc| pfc.protocol.as_deref())
+                                .or(detected.scheme.url_scheme());
                             match decision {
                                 crate::port_state::BrowserOpenDecision::Skip => {}
                                 crate::port_state::BrowserOpenDecision::OpenEach => {
@@ -1193,81 +1557,137 @@ impl App {
         }
     }
 
-    /// Fetch compose services for the currently selected container
-    async fn fetch_compose_services(&mut self) {
-        let container = match self.selected_container() {
-            Some(c) => c.clone(),
-            None => return,
-        };
+    /// Probe what the connected providers support, off the UI thread
+    fn probe_provider_capabilities(&self) {
+        let manager = Arc::clone(&self.manager);
+        let tx = self.async_event_tx.clone();
+        tokio::spawn(async move {
+            let capabilities = manager.read().await.provider_capabilities().await;
+            let _ = tx.send(AsyncEvent::CapabilitiesDetected(capabilities));
+        });
+    }
 
-        // Only fetch for compose containers
-        if container.compose_project.is_none() {
-            return;
+    /// Why compose actions are unavailable for this container's provider, if they are
+    pub fn compose_unavailable_reason(&self, container: &ContainerState) -> Option<String> {
+        let supported = self
+            .provider_capabilities
+            .get(&container.provider)
+            .is_none_or(|c| c.compose);
+        (container.compose_project.is_some() && !supported).then(|| {
+            format!(
+                "Compose is not available for {}; install it to manage this project",
+                container.provider
+            )
+        })
+    }
+
+    /// Fetch compose services for the currently selected container unless cached
+    fn fetch_compose_services(&mut self) {
+        let cached = self
+            .selected_container()
+            .is_some_and(|c| self.compose_state.services.contains_key(&c.id));
+        if !cached {
+            self.refresh_compose_services();
         }
+    }
 
-        // Already cached
-        if self.compose_state.services.contains_key(&container.id) {
+    /// Re-run `compose ps` for the selected container in the background.
+    ///
+    /// Cached rows stay on screen until [`AsyncEvent::ComposeServicesComplete`]
+    /// replaces them.
+    fn refresh_compose_services(&mut self) {
+        let Some(container) = self.selected_container() else {
+            return;
+        };
+        if container.compose_project.is_none()
+            || self.compose_state.services_loading
+            || self.compose_unavailable_reason(container).is_some()
+        {
             return;
         }
+        let container_id = container.id.clone();
 
         self.compose_state.services_loading = true;
+        self.compose_state.services_refreshed_at = Some(std::time::Instant::now());
+        let manager = Arc::clone(&self.manager);
+        let tx = self.async_event_tx.clone();
+        tokio::spawn(async move {
+            let result = manager
+                .read()
+                .await
+                .compose_services(&container_id)
+                .await
+                .map_err(|e| e.to_string());
+            let _ = tx.send(AsyncEvent::ComposeServicesComplete {
+                container_id,
+                result,
+            });
+        });
+    }
 
-        // Load compose file paths from the devcontainer config
-        let compose_info = {
-            let core_container = match Container::from_config(&container.config_path) {
-                Ok(c) => c,
-                Err(_) => {
-                    self.compose_state.services_loading = false;
-                    return;
-                }
-            };
-            let files = match core_container.compose_files() {
-                Some(f) => f,
-                None => {
-                    self.compose_state.services_loading = false;
-                    return;
-                }
-            };
-            let project_name = core_container.compose_project_name();
-            let workspace_path = core_container.workspace_path.clone();
-            (files, project_name, workspace_path)
-        };
+    /// Re-poll compose services every [`COMPOSE_REFRESH_INTERVAL`] while the
+    /// services table is on screen
+    fn auto_refresh_compose_services(&mut self) {
+        let has_services = self
+            .selected_container()
+            .and_then(|c| self.compose_state.services.get(&c.id))
+            .is_some_and(|s| !s.is_empty());
+        let due = self
+            .compose_state
+            .services_refreshed_at
+            .is_none_or(|at| at.elapsed() >= COMPOSE_REFRESH_INTERVAL);
+        if has_services && due {
+            self.refresh_compose_services();
+        }
+    }
 
-        let (files, project_name, workspace_path) = compose_info;
+    async fn handle_compose_services_complete(
+        &mut self,
+        container_id: String,
+        result: Result<Vec<devc_provider::ComposeServiceInfo>, String>,
+    ) -> AppResult<()> {
+        let selected = self
+            .selected_container()
+            .filter(|c| c.id == container_id)
+            .cloned();
+        if selected.is_some() {
+            self.compose_state.services_loading = false;
+        }
 
-        // Create a provider for the compose_ps call (use the container's own provider)
-        let provider = match Self::create_cli_provider(container.provider).await {
-            Ok(p) => p,
-            Err(_) => {
-                self.compose_state.services_loading = false;
-                return;
+        let services = match result {
+            Ok(services) => services,
+            Err(e) => {
+                tracing::debug!("compose ps failed for {}: {}", container_id, e);
+                // Keep the last good list; an empty entry stops open/refresh retry loops
+                self.compose_state.services.entry(container_id).or_default();
+                return Ok(());
             }
         };
 
-        let file_strs: Vec<String> = files
-            .iter()
-            .map(|f| f.to_string_lossy().to_string())
-            .collect();
-        let file_refs: Vec<&str> = file_strs.iter().map(|s| s.as_str()).collect();
+        // The manager records a recreated primary container's new ID; pick it up
+        let primary_moved = selected.as_ref().is_some_and(|c| {
+            services.iter().any(|s| {
+                c.compose_service.as_deref() == Some(s.service_name.as_str())
+                    && !s.container_id.0.is_empty()
+                    && c.container_id.as_deref() != Some(s.container_id.0.as_str())
+            })
+        });
 
-        match provider
-            .compose_ps(&file_refs, &project_name, &workspace_path)
-            .await
-        {
-            Ok(services) => {
-                self.compose_state
-                    .services
-                    .insert(container.id.clone(), services);
-            }
-            Err(_) => {
-                // Store empty vec so we don't retry
-                self.compose_state
-                    .services
-                    .insert(container.id.clone(), Vec::new());
-            }
+        if selected.is_some() && self.compose_state.selected_service >= services.len() {
+            self.compose_state.selected_service = services.len().saturating_sub(1);
+            self.compose_state
+                .services_table_state
+                .select(Some(self.compose_state.selected_service));
         }
+        self.compose_state.services.insert(container_id, services);
 
-        self.compose_state.services_loading = false;
+        if primary_moved {
+            self.refresh_containers().await?;
+            if self.view == View::ContainerDetail {
+                self.fetch_container_detail().await;
+            }
+        }
+        Ok(())
     }
 
     /// Fetch inspect details for the currently selected managed container
@@ -1298,6 +1718,23 @@ impl App {
                 // Non-fatal — detail view still shows ContainerState info
             }
         }
+
+        // `system df -v` walks every layer and volume, so don't block the view on it
+        let manager = Arc::clone(&self.manager);
+        let tx = self.async_event_tx.clone();
+        tokio::spawn(async move {
+            let usage = manager
+                .read()
+                .await
+                .disk_usage()
+                .await
+                .ok()
+                .and_then(|mut usage| usage.remove(&container.id));
+            let _ = tx.send(AsyncEvent::DiskUsageComplete {
+                container_id: container.id,
+                usage,
+            });
+        });
     }
 
     /// Handle a single build progress message
@@ -1307,7 +1744,53 @@ impl App {
         Ok(())
     }
 
-    async fn handle_build_finished(&mut self, success: bool) -> AppResult<()> {
+    /// Fold a feature download event into the feature table in the build output
+    fn handle_feature_progress(&mut self, event: FeatureEvent) {
+        // A table that is no longer in the output (it was cleared for a new
+        // build) is stale; start a new one at the end
+        if !self.feature_table_in_place() {
+            self.build_features = FeatureProgress::default();
+            self.build_features_rows = Some((self.build_output.len(), 0));
+        }
+        self.build_features.apply(event);
+        self.redraw_feature_table();
+
+        if let Some(ContainerOperation::Up { progress, .. }) = self.container_op.as_mut() {
+            *progress = self.build_features.summary();
+        }
+    }
+
+    /// Whether the feature table is still where it was last drawn
+    fn feature_table_in_place(&self) -> bool {
+        self.build_features_rows.is_some_and(|(start, len)| {
+            self.build_output.get(start..start + len)
+                == Some(&self.build_features.table(self.build_verbose)[..])
+        })
+    }
+
+    /// Rewrite the feature table's lines in the build output
+    fn redraw_feature_table(&mut self) {
+        let Some((start, len)) = self.build_features_rows else {
+            return;
+        };
+        let rows = self.build_features.table(self.build_verbose);
+        let new_len = rows.len();
+        self.build_output.splice(start..start + len, rows);
+        self.build_features_rows = Some((start, new_len));
+        if let Some(line) = self
+            .build_failed_line
+            .as_mut()
+            .filter(|line| **line >= start + len)
+        {
+            *line = *line + new_len - len;
+        }
+    }
+
+    async fn handle_build_finished(
+        &mut self,
+        success: bool,
+        failed_step: Option<String>,
+    ) -> AppResult<()> {
         if success {
             if self.current_build_stage != Some(BuildStage::Completed) {
                 self.handle_build_stage(BuildStage::Completed);
@@ -1316,9 +1799,45 @@ impl App {
             self.handle_build_stage(BuildStage::Failed);
         }
 
+        // Jump to where the failing step started so its output is on screen
+        if let Some(step) = failed_step {
+            self.build_failed_line = self
+                .build_output
+                .iter()
+                .position(|line| devc_provider::build_step_marker(line).as_deref() == Some(&step));
+            if let Some(line) = self.build_failed_line {
+                self.build_output_scroll = line;
+                self.build_auto_scroll = false;
+            }
+        }
+
         self.loading = false;
         self.build_complete = true;
         self.refresh_containers().await?;
+
+        if let Some(id) = self.attach_after_rebuild.take() {
+            if success {
+                self.attach_rebuilt_container(&id).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Leave the build view for a shell in a freshly rebuilt container
+    ///
+    /// Only switches to `View::Shell`; the main loop suspends the TUI before
+    /// the shell takes over the terminal.
+    #[cfg(unix)]
+    async fn attach_rebuilt_container(&mut self, id: &str) -> AppResult<()> {
+        let Some(container) = self.containers.iter().find(|c| c.id == id).cloned() else {
+            return Ok(());
+        };
+        self.view = View::Main;
+        self.enter_shell_mode(&container).await
+    }
+
+    #[cfg(not(unix))]
+    async fn attach_rebuilt_container(&mut self, _id: &str) -> AppResult<()> {
         Ok(())
     }
 
@@ -1363,10 +1882,12 @@ impl App {
                 self.handle_key(key.code, key.modifiers).await?;
             }
             Event::Tick => {
+                self.toasts.expire(std::time::Instant::now());
                 // Advance spinner frame when installing or operating
                 if self.port_state.socat_installing
                     || self.container_op.is_some()
                     || (self.view == View::BuildOutput && !self.build_complete)
+                    || self.compose_state.services_loading
                 {
                     self.spinner_frame = (self.spinner_frame + 1) % 10;
                 }
@@ -1374,9 +1895,13 @@ impl App {
                 if self.tab == Tab::Containers && self.view == View::Main && !self.loading {
                     self.refresh_containers().await?;
                 }
+                if self.view == View::ContainerDetail {
+                    self.auto_refresh_compose_services();
+                }
                 // Auto port forwarding: ensure detectors are running and poll for updates
                 self.ensure_auto_port_detection().await;
                 self.poll_auto_port_detectors().await;
+                self.stop_idle_containers().await;
             }
             Event::Resize(_, _) => {
                 // Terminal will redraw automatically
@@ -1412,6 +1937,7 @@ impl App {
             self.build_complete = false;
             self.current_build_stage = None;
             self.last_stage_marker = None;
+            self.build_failed_line = None;
             self.up_output_expanded = true;
             self.view = View::BuildOutput;
             return Ok(());
@@ -1429,8 +1955,7 @@ impl App {
 
         // Ctrl+C shows quit confirmation dialog
         if code == KeyCode::Char('c') && modifiers.contains(KeyModifiers::CONTROL) {
-            self.confirm_action = Some(ConfirmAction::QuitApp);
-            self.view = View::Confirm;
+            self.request_quit(true).await;
             return Ok(());
         }
 
@@ -1512,6 +2037,7 @@ impl App {
                             // Cancel
                             self.confirm_action = None;
                             self.rebuild_no_cache = false;
+                            self.rebuild_attach = false;
                             self.dialog_focus = DialogFocus::default();
                             self.view = View::Main;
                         }
@@ -1535,6 +2061,7 @@ impl App {
                             DialogFocus::Cancel => {
                                 self.confirm_action = None;
                                 self.rebuild_no_cache = false;
+                                self.rebuild_attach = false;
                                 self.dialog_focus = DialogFocus::default();
                                 self.view = View::Main;
                             }
@@ -1551,9 +2078,16 @@ impl App {
                         self.view = View::Main;
                     }
                 }
+                // `a` toggles opening a shell once a rebuild finishes
+                KeyCode::Char('a')
+                    if matches!(self.confirm_action, Some(ConfirmAction::Rebuild { .. })) =>
+                {
+                    self.rebuild_attach = !self.rebuild_attach;
+                }
                 KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
                     self.confirm_action = None;
                     self.rebuild_no_cache = false;
+                    self.rebuild_attach = false;
                     self.dialog_focus = DialogFocus::default();
                     self.view = View::Main;
                 }
@@ -1564,33 +2098,45 @@ impl App {
 
         // Handle Help view
         if self.view == View::Help {
-            // Any key returns to main view
-            self.view = View::Main;
+            // Any key returns to the view help was opened from
+            self.view = self.help_return_view;
             return Ok(());
         }
 
+        let context = self.key_context();
+
         // Check discover mode FIRST - Esc/q should exit discover mode, not quit app
-        if self.view == View::Main
-            && self.tab == Tab::Containers
-            && self.discover_mode
-            && (code == KeyCode::Esc || code == KeyCode::Char('q'))
+        if context == KeyContext::Discover
+            && keymap::action_for(context, code, modifiers) == Some(Action::ExitDiscover)
         {
             self.discover_mode = false;
             self.status_message = Some("Showing managed containers".to_string());
             return Ok(());
         }
 
+        // Editing a provider socket path: Esc cancels the edit rather than closing
+        if self.view == View::ProviderDetail
+            && self.provider_detail_state.editing
+            && code == KeyCode::Esc
+        {
+            self.provider_detail_state.cancel_edit();
+            return Ok(());
+        }
+
         // View-specific exit handling (runs BEFORE global q/Esc)
-        match (&self.view, code) {
-            (View::Ports, KeyCode::Char('q') | KeyCode::Esc) => {
+        match (&self.view, keymap::action_for(context, code, modifiers)) {
+            (View::Ports, Some(Action::Close)) => {
                 self.exit_ports_view();
                 return Ok(());
             }
-            (View::ProviderDetail, KeyCode::Esc) if self.provider_detail_state.editing => {
-                self.provider_detail_state.cancel_edit();
+            (View::ConfigView, Some(Action::Close)) => {
+                // Return to the detail popup the viewer was opened from
+                self.config_view_lines.clear();
+                self.config_view_scroll = 0;
+                self.view = View::ContainerDetail;
                 return Ok(());
             }
-            (View::BuildOutput, KeyCode::Char('q') | KeyCode::Esc)
+            (View::BuildOutput, Some(Action::Close))
                 if self.build_complete || self.up_output_expanded =>
             {
                 self.build_output.clear();
@@ -1599,6 +2145,7 @@ impl App {
                 self.build_auto_scroll = true;
                 self.current_build_stage = None;
                 self.last_stage_marker = None;
+                self.build_failed_line = None;
                 self.up_output_expanded = false;
                 self.view = View::Main;
                 return Ok(());
@@ -1607,29 +2154,34 @@ impl App {
         }
 
         // Global keys (work in any view)
-        match code {
-            KeyCode::Char('q') => {
-                // Don't close BuildOutput view during active build (unless expanded from Up)
+        let can_switch_tab = self.view == View::Main || self.is_popup_view();
+        match keymap::action_for(KeyContext::Global, code, modifiers) {
+            Some(Action::Quit) => {
+                // An active build (unless expanded from Up) asks before cancelling
                 if self.view == View::BuildOutput
                     && !self.build_complete
                     && !self.up_output_expanded
                 {
+                    self.confirm_action = Some(ConfirmAction::CancelBuild);
+                    self.view = View::Confirm;
                     return Ok(());
                 }
                 if self.view != View::Main {
                     self.cleanup_view_state();
                     self.view = View::Main;
                 } else {
-                    self.should_quit = true;
+                    self.request_quit(false).await;
                 }
                 return Ok(());
             }
-            KeyCode::Esc => {
-                // Don't close BuildOutput view during active build (unless expanded from Up)
+            Some(Action::Back) => {
+                // An active build (unless expanded from Up) asks before cancelling
                 if self.view == View::BuildOutput
                     && !self.build_complete
                     && !self.up_output_expanded
                 {
+                    self.confirm_action = Some(ConfirmAction::CancelBuild);
+                    self.view = View::Confirm;
                     return Ok(());
                 }
                 if self.view != View::Main {
@@ -1638,28 +2190,29 @@ impl App {
                 }
                 return Ok(());
             }
-            KeyCode::Char('?') | KeyCode::F(1) => {
+            Some(Action::Help) => {
+                self.help_return_view = self.view;
                 self.view = View::Help;
                 return Ok(());
             }
             // Tab switching with number keys (available in Main view and popup views)
-            KeyCode::Char('1') if self.view == View::Main || self.is_popup_view() => {
+            Some(Action::ContainersTab) if can_switch_tab => {
                 self.close_current_view();
                 self.tab = Tab::Containers;
                 return Ok(());
             }
-            KeyCode::Char('2') if self.view == View::Main || self.is_popup_view() => {
+            Some(Action::ProvidersTab) if can_switch_tab => {
                 self.close_current_view();
                 self.tab = Tab::Providers;
                 return Ok(());
             }
-            KeyCode::Char('3') if self.view == View::Main || self.is_popup_view() => {
+            Some(Action::SettingsTab) if can_switch_tab => {
                 self.close_current_view();
                 self.tab = Tab::Settings;
                 return Ok(());
             }
             // Tab key cycles through tabs (in Main view and popup views)
-            KeyCode::Tab if self.view == View::Main || self.is_popup_view() => {
+            Some(Action::NextTab) if can_switch_tab => {
                 self.close_current_view();
                 self.tab = match self.tab {
                     Tab::Containers => Tab::Providers,
@@ -1668,7 +2221,7 @@ impl App {
                 };
                 return Ok(());
             }
-            KeyCode::BackTab if self.view == View::Main || self.is_popup_view() => {
+            Some(Action::PrevTab) if can_switch_tab => {
                 self.close_current_view();
                 self.tab = match self.tab {
                     Tab::Containers => Tab::Settings,
@@ -1691,15 +2244,16 @@ impl App {
             View::ProviderDetail => self.handle_provider_detail_key(code, modifiers).await?,
             View::BuildOutput => self.handle_build_key(code, modifiers).await?,
             View::Logs => self.handle_logs_key(code, modifiers).await?,
+            View::ConfigView => self.handle_config_view_key(code, modifiers),
             View::Ports => self.handle_ports_key(code, modifiers).await?,
-            View::DiscoverDetail => match code {
-                KeyCode::Char('j') | KeyCode::Down => {
+            View::DiscoverDetail => match keymap::action_for(context, code, modifiers) {
+                Some(Action::MoveDown) => {
                     self.discover_detail_scroll = self.discover_detail_scroll.saturating_add(1)
                 }
-                KeyCode::Char('k') | KeyCode::Up => {
+                Some(Action::MoveUp) => {
                     self.discover_detail_scroll = self.discover_detail_scroll.saturating_sub(1)
                 }
-                KeyCode::Char('a') => {
+                Some(Action::Adopt) => {
                     if let Some(container) =
                         self.discovered_containers.get(self.selected_discovered)
                     {
@@ -1718,14 +2272,14 @@ impl App {
                 }
                 _ => {}
             },
-            View::AgentDiagnostics => match code {
-                KeyCode::Char('j') | KeyCode::Down if !self.agent_diagnostics_rows.is_empty() => {
+            View::AgentDiagnostics => match keymap::action_for(context, code, modifiers) {
+                Some(Action::MoveDown) if !self.agent_diagnostics_rows.is_empty() => {
                     self.agent_diagnostics_selected =
                         (self.agent_diagnostics_selected + 1) % self.agent_diagnostics_rows.len();
                     self.agent_diagnostics_table_state
                         .select(Some(self.agent_diagnostics_selected));
                 }
-                KeyCode::Char('k') | KeyCode::Up if !self.agent_diagnostics_rows.is_empty() => {
+                Some(Action::MoveUp) if !self.agent_diagnostics_rows.is_empty() => {
                     self.agent_diagnostics_selected = self
                         .agent_diagnostics_selected
                         .checked_sub(1)
@@ -1733,16 +2287,16 @@ impl App {
                     self.agent_diagnostics_table_state
                         .select(Some(self.agent_diagnostics_selected));
                 }
-                KeyCode::Char('g') | KeyCode::Home => {
+                Some(Action::First) => {
                     self.agent_diagnostics_selected = 0;
                     self.agent_diagnostics_table_state.select(Some(0));
                 }
-                KeyCode::Char('G') | KeyCode::End if !self.agent_diagnostics_rows.is_empty() => {
+                Some(Action::Last) if !self.agent_diagnostics_rows.is_empty() => {
                     self.agent_diagnostics_selected = self.agent_diagnostics_rows.len() - 1;
                     self.agent_diagnostics_table_state
                         .select(Some(self.agent_diagnostics_selected));
                 }
-                KeyCode::Char('r') => {
+                Some(Action::Refresh) => {
                     if let Some(container_id) = self.agent_diagnostics_container_id.clone() {
                         self.status_message = Some(format!(
                             "Refreshing agent status for '{}'...",
@@ -1755,10 +2309,10 @@ impl App {
                         .await;
                     }
                 }
-                KeyCode::Char('s') => {
+                Some(Action::SyncAgent) => {
                     self.sync_selected_agent_from_popup().await;
                 }
-                KeyCode::Char('A') => {
+                Some(Action::SyncAllAgents) => {
                     self.sync_enabled_agents_from_popup().await;
                 }
                 _ => {}
@@ -1774,10 +2328,19 @@ impl App {
     async fn handle_containers_key(
         &mut self,
         code: KeyCode,
-        _modifiers: KeyModifiers,
+        modifiers: KeyModifiers,
     ) -> AppResult<()> {
+        let context = if self.discover_mode {
+            KeyContext::Discover
+        } else {
+            KeyContext::Containers
+        };
+        let Some(action) = keymap::action_for(context, code, modifiers) else {
+            return Ok(());
+        };
+
         // Toggle discover mode with 'D'
-        if code == KeyCode::Char('D') {
+        if action == Action::ToggleDiscover {
             if self.discover_mode {
                 // Exit discover mode
                 self.discover_mode = false;
@@ -1794,15 +2357,15 @@ impl App {
 
         if self.discover_mode {
             // Discover mode key handling
-            match code {
+            match action {
                 // Navigation
-                KeyCode::Char('j') | KeyCode::Down if !self.discovered_containers.is_empty() => {
+                Action::MoveDown if !self.discovered_containers.is_empty() => {
                     self.selected_discovered =
                         (self.selected_discovered + 1) % self.discovered_containers.len();
                     self.discovered_table_state
                         .select(Some(self.selected_discovered));
                 }
-                KeyCode::Char('k') | KeyCode::Up if !self.discovered_containers.is_empty() => {
+                Action::MoveUp if !self.discovered_containers.is_empty() => {
                     self.selected_discovered = self
                         .selected_discovered
                         .checked_sub(1)
@@ -1810,17 +2373,17 @@ impl App {
                     self.discovered_table_state
                         .select(Some(self.selected_discovered));
                 }
-                KeyCode::Char('g') | KeyCode::Home => {
+                Action::First => {
                     self.selected_discovered = 0;
                     self.discovered_table_state.select(Some(0));
                 }
-                KeyCode::Char('G') | KeyCode::End if !self.discovered_containers.is_empty() => {
+                Action::Last if !self.discovered_containers.is_empty() => {
                     self.selected_discovered = self.discovered_containers.len() - 1;
                     self.discovered_table_state
                         .select(Some(self.selected_discovered));
                 }
                 // Adopt selected container
-                KeyCode::Char('a') if !self.discovered_containers.is_empty() => {
+                Action::Adopt if !self.discovered_containers.is_empty() => {
                     let container = &self.discovered_containers[self.selected_discovered];
                     if container.source != DevcontainerSource::Devc {
                         self.dialog_focus = DialogFocus::Cancel;
@@ -1838,7 +2401,7 @@ impl App {
                     }
                 }
                 // Inspect selected container
-                KeyCode::Enter if !self.discovered_containers.is_empty() => {
+                Action::Open if !self.discovered_containers.is_empty() => {
                     let container = &self.discovered_containers[self.selected_discovered];
                     let provider_type = container.provider;
                     let container_id = container.id.clone();
@@ -1861,7 +2424,7 @@ impl App {
                     }
                 }
                 // Refresh discovered containers
-                KeyCode::Char('r') | KeyCode::F(5) => {
+                Action::Refresh => {
                     self.refresh_discovered().await?;
                     self.status_message = Some("Refreshed discovered containers".to_string());
                 }
@@ -1869,9 +2432,9 @@ impl App {
             }
         } else {
             // Normal (managed) mode key handling
-            match code {
+            match action {
                 // Navigation
-                KeyCode::Char('j') | KeyCode::Down if !self.containers.is_empty() => {
+                Action::MoveDown if !self.containers.is_empty() => {
                     let prev = self.selected;
                     self.selected = (self.selected + 1) % self.containers.len();
                     self.containers_table_state.select(Some(self.selected));
@@ -1879,7 +2442,7 @@ impl App {
                         self.on_container_switch();
                     }
                 }
-                KeyCode::Char('k') | KeyCode::Up if !self.containers.is_empty() => {
+                Action::MoveUp if !self.containers.is_empty() => {
                     let prev = self.selected;
                     self.selected = self
                         .selected
@@ -1890,7 +2453,7 @@ impl App {
                         self.on_container_switch();
                     }
                 }
-                KeyCode::Char('g') | KeyCode::Home => {
+                Action::First => {
                     let prev = self.selected;
                     self.selected = 0;
                     self.containers_table_state.select(Some(0));
@@ -1898,7 +2461,7 @@ impl App {
                         self.on_container_switch();
                     }
                 }
-                KeyCode::Char('G') | KeyCode::End if !self.containers.is_empty() => {
+                Action::Last if !self.containers.is_empty() => {
                     let prev = self.selected;
                     self.selected = self.containers.len() - 1;
                     self.containers_table_state.select(Some(self.selected));
@@ -1908,22 +2471,28 @@ impl App {
                 }
 
                 // Actions
-                KeyCode::Enter if !self.containers.is_empty() => {
+                Action::Open if !self.containers.is_empty() => {
                     self.view = View::ContainerDetail;
                     self.container_detail = None;
+                    self.container_disk_usage = None;
+                    self.vscode_extensions =
+                        vscode_extensions_for_config(&self.containers[self.selected].config_path);
                     self.container_detail_scroll = 0;
                     self.compose_state.selected_service = 0;
                     self.compose_state.services_table_state.select(Some(0));
-                    self.fetch_compose_services().await;
+                    self.fetch_compose_services();
                     self.fetch_container_detail().await;
                 }
-                KeyCode::Char('s') => {
+                Action::ToggleStartStop => {
                     self.toggle_selected().await?;
                 }
-                KeyCode::Char('u') => {
+                Action::TogglePause => {
+                    self.toggle_pause_selected().await?;
+                }
+                Action::Up => {
                     self.up_selected().await?;
                 }
-                KeyCode::Char('d') | KeyCode::Delete if !self.containers.is_empty() => {
+                Action::Delete if !self.containers.is_empty() => {
                     let container = &self.containers[self.selected];
                     if container.status.is_available() {
                         self.status_message =
@@ -1934,11 +2503,9 @@ impl App {
                         self.view = View::Confirm;
                     }
                 }
-                KeyCode::Char('f') if !self.containers.is_empty() => {
+                Action::Forget if !self.containers.is_empty() => {
                     let container = &self.containers[self.selected];
-                    if container.source != DevcontainerSource::Devc
-                        && !container.status.is_available()
-                    {
+                    if self.can_forget(container) {
                         self.confirm_action = Some(ConfirmAction::Forget {
                             id: container.id.clone(),
                             name: container.name.clone(),
@@ -1950,31 +2517,52 @@ impl App {
                             Some("Cannot forget devc-created containers".to_string());
                     }
                 }
-                KeyCode::Char('r') | KeyCode::F(5) => {
+                Action::Refresh => {
                     self.refresh_containers().await?;
                     self.status_message = Some("Refreshed".to_string());
                 }
-                KeyCode::Char('b') => {
+                Action::SyncStatus => {
+                    self.sync_selected_status();
+                }
+                Action::CycleSort => {
+                    self.sort_order = next_sort_order(self.sort_order);
+                    self.sort_and_preserve_selection(None);
+                    self.status_message = Some(format!("Sorted by {}", self.sort_order));
+                }
+                Action::ToggleFullIds => {
+                    self.toggle_full_ids().await;
+                }
+                Action::ToggleWorkspaceGroups => {
+                    self.toggle_workspace_groups().await;
+                }
+                Action::Build => {
                     self.start_build_dialog();
                 }
-                KeyCode::Char('R') => {
-                    self.start_rebuild_dialog();
+                Action::Rebuild => {
+                    self.start_rebuild_dialog().await;
                 }
-                KeyCode::Char('p') if !self.containers.is_empty() => {
+                Action::Ports if !self.containers.is_empty() => {
                     // Enter port forwarding view for selected container
                     let container = self.containers[self.selected].clone();
                     self.enter_ports_view(&container).await?;
                 }
-                KeyCode::Char('a') => {
+                Action::Agents => {
                     self.open_agent_manager_for_selected_container().await;
                 }
                 #[cfg(unix)]
-                KeyCode::Char('S') if !self.containers.is_empty() => {
+                Action::Shell if !self.containers.is_empty() => {
                     let container = self.containers[self.selected].clone();
                     self.enter_shell_mode(&container).await?;
                 }
+                #[cfg(unix)]
+                Action::RestartShell if !self.containers.is_empty() => {
+                    self.restart_shell_session().await?;
+                }
+                Action::CloseShell => {
+                    self.close_shell_session();
+                }
                 #[cfg(not(unix))]
-                KeyCode::Char('S') => {
+                Action::Shell => {
                     self.status_message = Some("Shell not supported on this platform".to_string());
                 }
 
@@ -1988,16 +2576,19 @@ impl App {
     async fn handle_providers_key(
         &mut self,
         code: KeyCode,
-        _modifiers: KeyModifiers,
+        modifiers: KeyModifiers,
     ) -> AppResult<()> {
-        match code {
+        let Some(action) = keymap::action_for(KeyContext::Providers, code, modifiers) else {
+            return Ok(());
+        };
+        match action {
             // Navigation
-            KeyCode::Char('j') | KeyCode::Down if !self.providers.is_empty() => {
+            Action::MoveDown if !self.providers.is_empty() => {
                 self.selected_provider = (self.selected_provider + 1) % self.providers.len();
                 self.providers_table_state
                     .select(Some(self.selected_provider));
             }
-            KeyCode::Char('k') | KeyCode::Up if !self.providers.is_empty() => {
+            Action::MoveUp if !self.providers.is_empty() => {
                 self.selected_provider = self
                     .selected_provider
                     .checked_sub(1)
@@ -2007,14 +2598,14 @@ impl App {
             }
 
             // Open provider detail/configuration
-            KeyCode::Enter if !self.providers.is_empty() => {
+            Action::Open if !self.providers.is_empty() => {
                 // Reset provider detail state and enter detail view
                 self.provider_detail_state = ProviderDetailState::new();
                 self.view = View::ProviderDetail;
             }
 
             // Set as active provider - show confirmation dialog
-            KeyCode::Char(' ') | KeyCode::Char('a') if !self.providers.is_empty() => {
+            Action::SetActive if !self.providers.is_empty() => {
                 let new_provider = self.providers[self.selected_provider].provider_type;
                 // Only show confirmation if it's a different provider
                 if self.active_provider != Some(new_provider) {
@@ -2025,7 +2616,7 @@ impl App {
             }
 
             // Save changes (for socket path edits)
-            KeyCode::Char('s') => {
+            Action::Save => {
                 let docker_socket = self.config.providers.docker.socket.clone();
                 let podman_socket = self.config.providers.podman.socket.clone();
                 match GlobalConfig::update_atomically(|cfg| {
@@ -2044,7 +2635,7 @@ impl App {
             }
 
             // Retry connection
-            KeyCode::Char('c') if !self.is_connected() => {
+            Action::RetryConnection if !self.is_connected() => {
                 self.retry_connection().await?;
             }
 
@@ -2057,7 +2648,7 @@ impl App {
     async fn handle_provider_detail_key(
         &mut self,
         code: KeyCode,
-        _modifiers: KeyModifiers,
+        modifiers: KeyModifiers,
     ) -> AppResult<()> {
         let provider = &self.providers[self.selected_provider];
         let provider_type = provider.provider_type;
@@ -2100,16 +2691,19 @@ impl App {
                 _ => {}
             }
         } else {
-            // Navigation mode
-            match code {
+            let Some(action) = keymap::action_for(KeyContext::ProviderDetail, code, modifiers)
+            else {
+                return Ok(());
+            };
+            match action {
                 // Edit socket path
-                KeyCode::Char('e') | KeyCode::Enter => {
+                Action::Edit => {
                     let current_socket = self.providers[self.selected_provider].socket.clone();
                     self.provider_detail_state.start_edit(&current_socket);
                 }
 
                 // Test connection by checking if the socket exists
-                KeyCode::Char('t') => {
+                Action::TestConnection => {
                     self.status_message = Some("Testing connection...".to_string());
                     self.provider_detail_state.clear_connection_status();
 
@@ -2141,7 +2735,7 @@ impl App {
                 }
 
                 // Set as active provider - show confirmation dialog
-                KeyCode::Char('a') | KeyCode::Char(' ') => {
+                Action::SetActive => {
                     let new_provider = self.providers[self.selected_provider].provider_type;
                     // Only show confirmation if it's a different provider
                     if self.active_provider != Some(new_provider) {
@@ -2152,7 +2746,7 @@ impl App {
                 }
 
                 // Save changes
-                KeyCode::Char('s') => {
+                Action::Save => {
                     let docker_socket = self.config.providers.docker.socket.clone();
                     let podman_socket = self.config.providers.podman.socket.clone();
                     match GlobalConfig::update_atomically(|cfg| {
@@ -2181,7 +2775,7 @@ impl App {
     async fn handle_settings_key(
         &mut self,
         code: KeyCode,
-        _modifiers: KeyModifiers,
+        modifiers: KeyModifiers,
     ) -> AppResult<()> {
         if self.settings_state.editing {
             // In edit mode
@@ -2207,27 +2801,31 @@ impl App {
                 _ => {}
             }
         } else {
-            // Navigation mode
-            match code {
-                KeyCode::Char('j') | KeyCode::Down => {
+            let Some(action) = keymap::action_for(KeyContext::Settings, code, modifiers) else {
+                return Ok(());
+            };
+            match action {
+                Action::MoveDown => {
                     self.settings_state.move_down();
                 }
-                KeyCode::Char('k') | KeyCode::Up => {
+                Action::MoveUp => {
                     self.settings_state.move_up();
                 }
-                KeyCode::Enter | KeyCode::Char(' ') => {
+                Action::Edit => {
                     if let Some(msg) = self.settings_state.start_edit() {
                         self.status_message = Some(msg);
                     }
                 }
-                KeyCode::Char('s') => {
+                Action::Save => {
                     // Save settings
                     let draft = self.settings_state.draft.clone();
                     let availability = self.settings_state.agent_availability.clone();
+                    let agent_auto = self.settings_state.agent_auto.clone();
                     match GlobalConfig::update_atomically(|cfg| {
                         let mut state = SettingsState::new(cfg);
                         state.draft = draft.clone();
                         state.agent_availability = availability.clone();
+                        state.agent_auto = agent_auto.clone();
                         state.apply_to_config(cfg);
                     }) {
                         Err(e) => {
@@ -2241,7 +2839,7 @@ impl App {
                         }
                     }
                 }
-                KeyCode::Char('r') => {
+                Action::Reset => {
                     // Reset to saved values
                     self.settings_state.reset_from_config(&self.config);
                     let availability = devc_core::agents::host_agent_availability(&self.config);
@@ -2274,62 +2872,85 @@ impl App {
     }
 
     /// Handle container detail view keys
-    async fn handle_detail_key(
-        &mut self,
-        code: KeyCode,
-        _modifiers: KeyModifiers,
-    ) -> AppResult<()> {
+    async fn handle_detail_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> AppResult<()> {
         let has_services = self
             .selected_container()
             .and_then(|c| self.compose_state.services.get(&c.id))
             .map(|s| !s.is_empty())
             .unwrap_or(false);
 
-        match code {
-            KeyCode::Char('j') | KeyCode::Down => {
+        let Some(action) = keymap::action_for(KeyContext::ContainerDetail, code, modifiers) else {
+            return Ok(());
+        };
+        match action {
+            Action::MoveDown => {
                 if has_services {
                     self.move_compose_service_selection(1);
                 } else {
                     self.container_detail_scroll = self.container_detail_scroll.saturating_add(1);
                 }
             }
-            KeyCode::Char('k') | KeyCode::Up => {
+            Action::MoveUp => {
                 if has_services {
                     self.move_compose_service_selection(-1);
                 } else {
                     self.container_detail_scroll = self.container_detail_scroll.saturating_sub(1);
                 }
             }
-            KeyCode::Char('r') | KeyCode::F(5) => {
-                // Refresh: invalidate cached services and re-fetch
-                if let Some(container) = self.selected_container() {
-                    let id = container.id.clone();
-                    self.compose_state.services.remove(&id);
-                }
-                self.fetch_compose_services().await;
+            Action::Refresh => {
+                self.refresh_compose_services();
+            }
+            Action::ToggleFullIds => {
+                self.toggle_full_ids().await;
             }
-            KeyCode::Char('s') => {
+            Action::ToggleStartStop => {
                 self.toggle_selected().await?;
             }
-            KeyCode::Char('u') => {
+            Action::TogglePause => {
+                self.toggle_pause_selected().await?;
+            }
+            Action::Up => {
                 self.up_selected().await?;
             }
-            KeyCode::Char('l') => {
+            Action::Logs => {
                 self.fetch_logs().await?;
             }
-            KeyCode::Char('b') => {
-                self.start_build_dialog();
+            Action::ViewConfig => {
+                self.open_config_view().await;
             }
-            KeyCode::Char('R') => {
-                self.start_rebuild_dialog();
+            Action::EditConfig => {
+                self.request_config_edit();
+            }
+            Action::Build => {
+                self.start_build_dialog();
+            }
+            Action::Rebuild => {
+                self.start_rebuild_dialog().await;
+            }
+            Action::RemovePreviousImage => {
+                self.start_remove_previous_image_dialog();
+            }
+            Action::CopyConnection => {
+                self.copy_connection_url().await;
             }
             #[cfg(unix)]
-            KeyCode::Char('S') if !self.containers.is_empty() => {
+            Action::Shell if !self.containers.is_empty() => {
                 let container = self.containers[self.selected].clone();
-                self.enter_shell_mode(&container).await?;
+                if let Some(svc) = self.selected_companion_service() {
+                    self.enter_service_shell_mode(&container, svc).await?;
+                } else {
+                    self.enter_shell_mode(&container).await?;
+                }
+            }
+            #[cfg(unix)]
+            Action::RestartShell if !self.containers.is_empty() => {
+                self.restart_shell_session().await?;
+            }
+            Action::CloseShell => {
+                self.close_shell_session();
             }
             #[cfg(not(unix))]
-            KeyCode::Char('S') => {
+            Action::Shell => {
                 self.status_message = Some("Shell not supported on this platform".to_string());
             }
             _ => {}
@@ -2337,32 +2958,206 @@ impl App {
         Ok(())
     }
 
+    /// Key of the shell session for the selection: the container, or the
+    /// companion service selected in the detail view
+    fn selected_shell_key(&self) -> Option<String> {
+        let container = self.selected_container()?;
+        Some(match self.selected_companion_service() {
+            Some(svc) => format!("{}/{}", container.id, svc.service_name),
+            None => container.id.clone(),
+        })
+    }
+
+    /// Kill and respawn the selected shell session
+    ///
+    /// Goes through the same path as a first attach, so postAttachCommand runs
+    /// again for devc containers.
+    #[cfg(unix)]
+    async fn restart_shell_session(&mut self) -> AppResult<()> {
+        let Some(key) = self.selected_shell_key() else {
+            return Ok(());
+        };
+        // Dropping the session kills its PTY
+        if self.shell_state.shell_sessions.remove(&key).is_none() {
+            self.status_message = Some("No shell session to restart".to_string());
+            return Ok(());
+        }
+        let container = self.containers[self.selected].clone();
+        match self.selected_companion_service() {
+            Some(svc) => self.enter_service_shell_mode(&container, svc).await,
+            None => self.enter_shell_mode(&container).await,
+        }
+    }
+
+    /// Drop the selected shell session, killing the shell if it is still running
+    fn close_shell_session(&mut self) {
+        let Some(key) = self.selected_shell_key() else {
+            return;
+        };
+        self.status_message = Some(match self.shell_state.shell_sessions.remove(&key) {
+            Some(session) => format!("Closed shell session for '{}'", session.container_name),
+            None => "No shell session to close".to_string(),
+        });
+    }
+
+    /// The compose service selected in the detail view, if it is a companion
+    /// (non-primary) service of the selected container.
+    fn selected_companion_service(&self) -> Option<devc_provider::ComposeServiceInfo> {
+        if self.view != View::ContainerDetail {
+            return None;
+        }
+        let container = self.selected_container()?;
+        let svc = self
+            .compose_state
+            .services
+            .get(&container.id)?
+            .get(self.compose_state.selected_service)?;
+        if container.compose_service.as_deref() == Some(svc.service_name.as_str()) {
+            None
+        } else {
+            Some(svc.clone())
+        }
+    }
+
+    /// Copy a connection URL for the selected compose service (or the container
+    /// itself) built from its image and published ports
+    async fn copy_connection_url(&mut self) {
+        let Some(container) = self.selected_container().cloned() else {
+            return;
+        };
+        let service = self
+            .compose_state
+            .services
+            .get(&container.id)
+            .and_then(|services| services.get(self.compose_state.selected_service))
+            .cloned();
+
+        let details = match service {
+            Some(svc) => {
+                let provider = match create_provider(container.provider, &self.config).await {
+                    Ok(p) => p,
+                    Err(e) => {
+                        self.status_message = Some(format!("Failed to inspect service: {}", e));
+                        return;
+                    }
+                };
+                match provider.inspect(&svc.container_id).await {
+                    Ok(details) => details,
+                    Err(e) => {
+                        self.status_message =
+                            Some(format!("Failed to inspect {}: {}", svc.service_name, e));
+                        return;
+                    }
+                }
+            }
+            None => match self.container_detail.clone() {
+                Some(details) => details,
+                None => {
+                    self.status_message = Some("Container is not running".to_string());
+                    return;
+                }
+            },
+        };
+
+        self.status_message = Some(
+            match crate::compose_state::connection_url(&details.image, &details.ports) {
+                Some(url) => match copy_to_clipboard(&url) {
+                    Ok(()) => format!("Copied {}", url),
+                    Err(e) => format!("Failed to copy: {}", e),
+                },
+                None => "No published ports to connect to".to_string(),
+            },
+        );
+    }
+
+    /// Load the resolved devcontainer.json for the selected container and open the viewer
+    async fn open_config_view(&mut self) {
+        let Some(container) = self.selected_container().cloned() else {
+            return;
+        };
+
+        let result = self
+            .manager
+            .read()
+            .await
+            .get_devcontainer_config(&container);
+        match result {
+            Ok(config) => {
+                self.config_view_lines = config_json_lines(&config);
+                self.config_view_scroll = 0;
+                self.view = View::ConfigView;
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to load config: {}", e));
+            }
+        }
+    }
+
+    /// Handle config viewer keys
+    fn handle_config_view_key(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        let page_size = 20;
+        let max_scroll = self.config_view_lines.len().saturating_sub(1);
+
+        let Some(action) = keymap::action_for(KeyContext::ConfigView, code, modifiers) else {
+            return;
+        };
+        match action {
+            Action::MoveDown => {
+                self.config_view_scroll = (self.config_view_scroll + 1).min(max_scroll);
+            }
+            Action::MoveUp => {
+                self.config_view_scroll = self.config_view_scroll.saturating_sub(1);
+            }
+            Action::First => {
+                self.config_view_scroll = 0;
+            }
+            Action::Last => {
+                self.config_view_scroll = max_scroll;
+            }
+            Action::PageDown => {
+                self.config_view_scroll = (self.config_view_scroll + page_size).min(max_scroll);
+            }
+            Action::PageUp => {
+                self.config_view_scroll = self.config_view_scroll.saturating_sub(page_size);
+            }
+            _ => {}
+        }
+    }
+
     /// Handle build output view keys
-    async fn handle_build_key(&mut self, code: KeyCode, _modifiers: KeyModifiers) -> AppResult<()> {
-        match code {
-            KeyCode::Char('j') | KeyCode::Down
+    async fn handle_build_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> AppResult<()> {
+        let Some(action) = keymap::action_for(KeyContext::BuildOutput, code, modifiers) else {
+            return Ok(());
+        };
+        match action {
+            Action::MoveDown
                 if self.build_output_scroll < self.build_output.len().saturating_sub(1) =>
             {
                 self.build_output_scroll += 1;
                 self.build_auto_scroll = false; // User took control
             }
-            KeyCode::Char('k') | KeyCode::Up if self.build_output_scroll > 0 => {
+            Action::MoveUp if self.build_output_scroll > 0 => {
                 self.build_output_scroll -= 1;
                 self.build_auto_scroll = false;
             }
-            KeyCode::Char('G') | KeyCode::End => {
+            Action::Last => {
                 self.build_output_scroll = self.build_output.len().saturating_sub(1);
                 self.build_auto_scroll = true; // Re-enable auto-scroll
             }
-            KeyCode::Char('g') | KeyCode::Home => {
+            Action::First => {
                 self.build_output_scroll = 0;
                 self.build_auto_scroll = false;
             }
-            KeyCode::Char('c') => {
-                // Copy all log lines to clipboard
-                let content = self.build_output.join("\n");
+            Action::Copy => {
+                // Copy the log as plain text, trimmed to the tail if it's huge
+                let (content, dropped) = build_export::clipboard_text(&self.build_output);
                 if let Err(e) = copy_to_clipboard(&content) {
                     self.status_message = Some(format!("Failed to copy: {}", e));
+                } else if dropped > 0 {
+                    self.status_message = Some(format!(
+                        "Copied last {} lines to clipboard (log too large; save with 's' for all)",
+                        self.build_output.len() - dropped
+                    ));
                 } else {
                     self.status_message = Some(format!(
                         "Copied {} lines to clipboard",
@@ -2370,7 +3165,34 @@ impl App {
                     ));
                 }
             }
-            KeyCode::Char('q') | KeyCode::Esc
+            Action::Save => {
+                let name = self
+                    .selected_container()
+                    .map(|c| c.name.clone())
+                    .unwrap_or_else(|| "devc".to_string());
+                let result = GlobalConfig::data_dir()
+                    .map_err(|e| e.to_string())
+                    .and_then(|dir| {
+                        build_export::write_log_file(
+                            &dir.join("build-logs"),
+                            &name,
+                            &self.build_output,
+                        )
+                        .map_err(|e| e.to_string())
+                    });
+                self.status_message = Some(match result {
+                    Ok(path) => format!("Saved build log to {}", path.display()),
+                    Err(e) => format!("Failed to save build log: {}", e),
+                });
+            }
+            Action::ToggleVerbose => {
+                let in_place = self.feature_table_in_place();
+                self.build_verbose = !self.build_verbose;
+                if in_place {
+                    self.redraw_feature_table();
+                }
+            }
+            Action::Close
                 // Build complete case handled by view-specific exit above global keys.
                 // Here we only handle the in-progress cancellation case.
                 if !self.build_complete =>
@@ -2387,62 +3209,95 @@ impl App {
     async fn handle_logs_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> AppResult<()> {
         let page_size = 20;
 
-        match code {
-            KeyCode::Char('j') | KeyCode::Down
-                if self.logs_scroll < self.logs.len().saturating_sub(1) =>
-            {
+        let Some(action) = keymap::action_for(KeyContext::Logs, code, modifiers) else {
+            return Ok(());
+        };
+        match action {
+            Action::MoveDown if self.logs_scroll < self.logs.len().saturating_sub(1) => {
                 self.logs_scroll += 1;
             }
-            KeyCode::Char('k') | KeyCode::Up => {
+            Action::MoveUp => {
                 self.logs_scroll = self.logs_scroll.saturating_sub(1);
             }
-            KeyCode::Char('g') | KeyCode::Home => {
+            Action::First => {
                 self.logs_scroll = 0;
             }
-            KeyCode::Char('G') | KeyCode::End => {
+            Action::Last => {
                 self.logs_scroll = self.logs.len().saturating_sub(1);
             }
-            KeyCode::Char('d') if modifiers.contains(KeyModifiers::CONTROL) => {
+            Action::HalfPageDown => {
                 self.logs_scroll =
                     (self.logs_scroll + page_size / 2).min(self.logs.len().saturating_sub(1));
             }
-            KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => {
+            Action::HalfPageUp => {
                 self.logs_scroll = self.logs_scroll.saturating_sub(page_size / 2);
             }
-            KeyCode::PageDown => {
+            Action::PageDown => {
                 self.logs_scroll =
                     (self.logs_scroll + page_size).min(self.logs.len().saturating_sub(1));
             }
-            KeyCode::PageUp => {
+            Action::PageUp => {
                 self.logs_scroll = self.logs_scroll.saturating_sub(page_size);
             }
-            KeyCode::Char('r') | KeyCode::F(5) => {
+            Action::Refresh => {
                 self.fetch_logs().await?;
             }
+            Action::ToggleTimestamps => {
+                self.toggle_log_timestamps().await?;
+            }
             _ => {}
         }
         Ok(())
     }
 
+    /// Flip timestamps in the logs view, persist the choice, and reload the logs
+    async fn toggle_log_timestamps(&mut self) -> AppResult<()> {
+        let timestamps = !self.log_timestamps;
+        self.log_timestamps = timestamps;
+        let save_error =
+            match GlobalConfig::update_atomically(|cfg| cfg.ui.log_timestamps = timestamps) {
+                Err(e) => Some(e),
+                Ok(updated) => {
+                    self.config = updated;
+                    self.sync_manager_config_from_app().await;
+                    None
+                }
+            };
+
+        // Keep the reader at the same line rather than jumping to the bottom
+        let scroll = self.logs_scroll;
+        self.fetch_logs().await?;
+        self.logs_scroll = scroll.min(self.logs.len().saturating_sub(1));
+
+        // The logs header shows the current state; only a failed save needs a message
+        if let Some(e) = save_error {
+            self.status_message = Some(format!("Failed to save timestamp setting: {}", e));
+        }
+        Ok(())
+    }
+
     /// Handle Port Forwarding view keys
-    async fn handle_ports_key(&mut self, code: KeyCode, _modifiers: KeyModifiers) -> AppResult<()> {
-        match code {
+    async fn handle_ports_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> AppResult<()> {
+        let Some(action) = keymap::action_for(KeyContext::Ports, code, modifiers) else {
+            return Ok(());
+        };
+        match action {
             // Navigation
-            KeyCode::Char('j') | KeyCode::Down => {
+            Action::MoveDown => {
                 self.port_state.select_next();
             }
-            KeyCode::Char('k') | KeyCode::Up => {
+            Action::MoveUp => {
                 self.port_state.select_prev();
             }
-            KeyCode::Char('g') | KeyCode::Home => {
+            Action::First => {
                 self.port_state.select_first();
             }
-            KeyCode::Char('G') | KeyCode::End => {
+            Action::Last => {
                 self.port_state.select_last();
             }
 
             // Forward selected port
-            KeyCode::Char('f') => {
+            Action::Forward => {
                 if self.port_state.socat_installed != Some(true) {
                     self.status_message = Some("socat required - press 'i' to install".to_string());
                 } else if let Some(port) = self.port_state.selected_port_info() {
@@ -2454,7 +3309,7 @@ impl App {
             }
 
             // Stop forwarding
-            KeyCode::Char('s') => {
+            Action::StopForward => {
                 if let Some(port) = self.port_state.selected_port_info() {
                     if port.is_forwarded {
                         let p = port.port;
@@ -2463,29 +3318,42 @@ impl App {
                 }
             }
 
+            // Start or stop forwarding the selected port
+            Action::ToggleForward => {
+                if let Some(port) = self.port_state.selected_port_info() {
+                    let (p, forwarded) = (port.port, port.is_forwarded);
+                    if forwarded {
+                        self.stop_forward(p).await;
+                    } else if self.port_state.socat_installed != Some(true) {
+                        self.status_message =
+                            Some("socat required - press 'i' to install".to_string());
+                    } else {
+                        self.forward_port(p).await?;
+                    }
+                }
+            }
+
             // Open in browser
-            KeyCode::Char('o') => {
+            Action::OpenBrowser => {
                 if self.port_state.socat_installed != Some(true) {
                     self.status_message = Some("socat required - press 'i' to install".to_string());
                 } else if let Some(port) = self.port_state.selected_port_info() {
                     if port.is_forwarded {
-                        // Look up protocol from auto_forward_configs for this port
-                        let protocol =
-                            self.port_state
-                                .provider_container_id
-                                .as_ref()
-                                .and_then(|cid| {
-                                    self.port_state.auto_forward_configs.get(cid).and_then(
-                                        |configs| {
-                                            configs
-                                                .iter()
-                                                .find(|c| c.port == port.port)
-                                                .and_then(|c| c.protocol.as_deref())
-                                        },
-                                    )
-                                });
-                        if let Err(e) = open_in_browser(port.port, protocol) {
-                            self.status_message = Some(format!("Failed to open browser: {}", e));
+                        match self.port_state.browser_scheme(port) {
+                            Some(scheme) => {
+                                if let Err(e) = open_in_browser(port.port, Some(scheme)) {
+                                    self.status_message =
+                                        Some(format!("Failed to open browser: {}", e));
+                                }
+                            }
+                            None if port.scheme == PortScheme::Unknown => {
+                                self.status_message =
+                                    Some(format!("Port {} has not been probed yet", port.port));
+                            }
+                            None => {
+                                self.status_message =
+                                    Some(format!("Port {} does not serve HTTP", port.port));
+                            }
                         }
                     } else {
                         self.status_message = Some("Port must be forwarded first".to_string());
@@ -2494,7 +3362,7 @@ impl App {
             }
 
             // Forward all
-            KeyCode::Char('a') => {
+            Action::ForwardAll => {
                 if self.port_state.socat_installed != Some(true) {
                     self.status_message = Some("socat required - press 'i' to install".to_string());
                 } else {
@@ -2512,7 +3380,7 @@ impl App {
             }
 
             // Toggle auto-forward all for this container
-            KeyCode::Char('A') => {
+            Action::ToggleAutoForward => {
                 if let Some(ref cid) = self.port_state.provider_container_id {
                     let global_on = self.config.defaults.auto_forward_ports != Some(false);
                     if global_on {
@@ -2531,12 +3399,12 @@ impl App {
             }
 
             // Stop all (none)
-            KeyCode::Char('n') => {
+            Action::StopAllForwards => {
                 self.stop_all_forwards_for_container().await;
             }
 
             // Install socat
-            KeyCode::Char('i')
+            Action::InstallSocat
                 if self.port_state.socat_installed == Some(false)
                     && !self.port_state.socat_installing =>
             {
@@ -2594,8 +3462,10 @@ impl App {
             .port_state
             .active_forwarders
             .keys()
-            .filter(|(cid, _)| cid == &provider_container_id)
-            .map(|(_, port)| *port)
+            .filter(|(cid, _, transport)| {
+                cid == &provider_container_id && *transport == PortTransport::Tcp
+            })
+            .map(|(_, port, _)| *port)
             .collect();
 
         // Start port detection polling - create a new provider instance for the background task
@@ -2779,11 +3649,22 @@ impl App {
         let prefix = self.port_state.runtime_prefix.clone();
 
         // Spawn forwarder (uses socat via exec, no SSH needed)
-        match spawn_forwarder(program, prefix, container_id.clone(), port, port).await {
+        match spawn_forwarder(
+            program,
+            prefix,
+            container_id.clone(),
+            self.port_state.bind_address_for(&container_id, port),
+            port,
+            port,
+            PortTransport::Tcp,
+        )
+        .await
+        {
             Ok(forwarder) => {
+                let warning = forwarder.exposure_warning();
                 self.port_state
                     .active_forwarders
-                    .insert((container_id.clone(), port), forwarder);
+                    .insert((container_id.clone(), port, PortTransport::Tcp), forwarder);
                 // Update detected_ports to reflect forwarded state
                 if let Some(p) = self
                     .port_state
@@ -2794,7 +3675,9 @@ impl App {
                     p.is_forwarded = true;
                 }
                 self.status_message =
-                    Some(format!("Forwarding port {} -> localhost:{}", port, port));
+                    Some(warning.unwrap_or_else(|| {
+                        format!("Forwarding port {} -> localhost:{}", port, port)
+                    }));
             }
             Err(e) => {
                 self.status_message = Some(format!("Failed to forward port {}: {}", port, e));
@@ -2810,18 +3693,10 @@ impl App {
             None => return,
         };
 
-        let key = (container_id, port);
+        let key = (container_id, port, PortTransport::Tcp);
         if let Some(forwarder) = self.port_state.active_forwarders.remove(&key) {
             forwarder.stop().await;
-            // Update detected_ports to reflect not forwarded state
-            if let Some(p) = self
-                .port_state
-                .detected_ports
-                .iter_mut()
-                .find(|p| p.port == port)
-            {
-                p.is_forwarded = false;
-            }
+            self.port_state.mark_unforwarded(port);
             self.status_message = Some(format!("Stopped forwarding port {}", port));
         }
     }
@@ -2833,11 +3708,11 @@ impl App {
             None => return,
         };
 
-        let keys_to_remove: Vec<(String, u16)> = self
+        let keys_to_remove: Vec<ForwarderKey> = self
             .port_state
             .active_forwarders
             .keys()
-            .filter(|(cid, _)| cid == &container_id)
+            .filter(|(cid, _, _)| cid == &container_id)
             .cloned()
             .collect();
 
@@ -2848,9 +3723,11 @@ impl App {
         }
 
         // Update all detected_ports to not forwarded
+        self.port_state.detected_ports.retain(|p| p.is_listening);
         for p in &mut self.port_state.detected_ports {
             p.is_forwarded = false;
         }
+        self.port_state.clamp_selection();
         self.status_message = Some("Stopped all port forwards".to_string());
     }
 
@@ -3029,28 +3906,64 @@ impl App {
             ContainerOpResult::Success(op) | ContainerOpResult::Failed(op, _) => match op {
                 ContainerOperation::Starting { id, .. }
                 | ContainerOperation::Stopping { id, .. }
+                | ContainerOperation::Pausing { id, .. }
+                | ContainerOperation::Unpausing { id, .. }
                 | ContainerOperation::Deleting { id, .. }
                 | ContainerOperation::Up { id, .. }
                 | ContainerOperation::Adopting { id, .. }
-                | ContainerOperation::Forgetting { id, .. } => Some(id.clone()),
+                | ContainerOperation::Forgetting { id, .. }
+                | ContainerOperation::RemovingImage { id, .. }
+                | ContainerOperation::Syncing { id, .. } => Some(id.clone()),
             },
         };
+        // A single-container sync reports its own outcome once the list is reloaded
+        let synced = match &result {
+            ContainerOpResult::Success(ContainerOperation::Syncing {
+                id, name, previous, ..
+            }) => Some((id.clone(), name.clone(), *previous)),
+            _ => None,
+        };
 
         match result {
             ContainerOpResult::Success(ref op) => {
                 let msg = match op {
                     ContainerOperation::Starting { name, .. } => format!("Started {}", name),
                     ContainerOperation::Stopping { name, .. } => format!("Stopped {}", name),
+                    ContainerOperation::Pausing { name, .. } => format!("Paused {}", name),
+                    ContainerOperation::Unpausing { name, .. } => format!("Unpaused {}", name),
                     ContainerOperation::Deleting { name, .. } => format!("Deleted {}", name),
                     ContainerOperation::Up { name, .. } => format!("Up completed for {}", name),
                     ContainerOperation::Adopting { name, .. } => format!("Adopted {}", name),
                     ContainerOperation::Forgetting { name, .. } => {
                         format!("Forgot '{}' (container still running)", name)
                     }
+                    ContainerOperation::RemovingImage { name, image, .. } => format!(
+                        "Removed previous image {} of {}",
+                        crate::ui::format_id(image, false),
+                        name
+                    ),
+                    ContainerOperation::Syncing { name, .. } => format!("Checked {}", name),
                 };
+                if synced.is_none() {
+                    self.toasts.push(msg.clone(), true);
+                }
                 self.status_message = Some(msg);
-                if matches!(op, ContainerOperation::Adopting { .. }) {
-                    self.discover_mode = false;
+                match op {
+                    ContainerOperation::Starting { id, .. } | ContainerOperation::Up { id, .. } => {
+                        self.session_started.insert(id.clone());
+                    }
+                    ContainerOperation::Stopping { id, .. }
+                    | ContainerOperation::Deleting { id, .. } => {
+                        self.session_started.remove(id);
+                    }
+                    ContainerOperation::Adopting { .. } => {
+                        self.discover_mode = false;
+                    }
+                    ContainerOperation::Pausing { .. }
+                    | ContainerOperation::Unpausing { .. }
+                    | ContainerOperation::Forgetting { .. }
+                    | ContainerOperation::RemovingImage { .. }
+                    | ContainerOperation::Syncing { .. } => {}
                 }
             }
             ContainerOpResult::Failed(op, err) => {
@@ -3061,6 +3974,12 @@ impl App {
                     ContainerOperation::Stopping { name, .. } => {
                         format!("Stop failed for {}: {}", name, err)
                     }
+                    ContainerOperation::Pausing { name, .. } => {
+                        format!("Pause failed for {}: {}", name, err)
+                    }
+                    ContainerOperation::Unpausing { name, .. } => {
+                        format!("Unpause failed for {}: {}", name, err)
+                    }
                     ContainerOperation::Deleting { name, .. } => {
                         format!("Delete failed for {}: {}", name, err)
                     }
@@ -3073,13 +3992,35 @@ impl App {
                     ContainerOperation::Forgetting { name, .. } => {
                         format!("Forget failed for {}: {}", name, err)
                     }
+                    ContainerOperation::RemovingImage { name, .. } => {
+                        format!("Removing previous image failed for {}: {}", name, err)
+                    }
+                    ContainerOperation::Syncing { name, .. } => {
+                        format!("Status check failed for {}: {}", name, err)
+                    }
                 };
+                self.toasts.push(msg.clone(), false);
                 self.status_message = Some(msg);
             }
         }
 
         self.loading = false;
-        self.refresh_containers().await?;
+        if let Some((id, name, previous)) = synced {
+            self.reload_containers().await?;
+            let current = self
+                .containers
+                .iter()
+                .find(|c| c.id == id)
+                .map(|c| c.status)
+                .unwrap_or(previous);
+            self.status_message = Some(if current == previous {
+                format!("{}: {} (unchanged)", name, current)
+            } else {
+                format!("{}: {} -> {}", name, previous, current)
+            });
+        } else {
+            self.refresh_containers().await?;
+        }
 
         // Invalidate cached compose services so status gets refreshed
         if let Some(id) = affected_id {
@@ -3087,7 +4028,7 @@ impl App {
         }
         // Re-fetch if still in detail view
         if self.view == View::ContainerDetail {
-            self.fetch_compose_services().await;
+            self.fetch_compose_services();
         }
 
         Ok(())
@@ -3096,6 +4037,10 @@ impl App {
     /// Enter shell mode for a container
     #[cfg(unix)]
     async fn enter_shell_mode(&mut self, container: &ContainerState) -> AppResult<()> {
+        if container.status == DevcContainerStatus::Paused {
+            self.status_message = Some("Container is paused (z to resume)".to_string());
+            return Ok(());
+        }
         if container.status != DevcContainerStatus::Running {
             self.status_message = Some("Container must be running to open shell".to_string());
             return Ok(());
@@ -3199,9 +4144,11 @@ impl App {
         } else if let Some(user) = container.metadata.get("remote_user") {
             Some(user.clone())
         } else {
-            parsed
-                .as_ref()
-                .and_then(|c| c.devcontainer.effective_user().map(|s| s.to_string()))
+            parsed.as_ref().and_then(|c| {
+                c.devcontainer
+                    .effective_remote_user()
+                    .map(|s| s.to_string())
+            })
         };
         let effective_working_dir =
             container
@@ -3228,6 +4175,7 @@ impl App {
                 env: shell_env,
                 host_workspace_path,
                 pty: None,
+                exit_reason: None,
             },
         );
 
@@ -3263,6 +4211,65 @@ impl App {
         Ok(())
     }
 
+    /// Enter shell mode for a companion compose service of `container`.
+    ///
+    /// Companion services run arbitrary images, so the shell uses the image's
+    /// default user and working directory and skips devcontainer-specific setup
+    /// (credentials, remoteEnv, postAttachCommand).
+    #[cfg(unix)]
+    async fn enter_service_shell_mode(
+        &mut self,
+        container: &ContainerState,
+        svc: devc_provider::ComposeServiceInfo,
+    ) -> AppResult<()> {
+        if svc.status != devc_provider::ContainerStatus::Running {
+            self.status_message = Some(format!(
+                "Service '{}' is not running ({})",
+                svc.service_name, svc.status
+            ));
+            return Ok(());
+        }
+
+        let session_key = format!("{}/{}", container.id, svc.service_name);
+
+        if let Some(session) = self.shell_state.shell_sessions.get_mut(&session_key) {
+            if session.pty.as_mut().is_some_and(|p| p.is_alive()) {
+                self.shell_state.active_shell_container = Some(session_key);
+                self.view = View::Shell;
+                return Ok(());
+            }
+            self.shell_state.shell_sessions.remove(&session_key);
+        }
+
+        let (rt_program, rt_prefix) = self
+            .manager
+            .read()
+            .await
+            .runtime_args_for(container)
+            .unwrap_or_else(|_| (container.provider.to_string(), vec![]));
+
+        self.shell_state.shell_sessions.insert(
+            session_key.clone(),
+            ShellSession {
+                container_id: container.id.clone(),
+                container_name: format!("{}/{}", container.name, svc.service_name),
+                provider_container_id: svc.container_id.0,
+                runtime_program: rt_program,
+                runtime_prefix: rt_prefix,
+                user: None,
+                working_dir: None,
+                env: std::collections::HashMap::new(),
+                host_workspace_path: None,
+                pty: None,
+                exit_reason: None,
+            },
+        );
+
+        self.shell_state.active_shell_container = Some(session_key);
+        self.view = View::Shell;
+        Ok(())
+    }
+
     #[cfg(unix)]
     #[allow(clippy::too_many_arguments)]
     fn make_shell_config(
@@ -3275,6 +4282,7 @@ impl App {
         env: std::collections::HashMap<String, String>,
         browser_queue_path: Option<String>,
     ) -> ShellConfig {
+        let bind_addresses = self.port_state.bind_addresses(&container_id);
         ShellConfig {
             runtime_program,
             runtime_prefix,
@@ -3285,6 +4293,7 @@ impl App {
             env,
             browser_queue_path,
             auto_forward_enabled: self.config.defaults.auto_forward_ports != Some(false),
+            bind_addresses,
         }
     }
 
@@ -3506,16 +4515,17 @@ impl App {
                         container_name
                     ));
                 }
-                ShellExitReason::Exited => {
-                    // Shell exited - clean up session
+                reason @ (ShellExitReason::Exited | ShellExitReason::Error(_)) => {
+                    // Keep the session without its PTY so the user can see why
+                    // it ended and choose to restart or close it
                     drop(pty);
-                    self.shell_state.shell_sessions.remove(&container_id);
-                    self.status_message = Some("Shell exited".to_string());
-                }
-                ShellExitReason::Error(e) => {
-                    drop(pty);
-                    self.shell_state.shell_sessions.remove(&container_id);
-                    self.status_message = Some(format!("Shell error: {}", e));
+                    self.status_message = Some(format!(
+                        "Shell for '{}' {} (Ctrl+R: restart, x: close)",
+                        container_name, reason
+                    ));
+                    if let Some(session) = self.shell_state.shell_sessions.get_mut(&container_id) {
+                        session.exit_reason = Some(reason);
+                    }
                 }
             },
             Err(e) => {
@@ -3553,20 +4563,100 @@ impl App {
         Ok(())
     }
 
-    /// Refresh container list
-    async fn refresh_containers(&mut self) -> AppResult<()> {
-        // Capture the selected container's ID BEFORE replacing the list,
-        // since self.selected is an index into the current (sorted) list.
-        let prev_selected_id = self.containers.get(self.selected).map(|c| c.id.clone());
+    /// Queue the selected container's devcontainer.json for editing
+    fn request_config_edit(&mut self) {
+        let Some(container) = self.containers.get(self.selected) else {
+            return;
+        };
+        if !container.config_path.is_file() {
+            self.status_message = Some(format!(
+                "'{}' has no devcontainer.json to edit",
+                container.name
+            ));
+            return;
+        }
+        self.pending_config_edit = Some(container.id.clone());
+    }
 
-        self.containers = self.manager.read().await.list().await?;
+    /// Open a container's devcontainer.json in the configured editor with the
+    /// TUI suspended, then offer a rebuild if a built container's config changed
+    async fn run_config_editor<B: Backend + std::io::Write>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        events: &mut Option<EventHandler>,
+        id: &str,
+    ) -> AppResult<()> {
+        let Some(container) = self.containers.iter().find(|c| c.id == id).cloned() else {
+            return Ok(());
+        };
+        let before = std::fs::read(&container.config_path).ok();
+        let editor = devc_config::editor(self.config.defaults.editor.as_deref());
+
+        // Same kill/recreate of the event handler as shell mode, so the
+        // editor gets every keystroke
+        if let Some(mut handler) = events.take() {
+            handler.stop();
+        }
+        suspend_tui(terminal.backend_mut())?;
+        let status = devc_config::open_in_editor(&editor.command, &container.config_path);
+        resume_tui(terminal.backend_mut())?;
+        *events = Some(EventHandler::new(Duration::from_millis(250)));
+        terminal.clear()?;
+
+        match status {
+            Err(e) => {
+                self.status_message =
+                    Some(format!("Failed to open editor '{}': {}", editor.command, e));
+                return Ok(());
+            }
+            Ok(status) if !status.success() => {
+                self.status_message = Some(format!("Editor exited with {}", status));
+                return Ok(());
+            }
+            Ok(_) => {}
+        }
 
+        self.refresh_containers().await?;
+        let after = std::fs::read(&container.config_path).ok();
+        if before != after && self.view == View::ContainerDetail {
+            self.vscode_extensions = vscode_extensions_for_config(&container.config_path);
+        }
+        if before == after {
+            self.status_message = Some("devcontainer.json unchanged".to_string());
+        } else if container.image_id.is_some() {
+            self.status_message =
+                Some("devcontainer.json changed; rebuild to apply it".to_string());
+            self.start_rebuild_dialog().await;
+        } else {
+            self.status_message = Some("devcontainer.json saved".to_string());
+        }
+        if let Some(warning) = editor.warning {
+            let warning = format!("{}; used '{}'", warning, editor.command);
+            self.status_message = Some(match self.status_message.take() {
+                Some(status) => format!("{} ({})", status, warning),
+                None => warning,
+            });
+        }
+        Ok(())
+    }
+
+    /// Refresh container list
+    async fn refresh_containers(&mut self) -> AppResult<()> {
         // Sync status for all registered containers
-        for container in &self.containers {
+        let containers = self.manager.read().await.list().await?;
+        for container in &containers {
             let _ = self.manager.read().await.sync_status(&container.id).await;
         }
 
-        // Re-fetch after sync
+        self.reload_containers().await
+    }
+
+    /// Reload the container list from state without inspecting the runtime
+    async fn reload_containers(&mut self) -> AppResult<()> {
+        // Capture the selected container's ID BEFORE replacing the list,
+        // since self.selected is an index into the current (sorted) list.
+        let prev_selected_id = self.containers.get(self.selected).map(|c| c.id.clone());
+
         self.containers = self.manager.read().await.list().await?;
 
         // Append ephemeral Available entries for unregistered configs
@@ -3584,6 +4674,7 @@ impl App {
         }
 
         self.sort_and_preserve_selection(prev_selected_id);
+        self.missing_workspaces = missing_workspaces(&self.containers);
 
         // Invalidate stale compose_services entries for containers that no longer exist
         let container_ids: HashSet<String> = self.containers.iter().map(|c| c.id.clone()).collect();
@@ -3594,31 +4685,160 @@ impl App {
         Ok(())
     }
 
-    /// Sort container list by status/name and preserve the selected container by ID.
+    /// Stop containers left unused past `defaults.idle_stop_minutes`.
+    ///
+    /// Runs at most once per [`IDLE_CHECK_INTERVAL`]. Containers with a live
+    /// shell session or forwarded port count as in use; their `last_used` is
+    /// bumped once, when the last of those goes away, so the state file is
+    /// only written when something changed. Sessions the TUI doesn't own
+    /// (`devc shell` in another terminal, an attached editor) are checked in
+    /// the container before stopping it, and also bump `last_used`.
+    async fn stop_idle_containers(&mut self) {
+        let minutes = self.config.defaults.idle_stop_minutes.unwrap_or(0);
+        if minutes == 0
+            || !self.is_connected()
+            || self.last_idle_check.elapsed() < IDLE_CHECK_INTERVAL
+        {
+            return;
+        }
+        self.last_idle_check = std::time::Instant::now();
+
+        let active: HashSet<String> = self
+            .containers
+            .iter()
+            .filter(|c| {
+                self.shell_state
+                    .shell_sessions
+                    .values()
+                    .any(|s| s.container_id == c.id && s.exit_reason.is_none())
+                    || c.container_id.as_ref().is_some_and(|cid| {
+                        self.port_state
+                            .active_forwarders
+                            .keys()
+                            .any(|(fwd_cid, _, _)| fwd_cid == cid)
+                    })
+            })
+            .map(|c| c.id.clone())
+            .collect();
+        let previously_active = std::mem::replace(&mut self.idle_check_active, active);
+        {
+            let manager = self.manager.read().await;
+            for id in previously_active.difference(&self.idle_check_active) {
+                if let Err(e) = manager.touch(id).await {
+                    tracing::warn!("Failed to record activity for {}: {}", id, e);
+                }
+            }
+        }
+
+        // The container list still has the old `last_used` for the ones just
+        // touched, so they're kept out of this round as well
+        let in_use: HashSet<String> = previously_active
+            .union(&self.idle_check_active)
+            .cloned()
+            .collect();
+        let mut idle = idle_container_ids(
+            &self.containers,
+            &in_use,
+            chrono::Duration::minutes(i64::from(minutes)),
+            chrono::Utc::now(),
+        );
+        {
+            let manager = self.manager.read().await;
+            let mut attached = Vec::new();
+            for id in &idle {
+                match manager.has_exec_sessions(id).await {
+                    Ok(false) => {}
+                    Ok(true) => attached.push(id.clone()),
+                    // Can't tell, so leave it running
+                    Err(e) => {
+                        tracing::debug!("Failed to check sessions of {}: {}", id, e);
+                        attached.push(id.clone());
+                    }
+                }
+            }
+            for id in &attached {
+                if let Err(e) = manager.touch(id).await {
+                    tracing::warn!("Failed to record activity for {}: {}", id, e);
+                }
+            }
+            idle.retain(|id| !attached.contains(id));
+        }
+        if idle.is_empty() {
+            return;
+        }
+
+        let mut names = Vec::new();
+        for id in &idle {
+            self.shell_state.remove_container_sessions(id);
+            self.session_started.remove(id);
+            if let Some(c) = self.containers.iter().find(|c| &c.id == id) {
+                names.push(c.name.clone());
+            }
+        }
+        self.status_message = Some(format!("Stopping idle: {}", names.join(", ")));
+
+        let manager = Arc::clone(&self.manager);
+        tokio::spawn(async move {
+            for id in idle {
+                if let Err(e) = manager.read().await.stop(&id).await {
+                    tracing::warn!("Failed to stop idle container {}: {}", id, e);
+                }
+            }
+        });
+    }
+
+    /// Switch between short and full container/image IDs and persist the choice
+    async fn toggle_full_ids(&mut self) {
+        let full_ids = !self.full_ids;
+        self.full_ids = full_ids;
+        let label = if full_ids { "full" } else { "short" };
+        match GlobalConfig::update_atomically(|cfg| cfg.ui.full_ids = full_ids) {
+            Err(e) => {
+                self.status_message =
+                    Some(format!("Showing {} IDs (failed to save: {})", label, e));
+            }
+            Ok(updated) => {
+                self.config = updated;
+                self.sync_manager_config_from_app().await;
+                self.status_message = Some(format!("Showing {} IDs", label));
+            }
+        }
+    }
+
+    /// Switch grouping of the container list by workspace folder and persist the choice
+    async fn toggle_workspace_groups(&mut self) {
+        let group = !self.group_by_workspace;
+        self.group_by_workspace = group;
+        self.sort_and_preserve_selection(None);
+        let label = if group {
+            "Grouped by workspace"
+        } else {
+            "Ungrouped"
+        };
+        match GlobalConfig::update_atomically(|cfg| cfg.ui.group_by_workspace = group) {
+            Err(e) => {
+                self.status_message = Some(format!("{} (failed to save: {})", label, e));
+            }
+            Ok(updated) => {
+                self.config = updated;
+                self.sync_manager_config_from_app().await;
+                self.status_message = Some(label.to_string());
+            }
+        }
+    }
+
+    /// Sort container list by the current sort order and preserve the selected container by ID.
     ///
     /// Called after any operation that may change container order (refresh, reconnect, etc.)
     fn sort_and_preserve_selection(&mut self, prev_selected_id: Option<String>) {
         let prev_selected_id =
             prev_selected_id.or_else(|| self.containers.get(self.selected).map(|c| c.id.clone()));
 
-        self.containers.sort_by(|a, b| {
-            let status_ord = |s: DevcContainerStatus| -> u8 {
-                match s {
-                    DevcContainerStatus::Running => 0,
-                    DevcContainerStatus::Building => 1,
-                    DevcContainerStatus::Built => 2,
-                    DevcContainerStatus::Created => 3,
-                    DevcContainerStatus::Stopped => 4,
-                    DevcContainerStatus::Configured => 5,
-                    DevcContainerStatus::Failed => 6,
-                    DevcContainerStatus::Available => 7,
-                }
-            };
-            status_ord(a.status)
-                .cmp(&status_ord(b.status))
-                .then_with(|| a.name.cmp(&b.name))
-                .then_with(|| a.id.cmp(&b.id))
-        });
+        sort_containers(
+            &mut self.containers,
+            self.sort_order,
+            self.group_by_workspace,
+        );
 
         if let Some(prev_id) = prev_selected_id {
             if let Some(pos) = self.containers.iter().position(|c| c.id == prev_id) {
@@ -3670,13 +4890,17 @@ impl App {
         let name = container.name.clone();
 
         match container.status {
-            DevcContainerStatus::Running => {
+            DevcContainerStatus::Running | DevcContainerStatus::Paused => {
                 // Show confirmation dialog before stopping
                 self.dialog_focus = DialogFocus::Cancel;
                 self.confirm_action = Some(ConfirmAction::Stop(id));
                 self.view = View::Confirm;
             }
             DevcContainerStatus::Stopped | DevcContainerStatus::Created => {
+                if let Some(reason) = self.compose_unavailable_reason(container) {
+                    self.status_message = Some(reason);
+                    return Ok(());
+                }
                 let op = ContainerOperation::Starting {
                     id: id.clone(),
                     name,
@@ -3694,9 +4918,79 @@ impl App {
         Ok(())
     }
 
+    /// Re-check the selected container's status against the runtime
+    ///
+    /// Cheaper than a full refresh, which inspects every container; the
+    /// resulting status change is reported once the operation completes.
+    fn sync_selected_status(&mut self) {
+        if self.container_op.is_some() {
+            return;
+        }
+        let Some(container) = self.containers.get(self.selected) else {
+            return;
+        };
+        if container.status == DevcContainerStatus::Available {
+            self.status_message = Some("Container has not been created yet".to_string());
+            return;
+        }
+
+        let id = container.id.clone();
+        let op = ContainerOperation::Syncing {
+            id: id.clone(),
+            name: container.name.clone(),
+            previous: container.status,
+        };
+        self.spawn_container_op(op, false, |mgr, _, _| async move {
+            mgr.read().await.sync_status(&id).await?;
+            Ok(())
+        });
+    }
+
+    /// Pause the selected running container, or resume it if paused
+    async fn toggle_pause_selected(&mut self) -> AppResult<()> {
+        if self.containers.is_empty() || self.container_op.is_some() {
+            return Ok(());
+        }
+
+        let container = &self.containers[self.selected];
+        let id = container.id.clone();
+        let name = container.name.clone();
+
+        match container.status {
+            DevcContainerStatus::Running => {
+                let op = ContainerOperation::Pausing {
+                    id: id.clone(),
+                    name,
+                };
+                self.spawn_container_op(op, false, |mgr, _, _| async move {
+                    mgr.read().await.pause(&id).await?;
+                    Ok(())
+                });
+            }
+            DevcContainerStatus::Paused => {
+                let op = ContainerOperation::Unpausing {
+                    id: id.clone(),
+                    name,
+                };
+                self.spawn_container_op(op, false, |mgr, _, _| async move {
+                    mgr.read().await.unpause(&id).await?;
+                    Ok(())
+                });
+            }
+            _ => {
+                self.status_message = Some("Only running containers can be paused".to_string());
+            }
+        }
+
+        Ok(())
+    }
+
     /// Run full up (build, create, start) for selected container
     async fn up_selected(&mut self) -> AppResult<()> {
-        if self.containers.is_empty() || self.container_op.is_some() {
+        if self.container_op.is_some()
+            || self.cancelled_build_pending()
+            || self.containers.is_empty()
+        {
             return Ok(());
         }
 
@@ -3738,6 +5032,10 @@ impl App {
         }
 
         let container = &self.containers[self.selected];
+        if let Some(reason) = self.compose_unavailable_reason(container) {
+            self.status_message = Some(reason);
+            return Ok(());
+        }
         let id = container.id.clone();
         let name = container.name.clone();
 
@@ -3745,6 +5043,7 @@ impl App {
             id: id.clone(),
             name: name.clone(),
             progress: format!("Starting up {}...", name),
+            finalizing: false,
         };
 
         // Create a separate channel for Docker build output (shown in BuildOutput
@@ -3785,28 +5084,9 @@ impl App {
         let container = &self.containers[self.selected];
 
         // Check if we should fetch logs for a companion service
-        let companion = if self.view == View::ContainerDetail {
-            let container_id = container.id.clone();
-            self.compose_state
-                .services
-                .get(&container_id)
-                .and_then(|services| {
-                    services
-                        .get(self.compose_state.selected_service)
-                        .and_then(|svc| {
-                            // If this is the primary service, use normal log path
-                            let is_primary =
-                                container.compose_service.as_deref() == Some(&svc.service_name);
-                            if is_primary {
-                                None
-                            } else {
-                                Some((svc.container_id.clone(), svc.service_name.clone()))
-                            }
-                        })
-                })
-        } else {
-            None
-        };
+        let companion = self
+            .selected_companion_service()
+            .map(|svc| (svc.container_id, svc.service_name));
 
         if let Some((svc_container_id, svc_name)) = companion {
             // Fetch logs directly from the provider for the companion service
@@ -3819,10 +5099,11 @@ impl App {
                         follow: false,
                         stdout: true,
                         stderr: true,
-                        tail: Some(1000),
-                        timestamps: false,
+                        tail: Some(devc_core::DEFAULT_LOG_TAIL),
+                        timestamps: self.log_timestamps,
                         since: None,
                         until: None,
+                        demux: false,
                     };
                     match provider.logs(&svc_container_id, &log_config).await {
                         Ok(log_stream) => {
@@ -3867,7 +5148,11 @@ impl App {
             .manager
             .read()
             .await
-            .logs(&container.id, Some(1000))
+            .logs_with_timestamps(
+                &container.id,
+                Some(devc_core::DEFAULT_LOG_TAIL),
+                self.log_timestamps,
+            )
             .await
         {
             Ok(lines) => {
@@ -3892,8 +5177,8 @@ impl App {
                 if self.container_op.is_some() {
                     return Ok(());
                 }
-                // Clean up any shell session for this container
-                self.shell_state.shell_sessions.remove(&id);
+                // Clean up any shell sessions for this container (incl. compose services)
+                self.shell_state.remove_container_sessions(&id);
 
                 let name = self
                     .containers
@@ -3907,7 +5192,7 @@ impl App {
                     name,
                 };
                 self.spawn_container_op(op, false, |mgr, _, _| async move {
-                    mgr.read().await.remove(&id, true).await?;
+                    mgr.read().await.remove(&id, true, false).await?;
                     Ok(())
                 });
             }
@@ -3915,8 +5200,8 @@ impl App {
                 if self.container_op.is_some() {
                     return Ok(());
                 }
-                // Clean up any shell session for this container
-                self.shell_state.shell_sessions.remove(&id);
+                // Clean up any shell sessions for this container (incl. compose services)
+                self.shell_state.remove_container_sessions(&id);
 
                 let name = self
                     .containers
@@ -3935,6 +5220,10 @@ impl App {
                 });
             }
             ConfirmAction::Rebuild { id, .. } => {
+                // The old sessions point at the container being replaced
+                self.shell_state.remove_container_sessions(&id);
+                self.attach_after_rebuild = self.rebuild_attach.then(|| id.clone());
+                self.rebuild_attach = false;
                 self.loading = true;
                 self.view = View::BuildOutput;
                 self.build_output.clear();
@@ -3943,6 +5232,7 @@ impl App {
                 self.build_complete = false;
                 self.current_build_stage = None;
                 self.last_stage_marker = None;
+                self.build_failed_line = None;
 
                 let event_tx = self.async_event_tx.clone();
                 let (progress_tx, mut progress_rx) = mpsc::unbounded_channel();
@@ -3981,11 +5271,17 @@ impl App {
                         .await
                     {
                         Ok(()) => {
-                            let _ = done_tx.send(AsyncEvent::BuildFinished { success: true });
+                            let _ = done_tx.send(AsyncEvent::BuildFinished {
+                                success: true,
+                                failed_step: None,
+                            });
                         }
                         Err(e) => {
                             let _ = progress_tx.send(format!("Error: Rebuild failed: {}", e));
-                            let _ = done_tx.send(AsyncEvent::BuildFinished { success: false });
+                            let _ = done_tx.send(AsyncEvent::BuildFinished {
+                                success: false,
+                                failed_step: failed_build_step(&e),
+                            });
                         }
                     }
                 });
@@ -4027,6 +5323,7 @@ impl App {
                 self.build_complete = false;
                 self.current_build_stage = None;
                 self.last_stage_marker = None;
+                self.build_failed_line = None;
 
                 let event_tx = self.async_event_tx.clone();
                 let (progress_tx, mut progress_rx) = mpsc::unbounded_channel();
@@ -4060,11 +5357,17 @@ impl App {
                         .await
                     {
                         Ok(()) => {
-                            let _ = done_tx.send(AsyncEvent::BuildFinished { success: true });
+                            let _ = done_tx.send(AsyncEvent::BuildFinished {
+                                success: true,
+                                failed_step: None,
+                            });
                         }
                         Err(e) => {
                             let _ = progress_tx.send(format!("Error: Build failed: {}", e));
-                            let _ = done_tx.send(AsyncEvent::BuildFinished { success: false });
+                            let _ = done_tx.send(AsyncEvent::BuildFinished {
+                                success: false,
+                                failed_step: failed_build_step(&e),
+                            });
                         }
                     }
                 });
@@ -4150,8 +5453,25 @@ impl App {
                     Ok(())
                 });
             }
+            ConfirmAction::RemovePreviousImage { id, name, image } => {
+                if self.container_op.is_some() {
+                    return Ok(());
+                }
+                let op = ContainerOperation::RemovingImage {
+                    id: id.clone(),
+                    name,
+                    image,
+                };
+                self.spawn_container_op(op, false, |mgr, _, _| async move {
+                    mgr.read().await.remove_previous_image(&id).await?;
+                    Ok(())
+                });
+            }
             ConfirmAction::CancelBuild => {
-                // Cancel the in-progress build and return to main view
+                // Leave the build running in the background but drop whatever
+                // it reports, so a rebuild that later succeeds opens no shell
+                self.attach_after_rebuild = None;
+                self.build_cancelled = true;
                 self.loading = false;
                 self.build_complete = false;
                 self.build_output.clear();
@@ -4159,17 +5479,64 @@ impl App {
                 self.build_auto_scroll = true;
                 self.current_build_stage = None;
                 self.last_stage_marker = None;
+                self.build_failed_line = None;
                 self.view = View::Main;
                 self.status_message = Some("Build cancelled".to_string());
                 self.refresh_containers().await?;
             }
             ConfirmAction::QuitApp => {
+                self.run_shutdown_actions().await;
                 self.should_quit = true;
             }
         }
         Ok(())
     }
 
+    /// Quit the app, applying `shutdownAction` to containers started this session.
+    ///
+    /// Shows the quit dialog when `confirm` is set, or when containers would be
+    /// stopped and `confirm_shutdown` is not disabled; otherwise quits directly.
+    async fn request_quit(&mut self, confirm: bool) {
+        self.shutdown_on_quit = self.pending_shutdowns().await;
+        let confirm_shutdown = !self.shutdown_on_quit.is_empty()
+            && self.config.defaults.confirm_shutdown != Some(false);
+        if confirm || confirm_shutdown {
+            self.confirm_action = Some(ConfirmAction::QuitApp);
+            self.view = View::Confirm;
+        } else {
+            self.run_shutdown_actions().await;
+            self.should_quit = true;
+        }
+    }
+
+    /// Running containers started this session whose config sets a `shutdownAction`
+    async fn pending_shutdowns(&self) -> Vec<(String, String)> {
+        let manager = self.manager.read().await;
+        self.containers
+            .iter()
+            .filter(|c| {
+                c.status == DevcContainerStatus::Running && self.session_started.contains(&c.id)
+            })
+            .filter(|c| {
+                manager
+                    .get_devcontainer_config(c)
+                    .map(|cfg| cfg.effective_shutdown_action() != ShutdownAction::None)
+                    .unwrap_or(false)
+            })
+            .map(|c| (c.id.clone(), c.name.clone()))
+            .collect()
+    }
+
+    async fn run_shutdown_actions(&mut self) {
+        let targets = std::mem::take(&mut self.shutdown_on_quit);
+        let manager = self.manager.read().await;
+        for (id, name) in targets {
+            if let Err(e) = manager.shutdown(&id).await {
+                tracing::warn!("shutdownAction failed for {}: {}", name, e);
+            }
+        }
+    }
+
     /// Push the current app config into the live manager snapshot.
     async fn sync_manager_config_from_app(&self) {
         let mut manager = self.manager.write().await;
@@ -4181,6 +5548,44 @@ impl App {
         self.containers.get(self.selected)
     }
 
+    /// Whether a container may be dropped from tracking.
+    ///
+    /// devc-created containers are normally removed instead, but once their
+    /// workspace is gone there is no config left to remove them with.
+    pub fn can_forget(&self, container: &ContainerState) -> bool {
+        !container.status.is_available()
+            && (container.source != DevcontainerSource::Devc
+                || self.missing_workspaces.contains(&container.id))
+    }
+
+    /// The keybinding context for the current view and tab.
+    ///
+    /// While the help view is open this reports the view it was opened from,
+    /// so help lists the bindings of the screen underneath it.
+    pub fn key_context(&self) -> KeyContext {
+        let view = if self.view == View::Help {
+            self.help_return_view
+        } else {
+            self.view
+        };
+        match view {
+            View::ContainerDetail => KeyContext::ContainerDetail,
+            View::ProviderDetail => KeyContext::ProviderDetail,
+            View::BuildOutput => KeyContext::BuildOutput,
+            View::Logs => KeyContext::Logs,
+            View::ConfigView => KeyContext::ConfigView,
+            View::Ports => KeyContext::Ports,
+            View::DiscoverDetail => KeyContext::DiscoverDetail,
+            View::AgentDiagnostics => KeyContext::AgentDiagnostics,
+            View::Main | View::Help | View::Confirm | View::Shell => match self.tab {
+                Tab::Containers if self.discover_mode => KeyContext::Discover,
+                Tab::Containers => KeyContext::Containers,
+                Tab::Providers => KeyContext::Providers,
+                Tab::Settings => KeyContext::Settings,
+            },
+        }
+    }
+
     /// Check if the current view is a popup overlay
     fn is_popup_view(&self) -> bool {
         matches!(
@@ -4191,6 +5596,7 @@ impl App {
                 | View::Logs
                 | View::DiscoverDetail
                 | View::AgentDiagnostics
+                | View::ConfigView
         )
     }
 
@@ -4200,6 +5606,15 @@ impl App {
             View::ContainerDetail => {
                 self.compose_state.reset_detail();
                 self.container_detail = None;
+                self.container_disk_usage = None;
+                self.container_detail_scroll = 0;
+            }
+            View::ConfigView => {
+                self.config_view_lines.clear();
+                self.config_view_scroll = 0;
+                self.compose_state.reset_detail();
+                self.container_detail = None;
+                self.container_disk_usage = None;
                 self.container_detail_scroll = 0;
             }
             View::Logs => {
@@ -4231,7 +5646,13 @@ impl App {
 
         // Container detail
         self.container_detail = None;
+        self.container_disk_usage = None;
         self.container_detail_scroll = 0;
+        self.vscode_extensions.clear();
+
+        // Config viewer
+        self.config_view_lines.clear();
+        self.config_view_scroll = 0;
 
         // Compose (reset service selection + logs service name)
         self.compose_state.reset_detail();
@@ -4256,6 +5677,7 @@ impl App {
             self.build_complete = false;
             self.current_build_stage = None;
             self.last_stage_marker = None;
+            self.build_failed_line = None;
         }
 
         // Up output (only if no active container operation)
@@ -4299,6 +5721,9 @@ impl App {
                 // Update app state
                 self.active_provider = Some(provider_type);
                 self.connection_error = None;
+                // The new connection may support a different set of features
+                self.provider_capabilities.remove(&provider_type);
+                self.probe_provider_capabilities();
 
                 // Update provider status
                 for p in &mut self.providers {
@@ -4321,6 +5746,106 @@ impl App {
     }
 }
 
+/// How often the idle auto-stop check runs
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How often the compose services table re-runs `compose ps` while shown
+const COMPOSE_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Shown once per port detector when the web-port probe can't run
+const SOCAT_MISSING_FOR_PROBE: &str = "socat not found in container; web ports won't be labelled";
+
+/// Running devc-created containers not used within `idle_for`, excluding `active` ones.
+/// Adopted containers belong to another tool and are left alone.
+fn idle_container_ids(
+    containers: &[ContainerState],
+    active: &HashSet<String>,
+    idle_for: chrono::Duration,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Vec<String> {
+    containers
+        .iter()
+        .filter(|c| {
+            c.status == DevcContainerStatus::Running
+                && c.source == DevcontainerSource::Devc
+                && !active.contains(&c.id)
+                && now - c.last_used >= idle_for
+        })
+        .map(|c| c.id.clone())
+        .collect()
+}
+
+/// `customizations.vscode.extensions` from the container's config, if it still parses
+fn vscode_extensions_for_config(config_path: &Path) -> Vec<String> {
+    devc_config::DevContainerConfig::load_from(config_path)
+        .map(|config| config.vscode_extensions().to_vec())
+        .unwrap_or_default()
+}
+
+/// Collect tracked containers whose workspace was moved or deleted.
+/// Checked on refresh rather than per frame to keep rendering off the filesystem.
+fn missing_workspaces(containers: &[ContainerState]) -> HashSet<String> {
+    containers
+        .iter()
+        .filter(|c| !c.status.is_available() && c.workspace_missing())
+        .map(|c| c.id.clone())
+        .collect()
+}
+
+/// Order the container list for display.
+///
+/// Unregistered (Available) configs have never been used, so `Recent` keeps
+/// them at the bottom. Ties fall back to name, then ID, for a stable order.
+/// With `group_by_workspace`, containers sharing a workspace folder are kept
+/// together; groups appear in the order of their first member.
+fn sort_containers(containers: &mut [ContainerState], order: SortOrder, group_by_workspace: bool) {
+    let status_ord = |s: DevcContainerStatus| -> u8 {
+        match s {
+            DevcContainerStatus::Running => 0,
+            DevcContainerStatus::Paused => 1,
+            DevcContainerStatus::Building => 2,
+            DevcContainerStatus::Built => 3,
+            DevcContainerStatus::Created => 4,
+            DevcContainerStatus::Stopped => 5,
+            DevcContainerStatus::Configured => 6,
+            DevcContainerStatus::Failed => 7,
+            DevcContainerStatus::Available => 8,
+        }
+    };
+    containers.sort_by(|a, b| {
+        let primary = match order {
+            SortOrder::Status => status_ord(a.status).cmp(&status_ord(b.status)),
+            SortOrder::Name => std::cmp::Ordering::Equal,
+            SortOrder::Recent => a
+                .status
+                .is_available()
+                .cmp(&b.status.is_available())
+                .then_with(|| b.last_used.cmp(&a.last_used)),
+        };
+        primary
+            .then_with(|| a.name.cmp(&b.name))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+
+    if group_by_workspace {
+        let mut rank: HashMap<PathBuf, usize> = HashMap::new();
+        for c in containers.iter() {
+            let next = rank.len();
+            rank.entry(c.workspace_path.clone()).or_insert(next);
+        }
+        containers.sort_by_key(|c| rank[&c.workspace_path]);
+    }
+}
+
+/// Next ordering when cycling with the sort key
+fn next_sort_order(order: SortOrder) -> SortOrder {
+    match order {
+        SortOrder::Status => SortOrder::Recent,
+        SortOrder::Recent => SortOrder::Name,
+        SortOrder::Name => SortOrder::Status,
+    }
+}
+
 /// Build an ephemeral ContainerState for an unregistered config.
 /// Uses a deterministic ID derived from the config path so it stays
 /// stable across refreshes.
@@ -4354,11 +5879,130 @@ fn make_available_entry(
     }
 }
 
+/// The build step named by a failed build's error, if the runtime reported one
+fn failed_build_step(err: &devc_core::CoreError) -> Option<String> {
+    match err {
+        devc_core::CoreError::Provider(devc_provider::ProviderError::BuildFailed {
+            step, ..
+        }) => Some(step.clone()),
+        _ => None,
+    }
+}
+
+/// Pretty-print a parsed devcontainer config as JSON lines, omitting unset fields.
+fn config_json_lines(config: &devc_config::DevContainerConfig) -> Vec<String> {
+    let mut value = serde_json::to_value(config).unwrap_or(serde_json::Value::Null);
+    strip_json_nulls(&mut value);
+    serde_json::to_string_pretty(&value)
+        .unwrap_or_default()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+/// Recursively drop `null` object entries (unset `Option` fields serialize as null).
+fn strip_json_nulls(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            for v in map.values_mut() {
+                strip_json_nulls(v);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for v in items {
+                strip_json_nulls(v);
+            }
+        }
+        _ => {}
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use devc_provider::{ComposeServiceInfo, ContainerId, ContainerStatus};
 
+    #[test]
+    fn test_idle_container_ids_respects_threshold_and_activity() {
+        let running = App::create_test_container("busy", DevcContainerStatus::Running);
+        let idle = App::create_test_container("idle", DevcContainerStatus::Running);
+        let stopped = App::create_test_container("stopped", DevcContainerStatus::Stopped);
+        let mut adopted = App::create_test_container("vscode", DevcContainerStatus::Running);
+        adopted.source = DevcontainerSource::VsCode;
+        let now = idle.last_used + chrono::Duration::minutes(30);
+        let containers = vec![running.clone(), idle.clone(), stopped, adopted];
+        let active: HashSet<String> = [running.id.clone()].into_iter().collect();
+
+        let ids = idle_container_ids(&containers, &active, chrono::Duration::minutes(30), now);
+        assert_eq!(ids, vec![idle.id.clone()]);
+
+        let ids = idle_container_ids(&containers, &active, chrono::Duration::minutes(31), now);
+        assert!(ids.is_empty());
+    }
+
+    /// A container with a session the TUI doesn't own (`devc shell` in
+    /// another terminal) is not stopped; one with none is
+    #[tokio::test]
+    async fn test_stop_idle_containers_skips_cli_sessions() {
+        use devc_core::test_support::MockProvider;
+        use devc_core::StateStore;
+        use devc_provider::ProviderType;
+
+        for (exec_output, expect_stop) in [("4321\n", false), ("", true)] {
+            let mut app = App::new_for_testing();
+            app.active_provider = Some(ProviderType::Docker);
+            app.config.defaults.idle_stop_minutes = Some(30);
+            app.last_idle_check = std::time::Instant::now() - IDLE_CHECK_INTERVAL;
+            let container = App::create_test_container("cli", DevcContainerStatus::Running);
+            let mut store = StateStore::new();
+            store.add(container.clone());
+            app.containers.push(container);
+
+            let mock = MockProvider::new(ProviderType::Docker);
+            *mock.exec_output.lock().unwrap() = exec_output.to_string();
+            let calls = mock.calls.clone();
+            app.manager = Arc::new(RwLock::new(ContainerManager::new_for_testing(
+                Box::new(mock),
+                GlobalConfig::default(),
+                store,
+            )));
+
+            app.stop_idle_containers().await;
+            assert!(calls
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|c| matches!(c, devc_core::test_support::MockCall::Exec { .. })));
+            let stopping = app
+                .status_message
+                .as_deref()
+                .is_some_and(|m| m.starts_with("Stopping idle: cli"));
+            assert_eq!(stopping, expect_stop, "exec output {:?}", exec_output);
+        }
+    }
+
+    #[test]
+    fn test_vscode_extensions_for_config() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config_path = tmp.path().join("devcontainer.json");
+        std::fs::write(
+            &config_path,
+            r#"{
+                // extensions for VS Code users
+                "image": "ubuntu",
+                "customizations": {"vscode": {"extensions": ["ms-python.python"]}}
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            vscode_extensions_for_config(&config_path),
+            vec!["ms-python.python".to_string()]
+        );
+        assert!(vscode_extensions_for_config(&tmp.path().join("missing.json")).is_empty());
+    }
+
     #[test]
     fn test_compose_service_selection_forward_wraps() {
         let mut app = App::new_for_testing();
@@ -4466,6 +6110,43 @@ mod tests {
         assert_eq!(app.compose_state.selected_service, 0);
     }
 
+    #[tokio::test]
+    async fn test_compose_services_refresh_result_updates_table() {
+        let mut app = App::new_for_testing();
+        let container = App::create_test_compose_container(
+            "myapp",
+            DevcContainerStatus::Running,
+            "proj",
+            "app",
+        );
+        let cid = container.id.clone();
+        app.containers.push(container);
+        app.selected = 0;
+        app.compose_state.services_loading = true;
+        app.compose_state.selected_service = 2;
+
+        let services = vec![ComposeServiceInfo {
+            service_name: "app".to_string(),
+            container_id: ContainerId::new("container-myapp"),
+            status: ContainerStatus::Exited,
+        }];
+        app.handle_compose_services_complete(cid.clone(), Ok(services))
+            .await
+            .unwrap();
+        assert!(!app.compose_state.services_loading);
+        assert_eq!(app.compose_state.selected_service, 0);
+        assert_eq!(app.compose_state.services[&cid].len(), 1);
+
+        // A failed refresh keeps the last known statuses
+        app.handle_compose_services_complete(cid.clone(), Err("boom".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(
+            app.compose_state.services[&cid][0].status,
+            ContainerStatus::Exited
+        );
+    }
+
     #[tokio::test]
     async fn test_sync_manager_config_from_app_updates_live_manager_config() {
         let mut app = App::new_for_testing();
@@ -4508,6 +6189,38 @@ mod tests {
         assert_eq!(app.containers_table_state.selected(), Some(1));
     }
 
+    #[test]
+    fn test_sort_containers_recent_keeps_available_last() {
+        let mut old = App::create_test_container("old", DevcContainerStatus::Stopped);
+        let mut new = App::create_test_container("new", DevcContainerStatus::Stopped);
+        let mut avail = App::create_test_container("avail", DevcContainerStatus::Available);
+        old.last_used -= chrono::Duration::days(1);
+        new.last_used += chrono::Duration::days(1);
+        avail.last_used += chrono::Duration::days(2);
+        let mut containers = vec![avail, old, new];
+
+        sort_containers(&mut containers, SortOrder::Recent, false);
+
+        let names: Vec<&str> = containers.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["new", "old", "avail"]);
+    }
+
+    #[test]
+    fn test_sort_containers_groups_by_workspace() {
+        let mut a_running = App::create_test_container("a-web", DevcContainerStatus::Running);
+        let mut b_running = App::create_test_container("b-api", DevcContainerStatus::Running);
+        let mut a_stopped = App::create_test_container("a-db", DevcContainerStatus::Stopped);
+        a_running.workspace_path = PathBuf::from("/work/a");
+        a_stopped.workspace_path = PathBuf::from("/work/a");
+        b_running.workspace_path = PathBuf::from("/work/b");
+        let mut containers = vec![a_stopped, b_running, a_running];
+
+        sort_containers(&mut containers, SortOrder::Status, true);
+
+        let names: Vec<&str> = containers.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["a-web", "a-db", "b-api"]);
+    }
+
     #[test]
     fn test_sort_and_preserve_selection_after_status_change() {
         let mut app = App::new_for_testing();
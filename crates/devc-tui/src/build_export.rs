@@ -0,0 +1,112 @@
+//! Plain-text export of the build output log for bug reports
+
+use std::path::{Path, PathBuf};
+
+/// Clipboard tools struggle with very large payloads, so copies keep only the
+/// tail of the log, which is where a failure is
+const MAX_CLIPBOARD_BYTES: usize = 512 * 1024;
+
+/// The log as plain text: ANSI escapes and carriage returns removed
+pub(crate) fn plain_text(lines: &[String]) -> String {
+    lines
+        .iter()
+        .map(|line| strip_ansi(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Plain text for the clipboard, and how many leading lines were dropped to fit
+pub(crate) fn clipboard_text(lines: &[String]) -> (String, usize) {
+    let mut size = 0;
+    let mut kept = 0;
+    for line in lines.iter().rev() {
+        size += strip_ansi(line).len() + 1;
+        if size > MAX_CLIPBOARD_BYTES {
+            break;
+        }
+        kept += 1;
+    }
+    let dropped = lines.len() - kept;
+    (plain_text(&lines[dropped..]), dropped)
+}
+
+/// Write the log to `dir/build-<name>-<timestamp>.log`
+pub(crate) fn write_log_file(dir: &Path, name: &str, lines: &[String]) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let path = dir.join(format!("build-{}-{}.log", name, timestamp));
+    std::fs::write(&path, plain_text(lines) + "\n")?;
+    Ok(path)
+}
+
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => match chars.next() {
+                // CSI: parameters up to a final byte in '@'..='~'
+                Some('[') => {
+                    for c in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+                // OSC: up to BEL or ESC \
+                Some(']') => {
+                    while let Some(c) = chars.next() {
+                        if c == '\x07' {
+                            break;
+                        }
+                        if c == '\x1b' && chars.peek() == Some(&'\\') {
+                            chars.next();
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(strip_ansi("\x1b[1;31merror\x1b[0m: boom"), "error: boom");
+        assert_eq!(strip_ansi("\x1b]0;title\x07done\r"), "done");
+        assert_eq!(strip_ansi("\x1b]8;;http://x\x1b\\link"), "link");
+        assert_eq!(strip_ansi("plain"), "plain");
+    }
+
+    #[test]
+    fn test_clipboard_text_keeps_tail() {
+        let line = "x".repeat(1024);
+        let lines: Vec<String> = (0..1000).map(|_| line.clone()).collect();
+        let (text, dropped) = clipboard_text(&lines);
+        assert!(dropped > 0);
+        assert!(text.len() <= MAX_CLIPBOARD_BYTES);
+        assert_eq!(text.lines().count(), lines.len() - dropped);
+
+        let (text, dropped) = clipboard_text(&lines[..3]);
+        assert_eq!(dropped, 0);
+        assert_eq!(text.lines().count(), 3);
+    }
+
+    #[test]
+    fn test_write_log_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let lines = vec!["\x1b[32mStep 1\x1b[0m".to_string(), "failed".to_string()];
+        let path = write_log_file(&dir.path().join("build-logs"), "app", &lines).unwrap();
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        assert!(name.starts_with("build-app-") && name.ends_with(".log"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "Step 1\nfailed\n");
+    }
+}
@@ -2,6 +2,7 @@
 
 use ratatui::widgets::TableState;
 use std::collections::HashMap;
+use std::time::Instant;
 
 /// State for the compose services detail view.
 #[derive(Debug)]
@@ -14,6 +15,8 @@ pub struct ComposeViewState {
     pub selected_service: usize,
     /// Whether compose services are currently being loaded
     pub services_loading: bool,
+    /// When `compose ps` was last started, for the detail view's auto-refresh
+    pub services_refreshed_at: Option<Instant>,
     /// Name of the service whose logs are being viewed (None = primary container)
     pub logs_service_name: Option<String>,
 }
@@ -25,6 +28,7 @@ impl ComposeViewState {
             services_table_state: TableState::default(),
             selected_service: 0,
             services_loading: false,
+            services_refreshed_at: None,
             logs_service_name: None,
         }
     }
@@ -35,6 +39,7 @@ impl ComposeViewState {
         self.selected_service = 0;
         self.services_table_state = TableState::default();
         self.services_loading = false;
+        self.services_refreshed_at = None;
     }
 
     pub fn reset_logs(&mut self) {
@@ -47,3 +52,110 @@ impl Default for ComposeViewState {
         Self::new()
     }
 }
+
+/// Database-style services recognized by image name: (name fragments, URL
+/// scheme, default container port)
+const KNOWN_SERVICES: &[(&[&str], &str, u16)] = &[
+    (&["postgres", "postgis", "timescaledb"], "postgres", 5432),
+    (&["mysql", "mariadb"], "mysql", 3306),
+    (&["redis", "valkey"], "redis", 6379),
+    (&["mongo"], "mongodb", 27017),
+];
+
+/// Connection string for a service's published port, e.g. `postgres://localhost:5432`
+///
+/// The type is guessed from the image's repository name; unknown images get a
+/// bare `host:port` for the first published TCP port. `None` when nothing is
+/// published to the host.
+pub fn connection_url(image: &str, ports: &[devc_provider::PortInfo]) -> Option<String> {
+    let repo = image.split('@').next().unwrap_or(image);
+    let repo = repo.rsplit('/').next().unwrap_or(repo);
+    let repo = repo.split(':').next().unwrap_or(repo).to_lowercase();
+    let known = KNOWN_SERVICES
+        .iter()
+        .find(|(names, _, _)| names.iter().any(|n| repo.contains(n)));
+
+    let published = |p: &&devc_provider::PortInfo| p.host_port.is_some() && p.protocol == "tcp";
+    let port = known
+        .and_then(|(_, _, default)| {
+            ports
+                .iter()
+                .filter(published)
+                .find(|p| p.container_port == *default)
+        })
+        .or_else(|| ports.iter().find(published))?;
+
+    let host = match port.host_ip.as_deref() {
+        None | Some("") | Some("0.0.0.0") | Some("::") => "localhost".to_string(),
+        Some(ip) if ip.contains(':') => format!("[{}]", ip),
+        Some(ip) => ip.to_string(),
+    };
+    let address = format!("{}:{}", host, port.host_port?);
+    Some(match known {
+        Some((_, scheme, _)) => format!("{}://{}", scheme, address),
+        None => address,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use devc_provider::PortInfo;
+
+    fn port(container_port: u16, host_port: Option<u16>, host_ip: Option<&str>) -> PortInfo {
+        PortInfo {
+            container_port,
+            host_port,
+            protocol: "tcp".to_string(),
+            host_ip: host_ip.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_connection_url_known_images() {
+        assert_eq!(
+            connection_url("postgres:16", &[port(5432, Some(5432), Some("0.0.0.0"))]).as_deref(),
+            Some("postgres://localhost:5432")
+        );
+        assert_eq!(
+            connection_url(
+                "docker.io/library/mariadb:11",
+                &[port(3306, Some(13306), Some("127.0.0.1"))]
+            )
+            .as_deref(),
+            Some("mysql://127.0.0.1:13306")
+        );
+        assert_eq!(
+            connection_url("bitnami/redis@sha256:abc", &[port(6379, Some(6380), None)]).as_deref(),
+            Some("redis://localhost:6380")
+        );
+    }
+
+    #[test]
+    fn test_connection_url_prefers_default_port() {
+        let ports = [port(8080, Some(8080), None), port(5432, Some(15432), None)];
+        assert_eq!(
+            connection_url("postgis/postgis", &ports).as_deref(),
+            Some("postgres://localhost:15432")
+        );
+    }
+
+    #[test]
+    fn test_connection_url_unknown_image_falls_back_to_host_port() {
+        let ports = [port(80, None, None), port(3000, Some(3000), Some("::"))];
+        assert_eq!(
+            connection_url("ghcr.io/acme/api:latest", &ports).as_deref(),
+            Some("localhost:3000")
+        );
+        assert_eq!(
+            connection_url("nginx", &[port(80, Some(8080), Some("::1"))]).as_deref(),
+            Some("[::1]:8080")
+        );
+    }
+
+    #[test]
+    fn test_connection_url_none_without_published_port() {
+        assert!(connection_url("postgres", &[port(5432, None, None)]).is_none());
+        assert!(connection_url("postgres", &[]).is_none());
+    }
+}
@@ -0,0 +1,437 @@
+//! Keybinding registry
+//!
+//! Every key the TUI reacts to outside of text-entry modes and the confirm
+//! dialog is declared once in [`BINDINGS`]. The key handlers in `app.rs`
+//! resolve presses through [`action_for`], and the help view renders the same
+//! table via [`bindings_for`], so the help screen always matches behavior.
+
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// The set of bindings active for a view/tab combination
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyContext {
+    /// Keys that work in every view
+    Global,
+    Containers,
+    Discover,
+    Providers,
+    Settings,
+    ContainerDetail,
+    ProviderDetail,
+    BuildOutput,
+    Logs,
+    ConfigView,
+    Ports,
+    DiscoverDetail,
+    AgentDiagnostics,
+}
+
+impl KeyContext {
+    /// Section heading shown in the help view
+    pub fn title(&self) -> &'static str {
+        match self {
+            KeyContext::Global => "Global Keys",
+            KeyContext::Containers => "Containers Tab",
+            KeyContext::Discover => "Discover Mode",
+            KeyContext::Providers => "Providers Tab",
+            KeyContext::Settings => "Settings Tab",
+            KeyContext::ContainerDetail => "Container Details",
+            KeyContext::ProviderDetail => "Provider Details",
+            KeyContext::BuildOutput => "Build Output",
+            KeyContext::Logs => "Logs",
+            KeyContext::ConfigView => "devcontainer.json Viewer",
+            KeyContext::Ports => "Port Forwarding",
+            KeyContext::DiscoverDetail => "Discovered Container",
+            KeyContext::AgentDiagnostics => "Agent Manager",
+        }
+    }
+
+    /// Extra lines shown under the bindings in the help view
+    pub fn notes(&self) -> &'static [&'static str] {
+        match self {
+            KeyContext::Containers => &["S opens a persistent session; Ctrl+\\ detaches."],
+            KeyContext::Providers => &[
+                "The active provider is used for new containers.",
+                "Existing containers keep their original provider.",
+            ],
+            KeyContext::Settings | KeyContext::ProviderDetail => {
+                &["When editing text: Enter confirms, Esc cancels."]
+            }
+            _ => &[],
+        }
+    }
+}
+
+/// What a key press does once resolved against a [`KeyContext`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    Back,
+    Help,
+    ContainersTab,
+    ProvidersTab,
+    SettingsTab,
+    NextTab,
+    PrevTab,
+    MoveDown,
+    MoveUp,
+    First,
+    Last,
+    PageDown,
+    PageUp,
+    HalfPageDown,
+    HalfPageUp,
+    Open,
+    Refresh,
+    SyncStatus,
+    ToggleStartStop,
+    TogglePause,
+    Up,
+    Build,
+    Rebuild,
+    Shell,
+    RestartShell,
+    CloseShell,
+    Ports,
+    Agents,
+    Delete,
+    Forget,
+    ToggleDiscover,
+    CycleSort,
+    ToggleFullIds,
+    ToggleWorkspaceGroups,
+    ToggleTimestamps,
+    RemovePreviousImage,
+    CopyConnection,
+    ExitDiscover,
+    Adopt,
+    Logs,
+    ViewConfig,
+    EditConfig,
+    Copy,
+    Close,
+    SetActive,
+    Save,
+    Reset,
+    Edit,
+    TestConnection,
+    RetryConnection,
+    SyncAgent,
+    SyncAllAgents,
+    Forward,
+    StopForward,
+    ToggleForward,
+    OpenBrowser,
+    ForwardAll,
+    ToggleAutoForward,
+    StopAllForwards,
+    InstallSocat,
+    ToggleVerbose,
+}
+
+/// A single key, with the modifiers it requires
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    const fn plain(code: KeyCode) -> Self {
+        Self {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    const fn ctrl(c: char) -> Self {
+        Self {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::CONTROL,
+        }
+    }
+
+    /// Whether a press matches this key. Extra modifiers are tolerated so that
+    /// terminals reporting SHIFT for uppercase letters still match.
+    fn matches(&self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        self.code == code && modifiers.contains(self.modifiers)
+    }
+
+    /// Human-readable name, e.g. `j`, `Down`, `Ctrl+d`
+    pub fn label(&self) -> String {
+        let name = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::F(n) => format!("F{}", n),
+            KeyCode::Up => "Up".to_string(),
+            KeyCode::Down => "Down".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::BackTab => "Shift+Tab".to_string(),
+            KeyCode::Delete => "Delete".to_string(),
+            other => format!("{:?}", other),
+        };
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            format!("Ctrl+{}", name)
+        } else {
+            name
+        }
+    }
+}
+
+/// A registry entry: the keys that trigger `action` in `context`
+#[derive(Debug, Clone, Copy)]
+pub struct KeyBinding {
+    pub context: KeyContext,
+    pub keys: &'static [Key],
+    pub action: Action,
+    pub description: &'static str,
+}
+
+impl KeyBinding {
+    /// All keys joined for display, e.g. `j/Down`
+    pub fn label(&self) -> String {
+        self.keys
+            .iter()
+            .map(Key::label)
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+const fn ch(c: char) -> Key {
+    Key::plain(KeyCode::Char(c))
+}
+
+const DOWN: Key = Key::plain(KeyCode::Down);
+const UP: Key = Key::plain(KeyCode::Up);
+const HOME: Key = Key::plain(KeyCode::Home);
+const END: Key = Key::plain(KeyCode::End);
+const ENTER: Key = Key::plain(KeyCode::Enter);
+const ESC: Key = Key::plain(KeyCode::Esc);
+const F5: Key = Key::plain(KeyCode::F(5));
+
+macro_rules! bind {
+    ($ctx:ident, [$($key:expr),+ $(,)?], $action:ident, $desc:expr) => {
+        KeyBinding {
+            context: KeyContext::$ctx,
+            keys: &[$($key),+],
+            action: Action::$action,
+            description: $desc,
+        }
+    };
+}
+
+/// Every binding, grouped by context in help display order.
+///
+/// Within a context the first matching entry wins, so Ctrl chords must come
+/// before a plain binding on the same letter.
+#[rustfmt::skip]
+pub const BINDINGS: &[KeyBinding] = &[
+    // Global
+    bind!(Global, [Key::plain(KeyCode::Tab)], NextTab, "Next tab"),
+    bind!(Global, [Key::plain(KeyCode::BackTab)], PrevTab, "Previous tab"),
+    bind!(Global, [ch('1')], ContainersTab, "Jump to Containers tab"),
+    bind!(Global, [ch('2')], ProvidersTab, "Jump to Providers tab"),
+    bind!(Global, [ch('3')], SettingsTab, "Jump to Settings tab"),
+    bind!(Global, [ch('?'), Key::plain(KeyCode::F(1))], Help, "Show this help"),
+    bind!(Global, [ch('q')], Quit, "Quit (or go back from subview)"),
+    bind!(Global, [ESC], Back, "Go back / Cancel"),
+    // Containers tab
+    bind!(Containers, [ch('j'), DOWN], MoveDown, "Move selection down"),
+    bind!(Containers, [ch('k'), UP], MoveUp, "Move selection up"),
+    bind!(Containers, [ch('g'), HOME], First, "Go to first container"),
+    bind!(Containers, [ch('G'), END], Last, "Go to last container"),
+    bind!(Containers, [ENTER], Open, "View container details"),
+    bind!(Containers, [ch('s')], ToggleStartStop, "Start or Stop container"),
+    bind!(Containers, [ch('z')], TogglePause, "Pause or unpause container"),
+    bind!(Containers, [ch('u')], Up, "Up - build, create, and start"),
+    bind!(Containers, [ch('S')], Shell, "Shell into container"),
+    bind!(Containers, [Key::ctrl('r')], RestartShell, "Restart shell session (re-runs postAttach)"),
+    bind!(Containers, [ch('x')], CloseShell, "Close shell session"),
+    bind!(Containers, [ch('b')], Build, "Build container image"),
+    bind!(Containers, [ch('R')], Rebuild, "Rebuild - destroy and rebuild container"),
+    bind!(Containers, [ch('p')], Ports, "Port forwarding"),
+    bind!(Containers, [ch('a')], Agents, "Open Agent Manager (running container)"),
+    bind!(Containers, [ch('d'), Key::plain(KeyCode::Delete)], Delete, "Delete container"),
+    bind!(Containers, [ch('f')], Forget, "Forget adopted container"),
+    bind!(Containers, [ch('D')], ToggleDiscover, "Discover all devcontainers"),
+    bind!(Containers, [ch('o')], CycleSort, "Cycle sort order (status/recent/name)"),
+    bind!(Containers, [ch('i')], ToggleFullIds, "Toggle short/full container and image IDs"),
+    bind!(Containers, [ch('w')], ToggleWorkspaceGroups, "Group containers by workspace folder"),
+    bind!(Containers, [ch('r'), F5], Refresh, "Refresh list"),
+    bind!(Containers, [ch('c')], SyncStatus, "Check selected container status against the runtime"),
+    // Discover mode
+    bind!(Discover, [ch('j'), DOWN], MoveDown, "Move selection down"),
+    bind!(Discover, [ch('k'), UP], MoveUp, "Move selection up"),
+    bind!(Discover, [ch('g'), HOME], First, "Go to first container"),
+    bind!(Discover, [ch('G'), END], Last, "Go to last container"),
+    bind!(Discover, [ENTER], Open, "Inspect container"),
+    bind!(Discover, [ch('a')], Adopt, "Adopt container into devc"),
+    bind!(Discover, [ch('r'), F5], Refresh, "Refresh discovered containers"),
+    bind!(Discover, [ch('D')], ToggleDiscover, "Show managed containers"),
+    bind!(Discover, [ESC, ch('q')], ExitDiscover, "Exit discover mode"),
+    // Providers tab
+    bind!(Providers, [ch('j'), DOWN], MoveDown, "Move selection down"),
+    bind!(Providers, [ch('k'), UP], MoveUp, "Move selection up"),
+    bind!(Providers, [ENTER], Open, "Configure selected provider"),
+    bind!(Providers, [ch(' '), ch('a')], SetActive, "Set selected provider as active"),
+    bind!(Providers, [ch('s')], Save, "Save provider settings to config"),
+    bind!(Providers, [ch('c')], RetryConnection, "Retry connection (when disconnected)"),
+    // Settings tab
+    bind!(Settings, [ch('j'), DOWN], MoveDown, "Move to next setting"),
+    bind!(Settings, [ch('k'), UP], MoveUp, "Move to previous setting"),
+    bind!(Settings, [ENTER, ch(' ')], Edit, "Edit setting (text) or toggle (checkbox)"),
+    bind!(Settings, [ch('s')], Save, "Save all settings to config file"),
+    bind!(Settings, [ch('r')], Reset, "Reset to saved values"),
+    // Container detail popup
+    bind!(ContainerDetail, [ch('j'), DOWN], MoveDown, "Scroll down / next compose service"),
+    bind!(ContainerDetail, [ch('k'), UP], MoveUp, "Scroll up / previous compose service"),
+    bind!(ContainerDetail, [ch('s')], ToggleStartStop, "Start or Stop container"),
+    bind!(ContainerDetail, [ch('z')], TogglePause, "Pause or unpause container"),
+    bind!(ContainerDetail, [ch('u')], Up, "Up - build, create, and start"),
+    bind!(ContainerDetail, [ch('S')], Shell, "Shell (into selected compose service)"),
+    bind!(ContainerDetail, [Key::ctrl('r')], RestartShell, "Restart shell session (re-runs postAttach)"),
+    bind!(ContainerDetail, [ch('x')], CloseShell, "Close shell session"),
+    bind!(ContainerDetail, [ch('l')], Logs, "View logs"),
+    bind!(ContainerDetail, [ch('v')], ViewConfig, "View resolved devcontainer.json"),
+    bind!(ContainerDetail, [ch('e')], EditConfig, "Edit devcontainer.json in $EDITOR"),
+    bind!(ContainerDetail, [ch('b')], Build, "Build container image"),
+    bind!(ContainerDetail, [ch('R')], Rebuild, "Rebuild - destroy and rebuild container"),
+    bind!(ContainerDetail, [ch('r'), F5], Refresh, "Refresh compose services"),
+    bind!(ContainerDetail, [ch('i')], ToggleFullIds, "Toggle short/full container and image IDs"),
+    bind!(ContainerDetail, [ch('P')], RemovePreviousImage, "Remove the image replaced by the last rebuild"),
+    bind!(ContainerDetail, [ch('c')], CopyConnection, "Copy connection URL (postgres://..., redis://...) of the selected service"),
+    // Provider detail popup
+    bind!(ProviderDetail, [ch('e'), ENTER], Edit, "Edit socket path"),
+    bind!(ProviderDetail, [ch('t')], TestConnection, "Test connection"),
+    bind!(ProviderDetail, [ch('a'), ch(' ')], SetActive, "Set as active provider"),
+    bind!(ProviderDetail, [ch('s')], Save, "Save provider settings to config"),
+    // Build output
+    bind!(BuildOutput, [ch('j'), DOWN], MoveDown, "Scroll down"),
+    bind!(BuildOutput, [ch('k'), UP], MoveUp, "Scroll up"),
+    bind!(BuildOutput, [ch('g'), HOME], First, "Go to top"),
+    bind!(BuildOutput, [ch('G'), END], Last, "Go to bottom (follow output)"),
+    bind!(BuildOutput, [ch('c')], Copy, "Copy output to clipboard"),
+    bind!(BuildOutput, [ch('s')], Save, "Save output to a log file"),
+    bind!(BuildOutput, [ch('v')], ToggleVerbose, "Show/hide each feature's download messages"),
+    bind!(BuildOutput, [ch('q'), ESC], Close, "Close, or cancel a running build"),
+    // Logs
+    bind!(Logs, [Key::ctrl('d')], HalfPageDown, "Half page down"),
+    bind!(Logs, [Key::ctrl('u')], HalfPageUp, "Half page up"),
+    bind!(Logs, [ch('j'), DOWN], MoveDown, "Scroll down"),
+    bind!(Logs, [ch('k'), UP], MoveUp, "Scroll up"),
+    bind!(Logs, [ch('g'), HOME], First, "Go to top"),
+    bind!(Logs, [ch('G'), END], Last, "Go to bottom"),
+    bind!(Logs, [Key::plain(KeyCode::PageDown)], PageDown, "Page down"),
+    bind!(Logs, [Key::plain(KeyCode::PageUp)], PageUp, "Page up"),
+    bind!(Logs, [ch('r'), F5], Refresh, "Reload logs"),
+    bind!(Logs, [ch('t')], ToggleTimestamps, "Toggle timestamps"),
+    // devcontainer.json viewer
+    bind!(ConfigView, [ch('j'), DOWN], MoveDown, "Scroll down"),
+    bind!(ConfigView, [ch('k'), UP], MoveUp, "Scroll up"),
+    bind!(ConfigView, [ch('g'), HOME], First, "Go to top"),
+    bind!(ConfigView, [ch('G'), END], Last, "Go to bottom"),
+    bind!(ConfigView, [Key::plain(KeyCode::PageDown)], PageDown, "Page down"),
+    bind!(ConfigView, [Key::plain(KeyCode::PageUp)], PageUp, "Page up"),
+    bind!(ConfigView, [ch('q'), ESC], Close, "Back to container details"),
+    // Port forwarding
+    bind!(Ports, [ch('j'), DOWN], MoveDown, "Move selection down"),
+    bind!(Ports, [ch('k'), UP], MoveUp, "Move selection up"),
+    bind!(Ports, [ch('g'), HOME], First, "Go to first port"),
+    bind!(Ports, [ch('G'), END], Last, "Go to last port"),
+    bind!(Ports, [ch('f')], Forward, "Forward selected port"),
+    bind!(Ports, [ch('s')], StopForward, "Stop forwarding selected port"),
+    bind!(Ports, [ENTER, ch(' ')], ToggleForward, "Start/stop forwarding selected port"),
+    bind!(Ports, [ch('o')], OpenBrowser, "Open forwarded port in browser"),
+    bind!(Ports, [ch('a')], ForwardAll, "Forward all detected ports"),
+    bind!(Ports, [ch('A')], ToggleAutoForward, "Toggle auto-forward all"),
+    bind!(Ports, [ch('n')], StopAllForwards, "Stop all forwards"),
+    bind!(Ports, [ch('i')], InstallSocat, "Install socat in container"),
+    bind!(Ports, [ch('q'), ESC], Close, "Back"),
+    // Discovered container detail
+    bind!(DiscoverDetail, [ch('j'), DOWN], MoveDown, "Scroll down"),
+    bind!(DiscoverDetail, [ch('k'), UP], MoveUp, "Scroll up"),
+    bind!(DiscoverDetail, [ch('a')], Adopt, "Adopt container into devc"),
+    // Agent manager
+    bind!(AgentDiagnostics, [ch('j'), DOWN], MoveDown, "Move selection down"),
+    bind!(AgentDiagnostics, [ch('k'), UP], MoveUp, "Move selection up"),
+    bind!(AgentDiagnostics, [ch('g'), HOME], First, "Go to first agent"),
+    bind!(AgentDiagnostics, [ch('G'), END], Last, "Go to last agent"),
+    bind!(AgentDiagnostics, [ch('s')], SyncAgent, "Sync selected agent"),
+    bind!(AgentDiagnostics, [ch('A')], SyncAllAgents, "Sync all enabled agents"),
+    bind!(AgentDiagnostics, [ch('r')], Refresh, "Refresh agent status"),
+];
+
+/// Resolve a key press to an action within `context`
+pub fn action_for(context: KeyContext, code: KeyCode, modifiers: KeyModifiers) -> Option<Action> {
+    BINDINGS
+        .iter()
+        .filter(|b| b.context == context)
+        .find(|b| b.keys.iter().any(|k| k.matches(code, modifiers)))
+        .map(|b| b.action)
+}
+
+/// All bindings registered for `context`, in display order
+pub fn bindings_for(context: KeyContext) -> impl Iterator<Item = &'static KeyBinding> {
+    BINDINGS.iter().filter(move |b| b.context == context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_key_bound_twice_in_a_context() {
+        for (i, a) in BINDINGS.iter().enumerate() {
+            for b in &BINDINGS[i + 1..] {
+                if a.context != b.context {
+                    continue;
+                }
+                for key in a.keys {
+                    assert!(
+                        !b.keys.contains(key),
+                        "{:?} bound to both {:?} and {:?} in {:?}",
+                        key,
+                        a.action,
+                        b.action,
+                        a.context
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_action_for_tolerates_shift_and_prefers_ctrl() {
+        assert_eq!(
+            action_for(
+                KeyContext::Containers,
+                KeyCode::Char('G'),
+                KeyModifiers::SHIFT
+            ),
+            Some(Action::Last)
+        );
+        assert_eq!(
+            action_for(KeyContext::Logs, KeyCode::Char('d'), KeyModifiers::CONTROL),
+            Some(Action::HalfPageDown)
+        );
+        assert_eq!(
+            action_for(KeyContext::Logs, KeyCode::Char('d'), KeyModifiers::NONE),
+            None
+        );
+    }
+
+    #[test]
+    fn test_binding_label_joins_keys() {
+        let labels: Vec<String> = bindings_for(KeyContext::Logs).map(|b| b.label()).collect();
+        assert_eq!(labels[0], "Ctrl+d");
+        assert!(labels.contains(&"j/Down".to_string()));
+        assert!(labels.contains(&"r/F5".to_string()));
+    }
+}
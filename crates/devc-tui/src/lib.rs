@@ -3,14 +3,17 @@
 //! Built with Ratatui for a modern terminal UI experience.
 
 pub mod app;
+mod build_export;
 mod clipboard;
 pub mod compose_state;
 mod event;
+pub mod keymap;
 pub mod port_state;
 pub mod ports;
 pub mod settings;
 pub mod shell;
 pub mod shell_state;
+pub mod toast;
 pub mod tunnel;
 pub mod ui;
 pub mod widgets;
@@ -33,19 +36,29 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use devc_core::ContainerManager;
+use devc_provider::ProviderType;
 use ratatui::prelude::*;
 use std::io::{self, Write};
 use std::path::Path;
 use tracing_subscriber::layer::SubscriberExt;
 
 /// Run the TUI application
-pub async fn run(manager: ContainerManager, workspace_dir: Option<&Path>) -> AppResult<()> {
+///
+/// `provider_override` carries the CLI's `--provider` flag, if one was given.
+pub async fn run(
+    manager: ContainerManager,
+    workspace_dir: Option<&Path>,
+    provider_override: Option<ProviderType>,
+) -> AppResult<()> {
     // Suppress tracing output during TUI (use a no-op subscriber to prevent logs from corrupting display)
     // The guard restores the previous subscriber when dropped
     let _guard = tracing::subscriber::set_default(
         tracing_subscriber::registry().with(tracing_subscriber::layer::Identity::new()),
     );
 
+    // Make sure a panic doesn't leave the user's shell in raw/alternate-screen mode
+    let _panic_guard = PanicHookGuard::install();
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -54,7 +67,7 @@ pub async fn run(manager: ContainerManager, workspace_dir: Option<&Path>) -> App
     let mut terminal = Terminal::new(backend)?;
 
     // Create app and run
-    let mut app = App::new(manager, workspace_dir).await?;
+    let mut app = App::new(manager, workspace_dir, provider_override).await?;
     let res = app.run(&mut terminal).await;
 
     // Restore terminal
@@ -69,6 +82,54 @@ pub async fn run(manager: ContainerManager, workspace_dir: Option<&Path>) -> App
     res
 }
 
+type PanicHook = dyn Fn(&std::panic::PanicHookInfo<'_>) + Sync + Send + 'static;
+
+/// Panic hook that restores the terminal before the previous hook prints the
+/// panic message. Dropping the guard reinstates the previous hook.
+struct PanicHookGuard {
+    prev_hook: std::sync::Arc<PanicHook>,
+}
+
+impl PanicHookGuard {
+    fn install() -> Self {
+        let prev_hook: std::sync::Arc<PanicHook> = std::panic::take_hook().into();
+        let hook = prev_hook.clone();
+        std::panic::set_hook(Box::new(move |info| {
+            // The TUI loop runs on the main thread; a panicking background task
+            // is caught by tokio and must not tear down a still-running UI.
+            if std::thread::current().name() == Some("main") {
+                restore_terminal();
+            }
+            hook(info);
+        }));
+        Self { prev_hook }
+    }
+}
+
+impl Drop for PanicHookGuard {
+    fn drop(&mut self) {
+        // The hook can't be replaced while unwinding; leave ours in place then.
+        if !std::thread::panicking() {
+            let prev_hook = self.prev_hook.clone();
+            std::panic::set_hook(Box::new(move |info| prev_hook(info)));
+        }
+    }
+}
+
+/// Best-effort terminal restore, safe to call whether or not the TUI (or a
+/// suspended shell session) currently owns the terminal.
+fn restore_terminal() {
+    let mut stdout = io::stdout();
+    let _ = execute!(
+        stdout,
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        crossterm::cursor::Show
+    );
+    let _ = disable_raw_mode();
+    reset_terminal();
+}
+
 /// Suspend TUI mode for shell access
 ///
 /// Leaves alternate screen and disables raw mode so the shell process
@@ -3,11 +3,17 @@
 use crate::ports::DetectedPort;
 use crate::ports::PortDetectionUpdate;
 use crate::tunnel::{spawn_forwarder, PortForwarder};
+use devc_config::PortTransport;
 use ratatui::widgets::TableState;
 use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, SocketAddr};
 use std::time::Duration;
 use tokio::sync::{mpsc, oneshot};
 
+/// Key of an active forwarder: (provider container ID, port, transport), so a
+/// TCP and a UDP forward of the same port can coexist
+pub type ForwarderKey = (String, u16, PortTransport);
+
 /// All port-forwarding state, both per-view and persistent across views.
 pub struct PortForwardingState {
     // === Per-view state (cleared when exiting ports view) ===
@@ -33,8 +39,8 @@ pub struct PortForwardingState {
     pub port_detect_handle: Option<tokio::task::JoinHandle<()>>,
 
     // === Persistent state (survives view changes) ===
-    /// Active port forwarders: (container_id, port) -> PortForwarder
-    pub active_forwarders: HashMap<(String, u16), PortForwarder>,
+    /// Active port forwarders: (container_id, port, transport) -> PortForwarder
+    pub active_forwarders: HashMap<ForwarderKey, PortForwarder>,
 
     // === Auto port forwarding state ===
     /// Background port detectors for auto-forwarding, keyed by provider container ID
@@ -76,19 +82,25 @@ impl PortForwardingState {
     }
 
     /// Handle a port detection update (updates detected_ports list)
+    ///
+    /// Forwards whose port stopped listening stay in the list, flagged as not
+    /// listening, so they can still be stopped; the forwarder itself keeps
+    /// running and resumes once the server comes back.
     pub fn handle_port_update(&mut self, update: PortDetectionUpdate) {
         let forwarded_ports: HashSet<u16> =
             if let Some(ref container_id) = self.provider_container_id {
                 self.active_forwarders
                     .keys()
-                    .filter(|(cid, _)| cid == container_id)
-                    .map(|(_, port)| *port)
+                    .filter(|(cid, _, transport)| {
+                        cid == container_id && *transport == PortTransport::Tcp
+                    })
+                    .map(|(_, port, _)| *port)
                     .collect()
             } else {
                 HashSet::new()
             };
 
-        self.detected_ports = update
+        let mut ports: Vec<DetectedPort> = update
             .ports
             .into_iter()
             .map(|mut p| {
@@ -97,6 +109,40 @@ impl PortForwardingState {
             })
             .collect();
 
+        for port in forwarded_ports {
+            if ports.iter().any(|p| p.port == port) {
+                continue;
+            }
+            let previous = self.detected_ports.iter().find(|p| p.port == port);
+            ports.push(DetectedPort {
+                port,
+                protocol: "tcp".to_string(),
+                process: previous.and_then(|p| p.process.clone()),
+                is_new: false,
+                is_forwarded: true,
+                is_listening: false,
+                scheme: previous.map(|p| p.scheme).unwrap_or_default(),
+            });
+        }
+        ports.sort_by_key(|p| p.port);
+
+        self.detected_ports = ports;
+        self.clamp_selection();
+    }
+
+    /// Reflect a stopped forward in the list, dropping the row entirely if the
+    /// port was only shown because it was forwarded
+    pub fn mark_unforwarded(&mut self, port: u16) {
+        self.detected_ports
+            .retain(|p| p.port != port || p.is_listening);
+        if let Some(p) = self.detected_ports.iter_mut().find(|p| p.port == port) {
+            p.is_forwarded = false;
+        }
+        self.clamp_selection();
+    }
+
+    /// Keep the selection within the port list after it changes size
+    pub fn clamp_selection(&mut self) {
         if !self.detected_ports.is_empty() && self.selected_port >= self.detected_ports.len() {
             self.selected_port = self.detected_ports.len() - 1;
         }
@@ -105,6 +151,61 @@ impl PortForwardingState {
         }
     }
 
+    /// Host address the given container port is forwarded to, if any, e.g.
+    /// `localhost:3000` or `0.0.0.0:3000`
+    pub fn local_address(&self, port: u16) -> Option<String> {
+        let cid = self.provider_container_id.as_ref()?;
+        let forwarder = self
+            .active_forwarders
+            .get(&(cid.clone(), port, PortTransport::Tcp))?;
+        Some(if forwarder.bind_address.is_loopback() {
+            format!("localhost:{}", forwarder.local_port)
+        } else {
+            SocketAddr::new(forwarder.bind_address, forwarder.local_port).to_string()
+        })
+    }
+
+    /// Host address a manual forward of `port` should listen on: whatever the
+    /// container's config declares for it, loopback otherwise
+    pub fn bind_address_for(&self, provider_container_id: &str, port: u16) -> IpAddr {
+        self.auto_forward_configs
+            .get(provider_container_id)
+            .and_then(|configs| {
+                configs
+                    .iter()
+                    .find(|c| c.port == port && c.transport == PortTransport::Tcp)
+            })
+            .map_or(devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS, |c| {
+                c.bind_address
+            })
+    }
+
+    /// Host address of every TCP port the container's config declares one
+    /// for, so the shell's on-demand forwards honour it too
+    pub fn bind_addresses(&self, provider_container_id: &str) -> HashMap<u16, IpAddr> {
+        self.auto_forward_configs
+            .get(provider_container_id)
+            .map(|configs| {
+                configs
+                    .iter()
+                    .filter(|c| c.transport == PortTransport::Tcp)
+                    .map(|c| (c.port, c.bind_address))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Compact summary of a container's active forwards, e.g. `3000, 8080→18080`
+    pub fn forward_summary(&self, provider_container_id: &str) -> String {
+        let forwards = self
+            .active_forwarders
+            .iter()
+            .filter(|((cid, _, _), _)| cid == provider_container_id)
+            .map(|((_, port, _), f)| (*port, f.local_port))
+            .collect();
+        format_forwards(forwards)
+    }
+
     /// Move selection to the next port (wrapping)
     pub fn select_next(&mut self) {
         if !self.detected_ports.is_empty() {
@@ -145,6 +246,24 @@ impl PortForwardingState {
         self.detected_ports.get(self.selected_port)
     }
 
+    /// Scheme to open a port in the browser with
+    ///
+    /// An explicit `protocol` in the container's port attributes wins; otherwise
+    /// the scheme the background probe detected. `None` means the port is not
+    /// known to serve HTTP.
+    pub fn browser_scheme<'a>(&'a self, port: &DetectedPort) -> Option<&'a str> {
+        self.provider_container_id
+            .as_ref()
+            .and_then(|cid| self.auto_forward_configs.get(cid))
+            .and_then(|configs| {
+                configs
+                    .iter()
+                    .find(|c| c.port == port.port && c.transport == PortTransport::Tcp)
+            })
+            .and_then(|c| c.protocol.as_deref())
+            .or(port.scheme.url_scheme())
+    }
+
     /// Initialize per-view state for entering the ports view
     pub fn enter_view(
         &mut self,
@@ -216,6 +335,23 @@ impl Default for PortForwardingState {
     }
 }
 
+/// Format (container port, host port) pairs sorted by container port; the
+/// arrow is only shown when the host port differs.
+fn format_forwards(mut forwards: Vec<(u16, u16)>) -> String {
+    forwards.sort_unstable();
+    forwards
+        .iter()
+        .map(|&(remote, local)| {
+            if remote == local {
+                remote.to_string()
+            } else {
+                format!("{}→{}", remote, local)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// What (if anything) to do with the user's browser when a port is newly forwarded.
 #[derive(Debug, PartialEq, Eq)]
 pub enum BrowserOpenDecision {
@@ -257,7 +393,7 @@ pub struct ShellAutoForwardState {
     pub runtime_args: HashMap<String, (String, Vec<String>)>,
     pub forwarded_ports: HashSet<(String, u16)>,
     pub opened_ports: HashSet<(String, u16)>,
-    pub forwarders: HashMap<(String, u16), PortForwarder>,
+    pub forwarders: HashMap<ForwarderKey, PortForwarder>,
     pub auto_forward_all: HashSet<String>,
     pub auto_forward_all_global: bool,
     pub auto_open_browser_global: bool,
@@ -279,7 +415,9 @@ async fn poll_detectors_background(state: &mut ShellAutoForwardState) {
 
             for detected in &update.ports {
                 // Check if this port matches a config entry
-                let matching_config = config.iter().find(|pfc| pfc.port == detected.port);
+                let matching_config = config
+                    .iter()
+                    .find(|pfc| pfc.port == detected.port && pfc.transport == PortTransport::Tcp);
 
                 // Determine if we should forward this port
                 let should_forward = if let Some(pfc) = matching_config {
@@ -296,7 +434,10 @@ async fn poll_detectors_background(state: &mut ShellAutoForwardState) {
                 if state.forwarded_ports.contains(&key) {
                     continue;
                 }
-                if state.forwarders.contains_key(&key) {
+                if state
+                    .forwarders
+                    .contains_key(&(cid.clone(), detected.port, PortTransport::Tcp))
+                {
                     state.forwarded_ports.insert(key);
                     continue;
                 }
@@ -311,20 +452,29 @@ async fn poll_detectors_background(state: &mut ShellAutoForwardState) {
                     rt_prog,
                     rt_prefix,
                     cid.clone(),
+                    matching_config.map_or(
+                        devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS,
+                        |pfc| pfc.bind_address,
+                    ),
                     detected.port,
                     detected.port,
+                    PortTransport::Tcp,
                 )
                 .await
                 {
                     Ok(forwarder) => {
-                        state.forwarders.insert(key.clone(), forwarder);
+                        state
+                            .forwarders
+                            .insert((cid.clone(), detected.port, PortTransport::Tcp), forwarder);
                         state.forwarded_ports.insert(key.clone());
                         let decision = browser_open_decision(
                             matching_config.map(|pfc| &pfc.action),
                             state.auto_open_browser_global,
                             state.opened_ports.contains(&key),
                         );
-                        let protocol = matching_config.and_then(|pfc| pfc.protocol.as_deref());
+                        let protocol = matching_config
+                            .and_then(|pfc| pfc.protocol.as_deref())
+                            .or(detected.scheme.url_scheme());
                         match decision {
                             BrowserOpenDecision::Skip => {}
                             BrowserOpenDecision::OpenEach => {
@@ -442,4 +592,160 @@ mod tests {
             );
         }
     }
+
+    fn detected(port: u16) -> DetectedPort {
+        DetectedPort {
+            port,
+            protocol: "tcp".to_string(),
+            process: None,
+            is_new: false,
+            is_forwarded: false,
+            is_listening: true,
+            scheme: crate::ports::PortScheme::Unknown,
+        }
+    }
+
+    #[tokio::test]
+    async fn forwarded_port_that_stops_listening_stays_listed() {
+        let Ok(forwarder) = spawn_forwarder(
+            "docker".to_string(),
+            vec![],
+            "cid".to_string(),
+            devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS,
+            0,
+            4000,
+            PortTransport::Tcp,
+        )
+        .await
+        else {
+            eprintln!("Skipping: cannot bind localhost in this environment");
+            return;
+        };
+        let mut state = PortForwardingState::new();
+        state.provider_container_id = Some("cid".to_string());
+        state
+            .active_forwarders
+            .insert(("cid".to_string(), 4000, PortTransport::Tcp), forwarder);
+
+        state.handle_port_update(PortDetectionUpdate {
+            ports: vec![detected(3000), detected(4000)],
+            socat_missing: false,
+        });
+        assert!(state.detected_ports[1].is_forwarded);
+        assert!(state.detected_ports[1].is_listening);
+
+        // Server on 4000 goes away while forwarded
+        state.selected_port = 1;
+        state.handle_port_update(PortDetectionUpdate {
+            ports: vec![detected(3000)],
+            socat_missing: false,
+        });
+        assert_eq!(state.detected_ports.len(), 2);
+        assert!(state.detected_ports[1].is_forwarded);
+        assert!(!state.detected_ports[1].is_listening);
+
+        // Stopping the idle forward removes its row and keeps selection in range
+        state.active_forwarders.clear();
+        state.mark_unforwarded(4000);
+        assert_eq!(state.detected_ports.len(), 1);
+        assert_eq!(state.selected_port, 0);
+    }
+
+    #[tokio::test]
+    async fn local_address_shows_non_loopback_bind() {
+        let bind_address = std::net::Ipv4Addr::UNSPECIFIED.into();
+        let Ok(forwarder) = spawn_forwarder(
+            "docker".to_string(),
+            vec![],
+            "cid".to_string(),
+            bind_address,
+            0,
+            5000,
+            PortTransport::Tcp,
+        )
+        .await
+        else {
+            eprintln!("Skipping: cannot bind in this environment");
+            return;
+        };
+        assert!(forwarder.exposure_warning().is_some());
+
+        let mut state = PortForwardingState::new();
+        state.provider_container_id = Some("cid".to_string());
+        let config = devc_config::DevContainerConfig {
+            forward_ports: Some(vec![devc_config::PortMapping::Number(5000)]),
+            ..Default::default()
+        };
+        let mut configs = config.auto_forward_config();
+        configs[0].bind_address = bind_address;
+        state
+            .auto_forward_configs
+            .insert("cid".to_string(), configs);
+        assert_eq!(state.bind_address_for("cid", 5000), bind_address);
+        assert!(state.bind_address_for("cid", 5001).is_loopback());
+        assert_eq!(
+            state.bind_addresses("cid"),
+            HashMap::from([(5000, bind_address)])
+        );
+
+        state
+            .active_forwarders
+            .insert(("cid".to_string(), 5000, PortTransport::Tcp), forwarder);
+        assert_eq!(state.local_address(5000).as_deref(), Some("0.0.0.0:0"));
+        assert_eq!(state.local_address(5001), None);
+    }
+
+    #[tokio::test]
+    async fn tcp_and_udp_forwards_of_same_port_coexist() {
+        let mut state = PortForwardingState::new();
+        state.provider_container_id = Some("cid".to_string());
+        for transport in [PortTransport::Tcp, PortTransport::Udp] {
+            let Ok(forwarder) = spawn_forwarder(
+                "docker".to_string(),
+                vec![],
+                "cid".to_string(),
+                devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS,
+                0,
+                5353,
+                transport,
+            )
+            .await
+            else {
+                eprintln!("Skipping: cannot bind localhost in this environment");
+                return;
+            };
+            state
+                .active_forwarders
+                .insert(("cid".to_string(), 5353, transport), forwarder);
+        }
+        assert_eq!(state.active_forwarders.len(), 2);
+
+        // Only the TCP forward shows in the detected (TCP) port list
+        state.handle_port_update(PortDetectionUpdate {
+            ports: vec![],
+            socat_missing: false,
+        });
+        assert_eq!(state.detected_ports.len(), 1);
+        assert!(state.detected_ports[0].is_forwarded);
+    }
+
+    #[test]
+    fn format_forwards_sorts_and_marks_remapped_ports() {
+        assert_eq!(format_forwards(vec![]), "");
+        assert_eq!(
+            format_forwards(vec![(8080, 18080), (3000, 3000)]),
+            "3000, 8080→18080"
+        );
+    }
+
+    #[test]
+    fn mark_unforwarded_keeps_listening_ports() {
+        let mut state = PortForwardingState::new();
+        let mut port = detected(3000);
+        port.is_forwarded = true;
+        state.detected_ports = vec![port];
+        state.mark_unforwarded(3000);
+        assert_eq!(state.detected_ports.len(), 1);
+        assert!(!state.detected_ports[0].is_forwarded);
+    }
 }
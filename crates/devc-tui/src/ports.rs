@@ -1,6 +1,7 @@
 //! Port detection for containers
 //!
-//! Detects listening ports inside containers by parsing /proc/net/tcp
+//! Detects listening ports inside containers by parsing /proc/net/tcp, then
+//! probes each new port with an HTTP HEAD to tell web servers apart
 
 use devc_provider::{ContainerId, ContainerProvider, ExecConfig, ProviderType};
 use std::collections::{HashMap, HashSet};
@@ -23,8 +24,56 @@ pub struct DetectedPort {
     pub is_new: bool,
     /// Whether this port is currently being forwarded
     pub is_forwarded: bool,
+    /// Whether the container still listens on this port (false for a forward
+    /// kept alive after its server went away)
+    pub is_listening: bool,
+    /// What the port speaks, once the background probe has classified it
+    pub scheme: PortScheme,
 }
 
+/// Classification of a listening port from a quick HTTP HEAD probe
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PortScheme {
+    /// Not probed yet, the probe could not run (e.g. no socat), or the
+    /// answer was ambiguous (a bare `400 Bad Request`)
+    #[default]
+    Unknown,
+    /// Answered the HEAD request with an HTTP response
+    Http,
+    /// Answered with a TLS record or an "HTTP sent to HTTPS port" error
+    Https,
+    /// Answered with something that is not HTTP, or nothing at all
+    Other,
+}
+
+impl PortScheme {
+    /// URL scheme for web ports, `None` otherwise
+    pub fn url_scheme(self) -> Option<&'static str> {
+        match self {
+            PortScheme::Http => Some("http"),
+            PortScheme::Https => Some("https"),
+            PortScheme::Unknown | PortScheme::Other => None,
+        }
+    }
+}
+
+/// Fixed part of the bound on a probe exec, for starting the exec itself
+const PROBE_TIMEOUT_BASE: Duration = Duration::from_secs(3);
+
+/// Added to the probe bound per port: a silent port (a database waiting for
+/// its own protocol) holds socat for up to `connect-timeout` plus `-T1`
+const PROBE_TIMEOUT_PER_PORT: Duration = Duration::from_secs(2);
+
+/// Upper bound on a single probe exec, covering every port in the batch.
+/// Ports the probe does not report back stay `Unknown` and are retried on
+/// the next detection cycle.
+fn probe_timeout(ports: usize) -> Duration {
+    PROBE_TIMEOUT_BASE + PROBE_TIMEOUT_PER_PORT * u32::try_from(ports).unwrap_or(u32::MAX)
+}
+
+/// Line the probe script prints instead of results when socat is missing
+const NO_SOCAT_MARKER: &str = "devc:no-socat";
+
 /// Parse /proc/net/tcp or /proc/net/tcp6 to find listening ports
 ///
 /// Format of /proc/net/tcp:
@@ -129,11 +178,111 @@ pub async fn detect_listening_ports_via_cmd(
     ports
 }
 
+/// Classify the first bytes a port sent back in reply to `HEAD / HTTP/1.0`
+///
+/// TLS servers answer plaintext with a TLS record (`0x15 0x03 ..` alert or
+/// `0x16 0x03 ..` handshake). Only the status line of an HTTP response is
+/// looked at, so headers and bodies that merely link to `https://` (such as
+/// a redirect's `Location`) don't count. A `400 Bad Request` is how nginx and
+/// other TLS servers answer plaintext on an HTTPS port, so it is `Https` when
+/// the body says so and `Unknown` otherwise.
+pub fn classify_probe_response(response: &[u8]) -> PortScheme {
+    if response.len() >= 2 && matches!(response[0], 0x15 | 0x16) && response[1] == 0x03 {
+        return PortScheme::Https;
+    }
+    if response.starts_with(b"HTTP/") {
+        let text = String::from_utf8_lossy(response).to_ascii_lowercase();
+        let status_line = text.lines().next().unwrap_or_default();
+        if status_line.contains("https") || text.contains("sent to https port") {
+            return PortScheme::Https;
+        }
+        if status_line.split_whitespace().nth(1) == Some("400") {
+            return PortScheme::Unknown;
+        }
+        return PortScheme::Http;
+    }
+    PortScheme::Other
+}
+
+/// Parse the probe script's output: one `<port> <hex bytes>` line per port
+fn parse_probe_output(output: &str) -> HashMap<u16, PortScheme> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let port = parts.next()?.parse::<u16>().ok()?;
+            let hex = parts.next().unwrap_or("");
+            let bytes: Vec<u8> = (0..hex.len() / 2)
+                .filter_map(|i| u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok())
+                .collect();
+            Some((port, classify_probe_response(&bytes)))
+        })
+        .collect()
+}
+
+/// Probe ports inside the container with an HTTP HEAD over socat
+///
+/// Runs a single exec for the whole batch. Returns `None` when socat is
+/// missing, and an empty map when the exec fails or the probe exceeds
+/// `probe_timeout`.
+pub async fn probe_port_schemes(
+    provider: &dyn ContainerProvider,
+    container_id: &ContainerId,
+    ports: &[u16],
+) -> Option<HashMap<u16, PortScheme>> {
+    if ports.is_empty() {
+        return Some(HashMap::new());
+    }
+    let port_list: Vec<String> = ports.iter().map(|p| p.to_string()).collect();
+    let script = format!(
+        "command -v socat >/dev/null 2>&1 || {{ echo {}; exit 0; }}; \
+         for p in {}; do \
+           printf '%s ' \"$p\"; \
+           printf 'HEAD / HTTP/1.0\\r\\nHost: localhost\\r\\n\\r\\n' \
+             | socat -T1 - TCP:localhost:$p,connect-timeout=1 2>/dev/null \
+             | head -c 256 | od -An -tx1 -v | tr -d ' \\n'; \
+           echo; \
+         done",
+        NO_SOCAT_MARKER,
+        port_list.join(" ")
+    );
+    let config = ExecConfig {
+        cmd: vec!["/bin/sh".to_string(), "-c".to_string(), script],
+        env: HashMap::new(),
+        working_dir: None,
+        user: Some("root".to_string()),
+        tty: false,
+        stdin: false,
+        privileged: false,
+    };
+
+    let timeout = probe_timeout(ports.len());
+    match tokio::time::timeout(timeout, provider.exec(container_id, &config)).await {
+        Ok(Ok(result)) if result.exit_code == 0 && result.output.trim() == NO_SOCAT_MARKER => None,
+        Ok(Ok(result)) if result.exit_code == 0 => Some(parse_probe_output(&result.output)),
+        Ok(Ok(result)) => {
+            tracing::debug!("Port probe exited with code {}", result.exit_code);
+            Some(HashMap::new())
+        }
+        Ok(Err(e)) => {
+            tracing::debug!("Port probe error: {}", e);
+            Some(HashMap::new())
+        }
+        Err(_) => {
+            tracing::debug!("Port probe timed out");
+            Some(HashMap::new())
+        }
+    }
+}
+
 /// Port detection update message
 #[derive(Debug, Clone)]
 pub struct PortDetectionUpdate {
     /// Updated list of detected ports
     pub ports: Vec<DetectedPort>,
+    /// Set on the one update sent when the scheme probe found no socat in the
+    /// container; ports then stay `Unknown` and are not probed again
+    pub socat_missing: bool,
 }
 
 /// Spawn a background task that periodically detects ports in a container
@@ -150,6 +299,8 @@ pub fn spawn_port_detector(
 
     tokio::spawn(async move {
         let mut last_ports: HashSet<u16> = HashSet::new();
+        let mut schemes: HashMap<u16, PortScheme> = HashMap::new();
+        let mut probe_available = true;
         let mut iteration = 0;
 
         loop {
@@ -160,24 +311,53 @@ pub fn spawn_port_detector(
                     let current: HashSet<u16> = ports.iter().copied().collect();
                     let new_ports: HashSet<u16> =
                         current.difference(&last_ports).copied().collect();
+                    schemes.retain(|port, _| current.contains(port));
 
                     // Only mark as new on first detection after initial scan
-                    let detected: Vec<DetectedPort> = ports
-                        .into_iter()
-                        .map(|port| DetectedPort {
-                            port,
-                            protocol: "tcp".to_string(),
-                            process: None, // Process detection would require additional exec
-                            is_new: iteration > 1 && new_ports.contains(&port),
-                            is_forwarded: forwarded_ports.contains(&port),
-                        })
-                        .collect();
+                    let build_update = |schemes: &HashMap<u16, PortScheme>, socat_missing: bool| {
+                        PortDetectionUpdate {
+                            ports: ports
+                                .iter()
+                                .map(|&port| DetectedPort {
+                                    port,
+                                    protocol: "tcp".to_string(),
+                                    process: None, // Process detection would require additional exec
+                                    is_new: iteration > 1 && new_ports.contains(&port),
+                                    is_forwarded: forwarded_ports.contains(&port),
+                                    is_listening: true,
+                                    scheme: schemes.get(&port).copied().unwrap_or_default(),
+                                })
+                                .collect(),
+                            socat_missing,
+                        }
+                    };
 
-                    let update = PortDetectionUpdate { ports: detected };
-                    if tx.send(update).is_err() {
+                    if tx.send(build_update(&schemes, false)).is_err() {
                         // Receiver dropped, exit task
                         break;
                     }
+
+                    // Probe ports we haven't classified yet, then resend with schemes
+                    let unprobed: Vec<u16> = ports
+                        .iter()
+                        .copied()
+                        .filter(|port| probe_available && !schemes.contains_key(port))
+                        .collect();
+                    match probe_port_schemes(provider.as_ref(), &container_id, &unprobed).await {
+                        Some(probed) if !probed.is_empty() => {
+                            schemes.extend(probed);
+                            if tx.send(build_update(&schemes, false)).is_err() {
+                                break;
+                            }
+                        }
+                        Some(_) => {}
+                        None => {
+                            probe_available = false;
+                            if tx.send(build_update(&schemes, true)).is_err() {
+                                break;
+                            }
+                        }
+                    }
                     last_ports = current;
                 }
                 Err(e) => {
@@ -223,6 +403,63 @@ mod tests {
         assert!(ports.is_empty());
     }
 
+    #[test]
+    fn test_classify_probe_response() {
+        assert_eq!(
+            classify_probe_response(b"HTTP/1.1 200 OK\r\nServer: test\r\n"),
+            PortScheme::Http
+        );
+        assert_eq!(
+            classify_probe_response(&[0x15, 0x03, 0x03, 0x00, 0x02, 0x02, 0x0a]),
+            PortScheme::Https
+        );
+        assert_eq!(
+            classify_probe_response(&[0x16, 0x03, 0x01, 0x00, 0x4a, 0x02]),
+            PortScheme::Https
+        );
+        // A plain-HTTP redirect to https:// is still plain HTTP
+        assert_eq!(
+            classify_probe_response(
+                b"HTTP/1.1 301 Moved Permanently\r\nLocation: https://localhost/\r\n\r\n"
+            ),
+            PortScheme::Http
+        );
+        assert_eq!(
+            classify_probe_response(b"SSH-2.0-OpenSSH_9.6\r\n"),
+            PortScheme::Other
+        );
+        assert_eq!(classify_probe_response(b""), PortScheme::Other);
+        // nginx answering plaintext on its HTTPS port
+        assert_eq!(
+            classify_probe_response(
+                b"HTTP/1.1 400 Bad Request\r\nServer: nginx\r\n\r\n<center>The plain HTTP request was sent to HTTPS port</center>"
+            ),
+            PortScheme::Https
+        );
+        // ...and without a body, as a HEAD reply has none
+        assert_eq!(
+            classify_probe_response(b"HTTP/1.1 400 Bad Request\r\nServer: nginx\r\n\r\n"),
+            PortScheme::Unknown
+        );
+    }
+
+    #[test]
+    fn test_probe_timeout_scales_with_ports() {
+        // Three silent ports fit, with room for the exec itself
+        assert!(probe_timeout(3) > PROBE_TIMEOUT_PER_PORT * 3);
+        assert!(probe_timeout(10) > probe_timeout(3));
+    }
+
+    #[test]
+    fn test_parse_probe_output() {
+        let output = "3000 485454502f312e3120323030\n8443 150303000202\n5432 \nbogus\n";
+        let schemes = parse_probe_output(output);
+        assert_eq!(schemes.get(&3000), Some(&PortScheme::Http));
+        assert_eq!(schemes.get(&8443), Some(&PortScheme::Https));
+        assert_eq!(schemes.get(&5432), Some(&PortScheme::Other));
+        assert_eq!(schemes.len(), 3);
+    }
+
     #[test]
     fn test_parse_proc_net_tcp_malformed() {
         let data = "malformed data\nno valid lines here";
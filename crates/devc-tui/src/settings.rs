@@ -6,7 +6,7 @@
 use crate::widgets::TextInputState;
 use devc_config::GlobalConfig;
 use devc_core::agents::{AgentKind, HostAgentAvailability};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Settings section for visual grouping
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -54,6 +54,8 @@ impl SettingsSection {
             Self::Credentials => &[
                 SettingsField::CredentialsDocker,
                 SettingsField::CredentialsGit,
+                SettingsField::CredentialsGh,
+                SettingsField::CredentialsSshAgent,
             ],
             Self::Agents => &[
                 SettingsField::AgentCodexEnabled,
@@ -83,6 +85,8 @@ pub enum SettingsField {
     // Credentials
     CredentialsDocker,
     CredentialsGit,
+    CredentialsGh,
+    CredentialsSshAgent,
     // Agents
     AgentCodexEnabled,
     AgentClaudeEnabled,
@@ -108,6 +112,8 @@ impl SettingsField {
             // Credentials
             SettingsField::CredentialsDocker,
             SettingsField::CredentialsGit,
+            SettingsField::CredentialsGh,
+            SettingsField::CredentialsSshAgent,
             // Agents
             SettingsField::AgentCodexEnabled,
             SettingsField::AgentClaudeEnabled,
@@ -128,6 +134,8 @@ impl SettingsField {
             Self::AutoOpenBrowser => "Auto-Open Browser",
             Self::CredentialsDocker => "Docker Credentials",
             Self::CredentialsGit => "Git Credentials",
+            Self::CredentialsGh => "GitHub CLI Token",
+            Self::CredentialsSshAgent => "SSH Agent",
             Self::AgentCodexEnabled => "Codex",
             Self::AgentClaudeEnabled => "Claude",
             Self::AgentCursorEnabled => "Cursor",
@@ -141,7 +149,10 @@ impl SettingsField {
             Self::DotfilesRepo | Self::DotfilesLocal => SettingsSection::Dotfiles,
             Self::SshEnabled | Self::SshKeyPath => SettingsSection::Ssh,
             Self::AutoForwardPorts | Self::AutoOpenBrowser => SettingsSection::PortForwarding,
-            Self::CredentialsDocker | Self::CredentialsGit => SettingsSection::Credentials,
+            Self::CredentialsDocker
+            | Self::CredentialsGit
+            | Self::CredentialsGh
+            | Self::CredentialsSshAgent => SettingsSection::Credentials,
             Self::AgentCodexEnabled
             | Self::AgentClaudeEnabled
             | Self::AgentCursorEnabled
@@ -157,6 +168,8 @@ impl SettingsField {
                 | Self::AutoOpenBrowser
                 | Self::CredentialsDocker
                 | Self::CredentialsGit
+                | Self::CredentialsGh
+                | Self::CredentialsSshAgent
                 | Self::AgentCodexEnabled
                 | Self::AgentClaudeEnabled
                 | Self::AgentCursorEnabled
@@ -172,6 +185,8 @@ impl SettingsField {
                 | Self::AutoOpenBrowser
                 | Self::CredentialsDocker
                 | Self::CredentialsGit
+                | Self::CredentialsGh
+                | Self::CredentialsSshAgent
                 | Self::AgentCodexEnabled
                 | Self::AgentClaudeEnabled
                 | Self::AgentCursorEnabled
@@ -191,6 +206,10 @@ impl SettingsField {
             Self::AutoOpenBrowser => "Honor openBrowser actions from devcontainer.json",
             Self::CredentialsDocker => "Forward Docker registry credentials into containers",
             Self::CredentialsGit => "Forward Git credentials into containers",
+            Self::CredentialsGh => "Forward the host's `gh auth token` as GH_TOKEN",
+            Self::CredentialsSshAgent => {
+                "Forward the host SSH agent socket (mounted when a container is created)"
+            }
             Self::AgentCodexEnabled => {
                 "Enable Codex config/auth sync and install-if-missing (requires Node/npm)"
             }
@@ -237,6 +256,9 @@ pub struct SettingsState {
     pub saved: SettingsDraft,
     /// Host availability for agent toggles.
     pub agent_availability: HashMap<SettingsField, AgentAvailability>,
+    /// Agent toggles with no `agents.<kind>.enabled` in the config, whose value
+    /// is resolved from host availability; toggling one makes it explicit
+    pub agent_auto: HashSet<SettingsField>,
 }
 
 // Legacy accessor methods for backwards compatibility with existing code
@@ -270,6 +292,8 @@ pub struct SettingsDraft {
     // Credentials
     pub credentials_docker: bool,
     pub credentials_git: bool,
+    pub credentials_gh: bool,
+    pub credentials_ssh_agent: bool,
     // Agents
     pub agent_codex_enabled: bool,
     pub agent_claude_enabled: bool,
@@ -287,6 +311,7 @@ impl SettingsState {
             saved: draft.clone(),
             draft,
             agent_availability: HashMap::new(),
+            agent_auto: Self::auto_agent_fields(config),
         }
     }
 
@@ -347,6 +372,12 @@ impl SettingsState {
             SettingsField::CredentialsGit => {
                 self.draft.credentials_git = !self.draft.credentials_git;
             }
+            SettingsField::CredentialsGh => {
+                self.draft.credentials_gh = !self.draft.credentials_gh;
+            }
+            SettingsField::CredentialsSshAgent => {
+                self.draft.credentials_ssh_agent = !self.draft.credentials_ssh_agent;
+            }
             SettingsField::AgentCodexEnabled => {
                 self.draft.agent_codex_enabled = !self.draft.agent_codex_enabled;
             }
@@ -361,6 +392,7 @@ impl SettingsState {
             }
             _ => {}
         }
+        self.agent_auto.remove(&field);
         None
     }
 
@@ -415,32 +447,50 @@ impl SettingsState {
         // Credentials
         config.credentials.docker = self.draft.credentials_docker;
         config.credentials.git = self.draft.credentials_git;
-        // Agents
-        config.agents.codex.enabled = Some(
-            self.draft.agent_codex_enabled
-                && self.agent_field_available(SettingsField::AgentCodexEnabled),
-        );
-        config.agents.claude.enabled = Some(
-            self.draft.agent_claude_enabled
-                && self.agent_field_available(SettingsField::AgentClaudeEnabled),
-        );
-        config.agents.cursor.enabled = Some(
-            self.draft.agent_cursor_enabled
-                && self.agent_field_available(SettingsField::AgentCursorEnabled),
-        );
-        config.agents.gemini.enabled = Some(
-            self.draft.agent_gemini_enabled
-                && self.agent_field_available(SettingsField::AgentGeminiEnabled),
-        );
+        config.credentials.gh = self.draft.credentials_gh;
+        config.credentials.ssh_agent = self.draft.credentials_ssh_agent;
+        // Agents: ones left on auto stay unset so they keep following the host
+        for kind in AgentKind::ALL {
+            let Some(field) = Self::agent_field_for_kind(kind) else {
+                continue;
+            };
+            if self.agent_auto.contains(&field) {
+                continue;
+            }
+            let enabled =
+                self.draft.get_value(&field) == "true" && self.agent_field_available(field);
+            let agent = match kind {
+                AgentKind::Codex => &mut config.agents.codex,
+                AgentKind::Claude => &mut config.agents.claude,
+                AgentKind::Cursor => &mut config.agents.cursor,
+                AgentKind::Gemini => &mut config.agents.gemini,
+            };
+            agent.enabled = Some(enabled);
+        }
     }
 
     /// Reset draft from config
     pub fn reset_from_config(&mut self, config: &GlobalConfig) {
         self.draft = SettingsDraft::from_config(config);
         self.saved = self.draft.clone();
+        self.agent_auto = Self::auto_agent_fields(config);
         self.focused = 0;
     }
 
+    /// Whether an agent toggle shows the auto-enable state rather than an
+    /// explicit setting
+    pub fn agent_is_auto(&self, field: SettingsField) -> bool {
+        self.agent_auto.contains(&field)
+    }
+
+    fn auto_agent_fields(config: &GlobalConfig) -> HashSet<SettingsField> {
+        AgentKind::ALL
+            .into_iter()
+            .filter(|kind| Self::agent_enabled_override(config, *kind).is_none())
+            .filter_map(Self::agent_field_for_kind)
+            .collect()
+    }
+
     pub fn apply_agent_host_availability(
         &mut self,
         availability: &[HostAgentAvailability],
@@ -487,7 +537,7 @@ impl SettingsState {
         })
     }
 
-    fn agent_field_available(&self, field: SettingsField) -> bool {
+    pub fn agent_field_available(&self, field: SettingsField) -> bool {
         self.agent_availability
             .get(&field)
             .map(|a| a.available)
@@ -537,6 +587,8 @@ impl SettingsDraft {
             auto_open_browser: config.defaults.auto_open_browser.unwrap_or(true),
             credentials_docker: config.credentials.docker,
             credentials_git: config.credentials.git,
+            credentials_gh: config.credentials.gh,
+            credentials_ssh_agent: config.credentials.ssh_agent,
             agent_codex_enabled: config.agents.codex.enabled.unwrap_or(false),
             agent_claude_enabled: config.agents.claude.enabled.unwrap_or(false),
             agent_cursor_enabled: config.agents.cursor.enabled.unwrap_or(false),
@@ -578,6 +630,15 @@ impl SettingsDraft {
                 "false"
             }
             .to_string(),
+            SettingsField::CredentialsGh => {
+                if self.credentials_gh { "true" } else { "false" }.to_string()
+            }
+            SettingsField::CredentialsSshAgent => if self.credentials_ssh_agent {
+                "true"
+            } else {
+                "false"
+            }
+            .to_string(),
             SettingsField::AgentCodexEnabled => if self.agent_codex_enabled {
                 "true"
             } else {
@@ -633,6 +694,12 @@ impl SettingsDraft {
             SettingsField::CredentialsGit => {
                 self.credentials_git = value == "true" || value == "1" || value == "yes";
             }
+            SettingsField::CredentialsGh => {
+                self.credentials_gh = value == "true" || value == "1" || value == "yes";
+            }
+            SettingsField::CredentialsSshAgent => {
+                self.credentials_ssh_agent = value == "true" || value == "1" || value == "yes";
+            }
             SettingsField::AgentCodexEnabled => {
                 self.agent_codex_enabled = value == "true" || value == "1" || value == "yes";
             }
@@ -945,6 +1012,29 @@ mod tests {
         assert_eq!(updated.agents.gemini.enabled, Some(true));
     }
 
+    #[test]
+    fn test_credential_toggles_apply_to_config() {
+        let config = GlobalConfig::default();
+        let mut state = SettingsState::new(&config);
+
+        for field in SettingsSection::Credentials.fields() {
+            state.focused = SettingsField::all()
+                .iter()
+                .position(|f| f == field)
+                .unwrap();
+            assert_eq!(state.draft.get_value(field), "true");
+            assert!(state.start_edit().is_none());
+            assert_eq!(state.draft.get_value(field), "false");
+        }
+
+        let mut updated = GlobalConfig::default();
+        state.apply_to_config(&mut updated);
+        assert!(!updated.credentials.docker);
+        assert!(!updated.credentials.git);
+        assert!(!updated.credentials.gh);
+        assert!(!updated.credentials.ssh_agent);
+    }
+
     #[test]
     fn test_unavailable_agent_forced_disabled_and_blocked() {
         let mut config = GlobalConfig::default();
@@ -972,4 +1062,41 @@ mod tests {
         state.apply_to_config(&mut updated);
         assert_eq!(updated.agents.codex.enabled, Some(false));
     }
+
+    #[test]
+    fn test_untouched_auto_agents_stay_unset() {
+        let config = GlobalConfig::default();
+        let mut state = SettingsState::new(&config);
+        state.apply_agent_host_availability(
+            &[
+                HostAgentAvailability {
+                    agent: AgentKind::Claude,
+                    available: true,
+                    reason: None,
+                },
+                HostAgentAvailability {
+                    agent: AgentKind::Cursor,
+                    available: true,
+                    reason: None,
+                },
+            ],
+            &config,
+        );
+        // Resolved from the host while unset
+        assert!(state.draft.agent_claude_enabled);
+        assert!(state.agent_is_auto(SettingsField::AgentClaudeEnabled));
+
+        state.focused = SettingsField::all()
+            .iter()
+            .position(|f| *f == SettingsField::AgentCursorEnabled)
+            .unwrap();
+        assert!(state.toggle_field().is_none());
+        assert!(!state.agent_is_auto(SettingsField::AgentCursorEnabled));
+
+        let mut updated = config.clone();
+        state.apply_to_config(&mut updated);
+        assert_eq!(updated.agents.claude.enabled, None);
+        assert_eq!(updated.agents.cursor.enabled, Some(false));
+        assert_eq!(updated.agents.codex.enabled, None);
+    }
 }
@@ -155,6 +155,9 @@ pub struct ShellConfig {
     pub browser_queue_path: Option<String>,
     /// Whether auto-forwarding is enabled (global setting)
     pub auto_forward_enabled: bool,
+    /// Host address configured for each TCP port; other ports forwarded on
+    /// demand use the default address
+    pub bind_addresses: std::collections::HashMap<u16, std::net::IpAddr>,
 }
 
 /// Why the relay loop stopped
@@ -167,6 +170,16 @@ pub enum ShellExitReason {
     Error(io::Error),
 }
 
+impl std::fmt::Display for ShellExitReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShellExitReason::Detached => write!(f, "detached"),
+            ShellExitReason::Exited => write!(f, "exited"),
+            ShellExitReason::Error(e) => write!(f, "error: {}", e),
+        }
+    }
+}
+
 // --- Unix-only: PTY shell implementation ---
 
 #[cfg(unix)]
@@ -195,6 +208,7 @@ mod pty {
         runtime_prefix: Vec<String>,
         container_id: String,
         auto_forward_enabled: bool,
+        bind_addresses: std::collections::HashMap<u16, std::net::IpAddr>,
         /// Forwarders spawned on-demand for browser URL requests, kept alive for the shell's lifetime
         on_demand_forwarders: Vec<crate::tunnel::PortForwarder>,
         /// Ports we've already attempted to forward on-demand, to avoid retrying on repeat URLs
@@ -272,6 +286,7 @@ mod pty {
                 runtime_prefix: config.runtime_prefix.clone(),
                 container_id: config.container_id.clone(),
                 auto_forward_enabled: config.auto_forward_enabled,
+                bind_addresses: config.bind_addresses.clone(),
                 on_demand_forwarders: Vec::new(),
                 attempted_on_demand_ports: std::collections::HashSet::new(),
             })
@@ -475,8 +490,13 @@ mod pty {
                         self.runtime_program.clone(),
                         self.runtime_prefix.clone(),
                         self.container_id.clone(),
+                        self.bind_addresses
+                            .get(&port)
+                            .copied()
+                            .unwrap_or(devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS),
                         port,
                         port,
+                        devc_config::PortTransport::Tcp,
                     )) {
                         self.on_demand_forwarders.push(forwarder);
                     }
@@ -590,6 +610,7 @@ mod tests {
             env: std::collections::HashMap::new(),
             browser_queue_path: None,
             auto_forward_enabled: false,
+            bind_addresses: std::collections::HashMap::new(),
         };
         assert_eq!(config.container_id, "abc123");
         assert_eq!(config.shell, "/bin/bash");
@@ -607,6 +628,7 @@ mod tests {
             env: std::collections::HashMap::new(),
             browser_queue_path: None,
             auto_forward_enabled: false,
+            bind_addresses: std::collections::HashMap::new(),
         };
         assert_eq!(config.user, Some("root".to_string()));
         assert_eq!(config.working_dir, Some("/workspace".to_string()));
@@ -624,6 +646,7 @@ mod tests {
             env: std::collections::HashMap::new(),
             browser_queue_path: None,
             auto_forward_enabled: false,
+            bind_addresses: std::collections::HashMap::new(),
         };
         assert_eq!(config.runtime_program, "flatpak-spawn");
         assert_eq!(config.runtime_prefix, vec!["--host", "podman"]);
@@ -2,6 +2,7 @@
 
 #[cfg(unix)]
 use crate::shell::PtyShell;
+use crate::shell::ShellExitReason;
 use std::collections::HashMap;
 
 /// Active shell session state (persistent across attach/detach cycles)
@@ -21,11 +22,15 @@ pub struct ShellSession {
     pub host_workspace_path: Option<String>,
     #[cfg(unix)]
     pub pty: Option<PtyShell>,
+    /// Why the shell ended, if it has; the session is kept so it can be
+    /// restarted or closed from the TUI
+    pub exit_reason: Option<ShellExitReason>,
 }
 
 /// State for persistent shell sessions.
 pub struct ShellState {
-    /// Persistent shell sessions keyed by container_id
+    /// Persistent shell sessions keyed by container_id, or `container_id/service`
+    /// for companion compose services
     pub shell_sessions: HashMap<String, ShellSession>,
     /// Which container's shell is currently active (when View::Shell)
     pub active_shell_container: Option<String>,
@@ -38,6 +43,13 @@ impl ShellState {
             active_shell_container: None,
         }
     }
+
+    /// Drop every session belonging to a devc container, including its
+    /// companion compose service sessions.
+    pub fn remove_container_sessions(&mut self, container_id: &str) {
+        self.shell_sessions
+            .retain(|_, session| session.container_id != container_id);
+    }
 }
 
 impl Default for ShellState {
@@ -0,0 +1,89 @@
+//! Transient notifications for background operations
+//!
+//! Toasts are drawn over whatever view is active and never take input; they
+//! expire on their own after [`TOAST_TTL`].
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How long a toast stays on screen
+pub const TOAST_TTL: Duration = Duration::from_secs(6);
+/// Oldest toasts are dropped beyond this many
+pub const MAX_TOASTS: usize = 4;
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub message: String,
+    pub success: bool,
+    pub shown_at: Instant,
+}
+
+/// Stack of visible toasts, newest last
+#[derive(Debug, Default)]
+pub struct Toasts {
+    items: VecDeque<Toast>,
+}
+
+impl Toasts {
+    pub fn push(&mut self, message: impl Into<String>, success: bool) {
+        self.items.push_back(Toast {
+            message: message.into(),
+            success,
+            shown_at: Instant::now(),
+        });
+        while self.items.len() > MAX_TOASTS {
+            self.items.pop_front();
+        }
+    }
+
+    /// Drop toasts older than [`TOAST_TTL`] as of `now`
+    pub fn expire(&mut self, now: Instant) {
+        self.items
+            .retain(|toast| now.saturating_duration_since(toast.shown_at) < TOAST_TTL);
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Toast> {
+        self.items.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_keeps_newest_toasts() {
+        let mut toasts = Toasts::default();
+        for i in 0..MAX_TOASTS + 2 {
+            toasts.push(format!("toast {}", i), true);
+        }
+        assert_eq!(toasts.len(), MAX_TOASTS);
+        assert_eq!(toasts.iter().next().unwrap().message, "toast 2");
+        assert_eq!(
+            toasts.iter().last().unwrap().message,
+            format!("toast {}", MAX_TOASTS + 1)
+        );
+    }
+
+    #[test]
+    fn test_expire_drops_old_toasts() {
+        let mut toasts = Toasts::default();
+        toasts.push("old", true);
+        toasts.push("new", false);
+        let start = toasts.iter().next().unwrap().shown_at;
+
+        toasts.expire(start + TOAST_TTL / 2);
+        assert_eq!(toasts.len(), 2);
+
+        toasts.expire(start + TOAST_TTL * 2);
+        assert!(toasts.is_empty());
+    }
+}
@@ -3,9 +3,12 @@
 //! Uses socat inside the container to forward ports directly through `podman exec`
 //! or `docker exec`, without requiring SSH.
 
+use devc_config::PortTransport;
+use std::net::{IpAddr, SocketAddr};
 use std::process::Stdio;
-use tokio::io::AsyncWriteExt;
-use tokio::net::TcpListener;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, UdpSocket};
 use tokio::process::Command;
 use tokio::task::JoinHandle;
 
@@ -82,6 +85,8 @@ pub async fn install_socat(program: &str, prefix: &[String], container_id: &str)
 
 /// Handle to a running port forwarder
 pub struct PortForwarder {
+    /// Host address the listener is bound to
+    pub bind_address: IpAddr,
     /// Local port on host
     pub local_port: u16,
     /// Remote port in container
@@ -113,6 +118,20 @@ impl PortForwarder {
     pub fn is_running(&self) -> bool {
         !self.listener_handle.is_finished()
     }
+
+    /// Warning to surface when the forward is reachable from other machines
+    pub fn exposure_warning(&self) -> Option<String> {
+        exposure_warning(self.bind_address, self.local_port)
+    }
+}
+
+fn exposure_warning(bind_address: IpAddr, local_port: u16) -> Option<String> {
+    (!bind_address.is_loopback()).then(|| {
+        format!(
+            "Warning: port {} is listening on {} and reachable from other machines",
+            local_port, bind_address
+        )
+    })
 }
 
 impl Drop for PortForwarder {
@@ -158,14 +177,17 @@ impl std::fmt::Display for ForwarderError {
 
 impl std::error::Error for ForwarderError {}
 
-/// Spawn a port forwarder that forwards connections from localhost to the container
+/// Spawn a port forwarder that forwards connections from the host to the container
 ///
 /// # Arguments
 /// * `program` - Runtime program (e.g. "docker", "flatpak-spawn")
 /// * `prefix` - Runtime prefix args (e.g. ["--host", "podman"])
 /// * `container_id` - Container ID to forward to
+/// * `bind_address` - Host address to listen on; anything but loopback is
+///   logged as a warning since it exposes the port to the network
 /// * `local_port` - Port on host to listen on
 /// * `remote_port` - Port in container to forward to
+/// * `transport` - TCP relays each connection, UDP each client address
 ///
 /// # Returns
 /// A `PortForwarder` that can be used to monitor and stop the forwarding
@@ -173,18 +195,40 @@ pub async fn spawn_forwarder(
     program: String,
     prefix: Vec<String>,
     container_id: String,
+    bind_address: IpAddr,
     local_port: u16,
     remote_port: u16,
+    transport: PortTransport,
 ) -> Result<PortForwarder, ForwarderError> {
+    if let Some(warning) = exposure_warning(bind_address, local_port) {
+        tracing::warn!("{}", warning);
+    }
+
+    if transport == PortTransport::Udp {
+        return spawn_udp_forwarder(
+            program,
+            prefix,
+            container_id,
+            bind_address,
+            local_port,
+            remote_port,
+        )
+        .await;
+    }
+
     // Try to bind the local port
-    let listener = TcpListener::bind(format!("127.0.0.1:{}", local_port))
+    let listener = TcpListener::bind(SocketAddr::new(bind_address, local_port))
         .await
         .map_err(|e| ForwarderError::PortInUse(local_port, e.to_string()))?;
 
     let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
 
     let listener_handle = tokio::spawn(async move {
+        // Connections live in a JoinSet owned by this task, so aborting the
+        // listener also aborts them and their kill_on_drop exec children.
+        let mut connections = tokio::task::JoinSet::new();
         loop {
+            while connections.try_join_next().is_some() {}
             let mut shutdown_rx_clone = shutdown_rx.clone();
 
             tokio::select! {
@@ -204,7 +248,7 @@ pub async fn spawn_forwarder(
                             let prog = program.clone();
                             let pfx = prefix.clone();
                             let rp = remote_port;
-                            tokio::spawn(async move {
+                            connections.spawn(async move {
                                 if let Err(e) = handle_connection(stream, &prog, &pfx, &cid, rp).await {
                                     tracing::debug!("Connection error: {}", e);
                                 }
@@ -221,6 +265,7 @@ pub async fn spawn_forwarder(
     });
 
     Ok(PortForwarder {
+        bind_address,
         local_port,
         remote_port,
         listener_handle,
@@ -236,7 +281,7 @@ async fn handle_connection(
     container_id: &str,
     remote_port: u16,
 ) -> Result<(), std::io::Error> {
-    let socat_cmd = format!("socat - TCP:localhost:{}", remote_port);
+    let socat_cmd = socat_command(PortTransport::Tcp, remote_port);
 
     let mut child = Command::new(program)
         .args(prefix)
@@ -283,6 +328,144 @@ async fn handle_connection(
     Ok(())
 }
 
+/// socat invocation relaying stdin/stdout to `remote_port` inside the container
+///
+/// For UDP, socat sends each read from stdin as one datagram, so the relay is
+/// fed exactly one datagram before stdin is closed. The block size fits any
+/// datagram and the close timeout leaves time for the replies to come back.
+fn socat_command(transport: PortTransport, remote_port: u16) -> String {
+    match transport {
+        PortTransport::Tcp => format!("socat - TCP:localhost:{}", remote_port),
+        PortTransport::Udp => format!(
+            "socat -b {} -t {} - UDP:localhost:{}",
+            UDP_MAX_DATAGRAM, UDP_REPLY_TIMEOUT_SECS, remote_port
+        ),
+    }
+}
+
+/// Largest UDP payload, used for buffers and socat's block size
+const UDP_MAX_DATAGRAM: usize = 65536;
+
+/// How long a UDP relay waits for replies after sending its datagram
+const UDP_REPLY_TIMEOUT_SECS: u64 = 5;
+
+/// Datagrams relayed at once; further ones are dropped until a relay finishes,
+/// as a congested UDP path would
+const UDP_MAX_IN_FLIGHT: usize = 64;
+
+/// UDP variant of [`spawn_forwarder`]
+///
+/// A pipe into `exec` carries bytes, not datagrams, so each datagram gets its
+/// own exec'd socat: it is written whole, stdin is closed, and every read of
+/// socat's output goes back to the sender as one reply datagram. Relays run
+/// as separate tasks, so a slow one never holds up other clients.
+async fn spawn_udp_forwarder(
+    program: String,
+    prefix: Vec<String>,
+    container_id: String,
+    bind_address: IpAddr,
+    local_port: u16,
+    remote_port: u16,
+) -> Result<PortForwarder, ForwarderError> {
+    let socket = UdpSocket::bind(SocketAddr::new(bind_address, local_port))
+        .await
+        .map(Arc::new)
+        .map_err(|e| ForwarderError::PortInUse(local_port, e.to_string()))?;
+
+    let (shutdown_tx, mut shutdown_rx) = tokio::sync::watch::channel(false);
+
+    let listener_handle = tokio::spawn(async move {
+        // As with TCP, relays are owned by this task so aborting it kills them
+        let mut relays = tokio::task::JoinSet::new();
+        let mut buf = vec![0u8; UDP_MAX_DATAGRAM];
+        loop {
+            while relays.try_join_next().is_some() {}
+
+            tokio::select! {
+                biased;
+
+                _ = shutdown_rx.changed() => {
+                    if *shutdown_rx.borrow() {
+                        tracing::debug!("UDP forwarder shutdown signal received");
+                        break;
+                    }
+                }
+
+                recv = socket.recv_from(&mut buf) => {
+                    let (len, peer) = match recv {
+                        Ok(r) => r,
+                        Err(e) => {
+                            tracing::warn!("UDP receive error (continuing): {}", e);
+                            continue;
+                        }
+                    };
+                    if relays.len() >= UDP_MAX_IN_FLIGHT {
+                        tracing::debug!("UDP relay limit reached, dropping datagram from {}", peer);
+                        continue;
+                    }
+                    let datagram = buf[..len].to_vec();
+                    let socket = Arc::clone(&socket);
+                    let cid = container_id.clone();
+                    let prog = program.clone();
+                    let pfx = prefix.clone();
+                    relays.spawn(async move {
+                        if let Err(e) =
+                            relay_datagram(&socket, peer, &datagram, &prog, &pfx, &cid, remote_port).await
+                        {
+                            tracing::debug!("UDP relay error: {}", e);
+                        }
+                    });
+                }
+            }
+        }
+    });
+
+    Ok(PortForwarder {
+        bind_address,
+        local_port,
+        remote_port,
+        listener_handle,
+        shutdown_tx: Some(shutdown_tx),
+    })
+}
+
+/// Send one datagram into the container and relay the replies back to `peer`
+async fn relay_datagram(
+    socket: &UdpSocket,
+    peer: SocketAddr,
+    datagram: &[u8],
+    program: &str,
+    prefix: &[String],
+    container_id: &str,
+    remote_port: u16,
+) -> Result<(), std::io::Error> {
+    let mut child = Command::new(program)
+        .args(prefix)
+        .args(["exec", "-i", container_id, "sh", "-c"])
+        .arg(socat_command(PortTransport::Udp, remote_port))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin must exist when piped");
+    let mut stdout = child.stdout.take().expect("stdout must exist when piped");
+
+    stdin.write_all(datagram).await?;
+    drop(stdin);
+
+    let mut reply = vec![0u8; UDP_MAX_DATAGRAM];
+    loop {
+        let n = stdout.read(&mut reply).await?;
+        if n == 0 {
+            break;
+        }
+        socket.send_to(&reply[..n], peer).await?;
+    }
+    Ok(())
+}
+
 /// Spawn the platform-specific browser command for a URL.
 fn spawn_browser(url: &str) -> Result<(), String> {
     #[cfg(target_os = "linux")]
@@ -346,9 +529,10 @@ mod tests {
     use super::*;
     use std::net::TcpStream;
 
-    /// Helper to check if a port is available (not bound)
-    fn port_is_available(port: u16) -> bool {
-        std::net::TcpListener::bind(format!("127.0.0.1:{}", port)).is_ok()
+    /// Helper to check if a port is available (not bound) on the address a
+    /// forward would listen on
+    fn port_is_available(bind_address: IpAddr, port: u16) -> bool {
+        std::net::TcpListener::bind(SocketAddr::new(bind_address, port)).is_ok()
     }
 
     /// Helper to check if a port is listening (can connect)
@@ -368,6 +552,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_socat_command_selects_transport() {
+        assert_eq!(
+            socat_command(PortTransport::Tcp, 8080),
+            "socat - TCP:localhost:8080"
+        );
+        assert_eq!(
+            socat_command(PortTransport::Udp, 53),
+            "socat -b 65536 -t 5 - UDP:localhost:53"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_udp_forwarder_binds_udp_port() {
+        if !can_bind_localhost() {
+            return;
+        }
+        let port = 19882;
+        let forwarder = spawn_forwarder(
+            "docker".to_string(),
+            vec![],
+            "fake-container".to_string(),
+            devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS,
+            port,
+            53,
+            PortTransport::Udp,
+        )
+        .await
+        .expect("Should bind UDP port");
+
+        assert!(std::net::UdpSocket::bind(format!("127.0.0.1:{}", port)).is_err());
+        // The TCP side of the same port is left alone
+        assert!(port_is_available(
+            devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS,
+            port
+        ));
+        assert!(forwarder.is_running());
+
+        forwarder.stop().await;
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(std::net::UdpSocket::bind(format!("127.0.0.1:{}", port)).is_ok());
+    }
+
     #[tokio::test]
     async fn test_forwarder_binds_port() {
         if !can_bind_localhost() {
@@ -378,7 +605,7 @@ mod tests {
 
         // Port should be available before
         assert!(
-            port_is_available(port),
+            port_is_available(devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS, port),
             "Port should be available before test"
         );
 
@@ -387,15 +614,17 @@ mod tests {
             "docker".to_string(),
             vec![],
             "fake-container".to_string(),
+            devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS,
             port,
             3000,
+            PortTransport::Tcp,
         )
         .await
         .expect("Should bind port");
 
         // Port should no longer be available (forwarder has it)
         assert!(
-            !port_is_available(port),
+            !port_is_available(devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS, port),
             "Port should be bound by forwarder"
         );
 
@@ -413,7 +642,7 @@ mod tests {
 
         // Port should be available again
         assert!(
-            port_is_available(port),
+            port_is_available(devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS, port),
             "Port should be released after stop"
         );
     }
@@ -427,7 +656,7 @@ mod tests {
         let port = 19877;
 
         assert!(
-            port_is_available(port),
+            port_is_available(devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS, port),
             "Port should be available before test"
         );
 
@@ -437,13 +666,18 @@ mod tests {
                 "docker".to_string(),
                 vec![],
                 "fake-container".to_string(),
+                devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS,
                 port,
                 3000,
+                PortTransport::Tcp,
             )
             .await
             .expect("Should bind port");
 
-            assert!(!port_is_available(port), "Port should be bound");
+            assert!(
+                !port_is_available(devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS, port),
+                "Port should be bound"
+            );
             assert!(forwarder.is_running());
 
             // forwarder is dropped here without calling stop()
@@ -454,11 +688,39 @@ mod tests {
 
         // Port should be released by Drop impl
         assert!(
-            port_is_available(port),
+            port_is_available(devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS, port),
             "Port should be released after drop"
         );
     }
 
+    /// A forward on a non-loopback address holds the port on that address
+    #[tokio::test]
+    async fn test_forwarder_binds_configured_address() {
+        if !can_bind_localhost() {
+            return;
+        }
+        let port = 19883;
+        let any = IpAddr::V4(std::net::Ipv4Addr::UNSPECIFIED);
+        assert!(port_is_available(any, port));
+
+        let forwarder = spawn_forwarder(
+            "docker".to_string(),
+            vec![],
+            "fake-container".to_string(),
+            any,
+            port,
+            3000,
+            PortTransport::Tcp,
+        )
+        .await
+        .expect("Should bind port");
+        assert!(!port_is_available(any, port), "Port should be bound");
+
+        forwarder.stop().await;
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(port_is_available(any, port), "Port should be released");
+    }
+
     #[tokio::test]
     async fn test_port_in_use_error() {
         if !can_bind_localhost() {
@@ -475,8 +737,10 @@ mod tests {
             "docker".to_string(),
             vec![],
             "fake-container".to_string(),
+            devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS,
             port,
             3000,
+            PortTransport::Tcp,
         )
         .await;
 
@@ -499,8 +763,10 @@ mod tests {
             "docker".to_string(),
             vec![],
             "container1".to_string(),
+            devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS,
             port1,
             3000,
+            PortTransport::Tcp,
         )
         .await
         .expect("Should bind port1");
@@ -509,29 +775,46 @@ mod tests {
             "docker".to_string(),
             vec![],
             "container2".to_string(),
+            devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS,
             port2,
             8080,
+            PortTransport::Tcp,
         )
         .await
         .expect("Should bind port2");
 
         assert!(forwarder1.is_running());
         assert!(forwarder2.is_running());
-        assert!(!port_is_available(port1));
-        assert!(!port_is_available(port2));
+        assert!(!port_is_available(
+            devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS,
+            port1
+        ));
+        assert!(!port_is_available(
+            devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS,
+            port2
+        ));
 
         // Stop one, verify the other still works
         forwarder1.stop().await;
         tokio::time::sleep(std::time::Duration::from_millis(50)).await;
 
-        assert!(port_is_available(port1), "Port1 should be released");
-        assert!(!port_is_available(port2), "Port2 should still be bound");
+        assert!(
+            port_is_available(devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS, port1),
+            "Port1 should be released"
+        );
+        assert!(
+            !port_is_available(devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS, port2),
+            "Port2 should still be bound"
+        );
         assert!(forwarder2.is_running());
 
         forwarder2.stop().await;
         tokio::time::sleep(std::time::Duration::from_millis(50)).await;
 
-        assert!(port_is_available(port2), "Port2 should be released");
+        assert!(
+            port_is_available(devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS, port2),
+            "Port2 should be released"
+        );
     }
 
     #[tokio::test]
@@ -545,8 +828,10 @@ mod tests {
             "docker".to_string(),
             vec![],
             "fake-container".to_string(),
+            devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS,
             port,
             3000,
+            PortTransport::Tcp,
         )
         .await
         .expect("Should bind port");
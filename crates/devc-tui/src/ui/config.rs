@@ -0,0 +1,172 @@
+use super::*;
+
+/// Draw the read-only resolved devcontainer.json viewer
+pub(super) fn draw_config_view(frame: &mut Frame, app: &App, area: Rect) {
+    let container_name = app
+        .selected_container()
+        .map(|c| c.name.as_str())
+        .unwrap_or("Unknown");
+
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let total_lines = app.config_view_lines.len();
+
+    let text: Vec<Line> = app
+        .config_view_lines
+        .iter()
+        .enumerate()
+        .skip(app.config_view_scroll)
+        .take(inner_height)
+        .map(|(i, line)| {
+            let mut spans = vec![Span::styled(
+                format!("{:>4} ", i + 1),
+                Style::default().fg(Color::DarkGray),
+            )];
+            spans.extend(highlight_json_line(line));
+            Line::from(spans)
+        })
+        .collect();
+
+    let title = if total_lines > 0 {
+        let percent = if total_lines <= inner_height {
+            100
+        } else {
+            ((app.config_view_scroll + inner_height).min(total_lines) * 100) / total_lines
+        };
+        format!(
+            " devcontainer.json: {} [{}/{}] {}% ",
+            container_name,
+            app.config_view_scroll + 1,
+            total_lines,
+            percent
+        )
+    } else {
+        format!(" devcontainer.json: {} (empty) ", container_name)
+    };
+
+    let config = Paragraph::new(text).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+
+    frame.render_widget(config, area);
+
+    // Render scrollbar if content exceeds visible area
+    if total_lines > inner_height {
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("▲"))
+            .end_symbol(Some("▼"));
+        let mut scrollbar_state = ScrollbarState::new(total_lines.saturating_sub(inner_height))
+            .position(app.config_view_scroll);
+
+        let scrollbar_area = Rect {
+            x: area.x + area.width.saturating_sub(1),
+            y: area.y + 1,
+            width: 1,
+            height: area.height.saturating_sub(2),
+        };
+        frame.render_stateful_widget(scrollbar, scrollbar_area, &mut scrollbar_state);
+    }
+}
+
+/// Split one line of pretty-printed JSON into styled spans.
+///
+/// Object keys are cyan, string values green, numbers and booleans yellow,
+/// and `null` dark gray. Punctuation and whitespace are left unstyled.
+fn highlight_json_line(line: &str) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut chars = line.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        if c == '"' {
+            // Consume the full string literal, honoring backslash escapes
+            let mut end = line.len();
+            let mut escaped = false;
+            for (i, ch) in chars.by_ref() {
+                if escaped {
+                    escaped = false;
+                } else if ch == '\\' {
+                    escaped = true;
+                } else if ch == '"' {
+                    end = i + 1;
+                    break;
+                }
+            }
+            let is_key = line[end..].trim_start().starts_with(':');
+            let color = if is_key { Color::Cyan } else { Color::Green };
+            flush_plain(&mut spans, &mut plain);
+            spans.push(Span::styled(
+                line[start..end].to_string(),
+                Style::default().fg(color),
+            ));
+        } else if c == '-' || c.is_ascii_alphanumeric() {
+            let mut end = start + c.len_utf8();
+            while let Some(&(i, ch)) = chars.peek() {
+                if ch.is_ascii_alphanumeric() || matches!(ch, '.' | '-' | '+') {
+                    end = i + ch.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let word = &line[start..end];
+            let color = if word == "null" {
+                Color::DarkGray
+            } else {
+                Color::Yellow
+            };
+            flush_plain(&mut spans, &mut plain);
+            spans.push(Span::styled(word.to_string(), Style::default().fg(color)));
+        } else {
+            plain.push(c);
+        }
+    }
+    flush_plain(&mut spans, &mut plain);
+    spans
+}
+
+fn flush_plain(spans: &mut Vec<Span<'static>>, plain: &mut String) {
+    if !plain.is_empty() {
+        spans.push(Span::raw(std::mem::take(plain)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn styled(spans: &[Span]) -> Vec<(String, Option<Color>)> {
+        spans
+            .iter()
+            .map(|s| (s.content.to_string(), s.style.fg))
+            .collect()
+    }
+
+    #[test]
+    fn test_highlight_key_and_string_value() {
+        let spans = highlight_json_line(r#"  "image": "ubuntu:22.04","#);
+        assert_eq!(
+            styled(&spans),
+            vec![
+                ("  ".to_string(), None),
+                ("\"image\"".to_string(), Some(Color::Cyan)),
+                (": ".to_string(), None),
+                ("\"ubuntu:22.04\"".to_string(), Some(Color::Green)),
+                (",".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_highlight_escaped_quote_and_literals() {
+        let spans = highlight_json_line(r#"    "a\"b": -1.5e3"#);
+        let styled = styled(&spans);
+        assert_eq!(styled[1], ("\"a\\\"b\"".to_string(), Some(Color::Cyan)));
+        assert_eq!(styled[3], ("-1.5e3".to_string(), Some(Color::Yellow)));
+
+        let spans = highlight_json_line("  true,");
+        assert_eq!(spans[1].style.fg, Some(Color::Yellow));
+    }
+}
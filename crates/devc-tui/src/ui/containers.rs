@@ -15,15 +15,19 @@ pub(super) fn draw_containers(frame: &mut Frame, app: &mut App, area: Rect) {
         return;
     }
 
+    let columns = ContainerColumns::for_width(area.width, app.full_ids);
+
     // Define header row
-    let header = Row::new(vec![
+    let header = Row::new(columns.select(vec![
         Cell::from(" "),
         Cell::from("Name"),
         Cell::from("Source"),
         Cell::from("Status"),
         Cell::from("Provider"),
+        Cell::from("Ports"),
+        Cell::from("ID"),
         Cell::from("Workspace"),
-    ])
+    ]))
     .style(
         Style::default()
             .fg(Color::Cyan)
@@ -33,13 +37,23 @@ pub(super) fn draw_containers(frame: &mut Frame, app: &mut App, area: Rect) {
 
     // Build data rows
     let display_names = display_name_map(&app.containers);
-    let rows: Vec<Row> = app
-        .containers
-        .iter()
-        .map(|container| {
+    let grouped = app.group_by_workspace;
+    let mut rows: Vec<Row> = Vec::with_capacity(app.containers.len());
+    let mut selected_row = app.selected;
+    let mut current_workspace: Option<&std::path::Path> = None;
+    for (index, container) in app.containers.iter().enumerate() {
+        if grouped && current_workspace != Some(container.workspace_path.as_path()) {
+            current_workspace = Some(container.workspace_path.as_path());
+            rows.push(workspace_header_row(&container.workspace_path, columns));
+        }
+        if index == app.selected {
+            selected_row = rows.len();
+        }
+        rows.push({
             let status_symbol = match container.status {
                 DevcContainerStatus::Available => "◌",
                 DevcContainerStatus::Running => "●",
+                DevcContainerStatus::Paused => "‖",
                 DevcContainerStatus::Stopped => "○",
                 DevcContainerStatus::Building => "◐",
                 DevcContainerStatus::Built => "◑",
@@ -51,6 +65,7 @@ pub(super) fn draw_containers(frame: &mut Frame, app: &mut App, area: Rect) {
             let status_color = match container.status {
                 DevcContainerStatus::Available => Color::DarkGray,
                 DevcContainerStatus::Running => Color::Green,
+                DevcContainerStatus::Paused => Color::Magenta,
                 DevcContainerStatus::Stopped => Color::DarkGray,
                 DevcContainerStatus::Building => Color::Yellow,
                 DevcContainerStatus::Built => Color::Blue,
@@ -66,16 +81,29 @@ pub(super) fn draw_containers(frame: &mut Frame, app: &mut App, area: Rect) {
             } else {
                 workspace
             };
+            let workspace_cell = if app.missing_workspaces.contains(&container.id) {
+                Cell::from(format!("⚠ {} (missing)", workspace_display))
+                    .style(Style::default().fg(Color::Yellow))
+            } else if grouped {
+                // The group header already names the folder
+                Cell::from("")
+            } else {
+                Cell::from(workspace_display).style(Style::default().fg(Color::DarkGray))
+            };
 
-            // Show [S] indicator if there's an active shell session for this container
-            let has_shell = app.shell_state.shell_sessions.contains_key(&container.id);
+            // Show [S] indicator if there's a shell session for this container
+            let shell = app.shell_state.shell_sessions.get(&container.id);
             let base_display = display_names
                 .get(&container.id)
                 .cloned()
                 .unwrap_or_else(|| container.name.clone());
 
-            let name_display = if has_shell {
-                format!("{} [S]", base_display)
+            let name_display = if let Some(session) = shell {
+                if session.exit_reason.is_some() {
+                    format!("{} [S ended]", base_display)
+                } else {
+                    format!("{} [S]", base_display)
+                }
             } else if container.compose_project.is_some() {
                 let suffix = match app.compose_state.services.get(&container.id) {
                     Some(s) => format!(":{}", s.len()),
@@ -85,38 +113,135 @@ pub(super) fn draw_containers(frame: &mut Frame, app: &mut App, area: Rect) {
             } else {
                 base_display
             };
+            let name_display = if grouped {
+                format!("  {}", name_display)
+            } else {
+                name_display
+            };
 
-            Row::new(vec![
+            // Active forwards come from the in-memory forwarder map, not the runtime
+            let ports_display = container
+                .container_id
+                .as_deref()
+                .map(|cid| app.port_state.forward_summary(cid))
+                .unwrap_or_default();
+
+            Row::new(columns.select(vec![
                 Cell::from(status_symbol).style(Style::default().fg(status_color)),
                 Cell::from(name_display).style(Style::default().bold()),
                 Cell::from(container.source.to_string())
                     .style(Style::default().fg(Color::DarkGray)),
                 Cell::from(container.status.to_string()).style(Style::default().fg(status_color)),
                 Cell::from(container.provider.to_string()),
-                Cell::from(workspace_display).style(Style::default().fg(Color::DarkGray)),
-            ])
-        })
-        .collect();
+                Cell::from(ports_display).style(Style::default().fg(Color::Green)),
+                Cell::from(
+                    container
+                        .container_id
+                        .as_deref()
+                        .map(|id| format_id(id, columns.full_ids))
+                        .unwrap_or_default(),
+                )
+                .style(Style::default().fg(Color::DarkGray)),
+                workspace_cell,
+            ]))
+        });
+    }
 
     // Define column widths
-    let widths = [
-        Constraint::Length(3),  // Status icon
-        Constraint::Length(24), // Name
-        Constraint::Length(8),  // Source
-        Constraint::Length(12), // Status
-        Constraint::Length(8),  // Provider
-        Constraint::Min(10),    // Workspace (takes remaining)
-    ];
+    let widths = columns.select(vec![
+        Constraint::Length(3),                                      // Status icon
+        Constraint::Min(20),                                        // Name
+        Constraint::Length(8),                                      // Source
+        Constraint::Length(12),                                     // Status
+        Constraint::Length(8),                                      // Provider
+        Constraint::Length(14),                                     // Ports
+        Constraint::Length(if columns.full_ids { 64 } else { 12 }), // Container ID
+        Constraint::Min(10),                                        // Workspace (takes remaining)
+    ]);
 
+    let title = match (app.sort_order, grouped) {
+        (SortOrder::Status, false) => " Containers ".to_string(),
+        (SortOrder::Status, true) => " Containers (by workspace) ".to_string(),
+        (order, false) => format!(" Containers (by {}) ", order),
+        (order, true) => format!(" Containers (by workspace, {}) ", order),
+    };
     let table = Table::new(rows, widths)
         .header(header)
-        .block(Block::default().title(" Containers ").borders(Borders::ALL))
+        .block(Block::default().title(title).borders(Borders::ALL))
         .highlight_style(Style::default().bg(Color::DarkGray).fg(Color::White))
         .highlight_symbol("▶ ");
 
+    // Header rows shift the table's row indices away from container indices
+    app.containers_table_state.select(Some(selected_row));
     frame.render_stateful_widget(table, area, &mut app.containers_table_state);
 }
 
+/// Which optional columns of the containers table fit the terminal
+///
+/// Ports, then the container ID, are dropped as the table narrows so the
+/// Name column keeps its minimum width; full IDs collapse to short ones
+/// unless there is room for all 64 characters.
+#[derive(Clone, Copy)]
+struct ContainerColumns {
+    ports: bool,
+    id: bool,
+    full_ids: bool,
+}
+
+impl ContainerColumns {
+    /// Narrowest table that still shows the Ports column
+    const PORTS_MIN_WIDTH: u16 = 100;
+    /// Narrowest table that still shows the ID column
+    const ID_MIN_WIDTH: u16 = 86;
+    /// Narrowest table that shows full 64-character IDs
+    const FULL_ID_MIN_WIDTH: u16 = 150;
+
+    fn for_width(width: u16, full_ids: bool) -> Self {
+        Self {
+            ports: width >= Self::PORTS_MIN_WIDTH,
+            id: width >= Self::ID_MIN_WIDTH,
+            full_ids: full_ids && width >= Self::FULL_ID_MIN_WIDTH,
+        }
+    }
+
+    /// Keep the visible columns of a full row (Ports is column 5, ID column 6)
+    fn select<T>(self, cells: Vec<T>) -> Vec<T> {
+        cells
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| match i {
+                5 => self.ports,
+                6 => self.id,
+                _ => true,
+            })
+            .map(|(_, cell)| cell)
+            .collect()
+    }
+}
+
+/// Header row naming a workspace folder in the grouped container list
+fn workspace_header_row(workspace: &std::path::Path, columns: ContainerColumns) -> Row<'static> {
+    let folder = workspace
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| workspace.display().to_string());
+    Row::new(columns.select(vec![
+        Cell::from("▾"),
+        Cell::from(folder),
+        Cell::from(""),
+        Cell::from(""),
+        Cell::from(""),
+        Cell::from(""),
+        Cell::from(""),
+        Cell::from(workspace.display().to_string()),
+    ]))
+    .style(
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
 /// Draw discovered containers using Table widget with headers
 pub(super) fn draw_discovered_containers(frame: &mut Frame, app: &mut App, area: Rect) {
     if app.discovered_containers.is_empty() {
@@ -297,10 +297,16 @@ pub(super) fn draw_provider_detail(frame: &mut Frame, app: &App, area: Rect) {
 pub(super) fn build_detail_text(
     container: &devc_core::ContainerState,
     details: Option<&devc_provider::ContainerDetails>,
+    disk: Option<&devc_provider::ContainerDiskUsage>,
+    shell_exit: Option<&crate::shell::ShellExitReason>,
+    extensions: &[String],
+    full_ids: bool,
+    now: i64,
 ) -> Vec<Line<'static>> {
     let status_color = match container.status {
         DevcContainerStatus::Available => Color::DarkGray,
         DevcContainerStatus::Running => Color::Green,
+        DevcContainerStatus::Paused => Color::Magenta,
         DevcContainerStatus::Stopped => Color::DarkGray,
         DevcContainerStatus::Building => Color::Yellow,
         DevcContainerStatus::Built => Color::Blue,
@@ -320,11 +326,20 @@ pub(super) fn build_detail_text(
                 container
                     .image_id
                     .as_deref()
-                    .unwrap_or("Not built")
-                    .to_string(),
+                    .map(|id| format_id(id, full_ids))
+                    .unwrap_or_else(|| "Not built".to_string()),
             ),
         ]),
     ];
+    if let Some(previous) = container.previous_image_id() {
+        runtime_lines.push(Line::from(vec![
+            Span::raw("Previous:    "),
+            Span::styled(
+                format!("{} (P to remove)", format_id(previous, full_ids)),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
     if let Some(d) = details {
         runtime_lines.push(Line::from(vec![
             Span::raw("Runtime Name: "),
@@ -337,10 +352,28 @@ pub(super) fn build_detail_text(
             container
                 .container_id
                 .as_deref()
-                .unwrap_or("Not created")
-                .to_string(),
+                .map(|id| format_id(id, full_ids))
+                .unwrap_or_else(|| "Not created".to_string()),
         ),
     ]));
+    if let Some(uptime) = details.and_then(|d| d.uptime_secs(now)) {
+        runtime_lines.push(Line::from(vec![
+            Span::raw("Uptime:      "),
+            Span::raw(devc_provider::format_uptime(uptime)),
+        ]));
+    }
+    if let Some(d) = details {
+        // Anything above zero means the runtime is restarting a crashing container
+        let color = if d.restart_count == 0 {
+            Color::Reset
+        } else {
+            Color::Yellow
+        };
+        runtime_lines.push(Line::from(vec![
+            Span::raw("Restarts:    "),
+            Span::styled(d.restart_count.to_string(), Style::default().fg(color)),
+        ]));
+    }
     if let Some(code) = details.and_then(|d| d.exit_code) {
         let color = if code == 0 { Color::Green } else { Color::Red };
         runtime_lines.push(Line::from(vec![
@@ -348,6 +381,22 @@ pub(super) fn build_detail_text(
             Span::styled(code.to_string(), Style::default().fg(color)),
         ]));
     }
+    if let Some(disk) = disk {
+        runtime_lines.push(Line::from(vec![
+            Span::raw("Disk:        "),
+            Span::raw(disk_usage_summary(disk)),
+        ]));
+    }
+    if let Some(reason) = shell_exit {
+        runtime_lines.push(Line::from(vec![
+            Span::raw("Shell:       "),
+            Span::styled(reason.to_string(), Style::default().fg(Color::Yellow)),
+            Span::styled(
+                "  (Ctrl+R: restart, x: close)",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
 
     let mut lines = vec![
         Line::from(Span::styled(
@@ -426,6 +475,17 @@ pub(super) fn build_detail_text(
         ]));
     }
 
+    if !extensions.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "─── VS Code Extensions (not installed by devc) ───",
+            Style::default().fg(Color::DarkGray),
+        )));
+        for ext in extensions {
+            lines.push(Line::from(format!("  {}", ext)));
+        }
+    }
+
     // Add inspect-based sections when available
     if let Some(details) = details {
         // Ports
@@ -580,6 +640,27 @@ pub(super) fn build_detail_text(
     lines
 }
 
+/// "12.5MB writable + 300.0MB in 1 volume (image 1.2GB)"
+fn disk_usage_summary(disk: &devc_provider::ContainerDiskUsage) -> String {
+    use devc_provider::format_bytes;
+
+    let mut summary = format!("{} writable", format_bytes(disk.writable_bytes));
+    if !disk.volumes.is_empty() {
+        let bytes: u64 = disk.volumes.iter().map(|v| v.bytes).sum();
+        let plural = if disk.volumes.len() == 1 { "" } else { "s" };
+        summary.push_str(&format!(
+            " + {} in {} volume{}",
+            format_bytes(bytes),
+            disk.volumes.len(),
+            plural
+        ));
+    }
+    if let Some(image) = disk.image_bytes {
+        summary.push_str(&format!(" (image {})", format_bytes(image)));
+    }
+    summary
+}
+
 /// Draw the container detail view
 pub(super) fn draw_detail(frame: &mut Frame, app: &mut App, area: Rect) {
     let container = match app.selected_container() {
@@ -588,7 +669,18 @@ pub(super) fn draw_detail(frame: &mut Frame, app: &mut App, area: Rect) {
     };
 
     let is_compose = container.compose_project.is_some();
-    let text = build_detail_text(&container, app.container_detail.as_ref());
+    let text = build_detail_text(
+        &container,
+        app.container_detail.as_ref(),
+        app.container_disk_usage.as_ref(),
+        app.shell_state
+            .shell_sessions
+            .get(&container.id)
+            .and_then(|s| s.exit_reason.as_ref()),
+        &app.vscode_extensions,
+        app.full_ids,
+        chrono::Utc::now().timestamp(),
+    );
 
     if is_compose {
         // For compose containers, render outer block then split into info + services
@@ -962,6 +1054,20 @@ pub(super) fn draw_compose_services(
     container: &devc_core::ContainerState,
     area: Rect,
 ) {
+    if let Some(reason) = app.compose_unavailable_reason(container) {
+        let unavailable = Paragraph::new(reason)
+            .style(Style::default().fg(Color::Yellow))
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::default()
+                    .title(" Compose Services ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            );
+        frame.render_widget(unavailable, area);
+        return;
+    }
+
     let services = app.compose_state.services.get(&container.id);
 
     if app.compose_state.services_loading && services.is_none() {
@@ -1042,11 +1148,20 @@ pub(super) fn draw_compose_services(
         Constraint::Min(10),    // Status
     ];
 
+    let title = if app.compose_state.services_loading {
+        format!(
+            " Compose Services {} ",
+            super::spinner::frame(app.spinner_frame)
+        )
+    } else {
+        " Compose Services ".to_string()
+    };
+
     let table = Table::new(rows, widths)
         .header(header)
         .block(
             Block::default()
-                .title(" Compose Services ")
+                .title(title)
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::DarkGray)),
         )
@@ -1060,6 +1175,44 @@ pub(super) fn draw_compose_services(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_format_id_short_and_full() {
+        let id = "sha256:4f0c8d7b9a1e2f3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f7081920a1b";
+        assert_eq!(format_id(id, false), "4f0c8d7b9a1e");
+        assert_eq!(format_id(id, true), id);
+        assert_eq!(format_id("abc", false), "abc");
+    }
+
+    #[test]
+    fn test_detail_text_shows_uptime_and_restarts_for_running_container() {
+        let container = crate::App::create_test_container("web", DevcContainerStatus::Running);
+        let mut details = devc_core::test_support::mock_container_details(
+            "abc",
+            devc_provider::ContainerStatus::Running,
+        );
+        let now = 1_700_000_000;
+        details.started_at = Some(now - (3 * 3600 + 5 * 60));
+        details.restart_count = 2;
+
+        let text: Vec<String> =
+            build_detail_text(&container, Some(&details), None, None, &[], false, now)
+                .iter()
+                .map(|line| line.to_string())
+                .collect();
+        assert!(text.contains(&"Uptime:      3h 5m".to_string()));
+        assert!(text.contains(&"Restarts:    2".to_string()));
+
+        // A stopped container has no uptime, only its restart history
+        details.status = devc_provider::ContainerStatus::Exited;
+        let text: Vec<String> =
+            build_detail_text(&container, Some(&details), None, None, &[], false, now)
+                .iter()
+                .map(|line| line.to_string())
+                .collect();
+        assert!(!text.iter().any(|line| line.starts_with("Uptime:")));
+        assert!(text.contains(&"Restarts:    2".to_string()));
+    }
+
     #[test]
     fn test_git_info_for_workspace_with_git_dir_head_ref() {
         let tmp = tempfile::tempdir().unwrap();
@@ -1148,4 +1301,27 @@ mod tests {
 
         assert!(git_info_for_workspace(&repo).is_none());
     }
+
+    #[test]
+    fn test_disk_usage_summary() {
+        let mut disk = devc_provider::ContainerDiskUsage {
+            id: devc_provider::ContainerId::new("abc123"),
+            name: "app".to_string(),
+            image: "ubuntu:22.04".to_string(),
+            writable_bytes: 12_500_000,
+            image_bytes: None,
+            volumes: Vec::new(),
+        };
+        assert_eq!(disk_usage_summary(&disk), "12.5MB writable");
+
+        disk.image_bytes = Some(1_200_000_000);
+        disk.volumes.push(devc_provider::VolumeUsage {
+            name: "a".repeat(64),
+            bytes: 300_000_000,
+        });
+        assert_eq!(
+            disk_usage_summary(&disk),
+            "12.5MB writable + 300.0MB in 1 volume (image 1.2GB)"
+        );
+    }
 }
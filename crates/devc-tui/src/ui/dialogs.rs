@@ -71,11 +71,31 @@ pub(super) fn draw_confirm_dialog(frame: &mut Frame, app: &App, area: Rect) {
                 .unwrap_or(id);
             draw_build_confirm_dialog(frame, app, area, name);
         }
+        Some(ConfirmAction::RemovePreviousImage { name, image, .. }) => {
+            draw_simple_confirm_dialog(
+                frame,
+                app,
+                area,
+                &format!(
+                    "Remove previous image {} of '{}'?",
+                    super::format_id(image, false),
+                    name
+                ),
+            );
+        }
         Some(ConfirmAction::CancelBuild) => {
             draw_simple_confirm_dialog(frame, app, area, "Cancel build in progress?");
         }
         Some(ConfirmAction::QuitApp) => {
-            draw_simple_confirm_dialog(frame, app, area, "Quit devc?");
+            let message = match app.shutdown_on_quit.as_slice() {
+                [] => "Quit devc?".to_string(),
+                [(_, name)] => format!("Quit devc and stop '{}'?", name),
+                targets => format!(
+                    "Quit devc and stop {} containers started this session?",
+                    targets.len()
+                ),
+            };
+            draw_simple_confirm_dialog(frame, app, area, &message);
         }
         None => {}
     }
@@ -177,6 +197,7 @@ pub(super) fn draw_rebuild_confirm_dialog(
             app.rebuild_no_cache,
             app.dialog_focus == DialogFocus::Checkbox,
         )
+        .checkbox("Open shell when done (a)", app.rebuild_attach, false)
         .empty_line()
         .buttons(app.dialog_focus)
         .empty_line()
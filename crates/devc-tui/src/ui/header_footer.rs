@@ -77,14 +77,18 @@ pub(super) fn container_list_footer(app: &App) -> String {
             keys.push("S: Shell");
             keys.push("l: Logs");
         }
+        if has_shell_session(app) {
+            keys.push("^R: Restart shell");
+            keys.push("x: Close shell");
+        }
         if st != DevcContainerStatus::Building && st != DevcContainerStatus::Available {
             keys.push("d: Delete");
         }
     }
 
-    // Show forget option for non-devc containers
+    // Show forget option for adopted containers and ones whose workspace is gone
     if let Some(container) = app.selected_container() {
-        if container.source != DevcontainerSource::Devc && !container.status.is_available() {
+        if app.can_forget(container) {
             keys.push("f: Forget");
         }
     }
@@ -100,6 +104,11 @@ pub(super) fn container_list_footer(app: &App) -> String {
     }
 }
 
+fn has_shell_session(app: &App) -> bool {
+    app.selected_container()
+        .is_some_and(|c| app.shell_state.shell_sessions.contains_key(&c.id))
+}
+
 /// Build context-sensitive footer help for the container detail view
 pub(super) fn container_detail_footer(app: &App) -> String {
     let has_services = app
@@ -142,6 +151,11 @@ pub(super) fn container_detail_footer(app: &App) -> String {
             keys.push("l: Logs");
             keys.push("S: Shell");
         }
+        if has_shell_session(app) {
+            keys.push("^R: Restart shell");
+            keys.push("x: Close shell");
+        }
+        keys.push("v: Config");
         if st != DevcContainerStatus::Building && st != DevcContainerStatus::Available {
             keys.push("d: Delete");
         }
@@ -187,12 +201,13 @@ pub(super) fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
         }
         View::BuildOutput => {
             if app.build_complete {
-                "j/k: Scroll  g/G: Top/Bottom  c: Copy  q/Esc: Close".to_string()
+                "j/k: Scroll  g/G: Top/Bottom  c: Copy  s: Save  q/Esc: Close".to_string()
             } else {
-                "j/k: Scroll  g/G: Top/Bottom  c: Copy  (building...)".to_string()
+                "j/k: Scroll  g/G: Top/Bottom  c: Copy  s: Save  (building...)".to_string()
             }
         }
         View::Logs => "j/k: Scroll  g/G: Top/Bottom  PgUp/PgDn: Page  r: Refresh  Esc/q: Back".to_string(),
+        View::ConfigView => "j/k: Scroll  g/G: Top/Bottom  PgUp/PgDn: Page  Esc/q: Back".to_string(),
         View::Ports => {
             // Show install option if socat not installed
             if app.port_state.socat_installed == Some(false) && !app.port_state.socat_installing {
@@ -200,10 +215,10 @@ pub(super) fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
             } else if app.port_state.socat_installing {
                 "Installing socat...  q/Esc: Back".to_string()
             } else {
-                let is_forwarded = app
-                    .port_state.detected_ports
-                    .get(app.port_state.selected_port)
-                    .map(|p| p.is_forwarded)
+                let selected = app.port_state.selected_port_info();
+                let is_forwarded = selected.map(|p| p.is_forwarded).unwrap_or(false);
+                let is_web = selected
+                    .map(|p| app.port_state.browser_scheme(p).is_some())
                     .unwrap_or(false);
                 let per_container_auto_all = app
                     .port_state
@@ -221,8 +236,10 @@ pub(super) fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
                 } else {
                     "[A]uto-all"
                 };
-                if is_forwarded {
+                if is_forwarded && is_web {
                     format!("[s]top  [o]pen browser  [n]one  {}  j/k: Navigate  1-3: Switch tab  q/Esc: Back", auto_all_label)
+                } else if is_forwarded {
+                    format!("[s]top  [n]one  {}  j/k: Navigate  1-3: Switch tab  q/Esc: Back", auto_all_label)
                 } else {
                     format!("[f]orward  [a]ll  {}  j/k: Navigate  1-3: Switch tab  q/Esc: Back", auto_all_label)
                 }
@@ -370,9 +387,15 @@ pub(super) fn draw_settings(frame: &mut Frame, app: &App, area: Rect) {
             format!(" {}", section.label()),
             Style::default().fg(Color::Cyan).bold(),
         )])));
+        if *section == SettingsSection::Credentials {
+            items.push(ListItem::new(Line::from(vec![Span::styled(
+                "   Applied when a shell or command starts; running containers need a new shell.",
+                Style::default().fg(Color::DarkGray).italic(),
+            )])));
+        }
         if *section == SettingsSection::Agents {
             items.push(ListItem::new(Line::from(vec![Span::styled(
-                "   Agent auto-install requires Node/npm in the container image.",
+                "   Agent auto-install requires Node/npm in the container image. (auto) follows host config until toggled.",
                 Style::default().fg(Color::DarkGray).italic(),
             )])));
         }
@@ -396,15 +419,27 @@ pub(super) fn draw_settings(frame: &mut Frame, app: &App, area: Rect) {
                 let after = &settings.edit_buffer()[cursor_pos..];
                 format!("{}│{}", before, after)
             } else if field.is_toggle() {
-                if value == "true" {
+                let mut s = if value == "true" {
                     "[●] Enabled  [ ] Disabled".to_string()
                 } else {
-                    let mut s = "[ ] Enabled  [●] Disabled".to_string();
+                    "[ ] Enabled  [●] Disabled".to_string()
+                };
+                if field.is_agent_field() {
+                    if settings.agent_is_auto(*field) {
+                        s.push_str(" (auto)");
+                    }
                     if is_disabled {
-                        s.push_str(" (not installed on host)");
+                        match settings.field_unavailable_reason(*field) {
+                            Some(reason) => {
+                                s.push_str(&format!("  host: not installed ({})", reason))
+                            }
+                            None => s.push_str("  host: not installed"),
+                        }
+                    } else if settings.agent_availability.contains_key(field) {
+                        s.push_str("  host: found");
                     }
-                    s
                 }
+                s
             } else if value.is_empty() {
                 "(not set)".to_string()
             } else {
@@ -452,80 +487,10 @@ pub(super) fn draw_settings(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 pub(super) fn draw_help(frame: &mut Frame, app: &App, area: Rect) {
-    let general_help = vec![
-        Line::from(""),
-        Line::from(Span::styled(
-            "Global Keys",
-            Style::default().bold().underlined(),
-        )),
-        Line::from(""),
-        Line::from("  Tab         Next tab"),
-        Line::from("  Shift+Tab   Previous tab"),
-        Line::from("  1/2/3       Jump to Containers/Providers/Settings tab"),
-        Line::from("  ?/F1        Show this help"),
-        Line::from("  q           Quit (or go back from subview)"),
-        Line::from("  Esc         Go back / Cancel"),
-        Line::from(""),
-    ];
-
-    let tab_help = match app.tab {
-        Tab::Containers => vec![
-            Line::from(Span::styled(
-                "Containers Tab",
-                Style::default().bold().underlined(),
-            )),
-            Line::from(""),
-            Line::from("  j/Down      Move selection down"),
-            Line::from("  k/Up        Move selection up"),
-            Line::from("  g/Home      Go to first container"),
-            Line::from("  G/End       Go to last container"),
-            Line::from("  Enter       View container details"),
-            Line::from(""),
-            Line::from("  s           Start or Stop container"),
-            Line::from("  u           Up - build, create, and start"),
-            Line::from("  S           Shell (persistent session, Ctrl+\\ to detach)"),
-            Line::from("  R           Rebuild - destroy and rebuild container"),
-            Line::from("  p           Port forwarding"),
-            Line::from("  a           Open Agent Manager (running container)"),
-            Line::from("  d/Delete    Delete container"),
-            Line::from("  r/F5        Refresh list"),
-        ],
-        Tab::Providers => vec![
-            Line::from(Span::styled(
-                "Providers Tab",
-                Style::default().bold().underlined(),
-            )),
-            Line::from(""),
-            Line::from("  j/Down      Move selection down"),
-            Line::from("  k/Up        Move selection up"),
-            Line::from("  Enter       Set selected provider as active"),
-            Line::from("  Space       Set selected provider as active"),
-            Line::from("  s           Save provider settings to config"),
-            Line::from(""),
-            Line::from("  The active provider is used for new containers."),
-            Line::from("  Existing containers keep their original provider."),
-        ],
-        Tab::Settings => vec![
-            Line::from(Span::styled(
-                "Settings Tab",
-                Style::default().bold().underlined(),
-            )),
-            Line::from(""),
-            Line::from("  j/Down      Move to next setting"),
-            Line::from("  k/Up        Move to previous setting"),
-            Line::from("  Enter       Edit setting (text) or toggle (checkbox)"),
-            Line::from("  Space       Edit setting (text) or toggle (checkbox)"),
-            Line::from("  s           Save all settings to config file"),
-            Line::from("  r           Reset to saved values"),
-            Line::from(""),
-            Line::from("  When editing text:"),
-            Line::from("    Enter     Confirm change"),
-            Line::from("    Esc       Cancel change"),
-        ],
-    };
-
-    let mut text = general_help;
-    text.extend(tab_help);
+    let mut text = vec![Line::from("")];
+    text.extend(help_section(KeyContext::Global));
+    text.push(Line::from(""));
+    text.extend(help_section(app.key_context()));
 
     let help = Paragraph::new(text)
         .block(Block::default().title(" Help ").borders(Borders::ALL))
@@ -534,6 +499,27 @@ pub(super) fn draw_help(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(help, area);
 }
 
+/// Heading, bindings, and notes for one keybinding context
+fn help_section(context: KeyContext) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(Span::styled(
+            context.title(),
+            Style::default().bold().underlined(),
+        )),
+        Line::from(""),
+    ];
+    lines.extend(
+        keymap::bindings_for(context)
+            .map(|b| Line::from(format!("  {:<12}{}", b.label(), b.description))),
+    );
+    let notes = context.notes();
+    if !notes.is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(notes.iter().map(|note| Line::from(format!("  {}", note))));
+    }
+    lines
+}
+
 pub(super) fn draw_agent_diagnostics(frame: &mut Frame, app: &mut App, area: Rect) {
     let title = if app.agent_diagnostics_title.is_empty() {
         " Agent Diagnostics ".to_string()
@@ -1,5 +1,6 @@
 //! UI rendering for the TUI application
 
+mod config;
 mod containers;
 mod detail;
 mod dialogs;
@@ -8,11 +9,14 @@ mod output;
 mod ports;
 mod progress;
 mod spinner;
+mod toasts;
 
 use crate::app::{App, ConfirmAction, ContainerOperation, DialogFocus, Tab, View};
+use crate::keymap::{self, KeyContext};
 use crate::settings::SettingsSection;
 use crate::widgets::{centered_rect, DialogBuilder};
 use ansi_to_tui::IntoText;
+use devc_config::SortOrder;
 use devc_core::{display_name_map, DevcContainerStatus};
 use devc_provider::{ContainerStatus, DevcontainerSource};
 use ratatui::{
@@ -23,6 +27,7 @@ use ratatui::{
     },
 };
 
+use config::*;
 use containers::*;
 use detail::*;
 use dialogs::*;
@@ -30,6 +35,7 @@ use header_footer::*;
 use output::*;
 use ports::*;
 use progress::*;
+use toasts::*;
 
 /// Main draw function
 pub fn draw(frame: &mut Frame, app: &mut App) {
@@ -106,6 +112,7 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         }
         View::BuildOutput => draw_build_output(frame, app, content_area),
         View::Logs => draw_logs(frame, app, content_area),
+        View::ConfigView => draw_config_view(frame, app, content_area),
         View::Ports => {
             draw_main_content(frame, app, content_area);
             let port_rows = app.port_state.detected_ports.len().max(3) as u16;
@@ -141,6 +148,7 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
     }
 
     draw_footer(frame, app, footer_area);
+    draw_toasts(frame, app, area);
 }
 
 /// Draw the main tab content (containers/providers/settings list)
@@ -158,6 +166,16 @@ fn draw_main_content(frame: &mut Frame, app: &mut App, area: Rect) {
     }
 }
 
+/// A container or image ID as displayed: 12 hex characters like `docker ps`,
+/// or the whole ID when `full` is set
+pub(crate) fn format_id(id: &str, full: bool) -> String {
+    if full {
+        id.to_string()
+    } else {
+        id.trim_start_matches("sha256:").chars().take(12).collect()
+    }
+}
+
 /// Calculate a popup rectangle centered in the given area with percentage-based sizing and minimums
 fn popup_rect(pct_w: u16, pct_h: u16, min_w: u16, min_h: u16, area: Rect) -> Rect {
     let w = ((area.width as u32 * pct_w as u32) / 100) as u16;
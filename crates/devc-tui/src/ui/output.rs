@@ -33,7 +33,7 @@ pub(super) fn draw_build_output(frame: &mut Frame, app: &App, area: Rect) {
             );
 
             // Use ansi-to-tui to parse the line content
-            match clean_line.into_text() {
+            let line = match clean_line.into_text() {
                 Ok(text) => {
                     // Combine line number with parsed content
                     let mut spans = vec![line_num];
@@ -46,6 +46,17 @@ pub(super) fn draw_build_output(frame: &mut Frame, app: &App, area: Rect) {
                     // Fallback to raw text if parsing fails
                     Line::from(vec![line_num, Span::raw(clean_line)])
                 }
+            };
+
+            if app.build_failed_line == Some(i) {
+                line.style(
+                    Style::default()
+                        .fg(Color::White)
+                        .bg(Color::Red)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                line
             }
         })
         .collect();
@@ -145,6 +156,11 @@ pub(super) fn draw_logs(frame: &mut Frame, app: &App, area: Rect) {
         })
         .collect();
 
+    let timestamps_badge = if app.log_timestamps {
+        " [timestamps]"
+    } else {
+        ""
+    };
     let scroll_info = if total_lines > 0 {
         let percent = if total_lines <= inner_height {
             100
@@ -152,14 +168,15 @@ pub(super) fn draw_logs(frame: &mut Frame, app: &App, area: Rect) {
             ((app.logs_scroll + inner_height).min(total_lines) * 100) / total_lines
         };
         format!(
-            " Logs: {} [{}/{}] {}% ",
+            " Logs: {} [{}/{}] {}%{} ",
             display_name,
             app.logs_scroll + 1,
             total_lines,
-            percent
+            percent,
+            timestamps_badge
         )
     } else {
-        format!(" Logs: {} (empty) ", display_name)
+        format!(" Logs: {} (empty){} ", display_name, timestamps_badge)
     };
 
     let logs = Paragraph::new(text).block(
@@ -77,7 +77,9 @@ pub(super) fn draw_ports(frame: &mut Frame, app: &mut App, area: Rect) {
                         .contains(&(cid.clone(), port.port))
                 })
                 .unwrap_or(false);
-            let status = if port.is_forwarded && is_auto {
+            let status = if port.is_forwarded && !port.is_listening {
+                "● Forwarded (idle)"
+            } else if port.is_forwarded && is_auto {
                 "● Forwarded [auto]"
             } else if port.is_forwarded {
                 "● Forwarded"
@@ -85,7 +87,9 @@ pub(super) fn draw_ports(frame: &mut Frame, app: &mut App, area: Rect) {
                 "○ Detected"
             };
             let local = if port.is_forwarded {
-                format!("localhost:{}", port.port)
+                app.port_state
+                    .local_address(port.port)
+                    .unwrap_or_else(|| format!("localhost:{}", port.port))
             } else {
                 "-".to_string()
             };
@@ -99,13 +103,20 @@ pub(super) fn draw_ports(frame: &mut Frame, app: &mut App, area: Rect) {
                     .find(|c| c.port == port.port)
                     .and_then(|c| c.label.as_deref())
             });
+            // Suffix the probed scheme so web ports stand out
+            let port_num = match port.scheme.url_scheme() {
+                Some(scheme) => format!("{}/{}", port.port, scheme),
+                None => port.port.to_string(),
+            };
             let port_cell = if let Some(label) = label {
-                format!("{} ({})", port.port, label)
+                format!("{} ({})", port_num, label)
             } else {
-                port.port.to_string()
+                port_num
             };
 
-            let style = if port.is_forwarded {
+            let style = if port.is_forwarded && !port.is_listening {
+                Style::default().fg(Color::Yellow)
+            } else if port.is_forwarded {
                 Style::default().fg(Color::Green)
             } else {
                 Style::default()
@@ -137,7 +148,7 @@ pub(super) fn draw_ports(frame: &mut Frame, app: &mut App, area: Rect) {
     let widths = [
         Constraint::Length(20),
         Constraint::Length(20),
-        Constraint::Length(18),
+        Constraint::Length(24),
         Constraint::Min(10),
     ];
 
@@ -54,10 +54,17 @@ pub(super) fn draw_operation_progress(frame: &mut Frame, app: &App, area: Rect)
     let title = match op {
         ContainerOperation::Starting { .. } => "Starting",
         ContainerOperation::Stopping { .. } => "Stopping",
+        ContainerOperation::Pausing { .. } => "Pausing",
+        ContainerOperation::Unpausing { .. } => "Unpausing",
         ContainerOperation::Deleting { .. } => "Deleting",
+        ContainerOperation::Up {
+            finalizing: true, ..
+        } => "Container Ready",
         ContainerOperation::Up { .. } => "Container Up",
         ContainerOperation::Adopting { .. } => "Adopting",
         ContainerOperation::Forgetting { .. } => "Forgetting",
+        ContainerOperation::RemovingImage { .. } => "Removing Image",
+        ContainerOperation::Syncing { .. } => "Checking Status",
     };
 
     let has_output = !app.up_output.is_empty();
@@ -0,0 +1,65 @@
+use super::*;
+
+const TOAST_WIDTH: u16 = 48;
+const TOAST_HEIGHT: u16 = 3;
+
+/// Stack toasts in the top-right corner below the header, newest on top.
+/// Drawn last so they sit above any view or dialog
+pub(super) fn draw_toasts(frame: &mut Frame, app: &App, area: Rect) {
+    if app.toasts.is_empty() || area.width < 12 {
+        return;
+    }
+    let width = TOAST_WIDTH.min(area.width - 2);
+    let x = area.x + area.width - width - 1;
+    let mut y = area.y + 3;
+
+    for toast in app.toasts.iter().rev() {
+        if y + TOAST_HEIGHT > area.y + area.height {
+            break;
+        }
+        let (icon, color) = if toast.success {
+            ("✓", Color::Green)
+        } else {
+            ("✗", Color::Red)
+        };
+        let rect = Rect::new(x, y, width, TOAST_HEIGHT);
+        let text = truncate_message(&toast.message, width.saturating_sub(6) as usize);
+        let paragraph = Paragraph::new(Line::from(vec![
+            Span::styled(format!(" {} ", icon), Style::default().fg(color).bold()),
+            Span::raw(text),
+        ]))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color)),
+        );
+        frame.render_widget(Clear, rect);
+        frame.render_widget(paragraph, rect);
+        y += TOAST_HEIGHT;
+    }
+}
+
+fn truncate_message(message: &str, max: usize) -> String {
+    let first_line = message.lines().next().unwrap_or_default();
+    if first_line.chars().count() <= max {
+        return first_line.to_string();
+    }
+    let mut out: String = first_line.chars().take(max.saturating_sub(1)).collect();
+    out.push('…');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_message() {
+        assert_eq!(truncate_message("Started app", 20), "Started app");
+        assert_eq!(
+            truncate_message("Up failed for app: boom", 10),
+            "Up failed…"
+        );
+        assert_eq!(truncate_message("first\nsecond", 20), "first");
+    }
+}
@@ -389,16 +389,37 @@ fn test_container_operation_label_up_with_progress() {
         id: "c1".to_string(),
         name: "my-app".to_string(),
         progress: "Building image...".to_string(),
+        finalizing: false,
     };
     assert_eq!(op.label(), "Building image...");
 }
 
+#[test]
+fn test_container_operation_label_up_finalizing() {
+    let mut op = ContainerOperation::Up {
+        id: "c1".to_string(),
+        name: "my-app".to_string(),
+        progress: devc_core::READY_FINALIZING_PROGRESS.to_string(),
+        finalizing: true,
+    };
+    assert_eq!(op.label(), "my-app ready (finalizing)...");
+
+    if let ContainerOperation::Up { progress, .. } = &mut op {
+        *progress = "Running postCreateCommand...".to_string();
+    }
+    assert_eq!(
+        op.label(),
+        "my-app ready (finalizing): Running postCreateCommand..."
+    );
+}
+
 #[test]
 fn test_container_operation_label_up_empty_progress() {
     let op = ContainerOperation::Up {
         id: "c1".to_string(),
         name: "my-app".to_string(),
         progress: String::new(),
+        finalizing: false,
     };
     assert_eq!(op.label(), "Starting up my-app...");
 }
@@ -437,6 +458,8 @@ fn test_ports_view_cleanup() {
             process: Some("node".to_string()),
             is_new: false,
             is_forwarded: false,
+            is_listening: true,
+            scheme: devc_tui::ports::PortScheme::Unknown,
         });
 
     // Close ports view manually
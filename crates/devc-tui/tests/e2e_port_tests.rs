@@ -2,6 +2,7 @@
 //!
 //! All tests require a container runtime (Docker or Podman) and are `#[ignore]`.
 
+use devc_config::PortTransport;
 use devc_core::test_support::{TestComposeGuard, TestContainerGuard};
 use devc_provider::{CliProvider, ContainerProvider, CreateContainerConfig, ExecConfig};
 use devc_tui::tunnel::spawn_forwarder;
@@ -315,9 +316,17 @@ async fn test_e2e_socat_forwarding_roundtrip() {
 
     // Spawn forwarder: localhost:14000 -> container:4000
     let (program, prefix) = provider.runtime_args();
-    let forwarder = spawn_forwarder(program, prefix, id.0.clone(), 14000, 4000)
-        .await
-        .expect("spawn_forwarder should succeed");
+    let forwarder = spawn_forwarder(
+        program,
+        prefix,
+        id.0.clone(),
+        devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS,
+        14000,
+        4000,
+        PortTransport::Tcp,
+    )
+    .await
+    .expect("spawn_forwarder should succeed");
 
     assert!(forwarder.is_running(), "forwarder should be running");
 
@@ -453,9 +462,17 @@ services:
 
     // Spawn forwarder: localhost:13000 -> container:3000
     let (program, prefix) = provider.runtime_args();
-    let forwarder = spawn_forwarder(program, prefix, app_id.0.clone(), 13000, 3000)
-        .await
-        .expect("spawn_forwarder should succeed");
+    let forwarder = spawn_forwarder(
+        program,
+        prefix,
+        app_id.0.clone(),
+        devc_config::PortForwardConfig::DEFAULT_BIND_ADDRESS,
+        13000,
+        3000,
+        PortTransport::Tcp,
+    )
+    .await
+    .expect("spawn_forwarder should succeed");
 
     // TCP roundtrip test
     let mut stream = tokio::net::TcpStream::connect("127.0.0.1:13000")
@@ -610,6 +627,7 @@ services:
         timestamps: false,
         since: None,
         until: None,
+        demux: false,
     };
 
     // Verify we can fetch logs for companion service without error
@@ -3,10 +3,13 @@
 mod helpers;
 
 use crossterm::event::{KeyCode, KeyModifiers};
+use devc_core::features::progress::{FeatureEvent, FeatureStatus};
 use devc_core::DevcContainerStatus;
-use devc_provider::{ComposeServiceInfo, ContainerId, ContainerStatus};
+use devc_provider::{ComposeServiceInfo, ContainerId, ContainerStatus, ProviderType};
+use devc_tui::keymap::KeyContext;
 use devc_tui::{
-    App, AsyncEvent, ConfirmAction, ContainerOpResult, ContainerOperation, DialogFocus, Tab, View,
+    App, AsyncEvent, ConfirmAction, ContainerOpResult, ContainerOperation, DialogFocus,
+    ShellExitReason, Tab, View,
 };
 use ratatui::widgets::TableState;
 
@@ -47,6 +50,133 @@ async fn test_j_moves_selection_down() {
     assert_eq!(app.selected, 2);
 }
 
+/// Start and up are refused up front for compose projects when the provider has no compose
+#[tokio::test]
+async fn test_compose_actions_blocked_without_compose_support() {
+    let mut app = app_with_containers();
+    app.containers[1].compose_project = Some("python-api".to_string());
+    let provider = app.containers[1].provider;
+    app.handle_async_event(AsyncEvent::CapabilitiesDetected(vec![(
+        provider,
+        devc_provider::ProviderCapabilities {
+            compose: false,
+            buildkit: true,
+            gpu: false,
+            stats: true,
+        },
+    )]))
+    .await
+    .unwrap();
+    app.selected = 1;
+    app.containers_table_state.select(Some(1));
+
+    for key in ['s', 'u'] {
+        app.status_message = None;
+        app.send_key(KeyCode::Char(key), KeyModifiers::NONE)
+            .await
+            .unwrap();
+        assert!(app.container_op.is_none(), "'{}' started an operation", key);
+        assert!(app
+            .status_message
+            .as_deref()
+            .is_some_and(|m| m.contains("Compose is not available")));
+    }
+}
+
+/// 'e' in the detail view queues the devcontainer.json for the editor,
+/// which the run loop opens with the TUI suspended
+#[tokio::test]
+async fn test_e_queues_config_edit() {
+    let mut app = app_with_containers();
+    app.view = View::ContainerDetail;
+    app.containers[0].config_path = "/nonexistent/.devcontainer/devcontainer.json".into();
+
+    app.send_key(KeyCode::Char('e'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(app.pending_config_edit.is_none());
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("'rust-project' has no devcontainer.json to edit")
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    let config_path = dir.path().join("devcontainer.json");
+    std::fs::write(&config_path, "{}").unwrap();
+    app.containers[0].config_path = config_path;
+    app.send_key(KeyCode::Char('e'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert_eq!(
+        app.pending_config_edit.as_deref(),
+        Some(app.containers[0].id.as_str())
+    );
+    assert_eq!(app.view, View::ContainerDetail);
+}
+
+/// 'P' in the detail view confirms removing the previous image, refusing
+/// while another tracked container still uses it
+#[tokio::test]
+async fn test_remove_previous_image_requires_unused_image() {
+    let mut app = app_with_containers();
+    app.view = View::ContainerDetail;
+    app.containers[1].image_id = Some("sha256:shared".to_string());
+
+    app.send_key(KeyCode::Char('P'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("rust-project has no previous image")
+    );
+
+    app.containers[0].metadata.insert(
+        devc_core::PREVIOUS_IMAGE_METADATA_KEY.to_string(),
+        "sha256:shared".to_string(),
+    );
+    app.send_key(KeyCode::Char('P'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert_eq!(app.view, View::ContainerDetail);
+    assert!(app
+        .status_message
+        .as_deref()
+        .is_some_and(|m| m.contains("still used by python-api")));
+
+    app.containers[1].image_id = Some("sha256:other".to_string());
+    app.send_key(KeyCode::Char('P'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert_eq!(app.view, View::Confirm);
+    assert!(matches!(
+        app.confirm_action,
+        Some(ConfirmAction::RemovePreviousImage { ref image, .. }) if image == "sha256:shared"
+    ));
+    assert_eq!(app.dialog_focus, DialogFocus::Cancel);
+}
+
+/// 'P' needs a provider to remove the image with
+#[tokio::test]
+async fn test_remove_previous_image_requires_connection() {
+    let mut app = app_with_containers();
+    app.view = View::ContainerDetail;
+    app.active_provider = None;
+    app.containers[0].metadata.insert(
+        devc_core::PREVIOUS_IMAGE_METADATA_KEY.to_string(),
+        "sha256:old".to_string(),
+    );
+
+    app.send_key(KeyCode::Char('P'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert_eq!(app.view, View::ContainerDetail);
+    assert!(app.confirm_action.is_none());
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("Not connected to provider")
+    );
+}
+
 /// Pressing 'k' at the top wraps selection to the last item
 #[tokio::test]
 async fn test_k_wraps_selection_up() {
@@ -59,6 +189,42 @@ async fn test_k_wraps_selection_up() {
     assert_eq!(app.selected, 2, "k at position 0 should wrap to last item");
 }
 
+/// 'o' cycles status -> recent -> name, keeping the focused container selected
+#[tokio::test]
+async fn test_o_cycles_sort_order_keeping_selection() {
+    let mut app = app_with_containers();
+    // Most recently used: python-api, then rust-project, then frontend-app
+    for (container, hours) in app.containers.iter_mut().zip([2, 3, 1]) {
+        container.last_used += chrono::Duration::hours(hours);
+    }
+    app.selected = 1; // python-api
+    app.containers_table_state.select(Some(1));
+
+    app.send_key(KeyCode::Char('o'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert_eq!(app.sort_order, devc_config::SortOrder::Recent);
+    let names: Vec<&str> = app.containers.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, ["python-api", "rust-project", "frontend-app"]);
+    assert_eq!(app.containers[app.selected].name, "python-api");
+
+    app.send_key(KeyCode::Char('o'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert_eq!(app.sort_order, devc_config::SortOrder::Name);
+    let names: Vec<&str> = app.containers.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, ["frontend-app", "python-api", "rust-project"]);
+    assert_eq!(app.containers[app.selected].name, "python-api");
+
+    app.send_key(KeyCode::Char('o'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert_eq!(app.sort_order, devc_config::SortOrder::Status);
+    assert_eq!(app.containers[0].name, "rust-project");
+    assert_eq!(app.containers[app.selected].name, "python-api");
+    assert_eq!(app.containers_table_state.selected(), Some(app.selected));
+}
+
 // ---------------------------------------------------------------------------
 // View transition tests
 // ---------------------------------------------------------------------------
@@ -93,6 +259,57 @@ async fn test_escape_closes_detail() {
     assert_eq!(app.view, View::Main);
 }
 
+/// Pressing 'v' in the detail view opens the resolved config, Esc returns to detail
+#[tokio::test]
+async fn test_v_opens_config_view() {
+    let tmp = tempfile::tempdir().unwrap();
+    let devcontainer_dir = tmp.path().join(".devcontainer");
+    std::fs::create_dir_all(&devcontainer_dir).unwrap();
+    let config_path = devcontainer_dir.join("devcontainer.json");
+    std::fs::write(
+        &config_path,
+        r#"{ "name": "demo", "image": "ubuntu:22.04", "workspaceFolder": "/workspaces/${localWorkspaceFolderBasename}" }"#,
+    )
+    .unwrap();
+
+    let mut app = app_with_containers();
+    app.containers[0].config_path = config_path;
+    app.containers[0].workspace_path = tmp.path().to_path_buf();
+
+    app.send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .await
+        .unwrap();
+    app.send_key(KeyCode::Char('v'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert_eq!(app.view, View::ConfigView);
+    assert!(app
+        .config_view_lines
+        .iter()
+        .any(|l| l.contains("\"image\": \"ubuntu:22.04\"")));
+    assert!(
+        !app.config_view_lines.iter().any(|l| l.contains("null")),
+        "unset fields should be omitted"
+    );
+    assert!(
+        !app.config_view_lines
+            .iter()
+            .any(|l| l.contains("${localWorkspaceFolderBasename}")),
+        "substitutions should be applied"
+    );
+
+    app.send_key(KeyCode::Char('G'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert_eq!(app.config_view_scroll, app.config_view_lines.len() - 1);
+
+    app.send_key(KeyCode::Esc, KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert_eq!(app.view, View::ContainerDetail);
+    assert!(app.config_view_lines.is_empty());
+}
+
 // ---------------------------------------------------------------------------
 // Tab navigation tests
 // ---------------------------------------------------------------------------
@@ -157,6 +374,108 @@ async fn test_question_mark_opens_help() {
     assert_eq!(app.view, View::Help);
 }
 
+/// Help opened from a subview describes that subview and closes back to it
+#[tokio::test]
+async fn test_help_returns_to_originating_view() {
+    let mut app = App::new_for_testing();
+    app.view = View::Logs;
+
+    app.send_key(KeyCode::F(1), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert_eq!(app.view, View::Help);
+    assert_eq!(app.key_context(), KeyContext::Logs);
+
+    app.send_key(KeyCode::Char('x'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert_eq!(app.view, View::Logs);
+}
+
+// ---------------------------------------------------------------------------
+// Quit and shutdownAction
+// ---------------------------------------------------------------------------
+
+/// App whose first (running) container was started this session and sets `shutdownAction`
+fn app_with_shutdown_action(tmp: &std::path::Path) -> App {
+    let devcontainer_dir = tmp.join(".devcontainer");
+    std::fs::create_dir_all(&devcontainer_dir).unwrap();
+    let config_path = devcontainer_dir.join("devcontainer.json");
+    std::fs::write(
+        &config_path,
+        r#"{ "image": "ubuntu:22.04", "shutdownAction": "stopContainer" }"#,
+    )
+    .unwrap();
+
+    let mut app = app_with_containers();
+    app.containers[0].config_path = config_path;
+    app.containers[0].workspace_path = tmp.to_path_buf();
+    let id = app.containers[0].id.clone();
+    app.session_started.insert(id);
+    app
+}
+
+/// 'q' quits immediately when nothing started this session needs stopping
+#[tokio::test]
+async fn test_q_quits_without_shutdown_targets() {
+    let mut app = app_with_containers();
+
+    app.send_key(KeyCode::Char('q'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(app.should_quit);
+    assert!(app.confirm_action.is_none());
+}
+
+/// 'q' asks before stopping a container started this session
+#[tokio::test]
+async fn test_q_prompts_before_shutdown_action() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut app = app_with_shutdown_action(tmp.path());
+
+    app.send_key(KeyCode::Char('q'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(!app.should_quit);
+    assert_eq!(app.view, View::Confirm);
+    assert!(matches!(app.confirm_action, Some(ConfirmAction::QuitApp)));
+    assert_eq!(app.shutdown_on_quit.len(), 1);
+    assert_eq!(app.shutdown_on_quit[0].1, "rust-project");
+
+    app.send_key(KeyCode::Char('y'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(app.should_quit);
+    assert!(app.shutdown_on_quit.is_empty());
+}
+
+/// With `confirm_shutdown = false`, 'q' skips the prompt
+#[tokio::test]
+async fn test_q_skips_prompt_when_confirm_shutdown_disabled() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut app = app_with_shutdown_action(tmp.path());
+    app.config.defaults.confirm_shutdown = Some(false);
+
+    app.send_key(KeyCode::Char('q'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(app.should_quit);
+    assert!(app.confirm_action.is_none());
+}
+
+/// Containers started before this session are left alone
+#[tokio::test]
+async fn test_q_ignores_containers_not_started_this_session() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut app = app_with_shutdown_action(tmp.path());
+    app.session_started.clear();
+
+    app.send_key(KeyCode::Char('q'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(app.should_quit);
+}
+
 // ---------------------------------------------------------------------------
 // Delete confirmation
 // ---------------------------------------------------------------------------
@@ -341,6 +660,178 @@ async fn test_rebuild_dialog_checkbox() {
     );
 }
 
+/// `R` on a container pinned to another provider warns about no provider change,
+/// since the rebuild keeps it where it is
+#[tokio::test]
+async fn test_rebuild_dialog_pinned_container_keeps_provider() {
+    let mut app = app_with_containers();
+    app.active_provider = Some(ProviderType::Docker);
+    app.selected = 1;
+    app.containers_table_state.select(Some(1));
+    app.containers[1].provider = ProviderType::Podman;
+    app.containers[1].metadata.insert(
+        devc_core::PINNED_PROVIDER_METADATA_KEY.to_string(),
+        "true".to_string(),
+    );
+
+    app.send_key(KeyCode::Char('R'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+
+    assert_eq!(app.view, View::Confirm);
+    match &app.confirm_action {
+        Some(ConfirmAction::Rebuild {
+            provider_change, ..
+        }) => assert_eq!(*provider_change, None),
+        other => panic!("expected a rebuild dialog, got {:?}", other),
+    }
+}
+
+/// `a` in the Rebuild dialog toggles opening a shell afterwards; cancelling resets it
+#[tokio::test]
+async fn test_rebuild_dialog_attach_toggle() {
+    let mut app = app_with_containers();
+    let id = app.containers[app.selected].id.clone();
+    app.confirm_action = Some(ConfirmAction::Rebuild {
+        id: id.clone(),
+        provider_change: None,
+    });
+    app.dialog_focus = DialogFocus::Cancel;
+    app.view = View::Confirm;
+
+    app.send_key(KeyCode::Char('a'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(app.rebuild_attach);
+    // Focus is left alone
+    assert_eq!(app.dialog_focus, DialogFocus::Cancel);
+
+    app.send_key(KeyCode::Esc, KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(!app.rebuild_attach);
+    assert_eq!(app.view, View::Main);
+
+    // A failed rebuild leaves the build view up instead of opening a shell
+    app.attach_after_rebuild = Some(id);
+    app.view = View::BuildOutput;
+    app.handle_async_event(AsyncEvent::BuildFinished {
+        success: false,
+        failed_step: None,
+    })
+    .await
+    .unwrap();
+    assert!(app.attach_after_rebuild.is_none());
+    assert_eq!(app.view, View::BuildOutput);
+}
+
+/// Cancelling a rebuild drops its result instead of attaching a shell afterwards
+#[tokio::test]
+async fn test_cancel_rebuild_skips_attach() {
+    let mut app = app_with_containers();
+    let id = app.containers[app.selected].id.clone();
+    app.attach_after_rebuild = Some(id);
+    app.loading = true;
+    app.build_complete = false;
+    app.view = View::BuildOutput;
+
+    app.send_key(KeyCode::Char('q'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(matches!(
+        app.confirm_action,
+        Some(ConfirmAction::CancelBuild)
+    ));
+    app.send_key(KeyCode::Char('y'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert_eq!(app.view, View::Main);
+    assert!(app.attach_after_rebuild.is_none());
+
+    // No new build starts while the cancelled one could still report back
+    app.send_key(KeyCode::Char('u'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(app.container_op.is_none());
+    assert!(app
+        .status_message
+        .as_deref()
+        .is_some_and(|m| m.contains("cancelled build is still running")));
+
+    // The rebuild still finishes in the background; its events are ignored
+    app.handle_async_event(AsyncEvent::BuildProgress("Step 9/9".to_string()))
+        .await
+        .unwrap();
+    app.handle_async_event(AsyncEvent::BuildFinished {
+        success: true,
+        failed_step: None,
+    })
+    .await
+    .unwrap();
+    assert_eq!(app.view, View::Main);
+    assert!(app.build_output.is_empty());
+    assert!(!app.build_complete);
+    assert!(!app.build_cancelled);
+}
+
+/// Feature download events update one table in place instead of stacking up,
+/// and `v` shows each feature's own messages under its row
+#[tokio::test]
+async fn test_feature_progress_updates_table_in_place() {
+    let mut app = app_with_containers();
+    app.view = View::BuildOutput;
+    let status = |index, total, id: &str, status| {
+        AsyncEvent::FeatureProgress(FeatureEvent::Status {
+            index,
+            total,
+            id: id.to_string(),
+            status,
+        })
+    };
+    for event in [
+        AsyncEvent::BuildProgress("Resolving features...".to_string()),
+        status(0, 1, "node", FeatureStatus::Fetching),
+        status(1, 2, "git", FeatureStatus::Fetching),
+        status(0, 2, "node", FeatureStatus::Cached),
+        AsyncEvent::FeatureProgress(FeatureEvent::Line {
+            index: 1,
+            line: "Downloading feature devcontainers/git:1...".to_string(),
+        }),
+        // Output that overtakes the last event stays below the table
+        AsyncEvent::BuildProgress("Step 1/4 : FROM alpine".to_string()),
+        status(1, 2, "git", FeatureStatus::Downloaded),
+    ] {
+        app.handle_async_event(event).await.unwrap();
+    }
+    assert_eq!(
+        app.build_output,
+        vec![
+            "Resolving features...",
+            "Features 2/2",
+            "  node  cached",
+            "  git   downloaded",
+            "Step 1/4 : FROM alpine",
+        ]
+    );
+
+    app.send_key(KeyCode::Char('v'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert_eq!(
+        app.build_output[3..],
+        [
+            "  git   downloaded",
+            "      Downloading feature devcontainers/git:1...",
+            "Step 1/4 : FROM alpine",
+        ]
+    );
+
+    app.send_key(KeyCode::Char('v'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert_eq!(app.build_output.len(), 5);
+}
+
 // ---------------------------------------------------------------------------
 // Arrow key navigation in confirm dialogs
 // ---------------------------------------------------------------------------
@@ -512,6 +1003,136 @@ async fn test_compose_detail_service_navigation() {
     );
 }
 
+/// 'S' on a companion compose service opens a shell session for that service;
+/// a stopped service is rejected with a status message.
+#[cfg(unix)]
+#[tokio::test]
+async fn test_compose_detail_shell_into_companion_service() {
+    let mut app = App::new_for_testing();
+    app.containers = vec![App::create_test_compose_container(
+        "compose-app",
+        DevcContainerStatus::Running,
+        "devc-compose-app",
+        "app",
+    )];
+    app.selected = 0;
+    app.containers_table_state.select(Some(0));
+    app.compose_state.services.insert(
+        "test-compose-app".to_string(),
+        vec![
+            ComposeServiceInfo {
+                service_name: "app".to_string(),
+                container_id: ContainerId::new("container-app-123"),
+                status: ContainerStatus::Running,
+            },
+            ComposeServiceInfo {
+                service_name: "db".to_string(),
+                container_id: ContainerId::new("container-db-456"),
+                status: ContainerStatus::Running,
+            },
+            ComposeServiceInfo {
+                service_name: "redis".to_string(),
+                container_id: ContainerId::new("container-redis-789"),
+                status: ContainerStatus::Exited,
+            },
+        ],
+    );
+    app.view = View::ContainerDetail;
+
+    // Stopped companion service is rejected
+    app.compose_state.selected_service = 2;
+    app.send_key(KeyCode::Char('S'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert_eq!(app.view, View::ContainerDetail);
+    assert!(app
+        .status_message
+        .as_deref()
+        .is_some_and(|m| m.contains("not running")));
+
+    // Running companion service gets its own session
+    app.compose_state.selected_service = 1;
+    app.send_key(KeyCode::Char('S'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert_eq!(app.view, View::Shell);
+    let key = "test-compose-app/db".to_string();
+    assert_eq!(app.shell_state.active_shell_container, Some(key.clone()));
+    let session = &app.shell_state.shell_sessions[&key];
+    assert_eq!(session.provider_container_id, "container-db-456");
+    assert_eq!(session.container_name, "compose-app/db");
+}
+
+/// Ctrl+R replaces an ended shell session with a fresh one; 'x' closes it.
+#[cfg(unix)]
+#[tokio::test]
+async fn test_restart_and_close_ended_shell_session() {
+    let mut app = App::new_for_testing();
+    app.containers = vec![App::create_test_compose_container(
+        "compose-app",
+        DevcContainerStatus::Running,
+        "devc-compose-app",
+        "app",
+    )];
+    app.selected = 0;
+    app.containers_table_state.select(Some(0));
+    app.compose_state.services.insert(
+        "test-compose-app".to_string(),
+        vec![
+            ComposeServiceInfo {
+                service_name: "app".to_string(),
+                container_id: ContainerId::new("container-app-123"),
+                status: ContainerStatus::Running,
+            },
+            ComposeServiceInfo {
+                service_name: "db".to_string(),
+                container_id: ContainerId::new("container-db-456"),
+                status: ContainerStatus::Running,
+            },
+        ],
+    );
+    app.view = View::ContainerDetail;
+    app.compose_state.selected_service = 1;
+    let key = "test-compose-app/db".to_string();
+
+    app.send_key(KeyCode::Char('r'), KeyModifiers::CONTROL)
+        .await
+        .unwrap();
+    assert_eq!(app.view, View::ContainerDetail);
+    assert!(app
+        .status_message
+        .as_deref()
+        .is_some_and(|m| m.contains("No shell session")));
+
+    app.send_key(KeyCode::Char('S'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    app.shell_state
+        .shell_sessions
+        .get_mut(&key)
+        .unwrap()
+        .exit_reason = Some(ShellExitReason::Exited);
+    app.shell_state.active_shell_container = None;
+    app.view = View::ContainerDetail;
+
+    app.send_key(KeyCode::Char('r'), KeyModifiers::CONTROL)
+        .await
+        .unwrap();
+    assert_eq!(app.view, View::Shell);
+    assert_eq!(app.shell_state.active_shell_container, Some(key.clone()));
+    assert!(app.shell_state.shell_sessions[&key].exit_reason.is_none());
+
+    app.view = View::ContainerDetail;
+    app.send_key(KeyCode::Char('x'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(!app.shell_state.shell_sessions.contains_key(&key));
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("Closed shell session for 'compose-app/db'")
+    );
+}
+
 #[tokio::test]
 async fn test_agent_sync_requires_running_container() {
     let mut app = App::new_for_testing();
@@ -617,6 +1238,58 @@ async fn test_confirm_stop_sets_operation_state() {
     assert_eq!(app.spinner_frame, 0);
 }
 
+/// 'z' pauses a running container and unpauses a paused one, without a confirm dialog
+#[tokio::test]
+async fn test_z_toggles_pause() {
+    let mut app = app_with_containers();
+    app.send_key(KeyCode::Char('z'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(
+        matches!(app.container_op, Some(ContainerOperation::Pausing { .. })),
+        "Expected Pausing op, got {:?}",
+        app.container_op
+    );
+
+    let mut app = app_with_containers();
+    app.containers[0].status = DevcContainerStatus::Paused;
+    app.send_key(KeyCode::Char('z'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(matches!(
+        app.container_op,
+        Some(ContainerOperation::Unpausing { .. })
+    ));
+
+    // Stopped containers can't be paused
+    let mut app = app_with_containers();
+    app.selected = 1;
+    app.containers_table_state.select(Some(1));
+    app.send_key(KeyCode::Char('z'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(app.container_op.is_none());
+}
+
+/// 'c' re-checks only the selected container's status, with a spinner
+#[tokio::test]
+async fn test_c_syncs_selected_container_status() {
+    let mut app = app_with_containers();
+    app.selected = 1;
+    app.containers_table_state.select(Some(1));
+    app.send_key(KeyCode::Char('c'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    match &app.container_op {
+        Some(ContainerOperation::Syncing { name, previous, .. }) => {
+            assert_eq!(name, "python-api");
+            assert_eq!(*previous, DevcContainerStatus::Stopped);
+        }
+        other => panic!("Expected Syncing op, got {:?}", other),
+    }
+    assert!(app.loading);
+}
+
 /// Confirming Delete sets up the Deleting operation with spinner state
 #[tokio::test]
 async fn test_confirm_delete_sets_operation_state() {
@@ -668,6 +1341,31 @@ async fn test_confirm_adopt_sets_operation_state() {
     assert_eq!(app.spinner_frame, 0);
 }
 
+/// devc-created containers can only be forgotten once their workspace is gone
+#[tokio::test]
+async fn test_forget_allowed_for_missing_workspace() {
+    let mut app = app_with_containers();
+    app.send_key(KeyCode::Char('f'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert_eq!(app.view, View::Main);
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("Cannot forget devc-created containers")
+    );
+
+    let id = app.containers[app.selected].id.clone();
+    app.missing_workspaces.insert(id.clone());
+    app.send_key(KeyCode::Char('f'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert_eq!(app.view, View::Confirm);
+    assert!(matches!(
+        app.confirm_action,
+        Some(ConfirmAction::Forget { id: ref forget_id, .. }) if *forget_id == id
+    ));
+}
+
 /// Confirming Forget sets up the Forgetting operation with spinner state
 #[tokio::test]
 async fn test_confirm_forget_sets_operation_state() {
@@ -721,6 +1419,7 @@ async fn test_operation_result_starting_success() {
     assert!(app.container_op.is_none());
     assert!(!app.loading);
     assert_eq!(app.status_message.as_deref(), Some("Started my-app"));
+    assert!(app.session_started.contains("c1"));
 }
 
 /// Successful Stopping operation clears state and sets status message
@@ -732,6 +1431,7 @@ async fn test_operation_result_stopping_success() {
         name: "my-app".to_string(),
     });
     app.loading = true;
+    app.session_started.insert("c1".to_string());
 
     app.handle_async_event(AsyncEvent::OperationComplete(ContainerOpResult::Success(
         ContainerOperation::Stopping {
@@ -745,6 +1445,33 @@ async fn test_operation_result_stopping_success() {
     assert!(app.container_op.is_none());
     assert!(!app.loading);
     assert_eq!(app.status_message.as_deref(), Some("Stopped my-app"));
+    assert!(!app.session_started.contains("c1"));
+}
+
+/// Successful Syncing operation reports the container's status in the status bar
+#[tokio::test]
+async fn test_operation_result_syncing_reports_status() {
+    let mut app = App::new_for_testing();
+    let op = ContainerOperation::Syncing {
+        id: "c1".to_string(),
+        name: "my-app".to_string(),
+        previous: DevcContainerStatus::Running,
+    };
+    app.container_op = Some(op.clone());
+    app.loading = true;
+
+    app.handle_async_event(AsyncEvent::OperationComplete(ContainerOpResult::Success(
+        op,
+    )))
+    .await
+    .unwrap();
+
+    assert!(app.container_op.is_none());
+    assert!(!app.loading);
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("my-app: running (unchanged)")
+    );
 }
 
 /// Successful Deleting operation clears state and sets status message
@@ -779,6 +1506,7 @@ async fn test_operation_result_up_success() {
         id: "c1".to_string(),
         name: "my-app".to_string(),
         progress: String::new(),
+        finalizing: false,
     });
     app.loading = true;
 
@@ -787,6 +1515,7 @@ async fn test_operation_result_up_success() {
             id: "c1".to_string(),
             name: "my-app".to_string(),
             progress: String::new(),
+            finalizing: false,
         },
     )))
     .await
@@ -800,6 +1529,36 @@ async fn test_operation_result_up_success() {
     );
 }
 
+/// Once the waitFor phase finishes, the Up operation is marked ready and keeps its progress
+#[tokio::test]
+async fn test_operation_progress_marks_up_finalizing() {
+    let mut app = App::new_for_testing();
+    app.container_op = Some(ContainerOperation::Up {
+        id: "c1".to_string(),
+        name: "my-app".to_string(),
+        progress: String::new(),
+        finalizing: false,
+    });
+
+    app.handle_async_event(AsyncEvent::OperationProgress(
+        devc_core::READY_FINALIZING_PROGRESS.to_string(),
+    ))
+    .await
+    .unwrap();
+    app.handle_async_event(AsyncEvent::OperationProgress(
+        "Running postCreateCommand...".to_string(),
+    ))
+    .await
+    .unwrap();
+
+    // Later progress keeps flowing while the operation stays marked ready
+    assert!(matches!(
+        &app.container_op,
+        Some(ContainerOperation::Up { progress, finalizing: true, .. })
+            if progress == "Running postCreateCommand..."
+    ));
+}
+
 /// Successful Adopting operation clears state, sets message, disables discover mode
 #[tokio::test]
 async fn test_operation_result_adopting_success() {
@@ -945,6 +1704,74 @@ async fn test_operation_result_forget_failure() {
     );
 }
 
+/// Background results raise a toast on any view without taking focus
+#[tokio::test]
+async fn test_operation_results_raise_toasts() {
+    let mut app = App::new_for_testing();
+    app.view = View::ConfigView;
+
+    app.handle_async_event(AsyncEvent::OperationComplete(ContainerOpResult::Success(
+        ContainerOperation::Up {
+            id: "c1".to_string(),
+            name: "web".to_string(),
+            progress: String::new(),
+            finalizing: false,
+        },
+    )))
+    .await
+    .unwrap();
+    app.handle_async_event(AsyncEvent::OperationComplete(ContainerOpResult::Failed(
+        ContainerOperation::Stopping {
+            id: "c2".to_string(),
+            name: "db".to_string(),
+        },
+        "timeout".to_string(),
+    )))
+    .await
+    .unwrap();
+
+    assert_eq!(app.view, View::ConfigView);
+    let toasts: Vec<_> = app
+        .toasts
+        .iter()
+        .map(|t| (t.message.as_str(), t.success))
+        .collect();
+    assert_eq!(
+        toasts,
+        vec![
+            ("Up completed for web", true),
+            ("Stop failed for db: timeout", false)
+        ]
+    );
+}
+
+/// A failed build jumps to and highlights the step the runtime reported
+#[tokio::test]
+async fn test_build_failed_jumps_to_failing_step() {
+    let mut app = App::new_for_testing();
+    app.view = View::BuildOutput;
+    app.build_output = vec![
+        "#5 [1/3] FROM docker.io/library/debian:bookworm".to_string(),
+        "#6 [2/3] RUN apt-get update".to_string(),
+        "#6 DONE 3.2s".to_string(),
+        "#7 [3/3] RUN apt-get install -y nosuchpkg".to_string(),
+        "#7 0.901 E: Unable to locate package nosuchpkg".to_string(),
+        "Build failed at step 3/3: RUN apt-get install -y nosuchpkg".to_string(),
+    ];
+
+    app.handle_async_event(AsyncEvent::BuildFinished {
+        success: false,
+        failed_step: Some("3/3: RUN apt-get install -y nosuchpkg".to_string()),
+    })
+    .await
+    .unwrap();
+
+    assert!(app.build_complete);
+    assert_eq!(app.build_failed_line, Some(3));
+    assert_eq!(app.build_output_scroll, 3);
+    assert!(!app.build_auto_scroll);
+}
+
 // ---------------------------------------------------------------------------
 // Container switch clears stale per-view state
 // ---------------------------------------------------------------------------
@@ -967,8 +1794,10 @@ async fn test_container_switch_clears_stale_state() {
         started_at: None,
         finished_at: None,
         exit_code: None,
+        restart_count: 0,
         labels: std::collections::HashMap::new(),
         env: vec![],
+        user: None,
         mounts: vec![],
         ports: vec![],
         network_settings: devc_provider::NetworkSettings {
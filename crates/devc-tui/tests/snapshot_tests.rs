@@ -42,6 +42,27 @@ fn test_containers_view_with_items() {
     insta::assert_snapshot!(output);
 }
 
+/// Test containers view grouped under workspace folder headers
+#[test]
+fn test_containers_view_grouped_by_workspace() {
+    let mut app = App::new_for_testing();
+    app.tab = Tab::Containers;
+    app.view = View::Main;
+    app.group_by_workspace = true;
+
+    let mut web = App::create_test_container("web", DevcContainerStatus::Running);
+    let mut db = App::create_test_container("db", DevcContainerStatus::Stopped);
+    let mut api = App::create_test_container("api", DevcContainerStatus::Running);
+    web.workspace_path = "/home/user/shop".into();
+    db.workspace_path = "/home/user/shop".into();
+    api.workspace_path = "/home/user/billing".into();
+    app.containers = vec![web, db, api];
+    app.selected = 1;
+
+    let output = render_app(&mut app, 120, 24);
+    insta::assert_snapshot!(output);
+}
+
 /// Test providers view rendering
 #[test]
 fn test_providers_view() {
@@ -139,6 +160,17 @@ fn test_help_view() {
     insta::assert_snapshot!(output);
 }
 
+/// Help opened from the logs viewer lists the logs bindings
+#[test]
+fn test_help_view_from_logs() {
+    let mut app = App::new_for_testing();
+    app.view = View::Help;
+    app.help_return_view = View::Logs;
+
+    let output = render_app(&mut app, 80, 32);
+    insta::assert_snapshot!(output);
+}
+
 /// Test build output view
 #[test]
 fn test_build_output_view() {
@@ -277,7 +309,7 @@ fn test_ports_popup_empty() {
 /// Test ports popup overlay with detected ports
 #[test]
 fn test_ports_popup_with_ports() {
-    use devc_tui::ports::DetectedPort;
+    use devc_tui::ports::{DetectedPort, PortScheme};
 
     let mut app = App::new_for_testing();
     app.tab = Tab::Containers;
@@ -301,6 +333,8 @@ fn test_ports_popup_with_ports() {
             process: Some("node".to_string()),
             is_new: false,
             is_forwarded: true,
+            is_listening: true,
+            scheme: PortScheme::Http,
         },
         DetectedPort {
             port: 8080,
@@ -308,6 +342,8 @@ fn test_ports_popup_with_ports() {
             process: Some("java".to_string()),
             is_new: true,
             is_forwarded: false,
+            is_listening: true,
+            scheme: PortScheme::Unknown,
         },
     ];
     app.port_state.selected_port = 0;
@@ -558,6 +594,7 @@ fn test_discover_detail_view() {
         started_at: Some(1705320060),
         finished_at: None,
         exit_code: None,
+        restart_count: 0,
         labels,
         env: vec![
             "PATH=/usr/bin".to_string(),
@@ -566,6 +603,7 @@ fn test_discover_detail_view() {
             "CARGO_HOME=/usr/local/cargo".to_string(),
             "HOSTNAME=abc123".to_string(),
         ],
+        user: None,
         mounts: vec![
             MountInfo {
                 mount_type: "bind".to_string(),